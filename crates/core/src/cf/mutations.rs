@@ -1,3 +1,4 @@
+use crate::cf::writer::edge;
 use crate::fflags::FFLAGS;
 use crate::sql::array::Array;
 use crate::sql::object::Object;
@@ -12,7 +13,7 @@ use std::collections::{BTreeMap, HashMap};
 use std::fmt::{self, Display, Formatter};
 
 // Mutation is a single mutation to a table.
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[non_exhaustive]
 pub enum TableMutation {
@@ -30,6 +31,12 @@ pub enum TableMutation {
 	#[revision(start = 2)]
 	/// Delete a record where the ID is stored, and the now-deleted value
 	DelWithOriginal(Thing, Value),
+	#[revision(start = 3)]
+	/// Creates a graph edge, storing the ID, the resolved `in` and `out` records, and the edge value
+	Relate(Thing, Thing, Thing, Value),
+	#[revision(start = 3)]
+	/// Removes a graph edge without its original value, storing the ID, and the resolved `in` and `out` records
+	Unrelate(Thing, Thing, Thing),
 }
 
 impl From<DefineTableStatement> for Value {
@@ -119,13 +126,36 @@ impl TableMutation {
 				h.insert("define_table".to_string(), Value::from(t));
 				h
 			}
-			TableMutation::DelWithOriginal(id, _val) => {
+			TableMutation::DelWithOriginal(id, val) => {
 				h.insert(
 					"delete".to_string(),
 					Value::Object(Object::from(map! {
-					"id".to_string() => Value::Thing(id),
+					"id".to_string() => Value::Thing(id.clone()),
 					})),
 				);
+				// The original value of a removed edge holds its resolved records
+				if let Some((i, o)) = edge(&val) {
+					h.insert("relation".to_string(), relation(id, i, o, "removed"));
+				}
+				h
+			}
+			TableMutation::Relate(id, i, o, v) => {
+				if FFLAGS.change_feed_live_queries.enabled() {
+					h.insert("create".to_string(), v);
+				} else {
+					h.insert("update".to_string(), v);
+				}
+				h.insert("relation".to_string(), relation(id, i, o, "created"));
+				h
+			}
+			TableMutation::Unrelate(id, i, o) => {
+				h.insert(
+					"delete".to_string(),
+					Value::Object(Object::from(map! {
+						"id".to_string() => Value::Thing(id.clone()),
+					})),
+				);
+				h.insert("relation".to_string(), relation(id, i, o, "removed"));
 				h
 			}
 		};
@@ -134,6 +164,16 @@ impl TableMutation {
 	}
 }
 
+/// Describes a change to a graph edge, so that graph consumers do not need to fetch the edge
+fn relation(id: Thing, i: Thing, o: Thing, action: &str) -> Value {
+	Value::Object(Object::from(map! {
+		"action".to_string() => Value::from(action),
+		"id".to_string() => Value::Thing(id),
+		"in".to_string() => Value::Thing(i),
+		"out".to_string() => Value::Thing(o),
+	}))
+}
+
 impl DatabaseMutation {
 	pub fn into_value(self) -> Value {
 		let mut changes = Vec::<Value>::new();
@@ -164,6 +204,8 @@ impl Display for TableMutation {
 			TableMutation::Del(id) => write!(f, "DEL {}", id),
 			TableMutation::DelWithOriginal(id, _) => write!(f, "DEL {}", id),
 			TableMutation::Def(t) => write!(f, "{}", t),
+			TableMutation::Relate(id, i, o, v) => write!(f, "RELATE {}->{}->{} {}", i, id, o, v),
+			TableMutation::Unrelate(id, i, o) => write!(f, "UNRELATE {}->{}->{}", i, id, o),
 		}
 	}
 }
@@ -309,4 +351,37 @@ mod tests {
 			r#"{"changes":[{"current":{"id":"mytb:tobie","note":"surreal"},"update":[{"op":"add","path":"/`/note`","value":"surreal"}]},{"current":{"id":"mytb:tobie2","note":"surreal"},"update":[{"op":"remove","path":"/`/temp`"}]},{"delete":{"id":"mytb:tobie"}},{"delete":{"id":"mytb:tobie"}},{"define_table":{"name":"mytb"}}],"versionstamp":65536}"#
 		);
 	}
+
+	#[test]
+	fn serialization_relations() {
+		use super::*;
+		let id = Thing::from(("likes".to_string(), "one".to_string()));
+		let i = Thing::from(("person".to_string(), "tobie".to_string()));
+		let o = Thing::from(("person".to_string(), "jaime".to_string()));
+		let cs = ChangeSet(
+			VersionStamp::from_u64(1),
+			DatabaseMutation(vec![TableMutations(
+				"likes".to_string(),
+				vec![
+					TableMutation::Relate(
+						id.clone(),
+						i.clone(),
+						o.clone(),
+						Value::Object(Object::from(map! {
+							"id" => Value::from(id.clone()),
+							"in" => Value::from(i.clone()),
+							"out" => Value::from(o.clone()),
+						})),
+					),
+					TableMutation::Unrelate(id, i, o),
+				],
+			)]),
+		);
+		let v = cs.into_value().into_json();
+		let s = serde_json::to_string(&v).unwrap();
+		assert_eq!(
+			s,
+			r#"{"changes":[{"relation":{"action":"created","id":"likes:one","in":"person:tobie","out":"person:jaime"},"update":{"id":"likes:one","in":"person:tobie","out":"person:jaime"}},{"delete":{"id":"likes:one"},"relation":{"action":"removed","id":"likes:one","in":"person:tobie","out":"person:jaime"}}],"versionstamp":65536}"#
+		);
+	}
 }
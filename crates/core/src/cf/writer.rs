@@ -4,8 +4,10 @@ use crate::cf::{TableMutation, TableMutations};
 use crate::doc::CursorValue;
 use crate::err::Error;
 use crate::kvs::{Key, KeyEncode};
+use crate::sql::paths::{EDGE, IN, OUT};
 use crate::sql::statements::DefineTableStatement;
 use crate::sql::thing::Thing;
+use crate::sql::value::Value;
 use crate::sql::Idiom;
 
 // PreparedWrite is a tuple of (versionstamp key, key prefix, key suffix, serialized table mutations).
@@ -75,6 +77,30 @@ impl Writer {
 		current: CursorValue,
 		store_difference: bool,
 	) {
+		// Graph edges which are created or removed are recorded with their resolved records
+		if previous.as_ref().is_none() {
+			if let Some((i, o)) = edge(current.as_ref()) {
+				self.buf.push(
+					ns.to_string(),
+					db.to_string(),
+					tb.to_string(),
+					TableMutation::Relate(id, i, o, current.into_owned()),
+				);
+				return;
+			}
+		}
+		// With the original value stored, a removed edge is recorded with its original instead
+		if current.as_ref().is_none() && !store_difference {
+			if let Some((i, o)) = edge(previous.as_ref()) {
+				self.buf.push(
+					ns.to_string(),
+					db.to_string(),
+					tb.to_string(),
+					TableMutation::Unrelate(id, i, o),
+				);
+				return;
+			}
+		}
 		if current.as_ref().is_some() {
 			self.buf.push(
 				ns.to_string(),
@@ -146,6 +172,14 @@ impl Writer {
 	}
 }
 
+/// Get the `in` and `out` records of a value, if it is a graph edge
+pub(crate) fn edge(v: &Value) -> Option<(Thing, Thing)> {
+	match (v.pick(&*EDGE), v.pick(&*IN), v.pick(&*OUT)) {
+		(Value::Bool(true), Value::Thing(i), Value::Thing(o)) => Some((i, o)),
+		_ => None,
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use std::time::Duration;
@@ -506,6 +540,60 @@ mod tests {
 		assert_eq!(r, expected);
 	}
 
+	#[test_log::test(tokio::test)]
+	async fn edges_record_resolved_relations() {
+		let ts = Datetime::default();
+		let ds = init(false).await;
+		ds.changefeed_process_at(ts.0.timestamp().try_into().unwrap()).await.unwrap();
+		let id = Thing::from((TB.to_string(), "edge".to_string()));
+		let i = Thing::from(("person".to_string(), "tobie".to_string()));
+		let o = Thing::from(("person".to_string(), "jaime".to_string()));
+		let edge = Value::Object(Object::from(map! {
+			"__".to_string() => Value::Bool(true),
+			"id".to_string() => Value::Thing(id.clone()),
+			"in".to_string() => Value::Thing(i.clone()),
+			"out".to_string() => Value::Thing(o.clone()),
+		}));
+		// Create the edge
+		let tx = ds.transaction(Write, Optimistic).await.unwrap();
+		tx.lock().await.record_change(
+			NS,
+			DB,
+			TB,
+			&id,
+			Value::None.into(),
+			edge.clone().into(),
+			DONT_STORE_PREVIOUS,
+		);
+		tx.lock().await.complete_changes(true).await.unwrap();
+		tx.commit().await.unwrap();
+		// Remove the edge
+		let tx = ds.transaction(Write, Optimistic).await.unwrap();
+		tx.lock().await.record_change(
+			NS,
+			DB,
+			TB,
+			&id,
+			edge.clone().into(),
+			Value::None.into(),
+			DONT_STORE_PREVIOUS,
+		);
+		tx.lock().await.complete_changes(true).await.unwrap();
+		tx.commit().await.unwrap();
+		// Check that both changes include the resolved records
+		let tx = ds.transaction(Write, Optimistic).await.unwrap();
+		let r = change_feed_ts(tx, &ts).await;
+		let mutations: Vec<TableMutation> =
+			r.into_iter().flat_map(|cs| cs.1 .0).flat_map(|tm| tm.1).collect();
+		assert_eq!(
+			mutations,
+			vec![
+				TableMutation::Relate(id.clone(), i.clone(), o.clone(), edge),
+				TableMutation::Unrelate(id, i, o),
+			]
+		);
+	}
+
 	async fn change_feed_ts(tx: Transaction, ts: &Datetime) -> Vec<ChangeSet> {
 		let r = crate::cf::read(&tx, NS, DB, Some(TB), ShowSince::Timestamp(ts.clone()), Some(10))
 			.await
@@ -53,14 +53,64 @@ pub static EXPORT_BATCH_SIZE: LazyLock<u32> =
 pub static COUNT_BATCH_SIZE: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_COUNT_BATCH_SIZE", u32, 10_000);
 
+/// The maximum number of keys that should be scanned at once for database consistency checks.
+pub static CHECK_BATCH_SIZE: LazyLock<u32> = lazy_env_parse!("SURREAL_CHECK_BATCH_SIZE", u32, 1000);
+
+/// The maximum number of issues which are listed in the report of a database consistency check.
+pub static CHECK_ISSUES_LIMIT: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_CHECK_ISSUES_LIMIT", usize, 1000);
+
+/// The maximum number of keys that should be scanned at once when evicting records from a cache table.
+pub static EVICT_BATCH_SIZE: LazyLock<u32> = lazy_env_parse!("SURREAL_EVICT_BATCH_SIZE", u32, 1000);
+
+/// The number of record access times which are kept for cache tables, dropping the least recent first.
+pub static RECORD_ACCESS_CACHE_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_RECORD_ACCESS_CACHE_SIZE", usize, 1_000_000);
+
+/// Statements which take longer than this duration are logged as slow queries
+pub static SLOW_QUERY_THRESHOLD: LazyLock<Option<std::time::Duration>> = LazyLock::new(|| {
+	std::env::var("SURREAL_SLOW_QUERY_THRESHOLD")
+		.ok()
+		.and_then(|s| s.parse::<crate::sql::Duration>().ok())
+		.map(|d| d.0)
+});
+
 /// The maximum size of the priority queue triggering usage of the priority queue for the result collector.
 pub static MAX_ORDER_LIMIT_PRIORITY_QUEUE_SIZE: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_MAX_ORDER_LIMIT_PRIORITY_QUEUE_SIZE", u32, 1000);
 
+/// The maximum number of distinct rows which a `SELECT DISTINCT` statement holds in memory.
+pub static DISTINCT_ROWS_LIMIT: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_DISTINCT_ROWS_LIMIT", usize, 1_000_000);
+
 /// The maximum number of keys that should be scanned at once per concurrent indexing batch.
 pub static INDEXING_BATCH_SIZE: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_INDEXING_BATCH_SIZE", u32, 250);
 
+/// The maximum number of records of a joined table which are held in memory, when a join can not use an index
+pub static JOIN_MAX_RECORDS: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_JOIN_MAX_RECORDS", usize, 100_000);
+
+/// The maximum number of queued asynchronous events which are processed at once
+pub static EVENT_PROCESS_BATCH_SIZE: LazyLock<u32> =
+	lazy_env_parse!("SURREAL_EVENT_PROCESS_BATCH_SIZE", u32, 100);
+
+/// The reserved namespace which stores the dead-letter table of asynchronous events
+pub static EVENT_DEAD_LETTER_NAMESPACE: LazyLock<String> =
+	lazy_env_parse!("SURREAL_EVENT_DEAD_LETTER_NAMESPACE", String, String::from("__surreal"));
+
+/// The table which asynchronous events are written to when they have failed on every retry
+pub static EVENT_DEAD_LETTER_TABLE: LazyLock<String> =
+	lazy_env_parse!("SURREAL_EVENT_DEAD_LETTER_TABLE", String, String::from("dead_letter"));
+
+/// The maximum number of seconds which an asynchronous event can run for (defaults to 60)
+pub static EVENT_PROCESS_TIMEOUT_SECS: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_EVENT_PROCESS_TIMEOUT_SECS", u64, 60);
+
+/// The maximum number of seconds which sys::sleep can sleep for at once (defaults to 10)
+pub static EVENT_SLEEP_MAX_SECS: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_EVENT_SLEEP_MAX_SECS", u64, 10);
+
 /// The maximum stack size of the JavaScript function runtime (defaults to 256 KiB)
 pub static SCRIPTING_MAX_STACK_SIZE: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_SCRIPTING_MAX_STACK_SIZE", usize, 256 * 1024);
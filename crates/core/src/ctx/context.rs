@@ -11,7 +11,8 @@ use crate::idx::trees::store::IndexStores;
 use crate::kvs::cache::ds::DatastoreCache;
 #[cfg(not(target_family = "wasm"))]
 use crate::kvs::IndexBuilder;
-use crate::kvs::Transaction;
+use crate::kvs::{Transaction, TransactionFactory, WriteLimits};
+use crate::sql::statements::with::NamedSubquery;
 use crate::sql::value::Value;
 use async_channel::Sender;
 use std::borrow::Cow;
@@ -38,8 +39,12 @@ pub struct MutableContext {
 	cancelled: Arc<AtomicBool>,
 	// A collection of read only values stored in this context.
 	values: HashMap<Cow<'static, str>, Arc<Value>>,
+	// The subqueries named by WITH statements, which are computed when first referenced
+	named: HashMap<Cow<'static, str>, Arc<NamedSubquery>>,
 	// Stores the notification channel if available
 	notifications: Option<Sender<Notification>>,
+	// The rate limits for writes to specific tables
+	write_limits: Option<Arc<WriteLimits>>,
 	// An optional query planner
 	query_planner: Option<Arc<QueryPlanner>>,
 	// An optional query executor
@@ -53,6 +58,8 @@ pub struct MutableContext {
 	// The index concurrent builders
 	#[cfg(not(target_family = "wasm"))]
 	index_builder: Option<IndexBuilder>,
+	// The factory for starting separate transactions on the datastore
+	transaction_factory: Option<TransactionFactory>,
 	// Capabilities
 	capabilities: Arc<Capabilities>,
 	#[cfg(storage)]
@@ -62,6 +69,10 @@ pub struct MutableContext {
 	transaction: Option<Arc<Transaction>>,
 	// Does not read from parent `values`.
 	isolated: bool,
+	// Does not read from parent `named` subqueries.
+	unnamed: bool,
+	// Whether this context runs an asynchronous event in the background
+	asynchronous: bool,
 }
 
 impl Default for MutableContext {
@@ -94,10 +105,12 @@ impl MutableContext {
 	pub(crate) fn background() -> Self {
 		Self {
 			values: HashMap::default(),
+			named: HashMap::default(),
 			parent: None,
 			deadline: None,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: None,
+			write_limits: None,
 			query_planner: None,
 			query_executor: None,
 			iteration_stage: None,
@@ -106,10 +119,13 @@ impl MutableContext {
 			cache: None,
 			#[cfg(not(target_family = "wasm"))]
 			index_builder: None,
+			transaction_factory: None,
 			#[cfg(storage)]
 			temporary_directory: None,
 			transaction: None,
 			isolated: false,
+			unnamed: false,
+			asynchronous: false,
 		}
 	}
 
@@ -117,9 +133,11 @@ impl MutableContext {
 	pub(crate) fn new(parent: &Context) -> Self {
 		MutableContext {
 			values: HashMap::default(),
+			named: HashMap::default(),
 			deadline: parent.deadline,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: parent.notifications.clone(),
+			write_limits: parent.write_limits.clone(),
 			query_planner: parent.query_planner.clone(),
 			query_executor: parent.query_executor.clone(),
 			iteration_stage: parent.iteration_stage.clone(),
@@ -128,10 +146,13 @@ impl MutableContext {
 			cache: parent.cache.clone(),
 			#[cfg(not(target_family = "wasm"))]
 			index_builder: parent.index_builder.clone(),
+			transaction_factory: parent.transaction_factory.clone(),
 			#[cfg(storage)]
 			temporary_directory: parent.temporary_directory.clone(),
 			transaction: parent.transaction.clone(),
 			isolated: false,
+			unnamed: false,
+			asynchronous: parent.asynchronous,
 			parent: Some(parent.clone()),
 		}
 	}
@@ -142,9 +163,11 @@ impl MutableContext {
 	pub(crate) fn new_isolated(parent: &Context) -> Self {
 		Self {
 			values: HashMap::default(),
+			named: HashMap::default(),
 			deadline: parent.deadline,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: parent.notifications.clone(),
+			write_limits: parent.write_limits.clone(),
 			query_planner: parent.query_planner.clone(),
 			query_executor: parent.query_executor.clone(),
 			iteration_stage: parent.iteration_stage.clone(),
@@ -153,10 +176,13 @@ impl MutableContext {
 			cache: parent.cache.clone(),
 			#[cfg(not(target_family = "wasm"))]
 			index_builder: parent.index_builder.clone(),
+			transaction_factory: parent.transaction_factory.clone(),
 			#[cfg(storage)]
 			temporary_directory: parent.temporary_directory.clone(),
 			transaction: parent.transaction.clone(),
 			isolated: true,
+			unnamed: false,
+			asynchronous: parent.asynchronous,
 			parent: Some(parent.clone()),
 		}
 	}
@@ -168,9 +194,11 @@ impl MutableContext {
 	pub(crate) fn new_concurrent(from: &Context) -> Self {
 		Self {
 			values: HashMap::default(),
+			named: HashMap::default(),
 			deadline: None,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: from.notifications.clone(),
+			write_limits: from.write_limits.clone(),
 			query_planner: from.query_planner.clone(),
 			query_executor: from.query_executor.clone(),
 			iteration_stage: from.iteration_stage.clone(),
@@ -178,10 +206,13 @@ impl MutableContext {
 			index_stores: from.index_stores.clone(),
 			cache: from.cache.clone(),
 			index_builder: from.index_builder.clone(),
+			transaction_factory: from.transaction_factory.clone(),
 			#[cfg(storage)]
 			temporary_directory: from.temporary_directory.clone(),
 			transaction: None,
 			isolated: false,
+			unnamed: false,
+			asynchronous: from.asynchronous,
 			parent: None,
 		}
 	}
@@ -193,14 +224,17 @@ impl MutableContext {
 		index_stores: IndexStores,
 		cache: Arc<DatastoreCache>,
 		#[cfg(not(target_family = "wasm"))] index_builder: IndexBuilder,
+		transaction_factory: TransactionFactory,
 		#[cfg(storage)] temporary_directory: Option<Arc<PathBuf>>,
 	) -> Result<MutableContext, Error> {
 		let mut ctx = Self {
 			values: HashMap::default(),
+			named: HashMap::default(),
 			parent: None,
 			deadline: None,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: None,
+			write_limits: None,
 			query_planner: None,
 			query_executor: None,
 			iteration_stage: None,
@@ -209,10 +243,13 @@ impl MutableContext {
 			cache: Some(cache),
 			#[cfg(not(target_family = "wasm"))]
 			index_builder: Some(index_builder),
+			transaction_factory: Some(transaction_factory),
 			#[cfg(storage)]
 			temporary_directory,
 			transaction: None,
 			isolated: false,
+			unnamed: false,
+			asynchronous: false,
 		};
 		if let Some(timeout) = time_out {
 			ctx.add_timeout(timeout)?;
@@ -240,6 +277,20 @@ impl MutableContext {
 		self.values.insert(key.into(), value);
 	}
 
+	/// Add a subquery named by a WITH statement to the context.
+	pub(crate) fn add_named<K>(&mut self, key: K, value: Arc<NamedSubquery>)
+	where
+		K: Into<Cow<'static, str>>,
+	{
+		self.named.insert(key.into(), value);
+	}
+
+	/// Remove a value from the context, returning the value which was
+	/// previously set with the same key.
+	pub(crate) fn remove_value(&mut self, key: &str) -> Option<Arc<Value>> {
+		self.values.remove(key)
+	}
+
 	/// Add a value to the context. It overwrites any previously set values
 	/// with the same key.
 	pub(crate) fn add_values<T, K, V>(&mut self, iter: T)
@@ -286,6 +337,22 @@ impl MutableContext {
 		self.notifications = chn.cloned()
 	}
 
+	/// Marks this context as running an asynchronous event in the background
+	pub(crate) fn set_asynchronous(&mut self) {
+		self.asynchronous = true
+	}
+
+	/// Check if this context runs an asynchronous event in the background
+	pub(crate) fn is_asynchronous(&self) -> bool {
+		self.asynchronous
+	}
+
+	/// Add the table write limits to the context, so that
+	/// writes to limited tables can be throttled.
+	pub(crate) fn add_write_limits(&mut self, limits: Arc<WriteLimits>) {
+		self.write_limits = Some(limits)
+	}
+
 	pub(crate) fn set_query_planner(&mut self, qp: QueryPlanner) {
 		self.query_planner = Some(Arc::new(qp));
 	}
@@ -322,6 +389,18 @@ impl MutableContext {
 		self.notifications.is_some()
 	}
 
+	/// Takes a write token for the specified table, failing
+	/// if the write rate limit for the table was exceeded.
+	pub(crate) fn check_write_limit(&self, ns: &str, db: &str, tb: &str) -> Result<(), Error> {
+		if let Some(limits) = &self.write_limits {
+			// The token is returned if the transaction is not committed
+			if let Some(token) = limits.check(ns, db, tb)? {
+				self.tx().charge(token);
+			}
+		}
+		Ok(())
+	}
+
 	pub(crate) fn get_query_planner(&self) -> Option<&QueryPlanner> {
 		self.query_planner.as_ref().map(|qp| qp.as_ref())
 	}
@@ -339,6 +418,11 @@ impl MutableContext {
 		&self.index_stores
 	}
 
+	/// Get the factory for starting transactions which are separate from the current one
+	pub(crate) fn get_transaction_factory(&self) -> Option<&TransactionFactory> {
+		self.transaction_factory.as_ref()
+	}
+
 	/// Get the index_builder for this context/ds
 	#[cfg(not(target_family = "wasm"))]
 	pub(crate) fn get_index_builder(&self) -> Option<&IndexBuilder> {
@@ -405,6 +489,18 @@ impl MutableContext {
 		}
 	}
 
+	/// Get a subquery named by a WITH statement from the context.
+	pub(crate) fn named(&self, key: &str) -> Option<&NamedSubquery> {
+		match self.named.get(key) {
+			Some(v) => Some(v.as_ref()),
+			None if !self.isolated && !self.unnamed => match &self.parent {
+				Some(p) => p.named(key),
+				_ => None,
+			},
+			None => None,
+		}
+	}
+
 	/// Get a 'static view into the cancellation status.
 	#[cfg(feature = "scripting")]
 	pub(crate) fn cancellation(&self) -> crate::ctx::cancellation::Cancellation {
@@ -470,4 +566,14 @@ impl MutableContext {
 			_ => Err(Error::InvalidUrl(url.to_string())),
 		}
 	}
+
+	/// Check if an environment variable is allowed to be read
+	pub(crate) fn check_allowed_env(&self, name: &str) -> Result<(), Error> {
+		if !self.capabilities.allows_env_var(name) {
+			warn!("Capabilities denied environment variable access attempt, target: '{name}'");
+			return Err(Error::EnvTargetNotAllowed(name.to_string()));
+		}
+		trace!("Capabilities allowed environment variable access, target: '{name}'");
+		Ok(())
+	}
 }
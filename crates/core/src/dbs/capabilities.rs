@@ -5,6 +5,7 @@ use std::net::IpAddr;
 
 use crate::iam::{Auth, Level};
 use crate::rpc::Method;
+use crate::sql::Value;
 use ipnet::IpNet;
 use url::Url;
 
@@ -259,6 +260,49 @@ impl std::str::FromStr for NetTarget {
 	}
 }
 
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct EnvTarget(pub String);
+
+impl fmt::Display for EnvTarget {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.0)
+	}
+}
+
+impl Target for EnvTarget {
+	fn matches(&self, elem: &Self) -> bool {
+		self.0 == elem.0
+	}
+}
+
+impl Target<str> for EnvTarget {
+	fn matches(&self, elem: &str) -> bool {
+		self.0 == elem
+	}
+}
+
+#[derive(Debug)]
+pub struct ParseEnvTargetError;
+
+impl std::error::Error for ParseEnvTargetError {}
+impl fmt::Display for ParseEnvTargetError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "The provided environment variable target is not a valid variable name")
+	}
+}
+
+impl std::str::FromStr for EnvTarget {
+	type Err = ParseEnvTargetError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		if s.is_empty() || !s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+			return Err(ParseEnvTargetError);
+		}
+		Ok(EnvTarget(s.to_string()))
+	}
+}
+
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct MethodTarget {
 	pub method: Method,
@@ -316,6 +360,7 @@ pub enum RouteTarget {
 	Ml,
 	GraphQL,
 	Api,
+	Stats,
 }
 
 // impl display
@@ -335,6 +380,7 @@ impl fmt::Display for RouteTarget {
 			RouteTarget::Ml => write!(f, "ml"),
 			RouteTarget::GraphQL => write!(f, "graphql"),
 			RouteTarget::Api => write!(f, "api"),
+			RouteTarget::Stats => write!(f, "stats"),
 		}
 	}
 }
@@ -373,6 +419,7 @@ impl std::str::FromStr for RouteTarget {
 			"ml" => Ok(RouteTarget::Ml),
 			"graphql" => Ok(RouteTarget::GraphQL),
 			"api" => Ok(RouteTarget::Api),
+			"stats" => Ok(RouteTarget::Stats),
 			_ => Err(ParseRouteTargetError),
 		}
 	}
@@ -514,6 +561,8 @@ pub struct Capabilities {
 	deny_funcs: Targets<FuncTarget>,
 	allow_net: Targets<NetTarget>,
 	deny_net: Targets<NetTarget>,
+	allow_env: Targets<EnvTarget>,
+	deny_env: Targets<EnvTarget>,
 	allow_rpc: Targets<MethodTarget>,
 	deny_rpc: Targets<MethodTarget>,
 	allow_http: Targets<RouteTarget>,
@@ -528,8 +577,8 @@ impl fmt::Display for Capabilities {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		write!(
             f,
-            "scripting={}, guest_access={}, live_query_notifications={}, allow_funcs={}, deny_funcs={}, allow_net={}, deny_net={}, allow_rpc={}, deny_rpc={}, allow_http={}, deny_http={}, allow_experimental={}, deny_experimental={}, allow_arbitrary_query={}, deny_arbitrary_query={}",
-            self.scripting, self.guest_access, self.live_query_notifications, self.allow_funcs, self.deny_funcs, self.allow_net, self.deny_net, self.allow_rpc, self.deny_rpc, self.allow_http, self.deny_http, self.allow_experimental, self.deny_experimental, self.allow_arbitrary_query, self.deny_arbitrary_query,
+            "scripting={}, guest_access={}, live_query_notifications={}, allow_funcs={}, deny_funcs={}, allow_net={}, deny_net={}, allow_env={}, deny_env={}, allow_rpc={}, deny_rpc={}, allow_http={}, deny_http={}, allow_experimental={}, deny_experimental={}, allow_arbitrary_query={}, deny_arbitrary_query={}",
+            self.scripting, self.guest_access, self.live_query_notifications, self.allow_funcs, self.deny_funcs, self.allow_net, self.deny_net, self.allow_env, self.deny_env, self.allow_rpc, self.deny_rpc, self.allow_http, self.deny_http, self.allow_experimental, self.deny_experimental, self.allow_arbitrary_query, self.deny_arbitrary_query,
         )
 	}
 }
@@ -545,6 +594,8 @@ impl Default for Capabilities {
 			deny_funcs: Targets::None,
 			allow_net: Targets::None,
 			deny_net: Targets::None,
+			allow_env: Targets::None,
+			deny_env: Targets::None,
 			allow_rpc: Targets::All,
 			deny_rpc: Targets::None,
 			allow_http: Targets::All,
@@ -568,6 +619,8 @@ impl Capabilities {
 			deny_funcs: Targets::None,
 			allow_net: Targets::All,
 			deny_net: Targets::None,
+			allow_env: Targets::None,
+			deny_env: Targets::None,
 			allow_rpc: Targets::All,
 			deny_rpc: Targets::None,
 			allow_http: Targets::All,
@@ -589,6 +642,8 @@ impl Capabilities {
 			deny_funcs: Targets::None,
 			allow_net: Targets::None,
 			deny_net: Targets::None,
+			allow_env: Targets::None,
+			deny_env: Targets::None,
 			allow_rpc: Targets::None,
 			deny_rpc: Targets::None,
 			allow_http: Targets::None,
@@ -685,6 +740,24 @@ impl Capabilities {
 		&mut self.deny_net
 	}
 
+	pub fn with_env_vars(mut self, allow_env: Targets<EnvTarget>) -> Self {
+		self.allow_env = allow_env;
+		self
+	}
+
+	pub fn allowed_env_vars_mut(&mut self) -> &mut Targets<EnvTarget> {
+		&mut self.allow_env
+	}
+
+	pub fn without_env_vars(mut self, deny_env: Targets<EnvTarget>) -> Self {
+		self.deny_env = deny_env;
+		self
+	}
+
+	pub fn denied_env_vars_mut(&mut self) -> &mut Targets<EnvTarget> {
+		&mut self.deny_env
+	}
+
 	pub fn with_rpc_methods(mut self, allow_rpc: Targets<MethodTarget>) -> Self {
 		self.allow_rpc = allow_rpc;
 		self
@@ -741,6 +814,10 @@ impl Capabilities {
 		self.allow_net.matches(target) && !self.deny_net.matches(target)
 	}
 
+	pub fn allows_env_var(&self, target: &str) -> bool {
+		self.allow_env.matches(target) && !self.deny_env.matches(target)
+	}
+
 	pub fn allows_rpc_method(&self, target: &MethodTarget) -> bool {
 		self.allow_rpc.matches(target) && !self.deny_rpc.matches(target)
 	}
@@ -750,6 +827,44 @@ impl Capabilities {
 	}
 }
 
+impl<T: Hash + Eq + PartialEq + fmt::Display> From<&Targets<T>> for Value {
+	fn from(targets: &Targets<T>) -> Self {
+		match targets {
+			Targets::None => Value::Bool(false),
+			Targets::All => Value::Bool(true),
+			Targets::Some(targets) => {
+				let mut targets: Vec<String> = targets.iter().map(ToString::to_string).collect();
+				targets.sort();
+				targets.into()
+			}
+		}
+	}
+}
+
+impl From<&Capabilities> for Value {
+	fn from(caps: &Capabilities) -> Self {
+		Value::from(map! {
+			"scripting".to_string() => caps.scripting.into(),
+			"guest_access".to_string() => caps.guest_access.into(),
+			"live_query_notifications".to_string() => caps.live_query_notifications.into(),
+			"allow_funcs".to_string() => (&caps.allow_funcs).into(),
+			"deny_funcs".to_string() => (&caps.deny_funcs).into(),
+			"allow_net".to_string() => (&caps.allow_net).into(),
+			"deny_net".to_string() => (&caps.deny_net).into(),
+			"allow_env".to_string() => (&caps.allow_env).into(),
+			"deny_env".to_string() => (&caps.deny_env).into(),
+			"allow_rpc".to_string() => (&caps.allow_rpc).into(),
+			"deny_rpc".to_string() => (&caps.deny_rpc).into(),
+			"allow_http".to_string() => (&caps.allow_http).into(),
+			"deny_http".to_string() => (&caps.deny_http).into(),
+			"allow_experimental".to_string() => (&caps.allow_experimental).into(),
+			"deny_experimental".to_string() => (&caps.deny_experimental).into(),
+			"allow_arbitrary_query".to_string() => (&caps.allow_arbitrary_query).into(),
+			"deny_arbitrary_query".to_string() => (&caps.deny_arbitrary_query).into(),
+		})
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use std::str::FromStr;
@@ -941,6 +1056,38 @@ mod tests {
 			.matches(&MethodTarget::from_str("ping").unwrap()));
 	}
 
+	#[test]
+	fn test_env_target() {
+		assert!(EnvTarget::from_str("HOME").unwrap().matches("HOME"));
+		assert!(!EnvTarget::from_str("HOME").unwrap().matches("home"));
+		assert!(EnvTarget::from_str("APP_NAME_1").unwrap().matches("APP_NAME_1"));
+		EnvTarget::from_str("").unwrap_err();
+		EnvTarget::from_str("APP-NAME").unwrap_err();
+		EnvTarget::from_str("APP=1").unwrap_err();
+	}
+
+	#[test]
+	fn test_env_capabilities() {
+		// Environment variables are denied by default
+		assert!(!Capabilities::default().allows_env_var("HOME"));
+		assert!(!Capabilities::all().allows_env_var("HOME"));
+		// When specific variables are allowed
+		{
+			let caps = Capabilities::default()
+				.with_env_vars(Targets::Some([EnvTarget::from_str("APP_NAME").unwrap()].into()));
+			assert!(caps.allows_env_var("APP_NAME"));
+			assert!(!caps.allows_env_var("HOME"));
+		}
+		// When all variables are allowed except some
+		{
+			let caps = Capabilities::default()
+				.with_env_vars(Targets::All)
+				.without_env_vars(Targets::Some([EnvTarget::from_str("SECRET").unwrap()].into()));
+			assert!(caps.allows_env_var("HOME"));
+			assert!(!caps.allows_env_var("SECRET"));
+		}
+	}
+
 	#[test]
 	fn test_targets() {
 		assert!(Targets::<NetTarget>::All.matches(&NetTarget::from_str("example.com").unwrap()));
@@ -1,7 +1,11 @@
+use crate::cnf::DISTINCT_ROWS_LIMIT;
 use crate::ctx::Context;
-use crate::dbs::Processed;
+use crate::dbs::{Processed, Statement};
+use crate::err::Error;
 use crate::kvs::Key;
+use crate::sql::{Distinct as DistinctClause, Fields, Value};
 use radix_trie::Trie;
+use std::collections::BTreeSet;
 use std::default::Default;
 
 // TODO: This is currently processed in memory. In the future is should be on disk (mmap?)
@@ -36,3 +40,49 @@ impl SyncDistinct {
 		}
 	}
 }
+
+/// Removes the duplicate rows of a `SELECT DISTINCT` statement
+///
+/// The rows are compared in memory, and no index is used, so a statement
+/// fails once it finds more distinct rows than `SURREAL_DISTINCT_ROWS_LIMIT`.
+#[derive(Default)]
+#[allow(clippy::mutable_key_type)]
+pub(super) struct DistinctRows {
+	seen: BTreeSet<Value>,
+}
+
+impl DistinctRows {
+	/// Checks if the duplicate rows can be removed as they are collected
+	///
+	/// Otherwise they are removed once every row is collected, grouped and ordered, as the
+	/// rows are changed by GROUP BY, SPLIT and window functions, and as ORDER BY decides
+	/// which row is kept by `DISTINCT ON`.
+	pub(super) fn is_streaming(stm: &Statement<'_>) -> bool {
+		if stm.group().is_some() || stm.split().is_some() {
+			return false;
+		}
+		if stm.expr().is_some_and(Fields::has_windows) {
+			return false;
+		}
+		!matches!(stm.distinct(), Some(DistinctClause::On(_))) || stm.order().is_none()
+	}
+
+	/// Returns true if a row with the same values was already seen
+	pub(super) fn check_already_seen(
+		&mut self,
+		distinct: &DistinctClause,
+		row: &Value,
+	) -> Result<bool, Error> {
+		let key = distinct.key(row);
+		if self.seen.contains(&key) {
+			return Ok(true);
+		}
+		if self.seen.len() >= *DISTINCT_ROWS_LIMIT {
+			return Err(Error::DistinctRowsLimit {
+				limit: *DISTINCT_ROWS_LIMIT,
+			});
+		}
+		self.seen.insert(key);
+		Ok(false)
+	}
+}
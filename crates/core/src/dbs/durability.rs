@@ -0,0 +1,67 @@
+use crate::err::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Specifies how far a write must have progressed before it is acknowledged
+///
+/// This only applies to statements which are not part of an explicit
+/// `BEGIN` / `COMMIT` block.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Durability {
+	/// Acknowledge the write once it has been committed in memory by the storage engine,
+	/// without waiting for it to be synced to disk, even if `SURREAL_SYNC_DATA` is enabled.
+	/// The write can be read straight away, but may be lost if the machine crashes. This
+	/// is no faster than `Applied` with storage engines which always replicate writes.
+	Memory,
+	/// Acknowledge the write once the transaction has been committed, syncing it to
+	/// disk only if `SURREAL_SYNC_DATA` is enabled
+	#[default]
+	Applied,
+	/// Acknowledge the write once it has been synced to disk by a local storage
+	/// engine, or replicated by a distributed storage engine. Writes fail with
+	/// storage engines which can not guarantee this, which are the in-memory and
+	/// IndexedDB engines.
+	Replicated,
+}
+
+impl Durability {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Memory => "memory",
+			Self::Applied => "applied",
+			Self::Replicated => "replicated",
+		}
+	}
+}
+
+impl fmt::Display for Durability {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl FromStr for Durability {
+	type Err = Error;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"memory" => Ok(Self::Memory),
+			"applied" => Ok(Self::Applied),
+			"replicated" => Ok(Self::Replicated),
+			_ => Err(Error::InvalidDurability(s.to_owned())),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn durability_round_trip() {
+		for durability in [Durability::Memory, Durability::Applied, Durability::Replicated] {
+			assert_eq!(durability.to_string().parse::<Durability>().unwrap(), durability);
+		}
+		assert!("eventual".parse::<Durability>().is_err());
+	}
+}
@@ -1,7 +1,12 @@
+use crate::cnf::SLOW_QUERY_THRESHOLD;
 use crate::ctx::reason::Reason;
 use crate::ctx::Context;
 use crate::dbs::response::Response;
+use crate::dbs::Durability;
 use crate::dbs::Force;
+use crate::dbs::HeldTransaction;
+use crate::dbs::Notification;
+use crate::dbs::OpenTransaction;
 use crate::dbs::Options;
 use crate::dbs::QueryType;
 use crate::err::Error;
@@ -14,9 +19,15 @@ use crate::sql::paths::DB;
 use crate::sql::paths::NS;
 use crate::sql::query::Query;
 use crate::sql::statement::Statement;
-use crate::sql::statements::{OptionStatement, UseStatement};
+use crate::sql::statements::foreach::ForeachCursor;
+use crate::sql::statements::{
+	BeginStatement, CheckStatement, ForeachStatement, OptionStatement, RunStatement, UseStatement,
+};
 use crate::sql::value::Value;
 use crate::sql::Base;
+use crate::sql::Datetime;
+use async_channel::Receiver;
+use chrono::DateTime;
 use futures::{Stream, StreamExt};
 use reblessive::TreeStack;
 use std::pin::{pin, Pin};
@@ -36,6 +47,10 @@ pub struct Executor {
 	results: Vec<Response>,
 	opt: Options,
 	ctx: Context,
+	/// The values shadowed by `LET LOCAL` statements in the current transaction
+	locals: Vec<(String, Option<Arc<Value>>)>,
+	/// The slot in which the session keeps a transaction open between queries
+	open: Option<OpenTransaction>,
 }
 
 impl Executor {
@@ -45,9 +60,44 @@ impl Executor {
 			results: Vec::new(),
 			opt,
 			ctx,
+			locals: Vec::new(),
+			open: None,
 		}
 	}
 
+	/// Restores the values shadowed by `LET LOCAL` statements once their transaction has ended.
+	fn restore_local_values(&mut self) -> Result<(), Error> {
+		if self.locals.is_empty() {
+			return Ok(());
+		}
+		let ctx_ref = Arc::get_mut(&mut self.ctx)
+			.ok_or_else(|| fail!("Tried to unfreeze a Context with multiple references"))?;
+		// Restore in reverse order, so that the value from before the transaction wins
+		while let Some((name, previous)) = self.locals.pop() {
+			match previous {
+				Some(value) => ctx_ref.add_value(name, value),
+				None => {
+					ctx_ref.remove_value(&name);
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Pins the reads of a transaction to a snapshot, which is shared as the `$snapshot`
+	/// parameter until the transaction ends. Any other transaction, on this or any other
+	/// connection, can then read the same data with `BEGIN READONLY AT $snapshot`.
+	fn pin_snapshot(&mut self, txn: &Transaction, version: u64) -> Result<(), Error> {
+		txn.pin_snapshot(version)?;
+		let token = Datetime::from(DateTime::from_timestamp_nanos(version as i64));
+		let ctx_ref = Arc::get_mut(&mut self.ctx)
+			.ok_or_else(|| fail!("Tried to unfreeze a Context with multiple references"))?;
+		let previous = ctx_ref.remove_value("snapshot");
+		self.locals.push(("snapshot".to_owned(), previous));
+		ctx_ref.add_value("snapshot", Value::Datetime(token).into());
+		Ok(())
+	}
+
 	fn execute_use_statement(&mut self, stmt: UseStatement) -> Result<(), Error> {
 		let ctx_ref = Arc::get_mut(&mut self.ctx)
 			.ok_or_else(|| fail!("Tried to unfreeze a Context with multiple references"))?;
@@ -86,6 +136,9 @@ impl Executor {
 				};
 				self.opt.force = force;
 			}
+			"DRY_RUN" => {
+				self.opt.dry_run = stmt.what;
+			}
 			"FUTURES" => {
 				if stmt.what {
 					self.opt.set_futures(true);
@@ -121,12 +174,16 @@ impl Executor {
 					// TODO: Maybe catch Error::Return?
 					// Currently unsure of if that should be handled here.
 					Ok(val) => {
+						let ctx_ref = Arc::get_mut(&mut self.ctx).ok_or_else(|| {
+							fail!("Tried to unfreeze a Context with multiple references")
+						})?;
+						// Remember the shadowed value of a transaction-scoped parameter
+						if stm.local {
+							let previous = ctx_ref.remove_value(&stm.name);
+							self.locals.push((stm.name.clone(), previous));
+						}
 						// Set the parameter
-						Arc::get_mut(&mut self.ctx)
-							.ok_or_else(|| {
-								fail!("Tried to unfreeze a Context with multiple references")
-							})?
-							.add_value(stm.name, val.into());
+						ctx_ref.add_value(stm.name, val.into());
 						// Finalise transaction, returning nothing unless it couldn't commit
 						Ok(Value::None)
 					}
@@ -158,6 +215,199 @@ impl Executor {
 		return res;
 	}
 
+	/// Commits a transaction which was started for a single statement, and
+	/// flushes the notifications which were created within the transaction.
+	async fn commit_implicit_transaction(
+		&mut self,
+		txn: &Arc<Transaction>,
+		receiver: Option<Receiver<Notification>>,
+	) -> Result<(), Error> {
+		// Nothing is kept when this is a dry run
+		if self.opt.dry_run {
+			let _ = txn.cancel().await;
+			self.opt.sender = None;
+			return Ok(());
+		}
+		Self::commit_transaction(txn).await?;
+
+		// flush notifications.
+		if let Some(recv) = receiver {
+			self.opt.sender = None;
+			if let Some(sink) = self.ctx.notifications() {
+				spawn(async move {
+					while let Ok(x) = recv.recv().await {
+						if sink.send(x).await.is_err() {
+							break;
+						}
+					}
+				});
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Checks that the storage engine can guarantee the durability of a write
+	fn check_durability(&self, kvs: &Datastore) -> Result<(), Error> {
+		match self.opt.durability {
+			Durability::Replicated if !kvs.can_sync() => {
+				Err(Error::DurabilityUnsupported(self.opt.durability.to_string()))
+			}
+			_ => Ok(()),
+		}
+	}
+
+	/// Completes the changes of a transaction and commits it
+	async fn commit_transaction(txn: &Transaction) -> Result<(), Error> {
+		let mut lock = txn.lock().await;
+
+		if let Err(e) = lock.complete_changes(false).await {
+			let _ = lock.cancel().await;
+
+			return Err(Error::QueryNotExecutedDetail {
+				message: e.to_string(),
+			});
+		}
+
+		if let Err(e) = lock.commit().await {
+			return Err(match e {
+				// Keep conflicts intact, so that they can be retried
+				Error::TxRetryable => e,
+				e => Error::QueryNotExecutedDetail {
+					message: e.to_string(),
+				},
+			});
+		}
+
+		Ok(())
+	}
+
+	/// Executes a FOR statement which commits a separate transaction for each iteration.
+	async fn execute_foreach_each(
+		&mut self,
+		kvs: &Datastore,
+		stmt: ForeachStatement,
+	) -> Result<Value, Error> {
+		self.check_durability(kvs)?;
+		let mut cursor = ForeachCursor::default();
+		loop {
+			// Stop between iterations if the query should already be finished.
+			match self.ctx.done(true) {
+				None => {}
+				Some(Reason::Timedout) => {
+					return Err(cursor.interrupted(Error::QueryTimedout));
+				}
+				Some(Reason::Canceled) => {
+					return Err(cursor.interrupted(Error::QueryCancelled));
+				}
+			}
+
+			let txn = kvs
+				.durable_transaction(
+					TransactionType::Write,
+					LockType::Optimistic,
+					self.opt.durability,
+				)
+				.await
+				.map_err(|e| cursor.interrupted(e))?;
+			let txn = Arc::new(txn);
+			let receiver = self.ctx.has_notifications().then(|| {
+				let (send, recv) = async_channel::unbounded();
+				self.opt.sender = Some(send);
+				recv
+			});
+
+			Arc::get_mut(&mut self.ctx)
+				.ok_or_else(|| fail!("Tried to unfreeze a Context with multiple references"))?
+				.set_transaction(txn.clone());
+
+			let res = self
+				.stack
+				.enter(|stk| stmt.compute_batch(stk, &self.ctx, &self.opt, None, &mut cursor))
+				.finish()
+				.await;
+
+			match res {
+				Ok(done) => {
+					if let Err(e) = self.commit_implicit_transaction(&txn, receiver).await {
+						return Err(cursor.interrupted(e));
+					}
+					cursor.commit();
+					if let Some(value) = done {
+						return Ok(value);
+					}
+				}
+				Err(Error::Return {
+					value,
+				}) => {
+					if let Err(e) = self.commit_implicit_transaction(&txn, receiver).await {
+						return Err(cursor.interrupted(e));
+					}
+					return Ok(value);
+				}
+				// Only this iteration is rolled back, the previous ones stay committed,
+				// so the error reports where the loop can be resumed from.
+				Err(e) => {
+					let _ = txn.cancel().await;
+					self.opt.sender = None;
+					return Err(cursor.interrupted(e));
+				}
+			}
+		}
+	}
+
+	/// Executes a RUN TEST statement, running each test in a separate transaction which is always rolled back.
+	async fn execute_run_statement(
+		&mut self,
+		kvs: &Datastore,
+		stmt: RunStatement,
+	) -> Result<Value, Error> {
+		// Fetch the definitions of the tests to run
+		let txn = Arc::new(kvs.transaction(TransactionType::Read, LockType::Optimistic).await?);
+		Arc::get_mut(&mut self.ctx)
+			.ok_or_else(|| fail!("Tried to unfreeze a Context with multiple references"))?
+			.set_transaction(txn.clone());
+		let tests = stmt.tests(&self.ctx, &self.opt).await;
+		let _ = txn.cancel().await;
+		// Run each of the tests
+		let mut results = Vec::new();
+		for test in tests? {
+			// Stop between tests if the query should already be finished.
+			match self.ctx.done(true) {
+				None => {}
+				Some(Reason::Timedout) => {
+					return Err(Error::QueryTimedout);
+				}
+				Some(Reason::Canceled) => {
+					return Err(Error::QueryCancelled);
+				}
+			}
+
+			let txn =
+				Arc::new(kvs.transaction(TransactionType::Write, LockType::Optimistic).await?);
+
+			Arc::get_mut(&mut self.ctx)
+				.ok_or_else(|| fail!("Tried to unfreeze a Context with multiple references"))?
+				.set_transaction(txn.clone());
+
+			let res = self
+				.stack
+				.enter(|stk| test.block.compute(stk, &self.ctx, &self.opt, None))
+				.finish()
+				.await;
+			// Nothing a test writes is ever kept
+			let _ = txn.cancel().await;
+
+			results.push(RunStatement::result(&test, res));
+		}
+		Ok(results.into())
+	}
+
+	/// Executes a CHECK DATABASE statement, checking each batch of keys in a separate transaction.
+	async fn execute_check_statement(&mut self, stmt: CheckStatement) -> Result<Value, Error> {
+		self.stack.enter(|stk| stmt.check(stk, &self.ctx, &self.opt)).finish().await
+	}
+
 	/// Execute a query not wrapped in a transaction block.
 	async fn execute_bare_statement(
 		&mut self,
@@ -178,65 +428,70 @@ impl Executor {
 		match stmt {
 			// These statements don't need a transaction.
 			Statement::Use(stmt) => self.execute_use_statement(stmt).map(|_| Value::None),
-			stmt => {
-				let writeable = stmt.writeable();
-				let txn = Arc::new(kvs.transaction(writeable.into(), LockType::Optimistic).await?);
-				let receiver = self.ctx.has_notifications().then(|| {
-					let (send, recv) = async_channel::unbounded();
-					self.opt.sender = Some(send);
-					recv
-				});
-
-				match self.execute_transaction_statement(txn.clone(), stmt).await {
-					Ok(value)
-					| Err(Error::Return {
-						value,
-					}) => {
-						let mut lock = txn.lock().await;
-
-						// non-writable transactions might return an error on commit.
-						// So cancel them instead. This is fine since a non-writable transaction
-						// has nothing to commit anyway.
-						if !writeable {
-							let _ = lock.cancel().await;
-							return Ok(value);
-						}
-
-						if let Err(e) = lock.complete_changes(false).await {
-							let _ = lock.cancel().await;
-
-							return Err(Error::QueryNotExecutedDetail {
-								message: e.to_string(),
-							});
-						}
-
-						if let Err(e) = lock.commit().await {
-							return Err(Error::QueryNotExecutedDetail {
-								message: e.to_string(),
-							});
-						}
-
-						// flush notifications.
-						if let Some(recv) = receiver {
-							self.opt.sender = None;
-							if let Some(sink) = self.ctx.notifications() {
-								spawn(async move {
-									while let Ok(x) = recv.recv().await {
-										if sink.send(x).await.is_err() {
-											break;
-										}
-									}
-								});
+			// These statements manage their own transactions.
+			Statement::Foreach(stmt) if stmt.each => self.execute_foreach_each(kvs, stmt).await,
+			Statement::Run(stmt) => self.execute_run_statement(kvs, stmt).await,
+			Statement::Check(stmt) => self.execute_check_statement(stmt).await,
+			stmt => match kvs.retry_policy().copied() {
+				// Run the statement once
+				None => self.execute_implicit_transaction(kvs, stmt).await,
+				// Retry the statement if its transaction conflicts
+				Some(policy) => {
+					let mut attempt = 1;
+					loop {
+						match self.execute_implicit_transaction(kvs, stmt.clone()).await {
+							Err(Error::TxRetryable) if attempt < policy.max_attempts => {
+								trace!(target: TARGET, attempt, "Retrying conflicted transaction");
+								policy.wait(attempt).await;
+								attempt += 1;
 							}
+							res => break res,
 						}
-
-						Ok(value)
-					}
-					Err(e) => {
-						let _ = txn.cancel().await;
-						Err(e)
 					}
 				}
+			},
+		}
+	}
+
+	/// Execute a statement within its own transaction.
+	async fn execute_implicit_transaction(
+		&mut self,
+		kvs: &Datastore,
+		stmt: Statement,
+	) -> Result<Value, Error> {
+		let writeable = stmt.writeable();
+		if writeable {
+			self.check_durability(kvs)?;
+		}
+		let txn = kvs
+			.durable_transaction(writeable.into(), LockType::Optimistic, self.opt.durability)
+			.await?;
+		let txn = Arc::new(txn);
+		let receiver = self.ctx.has_notifications().then(|| {
+			let (send, recv) = async_channel::unbounded();
+			self.opt.sender = Some(send);
+			recv
+		});
+		match self.execute_transaction_statement(txn.clone(), stmt).await {
+			Ok(value)
+			| Err(Error::Return {
+				value,
+			}) => {
+				// non-writable transactions might return an error on commit.
+				// So cancel them instead. This is fine since a non-writable transaction
+				// has nothing to commit anyway.
+				if !writeable {
+					let _ = txn.cancel().await;
+					return Ok(value);
+				}
+
+				self.commit_implicit_transaction(&txn, receiver).await?;
+
+				Ok(value)
+			}
+			Err(e) => {
+				let _ = txn.cancel().await;
+				Err(e)
 			}
 		}
 	}
@@ -245,31 +500,20 @@ impl Executor {
 	async fn execute_begin_statement<S>(
 		&mut self,
 		kvs: &Datastore,
-		mut stream: Pin<&mut S>,
+		stmt: BeginStatement,
+		stream: Pin<&mut S>,
 	) -> Result<(), Error>
 	where
 		S: Stream<Item = Result<Statement, Error>>,
 	{
-		let Ok(txn) = kvs.transaction(TransactionType::Write, LockType::Optimistic).await else {
+		let write = match stmt.readonly {
+			true => TransactionType::Read,
+			false => TransactionType::Write,
+		};
+		let Ok(txn) = kvs.transaction(write, LockType::Optimistic).await else {
 			// couldn't create a transaction.
 			// Fast forward until we hit CANCEL or COMMIT
-			while let Some(stmt) = stream.next().await {
-				let stmt = stmt?;
-				if let Statement::Cancel(_) | Statement::Commit(_) = stmt {
-					return Ok(());
-				}
-
-				self.results.push(Response {
-					time: Duration::ZERO,
-					result: Err(Error::QueryNotExecuted),
-					query_type: QueryType::Other,
-				});
-			}
-
-			// Ran out of statements but still didn't hit a COMMIT or CANCEL
-			// Just break as we can't do anything else since the query is already
-			// effectively canceled.
-			return Ok(());
+			return self.skip_transaction(stream, || Error::QueryNotExecuted).await;
 		};
 
 		// Create a sender for this transaction only if the context allows for notifications.
@@ -280,6 +524,35 @@ impl Executor {
 		});
 
 		let txn = Arc::new(txn);
+
+		// Pin all reads within the transaction to the requested snapshot
+		if let Some(at) = stmt.at {
+			Arc::get_mut(&mut self.ctx)
+				.ok_or_else(|| fail!("Tried to unfreeze a Context with multiple references"))?
+				.set_transaction(txn.clone());
+			let res = self.stack.enter(|stk| at.compute(stk, &self.ctx, &self.opt, None)).finish();
+			if let Err(e) = res.await.and_then(|v| self.pin_snapshot(&txn, v)) {
+				let _ = txn.cancel().await;
+				return Err(e);
+			}
+		}
+
+		self.execute_in_transaction(txn, receiver, stream).await
+	}
+
+	/// Execute the statements within an open transaction, until it is committed or cancelled.
+	///
+	/// When the statements run out before the transaction ends, the transaction is kept open
+	/// for the following queries of the session if it allows this, and is cancelled otherwise.
+	async fn execute_in_transaction<S>(
+		&mut self,
+		txn: Arc<Transaction>,
+		receiver: Option<Receiver<Notification>>,
+		mut stream: Pin<&mut S>,
+	) -> Result<(), Error>
+	where
+		S: Stream<Item = Result<Statement, Error>>,
+	{
 		let start_results = self.results.len();
 		let mut skip_remaining = false;
 
@@ -290,6 +563,7 @@ impl Executor {
 				Err(e) => {
 					// make sure the transaction is properly canceled.
 					let _ = txn.cancel().await;
+					self.hold_transaction(None);
 					return Err(e);
 				}
 			};
@@ -305,24 +579,12 @@ impl Executor {
 					res.result = Err(Error::QueryCancelled);
 				}
 
-				while let Some(stmt) = stream.next().await {
-					let stmt = stmt?;
-					if let Statement::Cancel(_) | Statement::Commit(_) = stmt {
-						return Ok(());
-					}
-
-					self.results.push(Response {
-						time: Duration::ZERO,
-						result: Err(match done {
-							Reason::Timedout => Error::QueryTimedout,
-							Reason::Canceled => Error::QueryCancelled,
-						}),
-						query_type: QueryType::Other,
-					});
-				}
-
-				// Missing CANCEL/COMMIT statement, statement already canceled so nothing todo.
-				return Ok(());
+				return self
+					.skip_transaction(stream, || match done {
+						Reason::Timedout => Error::QueryTimedout,
+						Reason::Canceled => Error::QueryCancelled,
+					})
+					.await;
 			}
 
 			if skip_remaining && !matches!(stmt, Statement::Cancel(_) | Statement::Commit(_)) {
@@ -337,6 +599,7 @@ impl Executor {
 				_ => QueryType::Other,
 			};
 
+			let slow = SlowQuery::new(&stmt);
 			let before = Instant::now();
 			let value = match stmt {
 				Statement::Begin(_) => {
@@ -360,21 +623,7 @@ impl Executor {
 
 					self.opt.sender = None;
 
-					while let Some(stmt) = stream.next().await {
-						let stmt = stmt?;
-						if let Statement::Cancel(_) | Statement::Commit(_) = stmt {
-							return Ok(());
-						}
-
-						self.results.push(Response {
-							time: Duration::ZERO,
-							result: Err(Error::QueryNotExecuted),
-							query_type: QueryType::Other,
-						});
-					}
-
-					// Missing CANCEL/COMMIT statement, statement already canceled so nothing todo.
-					return Ok(());
+					return self.skip_transaction(stream, || Error::QueryNotExecuted).await;
 				}
 				Statement::Cancel(_) => {
 					let _ = txn.cancel().await;
@@ -386,10 +635,20 @@ impl Executor {
 					}
 
 					self.opt.sender = None;
+					self.hold_transaction(None);
 
 					return Ok(());
 				}
+				// Nothing is kept when this is a dry run
+				Statement::Commit(_) if self.opt.dry_run => {
+					let _ = txn.cancel().await;
+					self.opt.sender = None;
+					self.hold_transaction(None);
+					return Ok(());
+				}
 				Statement::Commit(_) => {
+					self.hold_transaction(None);
+
 					let mut lock = txn.lock().await;
 
 					// complete_changes and then commit.
@@ -468,22 +727,7 @@ impl Executor {
 
 							self.opt.sender = None;
 
-							while let Some(stmt) = stream.next().await {
-								let stmt = stmt?;
-								if let Statement::Cancel(_) | Statement::Commit(_) = stmt {
-									return Ok(());
-								}
-
-								self.results.push(Response {
-									time: Duration::ZERO,
-									result: Err(Error::QueryNotExecuted),
-									query_type: QueryType::Other,
-								});
-							}
-
-							// ran out of statements before the transaction ended.
-							// Just break as we have nothing else we can do.
-							return Ok(());
+							return self.skip_transaction(stream, || Error::QueryNotExecuted).await;
 						}
 					};
 
@@ -497,15 +741,27 @@ impl Executor {
 				}
 			};
 
+			let time = before.elapsed();
+			slow.log(time);
 			self.results.push(Response {
-				time: before.elapsed(),
+				time,
 				result: value,
 				query_type,
 			});
 		}
 
 		// we ran out of query but we still have an open transaction.
-		// Be conservative and treat this essentially as a CANCEL statement.
+		// Keep it open for the next query of the session, if the session allows this.
+		if self.open.is_some() {
+			self.hold_transaction(Some(HeldTransaction::Open {
+				txn,
+				sender: self.opt.sender.take(),
+				receiver,
+			}));
+			return Ok(());
+		}
+
+		// Otherwise be conservative and treat this essentially as a CANCEL statement.
 		let _ = txn.cancel().await;
 
 		for res in &mut self.results[start_results..] {
@@ -520,15 +776,55 @@ impl Executor {
 		Ok(())
 	}
 
+	/// Consume the statements of a transaction which failed, until we hit a cancel or commit.
+	///
+	/// If the statements run out first, the statements of the following queries of the
+	/// session are not executed either, until the transaction is ended.
+	async fn skip_transaction<S>(
+		&mut self,
+		mut stream: Pin<&mut S>,
+		err: impl Fn() -> Error,
+	) -> Result<(), Error>
+	where
+		S: Stream<Item = Result<Statement, Error>>,
+	{
+		while let Some(stmt) = stream.next().await {
+			let stmt = stmt?;
+			if let Statement::Cancel(_) | Statement::Commit(_) = stmt {
+				self.hold_transaction(None);
+				return Ok(());
+			}
+
+			self.results.push(Response {
+				time: Duration::ZERO,
+				result: Err(err()),
+				query_type: QueryType::Other,
+			});
+		}
+
+		// ran out of statements before the transaction ended.
+		self.hold_transaction(self.open.is_some().then_some(HeldTransaction::Failed));
+
+		Ok(())
+	}
+
+	/// Keeps a transaction open for the following queries of the session, or releases it.
+	fn hold_transaction(&self, held: Option<HeldTransaction>) {
+		if let Some(open) = &self.open {
+			open.set(held);
+		}
+	}
+
 	#[instrument(level = "debug", name = "executor", target = "surrealdb::core::dbs", skip_all)]
 	pub async fn execute(
 		kvs: &Datastore,
 		ctx: Context,
 		opt: Options,
 		qry: Query,
+		open: Option<OpenTransaction>,
 	) -> Result<Vec<Response>, Error> {
 		let stream = futures::stream::iter(qry.into_iter().map(Ok));
-		Self::execute_stream(kvs, ctx, opt, stream).await
+		Self::execute_stream(kvs, ctx, opt, stream, open).await
 	}
 
 	#[instrument(level = "debug", name = "executor", target = "surrealdb::core::dbs", skip_all)]
@@ -537,6 +833,7 @@ impl Executor {
 		ctx: Context,
 		opt: Options,
 		stream: S,
+		open: Option<OpenTransaction>,
 	) -> Result<Vec<Response>, Error>
 	where
 		S: Stream<Item = Result<Statement, Error>>,
@@ -544,6 +841,33 @@ impl Executor {
 		let mut this = Executor::new(ctx, opt);
 		let mut stream = pin!(stream);
 
+		// Continue the transaction which an earlier query of the session left open
+		this.open = open;
+		let held = this.open.as_ref().and_then(OpenTransaction::get);
+		let res = match held {
+			Some(HeldTransaction::Open {
+				txn,
+				sender,
+				receiver,
+			}) => {
+				this.opt.sender = sender;
+				this.execute_in_transaction(txn, receiver, stream.as_mut()).await
+			}
+			Some(HeldTransaction::Failed) => {
+				this.skip_transaction(stream.as_mut(), || Error::QueryNotExecuted).await
+			}
+			None => Ok(()),
+		};
+		if let Err(e) = res {
+			this.results.push(Response {
+				time: Duration::ZERO,
+				result: Err(e),
+				query_type: QueryType::Other,
+			});
+			return Ok(this.results);
+		}
+		this.restore_local_values()?;
+
 		while let Some(stmt) = stream.next().await {
 			let stmt = match stmt {
 				Ok(x) => x,
@@ -554,22 +878,23 @@ impl Executor {
 						query_type: QueryType::Other,
 					});
 
-					return Ok(this.results);
+					break;
 				}
 			};
 
 			match stmt {
 				Statement::Option(stmt) => this.execute_option_statement(stmt)?,
 				// handle option here because it doesn't produce a result.
-				Statement::Begin(_) => {
-					if let Err(e) = this.execute_begin_statement(kvs, stream.as_mut()).await {
+				Statement::Begin(stmt) => {
+					let res = this.execute_begin_statement(kvs, stmt, stream.as_mut()).await;
+					if let Err(e) = res {
 						this.results.push(Response {
 							time: Duration::ZERO,
 							result: Err(e),
 							query_type: QueryType::Other,
 						});
 
-						return Ok(this.results);
+						break;
 					}
 				}
 				stmt => {
@@ -579,20 +904,54 @@ impl Executor {
 						_ => QueryType::Other,
 					};
 
+					let slow = SlowQuery::new(&stmt);
 					let now = Instant::now();
 					let result = this.execute_bare_statement(kvs, stmt).await;
+					let time = now.elapsed();
+					slow.log(time);
 					this.results.push(Response {
-						time: now.elapsed(),
+						time,
 						result,
 						query_type,
 					});
 				}
 			}
+			// Transaction-scoped parameters end with their transaction
+			this.restore_local_values()?;
 		}
 		Ok(this.results)
 	}
 }
 
+/// A statement which is logged if it takes longer than the slow query threshold
+struct SlowQuery(Option<(String, Option<String>)>);
+
+impl SlowQuery {
+	fn new(stmt: &Statement) -> Self {
+		// Only format the statement when slow queries are being logged
+		Self(SLOW_QUERY_THRESHOLD.map(|_| {
+			let hint = match stmt {
+				Statement::Select(v) => v.with.as_ref().map(ToString::to_string),
+				_ => None,
+			};
+			(stmt.to_string(), hint)
+		}))
+	}
+
+	fn log(self, time: Duration) {
+		if let (Some((statement, hint)), Some(threshold)) = (self.0, *SLOW_QUERY_THRESHOLD) {
+			if time > threshold {
+				match hint {
+					Some(hint) => {
+						warn!(target: TARGET, %statement, %hint, ?time, "Slow query ran with a planner hint")
+					}
+					None => warn!(target: TARGET, %statement, ?time, "Slow query"),
+				}
+			}
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::{dbs::Session, iam::Role, kvs::Datastore};
@@ -705,4 +1064,82 @@ mod tests {
 			);
 		}
 	}
+
+	#[tokio::test]
+	async fn check_execute_durability() {
+		use crate::dbs::Durability;
+		use crate::err::Error;
+		use crate::sql::Value;
+
+		let ses = Session::owner().with_ns("NS").with_db("DB");
+		// Each level acknowledges writes which can be read afterwards
+		for durability in [Durability::Memory, Durability::Applied] {
+			let ds = Datastore::new("memory").await.unwrap();
+			let ses = ses.clone().with_durability(durability);
+			let res = ds.execute("CREATE test:1; CREATE test:2;", &ses, None).await.unwrap();
+			assert!(res.iter().all(|r| r.result.is_ok()), "{durability}: {res:?}");
+			let res = ds.execute("SELECT VALUE id FROM test", &ses, None).await.unwrap();
+			let val = res.into_iter().next().unwrap().result.unwrap();
+			assert_eq!(val.to_string(), "[test:1, test:2]", "{durability}");
+		}
+		// The in-memory storage engine can not sync writes to disk
+		{
+			let ds = Datastore::new("memory").await.unwrap();
+			let ses = ses.clone().with_durability(Durability::Replicated);
+			let res = ds.execute("CREATE test:1; SELECT * FROM test;", &ses, None).await.unwrap();
+			assert!(matches!(res[0].result, Err(Error::DurabilityUnsupported(_))), "{res:?}");
+			assert_eq!(res[1].result.as_ref().unwrap(), &Value::from(Vec::<Value>::new()));
+		}
+		// Writes are visible to the statements which follow them
+		for durability in [Durability::Memory, Durability::Applied] {
+			let ds = Datastore::new("memory").await.unwrap();
+			let ses = ses.clone().with_durability(durability);
+			let sql = "CREATE test:1; UPDATE test:1 SET n = 1; SELECT VALUE n FROM test:1;";
+			let res = ds.execute(sql, &ses, None).await.unwrap();
+			let val = res.into_iter().last().unwrap().result.unwrap();
+			assert_eq!(val.to_string(), "[1]", "{durability}");
+		}
+	}
+
+	#[tokio::test]
+	async fn check_execute_held_transaction() {
+		use crate::dbs::Response;
+		use crate::err::Error;
+
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("NS").with_db("DB").with_transactions();
+		let other = Session::owner().with_ns("NS").with_db("DB");
+		let count = "RETURN count(SELECT * FROM test)";
+		let value = |res: Vec<Response>| res.into_iter().last().unwrap().result.unwrap();
+		// The statements run within the transaction as they are sent
+		let res = ds.execute("BEGIN; CREATE test:1;", &ses, None).await.unwrap();
+		assert!(res.iter().all(|r| r.result.is_ok()), "{res:?}");
+		let res = ds.execute(&format!("CREATE test:2; {count}"), &ses, None).await.unwrap();
+		assert_eq!(value(res).to_string(), "2");
+		// The writes are not visible outside of the transaction
+		let res = ds.execute(count, &other, None).await.unwrap();
+		assert_eq!(value(res).to_string(), "0");
+		// Cancelling the transaction discards the writes
+		ds.execute("CANCEL;", &ses, None).await.unwrap();
+		let res = ds.execute(count, &ses, None).await.unwrap();
+		assert_eq!(value(res).to_string(), "0");
+		// Once a statement fails, the statements up to the end of the transaction are not run
+		ds.execute("BEGIN; CREATE test:1;", &ses, None).await.unwrap();
+		let res = ds.execute("CREATE test:1;", &ses, None).await.unwrap();
+		assert!(res[0].result.is_err(), "{res:?}");
+		let res = ds.execute("CREATE test:3; COMMIT;", &ses, None).await.unwrap();
+		assert!(matches!(res[0].result, Err(Error::QueryNotExecuted)), "{res:?}");
+		let res = ds.execute(count, &ses, None).await.unwrap();
+		assert_eq!(value(res).to_string(), "0");
+		// Committing the transaction keeps the writes
+		ds.execute("BEGIN; CREATE test:1;", &ses, None).await.unwrap();
+		ds.execute("CREATE test:2; COMMIT;", &ses, None).await.unwrap();
+		let res = ds.execute(count, &other, None).await.unwrap();
+		assert_eq!(value(res).to_string(), "2");
+		// Sessions which don't keep transactions open still cancel them
+		let res = ds.execute("BEGIN; CREATE test:3;", &other, None).await.unwrap();
+		assert!(res[0].result.is_err(), "{res:?}");
+		let res = ds.execute(count, &other, None).await.unwrap();
+		assert_eq!(value(res).to_string(), "2");
+	}
 }
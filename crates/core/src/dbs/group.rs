@@ -2,7 +2,10 @@ use crate::ctx::Context;
 use crate::dbs::plan::Explanation;
 use crate::dbs::store::MemoryCollector;
 use crate::dbs::{Options, Statement};
+use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::fnc::util::math::approx_distinct::ApproxDistinct;
+use crate::fnc::util::math::approx_percentile::ApproxPercentile;
 use crate::idx::planner::RecordStrategy;
 use crate::sql::function::OptimisedAggregate;
 use crate::sql::value::{TryAdd, TryFloatDiv, Value};
@@ -23,10 +26,12 @@ struct Aggregator {
 	first_val: Option<Value>,
 	count: Option<usize>,
 	count_function: Option<(Box<Function>, usize)>,
+	count_approx_distinct: Option<ApproxDistinct>,
 	math_max: Option<Value>,
 	math_min: Option<Value>,
 	math_sum: Option<Value>,
 	math_mean: Option<(Value, usize)>,
+	math_approx_percentile: Option<(Box<Function>, ApproxPercentile)>,
 	time_max: Option<Value>,
 	time_min: Option<Value>,
 }
@@ -157,7 +162,7 @@ impl GroupsCollector {
 											f.aggregate(vals)?.compute(stk, ctx, opt, None).await?
 										} else {
 											// The aggregation is optimised, just get the value
-											agr.compute(a)?
+											agr.compute(stk, ctx, opt, a).await?
 										};
 										obj.set(stk, ctx, opt, idiom.as_ref(), x).await?;
 									}
@@ -170,6 +175,13 @@ impl GroupsCollector {
 						}
 					}
 				}
+				// Filter the grouped results by any HAVING clause
+				if let Some(cond) = stm.having() {
+					let doc = CursorDoc::from(obj.clone());
+					if !cond.compute(stk, ctx, opt, Some(&doc)).await?.is_truthy() {
+						continue;
+					}
+				}
 				// Add the object to the results
 				results.push(obj);
 			}
@@ -219,6 +231,11 @@ impl Aggregator {
 					self.count_function = Some((f.unwrap().clone(), 0));
 				}
 			}
+			OptimisedAggregate::CountApproxDistinct => {
+				if self.count_approx_distinct.is_none() {
+					self.count_approx_distinct = Some(ApproxDistinct::default());
+				}
+			}
 			OptimisedAggregate::MathMax => {
 				if self.math_max.is_none() {
 					self.math_max = Some(Value::None);
@@ -239,6 +256,12 @@ impl Aggregator {
 					self.math_mean = Some((0.into(), 0));
 				}
 			}
+			OptimisedAggregate::MathApproxPercentile => {
+				if self.math_approx_percentile.is_none() {
+					self.math_approx_percentile =
+						Some((f.unwrap().clone(), ApproxPercentile::default()));
+				}
+			}
 			OptimisedAggregate::TimeMax => {
 				if self.time_max.is_none() {
 					self.time_max = Some(Value::None);
@@ -258,10 +281,18 @@ impl Aggregator {
 			first_val: self.first_val.as_ref().map(|_| Value::None),
 			count: self.count.as_ref().map(|_| 0),
 			count_function: self.count_function.as_ref().map(|(f, _)| (f.clone(), 0)),
+			count_approx_distinct: self
+				.count_approx_distinct
+				.as_ref()
+				.map(|_| ApproxDistinct::default()),
 			math_max: self.math_max.as_ref().map(|_| Value::None),
 			math_min: self.math_min.as_ref().map(|_| Value::None),
 			math_sum: self.math_sum.as_ref().map(|_| 0.into()),
 			math_mean: self.math_mean.as_ref().map(|_| (0.into(), 0)),
+			math_approx_percentile: self
+				.math_approx_percentile
+				.as_ref()
+				.map(|(f, _)| (f.clone(), ApproxPercentile::default())),
 			time_max: self.time_max.as_ref().map(|_| Value::None),
 			time_min: self.time_min.as_ref().map(|_| Value::None),
 		}
@@ -289,6 +320,14 @@ impl Aggregator {
 				*c += 1;
 			}
 		}
+		if let Some(ref mut s) = self.count_approx_distinct {
+			s.push(&val);
+		}
+		if let Value::Number(ref n) = val {
+			if let Some((_, ref mut s)) = self.math_approx_percentile {
+				s.push(n);
+			}
+		}
 		if val.is_number() {
 			if let Some(s) = self.math_sum.take() {
 				self.math_sum = Some(s.try_add(val.clone())?);
@@ -338,13 +377,24 @@ impl Aggregator {
 		Ok(())
 	}
 
-	fn compute(&mut self, a: OptimisedAggregate) -> Result<Value, Error> {
+	async fn compute(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		a: OptimisedAggregate,
+	) -> Result<Value, Error> {
 		Ok(match a {
 			OptimisedAggregate::None => Value::None,
 			OptimisedAggregate::Count => self.count.take().map(|v| v.into()).unwrap_or(Value::None),
 			OptimisedAggregate::CountFunction => {
 				self.count_function.take().map(|(_, v)| v.into()).unwrap_or(Value::None)
 			}
+			OptimisedAggregate::CountApproxDistinct => self
+				.count_approx_distinct
+				.take()
+				.map(|s| s.estimate().into())
+				.unwrap_or(Value::None),
 			OptimisedAggregate::MathMax => self.math_max.take().unwrap_or(Value::None),
 			OptimisedAggregate::MathMin => self.math_min.take().unwrap_or(Value::None),
 			OptimisedAggregate::MathSum => self.math_sum.take().unwrap_or(Value::None),
@@ -355,6 +405,27 @@ impl Aggregator {
 					Value::None
 				}
 			}
+			OptimisedAggregate::MathApproxPercentile => {
+				if let Some((f, s)) = self.math_approx_percentile.take() {
+					// The percentile is the second argument of the function
+					let Some(v) = f.args().get(1) else {
+						return Err(Error::InvalidArguments {
+							name: "math::approx_percentile".to_owned(),
+							message: String::from("Expected 2 arguments."),
+						});
+					};
+					let n =
+						v.compute(stk, ctx, opt, None).await?.coerce_to_number().map_err(|e| {
+							Error::InvalidArguments {
+								name: "math::approx_percentile".to_owned(),
+								message: format!("Argument 2 was the wrong type. {e}"),
+							}
+						})?;
+					s.percentile(n).into()
+				} else {
+					Value::None
+				}
+			}
 			OptimisedAggregate::TimeMax => self.time_max.take().unwrap_or(Value::None),
 			OptimisedAggregate::TimeMin => self.time_min.take().unwrap_or(Value::None),
 		})
@@ -385,6 +456,12 @@ impl Aggregator {
 		if self.count_function.is_some() {
 			collections.push("count+func".into());
 		}
+		if self.count_approx_distinct.is_some() {
+			collections.push("count::approx_distinct".into());
+		}
+		if self.math_approx_percentile.is_some() {
+			collections.push("math::approx_percentile".into());
+		}
 		if self.math_mean.is_some() {
 			collections.push("math::mean".into());
 		}
@@ -1,6 +1,6 @@
 use crate::ctx::Context;
 use crate::ctx::{Canceller, MutableContext};
-use crate::dbs::distinct::SyncDistinct;
+use crate::dbs::distinct::{DistinctRows, SyncDistinct};
 use crate::dbs::plan::{Explanation, Plan};
 use crate::dbs::result::Results;
 use crate::dbs::Options;
@@ -13,10 +13,13 @@ use crate::sql::array::Array;
 use crate::sql::edges::Edges;
 use crate::sql::mock::Mock;
 use crate::sql::object::Object;
+use crate::sql::order::Ordering;
 use crate::sql::table::Table;
 use crate::sql::thing::Thing;
 use crate::sql::value::Value;
 use crate::sql::{Fields, Id, IdRange};
+use rand::prelude::SliceRandom;
+use rand::thread_rng;
 use reblessive::tree::Stk;
 use std::mem;
 use std::sync::Arc;
@@ -120,6 +123,8 @@ pub(crate) struct Iterator {
 	guaranteed: Option<Iterable>,
 	/// Set if the iterator can be cancelled once it reaches start/limit
 	cancel_on_limit: Option<u32>,
+	/// The rows which were collected, when duplicate rows are removed as they are collected
+	distinct: DistinctRows,
 }
 
 impl Clone for Iterator {
@@ -135,6 +140,7 @@ impl Clone for Iterator {
 			entries: self.entries.clone(),
 			guaranteed: None,
 			cancel_on_limit: None,
+			distinct: DistinctRows::default(),
 		}
 	}
 }
@@ -363,6 +369,8 @@ impl Iterator {
 			self.output_split(stk, ctx, opt, stm, rs).await?;
 			// Process any GROUP BY clause
 			self.output_group(stk, ctx, opt, stm).await?;
+			// Process any window functions
+			self.output_window(stk, ctx, opt, stm).await?;
 			// Process any ORDER BY clause
 			if let Some(orders) = stm.order() {
 				#[cfg(not(target_family = "wasm"))]
@@ -370,6 +378,8 @@ impl Iterator {
 				#[cfg(target_family = "wasm")]
 				self.results.sort(orders);
 			}
+			// Process any DISTINCT clause
+			self.output_distinct(stm).await?;
 			// Process any START & LIMIT clause
 			self.results.start_limit(self.start_skip, self.start, self.limit).await?;
 			// Process any FETCH clause
@@ -432,6 +442,16 @@ impl Iterator {
 			return false;
 		}
 
+		// If there are window functions we can't, as they depend on every row
+		if stm.expr().is_some_and(Fields::has_windows) {
+			return false;
+		}
+
+		// If duplicate rows are removed once every row is collected we can't
+		if stm.distinct().is_some() && !DistinctRows::is_streaming(stm) {
+			return false;
+		}
+
 		// If there is no specified order, we can
 		if stm.order().is_none() {
 			return true;
@@ -468,7 +488,8 @@ impl Iterator {
 				}
 			}
 			// Check if we can skip processing the document below "start".
-			if let Some(false) = is_specific_permission {
+			// Skipped documents could be duplicates of later rows, so not with DISTINCT.
+			if let (Some(false), None) = (is_specific_permission, stm.distinct()) {
 				let s = self.start.unwrap_or(0) as usize;
 				if s > 0 {
 					self.start_skip = Some(s);
@@ -548,6 +569,46 @@ impl Iterator {
 		Ok(())
 	}
 
+	async fn output_window(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		if let Some(fields) = stm.expr().filter(|f| f.has_windows()) {
+			// Compute the window functions over every row
+			let mut values = self.results.take().await?;
+			fields.compute_windows(stk, ctx, opt, &mut values).await?;
+			// The rows are ordered after the window functions, so ORDER BY can use their results
+			match stm.order() {
+				Some(Ordering::Order(orders)) => values.sort_by(|a, b| orders.compare(a, b)),
+				Some(Ordering::Random) => values.shuffle(&mut thread_rng()),
+				None => (),
+			}
+			self.results = values.into();
+		}
+		// Everything ok
+		Ok(())
+	}
+
+	async fn output_distinct(&mut self, stm: &Statement<'_>) -> Result<(), Error> {
+		// Duplicate rows which were not removed as they were collected are removed in order
+		if let Some(distinct) = stm.distinct().filter(|_| !DistinctRows::is_streaming(stm)) {
+			let mut rows = DistinctRows::default();
+			let values = self.results.take().await?;
+			let mut distinct_values = Vec::with_capacity(values.len());
+			for v in values {
+				if !rows.check_already_seen(distinct, &v)? {
+					distinct_values.push(v);
+				}
+			}
+			self.results = distinct_values.into();
+		}
+		// Everything ok
+		Ok(())
+	}
+
 	async fn output_fetch(
 		&mut self,
 		stk: &mut Stk,
@@ -667,6 +728,18 @@ impl Iterator {
 				return;
 			}
 			Ok(v) => {
+				// Skip any row which duplicates a collected row
+				if let Some(distinct) = stm.distinct().filter(|_| DistinctRows::is_streaming(stm)) {
+					match self.distinct.check_already_seen(distinct, &v) {
+						Ok(true) => return,
+						Ok(false) => {}
+						Err(e) => {
+							self.error = Some(e);
+							self.run.cancel();
+							return;
+						}
+					}
+				}
 				if let Err(e) = self.results.push(stk, ctx, opt, stm, rs, v).await {
 					self.error = Some(e);
 					self.run.cancel();
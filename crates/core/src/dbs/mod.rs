@@ -3,6 +3,7 @@
 //! glue between the API and the response. In this module we use channels as a transport layer
 //! and executors to process the operations. This module also gives a `context` to the transaction.
 mod distinct;
+mod durability;
 mod executor;
 mod group;
 mod iterator;
@@ -21,6 +22,7 @@ pub mod capabilities;
 pub mod node;
 
 pub use self::capabilities::Capabilities;
+pub use self::durability::Durability;
 pub(crate) use self::executor::*;
 pub(crate) use self::iterator::*;
 pub use self::notification::*;
@@ -1,4 +1,5 @@
 use crate::cnf::MAX_COMPUTATION_DEPTH;
+use crate::dbs::Durability;
 use crate::dbs::Notification;
 use crate::err::Error;
 use crate::iam::{Action, Auth, ResourceKind};
@@ -46,6 +47,10 @@ pub struct Options {
 	pub(crate) version: Option<u64>,
 	/// The channel over which we send notifications
 	pub(crate) sender: Option<Sender<Notification>>,
+	/// How far writes must progress before they are acknowledged
+	pub(crate) durability: Durability,
+	/// Should we roll back all changes instead of committing them?
+	pub(crate) dry_run: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -88,6 +93,8 @@ impl Options {
 			sender: None,
 			auth: Arc::new(Auth::default()),
 			version: None,
+			durability: Durability::Applied,
+			dry_run: false,
 		}
 	}
 
@@ -167,6 +174,12 @@ impl Options {
 		self
 	}
 
+	/// Specify how far writes must progress before they are acknowledged
+	pub fn with_durability(mut self, durability: Durability) -> Self {
+		self.durability = durability;
+		self
+	}
+
 	/// Specify if we are currently importing data
 	pub fn with_import(mut self, import: bool) -> Self {
 		self.set_import(import);
@@ -2,6 +2,7 @@ use crate::ctx::Context;
 use crate::dbs::result::Results;
 use crate::dbs::{Iterable, Statement};
 use crate::idx::planner::RecordStrategy;
+use crate::sql::join::JoinStrategy;
 use crate::sql::{Object, Value};
 use std::collections::HashMap;
 
@@ -21,10 +22,19 @@ impl Plan {
 			None => (true, None),
 			Some(e) => {
 				let mut exp = Explanation::default();
+				let qp = ctx.get_query_planner();
+				let joined = qp.is_some_and(|qp| !qp.joins().is_empty());
 				for i in iterables {
+					// The joined rows are explained by their joins
+					if joined && matches!(i, Iterable::Value(_)) {
+						continue;
+					}
 					exp.add_iter(ctx, i);
 				}
-				if let Some(qp) = ctx.get_query_planner() {
+				if let Some(qp) = qp {
+					for (tb, strategy) in qp.joins() {
+						exp.add_join(tb, strategy);
+					}
 					for reason in qp.fallbacks() {
 						exp.add_fallback(reason.to_string());
 					}
@@ -59,6 +69,11 @@ impl Explanation {
 	) {
 		self.0.push(ExplainItem::new_collector(collector_type, details));
 	}
+
+	fn add_join(&mut self, tb: &str, strategy: &JoinStrategy) {
+		self.0.push(ExplainItem::new_join(tb, strategy));
+	}
+
 	fn add_fallback(&mut self, reason: String) {
 		self.0.push(ExplainItem::new_fallback(reason));
 	}
@@ -92,6 +107,18 @@ impl ExplainItem {
 		}
 	}
 
+	fn new_join(tb: &str, strategy: &JoinStrategy) -> Self {
+		let mut details =
+			vec![("table", Value::from(tb)), ("strategy", strategy.to_string().into())];
+		if let JoinStrategy::Index(ix) = strategy {
+			details.push(("index", Value::from(ix.as_str())));
+		}
+		Self {
+			name: "Join".into(),
+			details,
+		}
+	}
+
 	fn new_fallback(reason: String) -> Self {
 		Self {
 			name: "Fallback".into(),
@@ -1,4 +1,4 @@
-use crate::err::Error;
+use crate::err::{Error, ErrorDetails};
 use crate::sql::Value as CoreValue;
 use revision::revisioned;
 use revision::Revisioned;
@@ -72,26 +72,32 @@ impl Serialize for Response {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Debug, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct QueryMethodResponse {
 	pub time: String,
 	pub status: Status,
 	pub result: CoreValue,
+	/// The code and details of the error, when the query failed. This is
+	/// only sent in the revisioned format, to clients using `revision-2`.
+	#[revision(start = 2)]
+	#[serde(skip)]
+	pub error: Option<ErrorDetails>,
 }
 
 impl From<&Response> for QueryMethodResponse {
 	fn from(res: &Response) -> Self {
 		let time = res.speed();
-		let (status, result) = match &res.result {
-			Ok(value) => (Status::Ok, value.clone()),
-			Err(error) => (Status::Err, CoreValue::from(error.to_string())),
+		let (status, result, error) = match &res.result {
+			Ok(value) => (Status::Ok, value.clone(), None),
+			Err(error) => (Status::Err, CoreValue::from(error.to_string()), Some(error.details())),
 		};
 		Self {
 			status,
 			result,
 			time,
+			error,
 		}
 	}
 }
@@ -113,6 +119,6 @@ impl Revisioned for Response {
 	}
 
 	fn revision() -> u16 {
-		1
+		QueryMethodResponse::revision()
 	}
 }
@@ -1,5 +1,6 @@
 use crate::cnf::MAX_ORDER_LIMIT_PRIORITY_QUEUE_SIZE;
 use crate::ctx::Context;
+use crate::dbs::distinct::DistinctRows;
 #[cfg(storage)]
 use crate::dbs::file::FileCollector;
 use crate::dbs::group::GroupsCollector;
@@ -9,7 +10,7 @@ use crate::dbs::{Options, Statement};
 use crate::err::Error;
 use crate::idx::planner::RecordStrategy;
 use crate::sql::order::Ordering;
-use crate::sql::Value;
+use crate::sql::{Fields, Value};
 use reblessive::tree::Stk;
 
 pub(super) enum Results {
@@ -34,6 +35,10 @@ impl Results {
 		if stm.expr().is_some() && stm.group().is_some() {
 			return Ok(Self::Groups(GroupsCollector::new(stm)));
 		}
+		// Window functions are computed, and the rows ordered, once every row is collected
+		if stm.expr().is_some_and(Fields::has_windows) {
+			return Ok(Self::Memory(Default::default()));
+		}
 		#[cfg(storage)]
 		if stm.tempfiles() {
 			if let Some(temp_dir) = ctx.temporary_directory() {
@@ -44,6 +49,9 @@ impl Results {
 			return match ordering {
 				Ordering::Random => Ok(Self::MemoryRandom(MemoryRandom::new(None))),
 				Ordering::Order(orders) => {
+					// Duplicate rows which are removed after ordering could be within the limit
+					let limit = limit
+						.filter(|_| stm.distinct().is_none() || DistinctRows::is_streaming(stm));
 					if let Some(limit) = limit {
 						let limit = start.unwrap_or(0) + limit;
 						if limit <= *MAX_ORDER_LIMIT_PRIORITY_QUEUE_SIZE {
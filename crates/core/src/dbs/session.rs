@@ -1,10 +1,15 @@
 use crate::ctx::MutableContext;
+use crate::dbs::Durability;
+use crate::dbs::Notification;
 use crate::iam::Auth;
 use crate::iam::{Level, Role};
+use crate::kvs::Transaction;
 use crate::sql::value::Value;
+use async_channel::{Receiver, Sender};
 use chrono::Utc;
 use std::collections::BTreeMap;
-use std::sync::Arc;
+use std::fmt;
+use std::sync::{Arc, Mutex};
 
 /// Specifies the current session information when processing a query.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -34,6 +39,10 @@ pub struct Session {
 	pub exp: Option<i64>,
 	/// The parameters set
 	pub parameters: BTreeMap<String, Value>,
+	/// How far writes must progress before they are acknowledged
+	pub durability: Durability,
+	/// The transaction which is kept open between the queries of the session
+	pub(crate) transaction: Option<OpenTransaction>,
 }
 
 impl Session {
@@ -61,6 +70,19 @@ impl Session {
 		self
 	}
 
+	/// Set how far writes must progress before they are acknowledged
+	pub fn with_durability(mut self, durability: Durability) -> Session {
+		self.durability = durability;
+		self
+	}
+
+	/// Keep a transaction which a query begins without committing open, so that the
+	/// following queries of the session run within it until it is committed or cancelled
+	pub fn with_transactions(mut self) -> Session {
+		self.transaction = Some(OpenTransaction::default());
+		self
+	}
+
 	/// Retrieves the selected namespace
 	pub(crate) fn ns(&self) -> Option<Arc<str>> {
 		self.ns.as_deref().map(Into::into)
@@ -149,6 +171,8 @@ impl Session {
 			rd: Some(rid),
 			exp: None,
 			parameters: Default::default(),
+			durability: Default::default(),
+			transaction: None,
 		}
 	}
 
@@ -167,3 +191,49 @@ impl Session {
 		Session::for_level(Level::Root, Role::Viewer)
 	}
 }
+
+/// A transaction which is kept open between the queries of a session
+///
+/// The slot is shared by the clones of the session, so that it outlives the changes which are
+/// made to the session between queries.
+#[derive(Clone, Default)]
+pub(crate) struct OpenTransaction(Arc<Mutex<Option<HeldTransaction>>>);
+
+#[derive(Clone)]
+pub(crate) enum HeldTransaction {
+	/// The transaction is open
+	Open {
+		txn: Arc<Transaction>,
+		sender: Option<Sender<Notification>>,
+		receiver: Option<Receiver<Notification>>,
+	},
+	/// A statement in the transaction failed, so the statements up to its end are not executed
+	Failed,
+}
+
+impl OpenTransaction {
+	/// The transaction which is currently held
+	pub(crate) fn get(&self) -> Option<HeldTransaction> {
+		self.0.lock().unwrap_or_else(|e| e.into_inner()).clone()
+	}
+
+	/// Replaces the transaction which is held, or releases it
+	pub(crate) fn set(&self, held: Option<HeldTransaction>) {
+		*self.0.lock().unwrap_or_else(|e| e.into_inner()) = held;
+	}
+}
+
+impl PartialEq for OpenTransaction {
+	fn eq(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.0, &other.0)
+	}
+}
+
+impl Eq for OpenTransaction {}
+
+impl fmt::Debug for OpenTransaction {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let open = matches!(self.get(), Some(HeldTransaction::Open { .. }));
+		f.debug_struct("OpenTransaction").field("open", &open).finish()
+	}
+}
@@ -1,5 +1,6 @@
 use crate::sql::cond::Cond;
 use crate::sql::data::Data;
+use crate::sql::distinct::Distinct;
 use crate::sql::fetch::Fetchs;
 use crate::sql::field::Fields;
 use crate::sql::group::Groups;
@@ -307,6 +308,14 @@ impl Statement<'_> {
 		}
 	}
 
+	/// Returns any DISTINCT clause if specified
+	pub(crate) fn distinct(&self) -> Option<&Distinct> {
+		match self {
+			Statement::Select(v) => v.distinct.as_ref(),
+			_ => None,
+		}
+	}
+
 	/// Returns any GROUP clause if specified
 	pub(crate) fn group(&self) -> Option<&Groups> {
 		match self {
@@ -315,6 +324,14 @@ impl Statement<'_> {
 		}
 	}
 
+	/// Returns any HAVING clause if specified
+	pub(crate) fn having(&self) -> Option<&Cond> {
+		match self {
+			Statement::Select(v) => v.having.as_ref(),
+			_ => None,
+		}
+	}
+
 	/// Returns any ORDER clause if specified
 	pub(crate) fn order(&self) -> Option<&Ordering> {
 		match self {
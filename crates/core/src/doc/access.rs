@@ -0,0 +1,78 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::Document;
+use crate::err::Error;
+use crate::kvs::KeyEncode;
+
+impl Document {
+	/// Records that this document was read, if its table tracks record access
+	pub(super) async fn track_read(&self, ctx: &Context, opt: &Options) -> Result<(), Error> {
+		// Check if the record exists
+		if self.current.doc.as_ref().is_none() {
+			return Ok(());
+		}
+		// Access times are kept in the datastore cache
+		let Some(cache) = ctx.get_cache() else {
+			return Ok(());
+		};
+		// Check if the table tracks record access
+		if !self.tb(ctx, opt).await?.cache {
+			return Ok(());
+		}
+		// Get the record id
+		let rid = self.id()?;
+		// Get NS & DB
+		let (ns, db) = opt.ns_db()?;
+		// Update the access time of the record
+		let key = crate::key::thing::new(ns, db, &rid.tb, &rid.id).encode()?;
+		cache.touch_record(key);
+		// Carry on
+		Ok(())
+	}
+
+	/// Records that this document was written, if its table tracks record access
+	pub(super) async fn track_write(&self, ctx: &Context, opt: &Options) -> Result<(), Error> {
+		// Check if the record exists
+		if self.current.doc.as_ref().is_none() {
+			return Ok(());
+		}
+		// Access times are kept in the datastore cache
+		let Some(cache) = ctx.get_cache() else {
+			return Ok(());
+		};
+		// Check if the table tracks record access
+		if !self.tb(ctx, opt).await?.cache {
+			return Ok(());
+		}
+		// Get the record id
+		let rid = self.id()?;
+		// Get NS & DB
+		let (ns, db) = opt.ns_db()?;
+		// Update the access time of the record when the write is committed
+		let key = crate::key::thing::new(ns, db, &rid.tb, &rid.id).encode()?;
+		ctx.tx().track_access(cache.write_record(key));
+		// Carry on
+		Ok(())
+	}
+
+	/// Forgets the access time of this document, if its table tracks record access
+	pub(super) async fn forget_access(&self, ctx: &Context, opt: &Options) -> Result<(), Error> {
+		// Access times are kept in the datastore cache
+		let Some(cache) = ctx.get_cache() else {
+			return Ok(());
+		};
+		// Check if the table tracks record access
+		if !self.tb(ctx, opt).await?.cache {
+			return Ok(());
+		}
+		// Get the record id
+		let rid = self.id()?;
+		// Get NS & DB
+		let (ns, db) = opt.ns_db()?;
+		// Remove the access time of the record when the delete is committed
+		let key = crate::key::thing::new(ns, db, &rid.tb, &rid.id).encode()?;
+		ctx.tx().track_access(cache.forget_record(key));
+		// Carry on
+		Ok(())
+	}
+}
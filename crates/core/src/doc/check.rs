@@ -1,4 +1,4 @@
-use crate::ctx::Context;
+use crate::ctx::{Context, MutableContext};
 use crate::dbs::Options;
 use crate::dbs::Statement;
 use crate::dbs::Workable;
@@ -355,6 +355,8 @@ impl Document {
 					Permission::Specific(e) => {
 						// Disable permissions
 						let opt = &opt.new_with_perms(false);
+						// Hide any subqueries named by WITH statements
+						let ctx = &MutableContext::new_unnamed(ctx).freeze();
 						// Process the PERMISSION clause
 						if !e.compute(stk, ctx, opt, Some(doc)).await?.is_truthy() {
 							return Err(Error::Ignore);
@@ -437,6 +439,8 @@ impl Document {
 					Permission::Specific(e) => {
 						// Disable permissions
 						let opt = &opt.new_with_perms(false);
+						// Hide any subqueries named by WITH statements
+						let ctx = &MutableContext::new_unnamed(ctx).freeze();
 						// Process the PERMISSION clause
 						if !e
 							.compute(
@@ -18,6 +18,7 @@ impl Document {
 		self.check_permissions_quick(stk, ctx, opt, stm).await?;
 		self.check_where_condition(stk, ctx, opt, stm).await?;
 		self.check_permissions_table(stk, ctx, opt, stm).await?;
+		self.process_before_events(stk, ctx, opt, stm).await?;
 		self.cleanup_table_references(stk, ctx, opt).await?;
 		self.clear_record_data(ctx, opt, stm).await?;
 		self.store_index_data(stk, ctx, opt, stm).await?;
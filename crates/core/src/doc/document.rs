@@ -328,7 +328,7 @@ impl Document {
 							// Get the initial value
 							let val = Arc::new(full.doc.as_ref().pick(k));
 							// Configure the context
-							let mut ctx = MutableContext::new(ctx);
+							let mut ctx = MutableContext::new_unnamed(ctx);
 							ctx.add_value("value", val);
 							let ctx = ctx.freeze();
 							// Process the PERMISSION clause
@@ -527,6 +527,39 @@ impl Document {
 		}
 	}
 
+	/// Get the events for the database of this document
+	pub async fn db_ev(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+	) -> Result<Arc<[DefineEventStatement]>, Error> {
+		// Get the NS + DB
+		let (ns, db) = opt.ns_db()?;
+		// Get transaction
+		let txn = ctx.tx();
+		// Get the cache from the context
+		match ctx.get_cache() {
+			// A cache is present on the context
+			Some(cache) if txn.local() => {
+				// Get the cache entry key
+				let key = cache::ds::Lookup::Dbe(ns, db);
+				// Get or update the cache entry
+				match cache.get(&key) {
+					Some(val) => val,
+					None => {
+						let val = txn.all_db_events(ns, db).await?;
+						let val = cache::ds::Entry::Evs(val.clone());
+						cache.insert(key, val.clone());
+						val
+					}
+				}
+			}
+			.try_into_evs(),
+			// No cache is present on the context
+			_ => txn.all_db_events(ns, db).await,
+		}
+	}
+
 	/// Get the fields for this document
 	pub async fn fd(
 		&self,
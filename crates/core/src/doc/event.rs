@@ -3,15 +3,20 @@ use crate::dbs::Options;
 use crate::dbs::Statement;
 use crate::doc::Document;
 use crate::err::Error;
+use crate::kvs::QueuedEvent;
+use crate::sql::statements::define::{DatabaseEvent, TableEvent};
 use crate::sql::value::Value;
 use reblessive::tree::Stk;
+use std::time::Duration;
 
 impl Document {
 	/// Processes any DEFINE EVENT clauses which
 	/// have been defined for the table which this
 	/// record belongs to. This functions loops
 	/// through the events and processes them all
-	/// within the currently running transaction.
+	/// within the currently running transaction,
+	/// apart from any ASYNC events, which are
+	/// queued to be processed in the background.
 	pub(super) async fn process_table_events(
 		&mut self,
 		stk: &mut Stk,
@@ -31,6 +36,10 @@ impl Document {
 		let opt = &opt.new_with_perms(false);
 		// Loop through all event statements
 		for ev in self.ev(ctx, opt).await?.iter() {
+			// Skip events which run before the write
+			if !ev.before.is_empty() {
+				continue;
+			}
 			// Get the event action
 			let evt = if stm.is_delete() {
 				Value::from("DELETE")
@@ -41,6 +50,7 @@ impl Document {
 			};
 			let after = self.current.doc.as_arc();
 			let before = self.initial.doc.as_arc();
+			let id = self.id()?;
 			// Depending on type of event, how do we populate the document
 			let doc = match stm.is_delete() {
 				true => &mut self.initial,
@@ -48,22 +58,200 @@ impl Document {
 			};
 			// Configure the context
 			let mut ctx = MutableContext::new(ctx);
-			ctx.add_value("event", evt.into());
+			ctx.add_value("event", evt.clone().into());
 			ctx.add_value("value", doc.doc.as_arc());
-			ctx.add_value("after", after);
-			ctx.add_value("before", before);
+			ctx.add_value("after", after.clone());
+			ctx.add_value("before", before.clone());
 			// Freeze the context
 			let ctx = ctx.freeze();
 			// Process conditional clause
 			let val = ev.when.compute(stk, &ctx, opt, Some(doc)).await?;
-			// Execute event if value is truthy
-			if val.is_truthy() {
-				for v in ev.then.iter() {
-					v.compute(stk, &ctx, opt, Some(doc)).await?;
+			// Check if the event should be executed
+			if !val.is_truthy() {
+				continue;
+			}
+			// Queue an asynchronous event with this transaction
+			if ev.asynchronous {
+				let (ns, db) = opt.ns_db()?;
+				let qe = QueuedEvent {
+					ns: ns.to_owned(),
+					db: db.to_owned(),
+					tb: ev.what.to_string(),
+					ev: ev.name.to_string(),
+					id: id.as_ref().clone(),
+					event: evt,
+					value: doc.doc.as_ref().clone(),
+					before: before.as_ref().clone(),
+					after: after.as_ref().clone(),
+					attempts: 0,
+					auth: Some(opt.auth.as_ref().clone()),
+					values: Default::default(),
+				}
+				.with_values(&ctx);
+				qe.enqueue(&ctx.tx(), Duration::ZERO).await?;
+				continue;
+			}
+			// Execute the event
+			for v in ev.then.iter() {
+				v.compute(stk, &ctx, opt, Some(doc)).await?;
+			}
+		}
+		// Carry on
+		Ok(())
+	}
+
+	/// Processes any DEFINE EVENT clauses which run
+	/// WHEN BEFORE the record is written, for the
+	/// table which this record belongs to. Any value
+	/// other than NONE which is returned by an event
+	/// replaces the record which is about to be
+	/// written, and any error which is thrown by an
+	/// event aborts the write.
+	pub(super) async fn process_before_events(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		// Check import
+		if opt.import {
+			return Ok(());
+		}
+		// Get the event action
+		let action = if stm.is_delete() {
+			TableEvent::Delete
+		} else if self.is_new() {
+			TableEvent::Create
+		} else {
+			TableEvent::Update
+		};
+		// Get the record id
+		let id = self.id()?;
+		// Don't run permissions
+		let opt = &opt.new_with_perms(false);
+		// Loop through all event statements
+		for ev in self.ev(ctx, opt).await?.iter() {
+			// Check the event runs before this write
+			if !ev.before.contains(&action) {
+				continue;
+			}
+			let after = self.current.doc.as_arc();
+			let before = self.initial.doc.as_arc();
+			// Depending on type of event, how do we populate the document
+			let doc = match stm.is_delete() {
+				true => &mut self.initial,
+				false => &mut self.current,
+			};
+			// Configure the context
+			let mut ctx = MutableContext::new(ctx);
+			ctx.add_value("event", Value::from(action.to_string()).into());
+			ctx.add_value("value", doc.doc.as_arc());
+			ctx.add_value("after", after);
+			ctx.add_value("before", before);
+			// Freeze the context
+			let ctx = ctx.freeze();
+			// Execute the event, keeping the last value
+			let mut val = Value::None;
+			for v in ev.then.iter() {
+				val = v.compute(stk, &ctx, opt, Some(doc)).await?;
+			}
+			// A deleted record can not be replaced
+			if stm.is_delete() {
+				continue;
+			}
+			// Replace the record which is about to be written
+			match val {
+				Value::None => (),
+				Value::Object(_) => {
+					self.current.doc = val.into();
+					self.current.doc.to_mut().def(&id);
+				}
+				val => {
+					return Err(Error::EvInvalidResult {
+						name: ev.name.to_string(),
+						value: val.to_string(),
+					})
 				}
 			}
 		}
 		// Carry on
 		Ok(())
 	}
+
+	/// Checks whether the table which this record
+	/// belongs to is about to be created by this
+	/// write, when any DEFINE EVENT FOR DATABASE
+	/// clauses have been defined. This must run
+	/// before the table definition is fetched, as
+	/// fetching it creates the table automatically.
+	pub(super) async fn check_table_created(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+	) -> Result<bool, Error> {
+		// Check import
+		if opt.import {
+			return Ok(false);
+		}
+		// Check if there are any database events
+		if self.db_ev(ctx, opt).await?.is_empty() {
+			return Ok(false);
+		}
+		// Get the NS + DB
+		let (ns, db) = opt.ns_db()?;
+		// Get the record id
+		let id = self.id()?;
+		// Check if the table already exists
+		match ctx.tx().get_tb(ns, db, &id.tb).await {
+			Err(Error::TbNotFound {
+				..
+			}) => Ok(true),
+			Err(err) => Err(err),
+			Ok(_) => Ok(false),
+		}
+	}
+
+	/// Processes any DEFINE EVENT FOR DATABASE clauses
+	/// which trigger when a table is created. These
+	/// run once the write has passed the permission
+	/// checks, if this record created its table, and
+	/// are processed within the currently running
+	/// transaction.
+	pub(super) async fn process_database_events(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		_stm: &Statement<'_>,
+		created: bool,
+	) -> Result<(), Error> {
+		// Check if the table was created
+		if !created {
+			return Ok(());
+		}
+		// Get the record id
+		let id = self.id()?;
+		// Don't run permissions
+		let opt = &opt.new_with_perms(false);
+		// Loop through all event statements
+		for ev in self.db_ev(ctx, opt).await?.iter() {
+			// Check the event is triggered by table creation
+			if ev.database != Some(DatabaseEvent::TableCreated) {
+				continue;
+			}
+			// Configure the context
+			let mut ctx = MutableContext::new(ctx);
+			ctx.add_value("event", Value::from("TABLE_CREATED").into());
+			ctx.add_value("table", Value::from(id.tb.as_str()).into());
+			// Freeze the context
+			let ctx = ctx.freeze();
+			// Execute the event
+			for v in ev.then.iter() {
+				v.compute(stk, &ctx, opt, None).await?;
+			}
+		}
+		// Carry on
+		Ok(())
+	}
 }
@@ -578,8 +578,12 @@ impl FieldEditContext<'_> {
 					};
 					// Freeze the new context
 					let ctx = ctx.freeze();
-					// Process the PERMISSION clause
-					let res = expr.compute(self.stk, &ctx, opt, doc).await?;
+					// Process the PERMISSION clause, hiding
+					// any subqueries named by WITH statements
+					let res = {
+						let ctx = MutableContext::new_unnamed(&ctx).freeze();
+						expr.compute(self.stk, &ctx, opt, doc).await?
+					};
 					// Unfreeze the new context
 					self.context = Some(MutableContext::unfreeze(ctx)?);
 					// If the specific permissions
@@ -5,11 +5,12 @@ use crate::doc::{CursorDoc, Document};
 use crate::err::Error;
 use crate::idx::ft::FtIndex;
 use crate::idx::trees::mtree::MTreeIndex;
+use crate::idx::trigram::TrigramIndex;
 use crate::idx::IndexKeyBase;
 use crate::key;
 #[cfg(not(target_family = "wasm"))]
 use crate::kvs::ConsumeResult;
-use crate::kvs::TransactionType;
+use crate::kvs::{Key, KeyEncode, TransactionType};
 use crate::sql::array::Array;
 use crate::sql::index::{HnswParams, Index, MTreeParams, SearchParams};
 use crate::sql::statements::DefineIndexStatement;
@@ -94,6 +95,7 @@ impl Document {
 			Index::Search(p) => ic.index_full_text(stk, ctx, p).await?,
 			Index::MTree(p) => ic.index_mtree(stk, ctx, p).await?,
 			Index::Hnsw(p) => ic.index_hnsw(ctx, p).await?,
+			Index::Trigram => ic.index_trigram(ctx).await?,
 		}
 		Ok(())
 	}
@@ -119,6 +121,27 @@ impl Document {
 		}
 		Ok(Some(o))
 	}
+
+	/// Compute the keys under which a record is stored in a unique or non-unique index,
+	/// given the values which were extracted from the record with [`Document::build_opt_values`].
+	pub(crate) fn index_keys(
+		ns: &str,
+		db: &str,
+		ix: &DefineIndexStatement,
+		vals: Vec<Value>,
+		rid: &Thing,
+	) -> Result<Vec<Key>, Error> {
+		let mut keys = Vec::new();
+		for v in Indexable::new(vals, ix) {
+			let key = match ix.index {
+				Index::Uniq if v.is_all_none_or_null() => continue,
+				Index::Uniq => key::index::Index::new(ns, db, &ix.what, &ix.name, &v, None),
+				_ => key::index::Index::new(ns, db, &ix.what, &ix.name, &v, Some(&rid.id)),
+			};
+			keys.push(key.encode()?);
+		}
+		Ok(keys)
+	}
 }
 
 /// Extract from the given document, the values required by the index and put then in an array.
@@ -429,4 +452,10 @@ impl<'a> IndexOperation<'a> {
 		}
 		Ok(())
 	}
+
+	async fn index_trigram(&mut self, ctx: &Context) -> Result<(), Error> {
+		let (ns, db) = self.opt.ns_db()?;
+		let ti = TrigramIndex::new(ns, db, self.ix);
+		ti.index_document(ctx, self.rid, self.o.take(), self.n.take()).await
+	}
 }
@@ -103,15 +103,19 @@ impl Document {
 		opt: &Options,
 		stm: &Statement<'_>,
 	) -> Result<Value, Error> {
+		let created = self.check_table_created(ctx, opt).await?;
 		self.check_permissions_quick(stk, ctx, opt, stm).await?;
 		self.check_table_type(ctx, opt, stm).await?;
 		self.check_data_fields(stk, ctx, opt, stm).await?;
 		self.process_merge_data(stk, ctx, opt, stm).await?;
+		self.check_permissions_table(stk, ctx, opt, stm).await?;
+		self.process_before_events(stk, ctx, opt, stm).await?;
 		self.store_edges_data(ctx, opt, stm).await?;
 		self.process_table_fields(stk, ctx, opt, stm).await?;
 		self.cleanup_table_fields(ctx, opt, stm).await?;
 		self.default_record_data(ctx, opt, stm).await?;
 		self.check_permissions_table(stk, ctx, opt, stm).await?;
+		self.process_database_events(stk, ctx, opt, stm, created).await?;
 		self.store_record_data(ctx, opt, stm).await?;
 		self.store_index_data(stk, ctx, opt, stm).await?;
 		self.process_table_views(stk, ctx, opt, stm).await?;
@@ -134,6 +138,7 @@ impl Document {
 		self.check_data_fields(stk, ctx, opt, stm).await?;
 		self.check_permissions_table(stk, ctx, opt, stm).await?;
 		self.process_record_data(stk, ctx, opt, stm).await?;
+		self.process_before_events(stk, ctx, opt, stm).await?;
 		self.process_table_fields(stk, ctx, opt, stm).await?;
 		self.cleanup_table_fields(ctx, opt, stm).await?;
 		self.default_record_data(ctx, opt, stm).await?;
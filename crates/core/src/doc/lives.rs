@@ -234,6 +234,8 @@ impl Document {
 				Permission::Specific(e) => {
 					// Disable permissions
 					let opt = &opt.new_with_perms(false);
+					// Hide any subqueries named by WITH statements
+					let ctx = &MutableContext::new_unnamed(ctx).freeze();
 					// Process the PERMISSION clause
 					if !e.compute(stk, ctx, opt, Some(doc)).await?.is_truthy() {
 						return Err(Error::Ignore);
@@ -20,6 +20,7 @@ mod select; // Processes a SELECT statement for this document
 mod update; // Processes a UPDATE statement for this document
 mod upsert; // Processes a UPSERT statement for this document
 
+mod access; // Tracks the last access time of this document
 mod alter; // Modifies and updates the fields in this document
 mod changefeeds; // Processes any change feeds relevant for this document
 mod check; // Data and condition checking for this document
@@ -213,7 +213,7 @@ impl Document {
 								// Get the current value
 								let val = Arc::new(self.current.doc.as_ref().pick(k));
 								// Configure the context
-								let mut ctx = MutableContext::new(ctx);
+								let mut ctx = MutableContext::new_unnamed(ctx);
 								ctx.add_value("value", val);
 								let ctx = ctx.freeze();
 								// Process the PERMISSION clause
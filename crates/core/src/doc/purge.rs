@@ -44,8 +44,12 @@ impl Document {
 		if let Some(rid) = &self.id {
 			// Get the namespace / database
 			let (ns, db) = opt.ns_db()?;
+			// Check the table write limit
+			ctx.check_write_limit(ns, db, &rid.tb)?;
 			// Purge the record data
 			txn.del_record(ns, db, &rid.tb, &rid.id).await?;
+			// Purge the record access time
+			self.forget_access(ctx, opt).await?;
 			// Purge the record edges
 			match (
 				self.initial.doc.as_ref().pick(&*EDGE),
@@ -14,6 +14,8 @@ impl Document {
 		opt: &Options,
 		stm: &Statement<'_>,
 	) -> Result<Value, Error> {
+		// Check if this write creates the table
+		let created = self.check_table_created(ctx, opt).await?;
 		// Check if table has correct relation status
 		self.check_table_type(ctx, opt, stm).await?;
 		// Check whether current record exists
@@ -25,7 +27,7 @@ impl Document {
 			// We attempted to RELATE a document with an ID,
 			// which does not exist in the database, or we
 			// are creating a new record with a new ID.
-			false => self.relate_create(stk, ctx, opt, stm).await,
+			false => self.relate_create(stk, ctx, opt, stm, created).await,
 		}
 	}
 	/// Attempt to run a RELATE clause
@@ -35,16 +37,20 @@ impl Document {
 		ctx: &Context,
 		opt: &Options,
 		stm: &Statement<'_>,
+		created: bool,
 	) -> Result<Value, Error> {
 		self.check_permissions_quick(stk, ctx, opt, stm).await?;
 		self.check_table_type(ctx, opt, stm).await?;
 		self.check_data_fields(stk, ctx, opt, stm).await?;
 		self.process_record_data(stk, ctx, opt, stm).await?;
+		self.check_permissions_table(stk, ctx, opt, stm).await?;
+		self.process_before_events(stk, ctx, opt, stm).await?;
 		self.store_edges_data(ctx, opt, stm).await?;
 		self.process_table_fields(stk, ctx, opt, stm).await?;
 		self.cleanup_table_fields(ctx, opt, stm).await?;
 		self.default_record_data(ctx, opt, stm).await?;
 		self.check_permissions_table(stk, ctx, opt, stm).await?;
+		self.process_database_events(stk, ctx, opt, stm, created).await?;
 		self.store_record_data(ctx, opt, stm).await?;
 		self.store_index_data(stk, ctx, opt, stm).await?;
 		self.process_table_views(stk, ctx, opt, stm).await?;
@@ -67,6 +73,7 @@ impl Document {
 		self.check_permissions_table(stk, ctx, opt, stm).await?;
 		self.store_edges_data(ctx, opt, stm).await?;
 		self.process_record_data(stk, ctx, opt, stm).await?;
+		self.process_before_events(stk, ctx, opt, stm).await?;
 		self.process_table_fields(stk, ctx, opt, stm).await?;
 		self.cleanup_table_fields(ctx, opt, stm).await?;
 		self.default_record_data(ctx, opt, stm).await?;
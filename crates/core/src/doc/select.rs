@@ -18,6 +18,7 @@ impl Document {
 		self.check_permissions_quick(stk, ctx, opt, stm).await?;
 		self.check_where_condition(stk, ctx, opt, stm).await?;
 		self.check_permissions_table(stk, ctx, opt, stm).await?;
+		self.track_read(ctx, opt).await?;
 		self.pluck(stk, ctx, opt, stm).await
 	}
 }
@@ -23,6 +23,10 @@ impl Document {
 		let rid = self.id()?;
 		// Get NS & DB
 		let (ns, db) = opt.ns_db()?;
+		// Check the table write limit
+		ctx.check_write_limit(ns, db, &rid.tb)?;
+		// Update the access time of the record
+		self.track_write(ctx, opt).await?;
 		// Store the record data
 		let key = crate::key::thing::new(ns, db, &rid.tb, &rid.id);
 		// Match the statement type
@@ -2,6 +2,7 @@ use crate::api::err::ApiError;
 use crate::iam::Error as IamError;
 use crate::idx::ft::MatchRef;
 use crate::idx::trees::vector::SharedVector;
+use crate::sql::duration::Duration;
 use crate::sql::idiom::Idiom;
 use crate::sql::index::Distance;
 use crate::sql::thing::Thing;
@@ -16,8 +17,9 @@ use fst::Error as FstError;
 use http::header::{InvalidHeaderName, InvalidHeaderValue, ToStrError};
 use jsonwebtoken::errors::Error as JWTError;
 use object_store::Error as ObjectStoreError;
+use revision::revisioned;
 use revision::Error as RevisionError;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::io::Error as IoError;
 use std::string::FromUtf8Error;
 use storekey::decode::Error as DecodeError;
@@ -60,6 +62,27 @@ pub enum Error {
 	#[error("An error occurred: {0}")]
 	Thrown(String),
 
+	/// A joined table has too many records to be joined without an index
+	#[error("The joined table '{table}' has more than {max} records, define an index on the joined field, or increase SURREAL_JOIN_MAX_RECORDS")]
+	JoinTooLarge {
+		table: String,
+		max: usize,
+	},
+
+	/// An asynchronous event asked to be retried after a delay
+	#[error("The event asked to be retried after {0}")]
+	EventRetryAfter(Duration),
+
+	/// A function which can only be run within an asynchronous event was run elsewhere
+	#[error("The function '{name}' can only be run within an ASYNC event")]
+	AsyncEventOnly {
+		name: String,
+	},
+
+	/// An assertion within a test did not hold
+	#[error("Assertion failed: {0}")]
+	AssertionFailed(String),
+
 	/// There was a problem with the underlying datastore
 	#[error("There was a problem with the underlying datastore: {0}")]
 	Ds(String),
@@ -195,6 +218,12 @@ pub enum Error {
 		value: String,
 	},
 
+	/// The BATCH clause must evaluate to a positive integer
+	#[error("Found {value} but the BATCH clause must evaluate to a positive integer")]
+	InvalidBatch {
+		value: String,
+	},
+
 	/// The START clause must evaluate to a positive integer
 	#[error("Found {value} but the START clause must evaluate to a positive integer")]
 	InvalidStart {
@@ -281,10 +310,30 @@ pub enum Error {
 	#[error("Invalid timeout: {0:?} seconds")]
 	InvalidTimeout(u64),
 
+	/// Invalid write durability
+	#[error("Invalid durability '{0}', expected 'memory', 'applied' or 'replicated'")]
+	InvalidDurability(String),
+
+	/// The storage engine can not guarantee the requested write durability
+	#[error("The storage engine can not guarantee the '{0}' durability")]
+	DurabilityUnsupported(String),
+
 	/// The query timedout
 	#[error("The query was not executed because it exceeded the timeout")]
 	QueryTimedout,
 
+	/// Records were evicted from a table which does not track record access
+	#[error("The table '{name}' does not track record access. Define it with the CACHE clause to evict records from it")]
+	EvictNonCacheTable {
+		name: String,
+	},
+
+	/// A write was rejected because the write rate limit of the table was exceeded
+	#[error("The write rate limit for table '{tb}' has been exceeded. This write can be retried")]
+	WriteThrottled {
+		tb: String,
+	},
+
 	/// The query did not execute, because the transaction was cancelled
 	#[error("The query was not executed due to a cancelled transaction")]
 	QueryCancelled,
@@ -293,6 +342,12 @@ pub enum Error {
 	#[error("The query was not executed due to a failed transaction")]
 	QueryNotExecuted,
 
+	/// A `SELECT DISTINCT` statement found more distinct rows than can be held in memory
+	#[error("The query found more than {limit} distinct rows, which is the most that SELECT DISTINCT can hold in memory")]
+	DistinctRowsLimit {
+		limit: usize,
+	},
+
 	/// The query did not execute, because the transaction has failed (with a message)
 	#[error("The query was not executed due to a failed transaction. {message}")]
 	QueryNotExecutedDetail {
@@ -341,6 +396,13 @@ pub enum Error {
 		name: String,
 	},
 
+	/// A BEFORE event returned a value which can not replace the record
+	#[error("The event '{name}' must return an object to replace the record, or NONE to keep it, but found '{value}'")]
+	EvInvalidResult {
+		name: String,
+		value: String,
+	},
+
 	/// The requested function does not exist
 	#[error("The function 'fn::{name}' does not exist")]
 	FcNotFound {
@@ -383,6 +445,12 @@ pub enum Error {
 		name: String,
 	},
 
+	/// The requested test does not exist
+	#[error("The test '{name}' does not exist")]
+	TtNotFound {
+		name: String,
+	},
+
 	/// The requested live query does not exist
 	#[error("The live query '{name}' does not exist")]
 	LvNotFound {
@@ -457,6 +525,28 @@ pub enum Error {
 		value: String,
 	},
 
+	/// A FOR statement which commits each iteration was run within another statement or transaction
+	#[error("A FOR statement with COMMIT EACH can only be run as a top-level statement outside of a transaction")]
+	ForeachCommitEach,
+
+	/// A FOR statement which commits each iteration failed after some iterations were committed
+	#[error("The FOR statement was stopped after committing the iterations for {committed} values, up to and including {last}: {message}")]
+	ForeachInterrupted {
+		committed: usize,
+		last: String,
+		message: String,
+	},
+
+	/// A RUN TEST statement was run within another statement or transaction
+	#[error(
+		"A RUN TEST statement can only be run as a top-level statement outside of a transaction"
+	)]
+	RunTestInTransaction,
+
+	/// A CHECK DATABASE statement was run within another statement or transaction
+	#[error("A CHECK DATABASE statement can only be run as a top-level statement outside of a transaction")]
+	CheckInTransaction,
+
 	/// Can not execute CREATE statement using the specified value
 	#[error("Can not execute CREATE statement using value: {value}")]
 	CreateStatement {
@@ -848,6 +938,10 @@ pub enum Error {
 	#[error("Access to network target '{0}' is not allowed")]
 	NetTargetNotAllowed(String),
 
+	/// Environment variable is not allowed
+	#[error("Access to environment variable '{0}' is not allowed")]
+	EnvTargetNotAllowed(String),
+
 	//
 	// Authentication / Signup
 	//
@@ -946,6 +1040,15 @@ pub enum Error {
 		name: String,
 	},
 
+	/// The default value of a function argument does not match the type of the argument
+	#[error("The default value of the argument '${arg}' of the function 'fn::{name}' must be of type {kind}, but found {value}")]
+	FcInvalidDefault {
+		name: String,
+		arg: String,
+		kind: String,
+		value: String,
+	},
+
 	/// The requested index already exists
 	#[error("The index '{name}' already exists")]
 	IxAlreadyExists {
@@ -982,6 +1085,19 @@ pub enum Error {
 		name: String,
 	},
 
+	/// The requested test already exists
+	#[error("The test '{name}' already exists")]
+	TtAlreadyExists {
+		name: String,
+	},
+
+	/// The table can not be safely removed, as other definitions reference it
+	#[error("The table '{name}' can not be safely removed, as it is referenced by {references}")]
+	TbReferenced {
+		name: String,
+		references: String,
+	},
+
 	/// The requested namespace token already exists
 	#[error("The namespace token '{name}' already exists")]
 	NtAlreadyExists {
@@ -1493,4 +1609,622 @@ impl Error {
 			e => e,
 		}
 	}
+
+	/// Returns a stable code identifying the kind of this error
+	///
+	/// Unlike the error message, the code does not change between releases, so it can be
+	/// matched on by clients. Errors which do not have a dedicated code are reported as
+	/// `database`.
+	pub fn code(&self) -> &'static str {
+		match self {
+			Error::Thrown(_) => "thrown",
+			Error::InvalidQuery(_) => "parse_error",
+			Error::TxRetryable => "transaction_retryable",
+			Error::TxConditionNotMet => "transaction_condition_not_met",
+			Error::TxKeyAlreadyExists => "transaction_key_already_exists",
+			Error::QueryTimedout => "query_timedout",
+			Error::QueryCancelled => "query_cancelled",
+			Error::QueryNotExecuted => "query_not_executed",
+			Error::NsNotFound {
+				..
+			} => "namespace_not_found",
+			Error::DbNotFound {
+				..
+			} => "database_not_found",
+			Error::TbNotFound {
+				..
+			} => "table_not_found",
+			Error::IxNotFound {
+				..
+			} => "index_not_found",
+			Error::IdNotFound {
+				..
+			} => "record_not_found",
+			Error::TbAlreadyExists {
+				..
+			} => "table_already_exists",
+			Error::IxAlreadyExists {
+				..
+			} => "index_already_exists",
+			Error::RecordExists {
+				..
+			} => "record_exists",
+			Error::IndexExists {
+				..
+			} => "index_exists",
+			Error::FieldCheck {
+				..
+			} => "field_check",
+			Error::FieldValue {
+				..
+			} => "field_value",
+			Error::WriteThrottled {
+				..
+			} => "write_throttled",
+			Error::InvalidAuth => "invalid_auth",
+			Error::ExpiredToken => "expired_token",
+			Error::ExpiredSession => "expired_session",
+			Error::IamError(_) => "not_allowed",
+			// This match is exhaustive, so that every new error is given a code
+			Error::Ignore
+			| Error::Break
+			| Error::Continue
+			| Error::RetryWithId(..)
+			| Error::Unreachable(..)
+			| Error::Deprecated(..)
+			| Error::JoinTooLarge {
+				..
+			}
+			| Error::EventRetryAfter(..)
+			| Error::AsyncEventOnly {
+				..
+			}
+			| Error::AssertionFailed(..)
+			| Error::Ds(..)
+			| Error::Tx(..)
+			| Error::TxFailure
+			| Error::TxFinished
+			| Error::TxReadonly
+			| Error::TxKeyTooLarge
+			| Error::TxValueTooLarge
+			| Error::TxTooLarge
+			| Error::NsEmpty
+			| Error::DbEmpty
+			| Error::QueryEmpty
+			| Error::QueryRemaining
+			| Error::InvalidContent {
+				..
+			}
+			| Error::InvalidMerge {
+				..
+			}
+			| Error::InvalidPatch {
+				..
+			}
+			| Error::PatchTest {
+				..
+			}
+			| Error::HttpDisabled
+			| Error::InvalidParam {
+				..
+			}
+			| Error::InvalidField {
+				..
+			}
+			| Error::InvalidFetch {
+				..
+			}
+			| Error::InvalidSplit {
+				..
+			}
+			| Error::InvalidOrder {
+				..
+			}
+			| Error::InvalidGroup {
+				..
+			}
+			| Error::InvalidLimit {
+				..
+			}
+			| Error::InvalidBatch {
+				..
+			}
+			| Error::InvalidStart {
+				..
+			}
+			| Error::InvalidScript {
+				..
+			}
+			| Error::InvalidModel {
+				..
+			}
+			| Error::InvalidFunction {
+				..
+			}
+			| Error::InvalidArguments {
+				..
+			}
+			| Error::InvalidAggregation {
+				..
+			}
+			| Error::FunctionCheck {
+				..
+			}
+			| Error::InvalidUrl(..)
+			| Error::InvalidVectorDimension {
+				..
+			}
+			| Error::InvalidVectorDistance {
+				..
+			}
+			| Error::InvalidVectorType {
+				..
+			}
+			| Error::InvalidVectorValue(..)
+			| Error::InvalidRegex(..)
+			| Error::InvalidTimeout(..)
+			| Error::InvalidDurability(..)
+			| Error::DurabilityUnsupported(..)
+			| Error::EvictNonCacheTable {
+				..
+			}
+			| Error::DistinctRowsLimit {
+				..
+			}
+			| Error::QueryNotExecutedDetail {
+				..
+			}
+			| Error::NsNotAllowed {
+				..
+			}
+			| Error::DbNotAllowed {
+				..
+			}
+			| Error::NlNotFound {
+				..
+			}
+			| Error::DlNotFound {
+				..
+			}
+			| Error::EvNotFound {
+				..
+			}
+			| Error::EvInvalidResult {
+				..
+			}
+			| Error::FcNotFound {
+				..
+			}
+			| Error::FdNotFound {
+				..
+			}
+			| Error::MlNotFound {
+				..
+			}
+			| Error::NdNotFound {
+				..
+			}
+			| Error::PaNotFound {
+				..
+			}
+			| Error::CgNotFound {
+				..
+			}
+			| Error::TtNotFound {
+				..
+			}
+			| Error::LvNotFound {
+				..
+			}
+			| Error::LqNotFound {
+				..
+			}
+			| Error::ApNotFound {
+				..
+			}
+			| Error::AzNotFound {
+				..
+			}
+			| Error::UnsupportedDistance(..)
+			| Error::UserRootNotFound {
+				..
+			}
+			| Error::UserNsNotFound {
+				..
+			}
+			| Error::UserDbNotFound {
+				..
+			}
+			| Error::RealtimeDisabled
+			| Error::ComputationDepthExceeded
+			| Error::InvalidStatementTarget {
+				..
+			}
+			| Error::ForeachCommitEach
+			| Error::ForeachInterrupted {
+				..
+			}
+			| Error::RunTestInTransaction
+			| Error::CheckInTransaction
+			| Error::CreateStatement {
+				..
+			}
+			| Error::UpsertStatement {
+				..
+			}
+			| Error::UpdateStatement {
+				..
+			}
+			| Error::RelateStatement {
+				..
+			}
+			| Error::RelateStatementIn {
+				..
+			}
+			| Error::RelateStatementId {
+				..
+			}
+			| Error::RelateStatementOut {
+				..
+			}
+			| Error::DeleteStatement {
+				..
+			}
+			| Error::InsertStatement {
+				..
+			}
+			| Error::InsertStatementIn {
+				..
+			}
+			| Error::InsertStatementId {
+				..
+			}
+			| Error::InsertStatementOut {
+				..
+			}
+			| Error::LiveStatement {
+				..
+			}
+			| Error::KillStatement {
+				..
+			}
+			| Error::SingleOnlyOutput
+			| Error::TablePermissions {
+				..
+			}
+			| Error::ParamPermissions {
+				..
+			}
+			| Error::FunctionPermissions {
+				..
+			}
+			| Error::TableIsView {
+				..
+			}
+			| Error::TableCheck {
+				..
+			}
+			| Error::SetCheck {
+				..
+			}
+			| Error::FieldReadonly {
+				..
+			}
+			| Error::FieldUndefined {
+				..
+			}
+			| Error::IdInvalid {
+				..
+			}
+			| Error::IdMismatch {
+				..
+			}
+			| Error::InInvalid {
+				..
+			}
+			| Error::InMismatch {
+				..
+			}
+			| Error::InOverride {
+				..
+			}
+			| Error::OutInvalid {
+				..
+			}
+			| Error::OutMismatch {
+				..
+			}
+			| Error::OutOverride {
+				..
+			}
+			| Error::CoerceTo {
+				..
+			}
+			| Error::ConvertTo {
+				..
+			}
+			| Error::LengthInvalid {
+				..
+			}
+			| Error::TryAdd(..)
+			| Error::TrySub(..)
+			| Error::TryMul(..)
+			| Error::TryDiv(..)
+			| Error::TryRem(..)
+			| Error::TryPow(..)
+			| Error::TryNeg(..)
+			| Error::TryFrom(..)
+			| Error::Http(..)
+			| Error::Channel(..)
+			| Error::Io(..)
+			| Error::Encode(..)
+			| Error::Decode(..)
+			| Error::Revision(..)
+			| Error::CorruptedIndex(..)
+			| Error::NoIndexFoundForMatch {
+				..
+			}
+			| Error::AnalyzerError(..)
+			| Error::HighlightError(..)
+			| Error::Bincode(..)
+			| Error::FstError(..)
+			| Error::Utf8Error(..)
+			| Error::ObsError(..)
+			| Error::ModelComputation(..)
+			| Error::FeatureNotYetImplemented {
+				..
+			}
+			| Error::DuplicatedMatchRef {
+				..
+			}
+			| Error::TimestampOverflow(..)
+			| Error::Internal(..)
+			| Error::Unimplemented(..)
+			| Error::CorruptedVersionstampInKey(..)
+			| Error::InvalidLevel(..)
+			| Error::ScriptingNotAllowed
+			| Error::FunctionNotAllowed(..)
+			| Error::NetTargetNotAllowed(..)
+			| Error::EnvTargetNotAllowed(..)
+			| Error::TokenMakingFailed
+			| Error::NoRecordFound
+			| Error::SignupQueryFailed
+			| Error::SigninQueryFailed
+			| Error::MissingUserOrPass
+			| Error::NoSigninTarget
+			| Error::InvalidPass
+			| Error::UnexpectedAuth
+			| Error::InvalidSignup
+			| Error::UnknownAuth
+			| Error::MissingTokenHeader(..)
+			| Error::MissingTokenClaim(..)
+			| Error::MissingStorageEngine
+			| Error::ClAlreadyExists {
+				..
+			}
+			| Error::ApAlreadyExists {
+				..
+			}
+			| Error::AzAlreadyExists {
+				..
+			}
+			| Error::DbAlreadyExists {
+				..
+			}
+			| Error::EvAlreadyExists {
+				..
+			}
+			| Error::FdAlreadyExists {
+				..
+			}
+			| Error::FcAlreadyExists {
+				..
+			}
+			| Error::FcInvalidDefault {
+				..
+			}
+			| Error::MlAlreadyExists {
+				..
+			}
+			| Error::NsAlreadyExists {
+				..
+			}
+			| Error::PaAlreadyExists {
+				..
+			}
+			| Error::CgAlreadyExists {
+				..
+			}
+			| Error::TtAlreadyExists {
+				..
+			}
+			| Error::TbReferenced {
+				..
+			}
+			| Error::NtAlreadyExists {
+				..
+			}
+			| Error::DtAlreadyExists {
+				..
+			}
+			| Error::UserRootAlreadyExists {
+				..
+			}
+			| Error::UserNsAlreadyExists {
+				..
+			}
+			| Error::UserDbAlreadyExists {
+				..
+			}
+			| Error::IndexAlreadyBuilding {
+				..
+			}
+			| Error::NodeAgent(..)
+			| Error::Serialization(..)
+			| Error::AccessRootAlreadyExists {
+				..
+			}
+			| Error::AccessNsAlreadyExists {
+				..
+			}
+			| Error::AccessDbAlreadyExists {
+				..
+			}
+			| Error::AccessRootNotFound {
+				..
+			}
+			| Error::AccessGrantRootNotFound {
+				..
+			}
+			| Error::AccessNsNotFound {
+				..
+			}
+			| Error::AccessGrantNsNotFound {
+				..
+			}
+			| Error::AccessDbNotFound {
+				..
+			}
+			| Error::AccessGrantDbNotFound {
+				..
+			}
+			| Error::AccessLevelMismatch
+			| Error::AccessMethodMismatch
+			| Error::AccessNotFound
+			| Error::AccessInvalidDuration
+			| Error::AccessInvalidExpiration
+			| Error::AccessRecordSignupQueryFailed
+			| Error::AccessRecordSigninQueryFailed
+			| Error::AccessRecordNoSignup
+			| Error::AccessRecordNoSignin
+			| Error::AccessBearerMissingKey
+			| Error::AccessGrantBearerInvalid
+			| Error::AccessGrantInvalidSubject
+			| Error::AccessGrantRevoked
+			| Error::TbInvalid {
+				..
+			}
+			| Error::Return {
+				..
+			}
+			| Error::UnsupportedDestructure {
+				..
+			}
+			| Error::UnsupportedVersionedQueries
+			| Error::InvalidRangeValue {
+				..
+			}
+			| Error::RangeTooBig {
+				..
+			}
+			| Error::InvalidStorageVersion
+			| Error::OutdatedStorageVersion
+			| Error::NonComputed
+			| Error::QueryTooLarge
+			| Error::ArithmeticOverflow(..)
+			| Error::ArithmeticNegativeOverflow(..)
+			| Error::InsufficientReserve(..)
+			| Error::QueryStream(..)
+			| Error::OrderingError(..)
+			| Error::InvalidExportConfig(..)
+			| Error::InvalidBound {
+				..
+			}
+			| Error::IdiomRecursionLimitExceeded {
+				..
+			}
+			| Error::RepeatRecurseNotRecursing
+			| Error::IdiomRecursionAlreadyRecursing {
+				..
+			}
+			| Error::UnsupportedRepeatRecurse
+			| Error::InvalidVersion {
+				..
+			}
+			| Error::RecursionInstructionPlanConflict
+			| Error::DeleteRejectedByReference(..)
+			| Error::ReferenceTypeConflict(..)
+			| Error::RefsTypeConflict(..)
+			| Error::RefsMismatchingVariants
+			| Error::RefsUpdateFailure(..)
+			| Error::InvalidRefsContext
+			| Error::MismatchedFieldTypes {
+				..
+			}
+			| Error::ApiError(..)
+			| Error::InvalidBytesize
+			| Error::InvalidPath(..) => "database",
+		}
+	}
+
+	/// Returns the code, and the table, record, and index which this error relates to
+	pub fn details(&self) -> ErrorDetails {
+		let mut details = ErrorDetails {
+			code: self.code().to_owned(),
+			..Default::default()
+		};
+		match self {
+			Error::TbNotFound {
+				name,
+			}
+			| Error::TbAlreadyExists {
+				name,
+			}
+			| Error::WriteThrottled {
+				tb: name,
+			} => details.table = Some(name.clone()),
+			Error::IxNotFound {
+				name,
+			}
+			| Error::IxAlreadyExists {
+				name,
+			} => details.index = Some(name.clone()),
+			Error::IdNotFound {
+				rid,
+			} => details.record = Some(rid.clone()),
+			Error::RecordExists {
+				thing,
+			} => {
+				details.table = Some(thing.tb.clone());
+				details.record = Some(thing.to_string());
+			}
+			Error::IndexExists {
+				thing,
+				index,
+				..
+			} => {
+				details.table = Some(thing.tb.clone());
+				details.record = Some(thing.to_string());
+				details.index = Some(index.clone());
+			}
+			Error::FieldCheck {
+				thing,
+				..
+			}
+			| Error::FieldValue {
+				thing,
+				..
+			} => details.record = Some(thing.clone()),
+			_ => {}
+		}
+		details
+	}
+}
+
+/// The machine readable details of an error, which are sent to clients alongside the message
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ErrorDetails {
+	/// A stable code identifying the kind of error
+	pub code: String,
+	/// The table the error relates to
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub table: Option<String>,
+	/// The record ID the error relates to
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub record: Option<String>,
+	/// The index the error relates to
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub index: Option<String>,
 }
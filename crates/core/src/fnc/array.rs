@@ -159,9 +159,9 @@ pub fn boolean_xor((lh, rh): (Array, Array)) -> Result<Value, Error> {
 	Ok(results.into())
 }
 
-pub fn clump((array, clump_size): (Array, i64)) -> Result<Value, Error> {
+pub fn clump(name: &str, (array, clump_size): (Array, i64)) -> Result<Value, Error> {
 	let clump_size = clump_size.max(0) as usize;
-	Ok(array.clump(clump_size)?.into())
+	Ok(array.clump(name, clump_size)?.into())
 }
 
 pub fn combine((array, other): (Array, Array)) -> Result<Value, Error> {
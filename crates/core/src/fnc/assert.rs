@@ -0,0 +1,43 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::sql::value::Value;
+use crate::sql::Closure;
+use crate::sql::Function;
+use reblessive::tree::Stk;
+
+pub fn equal((actual, expected): (Value, Value)) -> Result<Value, Error> {
+	if actual == expected {
+		Ok(Value::None)
+	} else {
+		Err(Error::AssertionFailed(format!("expected {expected} but found {actual}")))
+	}
+}
+
+pub async fn throws(
+	(stk, ctx, opt, doc): (&mut Stk, &Context, Option<&Options>, Option<&CursorDoc>),
+	(closure, expected): (Closure, Option<String>),
+) -> Result<Value, Error> {
+	if let Some(opt) = opt {
+		let fnc = Function::Anonymous(closure.into(), vec![], true);
+		let found = match fnc.compute(stk, ctx, opt, doc).await {
+			Ok(_) => {
+				return Err(Error::AssertionFailed(
+					"expected the function to throw an error".to_string(),
+				))
+			}
+			// Compare thrown errors by their message alone
+			Err(Error::Thrown(v)) => v,
+			Err(e) => e.to_string(),
+		};
+		match expected {
+			Some(expected) if expected != found => Err(Error::AssertionFailed(format!(
+				"expected the error '{expected}' but found '{found}'"
+			))),
+			_ => Ok(found.into()),
+		}
+	} else {
+		Ok(Value::None)
+	}
+}
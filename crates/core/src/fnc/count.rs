@@ -1,4 +1,5 @@
 use crate::err::Error;
+use crate::fnc::util::math::approx_distinct::ApproxDistinct;
 use crate::sql::value::Value;
 
 pub fn count((arg,): (Option<Value>,)) -> Result<Value, Error> {
@@ -9,3 +10,9 @@ pub fn count((arg,): (Option<Value>,)) -> Result<Value, Error> {
 		})
 		.unwrap_or_else(|| 1.into()))
 }
+
+pub fn approx_distinct((array,): (Vec<Value>,)) -> Result<Value, Error> {
+	let mut sketch = ApproxDistinct::default();
+	array.iter().for_each(|v| sketch.push(v));
+	Ok(sketch.estimate().into())
+}
@@ -1,4 +1,5 @@
 use crate::err::Error;
+use crate::fnc::util::geo::crs::{self, Crs};
 use crate::sql::geometry::Geometry;
 use crate::sql::value::Value;
 use geo::algorithm::bearing::HaversineBearing;
@@ -49,6 +50,21 @@ pub fn distance((v, w): (Geometry, Geometry)) -> Result<Value, Error> {
 	})
 }
 
+pub fn transform((arg, to, from): (Geometry, String, Option<String>)) -> Result<Value, Error> {
+	let parse = |v: &str| {
+		Crs::parse(v).ok_or_else(|| Error::InvalidArguments {
+			name: String::from("geo::transform"),
+			message: format!("Unsupported coordinate reference system '{v}'. Supported systems are EPSG:4326, EPSG:3857, and the WGS84 UTM zones EPSG:32601-32660 and EPSG:32701-32760."),
+		})
+	};
+	let to = parse(&to)?;
+	let from = match from {
+		Some(from) => parse(&from)?,
+		None => Crs::Wgs84,
+	};
+	Ok(crs::transform(arg, from, to).into())
+}
+
 pub mod hash {
 
 	use crate::err::Error;
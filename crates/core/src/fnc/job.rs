@@ -0,0 +1,14 @@
+use crate::ctx::Context;
+use crate::err::Error;
+use crate::sql::{Duration, Value};
+
+/// Fails the running asynchronous event, so that it is retried after the given delay
+pub fn retry_after(ctx: &Context, (delay,): (Duration,)) -> Result<Value, Error> {
+	// Check this is running within an asynchronous event
+	if !ctx.is_asynchronous() {
+		return Err(Error::AsyncEventOnly {
+			name: String::from("job::retry_after"),
+		});
+	}
+	Err(Error::EventRetryAfter(delay))
+}
@@ -1,4 +1,5 @@
 use crate::err::Error;
+use crate::fnc::util::math::approx_percentile::ApproxPercentile;
 use crate::fnc::util::math::bottom::Bottom;
 use crate::fnc::util::math::deviation::Deviation;
 use crate::fnc::util::math::interquartile::Interquartile;
@@ -30,6 +31,12 @@ pub fn acot((arg,): (Number,)) -> Result<Value, Error> {
 	Ok(arg.acot().into())
 }
 
+pub fn approx_percentile((array, n): (Vec<Number>, Number)) -> Result<Value, Error> {
+	let mut sketch = ApproxPercentile::default();
+	array.iter().for_each(|v| sketch.push(v));
+	Ok(sketch.percentile(n).into())
+}
+
 pub fn asin((arg,): (Number,)) -> Result<Value, Error> {
 	Ok(arg.asin().into())
 }
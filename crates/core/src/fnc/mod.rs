@@ -11,6 +11,7 @@ use reblessive::tree::Stk;
 pub mod api;
 pub mod args;
 pub mod array;
+pub mod assert;
 pub mod bytes;
 pub mod count;
 pub mod crypto;
@@ -18,6 +19,7 @@ pub mod duration;
 pub mod encoding;
 pub mod geo;
 pub mod http;
+pub mod job;
 pub mod math;
 pub mod not;
 pub mod object;
@@ -30,6 +32,7 @@ pub mod search;
 pub mod session;
 pub mod sleep;
 pub mod string;
+pub mod sys;
 pub mod time;
 pub mod r#type;
 pub mod util;
@@ -46,6 +49,7 @@ pub async fn run(
 	args: Vec<Value>,
 ) -> Result<Value, Error> {
 	if name.eq("sleep")
+		|| name.eq("sys::sleep")
 		|| name.eq("api::invoke")
 		|| name.eq("array::all")
 		|| name.eq("array::any")
@@ -60,6 +64,7 @@ pub async fn run(
 		|| name.eq("array::map")
 		|| name.eq("array::reduce")
 		|| name.eq("array::some")
+		|| name.eq("assert::throws")
 		|| name.eq("record::exists")
 		|| name.eq("record::refs")
 		|| name.eq("type::field")
@@ -123,7 +128,9 @@ pub fn synchronous(
 		"array::boolean_not" => array::boolean_not,
 		"array::boolean_or" => array::boolean_or,
 		"array::boolean_xor" => array::boolean_xor,
-		"array::clump" => array::clump,
+		// An alias of array::clump
+		"array::chunk" => array::clump("array::chunk"),
+		"array::clump" => array::clump("array::clump"),
 		"array::combine" => array::combine,
 		"array::complement" => array::complement,
 		"array::concat" => array::concat,
@@ -162,9 +169,12 @@ pub fn synchronous(
 		"array::sort::desc" => array::sort::desc,
 		"array::windows" => array::windows,
 		//
+		"assert::equal" => assert::equal,
+		//
 		"bytes::len" => bytes::len,
 		//
 		"count" => count::count,
+		"count::approx_distinct" => count::approx_distinct,
 		//
 		"crypto::blake3" => crypto::blake3,
 		"crypto::md5" => crypto::md5,
@@ -200,10 +210,14 @@ pub fn synchronous(
 		"geo::hash::decode" => geo::hash::decode,
 		"geo::hash::encode" => geo::hash::encode,
 		"geo::is::valid" => geo::is::valid,
+		"geo::transform" => geo::transform,
+		//
+		"job::retry_after" => job::retry_after(ctx),
 		//
 		"math::abs" => math::abs,
 		"math::acos" => math::acos,
 		"math::acot" => math::acot,
+		"math::approx_percentile" => math::approx_percentile,
 		"math::asin" => math::asin,
 		"math::atan" => math::atan,
 		"math::bottom" => math::bottom,
@@ -338,11 +352,15 @@ pub fn synchronous(
 		"string::is::uuid" => string::is::uuid,
 		"string::is::record" => string::is::record,
 		//
+		"string::regex::captures" => string::regex::captures,
+		"string::regex::replace_all" => string::regex::replace_all,
+		//
 		"string::similarity::fuzzy" => string::similarity::fuzzy,
 		"string::similarity::jaro" => string::similarity::jaro,
 		"string::similarity::jaro_winkler" => string::similarity::jaro_winkler,
 		"string::similarity::smithwaterman" => string::similarity::smithwaterman,
 		"string::similarity::sorensen_dice" => string::similarity::sorensen_dice,
+		"string::similarity::trigram" => string::similarity::trigram,
 		//
 		"string::semver::compare" => string::semver::compare,
 		"string::semver::major" => string::semver::major,
@@ -355,6 +373,10 @@ pub fn synchronous(
 		"string::semver::set::minor" => string::semver::set::minor,
 		"string::semver::set::patch" => string::semver::set::patch,
 		//
+		"sys::env" => sys::env(ctx),
+		"sys::hostname" => sys::hostname(ctx),
+		//
+		"time::bucket" => time::bucket,
 		"time::ceil" => time::ceil,
 		"time::day" => time::day,
 		"time::floor" => time::floor,
@@ -499,6 +521,8 @@ pub async fn asynchronous(
 		"array::reduce" => array::reduce((stk, ctx, Some(opt), doc)).await,
 		"array::some" => array::any((stk, ctx, Some(opt), doc)).await,
 		//
+		"assert::throws" => assert::throws((stk, ctx, Some(opt), doc)).await,
+		//
 		"crypto::argon2::compare" => (cpu_intensive) crypto::argon2::cmp.await,
 		"crypto::argon2::generate" => (cpu_intensive) crypto::argon2::gen.await,
 		"crypto::bcrypt::compare" => (cpu_intensive) crypto::bcrypt::cmp.await,
@@ -520,11 +544,14 @@ pub async fn asynchronous(
 		//
 		"search::analyze" => search::analyze((stk, ctx, Some(opt))).await,
 		"search::score" => search::score((ctx, doc)).await,
+		"search::similarity" => search::similarity((ctx, doc)).await,
 		"search::highlight" => search::highlight((ctx, doc)).await,
 		"search::offsets" => search::offsets((ctx, doc)).await,
 		//
 		"sleep" => sleep::sleep(ctx).await,
 		//
+		"sys::sleep" => sys::sleep(ctx).await,
+		//
 		"type::field" => r#type::field((stk, ctx, Some(opt), doc)).await,
 		"type::fields" => r#type::fields((stk, ctx, Some(opt), doc)).await,
 		//
@@ -560,7 +587,9 @@ pub async fn idiom(
 				"boolean_not" => array::boolean_not,
 				"boolean_or" => array::boolean_or,
 				"boolean_xor" => array::boolean_xor,
-				"clump" => array::clump,
+				// An alias of array::clump
+				"chunk" => array::clump("array::chunk"),
+				"clump" => array::clump("array::clump"),
 				"combine" => array::combine,
 				"complement" => array::complement,
 				"concat" => array::concat,
@@ -671,6 +700,7 @@ pub async fn idiom(
 				"hash_decode" => geo::hash::decode,
 				"hash_encode" => geo::hash::encode,
 				"is_valid" => geo::is::valid,
+				"transform" => geo::transform,
 			)
 		}
 		Value::Thing(_) => {
@@ -774,11 +804,14 @@ pub async fn idiom(
 				"is_ulid" => string::is::ulid,
 				"is_uuid" => string::is::uuid,
 				"is_record" => string::is::record,
+				"regex_captures" => string::regex::captures,
+				"regex_replace_all" => string::regex::replace_all,
 				"similarity_fuzzy" => string::similarity::fuzzy,
 				"similarity_jaro" => string::similarity::jaro,
 				"similarity_jaro_winkler" => string::similarity::jaro_winkler,
 				"similarity_smithwaterman" => string::similarity::smithwaterman,
 				"similarity_sorensen_dice" => string::similarity::sorensen_dice,
+				"similarity_trigram" => string::similarity::trigram,
 				"semver_compare" => string::semver::compare,
 				"semver_major" => string::semver::major,
 				"semver_minor" => string::semver::minor,
@@ -797,6 +830,7 @@ pub async fn idiom(
 				args.clone(),
 				"no such method found for the datetime type",
 				//
+				"bucket" => time::bucket,
 				"ceil" => time::ceil,
 				"day" => time::day,
 				"floor" => time::floor,
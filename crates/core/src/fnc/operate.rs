@@ -3,6 +3,7 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::idx::planner::executor::QueryExecutor;
+use crate::idx::trigram;
 use crate::sql::value::TryRem;
 use crate::sql::value::{TryAdd, TryDiv, TryMul, TryNeg, TryPow, TrySub, Value};
 use crate::sql::{Expression, Thing};
@@ -96,6 +97,16 @@ pub fn not_like(a: &Value, b: &Value) -> Result<Value, Error> {
 	Ok((!a.fuzzy(b)).into())
 }
 
+pub fn similar(a: &Value, b: &Value) -> Result<Value, Error> {
+	Ok(match (a, b) {
+		(Value::Strand(a), Value::Strand(b)) => {
+			trigram::similarity(a, b) >= trigram::SIMILARITY_THRESHOLD
+		}
+		_ => false,
+	}
+	.into())
+}
+
 pub fn all_like(a: &Value, b: &Value) -> Result<Value, Error> {
 	Ok(a.all_fuzzy(b).into())
 }
@@ -19,6 +19,7 @@ impl_module_def!(
 	"boolean_not" => run,
 	"boolean_or" => run,
 	"boolean_xor" => run,
+	"chunk" => run,
 	"clump" => run,
 	"combine" => run,
 	"complement" => run,
@@ -0,0 +1,14 @@
+use super::fut;
+use super::run;
+use crate::fnc::script::modules::impl_module_def;
+use js::prelude::Async;
+
+#[non_exhaustive]
+pub struct Package;
+
+impl_module_def!(
+	Package,
+	"assert",
+	"equal" => run,
+	"throws" => fut Async
+);
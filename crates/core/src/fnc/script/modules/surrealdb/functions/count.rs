@@ -0,0 +1,27 @@
+use js::{prelude::Rest, Ctx};
+
+use super::run;
+use crate::sql::value::Value;
+
+#[non_exhaustive]
+pub struct Package;
+
+impl js::module::ModuleDef for Package {
+	fn declare(decls: &js::module::Declarations) -> js::Result<()> {
+		decls.declare("default")?;
+		decls.declare("approx_distinct")?;
+		Ok(())
+	}
+	fn evaluate<'js>(ctx: &js::Ctx<'js>, exports: &js::module::Exports<'js>) -> js::Result<()> {
+		let default = js::Function::new(ctx.clone(), |ctx: Ctx<'js>, args: Rest<Value>| {
+			run(ctx, "count", args.0)
+		})?
+		.with_name("count")?;
+		let value =
+			crate::fnc::script::modules::impl_module_def!(ctx, "count", "approx_distinct", run,);
+		exports.export("approx_distinct", value.clone())?;
+		default.set("approx_distinct", value)?;
+		exports.export("default", default)?;
+		Ok(())
+	}
+}
@@ -15,5 +15,6 @@ impl_module_def!(
 	"centroid" => run,
 	"distance" => run,
 	"hash" => (hash::Package),
-	"is" => (is::Package)
+	"is" => (is::Package),
+	"transform" => run
 );
@@ -10,6 +10,7 @@ impl_module_def!(
 	"abs" => run,
 	"acos" => run,
 	"acot" => run,
+	"approx_percentile" => run,
 	"asin" => run,
 	"atan" => run,
 	"bottom" => run,
@@ -9,12 +9,15 @@ use super::query::QueryContext;
 
 mod api;
 mod array;
+mod assert;
 mod bytes;
+mod count;
 mod crypto;
 mod duration;
 mod encoding;
 mod geo;
 mod http;
+mod job;
 mod math;
 mod meta;
 mod object;
@@ -24,6 +27,7 @@ mod record;
 mod search;
 mod session;
 mod string;
+mod sys;
 mod time;
 mod r#type;
 mod value;
@@ -37,13 +41,15 @@ impl_module_def!(
 	"", // root path
 	"api" => (api::Package),
 	"array" => (array::Package),
+	"assert" => (assert::Package),
 	"bytes" => (bytes::Package),
-	"count" => run,
+	"count" => (count::Package),
 	"crypto" => (crypto::Package),
 	"duration" => (duration::Package),
 	"encoding" => (encoding::Package),
 	"geo" => (geo::Package),
 	"http" => (http::Package),
+	"job" => (job::Package),
 	"math" => (math::Package),
 	"meta" => (meta::Package),
 	"not" => run,
@@ -55,6 +61,7 @@ impl_module_def!(
 	"session" => (session::Package),
 	"sleep" => fut Async,
 	"string" => (string::Package),
+	"sys" => (sys::Package),
 	"time" => (time::Package),
 	"type" => (r#type::Package),
 	"value" => (value::Package),
@@ -11,5 +11,6 @@ impl_module_def!(
 	"jaro" => run,
 	"jaro_winkler" => run,
 	"sorensen_dice" => run,
-	"smithwaterman" => run
+	"smithwaterman" => run,
+	"trigram" => run
 );
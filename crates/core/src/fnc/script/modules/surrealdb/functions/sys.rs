@@ -0,0 +1,14 @@
+use super::{fut, run};
+use crate::fnc::script::modules::impl_module_def;
+use js::prelude::Async;
+
+#[non_exhaustive]
+pub struct Package;
+
+impl_module_def!(
+	Package,
+	"sys",
+	"env" => run,
+	"hostname" => run,
+	"sleep" => fut Async
+);
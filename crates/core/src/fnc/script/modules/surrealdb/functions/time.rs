@@ -10,6 +10,7 @@ pub struct Package;
 impl_module_def!(
 	Package,
 	"time",
+	"bucket" => run,
 	"ceil" => run,
 	"day" => run,
 	"floor" => run,
@@ -32,6 +32,18 @@ pub async fn score(
 	Ok(Value::None)
 }
 
+/// Returns the trigram similarity of the record to the query of a `~%` expression,
+/// when the record was found with a trigram index
+pub async fn similarity(
+	(_ctx, doc): (&Context, Option<&CursorDoc>),
+	_: (),
+) -> Result<Value, Error> {
+	if let Some(similarity) = doc.and_then(|doc| doc.ir.as_ref()).and_then(|ir| ir.similarity()) {
+		return Ok(similarity.into());
+	}
+	Ok(Value::None)
+}
+
 pub async fn highlight(
 	(ctx, doc): (&Context, Option<&CursorDoc>),
 	args: (Value, Value, Value, Option<Value>),
@@ -326,10 +326,54 @@ pub mod is {
 	}
 }
 
+pub mod regex {
+
+	use super::limit;
+	use crate::err::Error;
+	use crate::sql::value::Value;
+	use crate::sql::{Object, Regex};
+
+	/// Returns the capture groups of the first match of a regex in a string
+	///
+	/// If the regex has named groups, then the groups are returned as an object keyed
+	/// by their names, otherwise as an array which starts with the whole match. Groups
+	/// which did not take part in the match are `NONE`, and so is a string which does
+	/// not match at all.
+	pub fn captures((val, regex): (String, Regex)) -> Result<Value, Error> {
+		let Some(caps) = regex.0.captures(&val) else {
+			return Ok(Value::None);
+		};
+		if regex.0.capture_names().any(|n| n.is_some()) {
+			let mut obj = Object::default();
+			for name in regex.0.capture_names().flatten() {
+				let v = caps.name(name).map(|m| m.as_str().into()).unwrap_or_default();
+				obj.insert(name.to_owned(), v);
+			}
+			Ok(obj.into())
+		} else {
+			let arr: Vec<Value> =
+				caps.iter().map(|m| m.map(|m| m.as_str().into()).unwrap_or_default()).collect();
+			Ok(arr.into())
+		}
+	}
+
+	/// Replaces every match of a regex in a string
+	///
+	/// The replacement can refer to the capture groups of each match, by number
+	/// with `$1` or by name with `$name`, and `${1}` or `${name}` when followed by
+	/// text which could be part of the reference.
+	pub fn replace_all((val, regex, replace): (String, Regex, String)) -> Result<Value, Error> {
+		let res = regex.0.replace_all(&val, replace.as_str());
+		limit("string::regex::replace_all", res.len())?;
+		Ok(res.into_owned().into())
+	}
+}
+
 pub mod similarity {
 
 	use crate::err::Error;
 	use crate::fnc::util::string::fuzzy::Fuzzy;
+	use crate::idx::trigram;
 	use crate::sql::Value;
 
 	use strsim;
@@ -359,6 +403,12 @@ pub mod similarity {
 	pub fn sorensen_dice((a, b): (String, String)) -> Result<Value, Error> {
 		Ok(strsim::sorensen_dice(&a, &b).into())
 	}
+
+	/// Calculate the ratio of the trigrams which two strings share,
+	/// as used by the `~%` operator and the trigram indexes.
+	pub fn trigram((a, b): (String, String)) -> Result<Value, Error> {
+		Ok(trigram::similarity(&a, &b).into())
+	}
 }
 
 pub mod semver {
@@ -476,6 +526,7 @@ pub mod semver {
 
 #[cfg(test)]
 mod tests {
+	use super::regex::{captures, replace_all};
 	use super::{contains, matches, replace, slice};
 	use crate::sql::Value;
 
@@ -554,6 +605,53 @@ mod tests {
 		test("foo bar", "bar", true);
 	}
 
+	#[test]
+	fn string_regex_captures() {
+		fn test(base: &str, regex: &str, expected: Value) {
+			assert_eq!(
+				captures((base.to_string(), regex.parse().unwrap())).unwrap(),
+				expected,
+				"captures({},{})",
+				base,
+				regex
+			);
+		}
+
+		test("foo", "bar", Value::None);
+		test(
+			"foo=1",
+			"(\\w+)=(\\d+)(;)?",
+			Value::from(vec![Value::from("foo=1"), "foo".into(), "1".into(), Value::None]),
+		);
+		test(
+			"foo=1",
+			"(?<key>\\w+)=(?<val>\\d+)",
+			Value::from(map! {
+				"key".to_string() => Value::from("foo"),
+				"val".to_string() => Value::from("1"),
+			}),
+		);
+	}
+
+	#[test]
+	fn string_regex_replace_all() {
+		fn test(base: &str, regex: &str, replace: &str, expected: &str) {
+			assert_eq!(
+				replace_all((base.to_string(), regex.parse().unwrap(), replace.to_string()))
+					.unwrap(),
+				Value::from(expected),
+				"replace_all({},{},{})",
+				base,
+				regex,
+				replace
+			);
+		}
+
+		test("foo bar", "baz", "$1", "foo bar");
+		test("foo=1;bar=2", "(\\w+)=(\\d+)", "$2=$1", "1=foo;2=bar");
+		test("foo=1;bar=2", "(?<k>\\w+)=(?<v>\\d+)", "${k}_${v}", "foo_1;bar_2");
+	}
+
 	#[test]
 	fn is_alphanum() {
 		let value = super::is::alphanum((String::from("abc123"),)).unwrap();
@@ -0,0 +1,46 @@
+use crate::cnf::EVENT_SLEEP_MAX_SECS;
+use crate::ctx::Context;
+use crate::err::Error;
+use crate::sql::value::Value;
+use crate::sql::Duration;
+
+/// The capability target which guards access to the host name
+const HOSTNAME: &str = "HOSTNAME";
+
+pub fn env(ctx: &Context, (name,): (String,)) -> Result<Value, Error> {
+	// Check if this environment variable can be read
+	ctx.check_allowed_env(&name)?;
+	// Fetch the environment variable value
+	Ok(match std::env::var(&name) {
+		Ok(v) => v.into(),
+		Err(_) => Value::None,
+	})
+}
+
+pub fn hostname(ctx: &Context, _: ()) -> Result<Value, Error> {
+	// Check if the host name can be read
+	ctx.check_allowed_env(HOSTNAME)?;
+	// Fetch the host name of this machine
+	Ok(match sysinfo::System::host_name() {
+		Some(v) => v.into(),
+		None => Value::None,
+	})
+}
+
+/// Sleeps within an asynchronous event, for no longer than the configured limit
+pub async fn sleep(ctx: &Context, (dur,): (Duration,)) -> Result<Value, Error> {
+	// Check this is running within an asynchronous event
+	if !ctx.is_asynchronous() {
+		return Err(Error::AsyncEventOnly {
+			name: String::from("sys::sleep"),
+		});
+	}
+	// Check the duration is within the limit
+	if dur.0 > std::time::Duration::from_secs(*EVENT_SLEEP_MAX_SECS) {
+		return Err(Error::InvalidArguments {
+			name: String::from("sys::sleep"),
+			message: format!("The duration can not be longer than {}s", *EVENT_SLEEP_MAX_SECS),
+		});
+	}
+	super::sleep::sleep(ctx, (dur,)).await
+}
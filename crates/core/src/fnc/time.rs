@@ -5,6 +5,38 @@ use crate::sql::value::Value;
 use chrono::offset::TimeZone;
 use chrono::{DateTime, Datelike, DurationRound, Local, Timelike, Utc};
 
+/// Returns the start of the interval of the given duration which a datetime falls into
+///
+/// The intervals are aligned to the unix epoch, or to the optional origin, so that
+/// datetimes can be grouped into fixed windows such as `5m` or `90s`.
+pub fn bucket(
+	(val, duration, origin): (Datetime, Duration, Option<Datetime>),
+) -> Result<Value, Error> {
+	let err = |message: &str| Error::InvalidArguments {
+		name: String::from("time::bucket"),
+		message: String::from(message),
+	};
+	let span = match i64::try_from(duration.as_nanos()) {
+		Ok(v) if v > 0 => v as i128,
+		_ => return Err(err("The second argument must be a duration greater than zero, and must be able to be represented as nanoseconds.")),
+	};
+	let nanos = |v: &Datetime| {
+		v.timestamp_nanos_opt()
+			.ok_or_else(|| err("The datetimes must be able to be represented as nanoseconds."))
+	};
+	let origin = match &origin {
+		Some(v) => nanos(v)? as i128,
+		None => 0,
+	};
+	// Datetimes before the origin fall into the preceding intervals
+	let offset = nanos(&val)? as i128 - origin;
+	let start = origin + offset.div_euclid(span) * span;
+	match i64::try_from(start) {
+		Ok(v) => Ok(Datetime::from(DateTime::from_timestamp_nanos(v)).into()),
+		_ => Err(err("The start of the interval must be able to be represented as nanoseconds.")),
+	}
+}
+
 pub fn ceil((val, duration): (Datetime, Duration)) -> Result<Value, Error> {
 	match chrono::Duration::from_std(*duration) {
 		Ok(d) => {
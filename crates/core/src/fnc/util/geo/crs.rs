@@ -0,0 +1,291 @@
+use crate::sql::geometry::Geometry;
+use geo::{Coord, MapCoords};
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+// WGS84 ellipsoid parameters
+const A: f64 = 6_378_137.0;
+const F: f64 = 1.0 / 298.257_223_563;
+// UTM scale factor on the central meridian
+const K0: f64 = 0.9996;
+// UTM false easting and (southern hemisphere) false northing
+const FALSE_EASTING: f64 = 500_000.0;
+const FALSE_NORTHING: f64 = 10_000_000.0;
+// The latitude at which Web Mercator becomes a square
+const MERCATOR_MAX_LAT: f64 = 85.051_128_779_806_59;
+
+/// A supported coordinate reference system
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Crs {
+	/// EPSG:4326 - longitude and latitude in degrees
+	Wgs84,
+	/// EPSG:3857 - spherical Web Mercator in metres
+	WebMercator,
+	/// EPSG:326xx / EPSG:327xx - WGS84 Universal Transverse Mercator in metres
+	Utm {
+		zone: u8,
+		north: bool,
+	},
+}
+
+impl Crs {
+	/// Parses a coordinate reference system identifier such as `EPSG:3857`
+	pub fn parse(v: &str) -> Option<Self> {
+		let v = v.trim().to_ascii_uppercase();
+		let code = match v.as_str() {
+			"WGS84" | "CRS:84" | "OGC:CRS84" => return Some(Crs::Wgs84),
+			v => v.strip_prefix("EPSG:")?.parse::<u32>().ok()?,
+		};
+		match code {
+			4326 => Some(Crs::Wgs84),
+			3857 | 3785 | 900913 | 102100 | 102113 => Some(Crs::WebMercator),
+			32601..=32660 => Some(Crs::Utm {
+				zone: (code - 32600) as u8,
+				north: true,
+			}),
+			32701..=32760 => Some(Crs::Utm {
+				zone: (code - 32700) as u8,
+				north: false,
+			}),
+			_ => None,
+		}
+	}
+
+	/// Converts a coordinate in this system into WGS84 longitude and latitude
+	fn unproject(self, c: Coord<f64>) -> Coord<f64> {
+		match self {
+			Crs::Wgs84 => c,
+			Crs::WebMercator => Coord {
+				x: (c.x / A).to_degrees(),
+				y: (2.0 * (c.y / A).exp().atan() - FRAC_PI_2).to_degrees(),
+			},
+			Crs::Utm {
+				zone,
+				north,
+			} => utm_inverse(c, zone, north),
+		}
+	}
+
+	/// Converts a WGS84 longitude and latitude into a coordinate in this system
+	fn project(self, c: Coord<f64>) -> Coord<f64> {
+		match self {
+			Crs::Wgs84 => c,
+			Crs::WebMercator => {
+				let lat = c.y.clamp(-MERCATOR_MAX_LAT, MERCATOR_MAX_LAT).to_radians();
+				Coord {
+					x: A * c.x.to_radians(),
+					y: A * (FRAC_PI_4 + lat / 2.0).tan().ln(),
+				}
+			}
+			Crs::Utm {
+				zone,
+				north,
+			} => utm_forward(c, zone, north),
+		}
+	}
+}
+
+/// Reprojects every coordinate of a geometry from one system into another
+pub fn transform(geo: Geometry, from: Crs, to: Crs) -> Geometry {
+	if from == to {
+		return geo;
+	}
+	let f = |c: Coord<f64>| to.project(from.unproject(c));
+	match geo {
+		Geometry::Point(v) => Geometry::Point(v.map_coords(f)),
+		Geometry::Line(v) => Geometry::Line(v.map_coords(f)),
+		Geometry::Polygon(v) => Geometry::Polygon(v.map_coords(f)),
+		Geometry::MultiPoint(v) => Geometry::MultiPoint(v.map_coords(f)),
+		Geometry::MultiLine(v) => Geometry::MultiLine(v.map_coords(f)),
+		Geometry::MultiPolygon(v) => Geometry::MultiPolygon(v.map_coords(f)),
+		Geometry::Collection(v) => {
+			Geometry::Collection(v.into_iter().map(|v| transform(v, from, to)).collect())
+		}
+	}
+}
+
+fn central_meridian(zone: u8) -> f64 {
+	(zone as f64 - 1.0) * 6.0 - 180.0 + 3.0
+}
+
+fn utm_forward(c: Coord<f64>, zone: u8, north: bool) -> Coord<f64> {
+	let e2 = F * (2.0 - F);
+	let ep2 = e2 / (1.0 - e2);
+	let lat = c.y.to_radians();
+	let (sin, cos, tan) = (lat.sin(), lat.cos(), lat.tan());
+	let n = A / (1.0 - e2 * sin * sin).sqrt();
+	let t = tan * tan;
+	let cc = ep2 * cos * cos;
+	let a = cos * (c.x - central_meridian(zone)).to_radians();
+	let m = meridian_arc(lat, e2);
+	let x = K0
+		* n * (a
+		+ (1.0 - t + cc) * a.powi(3) / 6.0
+		+ (5.0 - 18.0 * t + t * t + 72.0 * cc - 58.0 * ep2) * a.powi(5) / 120.0)
+		+ FALSE_EASTING;
+	let y = K0
+		* (m + n
+			* tan * (a * a / 2.0
+			+ (5.0 - t + 9.0 * cc + 4.0 * cc * cc) * a.powi(4) / 24.0
+			+ (61.0 - 58.0 * t + t * t + 600.0 * cc - 330.0 * ep2) * a.powi(6) / 720.0));
+	Coord {
+		x,
+		y: if north {
+			y
+		} else {
+			y + FALSE_NORTHING
+		},
+	}
+}
+
+fn utm_inverse(c: Coord<f64>, zone: u8, north: bool) -> Coord<f64> {
+	let e2 = F * (2.0 - F);
+	let ep2 = e2 / (1.0 - e2);
+	let x = c.x - FALSE_EASTING;
+	let y = if north {
+		c.y
+	} else {
+		c.y - FALSE_NORTHING
+	};
+	// Compute the footprint latitude
+	let mu = y / K0 / (A * (1.0 - e2 / 4.0 - 3.0 * e2 * e2 / 64.0 - 5.0 * e2.powi(3) / 256.0));
+	let e1 = (1.0 - (1.0 - e2).sqrt()) / (1.0 + (1.0 - e2).sqrt());
+	let phi = mu
+		+ (3.0 * e1 / 2.0 - 27.0 * e1.powi(3) / 32.0) * (2.0 * mu).sin()
+		+ (21.0 * e1 * e1 / 16.0 - 55.0 * e1.powi(4) / 32.0) * (4.0 * mu).sin()
+		+ (151.0 * e1.powi(3) / 96.0) * (6.0 * mu).sin()
+		+ (1097.0 * e1.powi(4) / 512.0) * (8.0 * mu).sin();
+	let (sin, cos, tan) = (phi.sin(), phi.cos(), phi.tan());
+	let cc = ep2 * cos * cos;
+	let t = tan * tan;
+	let n = A / (1.0 - e2 * sin * sin).sqrt();
+	let r = A * (1.0 - e2) / (1.0 - e2 * sin * sin).powf(1.5);
+	let d = x / (n * K0);
+	let lat = phi
+		- (n * tan / r)
+			* (d * d / 2.0
+				- (5.0 + 3.0 * t + 10.0 * cc - 4.0 * cc * cc - 9.0 * ep2) * d.powi(4) / 24.0
+				+ (61.0 + 90.0 * t + 298.0 * cc + 45.0 * t * t - 252.0 * ep2 - 3.0 * cc * cc)
+					* d.powi(6) / 720.0);
+	let lon = (d - (1.0 + 2.0 * t + cc) * d.powi(3) / 6.0
+		+ (5.0 - 2.0 * cc + 28.0 * t - 3.0 * cc * cc + 8.0 * ep2 + 24.0 * t * t) * d.powi(5)
+			/ 120.0)
+		/ cos;
+	Coord {
+		x: central_meridian(zone) + lon.to_degrees(),
+		y: lat.to_degrees(),
+	}
+}
+
+fn meridian_arc(lat: f64, e2: f64) -> f64 {
+	let (e4, e6) = (e2 * e2, e2 * e2 * e2);
+	A * ((1.0 - e2 / 4.0 - 3.0 * e4 / 64.0 - 5.0 * e6 / 256.0) * lat
+		- (3.0 * e2 / 8.0 + 3.0 * e4 / 32.0 + 45.0 * e6 / 1024.0) * (2.0 * lat).sin()
+		+ (15.0 * e4 / 256.0 + 45.0 * e6 / 1024.0) * (4.0 * lat).sin()
+		- (35.0 * e6 / 3072.0) * (6.0 * lat).sin())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn close(a: Coord<f64>, b: Coord<f64>, eps: f64) -> bool {
+		(a.x - b.x).abs() < eps && (a.y - b.y).abs() < eps
+	}
+
+	#[test]
+	fn parse_identifiers() {
+		assert_eq!(Crs::parse("EPSG:4326"), Some(Crs::Wgs84));
+		assert_eq!(Crs::parse("epsg:3857"), Some(Crs::WebMercator));
+		assert_eq!(
+			Crs::parse("EPSG:32733"),
+			Some(Crs::Utm {
+				zone: 33,
+				north: false
+			})
+		);
+		assert_eq!(Crs::parse("EPSG:2154"), None);
+		assert_eq!(Crs::parse("3857"), None);
+	}
+
+	#[test]
+	fn web_mercator() {
+		let c = Crs::WebMercator.project(Coord {
+			x: 180.0,
+			y: 0.0,
+		});
+		assert!(close(
+			c,
+			Coord {
+				x: 20_037_508.342_789_244,
+				y: 0.0
+			},
+			1e-6
+		));
+		let london = Coord {
+			x: -0.1278,
+			y: 51.5074,
+		};
+		let back = Crs::WebMercator.unproject(Crs::WebMercator.project(london));
+		assert!(close(london, back, 1e-9));
+	}
+
+	#[test]
+	fn utm_round_trip() {
+		for (c, zone, north) in [
+			(
+				Coord {
+					x: -0.1278,
+					y: 51.5074,
+				},
+				30,
+				true,
+			),
+			(
+				Coord {
+					x: 151.2093,
+					y: -33.8688,
+				},
+				56,
+				false,
+			),
+		] {
+			let crs = Crs::Utm {
+				zone,
+				north,
+			};
+			let projected = crs.project(c);
+			assert!((166_000.0..834_000.0).contains(&projected.x));
+			let back = crs.unproject(projected);
+			assert!(close(c, back, 1e-6));
+		}
+	}
+
+	#[test]
+	fn utm_reference_points() {
+		// The projection uses Snyder's series, which agrees with the reference
+		// coordinates from PROJ's `+proj=utm` to well within a centimetre here
+		for (code, c, expected) in [
+			("EPSG:32633", (15.0, 0.0), (500_000.0, 0.0)),
+			("EPSG:32633", (15.0, 45.0), (500_000.0, 4_982_950.400)),
+			("EPSG:32633", (12.4964, 41.9028), (292_335.313, 4_642_015.500)),
+			("EPSG:32633", (13.405, 52.52), (391_779.259, 5_820_072.159)),
+			("EPSG:32633", (12.0, 60.0), (332_705.179, 6_655_205.484)),
+			("EPSG:32630", (-0.1278, 51.5074), (699_316.234, 5_710_163.758)),
+			("EPSG:32756", (151.2093, -33.8688), (334_368.634, 6_250_948.345)),
+			("EPSG:32734", (18.4241, -33.9249), (261_881.599, 6_243_182.355)),
+		] {
+			let crs = Crs::parse(code).unwrap();
+			let c = Coord {
+				x: c.0,
+				y: c.1,
+			};
+			let expected = Coord {
+				x: expected.0,
+				y: expected.1,
+			};
+			let projected = crs.project(c);
+			assert!(close(projected, expected, 1e-2), "{code}: {projected:?} != {expected:?}");
+			assert!(close(crs.unproject(expected), c, 1e-7), "{code}: {expected:?}");
+		}
+	}
+}
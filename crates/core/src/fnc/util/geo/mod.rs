@@ -1,3 +1,5 @@
+pub mod crs;
+
 use crate::sql::geometry::Geometry;
 use crate::sql::strand::Strand;
 use geo::Point;
@@ -0,0 +1,57 @@
+use crate::sql::value::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// The number of bits of the hash which select a register
+const PRECISION: u32 = 12;
+
+/// The number of registers, which gives a standard error of about 1.6%
+const REGISTERS: usize = 1 << PRECISION;
+
+/// A HyperLogLog sketch, which estimates the number of distinct values in bounded memory
+///
+/// Values are distinct when their hashes are, so numbers of different kinds
+/// which are equal, such as `1` and `1.0`, are counted as distinct values.
+pub struct ApproxDistinct {
+	registers: Box<[u8; REGISTERS]>,
+}
+
+impl Default for ApproxDistinct {
+	fn default() -> Self {
+		Self {
+			registers: Box::new([0; REGISTERS]),
+		}
+	}
+}
+
+impl ApproxDistinct {
+	/// Adds a value to the sketch
+	pub fn push(&mut self, val: &Value) {
+		let mut hasher = DefaultHasher::new();
+		val.hash(&mut hasher);
+		let hash = hasher.finish();
+		// The first bits select the register
+		let index = (hash >> (64 - PRECISION)) as usize;
+		// The register keeps the longest run of leading zeros of the remaining bits
+		let rest = (hash << PRECISION) | (1 << (PRECISION - 1));
+		let rank = rest.leading_zeros() as u8 + 1;
+		if rank > self.registers[index] {
+			self.registers[index] = rank;
+		}
+	}
+
+	/// Estimates the number of distinct values which were added to the sketch
+	pub fn estimate(&self) -> i64 {
+		let m = REGISTERS as f64;
+		let alpha = 0.7213 / (1.0 + 1.079 / m);
+		let sum: f64 = self.registers.iter().map(|&r| 2f64.powi(-(r as i32))).sum();
+		let estimate = alpha * m * m / sum;
+		// Small cardinalities are estimated more accurately by counting the empty registers
+		let zeros = self.registers.iter().filter(|&&r| r == 0).count();
+		if estimate <= 2.5 * m && zeros > 0 {
+			(m * (m / zeros as f64).ln()).round() as i64
+		} else {
+			estimate.round() as i64
+		}
+	}
+}
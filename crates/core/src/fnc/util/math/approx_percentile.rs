@@ -0,0 +1,99 @@
+use crate::sql::number::Number;
+use std::collections::BTreeMap;
+
+/// The relative accuracy of the values which are returned
+const ACCURACY: f64 = 0.01;
+
+/// The maximum number of buckets for each sign of the values
+const MAX_BUCKETS: usize = 2048;
+
+/// Values which are closer to zero than this are counted as zero
+const MIN_VALUE: f64 = 1e-9;
+
+/// A sketch of the distribution of numbers, which estimates percentiles in bounded memory
+///
+/// Numbers are counted in buckets whose bounds grow exponentially, so that the
+/// value of each bucket is within 1% of the numbers counted in it. When there are
+/// too many buckets, the buckets of the numbers closest to zero are merged.
+#[derive(Default)]
+pub struct ApproxPercentile {
+	positive: BTreeMap<i32, u64>,
+	negative: BTreeMap<i32, u64>,
+	zero: u64,
+	count: u64,
+}
+
+fn gamma() -> f64 {
+	(1.0 + ACCURACY) / (1.0 - ACCURACY)
+}
+
+fn key(v: f64) -> i32 {
+	(v.ln() / gamma().ln()).ceil() as i32
+}
+
+fn value(key: i32) -> f64 {
+	2.0 * gamma().powi(key) / (gamma() + 1.0)
+}
+
+fn insert(buckets: &mut BTreeMap<i32, u64>, key: i32) {
+	*buckets.entry(key).or_default() += 1;
+	if buckets.len() > MAX_BUCKETS {
+		if let Some((_, count)) = buckets.pop_first() {
+			if let Some(mut first) = buckets.first_entry() {
+				*first.get_mut() += count;
+			}
+		}
+	}
+}
+
+impl ApproxPercentile {
+	/// Adds a number to the sketch
+	pub fn push(&mut self, num: &Number) {
+		let v = num.to_float();
+		if !v.is_finite() {
+			return;
+		}
+		if v > MIN_VALUE {
+			insert(&mut self.positive, key(v));
+		} else if v < -MIN_VALUE {
+			insert(&mut self.negative, key(-v));
+		} else {
+			self.zero += 1;
+		}
+		self.count += 1;
+	}
+
+	/// Estimates the N percentile of the numbers which were added to the sketch
+	pub fn percentile(&self, perc: Number) -> f64 {
+		// If an empty set, then return NaN
+		if self.count == 0 {
+			return f64::NAN;
+		}
+		// If an invalid percentile, then return NaN
+		let perc = perc.to_float();
+		if !(0.0..=100.0).contains(&perc) {
+			return f64::NAN;
+		}
+		// Get the rank of the specified percentile
+		let rank = (self.count - 1) as f64 * perc * (1.0 / 100.0);
+		let mut seen = 0;
+		// The negative numbers are in order of descending magnitude
+		for (k, c) in self.negative.iter().rev() {
+			seen += c;
+			if seen as f64 > rank {
+				return -value(*k);
+			}
+		}
+		seen += self.zero;
+		if seen as f64 > rank {
+			return 0.0;
+		}
+		for (k, c) in self.positive.iter() {
+			seen += c;
+			if seen as f64 > rank {
+				return value(*k);
+			}
+		}
+		f64::NAN
+	}
+}
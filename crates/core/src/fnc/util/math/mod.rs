@@ -2,6 +2,8 @@
 // https://docs.rs/statistical/1.0.0/src/statistical/stats_.rs.html
 // https://rust-lang-nursery.github.io/rust-cookbook/science/mathematics/statistics.html
 
+pub mod approx_distinct;
+pub mod approx_percentile;
 pub mod bottom;
 pub mod deviation;
 pub mod interquartile;
@@ -9,7 +9,7 @@ use super::Level;
 use cedar_policy::{Entity, EntityId, EntityTypeName, EntityUid, RestrictedExpression};
 use serde::{Deserialize, Serialize};
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Default, Debug, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -34,6 +34,8 @@ pub enum ResourceKind {
 	Config(ConfigKind),
 	#[revision(start = 3)]
 	Api,
+	#[revision(start = 4)]
+	Test,
 
 	// IAM
 	Actor,
@@ -60,6 +62,7 @@ impl std::fmt::Display for ResourceKind {
 			ResourceKind::Option => write!(f, "Option"),
 			ResourceKind::Function => write!(f, "Function"),
 			ResourceKind::Api => write!(f, "Api"),
+			ResourceKind::Test => write!(f, "Test"),
 			ResourceKind::Analyzer => write!(f, "Analyzer"),
 			ResourceKind::Parameter => write!(f, "Parameter"),
 			ResourceKind::Model => write!(f, "Model"),
@@ -5,6 +5,7 @@ use crate::dbs::Options;
 use crate::err::Error;
 use crate::idx::ft::FtIndex;
 use crate::idx::trees::mtree::MTreeIndex;
+use crate::idx::trigram::TrigramIndex;
 use crate::idx::IndexKeyBase;
 use crate::key;
 use crate::kvs::TransactionType;
@@ -51,6 +52,7 @@ impl<'a> IndexOperation<'a> {
 			Index::Search(p) => self.index_full_text(stk, p).await,
 			Index::MTree(p) => self.index_mtree(stk, p).await,
 			Index::Hnsw(p) => self.index_hnsw(p).await,
+			Index::Trigram => self.index_trigram().await,
 		}
 	}
 
@@ -187,6 +189,12 @@ impl<'a> IndexOperation<'a> {
 		}
 		Ok(())
 	}
+
+	async fn index_trigram(&mut self) -> Result<(), Error> {
+		let (ns, db) = self.opt.ns_db()?;
+		let ti = TrigramIndex::new(ns, db, self.ix);
+		ti.index_document(self.ctx, self.rid, self.o.take(), self.n.take()).await
+	}
 }
 
 /// Extract from the given document, the values required by the index and put then in an array.
@@ -3,6 +3,7 @@ pub(crate) mod ft;
 pub(crate) mod index;
 pub mod planner;
 pub mod trees;
+pub(crate) mod trigram;
 
 use crate::err::Error;
 use crate::idx::docids::DocId;
@@ -13,8 +13,8 @@ use crate::idx::planner::checker::{HnswConditionChecker, MTreeConditionChecker};
 use crate::idx::planner::iterators::{
 	IndexEqualThingIterator, IndexJoinThingIterator, IndexRangeThingIterator,
 	IndexUnionThingIterator, IteratorRange, IteratorRecord, IteratorRef, KnnIterator,
-	KnnIteratorResult, MatchesThingIterator, MultipleIterators, ThingIterator,
-	UniqueEqualThingIterator, UniqueJoinThingIterator, UniqueRangeThingIterator,
+	KnnIteratorResult, MatchesThingIterator, MultipleIterators, SimilarThingIterator,
+	ThingIterator, UniqueEqualThingIterator, UniqueJoinThingIterator, UniqueRangeThingIterator,
 	UniqueUnionThingIterator, ValueType,
 };
 use crate::idx::planner::knn::{KnnBruteForceResult, KnnPriorityList};
@@ -353,6 +353,7 @@ impl QueryExecutor {
 			} => self.new_search_index_iterator(irf, io.clone()).await,
 			Index::MTree(_) => Ok(self.new_mtree_index_knn_iterator(irf)),
 			Index::Hnsw(_) => Ok(self.new_hnsw_index_ann_iterator(irf)),
+			Index::Trigram => Self::new_trigram_index_iterator(opt, irf, io),
 		}
 	}
 
@@ -877,6 +878,19 @@ impl QueryExecutor {
 		Ok(None)
 	}
 
+	fn new_trigram_index_iterator(
+		opt: &Options,
+		irf: IteratorRef,
+		io: &IndexOption,
+	) -> Result<Option<ThingIterator>, Error> {
+		if let IndexOperator::Similar(qs) = io.op() {
+			let (ns, db) = opt.ns_db()?;
+			let it = SimilarThingIterator::new(irf, ns, db, io.ix_ref(), qs)?;
+			return Ok(Some(ThingIterator::Similar(it)));
+		}
+		Ok(None)
+	}
+
 	fn new_mtree_index_knn_iterator(&self, ir: IteratorRef) -> Option<ThingIterator> {
 		if let Some(IteratorEntry::Single(Some(exp), ..)) = self.0.it_entries.get(ir) {
 			if let Some(mte) = self.0.mt_entries.get(exp) {
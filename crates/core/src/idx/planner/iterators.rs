@@ -6,6 +6,7 @@ use crate::idx::ft::termdocs::TermsDocs;
 use crate::idx::ft::{FtIndex, HitsIterator};
 use crate::idx::planner::plan::RangeValue;
 use crate::idx::planner::tree::IndexReference;
+use crate::idx::trigram::{self, TrigramIndex, SIMILARITY_THRESHOLD};
 use crate::key::index::Index;
 use crate::kvs::{Key, Val};
 use crate::kvs::{KeyEncode, Transaction};
@@ -24,6 +25,8 @@ pub(crate) struct IteratorRecord {
 	irf: IteratorRef,
 	doc_id: Option<DocId>,
 	dist: Option<f64>,
+	/// The trigram similarity of the record to the query of a `~%` expression
+	similarity: Option<f64>,
 }
 
 impl IteratorRecord {
@@ -37,6 +40,10 @@ impl IteratorRecord {
 	pub(crate) fn dist(&self) -> Option<f64> {
 		self.dist
 	}
+
+	pub(crate) fn similarity(&self) -> Option<f64> {
+		self.similarity
+	}
 }
 impl From<IteratorRef> for IteratorRecord {
 	fn from(irf: IteratorRef) -> Self {
@@ -44,6 +51,7 @@ impl From<IteratorRef> for IteratorRecord {
 			irf,
 			doc_id: None,
 			dist: None,
+			similarity: None,
 		}
 	}
 }
@@ -115,6 +123,7 @@ pub(crate) enum ThingIterator {
 	UniqueUnion(UniqueUnionThingIterator),
 	UniqueJoin(Box<UniqueJoinThingIterator>),
 	Matches(MatchesThingIterator),
+	Similar(SimilarThingIterator),
 	Knn(KnnIterator),
 	Multiples(Box<MultipleIterators>),
 }
@@ -134,6 +143,7 @@ impl ThingIterator {
 			Self::IndexUnion(i) => i.next_batch(ctx, txn, size).await,
 			Self::UniqueUnion(i) => i.next_batch(ctx, txn, size).await,
 			Self::Matches(i) => i.next_batch(ctx, txn, size).await,
+			Self::Similar(i) => i.next_batch(ctx, txn, size).await,
 			Self::Knn(i) => i.next_batch(ctx, size).await,
 			Self::IndexJoin(i) => Box::pin(i.next_batch(ctx, txn, size)).await,
 			Self::UniqueJoin(i) => Box::pin(i.next_batch(ctx, txn, size)).await,
@@ -155,6 +165,7 @@ impl ThingIterator {
 			Self::IndexUnion(i) => i.next_count(ctx, txn, size).await,
 			Self::UniqueUnion(i) => i.next_count(ctx, txn, size).await,
 			Self::Matches(i) => i.next_count(ctx, txn, size).await,
+			Self::Similar(i) => i.next_count(ctx, txn, size).await,
 			Self::Knn(i) => i.next_count(ctx, size).await,
 			Self::IndexJoin(i) => Box::pin(i.next_count(ctx, txn, size)).await,
 			Self::UniqueJoin(i) => Box::pin(i.next_count(ctx, txn, size)).await,
@@ -1114,6 +1125,7 @@ impl MatchesThingIterator {
 						irf: self.irf,
 						doc_id: Some(doc_id),
 						dist: None,
+						similarity: None,
 					};
 					records.add(IndexItemRecord::new_key(thg, ir));
 					self.hits_left -= 1;
@@ -1151,6 +1163,160 @@ impl MatchesThingIterator {
 	}
 }
 
+/// The index entries of one trigram of the query, which are read in batches, in the order
+/// of the ids of the records
+struct TrigramPostings {
+	/// The length of the key prefix which precedes the record id
+	prefix: usize,
+	beg: Key,
+	end: Key,
+	batch: VecDeque<(Key, Val)>,
+	done: bool,
+}
+
+impl TrigramPostings {
+	const SCAN_BATCH_SIZE: u32 = 1000;
+
+	fn new((beg, end): (Key, Key)) -> Self {
+		Self {
+			prefix: beg.len() - 1,
+			beg,
+			end,
+			batch: VecDeque::new(),
+			done: false,
+		}
+	}
+
+	/// Reads the next batch of entries, once the current batch is consumed
+	async fn fill(&mut self, tx: &Transaction) -> Result<(), Error> {
+		if self.batch.is_empty() && !self.done {
+			let res =
+				tx.scan(self.beg.clone()..self.end.clone(), Self::SCAN_BATCH_SIZE, None).await?;
+			match res.last() {
+				Some((k, _)) if res.len() == Self::SCAN_BATCH_SIZE as usize => {
+					self.beg.clone_from(k);
+					self.beg.push(0x00);
+				}
+				_ => self.done = true,
+			}
+			self.batch = res.into();
+		}
+		Ok(())
+	}
+
+	/// The encoded id of the record of the next entry
+	fn head(&self) -> Option<&[u8]> {
+		self.batch.front().map(|(k, _)| &k[self.prefix..])
+	}
+}
+
+/// Finds the records which are similar to a query, with the trigram index
+///
+/// The entries of the trigrams of the query are merged in the order of the record ids,
+/// so that the records are returned as they are found, and only the entries which
+/// are needed to reach the LIMIT of the statement are read.
+pub(crate) struct SimilarThingIterator {
+	irf: IteratorRef,
+	/// The entries of each trigram of the query
+	postings: Vec<TrigramPostings>,
+}
+
+impl SimilarThingIterator {
+	pub(super) fn new(
+		irf: IteratorRef,
+		ns: &str,
+		db: &str,
+		ix: &DefineIndexStatement,
+		qs: &str,
+	) -> Result<Self, Error> {
+		let ranges = TrigramIndex::new(ns, db, ix).ranges(qs)?;
+		Ok(Self {
+			irf,
+			postings: ranges.into_iter().map(TrigramPostings::new).collect(),
+		})
+	}
+
+	/// Returns the next record which is similar enough to the query, with its similarity
+	async fn next_similar(
+		&mut self,
+		ctx: &Context,
+		tx: &Transaction,
+	) -> Result<Option<(Thing, f64)>, Error> {
+		let mut checked = 0usize;
+		while !ctx.is_done(checked % 100 == 0) {
+			checked += 1;
+			for p in self.postings.iter_mut() {
+				p.fill(tx).await?;
+			}
+			// The record with the lowest id which any trigram has left
+			let Some(id) = self.postings.iter().filter_map(TrigramPostings::head).min() else {
+				return Ok(None);
+			};
+			let id = id.to_vec();
+			// Consume the entries of the trigrams which the record shares with the query
+			let mut shared = 0;
+			let mut entry = None;
+			for p in self.postings.iter_mut() {
+				if p.head() == Some(id.as_slice()) {
+					shared += 1;
+					entry = p.batch.pop_front();
+				}
+			}
+			if let Some((_, v)) = entry {
+				let (rid, count): (Thing, u64) = revision::from_slice(&v)?;
+				let score = trigram::score(self.postings.len(), count as usize, shared);
+				// This is the same test as the one of the `~%` operator
+				if score >= SIMILARITY_THRESHOLD {
+					return Ok(Some((rid, score)));
+				}
+			}
+		}
+		Ok(None)
+	}
+
+	async fn next_batch<B: IteratorBatch>(
+		&mut self,
+		ctx: &Context,
+		tx: &Transaction,
+		limit: u32,
+	) -> Result<B, Error> {
+		let limit = limit as usize;
+		let mut records = B::with_capacity(limit.min(100));
+		while limit > records.len() && !ctx.is_done(records.len() % 100 == 0) {
+			if let Some((thing, score)) = self.next_similar(ctx, tx).await? {
+				let ir = IteratorRecord {
+					irf: self.irf,
+					doc_id: None,
+					dist: None,
+					similarity: Some(score),
+				};
+				records.add(IndexItemRecord::new_key(thing, ir));
+			} else {
+				break;
+			}
+		}
+		Ok(records)
+	}
+
+	async fn next_count(
+		&mut self,
+		ctx: &Context,
+		tx: &Transaction,
+		limit: u32,
+	) -> Result<usize, Error> {
+		let limit = limit as usize;
+		let mut count = 0;
+		while limit > count && !ctx.is_done(count % 100 == 0) {
+			if self.next_similar(ctx, tx).await?.is_some() {
+				count += 1;
+			} else {
+				break;
+			}
+		}
+		Ok(count)
+	}
+}
+
 pub(crate) type KnnIteratorResult = (Arc<Thing>, f64, Option<Arc<Value>>);
 
 pub(crate) struct KnnIterator {
@@ -1178,6 +1344,7 @@ impl KnnIterator {
 					irf: self.irf,
 					doc_id: None,
 					dist: Some(dist),
+					similarity: None,
 				};
 				records.add(IndexItemRecord::new(thing, ir, val));
 			} else {
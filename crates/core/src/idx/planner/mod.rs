@@ -14,6 +14,7 @@ use crate::idx::planner::iterators::IteratorRef;
 use crate::idx::planner::knn::KnnBruteForceResults;
 use crate::idx::planner::plan::{Plan, PlanBuilder};
 use crate::idx::planner::tree::Tree;
+use crate::sql::join::JoinStrategy;
 use crate::sql::with::With;
 use crate::sql::{order::Ordering, Cond, Fields, Groups, Table};
 use reblessive::tree::Stk;
@@ -184,6 +185,8 @@ pub(crate) struct QueryPlanner {
 	executors: HashMap<String, QueryExecutor>,
 	requires_distinct: bool,
 	fallbacks: Vec<String>,
+	/// The strategy of each joined table
+	joins: Vec<(String, JoinStrategy)>,
 	iteration_workflow: Vec<IterationStage>,
 	iteration_index: AtomicU8,
 	orders: Vec<IteratorRef>,
@@ -197,6 +200,7 @@ impl QueryPlanner {
 			executors: HashMap::default(),
 			requires_distinct: false,
 			fallbacks: vec![],
+			joins: vec![],
 			iteration_workflow: Vec::default(),
 			iteration_index: AtomicU8::new(0),
 			orders: vec![],
@@ -338,6 +342,14 @@ impl QueryPlanner {
 		&self.fallbacks
 	}
 
+	pub(crate) fn add_join(&mut self, tb: String, strategy: JoinStrategy) {
+		self.joins.push((tb, strategy));
+	}
+
+	pub(crate) fn joins(&self) -> &Vec<(String, JoinStrategy)> {
+		&self.joins
+	}
+
 	pub(crate) fn is_order(&self, irf: &IteratorRef) -> bool {
 		self.orders.contains(irf)
 	}
@@ -307,6 +307,7 @@ pub(super) enum IndexOperator {
 	Join(Vec<IndexOption>),
 	RangePart(Operator, Arc<Value>),
 	Matches(String, Option<MatchRef>),
+	Similar(String),
 	Knn(Arc<Vec<Number>>, u32),
 	Ann(Arc<Vec<Number>>, u32, u32),
 	Order,
@@ -381,6 +382,10 @@ impl IndexOption {
 				e.insert("operator", Value::from(Operator::Matches(*a).to_string()));
 				e.insert("value", Value::from(qs.to_owned()));
 			}
+			IndexOperator::Similar(qs) => {
+				e.insert("operator", Value::from(Operator::Similar.to_string()));
+				e.insert("value", Value::from(qs.to_owned()));
+			}
 			IndexOperator::RangePart(op, v) => {
 				e.insert("operator", Value::from(op.to_string()));
 				e.insert("value", v.as_ref().to_owned());
@@ -7,7 +7,7 @@ use crate::idx::planner::plan::{IndexOperator, IndexOption};
 use crate::idx::planner::rewriter::KnnConditionRewriter;
 use crate::idx::planner::StatementContext;
 use crate::kvs::Transaction;
-use crate::sql::index::Index;
+use crate::sql::index::{Distance, Index};
 use crate::sql::statements::{DefineFieldStatement, DefineIndexStatement};
 use crate::sql::{
 	order::{OrderList, Ordering},
@@ -401,9 +401,9 @@ impl<'a> TreeBuilder<'a> {
 					None
 				};
 				if let Some(id) = left.is_field() {
-					self.eval_bruteforce_knn(id, &right, &exp)?;
+					self.eval_bruteforce_knn(id, &left, &right, &exp)?;
 				} else if let Some(id) = right.is_field() {
-					self.eval_bruteforce_knn(id, &left, &exp)?;
+					self.eval_bruteforce_knn(id, &right, &left, &exp)?;
 				}
 				self.check_leaf_node_with_index(io.as_ref());
 				let re = ResolvedExpression {
@@ -462,6 +462,10 @@ impl<'a> TreeBuilder<'a> {
 		remote_irs: Option<&RemoteIndexRefs>,
 	) -> Result<Option<IndexOption>, Error> {
 		if let Some(remote_irs) = remote_irs {
+			// Joins have been disabled with WITH NOJOIN
+			if let Some(With::NoJoin) = self.ctx.with {
+				return Ok(None);
+			}
 			let mut remote_ios = Vec::with_capacity(remote_irs.len());
 			for (id, irs) in remote_irs.iter() {
 				if let Some(io) = self.lookup_index_option(irs, o, id, node, exp, p)? {
@@ -498,8 +502,13 @@ impl<'a> TreeBuilder<'a> {
 				Index::Search {
 					..
 				} if *col == 0 => Self::eval_matches_operator(op, n),
-				Index::MTree(_) if *col == 0 => self.eval_mtree_knn(e, op, n)?,
-				Index::Hnsw(_) if *col == 0 => self.eval_hnsw_knn(e, op, n)?,
+				Index::Trigram if *col == 0 => Self::eval_similar_operator(op, n),
+				Index::MTree(_) if *col == 0 && !self.brute_force() => {
+					self.eval_mtree_knn(e, op, n)?
+				}
+				Index::Hnsw(_) if *col == 0 && !self.brute_force() => {
+					self.eval_hnsw_knn(e, op, n)?
+				}
 				_ => None,
 			};
 			if res.is_none() {
@@ -532,6 +541,15 @@ impl<'a> TreeBuilder<'a> {
 		None
 	}
 
+	fn eval_similar_operator(op: &Operator, n: &Node) -> Option<IndexOperator> {
+		if let Some(v) = n.is_computed() {
+			if let (Operator::Similar, Value::Strand(s)) = (op, v.as_ref()) {
+				return Some(IndexOperator::Similar(s.0.clone()));
+			}
+		}
+		None
+	}
+
 	fn eval_mtree_knn(
 		&mut self,
 		exp: &Arc<Expression>,
@@ -567,22 +585,45 @@ impl<'a> TreeBuilder<'a> {
 	fn eval_bruteforce_knn(
 		&mut self,
 		id: &Idiom,
+		field: &Node,
 		val: &Node,
 		exp: &Arc<Expression>,
 	) -> Result<(), Error> {
-		if let Operator::Knn(k, Some(d)) = exp.operator() {
-			if let Node::Computed(v) = val {
-				let vec: Arc<Vec<Number>> = Arc::new(v.as_ref().try_into()?);
-				self.knn_expressions.insert(exp.clone());
-				self.knn_brute_force_expressions.insert(
-					exp.clone(),
-					KnnBruteForceExpression::new(*k, id.clone(), vec, d.clone()),
-				);
+		let (k, d) = match exp.operator() {
+			Operator::Knn(k, Some(d)) => (k, d.clone()),
+			// With WITH BRUTEFORCE, indexed KNN operators are computed by brute force too
+			Operator::Knn(k, None) | Operator::Ann(k, _) if self.brute_force() => {
+				(k, Self::knn_distance(field))
 			}
+			_ => return Ok(()),
+		};
+		if let Node::Computed(v) = val {
+			let vec: Arc<Vec<Number>> = Arc::new(v.as_ref().try_into()?);
+			self.knn_expressions.insert(exp.clone());
+			self.knn_brute_force_expressions
+				.insert(exp.clone(), KnnBruteForceExpression::new(*k, id.clone(), vec, d));
 		}
 		Ok(())
 	}
 
+	fn brute_force(&self) -> bool {
+		matches!(self.ctx.with, Some(With::BruteForce))
+	}
+
+	/// Returns the distance of the vector index on the field, if any
+	fn knn_distance(field: &Node) -> Distance {
+		if let Some((_, irs, _)) = field.is_indexed_field() {
+			for (ixr, _) in irs.iter() {
+				match &ixr.index {
+					Index::MTree(p) => return p.distance.clone(),
+					Index::Hnsw(p) => return p.distance.clone(),
+					_ => {}
+				}
+			}
+		}
+		Distance::Euclidean
+	}
+
 	fn eval_index_operator(
 		&mut self,
 		ixr: &IndexReference,
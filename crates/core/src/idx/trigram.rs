@@ -0,0 +1,126 @@
+use crate::ctx::Context;
+use crate::err::Error;
+use crate::key;
+use crate::kvs::{Key, KeyEncode};
+use crate::sql::statements::DefineIndexStatement;
+use crate::sql::{Array, Thing, Value};
+use std::collections::BTreeSet;
+
+/// The similarity from which two strings match with the `~%` operator
+pub(crate) const SIMILARITY_THRESHOLD: f64 = 0.3;
+
+/// Returns the trigrams of a string
+///
+/// The string is lowercased and split into words, and each word is padded with
+/// two spaces before and one space after, so that short words still have trigrams
+/// and the starts of the words weigh more than the ends.
+pub(crate) fn trigrams(s: &str) -> BTreeSet<String> {
+	let mut res = BTreeSet::new();
+	for word in s.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()) {
+		let chars: Vec<char> = format!("  {} ", word.to_lowercase()).chars().collect();
+		for w in chars.windows(3) {
+			res.insert(w.iter().collect());
+		}
+	}
+	res
+}
+
+/// Returns the ratio of the trigrams which two strings share, between 0 and 1
+pub(crate) fn similarity(a: &str, b: &str) -> f64 {
+	let a = trigrams(a);
+	let b = trigrams(b);
+	score(a.len(), b.len(), a.intersection(&b).count())
+}
+
+/// Returns the trigrams of every string of the indexed values
+fn values_trigrams(vals: Vec<Value>) -> BTreeSet<String> {
+	let mut res = BTreeSet::new();
+	for v in vals {
+		match v {
+			Value::Strand(s) => res.append(&mut trigrams(&s)),
+			Value::Array(a) => res.append(&mut values_trigrams(a.0)),
+			_ => {}
+		}
+	}
+	res
+}
+
+/// Returns the similarity of a record to a query, given the number of trigrams of each,
+/// and the number of trigrams which they share
+///
+/// This is the ratio computed by [`similarity`], without needing the strings themselves.
+pub(crate) fn score(query: usize, record: usize, shared: usize) -> f64 {
+	let all = query + record - shared;
+	if all == 0 {
+		return 0.0;
+	}
+	shared as f64 / all as f64
+}
+
+/// An index of the trigrams of strings, which finds the strings similar to a given string
+///
+/// Each trigram is stored as a non-unique index entry, with the id of every record
+/// whose indexed values contain the trigram, and the number of trigrams of the record,
+/// so that the similarity of a record can be computed from the entries alone.
+pub(crate) struct TrigramIndex<'a> {
+	ns: &'a str,
+	db: &'a str,
+	ix: &'a DefineIndexStatement,
+}
+
+impl<'a> TrigramIndex<'a> {
+	pub(crate) fn new(ns: &'a str, db: &'a str, ix: &'a DefineIndexStatement) -> Self {
+		Self {
+			ns,
+			db,
+			ix,
+		}
+	}
+
+	fn key(&self, trigram: &str, rid: &Thing) -> Result<Key, Error> {
+		let fd = Array::from(vec![Value::from(trigram)]);
+		key::index::Index::new(self.ns, self.db, &self.ix.what, &self.ix.name, &fd, Some(&rid.id))
+			.encode()
+	}
+
+	/// Returns the range of the index entries of each trigram of a string, whose keys end
+	/// with the record ids after the first byte of the range
+	pub(crate) fn ranges(&self, s: &str) -> Result<Vec<(Key, Key)>, Error> {
+		trigrams(s)
+			.into_iter()
+			.map(|t| {
+				let fd = Array::from(vec![Value::from(t)]);
+				let (tb, ix) = (&self.ix.what, &self.ix.name);
+				let beg = key::index::Index::prefix_ids_beg(self.ns, self.db, tb, ix, &fd)?;
+				let end = key::index::Index::prefix_ids_end(self.ns, self.db, tb, ix, &fd)?;
+				Ok((beg, end))
+			})
+			.collect()
+	}
+
+	/// Updates the trigrams of a record, given its old and new indexed values
+	pub(crate) async fn index_document(
+		&self,
+		ctx: &Context,
+		rid: &Thing,
+		o: Option<Vec<Value>>,
+		n: Option<Vec<Value>>,
+	) -> Result<(), Error> {
+		let o = o.map(values_trigrams).unwrap_or_default();
+		let n = n.map(values_trigrams).unwrap_or_default();
+		// Lock the transaction
+		let tx = ctx.tx();
+		let mut txn = tx.lock().await;
+		// Delete the trigrams which the record no longer has
+		for t in o.difference(&n) {
+			txn.del(self.key(t, rid)?).await?;
+		}
+		// Add the trigrams which the record did not have, or all of them if the number
+		// of trigrams of the record, which each entry stores, has changed
+		let val = revision::to_vec(&(rid.clone(), n.len() as u64))?;
+		for t in n.iter().filter(|t| o.len() != n.len() || !o.contains(*t)) {
+			txn.set(self.key(t, rid)?, val.clone(), None).await?;
+		}
+		Ok(())
+	}
+}
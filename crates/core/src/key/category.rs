@@ -22,6 +22,8 @@ pub enum Category {
 	AccessGrant,
 	/// crate::key::root::nd                 /!nd{nd}
 	Node,
+	/// crate::key::root::eq                 /!eq{ts}{id}
+	EventQueue,
 	/// crate::key::root::ni                 /!ni
 	NamespaceIdentifier,
 	/// crate::key::root::ns                 /!ns{ns}
@@ -72,6 +74,8 @@ pub enum Category {
 	DatabaseApi,
 	/// crate::key::database::az             /*{ns}*{db}!az{az}
 	DatabaseAnalyzer,
+	/// crate::key::database::ev             /*{ns}*{db}!ev{ev}
+	DatabaseEvent,
 	/// crate::key::database::fc             /*{ns}*{db}!fn{fc}
 	DatabaseFunction,
 	/// crate::key::database::ml             /*{ns}*{db}!ml{ml}{vn}
@@ -82,6 +86,8 @@ pub enum Category {
 	DatabaseTable,
 	/// crate::key::database::ts             /*{ns}*{db}!ts{ts}
 	DatabaseTimestamp,
+	/// crate::key::database::tt             /*{ns}*{db}!tt{tt}
+	DatabaseTest,
 	/// crate::key::database::us             /*{ns}*{db}!us{us}
 	DatabaseUser,
 	/// crate::key::database::vs             /*{ns}*{db}!vs
@@ -175,6 +181,7 @@ impl Display for Category {
 			Self::AccessRoot => "AccessRoot",
 			Self::AccessGrant => "AccessGrant",
 			Self::Node => "Node",
+			Self::EventQueue => "EventQueue",
 			Self::NamespaceIdentifier => "NamespaceIdentifier",
 			Self::Namespace => "Namespace",
 			Self::User => "User",
@@ -193,12 +200,14 @@ impl Display for Category {
 			Self::DatabaseAccessGrant => "DatabaseAccessGrant",
 			Self::DatabaseApi => "DatabaseApi",
 			Self::DatabaseAnalyzer => "DatabaseAnalyzer",
+			Self::DatabaseEvent => "DatabaseEvent",
 			Self::DatabaseFunction => "DatabaseFunction",
 			Self::DatabaseModel => "DatabaseModel",
 			Self::DatabaseParameter => "DatabaseParameter",
 			Self::DatabaseTable => "DatabaseTable",
 			Self::DatabaseTableIdentifier => "DatabaseTableIdentifier",
 			Self::DatabaseTimestamp => "DatabaseTimestamp",
+			Self::DatabaseTest => "DatabaseTest",
 			Self::DatabaseUser => "DatabaseUser",
 			Self::DatabaseVersionstamp => "DatabaseVersionstamp",
 			Self::DatabaseConfig => "DatabaseConfig",
@@ -0,0 +1,79 @@
+//! Stores a DEFINE EVENT ON DATABASE definition
+use crate::err::Error;
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::{impl_key, KeyEncode};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Ev<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub ev: &'a str,
+}
+impl_key!(Ev<'a>);
+
+pub fn new<'a>(ns: &'a str, db: &'a str, ev: &'a str) -> Ev<'a> {
+	Ev::new(ns, db, ev)
+}
+
+pub fn prefix(ns: &str, db: &str) -> Result<Vec<u8>, Error> {
+	let mut k = super::all::new(ns, db).encode()?;
+	k.extend_from_slice(b"!ev\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: &str, db: &str) -> Result<Vec<u8>, Error> {
+	let mut k = super::all::new(ns, db).encode()?;
+	k.extend_from_slice(b"!ev\xff");
+	Ok(k)
+}
+
+impl Categorise for Ev<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseEvent
+	}
+}
+
+impl<'a> Ev<'a> {
+	pub fn new(ns: &'a str, db: &'a str, ev: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'e',
+			_e: b'v',
+			ev,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::KeyDecode;
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Ev::new(
+			"testns",
+			"testdb",
+			"testev",
+		);
+		let enc = Ev::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\0*testdb\0!evtestev\0");
+
+		let dec = Ev::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
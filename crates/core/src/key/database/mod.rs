@@ -4,11 +4,13 @@ pub mod all;
 pub mod ap;
 pub mod az;
 pub mod cg;
+pub mod ev;
 pub mod fc;
 pub mod ml;
 pub mod pa;
 pub mod tb;
 pub mod ti;
 pub mod ts;
+pub mod tt;
 pub mod us;
 pub mod vs;
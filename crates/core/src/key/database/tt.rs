@@ -0,0 +1,90 @@
+//! Stores a DEFINE TEST config definition
+use crate::err::Error;
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::{impl_key, KeyEncode};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Tt<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub tt: &'a str,
+}
+impl_key!(Tt<'a>);
+
+pub fn new<'a>(ns: &'a str, db: &'a str, tt: &'a str) -> Tt<'a> {
+	Tt::new(ns, db, tt)
+}
+
+pub fn prefix(ns: &str, db: &str) -> Result<Vec<u8>, Error> {
+	let mut k = super::all::new(ns, db).encode()?;
+	k.extend_from_slice(b"!tt\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: &str, db: &str) -> Result<Vec<u8>, Error> {
+	let mut k = super::all::new(ns, db).encode()?;
+	k.extend_from_slice(b"!tt\xff");
+	Ok(k)
+}
+
+impl Categorise for Tt<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseTest
+	}
+}
+
+impl<'a> Tt<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tt: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b't',
+			_e: b't',
+			tt,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::KeyDecode;
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Tt::new(
+			"testns",
+			"testdb",
+			"testtt",
+		);
+		let enc = Tt::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\x00*testdb\x00!tttesttt\x00");
+		let dec = Tt::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix("testns", "testdb").unwrap();
+		assert_eq!(val, b"/*testns\0*testdb\0!tt\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix("testns", "testdb").unwrap();
+		assert_eq!(val, b"/*testns\0*testdb\0!tt\xff");
+	}
+}
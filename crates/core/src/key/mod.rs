@@ -4,6 +4,7 @@
 ///
 /// crate::key::root::all                /
 /// crate::key::root::ac                 /!ac{ac}
+/// crate::key::root::eq                 /!eq{ts}{id}
 /// crate::key::root::nd                 /!nd{nd}
 /// crate::key::root::ni                 /!ni
 /// crate::key::root::ns                 /!ns{ns}
@@ -28,12 +29,14 @@
 /// crate::key::database::all            /*{ns}*{db}
 /// crate::key::database::ac             /*{ns}*{db}!ac{ac}
 /// crate::key::database::az             /*{ns}*{db}!az{az}
+/// crate::key::database::ev             /*{ns}*{db}!ev{ev}
 /// crate::key::database::fc             /*{ns}*{db}!fn{fc}
 /// crate::key::database::ml             /*{ns}*{db}!ml{ml}{vn}
 /// crate::key::database::pa             /*{ns}*{db}!pa{pa}
 /// crate::key::database::tb             /*{ns}*{db}!tb{tb}
 /// crate::key::database::ti             /+{ns id}*{db id}!ti
 /// crate::key::database::ts             /*{ns}*{db}!ts{ts}
+/// crate::key::database::tt             /*{ns}*{db}!tt{tt}
 /// crate::key::database::us             /*{ns}*{db}!us{us}
 /// crate::key::database::vs             /*{ns}*{db}!vs
 /// crate::key::database::cg             /*{ns}*{db}!cg{ty}
@@ -0,0 +1,81 @@
+//! Stores the asynchronous events which are waiting to be processed
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::impl_key;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// The events are ordered by the time at which they are due to be processed,
+// in nanoseconds since the unix epoch, so that the due events are scanned first.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Eq {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub ts: u64,
+	#[serde(with = "uuid::serde::compact")]
+	pub id: Uuid,
+}
+impl_key!(Eq);
+
+pub fn new(ts: u64, id: Uuid) -> Eq {
+	Eq::new(ts, id)
+}
+
+pub fn prefix() -> Vec<u8> {
+	let mut k = crate::key::root::all::kv();
+	k.extend_from_slice(b"!eq\x00");
+	k
+}
+
+pub fn suffix() -> Vec<u8> {
+	let mut k = crate::key::root::all::kv();
+	k.extend_from_slice(b"!eq\xff");
+	k
+}
+
+impl Categorise for Eq {
+	fn categorise(&self) -> Category {
+		Category::EventQueue
+	}
+}
+
+impl Eq {
+	pub fn new(ts: u64, id: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'e',
+			_c: b'q',
+			ts,
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::{KeyDecode, KeyEncode};
+	#[test]
+	fn key() {
+		use super::*;
+		let val = Eq::new(1_000, Uuid::default());
+		let enc = val.encode().unwrap();
+		assert_eq!(
+			enc,
+			b"/!eq\x00\x00\x00\x00\x00\x00\x03\xe8\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00"
+		);
+		let dec = Eq::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn order() {
+		use super::*;
+		let a = Eq::new(1_000, Uuid::max()).encode().unwrap();
+		let b = Eq::new(2_000, Uuid::nil()).encode().unwrap();
+		assert!(a < b);
+	}
+}
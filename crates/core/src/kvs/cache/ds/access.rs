@@ -0,0 +1,122 @@
+use crate::kvs::Key;
+use dashmap::DashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// The last access times of the records in cache tables
+///
+/// At most [`RECORD_ACCESS_CACHE_SIZE`](crate::cnf::RECORD_ACCESS_CACHE_SIZE) access times are
+/// kept. The least recently accessed records are dropped first, and are then treated as if they
+/// were not accessed since the datastore started.
+pub(crate) struct RecordAccess {
+	/// The logical time at which each record was last accessed
+	times: DashMap<Key, u64>,
+	/// The logical clock used for record access times
+	clock: AtomicU64,
+	/// Whether the oldest access times are being dropped
+	pruning: AtomicBool,
+	/// The number of access times to keep
+	capacity: u64,
+}
+
+impl RecordAccess {
+	pub(super) fn new(capacity: usize) -> Self {
+		Self {
+			times: DashMap::new(),
+			clock: AtomicU64::new(0),
+			pruning: AtomicBool::new(false),
+			capacity: capacity.max(1) as u64,
+		}
+	}
+
+	/// Marks a record as accessed
+	pub(super) fn touch(&self, key: Key) {
+		let time = self.clock.fetch_add(1, Ordering::Relaxed) + 1;
+		self.times.insert(key, time);
+		// The map is pruned once it holds twice its capacity, so that pruning runs rarely
+		if self.times.len() as u64 > self.capacity * 2
+			&& !self.pruning.swap(true, Ordering::Acquire)
+		{
+			// Only the records accessed within the last `capacity` ticks can be kept
+			let oldest = time.saturating_sub(self.capacity);
+			self.times.retain(|_, v| *v > oldest);
+			self.pruning.store(false, Ordering::Release);
+		}
+	}
+
+	/// Forgets the access time of a record
+	pub(super) fn forget(&self, key: &[u8]) {
+		self.times.remove(key);
+	}
+
+	/// Forgets the access times of all records with keys starting with the prefix
+	pub(super) fn forget_prefix(&self, prefix: &[u8]) {
+		self.times.retain(|k, _| !k.starts_with(prefix));
+	}
+
+	/// Fetches the logical time at which a record was last accessed
+	pub(super) fn get(&self, key: &[u8]) -> u64 {
+		self.times.get(key).map(|v| *v).unwrap_or_default()
+	}
+}
+
+/// A change to the record access times, which is made when its transaction is committed
+pub(crate) struct PendingAccess {
+	access: Arc<RecordAccess>,
+	change: AccessChange,
+}
+
+pub(super) enum AccessChange {
+	/// A record was written
+	Touch(Key),
+	/// A record was deleted
+	Forget(Key),
+	/// A table was removed
+	ForgetPrefix(Key),
+}
+
+impl PendingAccess {
+	pub(super) fn new(access: Arc<RecordAccess>, change: AccessChange) -> Self {
+		Self {
+			access,
+			change,
+		}
+	}
+
+	/// Applies the change, once the transaction which made it is committed
+	pub(crate) fn apply(self) {
+		match self.change {
+			AccessChange::Touch(key) => self.access.touch(key),
+			AccessChange::Forget(key) => self.access.forget(&key),
+			AccessChange::ForgetPrefix(prefix) => self.access.forget_prefix(&prefix),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn access_times_are_bounded() {
+		let access = RecordAccess::new(2);
+		for i in 0..5u8 {
+			access.touch(vec![i]);
+		}
+		// The map was pruned to the most recently accessed records
+		assert_eq!(access.get(&[0]), 0);
+		assert_eq!(access.get(&[3]), 4);
+		assert_eq!(access.get(&[4]), 5);
+		assert!(access.times.len() <= 4);
+	}
+
+	#[test]
+	fn access_times_are_forgotten_by_prefix() {
+		let access = RecordAccess::new(10);
+		access.touch(b"a1".to_vec());
+		access.touch(b"b1".to_vec());
+		access.forget_prefix(b"a");
+		assert_eq!(access.get(b"a1"), 0);
+		assert_eq!(access.get(b"b1"), 2);
+	}
+}
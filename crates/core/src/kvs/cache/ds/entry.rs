@@ -13,7 +13,7 @@ use uuid::Uuid;
 pub(crate) enum Entry {
 	/// A cached entry of any type
 	Any(Arc<dyn Any + Send + Sync>),
-	/// A slice of DefineEventStatement specified on a table.
+	/// A slice of DefineEventStatement specified on a table or database.
 	Evs(Arc<[DefineEventStatement]>),
 	/// A slice of DefineFieldStatement specified on a table.
 	Fds(Arc<[DefineFieldStatement]>),
@@ -7,6 +7,8 @@ pub(crate) enum Key {
 	Db(String, String),
 	/// A cache key for a table
 	Tb(String, String, String),
+	/// A cache key for events (on a database)
+	Dbe(String, String),
 	/// A cache key for events (on a table)
 	Evs(String, String, String, Uuid),
 	/// A cache key for fieds (on a table)
@@ -27,6 +29,7 @@ impl<'a> From<Lookup<'a>> for Key {
 		match value {
 			Lookup::Db(a, b) => Key::Db(a.to_string(), b.to_string()),
 			Lookup::Tb(a, b, c) => Key::Tb(a.to_string(), b.to_string(), c.to_string()),
+			Lookup::Dbe(a, b) => Key::Dbe(a.to_string(), b.to_string()),
 			Lookup::Evs(a, b, c, d) => Key::Evs(a.to_string(), b.to_string(), c.to_string(), d),
 			Lookup::Fds(a, b, c, d) => Key::Fds(a.to_string(), b.to_string(), c.to_string(), d),
 			Lookup::Fts(a, b, c, d) => Key::Fts(a.to_string(), b.to_string(), c.to_string(), d),
@@ -8,6 +8,8 @@ pub(crate) enum Lookup<'a> {
 	Db(&'a str, &'a str),
 	/// A cache key for a table
 	Tb(&'a str, &'a str, &'a str),
+	/// A cache key for events (on a database)
+	Dbe(&'a str, &'a str),
 	/// A cache key for events (on a table)
 	Evs(&'a str, &'a str, &'a str, Uuid),
 	/// A cache key for fields (on a table)
@@ -28,6 +30,7 @@ impl Equivalent<Key> for Lookup<'_> {
 		match (self, key) {
 			(Self::Db(la, lb), Key::Db(ka, kb)) => la == ka && lb == kb,
 			(Self::Tb(la, lb, lc), Key::Tb(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::Dbe(la, lb), Key::Dbe(ka, kb)) => la == ka && lb == kb,
 			(Self::Evs(la, lb, lc, ld), Key::Evs(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Fds(la, lb, lc, ld), Key::Fds(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Fts(la, lb, lc, ld), Key::Fts(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
@@ -1,11 +1,16 @@
+mod access;
 mod entry;
 mod key;
 mod lookup;
 mod weight;
 
 use crate::err::Error;
+use crate::kvs::Key;
+pub(crate) use access::PendingAccess;
+use access::{AccessChange, RecordAccess};
 pub(crate) use entry::Entry;
 pub(crate) use lookup::Lookup;
+use std::sync::Arc;
 use uuid::Uuid;
 
 pub(crate) type Cache = quick_cache::sync::Cache<key::Key, Entry, weight::Weight>;
@@ -13,6 +18,8 @@ pub(crate) type Cache = quick_cache::sync::Cache<key::Key, Entry, weight::Weight
 pub struct DatastoreCache {
 	/// Store the cache entries
 	cache: Cache,
+	/// Store the last access times of records in cache tables
+	access: Arc<RecordAccess>,
 }
 
 impl DatastoreCache {
@@ -25,6 +32,7 @@ impl DatastoreCache {
 		);
 		Self {
 			cache,
+			access: Arc::new(RecordAccess::new(*crate::cnf::RECORD_ACCESS_CACHE_SIZE)),
 		}
 	}
 
@@ -44,6 +52,12 @@ impl DatastoreCache {
 		self.cache.remove(&key);
 	}
 
+	/// Clear the cache entry for the events on a database
+	pub(crate) fn clear_db_events(&self, ns: &str, db: &str) {
+		let key = Lookup::Dbe(ns, db);
+		self.cache.remove(&key);
+	}
+
 	/// Clear all items from the datastore cache
 	pub(crate) fn clear(&self) {
 		self.cache.clear();
@@ -64,6 +78,32 @@ impl DatastoreCache {
 		Ok(version)
 	}
 
+	/// Marks a record in a cache table as read
+	pub(crate) fn touch_record(&self, key: Key) {
+		self.access.touch(key);
+	}
+
+	/// Marks a record in a cache table as written, once the transaction is committed
+	pub(crate) fn write_record(&self, key: Key) -> PendingAccess {
+		PendingAccess::new(self.access.clone(), AccessChange::Touch(key))
+	}
+
+	/// Forgets the access time of a deleted record, once the transaction is committed
+	pub(crate) fn forget_record(&self, key: Key) -> PendingAccess {
+		PendingAccess::new(self.access.clone(), AccessChange::Forget(key))
+	}
+
+	/// Forgets the access times of the records of a removed table, once the transaction is committed
+	pub(crate) fn forget_records(&self, prefix: Key) -> PendingAccess {
+		PendingAccess::new(self.access.clone(), AccessChange::ForgetPrefix(prefix))
+	}
+
+	/// Fetches the logical time at which a record was last accessed,
+	/// which is zero if it was not accessed recently
+	pub(crate) fn record_access(&self, key: &[u8]) -> u64 {
+		self.access.get(key)
+	}
+
 	pub(crate) fn new_live_queries_version(&self, ns: &str, db: &str, tb: &str) {
 		let key = Lookup::Lvv(ns, db, tb);
 		self.insert(key, Entry::Lvv(Uuid::now_v7()));
@@ -14,6 +14,7 @@ use crate::sql::statements::DefineModelStatement;
 use crate::sql::statements::DefineNamespaceStatement;
 use crate::sql::statements::DefineParamStatement;
 use crate::sql::statements::DefineTableStatement;
+use crate::sql::statements::DefineTestStatement;
 use crate::sql::statements::DefineUserStatement;
 use crate::sql::statements::LiveStatement;
 use crate::sql::Value;
@@ -65,8 +66,10 @@ pub(crate) enum Entry {
 	Cgs(Arc<[DefineConfigStatement]>),
 	/// A slice of DefineParamStatement specified on a database.
 	Pas(Arc<[DefineParamStatement]>),
-	/// A slice of DefineEventStatement specified on a table.
+	/// A slice of DefineEventStatement specified on a table or database.
 	Evs(Arc<[DefineEventStatement]>),
+	/// A slice of DefineTestStatement specified on a database.
+	Tts(Arc<[DefineTestStatement]>),
 	/// A slice of DefineFieldStatement specified on a table.
 	Fds(Arc<[DefineFieldStatement]>),
 	/// A slice of DefineTableStatement specified on a table.
@@ -208,6 +211,14 @@ impl Entry {
 			_ => Err(fail!("Unable to convert type into Entry::Fcs")),
 		}
 	}
+	/// Converts this cache entry into a slice of [`DefineTestStatement`].
+	/// This panics if called on a cache entry that is not an [`Entry::Tts`].
+	pub(crate) fn try_into_tts(self) -> Result<Arc<[DefineTestStatement]>, Error> {
+		match self {
+			Entry::Tts(v) => Ok(v),
+			_ => Err(fail!("Unable to convert type into Entry::Tts")),
+		}
+	}
 	/// Converts this cache entry into a slice of [`DefineParamStatement`].
 	/// This panics if called on a cache entry that is not an [`Entry::Pas`].
 	pub(crate) fn try_into_pas(self) -> Result<Arc<[DefineParamStatement]>, Error> {
@@ -40,6 +40,10 @@ pub(crate) enum Key {
 	Cgs(String, String),
 	/// A cache key for parameters (on a database)
 	Pas(String, String),
+	/// A cache key for events (on a database)
+	Des(String, String),
+	/// A cache key for tests (on a database)
+	Tts(String, String),
 	/// A cache key for tables
 	Tbs(String, String),
 	/// A cache key for events (on a table)
@@ -88,6 +92,10 @@ pub(crate) enum Key {
 	Cg(String, String, String),
 	/// A cache key for a parameter (on a database)
 	Pa(String, String, String),
+	/// A cache key for an event (on a database)
+	De(String, String, String),
+	/// A cache key for a test (on a database)
+	Tt(String, String, String),
 	/// A cache key for a table
 	Tb(String, String, String),
 	/// A cache key for an event (on a table)
@@ -123,6 +131,8 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Mls(a, b) => Key::Mls(a.to_string(), b.to_string()),
 			Lookup::Cgs(a, b) => Key::Cgs(a.to_string(), b.to_string()),
 			Lookup::Pas(a, b) => Key::Pas(a.to_string(), b.to_string()),
+			Lookup::Des(a, b) => Key::Des(a.to_string(), b.to_string()),
+			Lookup::Tts(a, b) => Key::Tts(a.to_string(), b.to_string()),
 			Lookup::Tbs(a, b) => Key::Tbs(a.to_string(), b.to_string()),
 			Lookup::Evs(a, b, c) => Key::Evs(a.to_string(), b.to_string(), c.to_string()),
 			Lookup::Fds(a, b, c) => Key::Fds(a.to_string(), b.to_string(), c.to_string()),
@@ -148,6 +158,8 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Ml(a, b, c, d) => Key::Ml(a.to_string(), b.to_string(), c.to_string(), d.to_string()),
 			Lookup::Cg(a, b, c) => Key::Cg(a.to_string(), b.to_string(), c.to_string()),
 			Lookup::Pa(a, b, c) => Key::Pa(a.to_string(), b.to_string(), c.to_string()),
+			Lookup::De(a, b, c) => Key::De(a.to_string(), b.to_string(), c.to_string()),
+			Lookup::Tt(a, b, c) => Key::Tt(a.to_string(), b.to_string(), c.to_string()),
 			Lookup::Tb(a, b, c) => Key::Tb(a.to_string(), b.to_string(), c.to_string()),
 			Lookup::Ev(a, b, c, d) => Key::Ev(a.to_string(), b.to_string(), c.to_string(), d.to_string()),
 			Lookup::Fd(a, b, c, d) => Key::Fd(a.to_string(), b.to_string(), c.to_string(), d.to_string()),
@@ -41,6 +41,10 @@ pub(crate) enum Lookup<'a> {
 	Cgs(&'a str, &'a str),
 	/// A cache key for parameters (on a database)
 	Pas(&'a str, &'a str),
+	/// A cache key for events (on a database)
+	Des(&'a str, &'a str),
+	/// A cache key for tests (on a database)
+	Tts(&'a str, &'a str),
 	/// A cache key for tables
 	Tbs(&'a str, &'a str),
 	/// A cache key for events (on a table)
@@ -89,6 +93,10 @@ pub(crate) enum Lookup<'a> {
 	Cg(&'a str, &'a str, &'a str),
 	/// A cache key for a parameter (on a database)
 	Pa(&'a str, &'a str, &'a str),
+	/// A cache key for an event (on a database)
+	De(&'a str, &'a str, &'a str),
+	/// A cache key for a test (on a database)
+	Tt(&'a str, &'a str, &'a str),
 	/// A cache key for a table
 	Tb(&'a str, &'a str, &'a str),
 	/// A cache key for an event (on a table)
@@ -124,6 +132,8 @@ impl Equivalent<Key> for Lookup<'_> {
 			(Self::Mls(la, lb), Key::Mls(ka, kb)) => la == ka && lb == kb,
 			(Self::Cgs(la, lb), Key::Cgs(ka, kb)) => la == ka && lb == kb,
 			(Self::Pas(la, lb), Key::Pas(ka, kb)) => la == ka && lb == kb,
+			(Self::Des(la, lb), Key::Des(ka, kb)) => la == ka && lb == kb,
+			(Self::Tts(la, lb), Key::Tts(ka, kb)) => la == ka && lb == kb,
 			(Self::Tbs(la, lb), Key::Tbs(ka, kb)) => la == ka && lb == kb,
 			(Self::Evs(la, lb, lc), Key::Evs(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Fds(la, lb, lc), Key::Fds(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
@@ -149,6 +159,8 @@ impl Equivalent<Key> for Lookup<'_> {
 			(Self::Ml(la, lb, lc, ld), Key::Ml(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Cg(la, lb, lc), Key::Cg(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Pa(la, lb, lc), Key::Pa(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::De(la, lb, lc), Key::De(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::Tt(la, lb, lc), Key::Tt(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Tb(la, lb, lc), Key::Tb(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Ev(la, lb, lc, ld), Key::Ev(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Fd(la, lb, lc, ld), Key::Fd(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
@@ -11,7 +11,7 @@ use crate::dbs::capabilities::{
 };
 use crate::dbs::node::Timestamp;
 use crate::dbs::{
-	Attach, Capabilities, Executor, Notification, Options, Response, Session, Variables,
+	Attach, Capabilities, Durability, Executor, Notification, Options, Response, Session, Variables,
 };
 use crate::err::Error;
 #[cfg(feature = "jwks")]
@@ -24,8 +24,11 @@ use crate::kvs::clock::SizedClock;
 use crate::kvs::clock::SystemClock;
 #[cfg(not(target_family = "wasm"))]
 use crate::kvs::index::IndexBuilder;
-use crate::kvs::{LockType, LockType::*, TransactionType, TransactionType::*};
-use crate::sql::{statements::DefineUserStatement, Base, Query, Value};
+use crate::kvs::stats::{Counters, Statistics};
+use crate::kvs::{
+	LockType, LockType::*, Mirrored, RetryPolicy, TransactionType, TransactionType::*, WriteLimits,
+};
+use crate::sql::{statements::DefineUserStatement, Base, Query, Statement, Value};
 use crate::syn;
 use crate::syn::parser::{ParserSettings, StatementStream};
 use async_channel::{Receiver, Sender};
@@ -54,6 +57,9 @@ const TARGET: &str = "surrealdb::core::kvs::ds";
 // If there are an infinite number of heartbeats, then we want to go batch-by-batch spread over several checks
 const LQ_CHANNEL_SIZE: usize = 15_000;
 
+// The maximum number of write queries which can be waiting to be mirrored
+const MIRROR_CHANNEL_SIZE: usize = 10_000;
+
 // The role assigned to the initial user created when starting the server with credentials for the first time
 const INITIAL_USER_ROLE: &str = "owner";
 
@@ -72,10 +78,18 @@ pub struct Datastore {
 	query_timeout: Option<Duration>,
 	/// The maximum duration timeout for running multiple statements in a transaction.
 	transaction_timeout: Option<Duration>,
+	/// The policy for retrying transactions which fail due to a conflict.
+	retry_policy: Option<RetryPolicy>,
+	/// The rate limits for writes to specific tables.
+	write_limits: Option<Arc<WriteLimits>>,
+	/// The counters of the work which this datastore has done.
+	counters: Counters,
 	/// The security and feature capabilities for this datastore.
 	capabilities: Arc<Capabilities>,
 	// Whether this datastore enables live query notifications to subscribers.
 	notification_channel: Option<(Sender<Notification>, Receiver<Notification>)>,
+	// Whether this datastore mirrors write queries to subscribers.
+	mirror_channel: Option<(Sender<Mirrored>, Receiver<Mirrored>)>,
 	// The index store cache
 	index_stores: IndexStores,
 	// The cross transaction cache
@@ -91,8 +105,25 @@ pub struct Datastore {
 	temporary_directory: Option<Arc<PathBuf>>,
 }
 
+/// The size of the files of a datastore, before and after it was compacted
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Compaction {
+	/// The size in bytes of the datastore files before the compaction
+	pub before: u64,
+	/// The size in bytes of the datastore files after the compaction
+	pub after: u64,
+}
+
+impl Compaction {
+	/// The number of bytes which the compaction reclaimed
+	pub fn reclaimed(&self) -> u64 {
+		self.before.saturating_sub(self.after)
+	}
+}
+
 #[derive(Clone)]
-pub(super) struct TransactionFactory {
+pub(crate) struct TransactionFactory {
 	// Clock for tracking time. It is read only and accessible to all transactions. It is behind a mutex as tests may write to it.
 	clock: Arc<SizedClock>,
 	// The inner datastore type
@@ -100,12 +131,30 @@ pub(super) struct TransactionFactory {
 }
 
 impl TransactionFactory {
-	#[allow(unreachable_code)]
 	pub async fn transaction(
 		&self,
 		write: TransactionType,
 		lock: LockType,
 	) -> Result<Transaction, Error> {
+		self.durable_transaction(write, lock, Durability::Applied).await
+	}
+
+	/// Creates a new transaction, which is committed with the specified durability
+	#[allow(unreachable_code)]
+	pub async fn durable_transaction(
+		&self,
+		write: TransactionType,
+		lock: LockType,
+		durability: Durability,
+	) -> Result<Transaction, Error> {
+		// Specify if the commit is synced to disk, or
+		// if the storage engine configuration is used
+		#[allow(unused_variables)]
+		let sync = match durability {
+			Durability::Memory => Some(false),
+			Durability::Applied => None,
+			Durability::Replicated => Some(true),
+		};
 		// Specify if the transaction is writeable
 		#[allow(unused_variables)]
 		let write = match write {
@@ -128,7 +177,7 @@ impl TransactionFactory {
 			}
 			#[cfg(feature = "kv-rocksdb")]
 			DatastoreFlavor::RocksDB(v) => {
-				let tx = v.transaction(write, lock).await?;
+				let tx = v.transaction(write, lock, sync).await?;
 				(super::tr::Inner::RocksDB(tx), true)
 			}
 			#[cfg(feature = "kv-indxdb")]
@@ -148,7 +197,7 @@ impl TransactionFactory {
 			}
 			#[cfg(feature = "kv-surrealkv")]
 			DatastoreFlavor::SurrealKV(v) => {
-				let tx = v.transaction(write, lock).await?;
+				let tx = v.transaction(write, lock, sync).await?;
 				(super::tr::Inner::SurrealKV(tx), true)
 			}
 			#[cfg(feature = "kv-surrealcs")]
@@ -413,7 +462,11 @@ impl Datastore {
 				auth_enabled: false,
 				query_timeout: None,
 				transaction_timeout: None,
+				retry_policy: None,
+				write_limits: None,
+				counters: Counters::default(),
 				notification_channel: None,
+				mirror_channel: None,
 				capabilities: Arc::new(Capabilities::default()),
 				index_stores: IndexStores::default(),
 				#[cfg(not(target_family = "wasm"))]
@@ -437,8 +490,12 @@ impl Datastore {
 			auth_enabled: self.auth_enabled,
 			query_timeout: self.query_timeout,
 			transaction_timeout: self.transaction_timeout,
+			retry_policy: self.retry_policy,
+			write_limits: self.write_limits,
+			counters: self.counters,
 			capabilities: self.capabilities,
 			notification_channel: self.notification_channel,
+			mirror_channel: self.mirror_channel,
 			index_stores: Default::default(),
 			#[cfg(not(target_family = "wasm"))]
 			index_builder: IndexBuilder::new(self.transaction_factory.clone()),
@@ -469,6 +526,12 @@ impl Datastore {
 		self
 	}
 
+	/// Specify whether this datastore should mirror write queries to subscribers
+	pub fn with_mirroring(mut self) -> Self {
+		self.mirror_channel = Some(async_channel::bounded(MIRROR_CHANNEL_SIZE));
+		self
+	}
+
 	/// Set a global query timeout for this Datastore
 	pub fn with_query_timeout(mut self, duration: Option<Duration>) -> Self {
 		self.query_timeout = duration;
@@ -481,6 +544,18 @@ impl Datastore {
 		self
 	}
 
+	/// Set a policy for retrying transactions which fail due to a read or write conflict
+	pub fn with_retry_policy(mut self, policy: Option<RetryPolicy>) -> Self {
+		self.retry_policy = policy;
+		self
+	}
+
+	/// Set rate limits for writes to specific tables on this Datastore
+	pub fn with_write_limits(mut self, limits: WriteLimits) -> Self {
+		self.write_limits = Some(Arc::new(limits));
+		self
+	}
+
 	/// Set whether authentication is enabled for this Datastore
 	pub fn with_auth_enabled(mut self, enabled: bool) -> Self {
 		self.auth_enabled = enabled;
@@ -513,6 +588,16 @@ impl Datastore {
 		self.id
 	}
 
+	/// The number of queries and transaction conflicts since this Datastore was started
+	pub fn statistics(&self) -> Statistics {
+		self.counters.statistics()
+	}
+
+	/// The policy for retrying transactions which fail due to a conflict
+	pub(crate) fn retry_policy(&self) -> Option<&RetryPolicy> {
+		self.retry_policy.as_ref()
+	}
+
 	/// Does the datastore allow excecuting an RPC method?
 	pub(crate) fn allows_rpc_method(&self, method_target: &MethodTarget) -> bool {
 		self.capabilities.allows_rpc_method(method_target)
@@ -541,6 +626,24 @@ impl Datastore {
 		&self.capabilities
 	}
 
+	/// The metadata describing this server, as seen by the given session
+	///
+	/// Anonymous sessions only receive the version, so that the build,
+	/// storage engine, and capabilities are not exposed to guests.
+	pub fn server_info(&self, sess: &Session, version: Value) -> Value {
+		if self.auth_enabled && sess.au.is_anon() {
+			return Value::from(map! {
+				"version".to_string() => version,
+			});
+		}
+		Value::from(map! {
+			"version".to_string() => version,
+			"build".to_string() => format!("{} on {}", crate::env::os(), crate::env::arch()).into(),
+			"storage".to_string() => self.to_string().into(),
+			"capabilities".to_string() => self.get_capabilities().into(),
+		})
+	}
+
 	#[cfg(feature = "jwks")]
 	pub(crate) fn jwks_cache(&self) -> &Arc<RwLock<JwksCache>> {
 		&self.jwks_cache
@@ -762,6 +865,56 @@ impl Datastore {
 		}
 	}
 
+	/// Checks if committed writes can be synced to disk, or are replicated before a commit is acknowledged
+	///
+	/// The in-memory and IndexedDB storage engines never sync their writes.
+	pub(crate) fn can_sync(&self) -> bool {
+		match self.transaction_factory.flavor.as_ref() {
+			#[cfg(feature = "kv-rocksdb")]
+			DatastoreFlavor::RocksDB(_) => true,
+			#[cfg(feature = "kv-surrealkv")]
+			DatastoreFlavor::SurrealKV(_) => true,
+			#[cfg(feature = "kv-tikv")]
+			DatastoreFlavor::TiKV(_) => true,
+			#[cfg(feature = "kv-fdb")]
+			DatastoreFlavor::FoundationDB(_) => true,
+			#[cfg(feature = "kv-surrealcs")]
+			DatastoreFlavor::SurrealCS(_) => true,
+			#[allow(unreachable_patterns)]
+			_ => false,
+		}
+	}
+
+	/// Compacts the files of the storage engine, reclaiming the space of deleted data
+	///
+	/// Only the storage engines which store their files on this machine can be compacted.
+	pub async fn compact(&self) -> Result<Compaction, Error> {
+		match self.transaction_factory.flavor.as_ref() {
+			#[cfg(feature = "kv-rocksdb")]
+			DatastoreFlavor::RocksDB(v) => {
+				let before = dir_size(v.path())?;
+				v.compact().await?;
+				Ok(Compaction {
+					before,
+					after: dir_size(v.path())?,
+				})
+			}
+			#[cfg(feature = "kv-surrealkv")]
+			DatastoreFlavor::SurrealKV(v) => {
+				let before = dir_size(v.path())?;
+				v.compact().await?;
+				Ok(Compaction {
+					before,
+					after: dir_size(v.path())?,
+				})
+			}
+			#[allow(unreachable_patterns)]
+			_ => Err(Error::Unimplemented(format!(
+				"Compaction is not supported by the {self} storage engine"
+			))),
+		}
+	}
+
 	/// Create a new transaction on this datastore
 	///
 	/// ```rust,no_run
@@ -785,6 +938,16 @@ impl Datastore {
 		self.transaction_factory.transaction(write, lock).await
 	}
 
+	/// Create a new transaction on this datastore, which is committed with the specified durability
+	pub(crate) async fn durable_transaction(
+		&self,
+		write: TransactionType,
+		lock: LockType,
+		durability: Durability,
+	) -> Result<Transaction, Error> {
+		self.transaction_factory.durable_transaction(write, lock, durability).await
+	}
+
 	/// Parse and execute an SQL query
 	///
 	/// ```rust,no_run
@@ -913,7 +1076,7 @@ impl Datastore {
 			}
 		});
 
-		Executor::execute_stream(self, Arc::new(ctx), opt, stream).await
+		Executor::execute_stream(self, Arc::new(ctx), opt, stream, None).await
 	}
 
 	/// Execute a pre-parsed SQL query
@@ -955,6 +1118,14 @@ impl Datastore {
 		// Create a new query options
 		let opt = self.setup_options(sess);
 
+		// Keep a copy of the query if it should be mirrored
+		let mirrored = match &self.mirror_channel {
+			Some(_) if ast.iter().any(Statement::writeable) => {
+				Some(Mirrored::new(sess, ast.clone(), vars.clone()))
+			}
+			_ => None,
+		};
+
 		// Create a default context
 		let mut ctx = self.setup_ctx()?;
 		// Start an execution context
@@ -962,7 +1133,24 @@ impl Datastore {
 		// Store the query variables
 		vars.attach(&mut ctx)?;
 		// Process all statements
-		Executor::execute(self, ctx.freeze(), opt, ast).await
+		let res = Executor::execute(self, ctx.freeze(), opt, ast, sess.transaction.clone()).await?;
+		// Count the query, and any transaction conflicts
+		self.counters.record(&res);
+		// Mirror the statements which succeeded once the query has run
+		if let Some(mirrored) = mirrored.and_then(|v| v.successful(&res)) {
+			self.mirror(mirrored);
+		}
+		// Return the results
+		Ok(res)
+	}
+
+	/// Send a write query to any mirror subscribers, without waiting for it to be processed
+	fn mirror(&self, mirrored: Mirrored) {
+		if let Some((sender, _)) = &self.mirror_channel {
+			if sender.try_send(mirrored).is_err() {
+				warn!(target: TARGET, "The mirror channel is full, dropping a mirrored query");
+			}
+		}
 	}
 
 	/// Ensure a SQL [`Value`] is fully computed
@@ -1137,6 +1325,11 @@ impl Datastore {
 		self.notification_channel.as_ref().map(|v| v.1.clone())
 	}
 
+	/// Get a receiver for the write queries which should be mirrored
+	pub fn mirrored(&self) -> Option<Receiver<Mirrored>> {
+		self.mirror_channel.as_ref().map(|v| v.1.clone())
+	}
+
 	/// Performs a database import from SQL
 	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
 	pub async fn import(&self, sql: &str, sess: &Session) -> Result<Vec<Response>, Error> {
@@ -1224,6 +1417,7 @@ impl Datastore {
 			.with_auth(sess.au.clone())
 			.with_strict(self.strict)
 			.with_auth_enabled(self.auth_enabled)
+			.with_durability(sess.durability)
 	}
 	pub fn setup_ctx(&self) -> Result<MutableContext, Error> {
 		let mut ctx = MutableContext::from_ds(
@@ -1233,6 +1427,7 @@ impl Datastore {
 			self.cache.clone(),
 			#[cfg(not(target_family = "wasm"))]
 			self.index_builder.clone(),
+			self.transaction_factory.clone(),
 			#[cfg(storage)]
 			self.temporary_directory.clone(),
 		)?;
@@ -1240,6 +1435,10 @@ impl Datastore {
 		if let Some(channel) = &self.notification_channel {
 			ctx.add_notifications(Some(&channel.0));
 		}
+		// Setup the table write limits
+		if let Some(limits) = &self.write_limits {
+			ctx.add_write_limits(limits.clone());
+		}
 		Ok(ctx)
 	}
 
@@ -1257,6 +1456,21 @@ impl Datastore {
 	}
 }
 
+/// The total size in bytes of the files in a directory and its subdirectories
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+fn dir_size(path: &std::path::Path) -> Result<u64, Error> {
+	let mut size = 0;
+	for entry in std::fs::read_dir(path)? {
+		let entry = entry?;
+		let metadata = entry.metadata()?;
+		size += match metadata.is_dir() {
+			true => dir_size(&entry.path())?,
+			false => metadata.len(),
+		};
+	}
+	Ok(size)
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
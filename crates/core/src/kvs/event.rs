@@ -0,0 +1,330 @@
+use crate::cnf::{
+	EVENT_DEAD_LETTER_NAMESPACE, EVENT_DEAD_LETTER_TABLE, EVENT_PROCESS_BATCH_SIZE,
+	EVENT_PROCESS_TIMEOUT_SECS,
+};
+use crate::ctx::Context;
+use crate::dbs::{Options, Session};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Auth, Level, Role};
+use crate::key::root::eq;
+use crate::kvs::{Datastore, Key, KeyEncode, Transaction};
+use crate::kvs::{LockType::*, TransactionType::*};
+use crate::sql::statements::{CreateStatement, DefineEventStatement};
+use crate::sql::{Data, Datetime, Output, Table, Thing, Value, Values};
+use futures::future::join_all;
+use reblessive::tree::Stk;
+use reblessive::TreeStack;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(not(target_family = "wasm"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{error, instrument, trace, warn};
+use uuid::Uuid;
+#[cfg(target_family = "wasm")]
+use wasmtimer::std::{SystemTime, UNIX_EPOCH};
+
+const TARGET: &str = "surrealdb::core::kvs::event";
+
+/// The delay before the first retry of an event which does not specify a BACKOFF
+const DEFAULT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The database in the reserved namespace which the dead-letter table is stored in
+const DEAD_LETTER_DATABASE: &str = "events";
+
+/// The session values which are kept with a queued event
+const SESSION_VALUES: [&str; 4] = ["access", "auth", "token", "session"];
+
+/// An asynchronous table event which is waiting to be processed
+#[revisioned(revision = 2)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
+pub(crate) struct QueuedEvent {
+	pub(crate) ns: String,
+	pub(crate) db: String,
+	pub(crate) tb: String,
+	pub(crate) ev: String,
+	/// The record which triggered the event
+	pub(crate) id: Thing,
+	/// The action which triggered the event, available as `$event`
+	pub(crate) event: Value,
+	/// The document which the event is processed on, available as `$value`
+	pub(crate) value: Value,
+	pub(crate) before: Value,
+	pub(crate) after: Value,
+	/// The number of times the event has been claimed to be processed
+	pub(crate) attempts: u32,
+	/// The authentication of the session which triggered the event
+	#[revision(start = 2)]
+	pub(crate) auth: Option<Auth>,
+	/// The `$access`, `$auth`, `$token` and `$session` values of the session which triggered the event
+	#[revision(start = 2)]
+	pub(crate) values: BTreeMap<String, Value>,
+}
+
+impl QueuedEvent {
+	/// Keeps the session values of the context which triggered the event
+	pub(crate) fn with_values(mut self, ctx: &Context) -> Self {
+		for name in SESSION_VALUES {
+			if let Some(v) = ctx.value(name) {
+				self.values.insert(name.to_owned(), v.clone());
+			}
+		}
+		self
+	}
+
+	/// Adds the event to the queue, to be processed once the delay has passed
+	pub(crate) async fn enqueue(&self, tx: &Transaction, delay: Duration) -> Result<Key, Error> {
+		let ts = now()?.saturating_add(delay.as_nanos() as u64);
+		let key = eq::new(ts, Uuid::now_v7()).encode()?;
+		tx.set(key.clone(), revision::to_vec(self)?, None).await?;
+		Ok(key)
+	}
+
+	/// The session which the event is processed with, as it runs after the session which triggered it
+	///
+	/// Events which were queued before the triggering authentication was kept run as a database editor.
+	fn session(&self) -> Session {
+		let mut sess =
+			Session::for_level(Level::Database(self.ns.clone(), self.db.clone()), Role::Editor);
+		if let Some(auth) = &self.auth {
+			sess.au = Arc::new(auth.clone());
+		}
+		sess
+	}
+}
+
+/// Returns the current time in nanoseconds since the unix epoch
+fn now() -> Result<u64, Error> {
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).map_err(|e| {
+		Error::Internal(format!("Clock may have gone backwards: {:?}", e.duration()))
+	})?;
+	Ok(now.as_nanos() as u64)
+}
+
+async fn compute_then(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	doc: &CursorDoc,
+	ev: &DefineEventStatement,
+) -> Result<(), Error> {
+	for v in ev.then.iter() {
+		v.compute(stk, ctx, opt, Some(doc)).await?;
+	}
+	Ok(())
+}
+
+impl Datastore {
+	/// Run the background task to process the asynchronous events which are due
+	///
+	/// Events are fetched in batches until every event which was due when the
+	/// task started has been processed. Events which are retried, or which are
+	/// claimed while they run, are queued after this range, so the task ends.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::event", skip(self))]
+	pub async fn event_process(&self) -> Result<(), Error> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Processing asynchronous events");
+		// The range of events which are due
+		let mut beg = eq::prefix();
+		let end = eq::new(now()?, Uuid::max()).encode()?;
+		loop {
+			// Fetch the next batch of events which are due
+			let txn = self.transaction(Read, Optimistic).await?;
+			let due =
+				catch!(txn, txn.scan(beg..end.clone(), *EVENT_PROCESS_BATCH_SIZE, None).await);
+			txn.cancel().await?;
+			// Continue the next batch after the last event
+			let Some((last, _)) = due.last() else {
+				break;
+			};
+			beg = last.clone();
+			beg.push(0x00);
+			let more = due.len() >= *EVENT_PROCESS_BATCH_SIZE as usize;
+			// Process the events concurrently, each in its own transaction,
+			// so that an event which sleeps does not hold up the others
+			let events = due.into_iter().map(|(key, _)| async move {
+				if let Err(err) = self.event_handle(key).await {
+					warn!(target: TARGET, "Failed to process an asynchronous event: {err}");
+				}
+			});
+			join_all(events).await;
+			// Stop once the due range is drained
+			if !more {
+				break;
+			}
+		}
+		// Everything ok
+		Ok(())
+	}
+
+	/// Claims and runs a queued event, retrying it if it fails
+	async fn event_handle(&self, key: Key) -> Result<(), Error> {
+		// The event may have been claimed by another node
+		let Some((key, qe)) = self.event_claim(&key).await? else {
+			return Ok(());
+		};
+		if let Err(err) = self.event_run(&key, &qe).await {
+			self.event_failed(&key, qe, err).await?;
+		}
+		Ok(())
+	}
+
+	/// Claims a queued event, by moving it to a lease which is due after the event times out
+	///
+	/// If this node stops before the event is finished, the lease becomes due,
+	/// and the event is processed again, counting as another of its attempts.
+	async fn event_claim(&self, key: &Key) -> Result<Option<(Key, QueuedEvent)>, Error> {
+		let txn = self.transaction(Write, Optimistic).await?;
+		let Some(val) = catch!(txn, txn.get(key.clone(), None).await) else {
+			txn.cancel().await?;
+			return Ok(None);
+		};
+		let mut qe: QueuedEvent = catch!(txn, revision::from_slice(&val).map_err(Error::from));
+		qe.attempts += 1;
+		catch!(txn, txn.del(key.clone()).await);
+		let lease = Duration::from_secs(*EVENT_PROCESS_TIMEOUT_SECS).saturating_mul(2);
+		let key = catch!(txn, qe.enqueue(&txn, lease).await);
+		// The commit conflicts if another node claimed the event first
+		txn.commit().await?;
+		Ok(Some((key, qe)))
+	}
+
+	/// Runs a queued event, removing it from the queue if it succeeds
+	async fn event_run(&self, key: &Key, qe: &QueuedEvent) -> Result<(), Error> {
+		let txn = Arc::new(self.transaction(Write, Optimistic).await?);
+		// Check the event was not processed by another node
+		if catch!(txn, txn.get(key.clone(), None).await).is_none() {
+			return txn.cancel().await;
+		}
+		catch!(txn, txn.del(key.clone()).await);
+		// The event may have been removed since it was queued
+		match txn.get_tb_event(&qe.ns, &qe.db, &qe.tb, &qe.ev).await {
+			Ok(ev) => catch!(txn, self.event_compute(txn.clone(), qe, &ev).await),
+			Err(Error::EvNotFound {
+				..
+			}) => (),
+			Err(err) => {
+				let _ = txn.cancel().await;
+				return Err(err);
+			}
+		}
+		txn.commit().await
+	}
+
+	/// Processes the THEN clause of an event with the values of the queued event
+	async fn event_compute(
+		&self,
+		txn: Arc<Transaction>,
+		qe: &QueuedEvent,
+		ev: &DefineEventStatement,
+	) -> Result<(), Error> {
+		// Don't run permissions
+		let opt = self.setup_options(&qe.session()).new_with_perms(false);
+		// Configure the context
+		let mut ctx = self.setup_ctx()?;
+		ctx.set_transaction(txn);
+		ctx.add_values(qe.values.clone());
+		ctx.set_asynchronous();
+		ctx.add_timeout(Duration::from_secs(*EVENT_PROCESS_TIMEOUT_SECS))?;
+		ctx.add_value("event", qe.event.clone().into());
+		ctx.add_value("value", qe.value.clone().into());
+		ctx.add_value("after", qe.after.clone().into());
+		ctx.add_value("before", qe.before.clone().into());
+		let ctx = ctx.freeze();
+		// Execute the event
+		let doc = CursorDoc::new(Some(Arc::new(qe.id.clone())), None, qe.value.clone());
+		let mut stack = TreeStack::new();
+		stack.enter(|stk| compute_then(stk, &ctx, &opt, &doc, ev)).finish().await
+	}
+
+	/// Queues a failed event to be retried, or writes it to the dead-letter table once it has no retries left
+	async fn event_failed(&self, key: &Key, qe: QueuedEvent, err: Error) -> Result<(), Error> {
+		let txn = Arc::new(self.transaction(Write, Optimistic).await?);
+		// Check the event was not processed by another node
+		if catch!(txn, txn.get(key.clone(), None).await).is_none() {
+			return txn.cancel().await;
+		}
+		catch!(txn, txn.del(key.clone()).await);
+		// The event may have been removed since it failed
+		let ev = match txn.get_tb_event(&qe.ns, &qe.db, &qe.tb, &qe.ev).await {
+			Ok(ev) => ev,
+			Err(Error::EvNotFound {
+				..
+			}) => return txn.commit().await,
+			Err(err) => {
+				let _ = txn.cancel().await;
+				return Err(err);
+			}
+		};
+		if qe.attempts <= ev.retry.unwrap_or_default() {
+			let delay = match err {
+				// The event asked to be retried after a specific delay
+				Error::EventRetryAfter(ref v) => v.0,
+				// The backoff doubles on each retry
+				_ => {
+					let backoff = ev.backoff.as_ref().map_or(DEFAULT_BACKOFF, |v| v.0);
+					backoff.saturating_mul(1 << (qe.attempts - 1).min(16))
+				}
+			};
+			trace!(target: TARGET, "Retrying event '{}' on '{}' in {delay:?}: {err}", qe.ev, qe.tb);
+			catch!(txn, qe.enqueue(&txn, delay).await);
+		} else {
+			warn!(target: TARGET, "Event '{}' on '{}' failed {} times: {err}", qe.ev, qe.tb, qe.attempts);
+			// The event is dropped if it can not be written, so that it is not retried forever
+			if let Err(e) = self.event_dead_letter(txn.clone(), qe, err).await {
+				error!(target: TARGET, "Failed to write an event to the dead-letter table: {e}");
+			}
+		}
+		txn.commit().await
+	}
+
+	/// Writes an event which failed on every retry to the dead-letter table
+	///
+	/// The dead-letter table is stored in a reserved namespace, so that it is
+	/// never mixed with the tables of a database, and it records the namespace
+	/// and database of each event.
+	async fn event_dead_letter(
+		&self,
+		txn: Arc<Transaction>,
+		qe: QueuedEvent,
+		err: Error,
+	) -> Result<(), Error> {
+		let (ns, db, tb) =
+			(&*EVENT_DEAD_LETTER_NAMESPACE, DEAD_LETTER_DATABASE, &*EVENT_DEAD_LETTER_TABLE);
+		let sess = Session::for_level(Level::Database(ns.clone(), db.to_owned()), Role::Editor);
+		let opt = self.setup_options(&sess);
+		// The reserved namespace is defined even in strict mode
+		txn.ensure_ns_db_tb(ns, db, tb, false).await?;
+		// Configure the context
+		let mut ctx = self.setup_ctx()?;
+		ctx.set_transaction(txn);
+		let ctx = ctx.freeze();
+		// Create the dead-letter record
+		let stm = CreateStatement {
+			what: Values(vec![Value::Table(Table(tb.clone()))]),
+			data: Some(Data::ContentExpression(Value::from(map! {
+				"namespace".to_string() => Value::from(qe.ns),
+				"database".to_string() => Value::from(qe.db),
+				"event".to_string() => Value::from(qe.ev),
+				"table".to_string() => Value::from(qe.tb),
+				"record".to_string() => Value::from(qe.id),
+				"action".to_string() => qe.event,
+				"value".to_string() => qe.value,
+				"before".to_string() => qe.before,
+				"after".to_string() => qe.after,
+				"attempts".to_string() => Value::from(qe.attempts),
+				"error".to_string() => Value::from(err.to_string()),
+				"time".to_string() => Value::from(Datetime::default()),
+			}))),
+			output: Some(Output::None),
+			..Default::default()
+		};
+		let mut stack = TreeStack::new();
+		stack.enter(|stk| stm.compute(stk, &ctx, &opt, None)).finish().await?;
+		Ok(())
+	}
+}
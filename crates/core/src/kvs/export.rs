@@ -19,6 +19,8 @@ pub struct Config {
 	pub params: bool,
 	pub functions: bool,
 	pub analyzers: bool,
+	pub events: bool,
+	pub tests: bool,
 	pub tables: TableConfig,
 	pub versions: bool,
 	pub records: bool,
@@ -32,6 +34,8 @@ impl Default for Config {
 			params: true,
 			functions: true,
 			analyzers: true,
+			events: true,
+			tests: true,
 			tables: TableConfig::default(),
 			versions: false,
 			records: true,
@@ -47,6 +51,8 @@ impl From<Config> for Value {
 			"params" => config.params.into(),
 			"functions" => config.functions.into(),
 			"analyzers" => config.analyzers.into(),
+			"events" => config.events.into(),
+			"tests" => config.tests.into(),
 			"versions" => config.versions.into(),
 			"records" => config.records.into(),
 			"tables" => match config.tables {
@@ -89,6 +95,8 @@ impl TryFrom<&Value> for Config {
 				bool_prop!(params);
 				bool_prop!(functions);
 				bool_prop!(analyzers);
+				bool_prop!(events);
+				bool_prop!(tests);
 				bool_prop!(versions);
 				bool_prop!(records);
 
@@ -182,7 +190,7 @@ impl Transaction {
 		cfg: Config,
 		chn: Sender<Vec<u8>>,
 	) -> Result<(), Error> {
-		// Output USERS, ACCESSES, PARAMS, FUNCTIONS, ANALYZERS
+		// Output USERS, ACCESSES, PARAMS, FUNCTIONS, ANALYZERS, EVENTS, TESTS
 		self.export_metadata(&cfg, &chn, ns, db).await?;
 		// Output TABLES
 		self.export_tables(ns, db, &cfg, &chn).await?;
@@ -229,6 +237,18 @@ impl Transaction {
 			self.export_section("ANALYZERS", analyzers.to_vec(), chn).await?;
 		}
 
+		// Output EVENTS
+		if cfg.events {
+			let events = self.all_db_events(ns, db).await?;
+			self.export_section("EVENTS", events.to_vec(), chn).await?;
+		}
+
+		// Output TESTS
+		if cfg.tests {
+			let tests = self.all_db_tests(ns, db).await?;
+			self.export_section("TESTS", tests.to_vec(), chn).await?;
+		}
+
 		Ok(())
 	}
 
@@ -0,0 +1,94 @@
+use crate::dbs::{Response, Session, Variables};
+use crate::sql::{Object, Query, Statement, Statements};
+use std::time::Duration;
+use trice::Instant;
+
+/// A write query which was run against this datastore, so that it can be replayed elsewhere
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Mirrored {
+	/// The namespace which the query was run in
+	pub ns: Option<String>,
+	/// The database which the query was run in
+	pub db: Option<String>,
+	/// The query which was run
+	pub query: Query,
+	/// The session parameters and variables which the query was run with
+	pub vars: Object,
+	/// The time at which the query was run
+	time: Instant,
+}
+
+impl Mirrored {
+	pub(crate) fn new(sess: &Session, query: Query, vars: Variables) -> Self {
+		// The variables of the query take precedence over the session parameters
+		let mut params = sess.parameters.clone();
+		params.extend(vars.unwrap_or_default());
+		Self {
+			ns: sess.ns.clone(),
+			db: sess.db.clone(),
+			query,
+			vars: params.into(),
+			time: Instant::now(),
+		}
+	}
+
+	/// Keeps only the statements of the query which succeeded, if any of them write
+	///
+	/// When each statement has a response of its own, the statements which failed
+	/// are removed. Otherwise, as when the query contains a transaction, the query
+	/// is only kept if every one of its statements succeeded.
+	pub(crate) fn successful(mut self, res: &[Response]) -> Option<Self> {
+		if self.query.0.len() == res.len() {
+			let stmts = std::mem::take(&mut self.query.0 .0);
+			self.query = Query(Statements(
+				stmts
+					.into_iter()
+					.zip(res)
+					.filter(|(_, r)| r.result.is_ok())
+					.map(|(s, _)| s)
+					.collect(),
+			));
+		} else if res.iter().any(|r| r.result.is_err()) {
+			return None;
+		}
+		self.query.0.iter().any(Statement::writeable).then_some(self)
+	}
+
+	/// The time which has elapsed since the query was run
+	pub fn lag(&self) -> Duration {
+		self.time.elapsed()
+	}
+}
+
+#[cfg(all(test, feature = "kv-mem"))]
+mod tests {
+	use crate::dbs::Session;
+	use crate::kvs::Datastore;
+
+	#[tokio::test]
+	async fn only_write_queries_are_mirrored() {
+		let ds = Datastore::new("memory").await.unwrap().with_mirroring();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		ds.execute("SELECT * FROM person", &ses, None).await.unwrap();
+		ds.execute("CREATE person:one", &ses, None).await.unwrap();
+		let channel = ds.mirrored().unwrap();
+		assert_eq!(channel.len(), 1);
+		let mirrored = channel.try_recv().unwrap();
+		assert_eq!(mirrored.ns.as_deref(), Some("test"));
+		assert_eq!(mirrored.db.as_deref(), Some("test"));
+		assert_eq!(mirrored.query.len(), 1);
+	}
+
+	#[tokio::test]
+	async fn only_successful_writes_are_mirrored() {
+		let ds = Datastore::new("memory").await.unwrap().with_mirroring();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		ds.execute("CREATE person:one; CREATE person:one; THROW 'fail'", &ses, None).await.unwrap();
+		ds.execute("BEGIN; CREATE person:two; THROW 'fail'; COMMIT;", &ses, None).await.unwrap();
+		let channel = ds.mirrored().unwrap();
+		assert_eq!(channel.len(), 1);
+		let mirrored = channel.try_recv().unwrap();
+		assert_eq!(mirrored.query.len(), 1);
+	}
+}
@@ -14,18 +14,24 @@
 //! - `mem`: in-memory database
 
 pub mod export;
+pub mod verify;
 
 mod api;
 mod batch;
 mod cf;
 mod clock;
 mod ds;
+mod event;
 mod key;
 mod live;
+mod mirror;
 mod node;
+mod retry;
 mod scanner;
 mod stash;
+mod stats;
 mod threadpool;
+mod throttle;
 mod tr;
 mod tx;
 mod version;
@@ -56,8 +62,14 @@ mod util;
 pub(crate) use key::impl_key;
 pub use key::{KeyDecode, KeyDecodeOwned, KeyEncode};
 
-pub use ds::Datastore;
+pub(crate) use ds::TransactionFactory;
+pub use ds::{Compaction, Datastore};
+pub(crate) use event::QueuedEvent;
 pub use live::Live;
+pub use mirror::Mirrored;
+pub use retry::RetryPolicy;
+pub use stats::Statistics;
+pub use throttle::WriteLimits;
 pub use tr::{Check, LockType, TransactionType, Transactor};
 pub use tx::Transaction;
 
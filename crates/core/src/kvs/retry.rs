@@ -0,0 +1,150 @@
+use rand::Rng;
+use std::time::Duration;
+
+/// Configures how transactions which fail due to a read or write conflict in
+/// the underlying key-value store are automatically re-executed.
+///
+/// Each retry waits for a randomly jittered delay, which grows exponentially
+/// from the initial backoff up to the maximum backoff.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct RetryPolicy {
+	/// The maximum number of times a transaction is attempted, including the first attempt
+	pub max_attempts: u32,
+	/// The upper bound of the delay before the first retry
+	pub initial_backoff: Duration,
+	/// The upper bound of the delay between any two attempts
+	pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+	fn default() -> Self {
+		Self {
+			max_attempts: 5,
+			initial_backoff: Duration::from_millis(10),
+			max_backoff: Duration::from_secs(1),
+		}
+	}
+}
+
+impl RetryPolicy {
+	/// Create a new retry policy which attempts a transaction at most `max_attempts` times
+	pub fn new(max_attempts: u32) -> Self {
+		Self {
+			max_attempts,
+			..Default::default()
+		}
+	}
+
+	/// Set the upper bound of the delay before the first retry
+	pub fn with_initial_backoff(mut self, backoff: Duration) -> Self {
+		self.initial_backoff = backoff;
+		self
+	}
+
+	/// Set the upper bound of the delay between any two attempts
+	pub fn with_max_backoff(mut self, backoff: Duration) -> Self {
+		self.max_backoff = backoff;
+		self
+	}
+
+	/// Calculates the jittered delay before the specified retry, starting at 1
+	pub(crate) fn backoff(&self, retry: u32) -> Duration {
+		let factor = 1u32 << retry.saturating_sub(1).min(31);
+		let limit = self.initial_backoff.saturating_mul(factor).min(self.max_backoff);
+		limit.mul_f64(rand::thread_rng().gen::<f64>())
+	}
+
+	/// Waits for the jittered delay before the specified retry, starting at 1
+	pub(crate) async fn wait(&self, retry: u32) {
+		let dur = self.backoff(retry);
+		#[cfg(target_family = "wasm")]
+		wasmtimer::tokio::sleep(dur).await;
+		#[cfg(not(target_family = "wasm"))]
+		tokio::time::sleep(dur).await;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn backoff_is_bounded() {
+		let policy = RetryPolicy::new(10)
+			.with_initial_backoff(Duration::from_millis(10))
+			.with_max_backoff(Duration::from_millis(50));
+		for _ in 0..100 {
+			assert!(policy.backoff(1) <= Duration::from_millis(10));
+			assert!(policy.backoff(2) <= Duration::from_millis(20));
+			assert!(policy.backoff(3) <= Duration::from_millis(40));
+			assert!(policy.backoff(4) <= Duration::from_millis(50));
+			assert!(policy.backoff(64) <= Duration::from_millis(50));
+		}
+	}
+
+	#[cfg(feature = "kv-mem")]
+	mod conflicts {
+		use super::*;
+		use crate::dbs::{Capabilities, Session};
+		use crate::err::Error;
+		use crate::kvs::Datastore;
+		use crate::sql::Value;
+		use std::time::Instant;
+
+		// Each attempt updates the record, and then waits long enough for another write to commit
+		const SLOW_UPDATE: &str = "UPDATE counter:1 SET n += 1, wait = sleep(250ms) RETURN VALUE n";
+
+		async fn new_ds(policy: RetryPolicy) -> (Datastore, Session) {
+			let ds = Datastore::new("memory")
+				.await
+				.unwrap()
+				.with_capabilities(Capabilities::all())
+				.with_retry_policy(Some(policy));
+			let ses = Session::owner().with_ns("test").with_db("test");
+			ds.execute("CREATE counter:1 SET n = 0", &ses, None).await.unwrap();
+			(ds, ses)
+		}
+
+		#[tokio::test]
+		async fn conflicted_transaction_is_retried() {
+			let policy = RetryPolicy::new(3).with_initial_backoff(Duration::ZERO);
+			let (ds, ses) = new_ds(policy).await;
+			let (slow, _) = tokio::join!(ds.execute(SLOW_UPDATE, &ses, None), async {
+				// Commit a conflicting write while the first attempt is waiting
+				tokio::time::sleep(Duration::from_millis(50)).await;
+				ds.execute("UPDATE counter:1 SET n += 1", &ses, None).await.unwrap()
+			});
+			// The retried update sees the conflicting write
+			let res = slow.unwrap().remove(0).result.unwrap();
+			assert_eq!(res, Value::from(vec![Value::from(2)]));
+		}
+
+		#[tokio::test]
+		async fn conflicted_transaction_stops_after_max_attempts() {
+			let policy = RetryPolicy::new(3).with_initial_backoff(Duration::ZERO);
+			let (ds, ses) = new_ds(policy).await;
+			let done = std::sync::atomic::AtomicBool::new(false);
+			let start = Instant::now();
+			let (slow, _) = tokio::join!(
+				async {
+					let res = ds.execute(SLOW_UPDATE, &ses, None).await;
+					done.store(true, std::sync::atomic::Ordering::Relaxed);
+					res
+				},
+				async {
+					// Keep committing conflicting writes until the update gives up
+					while !done.load(std::sync::atomic::Ordering::Relaxed) {
+						tokio::time::sleep(Duration::from_millis(50)).await;
+						ds.execute("UPDATE counter:1 SET n += 1", &ses, None).await.unwrap();
+					}
+				}
+			);
+			let elapsed = start.elapsed();
+			assert!(matches!(slow.unwrap().remove(0).result, Err(Error::TxRetryable)));
+			// Every attempt waits once, and no more than the maximum are made
+			assert!(elapsed >= Duration::from_millis(750), "{elapsed:?}");
+			assert!(elapsed < Duration::from_millis(1000), "{elapsed:?}");
+		}
+	}
+}
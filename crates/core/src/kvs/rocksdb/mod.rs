@@ -11,6 +11,7 @@ use rocksdb::{
 };
 use std::fmt::Debug;
 use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::thread;
@@ -22,6 +23,7 @@ const TARGET: &str = "surrealdb::core::kvs::rocksdb";
 
 pub struct Datastore {
 	db: Pin<Arc<OptimisticTransactionDB>>,
+	path: PathBuf,
 }
 
 pub struct Transaction {
@@ -234,8 +236,20 @@ impl Datastore {
 		// Return the datastore
 		Ok(Datastore {
 			db,
+			path: PathBuf::from(path),
 		})
 	}
+	/// The directory which the database files are stored in
+	pub(crate) fn path(&self) -> &Path {
+		&self.path
+	}
+	/// Compact all of the database files
+	pub(crate) async fn compact(&self) -> Result<(), Error> {
+		// Flush the memtables first, so their data is compacted too
+		self.db.flush()?;
+		self.db.compact_range::<&[u8], &[u8]>(None, None);
+		Ok(())
+	}
 	/// Shutdown the database
 	pub(crate) async fn shutdown(&self) -> Result<(), Error> {
 		// Create new flush options
@@ -253,14 +267,20 @@ impl Datastore {
 		// All good
 		Ok(())
 	}
-	/// Start a new transaction
-	pub(crate) async fn transaction(&self, write: bool, _: bool) -> Result<Transaction, Error> {
+	/// Start a new transaction, which syncs its commit to disk if specified,
+	/// or according to `SURREAL_SYNC_DATA` otherwise
+	pub(crate) async fn transaction(
+		&self,
+		write: bool,
+		_: bool,
+		sync: Option<bool>,
+	) -> Result<Transaction, Error> {
 		// Set the transaction options
 		let mut to = OptimisticTransactionOptions::default();
 		to.set_snapshot(true);
 		// Set the write options
 		let mut wo = WriteOptions::default();
-		wo.set_sync(*cnf::SYNC_DATA);
+		wo.set_sync(sync.unwrap_or(*cnf::SYNC_DATA));
 		// Create a new transaction
 		let inner = self.db.transaction_opt(&wo, &to);
 		// The database reference must always outlive
@@ -0,0 +1,97 @@
+use super::{Datastore, LockType::*, TransactionType::*};
+use crate::cnf::CHECK_BATCH_SIZE;
+use crate::dbs::Response;
+use crate::err::Error;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Counts the work which a datastore has done since it was started
+#[derive(Debug, Default)]
+pub(super) struct Counters {
+	queries: AtomicU64,
+	conflicts: AtomicU64,
+}
+
+impl Counters {
+	/// Records a query which was processed, with the responses of its statements
+	pub(super) fn record(&self, responses: &[Response]) {
+		self.queries.fetch_add(1, Ordering::Relaxed);
+		let conflicts =
+			responses.iter().filter(|r| matches!(r.result, Err(Error::TxRetryable))).count();
+		if conflicts > 0 {
+			self.conflicts.fetch_add(conflicts as u64, Ordering::Relaxed);
+		}
+	}
+
+	pub(super) fn statistics(&self) -> Statistics {
+		Statistics {
+			queries: self.queries.load(Ordering::Relaxed),
+			conflicts: self.conflicts.load(Ordering::Relaxed),
+		}
+	}
+}
+
+/// The work which a datastore has done since it was started
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Statistics {
+	/// The number of queries which were processed
+	pub queries: u64,
+	/// The number of statements which failed due to a transaction conflict
+	pub conflicts: u64,
+}
+
+impl Datastore {
+	/// Scans the keys of every namespace, returning the number of bytes which each one stores
+	///
+	/// The sizes are the lengths of the keys and values, before they are compressed by the
+	/// storage engine. This reads the whole datastore, so should not be called often. Each
+	/// batch of keys is read in its own transaction, so that the scan is not limited by how
+	/// long the storage engine allows a transaction to run, which means that the sizes are
+	/// not measured at a single point in time.
+	pub async fn namespace_sizes(&self) -> Result<BTreeMap<String, u64>, Error> {
+		let mut sizes = BTreeMap::new();
+		let mut next = Some(b"/*".to_vec()..b"/+".to_vec());
+		while let Some(rng) = next {
+			let tx = self.transaction(Read, Optimistic).await?;
+			let batch = tx.batch_keys_vals(rng, *CHECK_BATCH_SIZE, None).await;
+			tx.cancel().await?;
+			let batch = batch?;
+			next = batch.next;
+			for (k, v) in batch.result {
+				let name = &k[2..];
+				let name = &name[..name.iter().position(|&b| b == 0x00).unwrap_or(name.len())];
+				let size = (k.len() + v.len()) as u64;
+				match sizes.get_mut(name) {
+					Some(total) => *total += size,
+					None => {
+						sizes.insert(name.to_vec(), size);
+					}
+				}
+			}
+		}
+		Ok(sizes
+			.into_iter()
+			.map(|(name, size)| (String::from_utf8_lossy(&name).into_owned(), size))
+			.collect())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::dbs::Session;
+	use crate::kvs::Datastore;
+
+	#[tokio::test]
+	async fn statistics_and_namespace_sizes() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		ds.execute("CREATE person:one SET name = 'a much longer name'", &ses, None).await.unwrap();
+		ds.execute("USE NS other DB other; CREATE person:two", &ses, None).await.unwrap();
+		assert_eq!(ds.statistics().queries, 2);
+		assert_eq!(ds.statistics().conflicts, 0);
+		let sizes = ds.namespace_sizes().await.unwrap();
+		assert_eq!(sizes.keys().collect::<Vec<_>>(), vec!["other", "test"]);
+		assert!(sizes["test"] > sizes["other"], "{sizes:?}");
+	}
+}
@@ -7,6 +7,7 @@ use crate::key::debug::Sprintable;
 use crate::kvs::{Check, Key, KeyEncode, Val, Version};
 use std::fmt::Debug;
 use std::ops::Range;
+use std::path::{Path, PathBuf};
 use surrealkv::Options;
 use surrealkv::Store;
 use surrealkv::Transaction as Tx;
@@ -16,6 +17,7 @@ const TARGET: &str = "surrealdb::core::kvs::surrealkv";
 
 pub struct Datastore {
 	db: Store,
+	path: PathBuf,
 }
 
 pub struct Transaction {
@@ -73,6 +75,7 @@ impl Datastore {
 		match Store::new(opts) {
 			Ok(db) => Ok(Datastore {
 				db,
+				path: PathBuf::from(path),
 			}),
 			Err(e) => Err(Error::Ds(e.to_string())),
 		}
@@ -90,6 +93,14 @@ impl Datastore {
 			_ => Err(Error::Ds("Invalid start string".into())),
 		}
 	}
+	/// The directory which the database files are stored in
+	pub(crate) fn path(&self) -> &Path {
+		&self.path
+	}
+	/// Compact the database files, removing deleted and overwritten data
+	pub(crate) async fn compact(&self) -> Result<(), Error> {
+		self.db.compact().await.map_err(|e| Error::Ds(e.to_string()))
+	}
 	/// Shutdown the database
 	pub(crate) async fn shutdown(&self) -> Result<(), Error> {
 		// Shutdown the database
@@ -99,8 +110,14 @@ impl Datastore {
 		// Nothing to do here
 		Ok(())
 	}
-	/// Start a new transaction
-	pub(crate) async fn transaction(&self, write: bool, _: bool) -> Result<Transaction, Error> {
+	/// Start a new transaction, which syncs its commit to disk if specified,
+	/// or according to `SURREAL_SYNC_DATA` otherwise
+	pub(crate) async fn transaction(
+		&self,
+		write: bool,
+		_: bool,
+		sync: Option<bool>,
+	) -> Result<Transaction, Error> {
 		// Specify the check level
 		#[cfg(not(debug_assertions))]
 		let check = Check::Warn;
@@ -112,7 +129,7 @@ impl Datastore {
 			false => self.db.begin_with_mode(Mode::ReadOnly),
 		}?;
 		// Set the transaction durability
-		match *cnf::SYNC_DATA {
+		match sync.unwrap_or(*cnf::SYNC_DATA) {
 			true => txn.set_durability(Durability::Immediate),
 			false => txn.set_durability(Durability::Eventual),
 		};
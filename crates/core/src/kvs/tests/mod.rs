@@ -22,6 +22,7 @@ macro_rules! include_tests {
 }
 
 mod multireader;
+mod multiwriter_counter;
 mod multiwriter_different_keys;
 mod multiwriter_same_keys_allow;
 mod multiwriter_same_keys_conflict;
@@ -76,7 +77,7 @@ mod mem {
 		(ds, Kvs::Mem)
 	}
 
-	include_tests!(new_ds => raw,snapshot,multireader,multiwriter_different_keys,multiwriter_same_keys_conflict,timestamp_to_versionstamp);
+	include_tests!(new_ds => raw,snapshot,multireader,multiwriter_different_keys,multiwriter_counter,multiwriter_same_keys_conflict,timestamp_to_versionstamp);
 }
 
 #[cfg(feature = "kv-rocksdb")]
@@ -97,7 +98,7 @@ mod rocksdb {
 		(ds, Kvs::Rocksdb)
 	}
 
-	include_tests!(new_ds => raw,snapshot,multireader,multiwriter_different_keys,multiwriter_same_keys_conflict,timestamp_to_versionstamp);
+	include_tests!(new_ds => raw,snapshot,multireader,multiwriter_different_keys,multiwriter_counter,multiwriter_same_keys_conflict,timestamp_to_versionstamp);
 }
 
 #[cfg(feature = "kv-surrealkv")]
@@ -118,7 +119,7 @@ mod surrealkv {
 		(ds, Kvs::SurrealKV)
 	}
 
-	include_tests!(new_ds => raw,snapshot,multireader,multiwriter_different_keys,multiwriter_same_keys_conflict,timestamp_to_versionstamp);
+	include_tests!(new_ds => raw,snapshot,multireader,multiwriter_different_keys,multiwriter_counter,multiwriter_same_keys_conflict,timestamp_to_versionstamp);
 }
 
 #[cfg(feature = "kv-tikv")]
@@ -140,7 +141,7 @@ mod tikv {
 		(ds, Kvs::Tikv)
 	}
 
-	include_tests!(new_ds => raw,snapshot,multireader,multiwriter_different_keys,multiwriter_same_keys_allow,timestamp_to_versionstamp);
+	include_tests!(new_ds => raw,snapshot,multireader,multiwriter_different_keys,multiwriter_counter,multiwriter_same_keys_allow,timestamp_to_versionstamp);
 }
 
 #[cfg(feature = "kv-fdb")]
@@ -162,5 +163,5 @@ mod fdb {
 		(ds, Kvs::Fdb)
 	}
 
-	include_tests!(new_ds => raw,snapshot,multireader,multiwriter_different_keys,multiwriter_same_keys_allow,timestamp_to_versionstamp);
+	include_tests!(new_ds => raw,snapshot,multireader,multiwriter_different_keys,multiwriter_counter,multiwriter_same_keys_allow,timestamp_to_versionstamp);
 }
@@ -0,0 +1,57 @@
+use super::CreateDs;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::{
+	dbs::node::Timestamp,
+	kvs::{
+		clock::{FakeClock, SizedClock},
+		LockType::*,
+		TransactionType::*,
+	},
+};
+
+pub async fn multiwriter_counter(new_ds: impl CreateDs) {
+	// Create a new datastore
+	let node_id = Uuid::parse_str("3a0f4c6e-9d1b-4e52-8a7f-2c5b6d8e1f90").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds.create_ds(node_id, clock).await;
+	// Insert an initial counter
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap().inner();
+	tx.set("counter", 0u64.to_be_bytes().to_vec(), None).await.unwrap();
+	tx.commit().await.unwrap();
+	// Increment the counter in several concurrent transactions
+	let mut txs = Vec::new();
+	for _ in 0..3 {
+		let mut tx = ds.transaction(Write, Optimistic).await.unwrap().inner();
+		let val = tx.get("counter", None).await.unwrap().unwrap();
+		let val = u64::from_be_bytes(val.try_into().unwrap());
+		tx.set("counter", (val + 1).to_be_bytes().to_vec(), None).await.unwrap();
+		txs.push(tx);
+	}
+	// Commit each transaction, some of which may conflict
+	let mut committed = 0u64;
+	for mut tx in txs {
+		if tx.commit().await.is_ok() {
+			committed += 1;
+		}
+	}
+	assert!(committed > 0);
+	// Check that no successful increment was lost
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap().inner();
+	let val = tx.get("counter", None).await.unwrap().unwrap();
+	let val = u64::from_be_bytes(val.try_into().unwrap());
+	assert_eq!(val, committed);
+	tx.cancel().await.unwrap();
+}
+
+macro_rules! define_tests {
+	($new_ds:ident) => {
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn multiwriter_counter() {
+			super::multiwriter_counter::multiwriter_counter($new_ds).await;
+		}
+	};
+}
+pub(crate) use define_tests;
@@ -0,0 +1,207 @@
+use crate::err::Error;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use trice::Instant;
+
+/// A function which is called with the namespace, database, and table of a throttled write
+type Hook = Arc<dyn Fn(&str, &str, &str) + Send + Sync>;
+
+/// Limits the rate at which records can be written to specific tables.
+///
+/// Each limited table has a bucket of write tokens, which holds at most the
+/// burst size of the table, and is refilled continuously. A write to a table
+/// with an empty bucket fails with [`Error::WriteThrottled`], which the client
+/// can retry once the bucket has been refilled. The tokens taken by a
+/// transaction are returned to the bucket if it is not committed.
+#[derive(Default)]
+#[non_exhaustive]
+pub struct WriteLimits {
+	limits: Vec<Arc<Limit>>,
+	hook: Option<Hook>,
+}
+
+struct Limit {
+	ns: String,
+	db: String,
+	tb: String,
+	rate: u32,
+	burst: u32,
+	bucket: Mutex<Bucket>,
+}
+
+/// A write token taken from the bucket of a limited table.
+///
+/// The token is returned to the bucket when it is dropped,
+/// unless the transaction which took it was committed.
+pub(crate) struct WriteToken {
+	limit: Arc<Limit>,
+	spent: bool,
+}
+
+impl WriteToken {
+	/// Keeps the token out of the bucket, once the write has been committed
+	pub(crate) fn spend(mut self) {
+		self.spent = true;
+	}
+}
+
+impl Drop for WriteToken {
+	fn drop(&mut self) {
+		if !self.spent {
+			let mut bucket = self.limit.bucket.lock().unwrap_or_else(|e| e.into_inner());
+			bucket.tokens = (bucket.tokens + 1.0).min(self.limit.burst as f64);
+		}
+	}
+}
+
+struct Bucket {
+	tokens: f64,
+	updated: Instant,
+}
+
+impl fmt::Debug for WriteLimits {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_list()
+			.entries(
+				self.limits
+					.iter()
+					.map(|l| format!("{}/{}/{}={}:{}", l.ns, l.db, l.tb, l.rate, l.burst)),
+			)
+			.finish()
+	}
+}
+
+impl WriteLimits {
+	/// Create a new set of write limits, which does not limit any table
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Limit the number of records which can be written to a table each second
+	pub fn with_limit(self, ns: &str, db: &str, tb: &str, per_second: u32) -> Self {
+		self.with_burst_limit(ns, db, tb, per_second, per_second)
+	}
+
+	/// Limit the number of records which can be written to a table each second,
+	/// allowing up to `burst` records to be written at once after a quiet period
+	pub fn with_burst_limit(
+		mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		per_second: u32,
+		burst: u32,
+	) -> Self {
+		self.limits.retain(|l| (l.ns.as_str(), l.db.as_str(), l.tb.as_str()) != (ns, db, tb));
+		self.limits.push(Arc::new(Limit {
+			ns: ns.to_owned(),
+			db: db.to_owned(),
+			tb: tb.to_owned(),
+			rate: per_second,
+			burst,
+			bucket: Mutex::new(Bucket {
+				tokens: burst as f64,
+				updated: Instant::now(),
+			}),
+		}));
+		self
+	}
+
+	/// Set a function which is called whenever a write to a table is throttled
+	pub fn with_hook<F>(mut self, hook: F) -> Self
+	where
+		F: Fn(&str, &str, &str) + Send + Sync + 'static,
+	{
+		self.hook = Some(Arc::new(hook));
+		self
+	}
+
+	/// Takes a write token for the specified table, if the table is limited
+	pub(crate) fn check(&self, ns: &str, db: &str, tb: &str) -> Result<Option<WriteToken>, Error> {
+		// Check if this table has a limit
+		let Some(limit) = self.limits.iter().find(|l| l.tb == tb && l.db == db && l.ns == ns)
+		else {
+			return Ok(None);
+		};
+		// Refill the bucket and try to take a token
+		let allowed = {
+			let mut bucket = limit.bucket.lock().unwrap_or_else(|e| e.into_inner());
+			let now = Instant::now();
+			let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+			bucket.updated = now;
+			bucket.tokens = (bucket.tokens + elapsed * limit.rate as f64).min(limit.burst as f64);
+			if bucket.tokens >= 1.0 {
+				bucket.tokens -= 1.0;
+				true
+			} else {
+				false
+			}
+		};
+		if allowed {
+			return Ok(Some(WriteToken {
+				limit: limit.clone(),
+				spent: false,
+			}));
+		}
+		// Notify about the throttled write
+		if let Some(hook) = &self.hook {
+			hook(ns, db, tb);
+		}
+		Err(Error::WriteThrottled {
+			tb: tb.to_owned(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	#[test]
+	fn writes_are_throttled() {
+		let throttled = Arc::new(AtomicUsize::new(0));
+		let counter = throttled.clone();
+		let limits = WriteLimits::new().with_limit("test", "test", "event", 3).with_hook(
+			move |ns, db, tb| {
+				assert_eq!((ns, db, tb), ("test", "test", "event"));
+				counter.fetch_add(1, Ordering::Relaxed);
+			},
+		);
+		for _ in 0..3 {
+			limits.check("test", "test", "event").unwrap().unwrap().spend();
+		}
+		assert!(matches!(limits.check("test", "test", "event"), Err(Error::WriteThrottled { .. })));
+		// Other tables are not limited
+		for _ in 0..10 {
+			assert!(limits.check("test", "test", "person").unwrap().is_none());
+			assert!(limits.check("test", "other", "event").unwrap().is_none());
+		}
+		assert_eq!(throttled.load(Ordering::Relaxed), 1);
+	}
+
+	#[test]
+	fn bursts_are_limited() {
+		let limits = WriteLimits::new().with_burst_limit("test", "test", "event", 1, 5);
+		for _ in 0..5 {
+			limits.check("test", "test", "event").unwrap().unwrap().spend();
+		}
+		assert!(matches!(limits.check("test", "test", "event"), Err(Error::WriteThrottled { .. })));
+	}
+
+	#[test]
+	fn uncommitted_writes_are_refunded() {
+		let limits = WriteLimits::new().with_limit("test", "test", "event", 2);
+		let tokens = [
+			limits.check("test", "test", "event").unwrap(),
+			limits.check("test", "test", "event").unwrap(),
+		];
+		assert!(matches!(limits.check("test", "test", "event"), Err(Error::WriteThrottled { .. })));
+		// Dropping the tokens of a cancelled transaction returns them to the bucket
+		drop(tokens);
+		for _ in 0..2 {
+			limits.check("test", "test", "event").unwrap().unwrap().spend();
+		}
+		assert!(matches!(limits.check("test", "test", "event"), Err(Error::WriteThrottled { .. })));
+	}
+}
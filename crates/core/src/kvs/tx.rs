@@ -10,8 +10,10 @@ use crate::dbs::node::Node;
 use crate::err::Error;
 use crate::idx::trees::store::cache::IndexTreeCaches;
 use crate::kvs::cache;
+use crate::kvs::cache::ds::PendingAccess;
 use crate::kvs::cache::tx::TransactionCache;
 use crate::kvs::scanner::Scanner;
+use crate::kvs::throttle::WriteToken;
 use crate::kvs::Transactor;
 use crate::sql::statements::define::ApiDefinition;
 use crate::sql::statements::define::DefineConfigStatement;
@@ -27,6 +29,7 @@ use crate::sql::statements::DefineModelStatement;
 use crate::sql::statements::DefineNamespaceStatement;
 use crate::sql::statements::DefineParamStatement;
 use crate::sql::statements::DefineTableStatement;
+use crate::sql::statements::DefineTestStatement;
 use crate::sql::statements::DefineUserStatement;
 use crate::sql::statements::LiveStatement;
 use crate::sql::Id;
@@ -37,7 +40,7 @@ use futures::lock::MutexGuard;
 use futures::stream::Stream;
 use std::fmt::Debug;
 use std::ops::Range;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use uuid::Uuid;
 
 #[non_exhaustive]
@@ -50,6 +53,12 @@ pub struct Transaction {
 	cache: TransactionCache,
 	/// Cache the index updates
 	index_caches: IndexTreeCaches,
+	/// The write tokens taken for limited tables
+	tokens: std::sync::Mutex<Vec<WriteToken>>,
+	/// The record access changes made to cache tables
+	accesses: std::sync::Mutex<Vec<PendingAccess>>,
+	/// The snapshot version which all reads are pinned to
+	snapshot: OnceLock<u64>,
 }
 
 impl Transaction {
@@ -60,6 +69,9 @@ impl Transaction {
 			tx: Mutex::new(tx),
 			cache: TransactionCache::new(),
 			index_caches: IndexTreeCaches::default(),
+			tokens: Default::default(),
+			accesses: Default::default(),
+			snapshot: OnceLock::new(),
 		}
 	}
 
@@ -83,6 +95,19 @@ impl Transaction {
 		self.local
 	}
 
+	/// Pins all reads in this transaction to a snapshot version.
+	///
+	/// Reads which specify their own version are not affected. A
+	/// transaction can only be pinned to a single snapshot.
+	pub(crate) fn pin_snapshot(&self, version: u64) -> Result<(), Error> {
+		self.snapshot.set(version).map_err(|_| fail!("The transaction snapshot was already pinned"))
+	}
+
+	/// The version to read at, which defaults to the pinned snapshot
+	fn snapshot(&self, version: Option<u64>) -> Option<u64> {
+		version.or_else(|| self.snapshot.get().copied())
+	}
+
 	/// Check if the transaction is finished.
 	///
 	/// If the transaction has been canceled or committed,
@@ -98,7 +123,12 @@ impl Transaction {
 	/// This reverses all changes made within the transaction.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip_all)]
 	pub async fn cancel(&self) -> Result<(), Error> {
-		self.lock().await.cancel().await
+		let res = self.lock().await.cancel().await;
+		// Return the write tokens to their buckets
+		self.tokens.lock().unwrap_or_else(|e| e.into_inner()).clear();
+		// Discard the record access changes
+		self.accesses.lock().unwrap_or_else(|e| e.into_inner()).clear();
+		res
 	}
 
 	/// Commit a transaction.
@@ -106,7 +136,29 @@ impl Transaction {
 	/// This attempts to commit all changes made within the transaction.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip_all)]
 	pub async fn commit(&self) -> Result<(), Error> {
-		self.lock().await.commit().await
+		self.lock().await.commit().await?;
+		// The writes were committed, so keep their write tokens
+		let tokens = std::mem::take(&mut *self.tokens.lock().unwrap_or_else(|e| e.into_inner()));
+		tokens.into_iter().for_each(WriteToken::spend);
+		// The writes were committed, so record their access times
+		let accesses =
+			std::mem::take(&mut *self.accesses.lock().unwrap_or_else(|e| e.into_inner()));
+		accesses.into_iter().for_each(PendingAccess::apply);
+		Ok(())
+	}
+
+	/// Keeps a write token taken for a limited table until the transaction finishes.
+	///
+	/// The token is only spent if the transaction is committed.
+	pub(crate) fn charge(&self, token: WriteToken) {
+		self.tokens.lock().unwrap_or_else(|e| e.into_inner()).push(token);
+	}
+
+	/// Keeps a record access change made to a cache table until the transaction finishes.
+	///
+	/// The change is only made if the transaction is committed.
+	pub(crate) fn track_access(&self, access: PendingAccess) {
+		self.accesses.lock().unwrap_or_else(|e| e.into_inner()).push(access);
 	}
 
 	/// Check if a key exists in the datastore.
@@ -115,7 +167,7 @@ impl Transaction {
 	where
 		K: KeyEncode + Debug,
 	{
-		self.lock().await.exists(key, version).await
+		self.lock().await.exists(key, self.snapshot(version)).await
 	}
 
 	/// Fetch a key from the datastore.
@@ -124,7 +176,7 @@ impl Transaction {
 	where
 		K: KeyEncode + Debug,
 	{
-		self.lock().await.get(key, version).await
+		self.lock().await.get(key, self.snapshot(version)).await
 	}
 
 	/// Retrieve a batch set of keys from the datastore.
@@ -159,7 +211,7 @@ impl Transaction {
 	where
 		K: KeyEncode + Debug,
 	{
-		self.lock().await.getr(rng, version).await
+		self.lock().await.getr(rng, self.snapshot(version)).await
 	}
 
 	/// Delete a key from the datastore.
@@ -297,7 +349,7 @@ impl Transaction {
 	where
 		K: KeyEncode + Debug,
 	{
-		self.lock().await.keys(rng, limit, version).await
+		self.lock().await.keys(rng, limit, self.snapshot(version)).await
 	}
 
 	/// Retrieve a specific range of keys from the datastore.
@@ -313,7 +365,7 @@ impl Transaction {
 	where
 		K: KeyEncode + Debug,
 	{
-		self.lock().await.scan(rng, limit, version).await
+		self.lock().await.scan(rng, limit, self.snapshot(version)).await
 	}
 
 	/// Count the total number of keys within a range in the datastore.
@@ -340,7 +392,7 @@ impl Transaction {
 	where
 		K: KeyEncode + Debug,
 	{
-		self.lock().await.batch_keys(rng, batch, version).await
+		self.lock().await.batch_keys(rng, batch, self.snapshot(version)).await
 	}
 
 	/// Retrieve a batched scan over a specific range of keys in the datastore.
@@ -356,7 +408,7 @@ impl Transaction {
 	where
 		K: KeyEncode + Debug,
 	{
-		self.lock().await.batch_keys_vals(rng, batch, version).await
+		self.lock().await.batch_keys_vals(rng, batch, self.snapshot(version)).await
 	}
 
 	/// Retrieve a batched scan over a specific range of keys in the datastore.
@@ -740,6 +792,50 @@ impl Transaction {
 		}
 	}
 
+	/// Retrieve all event definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn all_db_events(
+		&self,
+		ns: &str,
+		db: &str,
+	) -> Result<Arc<[DefineEventStatement]>, Error> {
+		let qey = cache::tx::Lookup::Des(ns, db);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_evs(),
+			None => {
+				let beg = crate::key::database::ev::prefix(ns, db)?;
+				let end = crate::key::database::ev::suffix(ns, db)?;
+				let val = self.getr(beg..end, None).await?;
+				let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+				let entry = cache::tx::Entry::Evs(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
+	/// Retrieve all test definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn all_db_tests(
+		&self,
+		ns: &str,
+		db: &str,
+	) -> Result<Arc<[DefineTestStatement]>, Error> {
+		let qey = cache::tx::Lookup::Tts(ns, db);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_tts(),
+			None => {
+				let beg = crate::key::database::tt::prefix(ns, db)?;
+				let end = crate::key::database::tt::suffix(ns, db)?;
+				let val = self.getr(beg..end, None).await?;
+				let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+				let entry = cache::tx::Entry::Tts(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
 	/// Retrieve all model definitions for a specific database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn all_db_models(
@@ -1333,6 +1429,56 @@ impl Transaction {
 		}
 	}
 
+	/// Retrieve a specific event definition from a database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_db_event(
+		&self,
+		ns: &str,
+		db: &str,
+		ev: &str,
+	) -> Result<Arc<DefineEventStatement>, Error> {
+		let qey = cache::tx::Lookup::De(ns, db, ev);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type(),
+			None => {
+				let key = crate::key::database::ev::new(ns, db, ev).encode()?;
+				let val = self.get(key, None).await?.ok_or_else(|| Error::EvNotFound {
+					name: ev.to_owned(),
+				})?;
+				let val: DefineEventStatement = revision::from_slice(&val)?;
+				let val = Arc::new(val);
+				let entr = cache::tx::Entry::Any(val.clone());
+				self.cache.insert(qey, entr);
+				Ok(val)
+			}
+		}
+	}
+
+	/// Retrieve a specific test definition from a database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_db_test(
+		&self,
+		ns: &str,
+		db: &str,
+		tt: &str,
+	) -> Result<Arc<DefineTestStatement>, Error> {
+		let qey = cache::tx::Lookup::Tt(ns, db, tt);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type(),
+			None => {
+				let key = crate::key::database::tt::new(ns, db, tt).encode()?;
+				let val = self.get(key, None).await?.ok_or_else(|| Error::TtNotFound {
+					name: tt.to_owned(),
+				})?;
+				let val: DefineTestStatement = revision::from_slice(&val)?;
+				let val = Arc::new(val);
+				let entr = cache::tx::Entry::Any(val.clone());
+				self.cache.insert(qey, entr);
+				Ok(val)
+			}
+		}
+	}
+
 	/// Retrieve a specific config definition from a database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn get_db_config(
@@ -0,0 +1,223 @@
+use super::KeyDecode as _;
+use super::Transaction;
+use crate::cnf::CHECK_BATCH_SIZE;
+use crate::err::Error;
+use crate::key::debug::Sprintable;
+use crate::sql::{Thing, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
+
+/// The kind of problem which was found in the keys of a datastore
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ProblemKind {
+	/// A key or value which can not be decoded
+	Corrupt,
+	/// Keys which belong to a namespace, database, table or index which is not defined
+	Orphaned,
+}
+
+impl Display for ProblemKind {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Corrupt => f.write_str("corrupt"),
+			Self::Orphaned => f.write_str("orphaned"),
+		}
+	}
+}
+
+/// A problem which was found in the keys of a datastore
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Problem {
+	pub kind: ProblemKind,
+	pub detail: String,
+}
+
+impl Display for Problem {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "[{}] {}", self.kind, self.detail)
+	}
+}
+
+/// The result of scanning every key of a datastore
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Verification {
+	/// The number of keys which were scanned
+	pub keys: u64,
+	/// The problems which were found
+	pub problems: Vec<Problem>,
+}
+
+/// The names of the namespaces, databases, tables and indexes which are defined
+type Catalog = HashMap<String, HashMap<String, HashMap<String, HashSet<String>>>>;
+
+impl Transaction {
+	/// Scans every key in the datastore, checking that it can be decoded and that it belongs to
+	/// a namespace, database, table and index which is defined.
+	///
+	/// Orphaned keys are reported once for each definition which they belong to.
+	pub async fn verify(&self) -> Result<Verification, Error> {
+		let catalog = self.catalog().await?;
+		let mut verification = Verification::default();
+		let mut orphans: BTreeMap<String, u64> = BTreeMap::new();
+		let mut next = Some(vec![0x00]..vec![0xff]);
+		while let Some(rng) = next {
+			let batch = self.batch_keys_vals(rng, *CHECK_BATCH_SIZE, None).await?;
+			next = batch.next;
+			for (k, v) in batch.result {
+				verification.keys += 1;
+				match check(&catalog, &k, &v) {
+					Ok(()) => (),
+					Err(Found::Corrupt(detail)) => verification.problems.push(Problem {
+						kind: ProblemKind::Corrupt,
+						detail: format!("{}: {detail}", k.sprint()),
+					}),
+					Err(Found::Orphaned(owner)) => *orphans.entry(owner).or_default() += 1,
+				}
+			}
+		}
+		for (owner, count) in orphans {
+			verification.problems.push(Problem {
+				kind: ProblemKind::Orphaned,
+				detail: format!("{count} keys belong to {owner}, which is not defined"),
+			});
+		}
+		Ok(verification)
+	}
+
+	async fn catalog(&self) -> Result<Catalog, Error> {
+		let mut catalog = Catalog::new();
+		for ns in self.all_ns().await?.iter() {
+			let dbs = catalog.entry(ns.name.to_raw()).or_default();
+			for db in self.all_db(&ns.name).await?.iter() {
+				let tbs = dbs.entry(db.name.to_raw()).or_default();
+				for tb in self.all_tb(&ns.name, &db.name, None).await?.iter() {
+					let ixs = self.all_tb_indexes(&ns.name, &db.name, &tb.name).await?;
+					tbs.insert(tb.name.to_raw(), ixs.iter().map(|ix| ix.name.to_raw()).collect());
+				}
+			}
+		}
+		Ok(catalog)
+	}
+}
+
+/// What was found to be wrong with a key
+enum Found {
+	Corrupt(String),
+	Orphaned(String),
+}
+
+/// Checks a key and its value, following the layout described in [`crate::key`]
+fn check(catalog: &Catalog, k: &[u8], v: &[u8]) -> Result<(), Found> {
+	// The storage version
+	if k == b"!v" {
+		return Ok(());
+	}
+	let Some(rest) = k.strip_prefix(b"/") else {
+		return Err(Found::Corrupt("The key is not in a known format".to_owned()));
+	};
+	// Only the keys of namespaces are checked further
+	let Some(rest) = rest.strip_prefix(b"*") else {
+		return Ok(());
+	};
+	let (ns, rest) = name(rest)?;
+	let Some(dbs) = catalog.get(&ns) else {
+		return Err(Found::Orphaned(format!("the namespace `{ns}`")));
+	};
+	let Some(rest) = rest.strip_prefix(b"*") else {
+		return Ok(());
+	};
+	let (db, rest) = name(rest)?;
+	let Some(tbs) = dbs.get(&db) else {
+		return Err(Found::Orphaned(format!("the database `{ns}/{db}`")));
+	};
+	let Some(rest) = rest.strip_prefix(b"*") else {
+		return Ok(());
+	};
+	let (tb, rest) = name(rest)?;
+	let Some(ixs) = tbs.get(&tb) else {
+		return Err(Found::Orphaned(format!("the table `{ns}/{db}/{tb}`")));
+	};
+	match rest.first() {
+		// A record
+		Some(b'*') => {
+			crate::key::thing::Thing::decode(k).map_err(|e| corrupt("record key", e))?;
+			revision::from_slice::<Value>(v).map_err(|e| corrupt("record", e))?;
+		}
+		// A graph edge
+		Some(b'~') => {
+			crate::key::graph::Graph::decode(k).map_err(|e| corrupt("graph edge key", e))?;
+		}
+		// The data of an index
+		Some(b'+') => {
+			let (ix, rest) = name(&rest[1..])?;
+			if !ixs.contains(&ix) {
+				return Err(Found::Orphaned(format!("the index `{ix}` on `{ns}/{db}/{tb}`")));
+			}
+			// The entries of unique and non-unique indexes point to a record
+			if rest.first() == Some(&b'*') {
+				crate::key::index::Index::decode(k).map_err(|e| corrupt("index entry key", e))?;
+				revision::from_slice::<Thing>(v).map_err(|e| corrupt("index entry", e))?;
+			}
+		}
+		_ => (),
+	}
+	Ok(())
+}
+
+/// Splits the null terminated name at the start of a key from the rest of the key
+fn name(k: &[u8]) -> Result<(String, &[u8]), Found> {
+	let end = k.iter().position(|&b| b == 0x00).ok_or_else(|| {
+		Found::Corrupt("The key contains a name which is not terminated".to_owned())
+	})?;
+	let name = std::str::from_utf8(&k[..end]).map_err(|e| corrupt("name in the key", e))?;
+	Ok((name.to_owned(), &k[end + 1..]))
+}
+
+fn corrupt(what: &str, e: impl Display) -> Found {
+	Found::Corrupt(format!("The {what} can not be decoded: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::{Datastore, LockType::*, TransactionType::*};
+
+	#[tokio::test]
+	async fn verify_finds_corrupt_and_orphaned_keys() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = crate::dbs::Session::owner().with_ns("test").with_db("test");
+		let sql = "DEFINE INDEX name ON person FIELDS name; CREATE person:one SET name = 'one';";
+		for res in ds.execute(sql, &ses, None).await.unwrap() {
+			res.result.unwrap();
+		}
+		// A consistent datastore has no problems
+		let tx = ds.transaction(Read, Optimistic).await.unwrap();
+		let verification = tx.verify().await.unwrap();
+		tx.cancel().await.unwrap();
+		assert!(verification.keys > 0);
+		assert_eq!(verification.problems, Vec::new());
+		// Corrupt a record, and leave the data of a table and an index behind
+		let tx = ds.transaction(Write, Optimistic).await.unwrap();
+		tx.set(b"/*test\0*test\0*person\0*\0\0\0\x01two\0".to_vec(), b"\xff".to_vec(), None)
+			.await
+			.unwrap();
+		tx.set(b"/*test\0*test\0*removed\0*\0\0\0\x01one\0".to_vec(), vec![], None).await.unwrap();
+		tx.set(b"/*test\0*test\0*person\0+removed\0*\0".to_vec(), vec![], None).await.unwrap();
+		tx.commit().await.unwrap();
+		let tx = ds.transaction(Read, Optimistic).await.unwrap();
+		let verification = tx.verify().await.unwrap();
+		tx.cancel().await.unwrap();
+		let kinds: Vec<_> = verification.problems.iter().map(|p| p.kind).collect();
+		assert_eq!(
+			kinds,
+			vec![ProblemKind::Corrupt, ProblemKind::Orphaned, ProblemKind::Orphaned],
+			"{:?}",
+			verification.problems
+		);
+		assert!(verification.problems[1].detail.contains("the index `removed`"));
+		assert!(verification.problems[2].detail.contains("the table `test/test/removed`"));
+	}
+}
@@ -10,8 +10,10 @@ use super::RpcError;
 use super::RpcProtocolV1;
 use super::RpcProtocolV2;
 use crate::dbs::Session;
+use crate::iam::{Action, ResourceKind};
 use crate::kvs::Datastore;
 use crate::sql::Array;
+use crate::sql::Value;
 
 #[allow(async_fn_in_trait)]
 pub trait RpcContext {
@@ -25,6 +27,21 @@ pub trait RpcContext {
 	fn set_session(&self, session: Arc<Session>);
 	/// The version information for this RPC context
 	fn version_data(&self) -> Data;
+	/// The server metadata for this RPC context
+	fn server_info_data(&self) -> Data {
+		self.kvs().server_info(&self.session(), self.version_data().into()).into()
+	}
+	/// Compacts the datastore, returning the size of its files before and after
+	async fn compact_data(&self) -> Result<Data, RpcError> {
+		// Compaction affects every namespace, so it is limited to root users
+		self.kvs().check(&self.session(), Action::Edit, ResourceKind::Any.on_root())?;
+		let compaction = self.kvs().compact().await?;
+		Ok(Value::from(map! {
+			"before".to_string() => compaction.before.into(),
+			"after".to_string() => compaction.after.into(),
+		})
+		.into())
+	}
 
 	// ------------------------------
 	// Realtime
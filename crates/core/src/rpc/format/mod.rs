@@ -10,12 +10,13 @@ use serde::Serialize;
 use super::{request::Request, RpcError};
 use crate::sql::Value;
 
-pub const PROTOCOLS: [&str; 5] = [
-	"json",     // For basic JSON serialisation
-	"cbor",     // For basic CBOR serialisation
-	"msgpack",  // For basic Msgpack serialisation
-	"bincode",  // For full internal serialisation
-	"revision", // For full versioned serialisation
+pub const PROTOCOLS: [&str; 6] = [
+	"json",       // For basic JSON serialisation
+	"cbor",       // For basic CBOR serialisation
+	"msgpack",    // For basic Msgpack serialisation
+	"bincode",    // For full internal serialisation
+	"revision",   // For full versioned serialisation
+	"revision-2", // For full versioned serialisation, with error details
 ];
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -41,6 +42,7 @@ impl From<&str> for Format {
 			s if s == PROTOCOLS[2] => Format::Msgpack,
 			s if s == PROTOCOLS[3] => Format::Bincode,
 			s if s == PROTOCOLS[4] => Format::Revision,
+			s if s == PROTOCOLS[5] => Format::Revision,
 			_ => Format::Unsupported,
 		}
 	}
@@ -23,6 +23,8 @@ pub enum Method {
 	Patch,
 	Delete,
 	Version,
+	ServerInfo,
+	Compact,
 	Query,
 	Relate,
 	Run,
@@ -74,6 +76,8 @@ impl Method {
 			"patch" => Self::Patch,
 			"delete" => Self::Delete,
 			"version" => Self::Version,
+			"server_info" => Self::ServerInfo,
+			"compact" => Self::Compact,
 			"query" => Self::Query,
 			"relate" => Self::Relate,
 			"run" => Self::Run,
@@ -109,6 +113,8 @@ impl Method {
 			Self::Patch => "patch",
 			Self::Delete => "delete",
 			Self::Version => "version",
+			Self::ServerInfo => "server_info",
+			Self::Compact => "compact",
 			Self::Query => "query",
 			Self::Relate => "relate",
 			Self::Run => "run",
@@ -129,4 +135,21 @@ impl Method {
 	pub fn is_valid(&self) -> bool {
 		!matches!(self, Self::Unknown)
 	}
+
+	/// Checks if the provided method can write data
+	pub fn is_write(&self) -> bool {
+		matches!(
+			self,
+			Self::Insert
+				| Self::Create
+				| Self::Upsert
+				| Self::Update
+				| Self::Merge
+				| Self::Patch
+				| Self::Delete
+				| Self::Query
+				| Self::Relate
+				| Self::Run | Self::InsertRelation
+		)
+	}
 }
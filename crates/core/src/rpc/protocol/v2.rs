@@ -60,6 +60,8 @@ pub trait RpcProtocolV2: RpcContext {
 			Method::Patch => self.patch(params).await,
 			Method::Delete => self.delete(params).await,
 			Method::Version => self.version(params).await,
+			Method::ServerInfo => self.server_info(params).await,
+			Method::Compact => self.compact(params).await,
 			Method::Query => self.query(params).await,
 			Method::Relate => self.relate(params).await,
 			Method::Run => self.run(params).await,
@@ -772,6 +774,24 @@ pub trait RpcProtocolV2: RpcContext {
 		}
 	}
 
+	async fn server_info(&self, params: Array) -> Result<Data, RpcError> {
+		match params.len() {
+			0 => Ok(self.server_info_data()),
+			_ => Err(RpcError::InvalidParams),
+		}
+	}
+
+	// ------------------------------
+	// Methods for maintenance
+	// ------------------------------
+
+	async fn compact(&self, params: Array) -> Result<Data, RpcError> {
+		match params.len() {
+			0 => self.compact_data().await,
+			_ => Err(RpcError::InvalidParams),
+		}
+	}
+
 	// ------------------------------
 	// Methods for querying
 	// ------------------------------
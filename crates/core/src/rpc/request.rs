@@ -1,3 +1,4 @@
+use crate::dbs::Durability;
 use crate::rpc::format::cbor::Cbor;
 use crate::rpc::format::msgpack::Pack;
 use crate::rpc::Method;
@@ -12,6 +13,7 @@ pub static ID: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("id")]);
 pub static METHOD: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("method")]);
 pub static PARAMS: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("params")]);
 pub static VERSION: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("version")]);
+pub static DURABILITY: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("durability")]);
 
 #[derive(Debug)]
 pub struct Request {
@@ -19,6 +21,7 @@ pub struct Request {
 	pub version: Option<u8>,
 	pub method: Method,
 	pub params: Array,
+	pub durability: Option<Durability>,
 }
 
 impl TryFrom<Cbor> for Request {
@@ -59,6 +62,13 @@ impl TryFrom<Value> for Request {
 			},
 			_ => return Err(RpcError::InvalidRequest),
 		};
+		// Fetch the 'durability' argument
+		let durability = match val.pick(&*DURABILITY) {
+			v if v.is_none() => None,
+			v if v.is_null() => None,
+			Value::Strand(v) => Some(v.parse().map_err(|_| RpcError::InvalidRequest)?),
+			_ => return Err(RpcError::InvalidRequest),
+		};
 		// Fetch the 'method' argument
 		let method = match val.pick(&*METHOD) {
 			Value::Strand(v) => v.to_raw(),
@@ -77,6 +87,7 @@ impl TryFrom<Value> for Request {
 			method,
 			params,
 			version,
+			durability,
 		})
 	}
 }
@@ -258,14 +258,14 @@ impl<T> Abolish<T> for Vec<T> {
 // ------------------------------
 
 pub(crate) trait Clump<T> {
-	fn clump(self, clump_size: usize) -> Result<T, Error>;
+	fn clump(self, name: &str, clump_size: usize) -> Result<T, Error>;
 }
 
 impl Clump<Array> for Array {
-	fn clump(self, clump_size: usize) -> Result<Array, Error> {
+	fn clump(self, name: &str, clump_size: usize) -> Result<Array, Error> {
 		if clump_size < 1 {
 			return Err(Error::InvalidArguments {
-				name: "array::clump".to_string(),
+				name: name.to_string(),
 				message: "The second argument must be an integer greater than 0".to_string(),
 			});
 		}
@@ -0,0 +1,37 @@
+use crate::sql::fmt::Fmt;
+use crate::sql::{Idiom, Value};
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// The `DISTINCT` clause of a `SELECT` statement
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum Distinct {
+	/// Rows are duplicates when every selected field is equal
+	#[default]
+	Rows,
+	/// Rows are duplicates when the `ON` fields are equal, keeping the first row
+	On(Vec<Idiom>),
+}
+
+impl Distinct {
+	/// Returns the values by which a row is compared with the other rows
+	pub(crate) fn key(&self, row: &Value) -> Value {
+		match self {
+			Self::Rows => row.clone(),
+			Self::On(idioms) => idioms.iter().map(|i| row.pick(i)).collect::<Vec<_>>().into(),
+		}
+	}
+}
+
+impl Display for Distinct {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Rows => f.write_str("DISTINCT"),
+			Self::On(idioms) => write!(f, "DISTINCT ON ({})", Fmt::comma_separated(idioms)),
+		}
+	}
+}
@@ -167,6 +167,7 @@ impl Expression {
 					Operator::NotLike => fnc::operate::not_like(&l, &r),
 					Operator::AllLike => fnc::operate::all_like(&l, &r),
 					Operator::AnyLike => fnc::operate::any_like(&l, &r),
+					Operator::Similar => fnc::operate::similar(&l, &r),
 					Operator::LessThan => fnc::operate::less_than(&l, &r),
 					Operator::LessThanOrEqual => fnc::operate::less_than_or_equal(&l, &r),
 					Operator::MoreThan => fnc::operate::more_than(&l, &r),
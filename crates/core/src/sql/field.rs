@@ -3,7 +3,7 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::sql::statements::info::InfoStructure;
-use crate::sql::{fmt::Fmt, Idiom, Part, Value};
+use crate::sql::{fmt::Fmt, Idiom, Part, Value, Window};
 use crate::syn;
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -72,6 +72,10 @@ impl Fields {
 		}
 		is_count_only
 	}
+	/// Check if any of the fields is a window function
+	pub(crate) fn has_windows(&self) -> bool {
+		self.0.iter().any(|v| matches!(v, Field::Window { .. }))
+	}
 }
 
 impl Deref for Fields {
@@ -270,13 +274,54 @@ impl Fields {
 						}
 					}
 				}
+				// This window function is computed once every row is known
+				Field::Window {
+					window,
+					alias,
+				} => {
+					let x = window.prepare(stk, ctx, opt, doc).await?;
+					// Check if this is a single VALUE field expression
+					match (self.single().is_some(), alias) {
+						(false, Some(alias)) => out.set(stk, ctx, opt, alias, x).await?,
+						(false, None) => out.set(stk, ctx, opt, &window.to_idiom(), x).await?,
+						(true, _) => out = x,
+					}
+				}
 			}
 		}
 		Ok(out)
 	}
+
+	/// Compute the window functions over the rows which were output by [`Fields::compute`]
+	pub(crate) async fn compute_windows(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		rows: &mut [Value],
+	) -> Result<(), Error> {
+		for v in self.other() {
+			if let Field::Window {
+				window,
+				alias,
+			} = v
+			{
+				let name = alias
+					.as_ref()
+					.map(Cow::Borrowed)
+					.unwrap_or_else(|| Cow::Owned(window.to_idiom()));
+				let path = match self.single() {
+					Some(_) => None,
+					None => Some(name.as_ref()),
+				};
+				window.compute(stk, ctx, opt, rows, path).await?;
+			}
+		}
+		Ok(())
+	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -290,6 +335,13 @@ pub enum Field {
 		/// The `quality` in `SELECT rating AS quality FROM ...`
 		alias: Option<Idiom>,
 	},
+	/// The 'rank() OVER (ORDER BY score)' in `SELECT rank() OVER (ORDER BY score) FROM ...`
+	#[revision(start = 2)]
+	Window {
+		window: Box<Window>,
+		/// The `position` in `SELECT rank() OVER (...) AS position FROM ...`
+		alias: Option<Idiom>,
+	},
 }
 
 impl Display for Field {
@@ -308,6 +360,18 @@ impl Display for Field {
 					Ok(())
 				}
 			}
+			Self::Window {
+				window,
+				alias,
+			} => {
+				Display::fmt(window, f)?;
+				if let Some(alias) = alias {
+					f.write_str(" AS ")?;
+					Display::fmt(alias, f)
+				} else {
+					Ok(())
+				}
+			}
 		}
 	}
 }
@@ -53,10 +53,12 @@ pub(crate) enum OptimisedAggregate {
 	None,
 	Count,
 	CountFunction,
+	CountApproxDistinct,
 	MathMax,
 	MathMin,
 	MathSum,
 	MathMean,
+	MathApproxPercentile,
 	TimeMax,
 	TimeMin,
 }
@@ -165,6 +167,8 @@ impl Function {
 			Self::Normal(f, _) if f == "array::group" => true,
 			Self::Normal(f, _) if f == "array::last" => true,
 			Self::Normal(f, _) if f == "count" => true,
+			Self::Normal(f, _) if f == "count::approx_distinct" => true,
+			Self::Normal(f, _) if f == "math::approx_percentile" => true,
 			Self::Normal(f, _) if f == "math::bottom" => true,
 			Self::Normal(f, _) if f == "math::interquartile" => true,
 			Self::Normal(f, _) if f == "math::max" => true,
@@ -196,6 +200,12 @@ impl Function {
 					OptimisedAggregate::CountFunction
 				}
 			}
+			Self::Normal(f, _) if f == "count::approx_distinct" => {
+				OptimisedAggregate::CountApproxDistinct
+			}
+			Self::Normal(f, _) if f == "math::approx_percentile" => {
+				OptimisedAggregate::MathApproxPercentile
+			}
 			Self::Normal(f, _) if f == "math::max" => OptimisedAggregate::MathMax,
 			Self::Normal(f, _) if f == "math::mean" => OptimisedAggregate::MathMean,
 			Self::Normal(f, _) if f == "math::min" => OptimisedAggregate::MathMin,
@@ -294,6 +304,8 @@ impl Function {
 						Permission::Specific(e) => {
 							// Disable permissions
 							let opt = &opt.new_with_perms(false);
+							// Hide any subqueries named by WITH statements
+							let ctx = &MutableContext::new_unnamed(ctx).freeze();
 							// Process the PERMISSION clause
 							if !stk.run(|stk| e.compute(stk, ctx, opt, doc)).await?.is_truthy() {
 								return Err(Error::FunctionPermissions {
@@ -308,7 +320,8 @@ impl Function {
 				// Track the number of required arguments
 				let mut min_args_len = 0;
 				// Check for any final optional arguments
-				val.args.iter().rev().for_each(|(_, kind)| match kind {
+				val.args.iter().rev().for_each(|(name, kind)| match kind {
+					_ if min_args_len == 0 && val.default(name).is_some() => {}
 					Kind::Option(_) if min_args_len == 0 => {}
 					Kind::Any if min_args_len == 0 => {}
 					_ => min_args_len += 1,
@@ -332,11 +345,23 @@ impl Function {
 						)
 					})
 					.await?;
+				let mut a = a.into_iter();
 				// Duplicate context
 				let mut ctx = MutableContext::new_isolated(ctx);
 				// Process the function arguments
-				for (val, (name, kind)) in a.into_iter().zip(&val.args) {
-					ctx.add_value(name.to_raw(), val.coerce_to(kind)?.into());
+				for (name, kind) in val.args.iter() {
+					let v = match (a.next(), val.default(name)) {
+						(Some(v), _) => v,
+						// Compute the default value with the arguments before it
+						(None, Some(v)) => {
+							let tmp = ctx.freeze();
+							let res = stk.run(|stk| v.compute(stk, &tmp, opt, None)).await?;
+							ctx = MutableContext::new(&tmp);
+							res
+						}
+						(None, None) => Value::None,
+					};
+					ctx.add_value(name.to_raw(), v.coerce_to(kind)?.into());
 				}
 				let ctx = ctx.freeze();
 				// Run the custom function
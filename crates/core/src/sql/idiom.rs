@@ -167,6 +167,16 @@ impl Idiom {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
 		match self.first() {
+			// The starting part is a subquery named by a WITH
+			// statement, so only borrow its shared result
+			Some(Part::Start(Value::Param(p))) if p.named(ctx).is_some() => {
+				// The named subquery is guaranteed to exist
+				let v = p.named(ctx).unwrap().compute(stk, opt).await?;
+				v.get(stk, ctx, opt, doc, self.as_ref().next())
+					.await?
+					.compute(stk, ctx, opt, doc)
+					.await
+			}
 			// The starting part is a value
 			Some(Part::Start(v)) => {
 				v.compute(stk, ctx, opt, doc)
@@ -12,7 +12,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::{Display, Formatter};
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -29,6 +29,9 @@ pub enum Index {
 	/// HNSW index for distance based metrics
 	#[revision(start = 2)]
 	Hnsw(HnswParams),
+	/// Trigram index for the similarity of strings
+	#[revision(start = 3)]
+	Trigram,
 }
 
 #[revisioned(revision = 2)]
@@ -282,6 +285,7 @@ impl Display for Index {
 				}
 				Ok(())
 			}
+			Self::Trigram => f.write_str("TRIGRAM"),
 		}
 	}
 }
@@ -0,0 +1,370 @@
+use crate::cnf::JOIN_MAX_RECORDS;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::sql::statements::SelectStatement;
+use crate::sql::{
+	Cond, Expression, Fields, Ident, Idiom, Limit, Operator, Part, Table, Value, Values, With,
+};
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum JoinKind {
+	/// Only the rows which match a row of the joined table
+	#[default]
+	Inner,
+	/// Every row, with the matching rows of the joined table if there are any
+	Left,
+}
+
+/// How the records of a joined table are matched with the rows
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum JoinStrategy {
+	/// The matching records are looked up with the index, for each distinct value of the rows
+	Index(String),
+	/// Every record is hashed on the joined field
+	Hash,
+	/// The condition is checked for every pair of a row and a record
+	Loop,
+}
+
+impl Display for JoinStrategy {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Index(_) => f.write_str("Index"),
+			Self::Hash => f.write_str("Hash"),
+			Self::Loop => f.write_str("NestedLoop"),
+		}
+	}
+}
+
+/// The records of a joined table, which are kept while the rows are joined one batch at a time
+#[derive(Default)]
+pub(crate) struct Joined {
+	/// Every record of the table, once it has been read
+	records: Option<Vec<Value>>,
+	/// The records matching each value of the rows, when they are hashed
+	hashed: Option<BTreeMap<Value, Vec<Value>>>,
+}
+
+/// A `JOIN` clause of a `SELECT` statement
+///
+/// Each joined row is an object, with the record of each table set on the name of the table.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Join {
+	pub kind: JoinKind,
+	/// The table which is joined
+	pub what: Table,
+	/// The name which the records of the joined table are set on, instead of the table name
+	pub alias: Option<Ident>,
+	/// The condition which the joined records match
+	pub cond: Value,
+}
+
+impl Join {
+	/// The name which the records of the joined table are set on
+	pub(crate) fn name(&self) -> &str {
+		self.alias.as_ref().map_or(&self.what.0, |v| &v.0)
+	}
+
+	/// Check if we require a writeable transaction
+	pub(crate) fn writeable(&self) -> bool {
+		self.cond.writeable()
+	}
+
+	/// Returns the value of the rows and the field of the joined records which must be equal
+	fn equi_join(&self) -> Option<(&Value, Idiom)> {
+		let Value::Expression(e) = &self.cond else {
+			return None;
+		};
+		let Expression::Binary {
+			l,
+			o: Operator::Equal | Operator::Exact,
+			r,
+		} = e.as_ref()
+		else {
+			return None;
+		};
+		// The field of the joined records can be on either side of the condition
+		let field = |v: &Value| match v {
+			Value::Idiom(i)
+				if i.len() > 1 && i[0] == Part::Field(Ident(self.name().to_owned())) =>
+			{
+				Some(Idiom::from(&i[1..]))
+			}
+			_ => None,
+		};
+		match (field(l), field(r)) {
+			(None, Some(i)) => Some((l, i)),
+			(Some(i), None) => Some((r, i)),
+			_ => None,
+		}
+	}
+
+	/// Returns the part of a `WHERE` condition which only checks the records of a table
+	///
+	/// The condition of the rows is split on `AND`, and the checks which only use the fields of
+	/// the table, parameters, and constant values are rewritten to check the records themselves.
+	/// Also returns whether the whole condition was rewritten.
+	pub(crate) fn push_down(tb: &str, cond: &Value) -> Option<(Value, bool)> {
+		match cond {
+			Value::Expression(e) => match e.as_ref() {
+				Expression::Binary {
+					l,
+					o: Operator::And,
+					r,
+				} => match (Self::push_down(tb, l), Self::push_down(tb, r)) {
+					(Some((l, lc)), Some((r, rc))) => Some((
+						Value::Expression(Box::new(Expression::Binary {
+							l,
+							o: Operator::And,
+							r,
+						})),
+						lc && rc,
+					)),
+					(Some((v, _)), None) | (None, Some((v, _))) => Some((v, false)),
+					(None, None) => None,
+				},
+				_ => Self::rewrite(tb, cond).map(|v| (v, true)),
+			},
+			_ => Self::rewrite(tb, cond).map(|v| (v, true)),
+		}
+	}
+
+	/// Rewrites a check of the rows as a check of the records of a table, if it only uses them
+	fn rewrite(tb: &str, v: &Value) -> Option<Value> {
+		match v {
+			Value::Idiom(i) if i.len() > 1 && i[0] == Part::Field(Ident(tb.to_owned())) => {
+				match i[1] {
+					Part::Field(_) => Some(Value::Idiom(Idiom::from(&i[1..]))),
+					_ => None,
+				}
+			}
+			Value::Idiom(_) => None,
+			Value::Param(_) => Some(v.clone()),
+			Value::Expression(e) => match e.as_ref() {
+				Expression::Unary {
+					o,
+					v,
+				} => Some(Value::Expression(Box::new(Expression::Unary {
+					o: o.clone(),
+					v: Self::rewrite(tb, v)?,
+				}))),
+				Expression::Binary {
+					l,
+					o,
+					r,
+				} => Some(Value::Expression(Box::new(Expression::Binary {
+					l: Self::rewrite(tb, l)?,
+					o: o.clone(),
+					r: Self::rewrite(tb, r)?,
+				}))),
+			},
+			v if v.is_static() => Some(v.clone()),
+			_ => None,
+		}
+	}
+
+	/// Returns the strategy which matches the records with the rows, given the hint of the statement
+	///
+	/// `WITH BRUTEFORCE` checks the condition for every pair of a row and a record. `WITH NOINDEX`
+	/// and `WITH NOJOIN` hash every record, and `WITH INDEX` only looks the records up with the
+	/// given indexes.
+	pub(crate) async fn strategy(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		with: Option<&With>,
+	) -> Result<JoinStrategy, Error> {
+		let Some((_, field)) = self.equi_join() else {
+			return Ok(JoinStrategy::Loop);
+		};
+		match with {
+			Some(With::BruteForce) => Ok(JoinStrategy::Loop),
+			Some(With::NoIndex | With::NoJoin) => Ok(JoinStrategy::Hash),
+			with => {
+				let (ns, db) = opt.ns_db()?;
+				let ixs = ctx.tx().all_tb_indexes(ns, db, &self.what.0).await?;
+				let ix = ixs.iter().find(|ix| {
+					ix.cols.first() == Some(&field)
+						&& match with {
+							Some(With::Index(names)) => names.contains(&ix.name.0),
+							_ => true,
+						}
+				});
+				Ok(ix.map_or(JoinStrategy::Hash, |ix| JoinStrategy::Index(ix.name.0.clone())))
+			}
+		}
+	}
+
+	/// Joins the records of the table with a batch of the rows of the previous tables
+	///
+	/// Without an index, every record of the joined table is held in memory until every batch
+	/// has been joined, so this fails when the table has more than `SURREAL_JOIN_MAX_RECORDS`
+	/// records. With an index, the records matching each distinct value of the batch are held in
+	/// memory. No more than `max` joined rows are returned, when given.
+	#[allow(clippy::mutable_key_type, clippy::too_many_arguments)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		strategy: &JoinStrategy,
+		joined: &mut Joined,
+		rows: Vec<Value>,
+		max: Option<usize>,
+	) -> Result<Vec<Value>, Error> {
+		let name = Idiom::from(self.name());
+		let mut res = Vec::new();
+		match (strategy, self.equi_join()) {
+			(JoinStrategy::Index(_) | JoinStrategy::Hash, Some((value, field))) => {
+				let indexed = match strategy {
+					JoinStrategy::Index(ix) => Some(With::Index(vec![ix.clone()])),
+					_ => None,
+				};
+				// Every record is hashed on the field once, unless it is indexed
+				if indexed.is_none() && joined.hashed.is_none() {
+					let mut hashed: BTreeMap<Value, Vec<Value>> = BTreeMap::new();
+					for record in self.select_all(stk, ctx, opt).await? {
+						hashed.entry(record.pick(&field)).or_default().push(record);
+					}
+					joined.hashed = Some(hashed);
+				}
+				// The index is queried once for each value of the batch
+				let mut looked_up: BTreeMap<Value, Vec<Value>> = BTreeMap::new();
+				for row in rows {
+					let key = value.compute(stk, ctx, opt, Some(&row.clone().into())).await?;
+					if indexed.is_some() && !looked_up.contains_key(&key) {
+						let cond = Cond(Value::Expression(Box::new(Expression::Binary {
+							l: Value::Idiom(field.clone()),
+							o: Operator::Equal,
+							r: key.clone(),
+						})));
+						let with = indexed.clone();
+						let records = self.select(stk, ctx, opt, Some(cond), with, None).await?;
+						looked_up.insert(key.clone(), records);
+					}
+					let matches = joined.hashed.as_ref().unwrap_or(&looked_up);
+					let records = matches.get(&key).map(Vec::as_slice).unwrap_or_default();
+					self.push(&mut res, &name, row, records.iter().cloned());
+					// Stop once there are enough rows
+					if max.is_some_and(|m| res.len() >= m) {
+						break;
+					}
+				}
+			}
+			_ => {
+				// Every record is read once
+				if joined.records.is_none() {
+					joined.records = Some(self.select_all(stk, ctx, opt).await?);
+				}
+				let records = joined.records.as_deref().unwrap_or_default();
+				for row in rows {
+					let mut matched = Vec::new();
+					for record in records.iter() {
+						let mut pair = row.clone();
+						pair.put(&name, record.clone());
+						let doc = CursorDoc::from(pair);
+						if self.cond.compute(stk, ctx, opt, Some(&doc)).await?.is_truthy() {
+							matched.push(record.clone());
+						}
+					}
+					self.push(&mut res, &name, row, matched.into_iter());
+					// Stop once there are enough rows
+					if max.is_some_and(|m| res.len() >= m) {
+						break;
+					}
+				}
+			}
+		}
+		Ok(res)
+	}
+
+	/// Adds a row joined with each matching record, or alone if this is a left join
+	fn push(
+		&self,
+		res: &mut Vec<Value>,
+		name: &Idiom,
+		row: Value,
+		records: impl ExactSizeIterator<Item = Value>,
+	) {
+		if records.len() == 0 {
+			if let JoinKind::Left = self.kind {
+				res.push(row);
+			}
+			return;
+		}
+		for record in records {
+			let mut joined = row.clone();
+			joined.put(name, record);
+			res.push(joined);
+		}
+	}
+
+	/// Selects every record of the joined table, up to the maximum which is held in memory
+	async fn select_all(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+	) -> Result<Vec<Value>, Error> {
+		let max = *JOIN_MAX_RECORDS;
+		let limit = Limit(Value::from(max.saturating_add(1) as i64));
+		let res = self.select(stk, ctx, opt, None, None, Some(limit)).await?;
+		if res.len() > max {
+			return Err(Error::JoinTooLarge {
+				table: self.what.0.clone(),
+				max,
+			});
+		}
+		Ok(res)
+	}
+
+	/// Selects the records of the joined table, which checks the permissions of the table
+	async fn select(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		cond: Option<Cond>,
+		with: Option<With>,
+		limit: Option<Limit>,
+	) -> Result<Vec<Value>, Error> {
+		let stm = SelectStatement {
+			expr: Fields::all(),
+			what: Values(vec![Value::Table(self.what.clone())]),
+			cond,
+			with,
+			limit,
+			..Default::default()
+		};
+		match stk.run(|stk| stm.compute(stk, ctx, opt, None)).await? {
+			Value::Array(v) => Ok(v.0),
+			_ => Ok(vec![]),
+		}
+	}
+}
+
+impl Display for Join {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		if let JoinKind::Left = self.kind {
+			f.write_str("LEFT ")?;
+		}
+		write!(f, "JOIN {}", self.what)?;
+		if let Some(ref v) = self.alias {
+			write!(f, " AS {v}")?;
+		}
+		write!(f, " ON {}", self.cond)
+	}
+}
@@ -19,6 +19,7 @@ pub(crate) mod constant;
 pub(crate) mod data;
 pub(crate) mod datetime;
 pub(crate) mod dir;
+pub(crate) mod distinct;
 pub(crate) mod duration;
 pub(crate) mod edges;
 pub(crate) mod escape;
@@ -36,6 +37,7 @@ pub(crate) mod group;
 pub(crate) mod id;
 pub(crate) mod ident;
 pub(crate) mod idiom;
+pub(crate) mod join;
 pub(crate) mod kind;
 pub(crate) mod language;
 pub(crate) mod limit;
@@ -57,6 +59,7 @@ pub(crate) mod reference;
 pub(crate) mod regex;
 pub(crate) mod scoring;
 pub(crate) mod script;
+pub(crate) mod set_operator;
 pub(crate) mod split;
 pub(crate) mod start;
 pub(crate) mod statement;
@@ -72,6 +75,7 @@ pub(crate) mod uuid;
 pub(crate) mod value;
 pub(crate) mod version;
 pub(crate) mod view;
+pub(crate) mod window;
 pub(crate) mod with;
 
 pub mod index;
@@ -97,6 +101,7 @@ pub use self::constant::Constant;
 pub use self::data::Data;
 pub use self::datetime::Datetime;
 pub use self::dir::Dir;
+pub use self::distinct::Distinct;
 pub use self::duration::Duration;
 pub use self::edges::Edges;
 pub use self::explain::Explain;
@@ -118,6 +123,9 @@ pub use self::ident::Ident;
 pub use self::idiom::Idiom;
 pub use self::idiom::Idioms;
 pub use self::index::Index;
+pub use self::join::Join;
+pub use self::join::JoinKind;
+pub(crate) use self::join::Joined;
 pub use self::kind::Kind;
 pub use self::kind::Literal;
 pub use self::limit::Limit;
@@ -138,6 +146,7 @@ pub use self::range::Range;
 pub use self::regex::Regex;
 pub use self::scoring::Scoring;
 pub use self::script::Script;
+pub use self::set_operator::SetOperator;
 pub use self::split::Split;
 pub use self::split::Splits;
 pub use self::start::Start;
@@ -153,11 +162,14 @@ pub use self::timeout::Timeout;
 pub use self::tokenizer::Tokenizer;
 pub use self::uuid::Uuid;
 pub use self::value::serde::from_value;
+pub use self::value::serde::from_value_ref;
 pub use self::value::serde::to_value;
 pub use self::value::Value;
 pub use self::value::Values;
 pub use self::version::Version;
 pub use self::view::View;
+pub use self::window::Window;
+pub use self::window::WindowFunction;
 pub use self::with::With;
 
 // module reexporting parsing function to prevent a breaking change.
@@ -1,4 +1,4 @@
-use crate::ctx::Context;
+use crate::ctx::{Context, MutableContext};
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
@@ -86,6 +86,8 @@ impl Model {
 				Permission::Specific(e) => {
 					// Disable permissions
 					let opt = &opt.new_with_perms(false);
+					// Hide any subqueries named by WITH statements
+					let ctx = &MutableContext::new_unnamed(ctx).freeze();
 					// Process the PERMISSION clause
 					if !stk.run(|stk| e.compute(stk, ctx, opt, doc)).await?.is_truthy() {
 						return Err(Error::FunctionPermissions {
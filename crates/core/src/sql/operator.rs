@@ -6,7 +6,7 @@ use std::fmt;
 use std::fmt::Write;
 
 /// Binary operators.
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -65,6 +65,9 @@ pub enum Operator {
 	Ann(u32, u32), // <|{k},{ef}|>
 	//
 	Rem, // %
+	//
+	#[revision(start = 3)]
+	Similar, // ~%
 }
 
 impl Default for Operator {
@@ -118,6 +121,7 @@ impl fmt::Display for Operator {
 			Self::NotLike => f.write_str("!~"),
 			Self::AllLike => f.write_str("*~"),
 			Self::AnyLike => f.write_str("?~"),
+			Self::Similar => f.write_str("~%"),
 			Self::LessThan => f.write_char('<'),
 			Self::LessThanOrEqual => f.write_str("<="),
 			Self::MoreThan => f.write_char('>'),
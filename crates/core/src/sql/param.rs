@@ -1,10 +1,11 @@
 use crate::{
-	ctx::Context,
+	cnf::PROTECTED_PARAM_NAMES,
+	ctx::{Context, MutableContext},
 	dbs::Options,
 	doc::CursorDoc,
 	err::Error,
 	iam::Action,
-	sql::{ident::Ident, value::Value, Permission},
+	sql::{ident::Ident, statements::with::NamedSubquery, value::Value, Permission},
 };
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -46,6 +47,14 @@ impl Deref for Param {
 }
 
 impl Param {
+	/// Get the subquery named by a WITH statement for this param, if any.
+	/// A named subquery can never shadow one of the protected params.
+	pub(crate) fn named<'a>(&self, ctx: &'a Context) -> Option<&'a NamedSubquery> {
+		match PROTECTED_PARAM_NAMES.contains(&self.as_str()) {
+			true => None,
+			false => ctx.named(self.as_str()),
+		}
+	}
 	/// Process this type returning a computed simple Value
 	pub(crate) async fn compute(
 		&self,
@@ -63,6 +72,12 @@ impl Param {
 				// The base document does not exist
 				None => Ok(Value::None),
 			},
+			// This is a subquery named by a WITH statement
+			_ if self.named(ctx).is_some() => {
+				// The named subquery is guaranteed to exist
+				let v = self.named(ctx).unwrap().compute(stk, opt).await?;
+				Ok(v.as_ref().clone())
+			}
 			// This is a normal param
 			v => match ctx.value(v) {
 				// The param has been set locally
@@ -90,6 +105,8 @@ impl Param {
 									Permission::Specific(e) => {
 										// Disable permissions
 										let opt = &opt.new_with_perms(false);
+										// Hide any subqueries named by WITH statements
+										let ctx = &MutableContext::new_unnamed(ctx).freeze();
 										// Process the PERMISSION clause
 										if !e.compute(stk, ctx, opt, doc).await?.is_truthy() {
 											return Err(Error::ParamPermissions {
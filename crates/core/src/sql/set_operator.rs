@@ -0,0 +1,97 @@
+use crate::sql::Value;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+
+/// An operator which combines the results of two `SELECT` statements
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum SetOperator {
+	/// The rows of both results, without duplicates
+	#[default]
+	Union,
+	/// The rows of both results, with duplicates
+	UnionAll,
+	/// The rows which are in both results, without duplicates
+	Intersect,
+	/// The rows which are in both results, as many times as they are in both
+	IntersectAll,
+	/// The rows of the first result which are not in the second, without duplicates
+	Except,
+	/// The rows of the first result, less one for each time they are in the second
+	ExceptAll,
+}
+
+impl SetOperator {
+	/// Whether this operator binds tighter than the others
+	pub(crate) fn is_intersect(&self) -> bool {
+		matches!(self, Self::Intersect | Self::IntersectAll)
+	}
+
+	/// Combines two results, keeping the order of the rows
+	#[allow(clippy::mutable_key_type)]
+	pub(crate) fn combine(&self, left: Value, right: Value) -> Value {
+		let (left, right) = (Self::rows(left), Self::rows(right));
+		let rows: Vec<Value> = match self {
+			Self::UnionAll => left.into_iter().chain(right).collect(),
+			Self::Union => {
+				let mut seen = BTreeSet::new();
+				left.into_iter().chain(right).filter(|v| seen.insert(v.clone())).collect()
+			}
+			Self::Intersect | Self::Except => {
+				let other: BTreeSet<Value> = right.into_iter().collect();
+				let keep = matches!(self, Self::Intersect);
+				let mut seen = BTreeSet::new();
+				left.into_iter()
+					.filter(|v| other.contains(v) == keep && seen.insert(v.clone()))
+					.collect()
+			}
+			Self::IntersectAll | Self::ExceptAll => {
+				let mut other: BTreeMap<Value, usize> = BTreeMap::new();
+				for v in right {
+					*other.entry(v).or_default() += 1;
+				}
+				let keep = matches!(self, Self::IntersectAll);
+				left.into_iter()
+					.filter(|v| {
+						// Each row of the second result matches one row of the first result
+						let matched = match other.get_mut(v) {
+							Some(n) if *n > 0 => {
+								*n -= 1;
+								true
+							}
+							_ => false,
+						};
+						matched == keep
+					})
+					.collect()
+			}
+		};
+		rows.into()
+	}
+
+	/// The rows of a result, which is a single row when it is not an array
+	fn rows(v: Value) -> Vec<Value> {
+		match v {
+			Value::Array(v) => v.0,
+			Value::None => vec![],
+			v => vec![v],
+		}
+	}
+}
+
+impl Display for SetOperator {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.write_str(match self {
+			Self::Union => "UNION",
+			Self::UnionAll => "UNION ALL",
+			Self::Intersect => "INTERSECT",
+			Self::IntersectAll => "INTERSECT ALL",
+			Self::Except => "EXCEPT",
+			Self::ExceptAll => "EXCEPT ALL",
+		})
+	}
+}
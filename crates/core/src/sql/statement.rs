@@ -8,11 +8,12 @@ use crate::sql::{
 	fmt::{Fmt, Pretty},
 	statements::{
 		AlterStatement, AnalyzeStatement, BeginStatement, BreakStatement, CancelStatement,
-		CommitStatement, ContinueStatement, CreateStatement, DefineStatement, DeleteStatement,
-		ForeachStatement, IfelseStatement, InfoStatement, InsertStatement, KillStatement,
-		LiveStatement, OptionStatement, OutputStatement, RelateStatement, RemoveStatement,
-		SelectStatement, SetStatement, ShowStatement, SleepStatement, ThrowStatement,
-		UpdateStatement, UpsertStatement, UseStatement,
+		CheckStatement, CommitStatement, ContinueStatement, CreateStatement, DefineStatement,
+		DeleteStatement, EvictStatement, ForeachStatement, IfelseStatement, InfoStatement,
+		InsertStatement, KillStatement, LiveStatement, OptionStatement, OutputStatement,
+		ReferencesStatement, RelateStatement, RemoveStatement, RunStatement, SelectStatement,
+		SetStatement, ShowStatement, SleepStatement, ThrowStatement, UpdateStatement,
+		UpsertStatement, UseStatement, WithStatement,
 	},
 	value::Value,
 };
@@ -55,7 +56,7 @@ impl Display for Statements {
 	}
 }
 
-#[revisioned(revision = 5)]
+#[revisioned(revision = 10)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -96,6 +97,16 @@ pub enum Statement {
 	// TODO(gguillemas): Document once bearer access is no longer experimental.
 	#[revision(start = 5)]
 	Access(AccessStatement),
+	#[revision(start = 6)]
+	References(ReferencesStatement),
+	#[revision(start = 7)]
+	Check(CheckStatement),
+	#[revision(start = 8)]
+	Run(RunStatement),
+	#[revision(start = 9)]
+	Evict(EvictStatement),
+	#[revision(start = 10)]
+	With(WithStatement),
 }
 
 impl Statement {
@@ -107,10 +118,12 @@ impl Statement {
 			Self::Alter(_) => true,
 			Self::Analyze(_) => false,
 			Self::Break(_) => false,
+			Self::Check(v) => v.writeable(),
 			Self::Continue(_) => false,
 			Self::Create(v) => v.writeable(),
 			Self::Define(_) => true,
 			Self::Delete(v) => v.writeable(),
+			Self::Evict(_) => true,
 			Self::Foreach(v) => v.writeable(),
 			Self::Ifelse(v) => v.writeable(),
 			Self::Info(_) => false,
@@ -120,8 +133,10 @@ impl Statement {
 			Self::Output(v) => v.writeable(),
 			Self::Option(_) => false,
 			Self::Rebuild(_) => true,
+			Self::References(_) => false,
 			Self::Relate(v) => v.writeable(),
 			Self::Remove(_) => true,
+			Self::Run(_) => true,
 			Self::Select(v) => v.writeable(),
 			Self::Set(v) => v.writeable(),
 			Self::Show(_) => false,
@@ -130,6 +145,7 @@ impl Statement {
 			Self::Upsert(v) => v.writeable(),
 			Self::Update(v) => v.writeable(),
 			Self::Use(_) => false,
+			Self::With(v) => v.writeable(),
 			_ => false,
 		}
 	}
@@ -162,9 +178,12 @@ impl Statement {
 			Self::Alter(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Analyze(v) => v.compute(ctx, opt, doc).await,
 			Self::Break(v) => v.compute(ctx, opt, doc).await,
+			Self::Check(v) => v.compute(ctx, opt, doc).await,
 			Self::Continue(v) => v.compute(ctx, opt, doc).await,
 			Self::Create(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Delete(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Evict(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Define(v) if opt.dry_run => v.dry_run(stk, ctx, opt, doc).await,
 			Self::Define(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Foreach(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Ifelse(v) => v.compute(stk, ctx, opt, doc).await,
@@ -175,7 +194,9 @@ impl Statement {
 			Self::Output(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Relate(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Rebuild(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::References(v) => v.compute(ctx, opt, doc).await,
 			Self::Remove(v) => v.compute(ctx, opt, doc).await,
+			Self::Run(v) => v.compute(ctx, opt, doc).await,
 			Self::Select(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Set(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Show(v) => v.compute(ctx, opt, doc).await,
@@ -183,6 +204,7 @@ impl Statement {
 			Self::Throw(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Update(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Upsert(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::With(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Value(v) => {
 				// Ensure futures are processed
 				let opt = &opt.new_with_futures(true);
@@ -204,11 +226,13 @@ impl Display for Statement {
 			Self::Begin(v) => write!(Pretty::from(f), "{v}"),
 			Self::Break(v) => write!(Pretty::from(f), "{v}"),
 			Self::Cancel(v) => write!(Pretty::from(f), "{v}"),
+			Self::Check(v) => write!(Pretty::from(f), "{v}"),
 			Self::Commit(v) => write!(Pretty::from(f), "{v}"),
 			Self::Continue(v) => write!(Pretty::from(f), "{v}"),
 			Self::Create(v) => write!(Pretty::from(f), "{v}"),
 			Self::Define(v) => write!(Pretty::from(f), "{v}"),
 			Self::Delete(v) => write!(Pretty::from(f), "{v}"),
+			Self::Evict(v) => write!(Pretty::from(f), "{v}"),
 			Self::Foreach(v) => write!(Pretty::from(f), "{v}"),
 			Self::Insert(v) => write!(Pretty::from(f), "{v}"),
 			Self::Ifelse(v) => write!(Pretty::from(f), "{v}"),
@@ -218,8 +242,10 @@ impl Display for Statement {
 			Self::Option(v) => write!(Pretty::from(f), "{v}"),
 			Self::Output(v) => write!(Pretty::from(f), "{v}"),
 			Self::Rebuild(v) => write!(Pretty::from(f), "{v}"),
+			Self::References(v) => write!(Pretty::from(f), "{v}"),
 			Self::Relate(v) => write!(Pretty::from(f), "{v}"),
 			Self::Remove(v) => write!(Pretty::from(f), "{v}"),
+			Self::Run(v) => write!(Pretty::from(f), "{v}"),
 			Self::Select(v) => write!(Pretty::from(f), "{v}"),
 			Self::Set(v) => write!(Pretty::from(f), "{v}"),
 			Self::Show(v) => write!(Pretty::from(f), "{v}"),
@@ -228,6 +254,7 @@ impl Display for Statement {
 			Self::Update(v) => write!(Pretty::from(f), "{v}"),
 			Self::Upsert(v) => write!(Pretty::from(f), "{v}"),
 			Self::Use(v) => write!(Pretty::from(f), "{v}"),
+			Self::With(v) => write!(Pretty::from(f), "{v}"),
 		}
 	}
 }
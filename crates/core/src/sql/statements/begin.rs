@@ -1,15 +1,30 @@
+use crate::sql::Version;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
-pub struct BeginStatement;
+pub struct BeginStatement {
+	/// Whether the transaction is only allowed to read data
+	#[revision(start = 2)]
+	pub readonly: bool,
+	/// The snapshot which all reads within the transaction are pinned to
+	#[revision(start = 2)]
+	pub at: Option<Version>,
+}
 
 impl fmt::Display for BeginStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		f.write_str("BEGIN TRANSACTION")
+		f.write_str("BEGIN TRANSACTION")?;
+		if self.readonly {
+			f.write_str(" READONLY")?;
+		}
+		if let Some(ref v) = self.at {
+			write!(f, " AT {}", v.0)?;
+		}
+		Ok(())
 	}
 }
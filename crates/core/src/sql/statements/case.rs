@@ -0,0 +1,90 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::sql::fmt::{fmt_separated_by, Fmt};
+use crate::sql::Value;
+
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct CaseStatement {
+	/// The value which each branch is compared with, if the branches are not conditions
+	pub value: Option<Value>,
+	/// Any number of branches, each with a condition or value followed by a body
+	pub exprs: Vec<(Value, Value)>,
+	/// The final else body, if there is one
+	pub close: Option<Value>,
+}
+
+impl CaseStatement {
+	/// Check if we require a writeable transaction
+	pub(crate) fn writeable(&self) -> bool {
+		if self.value.as_ref().is_some_and(Value::writeable) {
+			return true;
+		}
+		for (cond, then) in self.exprs.iter() {
+			if cond.writeable() || then.writeable() {
+				return true;
+			}
+		}
+		self.close.as_ref().is_some_and(Value::writeable)
+	}
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		let value = match self.value {
+			Some(ref v) => Some(v.compute(stk, ctx, opt, doc).await?),
+			None => None,
+		};
+		for (ref cond, ref then) in &self.exprs {
+			let v = cond.compute(stk, ctx, opt, doc).await?;
+			// Without a value, each branch is a condition
+			let matched = match value {
+				Some(ref value) => value.equal(&v),
+				None => v.is_truthy(),
+			};
+			if matched {
+				return then.compute_unbordered(stk, ctx, opt, doc).await;
+			}
+		}
+		match self.close {
+			Some(ref v) => v.compute_unbordered(stk, ctx, opt, doc).await,
+			None => Ok(Value::None),
+		}
+	}
+}
+
+impl Display for CaseStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("CASE")?;
+		if let Some(ref v) = self.value {
+			write!(f, " {v}")?;
+		}
+		write!(
+			f,
+			" {}",
+			&Fmt::new(
+				self.exprs.iter().map(|args| {
+					Fmt::new(args, |(cond, then), f| write!(f, "WHEN {cond} THEN {then}"))
+				}),
+				fmt_separated_by(" "),
+			)
+		)?;
+		if let Some(ref v) = self.close {
+			write!(f, " ELSE {v}")?;
+		}
+		f.write_str(" END")
+	}
+}
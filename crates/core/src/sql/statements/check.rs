@@ -0,0 +1,520 @@
+use crate::cnf::{CHECK_BATCH_SIZE, CHECK_ISSUES_LIMIT};
+use crate::ctx::{Context, MutableContext};
+use crate::dbs::Options;
+use crate::doc::{CursorDoc, Document};
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::key::thing;
+use crate::kvs::LockType::Optimistic;
+use crate::kvs::{Key, KeyDecode as _, Transaction, TransactionType, Val};
+use crate::sql::index::Index;
+use crate::sql::paths::{EDGE, IN, OUT};
+use crate::sql::statements::{DefineFieldStatement, DefineIndexStatement, DeleteStatement};
+use crate::sql::{Base, Duration, Kind, Part, Thing, Value, Values};
+
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::sync::Arc;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct CheckStatement {
+	pub fix: bool,
+	pub throttle: Option<Duration>,
+}
+
+impl CheckStatement {
+	/// Check if we require a writeable transaction
+	pub(crate) fn writeable(&self) -> bool {
+		self.fix
+	}
+	/// Process this type returning a computed simple Value
+	///
+	/// Checks are run by the executor, as each batch of
+	/// the check needs a transaction of its own.
+	pub(crate) async fn compute(
+		&self,
+		_ctx: &Context,
+		_opt: &Options,
+		_doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		Err(Error::CheckInTransaction)
+	}
+	/// Checks the database, outside of any transaction
+	pub(crate) async fn check(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		match self.fix {
+			true => opt.is_allowed(Action::Edit, ResourceKind::Database, &Base::Db)?,
+			false => opt.is_allowed(Action::View, ResourceKind::Database, &Base::Db)?,
+		}
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Check every table in the database
+		let mut checker = Checker {
+			ctx,
+			opt,
+			ns,
+			db,
+			fix: self.fix,
+			throttle: self.throttle.as_ref().map(|v| v.0),
+			issues: Vec::new(),
+			pending: Vec::new(),
+			tables: BTreeMap::new(),
+		};
+		let tbs = {
+			let ctx = checker.transaction(TransactionType::Read).await?;
+			let txn = ctx.tx();
+			let tbs = catch!(txn, txn.all_tb(ns, db, None).await);
+			txn.cancel().await?;
+			tbs
+		};
+		for tb in tbs.iter() {
+			// Views are computed from other tables
+			if tb.view.is_some() {
+				continue;
+			}
+			checker.table(stk, &tb.name).await?;
+		}
+		// Ok all good
+		Ok(checker.report())
+	}
+}
+
+impl Display for CheckStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "CHECK DATABASE")?;
+		if self.fix {
+			write!(f, " FIX")?
+		}
+		if let Some(ref v) = self.throttle {
+			write!(f, " THROTTLE {v}")?
+		}
+		Ok(())
+	}
+}
+
+/// The kind of inconsistency which was found
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Check {
+	Index,
+	Edge,
+	Field,
+}
+
+impl Check {
+	fn as_str(&self) -> &'static str {
+		match self {
+			Self::Index => "index",
+			Self::Edge => "edge",
+			Self::Field => "field",
+		}
+	}
+}
+
+/// A single inconsistency which was found in the database
+struct Issue {
+	check: Check,
+	table: String,
+	record: Thing,
+	/// The index or field which the issue relates to
+	name: Option<String>,
+	detail: String,
+	fixed: bool,
+}
+
+impl Issue {
+	fn structure(&self) -> Value {
+		Value::from(map! {
+			"check".to_string() => self.check.as_str().into(),
+			"table".to_string() => self.table.clone().into(),
+			"record".to_string() => self.record.clone().into(),
+			"name".to_string() => self.name.clone().map(Value::from).unwrap_or_default(),
+			"detail".to_string() => self.detail.clone().into(),
+			"fixed".to_string() => self.fixed.into(),
+		})
+	}
+}
+
+/// The number of records and issues which were found in a table
+#[derive(Default)]
+struct Summary {
+	records: u64,
+	issues: u64,
+	fixed: u64,
+}
+
+impl Summary {
+	fn structure(&self) -> Value {
+		Value::from(map! {
+			"records".to_string() => self.records.into(),
+			"issues".to_string() => self.issues.into(),
+			"fixed".to_string() => self.fixed.into(),
+		})
+	}
+}
+
+struct Checker<'a> {
+	ctx: &'a Context,
+	opt: &'a Options,
+	ns: &'a str,
+	db: &'a str,
+	fix: bool,
+	throttle: Option<std::time::Duration>,
+	/// The issues which are listed in the report, up to the configured limit
+	issues: Vec<Issue>,
+	/// The issues of the current batch, which are reported once it finishes
+	pending: Vec<Issue>,
+	tables: BTreeMap<String, Summary>,
+}
+
+impl Checker<'_> {
+	/// Outputs the summary of each table, and the issues which were found
+	fn report(&self) -> Value {
+		let total: u64 = self.tables.values().map(|v| v.issues).sum();
+		Value::from(map! {
+			"tables".to_string() => Value::from(
+				self.tables
+					.iter()
+					.map(|(tb, v)| (tb.clone(), v.structure()))
+					.collect::<BTreeMap<_, _>>(),
+			),
+			"issues".to_string() => self.issues.iter().map(Issue::structure).collect::<Vec<_>>().into(),
+			"truncated".to_string() => (total > self.issues.len() as u64).into(),
+		})
+	}
+
+	/// Records an issue which was found in the current batch
+	///
+	/// The issue is marked as fixed if its fix was written in the batch
+	/// transaction, and is only reported once that transaction finishes.
+	fn push(&mut self, issue: Issue) {
+		self.pending.push(issue);
+	}
+
+	/// Reports the issues of a finished batch, listing them if the report is not yet full
+	fn settle(&mut self, committed: bool) {
+		for mut issue in std::mem::take(&mut self.pending) {
+			issue.fixed &= committed;
+			let summary = self.tables.entry(issue.table.clone()).or_default();
+			summary.issues += 1;
+			summary.fixed += u64::from(issue.fixed);
+			if self.issues.len() < *CHECK_ISSUES_LIMIT {
+				self.issues.push(issue);
+			}
+		}
+	}
+
+	/// Checks every record and index entry of a table
+	///
+	/// Each batch of keys is checked, and fixed, in a separate transaction,
+	/// so that the check does not hold a single transaction open for the
+	/// whole database, and so that any throttle lets other queries through.
+	/// Index entries are fixed in place, rather than by rebuilding the index.
+	async fn table(&mut self, stk: &mut Stk, tb: &str) -> Result<(), Error> {
+		self.tables.entry(tb.to_owned()).or_default();
+		let (ixs, fds) = {
+			let ctx = self.transaction(TransactionType::Read).await?;
+			let txn = ctx.tx();
+			let ixs = catch!(txn, txn.all_tb_indexes(self.ns, self.db, tb).await);
+			let fds = catch!(txn, txn.all_tb_fields(self.ns, self.db, tb, None).await);
+			txn.cancel().await?;
+			(ixs, fds)
+		};
+		// Only plain indexes store an entry per record
+		let ixs: Vec<_> =
+			ixs.iter().filter(|ix| matches!(ix.index, Index::Uniq | Index::Idx)).collect();
+		// Only typed fields at a fixed path can be checked
+		let fds: Vec<_> = fds
+			.iter()
+			.filter(|fd| fd.kind.is_some() && !fd.name.is_id())
+			.filter(|fd| fd.name.iter().all(|p| matches!(p, Part::Field(_))))
+			.filter(|fd| !matches!(fd.kind, Some(Kind::References(_, _))))
+			.collect();
+		// Check each of the records in the table
+		let beg = thing::prefix(self.ns, self.db, tb)?;
+		let end = thing::suffix(self.ns, self.db, tb)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			self.cancelled()?;
+			let ctx = self.transaction(self.write()).await?;
+			let txn = ctx.tx();
+			let batch = catch!(txn, txn.batch_keys_vals(rng, *CHECK_BATCH_SIZE, None).await);
+			next = batch.next;
+			catch!(txn, self.records(stk, &ctx, tb, batch.result, &ixs, &fds).await);
+			self.finish(&txn).await?;
+			if next.is_some() {
+				self.throttle().await;
+			}
+		}
+		// Check each of the index entries of the table
+		for ix in ixs.iter() {
+			self.index_entries(stk, tb, ix).await?;
+		}
+		Ok(())
+	}
+
+	/// Checks a batch of records, removing any edges with missing ends
+	async fn records(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		tb: &str,
+		batch: Vec<(Key, Val)>,
+		ixs: &[&DefineIndexStatement],
+		fds: &[&DefineFieldStatement],
+	) -> Result<(), Error> {
+		let mut dangling = Vec::new();
+		for (k, v) in batch {
+			let k = thing::Thing::decode(&k)?;
+			let rid = Thing::from((k.tb.to_owned(), k.id));
+			let val: Value = revision::from_slice(&v)?;
+			if let Some(summary) = self.tables.get_mut(tb) {
+				summary.records += 1;
+			}
+			if !self.edge(ctx, tb, &rid, &val).await? {
+				dangling.push(rid.clone());
+			}
+			self.fields(tb, &rid, &val, fds);
+			for ix in ixs.iter() {
+				self.record_index(stk, ctx, tb, ix, &rid, val.clone()).await?;
+			}
+		}
+		// Remove each of the dangling edges
+		if self.fix {
+			for rid in dangling {
+				let stm = DeleteStatement {
+					what: Values(vec![Value::Thing(rid)]),
+					..Default::default()
+				};
+				stm.compute(stk, ctx, self.opt, None).await?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Checks that both ends of a graph edge exist
+	async fn edge(
+		&mut self,
+		ctx: &Context,
+		tb: &str,
+		rid: &Thing,
+		val: &Value,
+	) -> Result<bool, Error> {
+		if !val.pick(&*EDGE).is_true() {
+			return Ok(true);
+		}
+		let mut valid = true;
+		for (dir, end) in [("in", val.pick(&*IN)), ("out", val.pick(&*OUT))] {
+			let exists = match &end {
+				Value::Thing(v) => self.exists(ctx, v).await?,
+				_ => false,
+			};
+			if !exists {
+				valid = false;
+				self.push(Issue {
+					check: Check::Edge,
+					table: tb.to_owned(),
+					record: rid.clone(),
+					name: Some(dir.to_owned()),
+					detail: format!("The `{dir}` record {end} does not exist"),
+					fixed: self.fix,
+				});
+			}
+		}
+		Ok(valid)
+	}
+
+	/// Checks that every typed field matches its defined type
+	fn fields(&mut self, tb: &str, rid: &Thing, val: &Value, fds: &[&DefineFieldStatement]) {
+		for fd in fds {
+			let Some(kind) = &fd.kind else {
+				continue;
+			};
+			let field = val.pick(&fd.name);
+			if field.clone().coerce_to(kind).is_err() {
+				self.push(Issue {
+					check: Check::Field,
+					table: tb.to_owned(),
+					record: rid.clone(),
+					name: Some(fd.name.to_string()),
+					detail: format!("Found {field} but the field must conform to: {kind}"),
+					fixed: false,
+				});
+			}
+		}
+	}
+
+	/// Checks that a record has all of its entries in an index, adding any missing entries
+	async fn record_index(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		tb: &str,
+		ix: &DefineIndexStatement,
+		rid: &Thing,
+		val: Value,
+	) -> Result<(), Error> {
+		let txn = ctx.tx();
+		let expected = revision::to_vec(rid)?;
+		for key in self.index_keys(stk, ctx, ix, rid, val).await? {
+			let (detail, fixed) = match txn.get(key.clone(), None).await? {
+				Some(v) if v == expected => continue,
+				// The entry of a unique index belongs to another record
+				Some(_) => ("The index entry points to a different record", false),
+				None if self.fix => {
+					txn.set(key, expected.clone(), None).await?;
+					("The record is missing an index entry", true)
+				}
+				None => ("The record is missing an index entry", false),
+			};
+			self.push(Issue {
+				check: Check::Index,
+				table: tb.to_owned(),
+				record: rid.clone(),
+				name: Some(ix.name.to_raw()),
+				detail: detail.to_owned(),
+				fixed,
+			});
+		}
+		Ok(())
+	}
+
+	/// Checks that every entry in an index points to a record with matching values
+	async fn index_entries(
+		&mut self,
+		stk: &mut Stk,
+		tb: &str,
+		ix: &DefineIndexStatement,
+	) -> Result<(), Error> {
+		let beg = crate::key::index::Index::prefix_beg(self.ns, self.db, tb, &ix.name)?;
+		let end = crate::key::index::Index::prefix_end(self.ns, self.db, tb, &ix.name)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			self.cancelled()?;
+			let ctx = self.transaction(self.write()).await?;
+			let txn = ctx.tx();
+			let batch = catch!(txn, txn.batch_keys_vals(rng, *CHECK_BATCH_SIZE, None).await);
+			next = batch.next;
+			catch!(txn, self.entries(stk, &ctx, tb, ix, batch.result).await);
+			self.finish(&txn).await?;
+			if next.is_some() {
+				self.throttle().await;
+			}
+		}
+		Ok(())
+	}
+
+	/// Checks a batch of index entries, removing any stale entries
+	async fn entries(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		tb: &str,
+		ix: &DefineIndexStatement,
+		batch: Vec<(Key, Val)>,
+	) -> Result<(), Error> {
+		let txn = ctx.tx();
+		for (k, v) in batch {
+			let rid: Thing = revision::from_slice(&v)?;
+			let detail = match txn.get(thing::new(self.ns, self.db, &rid.tb, &rid.id), None).await?
+			{
+				None => "The index entry points to a record which does not exist",
+				Some(val) => {
+					let val: Value = revision::from_slice(&val)?;
+					if self.index_keys(stk, ctx, ix, &rid, val).await?.contains(&k) {
+						continue;
+					}
+					"The index entry does not match the values of the record"
+				}
+			};
+			if self.fix {
+				txn.del(k).await?;
+			}
+			self.push(Issue {
+				check: Check::Index,
+				table: tb.to_owned(),
+				record: rid,
+				name: Some(ix.name.to_raw()),
+				detail: detail.to_owned(),
+				fixed: self.fix,
+			});
+		}
+		Ok(())
+	}
+
+	/// Computes the index keys which a record should be stored under
+	async fn index_keys(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		ix: &DefineIndexStatement,
+		rid: &Thing,
+		val: Value,
+	) -> Result<Vec<Key>, Error> {
+		let doc = CursorDoc::new(Some(Arc::new(rid.clone())), None, val);
+		match Document::build_opt_values(stk, ctx, self.opt, ix, &doc).await? {
+			Some(vals) => Document::index_keys(self.ns, self.db, ix, vals, rid),
+			None => Ok(Vec::new()),
+		}
+	}
+
+	async fn exists(&self, ctx: &Context, rid: &Thing) -> Result<bool, Error> {
+		ctx.tx().exists(thing::new(self.ns, self.db, &rid.tb, &rid.id), None).await
+	}
+
+	/// The type of transaction which is needed for each batch
+	fn write(&self) -> TransactionType {
+		match self.fix {
+			true => TransactionType::Write,
+			false => TransactionType::Read,
+		}
+	}
+
+	/// Starts a transaction for a batch
+	async fn transaction(&self, write: TransactionType) -> Result<Context, Error> {
+		let tf =
+			self.ctx.get_transaction_factory().ok_or_else(|| fail!("No transaction factory"))?;
+		let txn = tf.transaction(write, Optimistic).await?;
+		let mut ctx = MutableContext::new(self.ctx);
+		ctx.set_transaction(Arc::new(txn));
+		Ok(ctx.freeze())
+	}
+
+	/// Commits the fixes which were made in a batch transaction, and reports its issues
+	async fn finish(&mut self, txn: &Transaction) -> Result<(), Error> {
+		match self.fix {
+			true => txn.commit().await?,
+			false => txn.cancel().await?,
+		}
+		self.settle(self.fix);
+		Ok(())
+	}
+
+	/// Stops the check if the query was cancelled or timed out
+	fn cancelled(&self) -> Result<(), Error> {
+		match self.ctx.done(true) {
+			Some(reason) => Err(reason.into()),
+			None => Ok(()),
+		}
+	}
+
+	/// Waits between batches, so that the check does not starve other queries
+	async fn throttle(&self) {
+		if let Some(dur) = self.throttle {
+			#[cfg(target_family = "wasm")]
+			wasmtimer::tokio::sleep(dur).await;
+			#[cfg(not(target_family = "wasm"))]
+			tokio::time::sleep(dur).await;
+		}
+	}
+}
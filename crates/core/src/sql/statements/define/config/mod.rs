@@ -47,10 +47,7 @@ impl DefineConfigStatement {
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Get the config kind
-		let cg = match &self.inner {
-			ConfigInner::GraphQL(_) => "graphql",
-			ConfigInner::Api(_) => "api",
-		};
+		let cg = self.inner.key();
 		// Check if the definition exists
 		let (ns, db) = opt.ns_db()?;
 		if txn.get_db_config(ns, db, cg).await.is_ok() {
@@ -66,7 +63,16 @@ impl DefineConfigStatement {
 		let key = crate::key::database::cg::new(ns, db, cg);
 		txn.get_or_add_ns(ns, opt.strict).await?;
 		txn.get_or_add_db(ns, db, opt.strict).await?;
-		txn.replace(key, revision::to_vec(self)?).await?;
+		txn.replace(
+			key,
+			revision::to_vec(&DefineConfigStatement {
+				// Don't persist the `IF NOT EXISTS` clause to schema
+				if_not_exists: false,
+				overwrite: false,
+				..self.clone()
+			})?,
+		)
+		.await?;
 		// Clear the cache
 		txn.clear();
 		// Ok all good
@@ -79,6 +85,14 @@ impl ConfigInner {
 		ConfigKind::from(self).to_string()
 	}
 
+	/// The name under which this config is stored
+	pub(crate) fn key(&self) -> &'static str {
+		match self {
+			ConfigInner::GraphQL(_) => "graphql",
+			ConfigInner::Api(_) => "api",
+		}
+	}
+
 	pub fn try_into_graphql(self) -> Result<GraphQLConfig, Error> {
 		match self {
 			ConfigInner::GraphQL(g) => Ok(g),
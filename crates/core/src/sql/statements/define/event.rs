@@ -3,16 +3,57 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
+use crate::sql::fmt::Fmt;
 use crate::sql::statements::define::DefineTableStatement;
 use crate::sql::statements::info::InfoStructure;
-use crate::sql::{Base, Ident, Strand, Value, Values};
+use crate::sql::{Base, Duration, Ident, Strand, Value, Values};
 
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 use uuid::Uuid;
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum DatabaseEvent {
+	/// A table was created automatically by a schemaless write
+	TableCreated,
+}
+
+impl Display for DatabaseEvent {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::TableCreated => f.write_str("table_created"),
+		}
+	}
+}
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum TableEvent {
+	/// A record is about to be created
+	Create,
+	/// A record is about to be updated
+	Update,
+	/// A record is about to be deleted
+	Delete,
+}
+
+impl Display for TableEvent {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Create => f.write_str("CREATE"),
+			Self::Update => f.write_str("UPDATE"),
+			Self::Delete => f.write_str("DELETE"),
+		}
+	}
+}
+
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -26,6 +67,21 @@ pub struct DefineEventStatement {
 	pub if_not_exists: bool,
 	#[revision(start = 3)]
 	pub overwrite: bool,
+	/// The database hook this event fires on, when defined FOR DATABASE
+	#[revision(start = 4)]
+	pub database: Option<DatabaseEvent>,
+	/// Whether the event runs in the background, after the transaction which triggered it
+	#[revision(start = 5)]
+	pub asynchronous: bool,
+	/// The number of times an asynchronous event is retried when it fails
+	#[revision(start = 5)]
+	pub retry: Option<u32>,
+	/// The delay before the first retry of an asynchronous event, which doubles on each retry
+	#[revision(start = 5)]
+	pub backoff: Option<Duration>,
+	/// The writes this event fires on before the record is stored, when defined WHEN BEFORE
+	#[revision(start = 6)]
+	pub before: Vec<TableEvent>,
 }
 
 impl DefineEventStatement {
@@ -42,6 +98,10 @@ impl DefineEventStatement {
 		let (ns, db) = opt.ns_db()?;
 		// Fetch the transaction
 		let txn = ctx.tx();
+		// Process a database event
+		if self.database.is_some() {
+			return self.compute_database(ctx, opt).await;
+		}
 		// Check if the definition exists
 		if txn.get_tb_event(ns, db, &self.what, &self.name).await.is_ok() {
 			if self.if_not_exists {
@@ -89,6 +149,47 @@ impl DefineEventStatement {
 		// Ok all good
 		Ok(Value::None)
 	}
+
+	/// Process a DEFINE EVENT FOR DATABASE statement
+	async fn compute_database(&self, ctx: &Context, opt: &Options) -> Result<Value, Error> {
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Check if the definition exists
+		if txn.get_db_event(ns, db, &self.name).await.is_ok() {
+			if self.if_not_exists {
+				return Ok(Value::None);
+			} else if !self.overwrite {
+				return Err(Error::EvAlreadyExists {
+					name: self.name.to_string(),
+				});
+			}
+		}
+		// Process the statement
+		let key = crate::key::database::ev::new(ns, db, &self.name);
+		txn.get_or_add_ns(ns, opt.strict).await?;
+		txn.get_or_add_db(ns, db, opt.strict).await?;
+		txn.set(
+			key,
+			revision::to_vec(&DefineEventStatement {
+				// Don't persist the `IF NOT EXISTS` clause to schema
+				if_not_exists: false,
+				overwrite: false,
+				..self.clone()
+			})?,
+			None,
+		)
+		.await?;
+		// Clear the cache
+		if let Some(cache) = ctx.get_cache() {
+			cache.clear_db_events(ns, db);
+		}
+		// Clear the cache
+		txn.clear();
+		// Ok all good
+		Ok(Value::None)
+	}
 }
 
 impl Display for DefineEventStatement {
@@ -100,7 +201,29 @@ impl Display for DefineEventStatement {
 		if self.overwrite {
 			write!(f, " OVERWRITE")?
 		}
-		write!(f, " {} ON {} WHEN {} THEN {}", self.name, self.what, self.when, self.then)?;
+		match self.database {
+			Some(ref v) => write!(f, " {} FOR DATABASE WHEN {v} THEN {}", self.name, self.then)?,
+			None if !self.before.is_empty() => write!(
+				f,
+				" {} ON {} WHEN BEFORE {} THEN {}",
+				self.name,
+				self.what,
+				Fmt::comma_separated(self.before.iter()),
+				self.then
+			)?,
+			None => {
+				write!(f, " {} ON {} WHEN {} THEN {}", self.name, self.what, self.when, self.then)?
+			}
+		}
+		if self.asynchronous {
+			write!(f, " ASYNC")?
+		}
+		if let Some(ref v) = self.retry {
+			write!(f, " RETRY {v}")?
+		}
+		if let Some(ref v) = self.backoff {
+			write!(f, " BACKOFF {v}")?
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
@@ -112,9 +235,18 @@ impl InfoStructure for DefineEventStatement {
 	fn structure(self) -> Value {
 		Value::from(map! {
 			"name".to_string() => self.name.structure(),
-			"what".to_string() => self.what.structure(),
-			"when".to_string() => self.when.structure(),
+			"what".to_string(), if self.database.is_none() => self.what.structure(),
+			"when".to_string() => match self.database {
+				Some(v) => v.to_string().into(),
+				None if !self.before.is_empty() => {
+					format!("BEFORE {}", Fmt::comma_separated(self.before.iter())).into()
+				}
+				None => self.when.structure(),
+			},
 			"then".to_string() => self.then.structure(),
+			"async".to_string(), if self.asynchronous => true.into(),
+			"retry".to_string(), if let Some(v) = self.retry => v.into(),
+			"backoff".to_string(), if let Some(v) = self.backoff => v.into(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 		})
 	}
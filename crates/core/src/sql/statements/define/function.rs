@@ -7,11 +7,12 @@ use crate::sql::fmt::{is_pretty, pretty_indent};
 use crate::sql::statements::info::InfoStructure;
 use crate::sql::{Base, Block, Ident, Kind, Permission, Strand, Value};
 
+use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Write};
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -27,18 +28,43 @@ pub struct DefineFunctionStatement {
 	pub overwrite: bool,
 	#[revision(start = 4)]
 	pub returns: Option<Kind>,
+	/// The default values of the arguments which may be omitted
+	#[revision(start = 5)]
+	pub defaults: Vec<(Ident, Value)>,
 }
 
 impl DefineFunctionStatement {
 	/// Process this type returning a computed simple Value
 	pub(crate) async fn compute(
 		&self,
+		stk: &mut Stk,
 		ctx: &Context,
 		opt: &Options,
 		_doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
 		// Allowed to run?
 		opt.is_allowed(Action::Edit, ResourceKind::Function, &Base::Db)?;
+		// Check the static default values match the argument types
+		for (name, kind) in self.args.iter() {
+			if let Some(v) = self.default(name).filter(|v| v.is_static()) {
+				let v = stk.run(|stk| v.compute(stk, ctx, opt, None)).await?;
+				match v.coerce_to(kind) {
+					Err(Error::CoerceTo {
+						from,
+						into,
+					}) => {
+						return Err(Error::FcInvalidDefault {
+							name: self.name.to_raw(),
+							arg: name.to_raw(),
+							kind: into,
+							value: from.to_string(),
+						})
+					}
+					Err(e) => return Err(e),
+					Ok(_) => (),
+				}
+			}
+		}
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Check if the definition exists
@@ -72,6 +98,10 @@ impl DefineFunctionStatement {
 		// Ok all good
 		Ok(Value::None)
 	}
+	/// Get the default value of an argument, if it has one
+	pub(crate) fn default(&self, name: &Ident) -> Option<&Value> {
+		self.defaults.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+	}
 }
 
 impl fmt::Display for DefineFunctionStatement {
@@ -89,6 +119,9 @@ impl fmt::Display for DefineFunctionStatement {
 				f.write_str(", ")?;
 			}
 			write!(f, "${name}: {kind}")?;
+			if let Some(v) = self.default(name) {
+				write!(f, " = {v}")?;
+			}
 		}
 		f.write_str(") ")?;
 		if let Some(ref v) = self.returns {
@@ -111,13 +144,17 @@ impl fmt::Display for DefineFunctionStatement {
 
 impl InfoStructure for DefineFunctionStatement {
 	fn structure(self) -> Value {
+		let args = self
+			.args
+			.iter()
+			.map(|(n, k)| match self.default(n) {
+				Some(d) => vec![n.clone().structure(), k.clone().structure(), d.clone()].into(),
+				None => vec![n.clone().structure(), k.clone().structure()].into(),
+			})
+			.collect::<Vec<Value>>();
 		Value::from(map! {
 			"name".to_string() => self.name.structure(),
-			"args".to_string() => self.args
-				.into_iter()
-				.map(|(n, k)| vec![n.structure(), k.structure()].into())
-				.collect::<Vec<Value>>()
-				.into(),
+			"args".to_string() => args.into(),
 			"block".to_string() => self.block.structure(),
 			"permissions".to_string() => self.permissions.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
@@ -121,9 +121,9 @@ impl DefineIndexStatement {
 		}
 		// Clear the cache
 		txn.clear();
-		// Process the index
+		// Process the index, building it within the transaction when this is a dry run
 		#[cfg(not(target_family = "wasm"))]
-		if self.concurrently {
+		if self.concurrently && !opt.dry_run {
 			self.async_index(ctx, opt)?;
 		} else {
 			self.sync_index(stk, ctx, opt, doc).await?;
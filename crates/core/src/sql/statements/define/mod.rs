@@ -12,6 +12,7 @@ mod model;
 mod namespace;
 mod param;
 mod table;
+mod test;
 mod user;
 
 pub use access::DefineAccessStatement;
@@ -27,6 +28,7 @@ pub use model::DefineModelStatement;
 pub use namespace::DefineNamespaceStatement;
 pub use param::DefineParamStatement;
 pub use table::DefineTableStatement;
+pub use test::DefineTestStatement;
 pub use user::DefineUserStatement;
 
 pub use deprecated::scope::DefineScopeStatement;
@@ -35,19 +37,21 @@ pub use deprecated::token::DefineTokenStatement;
 pub use api::ApiAction;
 pub use api::ApiDefinition;
 pub use api::FindApi;
+pub use event::{DatabaseEvent, TableEvent};
 
 use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::sql::value::Value;
+use crate::sql::Base;
 
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -80,6 +84,8 @@ pub enum DefineStatement {
 	Config(DefineConfigStatement),
 	#[revision(start = 3)]
 	Api(DefineApiStatement),
+	#[revision(start = 4)]
+	Test(DefineTestStatement),
 }
 
 // Revision implementations
@@ -115,7 +121,7 @@ impl DefineStatement {
 		match self {
 			Self::Namespace(ref v) => v.compute(ctx, opt, doc).await,
 			Self::Database(ref v) => v.compute(ctx, opt, doc).await,
-			Self::Function(ref v) => v.compute(ctx, opt, doc).await,
+			Self::Function(ref v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Param(ref v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Table(ref v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Event(ref v) => v.compute(ctx, opt, doc).await,
@@ -127,8 +133,113 @@ impl DefineStatement {
 			Self::Access(ref v) => v.compute(ctx, opt, doc).await,
 			Self::Config(ref v) => v.compute(ctx, opt, doc).await,
 			Self::Api(ref v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Test(ref v) => v.compute(ctx, opt, doc).await,
 		}
 	}
+	/// Process this type without keeping the result, reporting how the definition would change
+	pub(crate) async fn dry_run(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		// Fetch the definition before the statement
+		let before = self.existing(ctx, opt).await?;
+		// Process the statement, which is rolled back afterwards
+		self.compute(stk, ctx, opt, doc).await?;
+		// Fetch the definition after the statement
+		let after = self.existing(ctx, opt).await?;
+		// Work out what the statement changed
+		let action = match (&before, &after) {
+			(None, _) => "create",
+			(Some(before), Some(after)) if before == after => "unchanged",
+			_ => "overwrite",
+		};
+		Ok(Value::from(map! {
+			"action" => Value::from(action),
+			"before" => before.map(Value::from).unwrap_or_default(),
+			"after" => after.map(Value::from).unwrap_or_default(),
+		}))
+	}
+	/// Fetch the stored definition which this statement creates or replaces, if it exists
+	async fn existing(&self, ctx: &Context, opt: &Options) -> Result<Option<String>, Error> {
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Fetch the definition
+		let res = match self {
+			Self::Namespace(v) => txn.get_ns(&v.name).await.map(|v| v.to_string()),
+			Self::Database(v) => txn.get_db(opt.ns()?, &v.name).await.map(|v| v.to_string()),
+			Self::Function(v) => {
+				let (ns, db) = opt.ns_db()?;
+				txn.get_db_function(ns, db, &v.name).await.map(|v| v.to_string())
+			}
+			Self::Param(v) => {
+				let (ns, db) = opt.ns_db()?;
+				txn.get_db_param(ns, db, &v.name).await.map(|v| v.to_string())
+			}
+			Self::Table(v) => {
+				let (ns, db) = opt.ns_db()?;
+				txn.get_tb(ns, db, &v.name).await.map(|v| v.to_string())
+			}
+			Self::Event(v) => {
+				let (ns, db) = opt.ns_db()?;
+				match v.database {
+					Some(_) => txn.get_db_event(ns, db, &v.name).await.map(|v| v.to_string()),
+					None => txn.get_tb_event(ns, db, &v.what, &v.name).await.map(|v| v.to_string()),
+				}
+			}
+			Self::Field(v) => {
+				let (ns, db) = opt.ns_db()?;
+				let fd = v.name.to_string();
+				txn.get_tb_field(ns, db, &v.what, &fd).await.map(|v| v.to_string())
+			}
+			Self::Index(v) => {
+				let (ns, db) = opt.ns_db()?;
+				txn.get_tb_index(ns, db, &v.what, &v.name).await.map(|v| v.to_string())
+			}
+			Self::Analyzer(v) => {
+				let (ns, db) = opt.ns_db()?;
+				txn.get_db_analyzer(ns, db, &v.name).await.map(|v| v.to_string())
+			}
+			Self::User(v) => match v.base {
+				Base::Root => txn.get_root_user(&v.name).await.map(|v| v.to_string()),
+				Base::Ns => txn.get_ns_user(opt.ns()?, &v.name).await.map(|v| v.to_string()),
+				Base::Db => {
+					let (ns, db) = opt.ns_db()?;
+					txn.get_db_user(ns, db, &v.name).await.map(|v| v.to_string())
+				}
+				_ => return Ok(None),
+			},
+			Self::Model(v) => {
+				let (ns, db) = opt.ns_db()?;
+				txn.get_db_model(ns, db, &v.name, &v.version).await.map(|v| v.to_string())
+			}
+			Self::Access(v) => match v.base {
+				Base::Root => txn.get_root_access(&v.name).await.map(|v| v.to_string()),
+				Base::Ns => txn.get_ns_access(opt.ns()?, &v.name).await.map(|v| v.to_string()),
+				Base::Db => {
+					let (ns, db) = opt.ns_db()?;
+					txn.get_db_access(ns, db, &v.name).await.map(|v| v.to_string())
+				}
+				_ => return Ok(None),
+			},
+			Self::Config(v) => {
+				let (ns, db) = opt.ns_db()?;
+				txn.get_db_config(ns, db, v.inner.key()).await.map(|v| v.to_string())
+			}
+			Self::Api(v) => {
+				let (ns, db) = opt.ns_db()?;
+				txn.get_db_api(ns, db, &v.path.to_string()).await.map(|v| v.to_string())
+			}
+			Self::Test(v) => {
+				let (ns, db) = opt.ns_db()?;
+				txn.get_db_test(ns, db, &v.name).await.map(|v| v.to_string())
+			}
+		};
+		// Missing definitions are reported as not existing
+		Ok(res.ok())
+	}
 }
 
 impl Display for DefineStatement {
@@ -148,6 +259,7 @@ impl Display for DefineStatement {
 			Self::Access(v) => Display::fmt(v, f),
 			Self::Config(v) => Display::fmt(v, f),
 			Self::Api(v) => Display::fmt(v, f),
+			Self::Test(v) => Display::fmt(v, f),
 		}
 	}
 }
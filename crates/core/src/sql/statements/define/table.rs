@@ -1,4 +1,4 @@
-use super::DefineFieldStatement;
+use super::{DefineEventStatement, DefineFieldStatement, DefineIndexStatement};
 use crate::ctx::Context;
 use crate::dbs::{Force, Options};
 use crate::doc::CursorDoc;
@@ -22,7 +22,7 @@ use std::fmt::{self, Display, Write};
 use std::sync::Arc;
 use uuid::Uuid;
 
-#[revisioned(revision = 6)]
+#[revisioned(revision = 8)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -57,6 +57,12 @@ pub struct DefineTableStatement {
 	/// The last time that a LIVE query was added to this table
 	#[revision(start = 5, end = 6, convert_fn = "convert_cache_ts")]
 	pub cache_lives_ts: Uuid,
+	/// The table whose definition, fields, events and indexes this table copies
+	#[revision(start = 7)]
+	pub like: Option<Ident>,
+	/// Whether the last access time of each record is tracked, so that records can be evicted
+	#[revision(start = 8)]
+	pub cache: bool,
 }
 
 impl DefineTableStatement {
@@ -87,6 +93,20 @@ impl DefineTableStatement {
 		let key = crate::key::database::tb::new(ns, db, &self.name);
 		let nsv = txn.get_or_add_ns(ns, opt.strict).await?;
 		let dbv = txn.get_or_add_db(ns, db, opt.strict).await?;
+		// Inherit the table options from the template table
+		let base = match &self.like {
+			Some(like) => DefineTableStatement {
+				name: self.name.clone(),
+				comment: self.comment.clone(),
+				view: None,
+				cache_fields_ts: Uuid::now_v7(),
+				cache_events_ts: Uuid::now_v7(),
+				cache_tables_ts: Uuid::now_v7(),
+				cache_indexes_ts: Uuid::now_v7(),
+				..txn.get_tb(ns, db, like).await?.as_ref().clone()
+			},
+			None => self.clone(),
+		};
 		let mut dt = DefineTableStatement {
 			id: if self.id.is_none() && nsv.id.is_some() && dbv.id.is_some() {
 				Some(txn.lock().await.get_next_tb_id(nsv.id.unwrap(), dbv.id.unwrap()).await?)
@@ -96,7 +116,9 @@ impl DefineTableStatement {
 			// Don't persist the `IF NOT EXISTS` clause to schema
 			if_not_exists: false,
 			overwrite: false,
-			..self.clone()
+			// The template is copied now, so don't persist it to schema
+			like: None,
+			..base
 		};
 		// Add table relational fields
 		Self::add_in_out_fields(&txn, ns, db, &mut dt).await?;
@@ -112,6 +134,10 @@ impl DefineTableStatement {
 		if dt.changefeed.is_some() {
 			txn.lock().await.record_table_change(ns, db, &self.name, &dt);
 		}
+		// Copy the schema of the template table
+		if let Some(like) = &self.like {
+			self.copy_schema(stk, ctx, opt, doc, like).await?;
+		}
 		// Check if table is a view
 		if let Some(view) = &self.view {
 			// Force queries to run
@@ -161,6 +187,56 @@ impl DefineTableStatement {
 		Ok(Value::None)
 	}
 
+	/// Defines the fields, events and indexes of a template table on this table
+	async fn copy_schema(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		like: &Ident,
+	) -> Result<(), Error> {
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Fetch the template definitions
+		let fields = txn.all_tb_fields(ns, db, like, None).await?;
+		let events = txn.all_tb_events(ns, db, like).await?;
+		let indexes = txn.all_tb_indexes(ns, db, like).await?;
+		// Define each field on this table
+		for fd in fields.iter() {
+			let fd = DefineFieldStatement {
+				what: self.name.clone(),
+				if_not_exists: false,
+				overwrite: true,
+				..fd.clone()
+			};
+			fd.compute(ctx, opt, doc).await?;
+		}
+		// Define each event on this table
+		for ev in events.iter() {
+			let ev = DefineEventStatement {
+				what: self.name.clone(),
+				if_not_exists: false,
+				overwrite: true,
+				..ev.clone()
+			};
+			ev.compute(ctx, opt, doc).await?;
+		}
+		// Define each index on this table
+		for ix in indexes.iter() {
+			let ix = DefineIndexStatement {
+				what: self.name.clone(),
+				if_not_exists: false,
+				overwrite: true,
+				..ix.clone()
+			};
+			ix.compute(stk, ctx, opt, doc).await?;
+		}
+		Ok(())
+	}
+
 	fn convert_cache_ts(&self, _revision: u16, _value: Uuid) -> Result<(), RevisionError> {
 		Ok(())
 	}
@@ -237,6 +313,13 @@ impl Display for DefineTableStatement {
 			write!(f, " OVERWRITE")?
 		}
 		write!(f, " {}", self.name)?;
+		if let Some(ref v) = self.like {
+			write!(f, " LIKE {v}")?;
+			if let Some(ref v) = self.comment {
+				write!(f, " COMMENT {v}")?
+			}
+			return Ok(());
+		}
 		write!(f, " TYPE")?;
 		match &self.kind {
 			TableType::Normal => {
@@ -274,6 +357,9 @@ impl Display for DefineTableStatement {
 		} else {
 			" SCHEMALESS"
 		})?;
+		if self.cache {
+			f.write_str(" CACHE")?;
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
@@ -300,6 +386,7 @@ impl InfoStructure for DefineTableStatement {
 			"name".to_string() => self.name.structure(),
 			"drop".to_string() => self.drop.into(),
 			"full".to_string() => self.full.into(),
+			"cache".to_string(), if self.cache => true.into(),
 			"kind".to_string() => self.kind.structure(),
 			"view".to_string(), if let Some(v) = self.view => v.structure(),
 			"changefeed".to_string(), if let Some(v) = self.changefeed => v.structure(),
@@ -0,0 +1,162 @@
+use crate::cnf::EVICT_BATCH_SIZE;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::key::thing;
+use crate::kvs::KeyDecode as _;
+use crate::sql::statements::DeleteStatement;
+use crate::sql::{Base, Ident, Thing, Value, Values};
+
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::{self, Display, Formatter};
+
+const KB: u64 = 1024;
+const MB: u64 = 1024 * KB;
+const GB: u64 = 1024 * MB;
+const TB: u64 = 1024 * GB;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct EvictStatement {
+	/// The cache table to evict records from
+	pub what: Ident,
+	/// How much of the table to keep
+	pub keep: EvictLimit,
+}
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum EvictLimit {
+	/// Keep at most this number of records
+	Records(u64),
+	/// Keep at most this number of bytes of record data
+	Bytes(u64),
+}
+
+impl Default for EvictLimit {
+	fn default() -> Self {
+		Self::Records(0)
+	}
+}
+
+impl EvictLimit {
+	/// Parses a size unit, returning the number of bytes in the unit
+	pub(crate) fn unit(unit: &str) -> Option<u64> {
+		match unit.to_ascii_uppercase().as_str() {
+			"B" => Some(1),
+			"KB" => Some(KB),
+			"MB" => Some(MB),
+			"GB" => Some(GB),
+			"TB" => Some(TB),
+			_ => None,
+		}
+	}
+}
+
+impl EvictLimit {
+	/// The number of records or bytes to keep
+	fn max(&self) -> u64 {
+		match *self {
+			Self::Records(v) | Self::Bytes(v) => v,
+		}
+	}
+}
+
+impl Display for EvictLimit {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match *self {
+			Self::Records(v) => write!(f, "{v}"),
+			Self::Bytes(v) => match [(TB, "TB"), (GB, "GB"), (MB, "MB"), (KB, "KB")]
+				.into_iter()
+				.find(|(unit, _)| v != 0 && v % unit == 0)
+			{
+				Some((unit, name)) => write!(f, "{}{name}", v / unit),
+				None => write!(f, "{v}B"),
+			},
+		}
+	}
+}
+
+impl EvictStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		_doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Check that the table tracks record access
+		if !txn.get_tb(ns, db, &self.what).await?.cache {
+			return Err(Error::EvictNonCacheTable {
+				name: self.what.to_string(),
+			});
+		}
+		// Scan the records in batches, keeping the most recently
+		// accessed records which fit within the limit in a heap,
+		// and evicting the least recently accessed records from
+		// the heap once the limit is exceeded
+		let cache = ctx.get_cache();
+		let mut kept = BinaryHeap::new();
+		let mut used = 0;
+		let mut count = 0;
+		let beg = thing::prefix(ns, db, &self.what)?;
+		let end = thing::suffix(ns, db, &self.what)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys_vals(rng, *EVICT_BATCH_SIZE, None).await?;
+			next = batch.next;
+			let mut evict = Vec::new();
+			for (k, v) in batch.result {
+				let access = cache.as_ref().map(|c| c.record_access(&k)).unwrap_or_default();
+				let size = match self.keep {
+					EvictLimit::Records(_) => 1,
+					EvictLimit::Bytes(_) => (k.len() + v.len()) as u64,
+				};
+				used += size;
+				kept.push(Reverse((access, k, size)));
+				while used > self.keep.max() {
+					let Some(Reverse((_, k, size))) = kept.pop() else {
+						break;
+					};
+					used -= size;
+					let k = thing::Thing::decode(&k)?;
+					evict.push(Value::Thing(Thing::from((k.tb.to_owned(), k.id))));
+				}
+			}
+			// Delete the least recently accessed records of this batch
+			count += evict.len();
+			if !evict.is_empty() {
+				let stm = DeleteStatement {
+					what: Values(evict),
+					..Default::default()
+				};
+				stm.compute(stk, ctx, opt, None).await?;
+			}
+		}
+		// Return the number of evicted records
+		Ok(count.into())
+	}
+}
+
+impl Display for EvictStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "EVICT FROM {} LRU KEEP {}", self.what, self.keep)
+	}
+}
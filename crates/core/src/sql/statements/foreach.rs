@@ -1,16 +1,20 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::{Context, MutableContext};
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::sql::{block::Entry, Block, Param, Value};
+use crate::sql::statements::SelectStatement;
+use crate::sql::{
+	block::Entry, Block, Id, IdRange, Limit, Number, Param, Start, Subquery, Thing, Value, Values,
+};
 
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
-use std::ops::Deref;
+use std::ops::{Bound, Deref};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -18,6 +22,12 @@ pub struct ForeachStatement {
 	pub param: Param,
 	pub range: Value,
 	pub block: Block,
+	/// Iterate over arrays of this many values at a time
+	#[revision(start = 2)]
+	pub batch: Option<Value>,
+	/// Commit a separate transaction for each iteration
+	#[revision(start = 2)]
+	pub each: bool,
 }
 
 enum ForeachIter {
@@ -36,6 +46,162 @@ impl Iterator for ForeachIter {
 	}
 }
 
+/// The values which are left to be iterated over
+enum ForeachSource {
+	/// Values which have been computed up front
+	Values(ForeachIter),
+	/// A SELECT statement which is fetched one page at a time
+	Select(ForeachPage),
+}
+
+/// A SELECT statement, paged through either by record ID or by offset
+struct ForeachPage {
+	stm: SelectStatement,
+	/// The table to resume after the last record ID of the previous page
+	table: Option<String>,
+	last: Option<Id>,
+	start: usize,
+}
+
+impl ForeachPage {
+	fn new(stm: SelectStatement) -> Self {
+		// Table scans can resume after the last record ID, which means that
+		// records which are changed or deleted by the loop body are never
+		// skipped or returned twice. Each page scans a range of the table, so
+		// the records are returned in key order, even if a condition or a
+		// hint would otherwise iterate over an index in a different order
+		let table = match stm.what.0.as_slice() {
+			[Value::Table(tb)]
+				if !stm.only
+					&& stm.split.is_none()
+					&& stm.group.is_none()
+					&& stm.order.is_none() =>
+			{
+				Some(tb.0.clone())
+			}
+			_ => None,
+		};
+		Self {
+			stm,
+			table,
+			last: None,
+			start: 0,
+		}
+	}
+
+	async fn fetch(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		size: usize,
+	) -> Result<Vec<Value>, Error> {
+		let mut stm = self.stm.clone();
+		stm.limit = Some(Limit(Value::from(size as i64)));
+		match &self.table {
+			Some(tb) => {
+				let range = IdRange {
+					beg: match &self.last {
+						Some(id) => Bound::Excluded(id.clone()),
+						None => Bound::Unbounded,
+					},
+					end: Bound::Unbounded,
+				};
+				stm.what = Values(vec![Value::Thing(Thing::from((tb.clone(), range)))]);
+			}
+			None => stm.start = Some(Start(Value::from(self.start as i64))),
+		}
+		let res = Value::Subquery(Box::new(Subquery::Select(stm)));
+		let values = match stk.run(|stk| res.compute(stk, ctx, opt, doc)).await? {
+			Value::Array(v) => v.0,
+			Value::None | Value::Null => Vec::new(),
+			v => vec![v],
+		};
+		self.start += values.len();
+		if self.table.is_some() {
+			match values.last().map(|v| v.rid()) {
+				Some(Value::Thing(v)) => self.last = Some(v.id),
+				// Records without an ID can only be paged through by offset
+				Some(_) => self.table = None,
+				None => (),
+			}
+		}
+		Ok(values)
+	}
+}
+
+impl ForeachSource {
+	async fn fetch(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		size: usize,
+	) -> Result<Vec<Value>, Error> {
+		match self {
+			Self::Values(iter) => Ok(iter.by_ref().take(size).collect()),
+			Self::Select(page) => page.fetch(stk, ctx, opt, doc, size).await,
+		}
+	}
+}
+
+/// The progress of a FOR statement which is computed one batch at a time
+#[derive(Default)]
+pub(crate) struct ForeachCursor {
+	source: Option<ForeachSource>,
+	size: Option<usize>,
+	/// The fetched values which are still to be iterated over, without a batch size
+	page: std::vec::IntoIter<Value>,
+	/// The values which have been iterated over, and committed
+	progress: ForeachProgress,
+}
+
+#[derive(Default)]
+struct ForeachProgress {
+	/// The number of values which have been iterated over
+	iterated: usize,
+	/// The last value which has been iterated over, or its record ID
+	last: Option<Value>,
+	/// The number of values for which the iterations have been committed
+	committed: usize,
+	/// The last value for which the iteration has been committed
+	committed_last: Option<Value>,
+}
+
+impl ForeachProgress {
+	/// Records the values which are iterated over
+	fn iterating(&mut self, count: usize, last: Option<&Value>) {
+		self.iterated += count;
+		self.last = last.map(|v| match v.rid() {
+			Value::Thing(v) => Value::Thing(v),
+			_ => v.clone(),
+		});
+	}
+}
+
+impl ForeachCursor {
+	/// Marks the iterations so far as committed
+	pub(crate) fn commit(&mut self) {
+		let progress = &mut self.progress;
+		progress.committed = progress.iterated;
+		progress.committed_last.clone_from(&progress.last);
+	}
+	/// Reports where a loop which failed can be resumed from,
+	/// if any of its iterations have already been committed
+	pub(crate) fn interrupted(&self, e: Error) -> Error {
+		match self.progress.committed_last {
+			Some(ref last) => Error::ForeachInterrupted {
+				committed: self.progress.committed,
+				last: last.to_string(),
+				message: e.to_string(),
+			},
+			None => e,
+		}
+	}
+}
+
 impl ForeachStatement {
 	/// Check if we require a writeable transaction
 	pub(crate) fn writeable(&self) -> bool {
@@ -51,6 +217,113 @@ impl ForeachStatement {
 		opt: &Options,
 		doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
+		// Each iteration can only be committed by the executor
+		if self.each {
+			return Err(Error::ForeachCommitEach);
+		}
+		// Loop over all of the batches in this transaction
+		let mut cursor = ForeachCursor::default();
+		loop {
+			if let Some(v) = self.compute_batch(stk, ctx, opt, doc, &mut cursor).await? {
+				return Ok(v);
+			}
+		}
+	}
+	/// Process the next batch of this loop, returning the computed
+	/// value once the loop has finished, or None if there is more to do
+	pub(crate) async fn compute_batch(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		cursor: &mut ForeachCursor,
+	) -> Result<Option<Value>, Error> {
+		// Prepare the loop data
+		let source = match cursor.source {
+			Some(ref mut source) => source,
+			None => {
+				cursor.size = match self.batch {
+					Some(ref batch) => Some(Self::batch_size(stk, ctx, opt, doc, batch).await?),
+					None => None,
+				};
+				// Loops which commit each iteration never fetch everything up front
+				let paged = cursor.size.is_some() || self.each;
+				let source = self.source(stk, ctx, opt, doc, paged).await?;
+				cursor.source.insert(source)
+			}
+		};
+		match cursor.size {
+			// Loop over arrays of values
+			Some(size) => {
+				let values = source.fetch(stk, ctx, opt, doc, size).await?;
+				if values.is_empty() {
+					return Ok(Some(Value::None));
+				}
+				let done = values.len() < size;
+				cursor.progress.iterating(values.len(), values.last());
+				match self.iterate(stk, ctx, opt, doc, values.into()).await? {
+					Some(v) => Ok(Some(v)),
+					None if done => Ok(Some(Value::None)),
+					None => Ok(None),
+				}
+			}
+			// Loop over the values
+			None => loop {
+				let Some(v) = cursor.page.next() else {
+					// Fetch the next page of values
+					let size = *NORMAL_FETCH_SIZE as usize;
+					let values = source.fetch(stk, ctx, opt, doc, size).await?;
+					if values.is_empty() {
+						return Ok(Some(Value::None));
+					}
+					cursor.page = values.into_iter();
+					continue;
+				};
+				cursor.progress.iterating(1, Some(&v));
+				if let Some(v) = self.iterate(stk, ctx, opt, doc, v).await? {
+					return Ok(Some(v));
+				}
+				if self.each {
+					return Ok(None);
+				}
+			},
+		}
+	}
+	/// Compute the number of values in each batch
+	async fn batch_size(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		batch: &Value,
+	) -> Result<usize, Error> {
+		match batch.compute(stk, ctx, opt, doc).await? {
+			// This is a valid batch size
+			Value::Number(Number::Int(v)) if v > 0 => Ok(v as usize),
+			// An invalid value was specified
+			v => Err(Error::InvalidBatch {
+				value: v.as_string(),
+			}),
+		}
+	}
+	/// Compute the values to loop over
+	async fn source(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		paged: bool,
+	) -> Result<ForeachSource, Error> {
+		// Page through SELECT statements, rather than fetching everything up front
+		if let Value::Subquery(ref v) = self.range {
+			if let Subquery::Select(ref stm) = **v {
+				if paged && stm.limit.is_none() && stm.start.is_none() {
+					return Ok(ForeachSource::Select(ForeachPage::new(stm.clone())));
+				}
+			}
+		}
 		// Check the loop data
 		let data = self.range.compute(stk, ctx, opt, doc).await?;
 		let iter = match data {
@@ -66,67 +339,81 @@ impl ForeachStatement {
 				})
 			}
 		};
-
-		// Loop over the values
-		'foreach: for v in iter {
-			// Duplicate context
-			let ctx = MutableContext::new(ctx).freeze();
-			// Set the current parameter
-			let key = self.param.0.to_raw();
-			let val = stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await?;
-			let mut ctx = MutableContext::unfreeze(ctx)?;
-			ctx.add_value(key, val.into());
-			let mut ctx = ctx.freeze();
-			// Loop over the code block statements
-			for v in self.block.iter() {
-				// Compute each block entry
-				let res = match v {
-					Entry::Set(v) => {
-						let val = stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await?;
-						let mut c = MutableContext::unfreeze(ctx)?;
-						c.add_value(v.name.to_owned(), val.into());
-						ctx = c.freeze();
-						Ok(Value::None)
-					}
-					Entry::Value(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
-					Entry::Break(v) => v.compute(&ctx, opt, doc).await,
-					Entry::Continue(v) => v.compute(&ctx, opt, doc).await,
-					Entry::Foreach(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
-					Entry::Ifelse(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
-					Entry::Select(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
-					Entry::Create(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
-					Entry::Upsert(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
-					Entry::Update(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
-					Entry::Delete(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
-					Entry::Relate(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
-					Entry::Insert(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
-					Entry::Define(v) => v.compute(stk, &ctx, opt, doc).await,
-					Entry::Alter(v) => v.compute(stk, &ctx, opt, doc).await,
-					Entry::Rebuild(v) => v.compute(stk, &ctx, opt, doc).await,
-					Entry::Remove(v) => v.compute(&ctx, opt, doc).await,
-					Entry::Output(v) => {
-						return stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await;
-					}
-					Entry::Throw(v) => {
-						return stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await;
-					}
-				};
-				// Catch any special errors
-				match res {
-					Err(Error::Continue) => continue 'foreach,
-					Err(Error::Break) => return Ok(Value::None),
-					Err(err) => return Err(err),
-					_ => (),
-				};
-			}
+		Ok(ForeachSource::Values(iter))
+	}
+	/// Run the code block for a single value, returning
+	/// a value if the loop should not continue any further
+	async fn iterate(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		v: Value,
+	) -> Result<Option<Value>, Error> {
+		// Duplicate context
+		let ctx = MutableContext::new(ctx).freeze();
+		// Set the current parameter
+		let key = self.param.0.to_raw();
+		let val = stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await?;
+		let mut ctx = MutableContext::unfreeze(ctx)?;
+		ctx.add_value(key, val.into());
+		let mut ctx = ctx.freeze();
+		// Loop over the code block statements
+		for v in self.block.iter() {
+			// Compute each block entry
+			let res = match v {
+				Entry::Set(v) => {
+					let val = stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await?;
+					let mut c = MutableContext::unfreeze(ctx)?;
+					c.add_value(v.name.to_owned(), val.into());
+					ctx = c.freeze();
+					Ok(Value::None)
+				}
+				Entry::Value(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+				Entry::Break(v) => v.compute(&ctx, opt, doc).await,
+				Entry::Continue(v) => v.compute(&ctx, opt, doc).await,
+				Entry::Foreach(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+				Entry::Ifelse(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+				Entry::Select(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+				Entry::Create(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+				Entry::Upsert(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+				Entry::Update(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+				Entry::Delete(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+				Entry::Relate(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+				Entry::Insert(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+				Entry::Define(v) => v.compute(stk, &ctx, opt, doc).await,
+				Entry::Alter(v) => v.compute(stk, &ctx, opt, doc).await,
+				Entry::Rebuild(v) => v.compute(stk, &ctx, opt, doc).await,
+				Entry::Remove(v) => v.compute(&ctx, opt, doc).await,
+				Entry::Output(v) => {
+					return stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await.map(Some);
+				}
+				Entry::Throw(v) => {
+					return stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await.map(Some);
+				}
+			};
+			// Catch any special errors
+			match res {
+				Err(Error::Continue) => return Ok(None),
+				Err(Error::Break) => return Ok(Some(Value::None)),
+				Err(err) => return Err(err),
+				_ => (),
+			};
 		}
-		// Ok all good
-		Ok(Value::None)
+		Ok(None)
 	}
 }
 
 impl Display for ForeachStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "FOR {} IN {} {}", self.param, self.range, self.block)
+		write!(f, "FOR {} IN {}", self.param, self.range)?;
+		if let Some(ref v) = self.batch {
+			write!(f, " BATCH {v}")?;
+		}
+		if self.each {
+			write!(f, " COMMIT EACH")?;
+		}
+		write!(f, " {}", self.block)
 	}
 }
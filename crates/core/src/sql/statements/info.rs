@@ -4,6 +4,7 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::Action;
 use crate::iam::ResourceKind;
+use crate::sql::statements::references;
 use crate::sql::{Base, Ident, Object, Value, Version};
 use crate::sys::INFORMATION;
 
@@ -13,7 +14,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::sync::Arc;
 
-#[revisioned(revision = 5)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -38,6 +39,9 @@ pub enum InfoStatement {
 	#[revision(start = 3)]
 	#[revision(override(revision = 3, discriminant = 10))]
 	Index(Ident, Ident, bool),
+
+	#[revision(start = 6)]
+	Graph,
 }
 
 impl InfoStatement {
@@ -148,16 +152,21 @@ impl InfoStatement {
 				};
 				// Get the transaction
 				let txn = ctx.tx();
+				// Database events and tests are only listed when defined
+				let events = txn.all_db_events(ns, db).await?;
+				let tests = txn.all_db_tests(ns, db).await?;
 				// Create the result set
 				Ok(match structured {
 					true => Value::from(map! {
 						"accesses".to_string() => process(txn.all_db_accesses(ns, db).await?.iter().map(|v| v.redacted()).collect()),
 						"apis".to_string() => process(txn.all_db_apis(ns, db).await?),
 						"analyzers".to_string() => process(txn.all_db_analyzers(ns, db).await?),
+						"events".to_string(), if !events.is_empty() => process(events.clone()),
 						"functions".to_string() => process(txn.all_db_functions(ns, db).await?),
 						"models".to_string() => process(txn.all_db_models(ns, db).await?),
 						"params".to_string() => process(txn.all_db_params(ns, db).await?),
 						"tables".to_string() => process(txn.all_tb(ns, db, version).await?),
+						"tests".to_string(), if !tests.is_empty() => process(tests.clone()),
 						"users".to_string() => process(txn.all_db_users(ns, db).await?),
 						"configs".to_string() => process(txn.all_db_configs(ns, db).await?),
 					}),
@@ -183,6 +192,13 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"events".to_string(), if !events.is_empty() => {
+							let mut out = Object::default();
+							for v in events.iter() {
+								out.insert(v.name.to_raw(), v.to_string().into());
+							}
+							out.into()
+						},
 						"functions".to_string() => {
 							let mut out = Object::default();
 							for v in txn.all_db_functions(ns, db).await?.iter() {
@@ -211,6 +227,13 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"tests".to_string(), if !tests.is_empty() => {
+							let mut out = Object::default();
+							for v in tests.iter() {
+								out.insert(v.name.to_raw(), v.to_string().into());
+							}
+							out.into()
+						},
 						"users".to_string() => {
 							let mut out = Object::default();
 							for v in txn.all_db_users(ns, db).await?.iter() {
@@ -311,6 +334,14 @@ impl InfoStatement {
 					false => Value::from(res.to_string()),
 				})
 			}
+			InfoStatement::Graph => {
+				// Allowed to run?
+				opt.is_allowed(Action::View, ResourceKind::Any, &Base::Db)?;
+				// Get the NS and DB
+				let (ns, db) = opt.ns_db()?;
+				// Build the dependency graph
+				references::graph(&ctx.tx(), ns, db).await
+			}
 			#[allow(unused_variables)]
 			InfoStatement::Index(index, table, _structured) => {
 				// Allowed to run?
@@ -368,6 +399,7 @@ impl fmt::Display for InfoStatement {
 			},
 			Self::Index(ref i, ref t, false) => write!(f, "INFO FOR INDEX {i} ON {t}"),
 			Self::Index(ref i, ref t, true) => write!(f, "INFO FOR INDEX {i} ON {t} STRUCTURE"),
+			Self::Graph => f.write_str("INFO FOR DATABASE GRAPH"),
 		}
 	}
 }
@@ -385,6 +417,7 @@ impl InfoStatement {
 			InfoStatement::Tb(t, _, v) => InfoStatement::Tb(t, true, v),
 			InfoStatement::User(u, b, _) => InfoStatement::User(u, b, true),
 			InfoStatement::Index(i, t, _) => InfoStatement::Index(i, t, true),
+			InfoStatement::Graph => InfoStatement::Graph,
 		}
 	}
 
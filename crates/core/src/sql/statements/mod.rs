@@ -4,11 +4,14 @@ pub(crate) mod analyze;
 pub(crate) mod begin;
 pub(crate) mod r#break;
 pub(crate) mod cancel;
+pub(crate) mod case;
+pub(crate) mod check;
 pub(crate) mod commit;
 pub(crate) mod r#continue;
 pub(crate) mod create;
 pub(crate) mod define;
 pub(crate) mod delete;
+pub(crate) mod evict;
 pub(crate) mod foreach;
 pub(crate) mod ifelse;
 pub(crate) mod info;
@@ -18,8 +21,10 @@ pub(crate) mod live;
 pub(crate) mod option;
 pub(crate) mod output;
 pub(crate) mod rebuild;
+pub(crate) mod references;
 pub(crate) mod relate;
 pub(crate) mod remove;
+pub(crate) mod run;
 pub(crate) mod select;
 pub(crate) mod set;
 pub(crate) mod show;
@@ -28,14 +33,18 @@ pub(crate) mod throw;
 pub(crate) mod update;
 pub(crate) mod upsert;
 pub(crate) mod r#use;
+pub(crate) mod with;
 
 pub use self::access::{AccessGrant, AccessStatement};
 pub use self::analyze::AnalyzeStatement;
 pub use self::begin::BeginStatement;
 pub use self::cancel::CancelStatement;
+pub use self::case::CaseStatement;
+pub use self::check::CheckStatement;
 pub use self::commit::CommitStatement;
 pub use self::create::CreateStatement;
 pub use self::delete::DeleteStatement;
+pub use self::evict::{EvictLimit, EvictStatement};
 pub use self::foreach::ForeachStatement;
 pub use self::ifelse::IfelseStatement;
 pub use self::info::InfoStatement;
@@ -47,7 +56,9 @@ pub use self::output::OutputStatement;
 pub use self::r#break::BreakStatement;
 pub use self::r#continue::ContinueStatement;
 pub use self::r#use::UseStatement;
+pub use self::references::ReferencesStatement;
 pub use self::relate::RelateStatement;
+pub use self::run::RunStatement;
 pub use self::select::SelectStatement;
 pub use self::set::SetStatement;
 pub use self::show::ShowStatement;
@@ -55,6 +66,7 @@ pub use self::sleep::SleepStatement;
 pub use self::throw::ThrowStatement;
 pub use self::update::UpdateStatement;
 pub use self::upsert::UpsertStatement;
+pub use self::with::WithStatement;
 
 pub use self::alter::{AlterStatement, AlterTableStatement};
 
@@ -62,14 +74,14 @@ pub use self::define::{
 	DefineAccessStatement, DefineAnalyzerStatement, DefineApiStatement, DefineDatabaseStatement,
 	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
 	DefineModelStatement, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
-	DefineTableStatement, DefineUserStatement,
+	DefineTableStatement, DefineTestStatement, DefineUserStatement,
 };
 
 pub use self::remove::{
 	RemoveAccessStatement, RemoveAnalyzerStatement, RemoveDatabaseStatement, RemoveEventStatement,
 	RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement, RemoveModelStatement,
 	RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement, RemoveTableStatement,
-	RemoveUserStatement,
+	RemoveTestStatement, RemoveUserStatement,
 };
 
 pub use self::define::FindApi;
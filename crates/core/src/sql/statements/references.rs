@@ -0,0 +1,362 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::kvs::Transaction;
+use crate::sql::{function, param, table, thing};
+use crate::sql::{Base, Ident, Object, Value};
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use serde_content::{Data, Serializer};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct ReferencesStatement {
+	pub what: Ident,
+}
+
+impl ReferencesStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		_doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::View, ResourceKind::Table, &Base::Db)?;
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Find the definitions referencing the table
+		let refs = Reference::find(&ctx.tx(), ns, db, &self.what).await?;
+		// Ok all good
+		Ok(refs.iter().map(Reference::structure).collect::<Vec<_>>().into())
+	}
+}
+
+impl Display for ReferencesStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "WHAT REFERENCES {}", self.what)
+	}
+}
+
+/// A definition which depends on a table
+pub(crate) enum Reference {
+	Function(Ident),
+	Event(Ident, Option<Ident>),
+	View(Ident),
+}
+
+impl Reference {
+	/// Finds all definitions in the database which reference the specified table
+	pub(crate) async fn find(
+		txn: &Transaction,
+		ns: &str,
+		db: &str,
+		tb: &str,
+	) -> Result<Vec<Reference>, Error> {
+		let mut refs = Vec::new();
+		// Check the functions
+		for fc in txn.all_db_functions(ns, db).await?.iter() {
+			if Mentions::of(&fc.block)?.tables.contains(tb) {
+				refs.push(Reference::Function(fc.name.clone()));
+			}
+		}
+		// Check the database events
+		for ev in txn.all_db_events(ns, db).await?.iter() {
+			if Mentions::of(&ev.then)?.tables.contains(tb) {
+				refs.push(Reference::Event(ev.name.clone(), None));
+			}
+		}
+		// Check the events and views of other tables
+		for ft in txn.all_tb(ns, db, None).await?.iter().filter(|ft| ft.name.0 != tb) {
+			for ev in txn.all_tb_events(ns, db, &ft.name).await?.iter() {
+				if Mentions::of(&(&ev.when, &ev.then))?.tables.contains(tb) {
+					refs.push(Reference::Event(ev.name.clone(), Some(ft.name.clone())));
+				}
+			}
+			if let Some(view) = &ft.view {
+				if view.what.0.iter().any(|x| x.0 == tb) {
+					refs.push(Reference::View(ft.name.clone()));
+				}
+			}
+		}
+		Ok(refs)
+	}
+
+	fn structure(&self) -> Value {
+		let mut obj = Object::default();
+		match self {
+			Self::Function(name) => {
+				obj.insert("kind".to_owned(), "function".into());
+				obj.insert("name".to_owned(), format!("fn::{}", name.0).into());
+			}
+			Self::Event(name, table) => {
+				obj.insert("kind".to_owned(), "event".into());
+				obj.insert("name".to_owned(), name.to_raw().into());
+				if let Some(table) = table {
+					obj.insert("table".to_owned(), table.to_raw().into());
+				}
+			}
+			Self::View(name) => {
+				obj.insert("kind".to_owned(), "view".into());
+				obj.insert("name".to_owned(), name.to_raw().into());
+			}
+		}
+		obj.into()
+	}
+}
+
+impl Display for Reference {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Function(name) => write!(f, "function fn::{}", name.0),
+			Self::Event(name, Some(table)) => write!(f, "event {name} on table {table}"),
+			Self::Event(name, None) => write!(f, "event {name} on the database"),
+			Self::View(name) => write!(f, "view {name}"),
+		}
+	}
+}
+
+type Content = serde_content::Value<'static>;
+
+/// The tables, parameters and custom functions which a definition refers to
+#[derive(Debug, Default)]
+struct Mentions {
+	tables: BTreeSet<String>,
+	params: BTreeSet<String>,
+	functions: BTreeSet<String>,
+}
+
+impl Mentions {
+	/// Walks the syntax tree of a definition, collecting the tables, parameters and custom
+	/// functions which it refers to. Field names and strings are not collected.
+	fn of(ast: &impl Serialize) -> Result<Self, Error> {
+		let mut res = Self::default();
+		res.walk(Serializer::new().serialize(ast)?);
+		Ok(res)
+	}
+
+	fn walk(&mut self, content: Content) {
+		match content {
+			Content::Struct(v) => {
+				let serde_content::Struct {
+					name: ty,
+					data,
+				} = *v;
+				match (ty.as_ref(), data) {
+					(
+						table::TOKEN,
+						Data::NewType {
+							value,
+						},
+					) => self.tables.extend(name(value)),
+					(
+						param::TOKEN,
+						Data::NewType {
+							value,
+						},
+					) => self.params.extend(name(value)),
+					(
+						thing::TOKEN,
+						Data::Struct {
+							fields,
+						},
+					) => {
+						for (field, value) in fields {
+							match field.as_ref() {
+								"tb" => self.tables.extend(name(value)),
+								_ => self.walk(value),
+							}
+						}
+					}
+					(_, data) => self.walk_data(data),
+				}
+			}
+			Content::Enum(v) => {
+				let serde_content::Enum {
+					name: ty,
+					variant,
+					data,
+					..
+				} = *v;
+				match (ty.as_ref(), variant.as_ref(), data) {
+					(
+						function::TOKEN,
+						"Custom",
+						Data::Tuple {
+							values,
+						},
+					) => {
+						let mut values = values.into_iter();
+						self.functions.extend(values.next().and_then(name));
+						values.for_each(|v| self.walk(v));
+					}
+					(_, _, data) => self.walk_data(data),
+				}
+			}
+			Content::Seq(v) | Content::Tuple(v) => v.into_iter().for_each(|v| self.walk(v)),
+			Content::Map(v) => {
+				for (key, value) in v {
+					self.walk(key);
+					self.walk(value);
+				}
+			}
+			Content::Option(Some(v)) => self.walk(*v),
+			_ => {}
+		}
+	}
+
+	fn walk_data(&mut self, data: Data<'static>) {
+		match data {
+			Data::Unit => {}
+			Data::NewType {
+				value,
+			} => self.walk(value),
+			Data::Tuple {
+				values,
+			} => values.into_iter().for_each(|v| self.walk(v)),
+			Data::Struct {
+				fields,
+			} => fields.into_iter().for_each(|(_, v)| self.walk(v)),
+		}
+	}
+}
+
+/// The name within a string, or a newtype of a string such as an identifier
+fn name(content: Content) -> Option<String> {
+	match content {
+		Content::String(v) => Some(v.into_owned()),
+		Content::Struct(v) => match v.data {
+			Data::NewType {
+				value,
+			} => name(value),
+			_ => None,
+		},
+		_ => None,
+	}
+}
+
+/// The dependency graph between the definitions of a database
+#[derive(Default)]
+struct Graph {
+	nodes: BTreeMap<String, &'static str>,
+	edges: BTreeSet<(String, String)>,
+}
+
+impl Graph {
+	/// Adds an edge for every known definition which the definition refers to
+	fn mentions(&mut self, from: &str, ast: &impl Serialize, tables: bool) -> Result<(), Error> {
+		let mentions = Mentions::of(ast)?;
+		let names = mentions
+			.tables
+			.into_iter()
+			.filter(|_| tables)
+			.chain(mentions.functions.into_iter().map(|x| format!("fn::{x}")))
+			.chain(mentions.params.into_iter().map(|x| format!("${x}")));
+		for name in names {
+			self.edge(from, name);
+		}
+		Ok(())
+	}
+
+	/// Adds an edge, if the target is a known definition
+	fn edge(&mut self, from: &str, to: String) {
+		if from != to && self.nodes.contains_key(&to) {
+			self.edges.insert((from.to_owned(), to));
+		}
+	}
+
+	fn structure(self) -> Value {
+		let nodes = self
+			.nodes
+			.into_iter()
+			.map(|(id, kind)| {
+				Value::from(map! {
+					"id".to_string() => id.into(),
+					"kind".to_string() => kind.into(),
+				})
+			})
+			.collect::<Vec<_>>();
+		let edges = self
+			.edges
+			.into_iter()
+			.map(|(from, to)| {
+				Value::from(map! {
+					"from".to_string() => from.into(),
+					"to".to_string() => to.into(),
+				})
+			})
+			.collect::<Vec<_>>();
+		Value::from(map! {
+			"nodes".to_string() => nodes.into(),
+			"edges".to_string() => edges.into(),
+		})
+	}
+}
+
+/// Builds the dependency graph between the tables, views, functions, params and events of a
+/// database. An edge from one definition to another means the former depends on the latter.
+pub(crate) async fn graph(txn: &Transaction, ns: &str, db: &str) -> Result<Value, Error> {
+	let tbs = txn.all_tb(ns, db, None).await?;
+	let fcs = txn.all_db_functions(ns, db).await?;
+	let pas = txn.all_db_params(ns, db).await?;
+	let evs = txn.all_db_events(ns, db).await?;
+	let mut graph = Graph::default();
+	// Add all definitions as nodes
+	for tb in tbs.iter() {
+		let kind = if tb.view.is_some() {
+			"view"
+		} else {
+			"table"
+		};
+		graph.nodes.insert(tb.name.to_raw(), kind);
+	}
+	for fc in fcs.iter() {
+		graph.nodes.insert(format!("fn::{}", fc.name.0), "function");
+	}
+	for pa in pas.iter() {
+		graph.nodes.insert(format!("${}", pa.name.0), "param");
+	}
+	for ev in evs.iter() {
+		graph.nodes.insert(format!("{} FOR DATABASE", ev.name.to_raw()), "event");
+	}
+	// Add the dependencies of each definition as edges
+	for fc in fcs.iter() {
+		graph.mentions(&format!("fn::{}", fc.name.0), fc, true)?;
+	}
+	for pa in pas.iter() {
+		graph.mentions(&format!("${}", pa.name.0), &pa.value, true)?;
+	}
+	for ev in evs.iter() {
+		graph.mentions(&format!("{} FOR DATABASE", ev.name.to_raw()), &ev.then, true)?;
+	}
+	for tb in tbs.iter() {
+		let id = tb.name.to_raw();
+		// Views depend on the tables they select from
+		if let Some(view) = &tb.view {
+			for ft in view.what.0.iter() {
+				graph.edge(&id, ft.0.clone());
+			}
+			graph.mentions(&id, view, false)?;
+		}
+		// Tables depend on anything used in their fields
+		for fd in txn.all_tb_fields(ns, db, &tb.name, None).await?.iter() {
+			graph.mentions(&id, fd, true)?;
+		}
+		// Events depend on their table and anything used in their clauses
+		for ev in txn.all_tb_events(ns, db, &tb.name).await?.iter() {
+			let ev_id = format!("{} ON {}", ev.name.to_raw(), id);
+			graph.nodes.insert(ev_id.clone(), "event");
+			graph.edge(&ev_id, id.clone());
+			graph.mentions(&ev_id, &(&ev.when, &ev.then), true)?;
+		}
+	}
+	Ok(graph.structure())
+}
@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 use uuid::Uuid;
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -19,6 +19,9 @@ pub struct RemoveEventStatement {
 	pub what: Ident,
 	#[revision(start = 2)]
 	pub if_exists: bool,
+	/// Whether this removes an event defined FOR DATABASE
+	#[revision(start = 3)]
+	pub database: bool,
 }
 
 impl RemoveEventStatement {
@@ -31,6 +34,22 @@ impl RemoveEventStatement {
 			let (ns, db) = opt.ns_db()?;
 			// Get the transaction
 			let txn = ctx.tx();
+			// Remove a database event
+			if self.database {
+				// Get the definition
+				let ev = txn.get_db_event(ns, db, &self.name).await?;
+				// Delete the definition
+				let key = crate::key::database::ev::new(ns, db, &ev.name);
+				txn.del(key).await?;
+				// Clear the cache
+				if let Some(cache) = ctx.get_cache() {
+					cache.clear_db_events(ns, db);
+				}
+				// Clear the cache
+				txn.clear();
+				// Ok all good
+				return Ok(Value::None);
+			}
 			// Get the definition
 			let ev = txn.get_tb_event(ns, db, &self.what, &self.name).await?;
 			// Delete the definition
@@ -73,7 +92,10 @@ impl Display for RemoveEventStatement {
 		if self.if_exists {
 			write!(f, " IF EXISTS")?
 		}
-		write!(f, " {} ON {}", self.name, self.what)?;
+		match self.database {
+			true => write!(f, " {} FOR DATABASE", self.name)?,
+			false => write!(f, " {} ON {}", self.name, self.what)?,
+		}
 		Ok(())
 	}
 }
@@ -2,7 +2,10 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
+use crate::kvs::KeyEncode;
+use crate::sql::fmt::Fmt;
 use crate::sql::statements::define::DefineTableStatement;
+use crate::sql::statements::references::Reference;
 use crate::sql::{Base, Ident, Value};
 
 use revision::revisioned;
@@ -10,7 +13,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 use uuid::Uuid;
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -20,6 +23,9 @@ pub struct RemoveTableStatement {
 	pub if_exists: bool,
 	#[revision(start = 3)]
 	pub expunge: bool,
+	/// Fail if any other definition references this table
+	#[revision(start = 4)]
+	pub safe: bool,
 }
 
 impl RemoveTableStatement {
@@ -32,6 +38,16 @@ impl RemoveTableStatement {
 			let (ns, db) = opt.ns_db()?;
 			// Get the transaction
 			let txn = ctx.tx();
+			// Check that nothing depends on this table
+			if self.safe {
+				let refs = Reference::find(&txn, ns, db, &self.name).await?;
+				if !refs.is_empty() {
+					return Err(Error::TbReferenced {
+						name: self.name.to_string(),
+						references: Fmt::comma_separated(refs).to_string(),
+					});
+				}
+			}
 			// Remove the index stores
 			#[cfg(not(target_family = "wasm"))]
 			ctx.get_index_stores()
@@ -98,6 +114,12 @@ impl RemoveTableStatement {
 			// Clear the cache
 			if let Some(cache) = ctx.get_cache() {
 				cache.clear_tb(ns, db, &self.name);
+				// Forget the record access times of a cache table
+				if tb.cache {
+					let mut prefix = crate::key::table::all::new(ns, db, &self.name).encode()?;
+					prefix.push(b'*');
+					txn.track_access(cache.forget_records(prefix));
+				}
 			}
 			// Clear the cache
 			txn.clear();
@@ -121,6 +143,9 @@ impl Display for RemoveTableStatement {
 			write!(f, " IF EXISTS")?
 		}
 		write!(f, " {}", self.name)?;
+		if self.safe {
+			write!(f, " SAFE")?
+		}
 		Ok(())
 	}
 }
@@ -0,0 +1,78 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::statements::DefineTestStatement;
+use crate::sql::{Base, Ident, Value};
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct RunStatement {
+	/// The test to run, or all tests in the database if not specified
+	pub test: Option<Ident>,
+}
+
+impl RunStatement {
+	/// Process this type returning a computed simple Value
+	///
+	/// Tests are run by the executor, as each test needs a
+	/// transaction of its own which is rolled back afterwards.
+	pub(crate) async fn compute(
+		&self,
+		_ctx: &Context,
+		_opt: &Options,
+		_doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		Err(Error::RunTestInTransaction)
+	}
+	/// Fetches the definitions of the tests which should be run
+	pub(crate) async fn tests(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+	) -> Result<Vec<DefineTestStatement>, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::View, ResourceKind::Test, &Base::Db)?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Fetch the test definitions
+		let (ns, db) = opt.ns_db()?;
+		match &self.test {
+			Some(name) => Ok(vec![txn.get_db_test(ns, db, name).await?.as_ref().clone()]),
+			None => Ok(txn.all_db_tests(ns, db).await?.to_vec()),
+		}
+	}
+	/// Outputs the result of a single test
+	pub(crate) fn result(test: &DefineTestStatement, res: Result<Value, Error>) -> Value {
+		let error = match res {
+			// Returning early from a test is not a failure
+			Ok(_)
+			| Err(Error::Return {
+				..
+			}) => None,
+			Err(e) => Some(e.to_string()),
+		};
+		Value::from(map! {
+			"name".to_string() => test.name.clone().structure(),
+			"passed".to_string() => error.is_none().into(),
+			"error".to_string(), if let Some(v) = error => v.into(),
+		})
+	}
+}
+
+impl Display for RunStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match &self.test {
+			Some(v) => write!(f, "RUN TEST {v}"),
+			None => write!(f, "RUN TESTS"),
+		}
+	}
+}
@@ -1,3 +1,4 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::{Context, MutableContext};
 use crate::dbs::{Iterable, Iterator, Options, Statement};
 use crate::doc::CursorDoc;
@@ -5,21 +6,26 @@ use crate::err::Error;
 use crate::idx::planner::{GrantedPermission, QueryPlanner, RecordStrategy, StatementContext};
 use crate::sql::{
 	order::{OldOrders, Order, OrderList, Ordering},
-	Cond, Explain, Fetchs, Field, Fields, Groups, Idioms, Limit, Splits, Start, Timeout, Value,
-	Values, Version, With,
+	Cond, Distinct, Explain, Fetchs, Field, Fields, Groups, Idioms, Join, Joined, Limit,
+	SetOperator, Splits, Start, Timeout, Value, Values, Version, With,
 };
 
+use rand::prelude::SliceRandom;
+use rand::thread_rng;
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::sync::Arc;
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 8)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub struct SelectStatement {
+	/// Removes the duplicate rows from the results
+	#[revision(start = 6)]
+	pub distinct: Option<Distinct>,
 	/// The foo,bar part in SELECT foo,bar FROM baz.
 	pub expr: Fields,
 	pub omit: Option<Idioms>,
@@ -27,10 +33,16 @@ pub struct SelectStatement {
 	pub only: bool,
 	/// The baz part in SELECT foo,bar FROM baz.
 	pub what: Values,
+	/// The tables which are joined with the table of the statement, in order
+	#[revision(start = 8)]
+	pub join: Vec<Join>,
 	pub with: Option<With>,
 	pub cond: Option<Cond>,
 	pub split: Option<Splits>,
 	pub group: Option<Groups>,
+	/// The condition which the grouped results are filtered by
+	#[revision(start = 5)]
+	pub having: Option<Cond>,
 	#[revision(end = 4, convert_fn = "convert_old_orders")]
 	pub old_order: Option<OldOrders>,
 	#[revision(start = 4)]
@@ -44,6 +56,10 @@ pub struct SelectStatement {
 	pub explain: Option<Explain>,
 	#[revision(start = 3)]
 	pub tempfiles: bool,
+	/// The statements whose results are combined with the results of this statement, in order.
+	/// When set, the ORDER BY, START and LIMIT clauses apply to the combined results.
+	#[revision(start = 7)]
+	pub combine: Vec<(SetOperator, SelectStatement)>,
 }
 
 impl SelectStatement {
@@ -85,12 +101,25 @@ impl SelectStatement {
 				expr,
 				..
 			} => expr.writeable(),
+			Field::Window {
+				window,
+				..
+			} => window.writeable(),
 		}) {
 			return true;
 		}
 		if self.what.iter().any(|v| v.writeable()) {
 			return true;
 		}
+		if self.join.iter().any(Join::writeable) {
+			return true;
+		}
+		if self.having.as_deref().is_some_and(Value::writeable) {
+			return true;
+		}
+		if self.combine.iter().any(|(_, v)| v.writeable()) {
+			return true;
+		}
 		self.cond.as_deref().is_some_and(Value::writeable)
 	}
 
@@ -101,6 +130,72 @@ impl SelectStatement {
 		ctx: &Context,
 		opt: &Options,
 		doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		if self.combine.is_empty() {
+			return self.compute_select(stk, ctx, opt, doc).await;
+		}
+		// The ORDER BY, START and LIMIT clauses apply to the combined results
+		let first = SelectStatement {
+			order: None,
+			start: None,
+			limit: None,
+			combine: Vec::new(),
+			..self.clone()
+		};
+		// INTERSECT binds tighter than UNION and EXCEPT, so is combined first
+		let mut terms = vec![(None, first.compute_select(stk, ctx, opt, doc).await?)];
+		for (op, stm) in self.combine.iter() {
+			let other = stk.run(|stk| stm.compute_select(stk, ctx, opt, doc)).await?;
+			match (op.is_intersect(), terms.last_mut()) {
+				(true, Some((_, res))) => *res = op.combine(std::mem::take(res), other),
+				_ => terms.push((Some(*op), other)),
+			}
+		}
+		// Then UNION and EXCEPT are combined from left to right
+		let mut terms = terms.into_iter();
+		let mut res = terms.next().map(|(_, v)| v).unwrap_or_default();
+		for (op, other) in terms {
+			if let Some(op) = op {
+				res = op.combine(res, other);
+			}
+		}
+		self.compute_compound(stk, ctx, opt, doc, res).await
+	}
+
+	/// Orders, starts and limits the combined results of this statement
+	async fn compute_compound(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		res: Value,
+	) -> Result<Value, Error> {
+		let Value::Array(mut res) = res else {
+			return Ok(res);
+		};
+		match &self.order {
+			Some(Ordering::Random) => res.0.shuffle(&mut thread_rng()),
+			Some(Ordering::Order(orders)) => res.0.sort_by(|a, b| orders.compare(a, b)),
+			None => {}
+		}
+		let start = match &self.start {
+			Some(v) => v.process(stk, ctx, opt, doc).await? as usize,
+			None => 0,
+		};
+		let limit = match &self.limit {
+			Some(v) => v.process(stk, ctx, opt, doc).await? as usize,
+			None => usize::MAX,
+		};
+		Ok(res.0.into_iter().skip(start).take(limit).collect::<Vec<_>>().into())
+	}
+
+	async fn compute_select(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
 		// Valid options?
 		opt.valid_for_db()?;
@@ -137,87 +232,94 @@ impl SelectStatement {
 		// Get a query planner
 		let mut planner = QueryPlanner::new();
 		let stm_ctx = StatementContext::new(&ctx, &opt, &stm)?;
-		// Loop over the select targets
-		for w in self.what.0.iter() {
-			let v = w.compute(stk, &ctx, &opt, doc).await?;
-			match v {
-				Value::Thing(v) => {
-					let p = planner.check_table_permission(&stm_ctx, &v.tb).await?;
-					// We prepare it only if wer have a permission
-					if !matches!(p, GrantedPermission::None) {
-						match v.is_range() {
-							true => {
-								// Evaluate if we can only scan keys (rather than keys AND values), or count
-								let rs = stm_ctx.check_record_strategy(false, p).await?;
-								i.prepare_range(&stm, v, rs)?
+		// Join the rows of the target with the joined tables
+		if !self.join.is_empty() {
+			for v in self.compute_join(stk, &ctx, &opt, doc, limit, &mut planner).await? {
+				i.ingest(Iterable::Value(v));
+			}
+		} else {
+			// Loop over the select targets
+			for w in self.what.0.iter() {
+				let v = w.compute(stk, &ctx, &opt, doc).await?;
+				match v {
+					Value::Thing(v) => {
+						let p = planner.check_table_permission(&stm_ctx, &v.tb).await?;
+						// We prepare it only if wer have a permission
+						if !matches!(p, GrantedPermission::None) {
+							match v.is_range() {
+								true => {
+									// Evaluate if we can only scan keys (rather than keys AND values), or count
+									let rs = stm_ctx.check_record_strategy(false, p).await?;
+									i.prepare_range(&stm, v, rs)?
+								}
+								false => i.prepare_thing(&stm, v)?,
 							}
-							false => i.prepare_thing(&stm, v)?,
 						}
 					}
-				}
-				Value::Edges(v) => {
-					if self.only && !limit_is_one_or_zero {
-						return Err(Error::SingleOnlyOutput);
-					}
-					i.prepare_edges(&stm, *v)?;
-				}
-				Value::Mock(v) => {
-					if self.only && !limit_is_one_or_zero {
-						return Err(Error::SingleOnlyOutput);
-					}
-					i.prepare_mock(&stm, v)?;
-				}
-				Value::Table(t) => {
-					if self.only && !limit_is_one_or_zero {
-						return Err(Error::SingleOnlyOutput);
+					Value::Edges(v) => {
+						if self.only && !limit_is_one_or_zero {
+							return Err(Error::SingleOnlyOutput);
+						}
+						i.prepare_edges(&stm, *v)?;
 					}
-					let p = planner.check_table_permission(&stm_ctx, &t).await?;
-					// We add the iterable only if we have a permission
-					if !matches!(p, GrantedPermission::None) {
-						planner.add_iterables(stk, &stm_ctx, t, p, &mut i).await?;
+					Value::Mock(v) => {
+						if self.only && !limit_is_one_or_zero {
+							return Err(Error::SingleOnlyOutput);
+						}
+						i.prepare_mock(&stm, v)?;
 					}
-				}
-				Value::Array(v) => {
-					if self.only && !limit_is_one_or_zero {
-						return Err(Error::SingleOnlyOutput);
+					Value::Table(t) => {
+						if self.only && !limit_is_one_or_zero {
+							return Err(Error::SingleOnlyOutput);
+						}
+						let p = planner.check_table_permission(&stm_ctx, &t).await?;
+						// We add the iterable only if we have a permission
+						if !matches!(p, GrantedPermission::None) {
+							planner.add_iterables(stk, &stm_ctx, t, p, &mut i).await?;
+						}
 					}
-					for v in v {
-						match v {
-							Value::Table(t) => {
-								let p = planner.check_table_permission(&stm_ctx, &t).await?;
-								// We add the iterable only if we have a permission
-								if !matches!(p, GrantedPermission::None) {
-									planner.add_iterables(stk, &stm_ctx, t, p, &mut i).await?;
+					Value::Array(v) => {
+						if self.only && !limit_is_one_or_zero {
+							return Err(Error::SingleOnlyOutput);
+						}
+						for v in v {
+							match v {
+								Value::Table(t) => {
+									let p = planner.check_table_permission(&stm_ctx, &t).await?;
+									// We add the iterable only if we have a permission
+									if !matches!(p, GrantedPermission::None) {
+										planner.add_iterables(stk, &stm_ctx, t, p, &mut i).await?;
+									}
 								}
-							}
-							Value::Mock(v) => i.prepare_mock(&stm, v)?,
-							Value::Edges(v) => i.prepare_edges(&stm, *v)?,
-							Value::Thing(v) => {
-								let p = planner.check_table_permission(&stm_ctx, &v.tb).await?;
-								// We prepare it only if wer have a permission
-								if !matches!(p, GrantedPermission::None) {
-									match v.is_range() {
-										true => {
-											// Evaluate if we can only scan keys (rather than keys AND values) or just count
-											let rs =
-												stm_ctx.check_record_strategy(false, p).await?;
-											i.prepare_range(&stm, v, rs)?
+								Value::Mock(v) => i.prepare_mock(&stm, v)?,
+								Value::Edges(v) => i.prepare_edges(&stm, *v)?,
+								Value::Thing(v) => {
+									let p = planner.check_table_permission(&stm_ctx, &v.tb).await?;
+									// We prepare it only if wer have a permission
+									if !matches!(p, GrantedPermission::None) {
+										match v.is_range() {
+											true => {
+												// Evaluate if we can only scan keys (rather than keys AND values) or just count
+												let rs =
+													stm_ctx.check_record_strategy(false, p).await?;
+												i.prepare_range(&stm, v, rs)?
+											}
+											false => i.prepare_thing(&stm, v)?,
 										}
-										false => i.prepare_thing(&stm, v)?,
 									}
 								}
+								_ => i.ingest(Iterable::Value(v)),
 							}
-							_ => i.ingest(Iterable::Value(v)),
 						}
 					}
-				}
-				v => i.ingest(Iterable::Value(v)),
-			};
+					v => i.ingest(Iterable::Value(v)),
+				};
+			}
 		}
 		// Create a new context
 		let mut ctx = MutableContext::new(&ctx);
-		// Add query executors if any
-		if planner.has_executors() {
+		// Add query executors if any, or the joins to explain
+		if planner.has_executors() || (self.explain.is_some() && !planner.joins().is_empty()) {
 			ctx.set_query_planner(planner);
 		}
 		let ctx = ctx.freeze();
@@ -242,11 +344,111 @@ impl SelectStatement {
 			v => Ok(v),
 		}
 	}
+
+	/// Selects the records of the target table, and joins them with each joined table
+	async fn compute_join(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		limit: Option<u32>,
+		planner: &mut QueryPlanner,
+	) -> Result<Vec<Value>, Error> {
+		let tb = match self.what.0.as_slice() {
+			[w] => match w.compute(stk, ctx, opt, doc).await? {
+				Value::Table(t) => t,
+				v => {
+					return Err(Error::InvalidStatementTarget {
+						value: v.to_string(),
+					})
+				}
+			},
+			_ => {
+				return Err(Error::InvalidStatementTarget {
+					value: self.what.to_string(),
+				})
+			}
+		};
+		// The conditions which only check the target table filter its records as they are read
+		let (cond, complete) = match &self.cond {
+			Some(cond) => match Join::push_down(&tb.0, &cond.0) {
+				Some((cond, complete)) => (Some(Cond(cond)), complete),
+				None => (None, false),
+			},
+			None => (None, true),
+		};
+		// The rows after the limit are not needed, unless the rows are filtered, grouped or sorted
+		let max = match limit {
+			Some(l)
+				if complete
+					&& self.split.is_none()
+					&& self.group.is_none()
+					&& self.order.is_none()
+					&& self.distinct.is_none()
+					&& !self.expr.has_windows() =>
+			{
+				let start = match self.start {
+					Some(ref v) => v.process(stk, ctx, opt, doc).await?,
+					None => 0,
+				};
+				Some(l as usize + start as usize)
+			}
+			_ => None,
+		};
+		let mut strategies = Vec::with_capacity(self.join.len());
+		for join in self.join.iter() {
+			let strategy = join.strategy(ctx, opt, self.with.as_ref()).await?;
+			planner.add_join(join.what.0.clone(), strategy.clone());
+			strategies.push((strategy, Joined::default()));
+		}
+		// The records of the target table are read and joined one batch at a time. The index
+		// hints of the statement are for the joined tables, so they are not used here.
+		let batch = *NORMAL_FETCH_SIZE;
+		let mut res = Vec::new();
+		let mut offset = 0;
+		loop {
+			let stm = SelectStatement {
+				expr: Fields::all(),
+				what: Values(vec![Value::Table(tb.clone())]),
+				cond: cond.clone(),
+				limit: Some(Limit(Value::from(batch))),
+				start: Some(Start(Value::from(offset))),
+				..Default::default()
+			};
+			// Each record is set on the name of its table
+			let records = match stk.run(|stk| stm.compute(stk, ctx, opt, None)).await? {
+				Value::Array(v) => v.0,
+				_ => vec![],
+			};
+			let last = records.len() < batch as usize;
+			offset += records.len() as u32;
+			let mut rows: Vec<Value> =
+				records.into_iter().map(|v| Value::from(map! { tb.0.clone() => v })).collect();
+			for (i, (join, (strategy, joined))) in
+				self.join.iter().zip(strategies.iter_mut()).enumerate()
+			{
+				// Only the last join can stop early, as later joins may drop rows
+				let max = max.filter(|_| i + 1 == self.join.len()).map(|m| m - res.len());
+				rows = join.compute(stk, ctx, opt, strategy, joined, rows, max).await?;
+			}
+			res.append(&mut rows);
+			// Stop once there are enough rows, or every record has been read
+			if last || max.is_some_and(|m| res.len() >= m) {
+				break;
+			}
+		}
+		Ok(res)
+	}
 }
 
 impl fmt::Display for SelectStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "SELECT {}", self.expr)?;
+		f.write_str("SELECT ")?;
+		if let Some(ref v) = self.distinct {
+			write!(f, "{v} ")?
+		}
+		write!(f, "{}", self.expr)?;
 		if let Some(ref v) = self.omit {
 			write!(f, " OMIT {v}")?
 		}
@@ -255,6 +457,9 @@ impl fmt::Display for SelectStatement {
 			f.write_str(" ONLY")?
 		}
 		write!(f, " {}", self.what)?;
+		for v in self.join.iter() {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.with {
 			write!(f, " {v}")?
 		}
@@ -267,14 +472,12 @@ impl fmt::Display for SelectStatement {
 		if let Some(ref v) = self.group {
 			write!(f, " {v}")?
 		}
-		if let Some(ref v) = self.order {
-			write!(f, " {v}")?
+		if let Some(ref v) = self.having {
+			write!(f, " HAVING {}", v.0)?
 		}
-		if let Some(ref v) = self.limit {
-			write!(f, " {v}")?
-		}
-		if let Some(ref v) = self.start {
-			write!(f, " {v}")?
+		// The ORDER BY, LIMIT and START clauses of combined statements follow the last statement
+		if self.combine.is_empty() {
+			self.fmt_bounds(f)?
 		}
 		if let Some(ref v) = self.fetch {
 			write!(f, " {v}")?
@@ -291,6 +494,27 @@ impl fmt::Display for SelectStatement {
 		if let Some(ref v) = self.explain {
 			write!(f, " {v}")?
 		}
+		for (op, stm) in self.combine.iter() {
+			write!(f, " {op} {stm}")?
+		}
+		if !self.combine.is_empty() {
+			self.fmt_bounds(f)?
+		}
+		Ok(())
+	}
+}
+
+impl SelectStatement {
+	fn fmt_bounds(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if let Some(ref v) = self.order {
+			write!(f, " {v}")?
+		}
+		if let Some(ref v) = self.limit {
+			write!(f, " {v}")?
+		}
+		if let Some(ref v) = self.start {
+			write!(f, " {v}")?
+		}
 		Ok(())
 	}
 }
@@ -10,7 +10,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -19,6 +19,8 @@ pub struct SetStatement {
 	pub what: Value,
 	#[revision(start = 2)]
 	pub kind: Option<Kind>,
+	#[revision(start = 3)]
+	pub local: bool,
 }
 
 impl SetStatement {
@@ -56,7 +58,11 @@ impl SetStatement {
 
 impl fmt::Display for SetStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "LET ${}", self.name)?;
+		write!(f, "LET ")?;
+		if self.local {
+			write!(f, "LOCAL ")?;
+		}
+		write!(f, "${}", self.name)?;
 		if let Some(ref kind) = self.kind {
 			write!(f, ": {}", kind)?;
 		}
@@ -76,5 +82,8 @@ mod tests {
 
 		let query = parse("LET $param: number = 5").unwrap();
 		assert_eq!(format!("{}", query), "LET $param: number = 5;");
+
+		let query = parse("LET LOCAL $param: number = 5").unwrap();
+		assert_eq!(format!("{}", query), "LET LOCAL $param: number = 5;");
 	}
 }
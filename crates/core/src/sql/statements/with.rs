@@ -0,0 +1,90 @@
+use crate::ctx::{Context, MutableContext};
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::sql::fmt::Fmt;
+use crate::sql::{Ident, Statement, Subquery, Value};
+
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct WithStatement {
+	/// The subqueries which are named, in the order they were defined
+	pub named: Vec<(Ident, Subquery)>,
+	/// The statement which can reference the named subqueries
+	pub then: Box<Statement>,
+}
+
+impl WithStatement {
+	/// Check if we require a writeable transaction
+	pub(crate) fn writeable(&self) -> bool {
+		self.named.iter().any(|(_, v)| v.writeable()) || self.then.writeable()
+	}
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		// Each subquery can reference the subqueries which were named before it
+		let mut ctx = ctx.clone();
+		for (name, expr) in self.named.iter() {
+			let named = NamedSubquery {
+				ctx: ctx.clone(),
+				expr: expr.clone(),
+				value: OnceCell::new(),
+			};
+			let mut child = MutableContext::new(&ctx);
+			child.add_named(name.0.clone(), Arc::new(named));
+			ctx = child.freeze();
+		}
+		// Process the statement
+		stk.run(|stk| self.then.compute(stk, &ctx, opt, doc)).await
+	}
+}
+
+impl Display for WithStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"WITH {} {}",
+			Fmt::comma_separated(
+				self.named.iter().map(|(name, expr)| Fmt::new((name, expr), |(name, expr), f| {
+					write!(f, "{name} AS {expr}")
+				}))
+			),
+			self.then
+		)
+	}
+}
+
+/// A subquery of a WITH statement, which is computed once, when it is first referenced
+#[derive(Debug)]
+pub(crate) struct NamedSubquery {
+	/// The context of the WITH statement, with the subqueries which were named before this one
+	ctx: Context,
+	expr: Subquery,
+	value: OnceCell<Arc<Value>>,
+}
+
+impl NamedSubquery {
+	/// Returns the shared result of the subquery, computing it if it has not been referenced before
+	pub(crate) async fn compute(&self, stk: &mut Stk, opt: &Options) -> Result<Arc<Value>, Error> {
+		self.value
+			.get_or_try_init(|| async move {
+				stk.run(|stk| self.expr.compute(stk, &self.ctx, opt, None)).await.map(Arc::new)
+			})
+			.await
+			.cloned()
+	}
+}
@@ -4,9 +4,9 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::sql::statements::rebuild::RebuildStatement;
 use crate::sql::statements::{
-	AlterStatement, CreateStatement, DefineStatement, DeleteStatement, IfelseStatement,
-	InsertStatement, OutputStatement, RelateStatement, RemoveStatement, SelectStatement,
-	UpdateStatement, UpsertStatement,
+	AlterStatement, CaseStatement, CreateStatement, DefineStatement, DeleteStatement,
+	IfelseStatement, InsertStatement, OutputStatement, RelateStatement, RemoveStatement,
+	SelectStatement, UpdateStatement, UpsertStatement,
 };
 use crate::sql::value::Value;
 use reblessive::tree::Stk;
@@ -17,7 +17,7 @@ use std::fmt::{self, Display, Formatter};
 
 pub(crate) const TOKEN: &str = "$surrealdb::private::sql::Subquery";
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 #[serde(rename = "$surrealdb::private::sql::Subquery")]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -40,6 +40,8 @@ pub enum Subquery {
 	Upsert(UpsertStatement),
 	#[revision(start = 4)]
 	Alter(AlterStatement),
+	#[revision(start = 5)]
+	Case(CaseStatement),
 }
 
 impl PartialOrd for Subquery {
@@ -67,6 +69,7 @@ impl Subquery {
 			Self::Remove(v) => v.writeable(),
 			Self::Rebuild(v) => v.writeable(),
 			Self::Alter(v) => v.writeable(),
+			Self::Case(v) => v.writeable(),
 		}
 	}
 	/// Process this type returning a computed simple Value
@@ -115,6 +118,7 @@ impl Subquery {
 			Self::Relate(ref v) => v.compute(stk, &ctx, opt, doc).await,
 			Self::Insert(ref v) => v.compute(stk, &ctx, opt, doc).await,
 			Self::Alter(ref v) => v.compute(stk, &ctx, opt, doc).await,
+			Self::Case(ref v) => v.compute(stk, &ctx, opt, doc).await,
 		}
 	}
 }
@@ -136,6 +140,7 @@ impl Display for Subquery {
 			Self::Rebuild(v) => write!(f, "({v})"),
 			Self::Alter(v) => write!(f, "({v})"),
 			Self::Ifelse(v) => Display::fmt(v, f),
+			Self::Case(v) => Display::fmt(v, f),
 		}
 	}
 }
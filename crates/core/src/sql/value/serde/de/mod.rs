@@ -1,6 +1,7 @@
 use crate::err::Error;
 use crate::sql;
 use crate::sql::Value;
+use serde::de::Deserialize;
 use serde::de::DeserializeOwned;
 use serde_content::Deserializer;
 use serde_content::Number;
@@ -71,6 +72,31 @@ impl Value {
 			Value::Refs(_) => Ok(Content::Seq(vec![])),
 		}
 	}
+
+	fn as_content(&self) -> Result<Content<'_>, Error> {
+		match self {
+			Value::Strand(sql::Strand(v)) => Ok(Content::String(Cow::Borrowed(v))),
+			Value::Bytes(sql::Bytes(v)) => Ok(Content::Bytes(Cow::Borrowed(v))),
+			Value::Array(sql::Array(v)) => {
+				let mut vec = Vec::with_capacity(v.len());
+				for value in v {
+					vec.push(value.as_content()?);
+				}
+				Ok(Content::Seq(vec))
+			}
+			Value::Object(sql::Object(v)) => {
+				let mut vec = Vec::with_capacity(v.len());
+				for (key, value) in v {
+					let key = Content::String(Cow::Borrowed(key.as_str()));
+					let value = value.as_content()?;
+					vec.push((key, value));
+				}
+				Ok(Content::Map(vec))
+			}
+			// Everything else is either cheap to copy or goes through the serializer anyway
+			v => v.clone().into_content(),
+		}
+	}
 }
 
 /// Deserializes a value `T` from `SurrealDB` [`Value`]
@@ -82,3 +108,45 @@ where
 	let deserializer = Deserializer::new(content).coerce_numbers();
 	T::deserialize(deserializer).map_err(Into::into)
 }
+
+/// Deserializes a value `T` borrowing from a `SurrealDB` [`Value`]
+///
+/// Strings, object keys and bytes are borrowed from the value instead of
+/// being cloned, so `T` may hold `&str`, `&[u8]` or `Cow<str>` fields.
+pub fn from_value_ref<'a, T>(value: &'a Value) -> Result<T, Error>
+where
+	T: Deserialize<'a>,
+{
+	let content = value.as_content()?;
+	let deserializer = Deserializer::new(content).coerce_numbers();
+	T::deserialize(deserializer).map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::syn::value;
+	use serde::Deserialize;
+
+	#[derive(Debug, Deserialize, PartialEq)]
+	struct Person<'a> {
+		name: &'a str,
+		#[serde(borrow)]
+		title: Cow<'a, str>,
+		tags: Vec<String>,
+		age: i64,
+	}
+
+	#[test]
+	fn borrowed_matches_owned() {
+		let val = value("{ name: 'Tobie', title: 'CEO', tags: ['founder'], age: 30 }").unwrap();
+		let person: Person = from_value_ref(&val).unwrap();
+		assert_eq!(person.name, "Tobie");
+		assert!(matches!(person.title, Cow::Borrowed("CEO")));
+		assert_eq!(person.tags, vec!["founder".to_owned()]);
+		assert_eq!(person.age, 30);
+		let owned: serde_json::Value = from_value(val.clone()).unwrap();
+		let borrowed: serde_json::Value = from_value_ref(&val).unwrap();
+		assert_eq!(owned, borrowed);
+	}
+}
@@ -0,0 +1,297 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::sql::fmt::Fmt;
+use crate::sql::{Array, Function, Idiom, OrderList, Value};
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+
+/// A window function, which is computed over a partition of the selected rows
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Window {
+	pub func: WindowFunction,
+	/// The `PARTITION BY` fields, where no fields is a single partition
+	pub partition: Vec<Idiom>,
+	/// The `ORDER BY` fields, which order the rows within each partition
+	pub order: Option<OrderList>,
+}
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum WindowFunction {
+	/// The position of the row within its partition
+	#[default]
+	RowNumber,
+	/// The position of the first row with the same ordering values, leaving gaps after ties
+	Rank,
+	/// The number of distinct ordering values up to the row, without gaps after ties
+	DenseRank,
+	/// The value of a row before this row, with an optional offset and default value
+	Lag(Vec<Value>),
+	/// The value of a row after this row, with an optional offset and default value
+	Lead(Vec<Value>),
+	/// An aggregate function over the partition, or over the rows up to this row when ordered
+	Aggregate(Function),
+}
+
+impl WindowFunction {
+	/// The names of the functions which are only allowed with an `OVER` clause
+	pub(crate) const NAMES: [&'static str; 5] = ["row_number", "rank", "dense_rank", "lag", "lead"];
+
+	fn name(&self) -> &str {
+		match self {
+			Self::RowNumber => "row_number",
+			Self::Rank => "rank",
+			Self::DenseRank => "dense_rank",
+			Self::Lag(_) => "lag",
+			Self::Lead(_) => "lead",
+			Self::Aggregate(f) => f.name().unwrap_or("function"),
+		}
+	}
+
+	fn args(&self) -> &[Value] {
+		match self {
+			Self::Lag(a) | Self::Lead(a) => a,
+			Self::Aggregate(f) => f.args(),
+			_ => &[],
+		}
+	}
+}
+
+impl Display for WindowFunction {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Aggregate(v) => Display::fmt(v, f),
+			v => write!(f, "{}({})", v.name(), Fmt::comma_separated(v.args())),
+		}
+	}
+}
+
+impl Display for Window {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "{} OVER (", self.func)?;
+		if !self.partition.is_empty() {
+			write!(f, "PARTITION BY {}", Fmt::comma_separated(&self.partition))?;
+			if self.order.is_some() {
+				f.write_str(" ")?;
+			}
+		}
+		if let Some(order) = &self.order {
+			write!(f, "ORDER BY {order}")?;
+		}
+		f.write_str(")")
+	}
+}
+
+impl Window {
+	/// Convert the window function to a field name
+	pub(crate) fn to_idiom(&self) -> Idiom {
+		match &self.func {
+			WindowFunction::Aggregate(f) => f.to_idiom(),
+			v => v.name().to_owned().into(),
+		}
+	}
+
+	/// Checks if this window function is writable
+	pub(crate) fn writeable(&self) -> bool {
+		self.func.args().iter().any(Value::writeable)
+	}
+
+	/// Computes the values of a row which the window function depends on
+	///
+	/// The window function itself can only be computed once every row is known, so this
+	/// returns the partition values, ordering values and arguments of the row, which are
+	/// replaced with the result by [`Window::compute`].
+	pub(crate) async fn prepare(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: &CursorDoc,
+	) -> Result<Value, Error> {
+		let mut partition = Array::with_capacity(self.partition.len());
+		for idiom in &self.partition {
+			partition.push(idiom.compute(stk, ctx, opt, Some(doc)).await?);
+		}
+		let mut order = Array::new();
+		for v in self.order.iter().flat_map(|o| o.iter()) {
+			order.push(v.value.compute(stk, ctx, opt, Some(doc)).await?);
+		}
+		let mut args = Array::new();
+		match &self.func {
+			// Without arguments the aggregate function is computed on the row, as with GROUP BY
+			WindowFunction::Aggregate(f) if f.args().is_empty() => {
+				args.push(f.compute(stk, ctx, opt, Some(doc)).await?)
+			}
+			WindowFunction::Aggregate(f) => {
+				args.push(f.args()[0].compute(stk, ctx, opt, Some(doc)).await?)
+			}
+			v => {
+				for arg in v.args() {
+					args.push(arg.compute(stk, ctx, opt, Some(doc)).await?);
+				}
+			}
+		}
+		Ok(Value::Array(vec![partition.into(), order.into(), args.into()].into()))
+	}
+
+	/// Computes the window function over every row, replacing the values from [`Window::prepare`]
+	///
+	/// The prepared values are read from the path of the field in each row, or from the row
+	/// itself when the path is `None`, as with a `VALUE` projection.
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		rows: &mut [Value],
+		path: Option<&Idiom>,
+	) -> Result<(), Error> {
+		// Read the prepared values of each row
+		let prepared: Vec<[Vec<Value>; 3]> = rows
+			.iter()
+			.map(|row| {
+				let v = match path {
+					Some(path) => row.pick(path),
+					None => row.clone(),
+				};
+				let mut v = match v {
+					Value::Array(v) => v.0,
+					_ => vec![],
+				};
+				v.resize(3, Value::None);
+				let mut v = v.into_iter().map(|v| match v {
+					Value::Array(v) => v.0,
+					_ => vec![],
+				});
+				[(); 3].map(|_| v.next().unwrap_or_default())
+			})
+			.collect();
+		// Rows with the same ordering values are peers, which have the same rank
+		let peers = |a: usize, b: usize| match &self.order {
+			Some(order) => compare(order, &prepared[a][1], &prepared[b][1]) == Ordering::Equal,
+			None => true,
+		};
+		// Sort the rows by their partition values and then their ordering values, once. The sort
+		// is stable, so the peers within a partition keep the order they were selected in.
+		let mut sorted: Vec<usize> = (0..rows.len()).collect();
+		sorted.sort_by(|&a, &b| {
+			prepared[a][0].cmp(&prepared[b][0]).then_with(|| match &self.order {
+				Some(order) => compare(order, &prepared[a][1], &prepared[b][1]),
+				None => Ordering::Equal,
+			})
+		});
+		// Running aggregates are updated with each peer group, instead of being recomputed
+		let running = match &self.func {
+			WindowFunction::Aggregate(f) if self.order.is_some() => match f.name() {
+				Some("count" | "math::sum") => Some("math::sum"),
+				Some("math::min") => Some("math::min"),
+				Some("math::max") => Some("math::max"),
+				_ => None,
+			},
+			_ => None,
+		};
+		let mut results = vec![Value::None; rows.len()];
+		for partition in sorted.chunk_by(|&a, &b| prepared[a][0] == prepared[b][0]) {
+			let (mut pos, mut dense_rank) = (0usize, 0usize);
+			let mut total: Option<Value> = None;
+			for group in partition.chunk_by(|&a, &b| peers(a, b)) {
+				let rank = pos + 1;
+				dense_rank += 1;
+				// An aggregate is computed once for each peer group. An ordered window covers the
+				// rows up to and including the peers of this row, or otherwise the partition.
+				let aggregate = match &self.func {
+					WindowFunction::Aggregate(f) => {
+						let covered = match (running, &total) {
+							(Some(_), Some(_)) => group,
+							_ => &partition[..pos + group.len()],
+						};
+						let values = covered
+							.iter()
+							.map(|&v| prepared[v][2].first().cloned().unwrap_or_default())
+							.collect::<Vec<_>>();
+						let mut value =
+							f.aggregate(values.into())?.compute(stk, ctx, opt, None).await?;
+						if let (Some(combine), Some(previous)) = (running, total.take()) {
+							let combine = Function::Normal(combine.to_owned(), vec![]);
+							let values = vec![previous, value];
+							value = combine
+								.aggregate(values.into())?
+								.compute(stk, ctx, opt, None)
+								.await?;
+						}
+						if running.is_some() {
+							total = Some(value.clone());
+						}
+						value
+					}
+					_ => Value::None,
+				};
+				for &row in group {
+					results[row] = match &self.func {
+						WindowFunction::RowNumber => (pos + 1).into(),
+						WindowFunction::Rank => rank.into(),
+						WindowFunction::DenseRank => dense_rank.into(),
+						WindowFunction::Lag(_) | WindowFunction::Lead(_) => {
+							let args = &prepared[row][2];
+							let offset = match args.get(1) {
+								Some(Value::Number(v)) => v.to_usize(),
+								Some(v) if !v.is_none_or_null() => {
+									return Err(Error::InvalidArguments {
+										name: self.func.name().to_owned(),
+										message: String::from("The offset must be a number."),
+									})
+								}
+								_ => 1,
+							};
+							let other = match self.func {
+								WindowFunction::Lag(_) => pos.checked_sub(offset),
+								_ => pos.checked_add(offset),
+							};
+							match other.and_then(|v| partition.get(v)) {
+								Some(&v) => prepared[v][2].first().cloned().unwrap_or_default(),
+								None => args.get(2).cloned().unwrap_or_default(),
+							}
+						}
+						WindowFunction::Aggregate(_) => aggregate.clone(),
+					};
+					pos += 1;
+				}
+			}
+		}
+		// Replace the prepared values with the results
+		for (row, result) in rows.iter_mut().zip(results) {
+			match path {
+				Some(path) => row.put(path, result),
+				None => *row = result,
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Compares the ordering values of two rows
+fn compare(order: &OrderList, a: &[Value], b: &[Value]) -> Ordering {
+	for (i, order) in order.iter().enumerate() {
+		let (a, b) = (a.get(i).unwrap_or(&Value::None), b.get(i).unwrap_or(&Value::None));
+		// Reverse the ordering if DESC
+		let o = match order.direction {
+			true => a.compare(b, &[], order.collate, order.numeric),
+			false => b.compare(a, &[], order.collate, order.numeric),
+		};
+		match o {
+			Some(Ordering::Equal) | None => continue,
+			Some(o) => return o,
+		}
+	}
+	Ordering::Equal
+}
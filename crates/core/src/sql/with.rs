@@ -2,13 +2,22 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter, Result};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub enum With {
+	/// Don't use any index, and hash the records of any `JOIN`
 	NoIndex,
+	/// Only use the given indexes, including to look up the records of any `JOIN`
 	Index(Vec<String>),
+	/// Don't use remote record-link indexes to join tables, and hash the records of any `JOIN`
+	#[revision(start = 2)]
+	NoJoin,
+	/// Compute nearest neighbours by brute force, ignoring any vector indexes, and check each
+	/// pair of rows of any `JOIN`
+	#[revision(start = 2)]
+	BruteForce,
 }
 
 impl Display for With {
@@ -20,6 +29,8 @@ impl Display for With {
 				f.write_str(" INDEX ")?;
 				f.write_str(&i.join(","))
 			}
+			With::NoJoin => f.write_str(" NOJOIN"),
+			With::BruteForce => f.write_str(" BRUTEFORCE"),
 		}
 	}
 }
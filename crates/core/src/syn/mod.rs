@@ -23,7 +23,8 @@ mod test;
 use lexer::{compound, Lexer};
 use parser::{Parser, ParserSettings};
 use reblessive::Stack;
-use token::t;
+use std::collections::BTreeSet;
+use token::{t, TokenKind};
 
 const TARGET: &str = "surrealdb::core::syn";
 
@@ -32,6 +33,55 @@ pub fn could_be_reserved_keyword(s: &str) -> bool {
 	lexer::keywords::could_be_reserved(s)
 }
 
+/// Collects the names of the functions called in the given source, like `string::len` or `fn::greet`.
+///
+/// The source is only tokenized, not parsed, so a call is any path of identifiers which is
+/// followed by an opening parenthesis.
+pub fn functions(source: &str) -> BTreeSet<String> {
+	let mut res = BTreeSet::new();
+	let mut lexer = Lexer::new(source.as_bytes());
+	let mut path = Vec::new();
+	// Whether the path is a single identifier, like `count`, rather than a keyword
+	let mut ident = false;
+	let mut joined = false;
+	loop {
+		let token = lexer.next_token();
+		let segment = match token.kind {
+			TokenKind::Eof => return res,
+			t!("::") if !path.is_empty() => {
+				joined = true;
+				continue;
+			}
+			t!("(") => {
+				if path.len() > 1 || ident {
+					res.insert(path.join("::"));
+				}
+				None
+			}
+			TokenKind::Identifier => lexer.string.take(),
+			TokenKind::Keyword(_) => {
+				Some(String::from_utf8_lossy(lexer.span_bytes(token.span)).into())
+			}
+			_ => None,
+		};
+		match segment {
+			Some(x) if joined => {
+				path.push(x);
+				ident = false;
+			}
+			Some(x) => {
+				ident = token.kind == TokenKind::Identifier;
+				path = vec![x];
+			}
+			None => {
+				path.clear();
+				ident = false;
+			}
+		}
+		joined = false;
+	}
+}
+
 /// Parses a SurrealQL [`Query`]
 ///
 /// During query parsing, the total depth of calls to parse values (including arrays, expressions,
@@ -87,6 +137,35 @@ pub fn parse_with_capabilities(input: &str, capabilities: &Capabilities) -> Resu
 		.map_err(Error::InvalidQuery)
 }
 
+/// Parses a SurrealQL [`Query`] written for SurrealDB 1.x, such as an export file.
+///
+/// Strings which look like a UUID, record id, or datetime are parsed as such, like the parser of
+/// SurrealDB 1.x did.
+#[instrument(level = "trace", target = "surrealdb::core::syn", fields(length = input.len()))]
+pub fn parse_legacy(input: &str) -> Result<Query, Error> {
+	trace!(target: TARGET, "Parsing SurrealQL query, with legacy strings");
+
+	if input.len() > u32::MAX as usize {
+		return Err(Error::QueryTooLarge);
+	}
+
+	let mut parser = Parser::new_with_settings(
+		input.as_bytes(),
+		ParserSettings {
+			object_recursion_limit: *MAX_OBJECT_PARSING_DEPTH as usize,
+			query_recursion_limit: *MAX_QUERY_PARSING_DEPTH as usize,
+			legacy_strands: true,
+			..Default::default()
+		},
+	);
+	let mut stack = Stack::new();
+	stack
+		.enter(|stk| parser.parse_query(stk))
+		.finish()
+		.map_err(|e| e.render_on(input))
+		.map_err(Error::InvalidQuery)
+}
+
 /// Parses a SurrealQL [`Value`].
 #[instrument(level = "trace", target = "surrealdb::core::syn", fields(length = input.len()))]
 pub fn value(input: &str) -> Result<Value, Error> {
@@ -31,6 +31,7 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("array::boolean_not") => PathKind::Function,
 		UniCase::ascii("array::boolean_or") => PathKind::Function,
 		UniCase::ascii("array::boolean_xor") => PathKind::Function,
+		UniCase::ascii("array::chunk") => PathKind::Function,
 		UniCase::ascii("array::clump") => PathKind::Function,
 		UniCase::ascii("array::combine") => PathKind::Function,
 		UniCase::ascii("array::complement") => PathKind::Function,
@@ -81,9 +82,13 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("array::sort::asc") => PathKind::Function,
 		UniCase::ascii("array::sort::desc") => PathKind::Function,
 		//
+		UniCase::ascii("assert::equal") => PathKind::Function,
+		UniCase::ascii("assert::throws") => PathKind::Function,
+		//
 		UniCase::ascii("bytes::len") => PathKind::Function,
 		//
 		UniCase::ascii("count") => PathKind::Function,
+		UniCase::ascii("count::approx_distinct") => PathKind::Function,
 		//
 		UniCase::ascii("crypto::blake3") => PathKind::Function,
 		UniCase::ascii("crypto::md5") => PathKind::Function,
@@ -127,6 +132,7 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("geo::hash::decode") => PathKind::Function,
 		UniCase::ascii("geo::hash::encode") => PathKind::Function,
 		UniCase::ascii("geo::is::valid") => PathKind::Function,
+		UniCase::ascii("geo::transform") => PathKind::Function,
 		//
 		UniCase::ascii("http::head") => PathKind::Function,
 		UniCase::ascii("http::get") => PathKind::Function,
@@ -135,9 +141,12 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("http::patch") => PathKind::Function,
 		UniCase::ascii("http::delete") => PathKind::Function,
 		//
+		UniCase::ascii("job::retry_after") => PathKind::Function,
+		//
 		UniCase::ascii("math::abs") => PathKind::Function,
 		UniCase::ascii("math::acos") => PathKind::Function,
 		UniCase::ascii("math::acot") => PathKind::Function,
+		UniCase::ascii("math::approx_percentile") => PathKind::Function,
 		UniCase::ascii("math::asin") => PathKind::Function,
 		UniCase::ascii("math::atan") => PathKind::Function,
 		UniCase::ascii("math::bottom") => PathKind::Function,
@@ -222,6 +231,7 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		//
 		UniCase::ascii("search::analyze") => PathKind::Function,
 		UniCase::ascii("search::score") => PathKind::Function,
+		UniCase::ascii("search::similarity") => PathKind::Function,
 		UniCase::ascii("search::highlight") => PathKind::Function,
 		UniCase::ascii("search::offsets") => PathKind::Function,
 		//
@@ -291,13 +301,21 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("string::semver::set::minor") => PathKind::Function,
 		UniCase::ascii("string::semver::set::patch") => PathKind::Function,
 		//
+		UniCase::ascii("string::regex::captures") => PathKind::Function,
+		UniCase::ascii("string::regex::replace_all") => PathKind::Function,
 		UniCase::ascii("string::similarity::fuzzy") => PathKind::Function,
 		UniCase::ascii("string::similarity::jaro") => PathKind::Function,
 		UniCase::ascii("string::similarity::jaro_winkler") => PathKind::Function,
 		UniCase::ascii("string::similarity::smithwaterman") => PathKind::Function,
 		UniCase::ascii("string::similarity::sorensen_dice") => PathKind::Function,
+		UniCase::ascii("string::similarity::trigram") => PathKind::Function,
 		UniCase::ascii("string::matches") => PathKind::Function,
 		//
+		UniCase::ascii("sys::env") => PathKind::Function,
+		UniCase::ascii("sys::hostname") => PathKind::Function,
+		UniCase::ascii("sys::sleep") => PathKind::Function,
+		//
+		UniCase::ascii("time::bucket") => PathKind::Function,
 		UniCase::ascii("time::ceil") => PathKind::Function,
 		UniCase::ascii("time::day") => PathKind::Function,
 		UniCase::ascii("time::floor") => PathKind::Function,
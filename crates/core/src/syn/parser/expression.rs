@@ -312,6 +312,7 @@ impl Parser<'_> {
 				| Operator::AllLike
 				| Operator::AnyLike
 				| Operator::Like
+				| Operator::Similar
 				| Operator::Contain
 				| Operator::NotContain
 				| Operator::NotInside
@@ -348,7 +349,15 @@ impl Parser<'_> {
 			t!("!~") => Operator::NotLike,
 			t!("*~") => Operator::AllLike,
 			t!("?~") => Operator::AnyLike,
-			t!("~") => Operator::Like,
+			t!("~") => {
+				// `~%` is the operator for the similarity of strings
+				if self.peek_whitespace().kind == t!("%") {
+					self.pop_peek();
+					Operator::Similar
+				} else {
+					Operator::Like
+				}
+			}
 			t!("@") => {
 				let reference = (!self.eat(t!("@")))
 					.then(|| {
@@ -3,7 +3,8 @@ use reblessive::Stk;
 use crate::{
 	sql::{
 		part::{DestructurePart, Recurse, RecurseInstruction},
-		Dir, Edges, Field, Fields, Graph, Ident, Idiom, Param, Part, Table, Tables, Value,
+		Dir, Edges, Field, Fields, Function, Graph, Ident, Idiom, Param, Part, Table, Tables,
+		Value, WindowFunction,
 	},
 	syn::{
 		error::bail,
@@ -30,36 +31,29 @@ impl Parser<'_> {
 	/// # Parser State
 	/// Expects the next tokens to be of a field set.
 	pub(super) async fn parse_fields(&mut self, ctx: &mut Stk) -> ParseResult<Fields> {
+		self.parse_fields_with_windows(ctx, false).await
+	}
+
+	/// Parse fields of a selecting query, which can include window functions when `windows` is
+	/// true: `rank() OVER (ORDER BY score)` in `SELECT rank() OVER (ORDER BY score) FROM baz`.
+	///
+	/// # Parser State
+	/// Expects the next tokens to be of a field set.
+	pub(super) async fn parse_fields_with_windows(
+		&mut self,
+		ctx: &mut Stk,
+		windows: bool,
+	) -> ParseResult<Fields> {
 		if self.eat(t!("VALUE")) {
-			let expr = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
-			let alias = if self.eat(t!("AS")) {
-				Some(self.parse_plain_idiom(ctx).await?)
-			} else {
-				None
-			};
-			Ok(Fields(
-				vec![Field::Single {
-					expr,
-					alias,
-				}],
-				true,
-			))
+			let field = self.parse_field(ctx, windows).await?;
+			Ok(Fields(vec![field], true))
 		} else {
 			let mut fields = Vec::new();
 			loop {
 				let field = if self.eat(t!("*")) {
 					Field::All
 				} else {
-					let expr = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
-					let alias = if self.eat(t!("AS")) {
-						Some(self.parse_plain_idiom(ctx).await?)
-					} else {
-						None
-					};
-					Field::Single {
-						expr,
-						alias,
-					}
+					self.parse_field(ctx, windows).await?
 				};
 				fields.push(field);
 				if !self.eat(t!(",")) {
@@ -70,6 +64,74 @@ impl Parser<'_> {
 		}
 	}
 
+	/// Parse a single field with an optional alias.
+	async fn parse_field(&mut self, ctx: &mut Stk, windows: bool) -> ParseResult<Field> {
+		let before = self.peek().span;
+		// Window functions are only functions when followed by a window
+		let peek = self.peek();
+		let name = if windows && Self::kind_is_identifier(peek.kind) && self.peek1().kind == t!("(")
+		{
+			let name = self.lexer.span_str(peek.span).to_ascii_lowercase();
+			WindowFunction::NAMES.contains(&name.as_str()).then_some(name)
+		} else {
+			None
+		};
+		let (func, mut expr) = match name {
+			Some(name) => {
+				self.pop_peek();
+				let Function::Normal(name, args) =
+					ctx.run(|ctx| self.parse_builtin_function(ctx, name)).await?
+				else {
+					unreachable!()
+				};
+				let span = before.covers(self.last_span());
+				let func = match (name.as_str(), args.len()) {
+					("row_number", 0) => WindowFunction::RowNumber,
+					("rank", 0) => WindowFunction::Rank,
+					("dense_rank", 0) => WindowFunction::DenseRank,
+					("lag", 1..=3) => WindowFunction::Lag(args),
+					("lead", 1..=3) => WindowFunction::Lead(args),
+					("lag" | "lead", _) => {
+						bail!("The window function `{name}` expects 1 to 3 arguments", @span)
+					}
+					_ => bail!("The window function `{name}` expects no arguments", @span),
+				};
+				(Some(func), None)
+			}
+			None => (None, Some(ctx.run(|ctx| self.parse_value_field(ctx)).await?)),
+		};
+		let span = before.covers(self.last_span());
+		let window = if windows && self.eat_contextual("OVER") {
+			let func = match (func, expr.take()) {
+				(Some(func), _) => func,
+				(None, Some(Value::Function(f))) if f.is_aggregate() => {
+					WindowFunction::Aggregate(*f)
+				}
+				_ => bail!("Only window and aggregate functions can be used with OVER", @span),
+			};
+			Some(Box::new(self.parse_window(ctx, func).await?))
+		} else if let Some(func) = func {
+			bail!("The window function `{func}` requires an OVER clause", @span)
+		} else {
+			None
+		};
+		let alias = if self.eat(t!("AS")) {
+			Some(self.parse_plain_idiom(ctx).await?)
+		} else {
+			None
+		};
+		Ok(match window {
+			Some(window) => Field::Window {
+				window,
+				alias,
+			},
+			None => Field::Single {
+				expr: expr.unwrap_or_default(),
+				alias,
+			},
+		})
+	}
+
 	/// Parses a list of idioms separated by a `,`
 	pub(super) async fn parse_idiom_list(&mut self, ctx: &mut Stk) -> ParseResult<Vec<Idiom>> {
 		let mut res = vec![self.parse_plain_idiom(ctx).await?];
@@ -166,6 +166,8 @@ pub struct Parser<'a> {
 	token_buffer: TokenBuffer<4>,
 	glued_value: GluedValue,
 	pub(crate) table_as_field: bool,
+	/// The names of the subqueries of the WITH statements which are being parsed
+	named_subqueries: Vec<String>,
 	settings: ParserSettings,
 }
 
@@ -183,6 +185,7 @@ impl<'a> Parser<'a> {
 			token_buffer: TokenBuffer::new(),
 			glued_value: GluedValue::None,
 			table_as_field: true,
+			named_subqueries: Vec::new(),
 			settings,
 		}
 	}
@@ -333,6 +336,25 @@ impl<'a> Parser<'a> {
 		}
 	}
 
+	/// Eat the next token if it is an identifier matching the given contextual keyword.
+	/// Returns whether a token was eaten.
+	///
+	/// Contextual keywords are only treated as keywords in specific positions and can be used as
+	/// a normal identifier everywhere else.
+	pub fn eat_contextual(&mut self, keyword: &str) -> bool {
+		let peek = self.peek();
+		if peek.kind == TokenKind::Identifier
+			&& self.lexer.span_str(peek.span).eq_ignore_ascii_case(keyword)
+		{
+			self.token_buffer.pop();
+			self.lexer.string = None;
+			self.last_span = peek.span;
+			true
+		} else {
+			false
+		}
+	}
+
 	/// Eat the next token if it is of the given kind.
 	/// Returns whether a token was eaten.
 	///
@@ -111,7 +111,15 @@ impl Parser<'_> {
 						let str = self.next_token_value::<Ident>()?.0;
 						self.parse_thing_or_range(ctx, str).await.map(Value::Thing)
 					}
-					_ => Ok(Value::Table(self.next_token_value()?)),
+					_ => {
+						let ident: Ident = self.next_token_value()?;
+						if self.named_subqueries.contains(&ident.0) {
+							// This references a subquery named by a WITH statement
+							Ok(Value::Param(Param(ident)))
+						} else {
+							Ok(Value::Table(Table(ident.0)))
+						}
+					}
 				}
 			}
 			_ => unexpected!(self, token, "an expression"),
@@ -291,6 +299,13 @@ impl Parser<'_> {
 					Value::Subquery(Box::new(Subquery::Ifelse(stmt)))
 				})
 			}
+			t!("CASE") if self.peek_starts_case() => {
+				enter_query_recursion!(this = self => {
+					this.pop_peek();
+					let stmt = ctx.run(|ctx| this.parse_case_stmt(ctx)).await?;
+					Value::Subquery(Box::new(Subquery::Case(stmt)))
+				})
+			}
 			t!("(") => {
 				self.pop_peek();
 				let value = self.parse_inner_subquery_or_coordinate(ctx, token.span).await?;
@@ -330,10 +345,14 @@ impl Parser<'_> {
 						self.parse_thing_or_range(ctx, str).await?.into()
 					}
 					_ => {
-						if self.table_as_field {
-							Value::Idiom(Idiom(vec![Part::Field(self.next_token_value()?)]))
+						let ident: Ident = self.next_token_value()?;
+						if self.named_subqueries.contains(&ident.0) {
+							// This references a subquery named by a WITH statement
+							Value::Param(Param(ident))
+						} else if self.table_as_field {
+							Value::Idiom(Idiom(vec![Part::Field(ident)]))
 						} else {
-							Value::Table(self.next_token_value()?)
+							Value::Table(Table(ident.0))
 						}
 					}
 				}
@@ -0,0 +1,67 @@
+use reblessive::Stk;
+
+use crate::{
+	sql::statements::CaseStatement,
+	syn::{
+		parser::{
+			mac::{expected, unexpected},
+			ParseResult, Parser,
+		},
+		token::{t, TokenKind},
+	},
+};
+
+impl Parser<'_> {
+	/// Returns if the peeked `CASE` keyword starts a CASE expression, rather than a field named case.
+	pub(crate) fn peek_starts_case(&mut self) -> bool {
+		match self.peek1().kind {
+			t!("WHEN") | t!("true") | t!("false") | t!("NONE") | t!("NULL") => true,
+			// Other keywords can follow a field, like `FROM` or `AS`
+			TokenKind::Keyword(_) => false,
+			TokenKind::Identifier
+			| TokenKind::Parameter
+			| TokenKind::Digits
+			| TokenKind::Qoute(_)
+			| TokenKind::Glued(_)
+			| t!("(")
+			| t!("{") => true,
+			_ => false,
+		}
+	}
+
+	pub(crate) async fn parse_case_stmt(&mut self, ctx: &mut Stk) -> ParseResult<CaseStatement> {
+		// Without a value, each branch is a condition
+		let value = if self.peek_kind() == t!("WHEN") {
+			None
+		} else {
+			Some(ctx.run(|ctx| self.parse_value_inherit(ctx)).await?)
+		};
+
+		let mut res = CaseStatement {
+			value,
+			exprs: Vec::new(),
+			close: None,
+		};
+
+		expected!(self, t!("WHEN"));
+		loop {
+			let condition = ctx.run(|ctx| self.parse_value_inherit(ctx)).await?;
+			expected!(self, t!("THEN"));
+			let body = ctx.run(|ctx| self.parse_value_inherit(ctx)).await?;
+			res.exprs.push((condition, body));
+
+			let next = self.next();
+			match next.kind {
+				t!("WHEN") => {}
+				t!("ELSE") => {
+					let value = ctx.run(|ctx| self.parse_value_inherit(ctx)).await?;
+					expected!(self, t!("END"));
+					res.close = Some(value);
+					return Ok(res);
+				}
+				t!("END") => return Ok(res),
+				_ => unexpected!(self, next, "WHEN, ELSE or END"),
+			}
+		}
+	}
+}
@@ -7,7 +7,7 @@ use crate::sql::index::HnswParams;
 use crate::sql::statements::define::config::api::ApiConfig;
 use crate::sql::statements::define::config::graphql::{GraphQLConfig, TableConfig};
 use crate::sql::statements::define::config::ConfigInner;
-use crate::sql::statements::define::{ApiAction, DefineConfigStatement};
+use crate::sql::statements::define::{ApiAction, DatabaseEvent, DefineConfigStatement, TableEvent};
 use crate::sql::statements::DefineApiStatement;
 use crate::sql::Value;
 use crate::syn::error::bail;
@@ -21,7 +21,8 @@ use crate::{
 			define::config::graphql, DefineAccessStatement, DefineAnalyzerStatement,
 			DefineDatabaseStatement, DefineEventStatement, DefineFieldStatement,
 			DefineFunctionStatement, DefineIndexStatement, DefineNamespaceStatement,
-			DefineParamStatement, DefineStatement, DefineTableStatement, DefineUserStatement,
+			DefineParamStatement, DefineStatement, DefineTableStatement, DefineTestStatement,
+			DefineUserStatement,
 		},
 		table_type,
 		tokenizer::Tokenizer,
@@ -65,6 +66,12 @@ impl Parser<'_> {
 			t!("ANALYZER") => self.parse_define_analyzer().map(DefineStatement::Analyzer),
 			t!("ACCESS") => self.parse_define_access(ctx).await.map(DefineStatement::Access),
 			t!("CONFIG") => self.parse_define_config(ctx).await.map(DefineStatement::Config),
+			// `TEST` is a contextual keyword, so it can still be used as an identifier elsewhere.
+			TokenKind::Identifier
+				if self.lexer.span_str(next.span).eq_ignore_ascii_case("TEST") =>
+			{
+				self.parse_define_test(ctx).await.map(DefineStatement::Test)
+			}
 			_ => unexpected!(self, next, "a define statement keyword"),
 		}
 	}
@@ -146,6 +153,7 @@ impl Parser<'_> {
 		let name = self.parse_custom_function_name()?;
 		let token = expected!(self, t!("(")).span;
 		let mut args = Vec::new();
+		let mut defaults = Vec::new();
 		loop {
 			if self.eat(t!(")")) {
 				break;
@@ -155,6 +163,13 @@ impl Parser<'_> {
 			expected!(self, t!(":"));
 			let kind = ctx.run(|ctx| self.parse_inner_kind(ctx)).await?;
 
+			if self.eat(t!("=")) {
+				let value = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
+				defaults.push((param.clone(), value));
+			} else if !defaults.is_empty() && !matches!(kind, Kind::Option(_) | Kind::Any) {
+				bail!("Expected a default value for argument `${}`, as it follows an argument with a default value", param.0, @self.last_span);
+			}
+
 			args.push((param, kind));
 
 			if !self.eat(t!(",")) {
@@ -178,6 +193,7 @@ impl Parser<'_> {
 			if_not_exists,
 			overwrite,
 			returns,
+			defaults,
 			..Default::default()
 		};
 
@@ -198,6 +214,37 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	pub async fn parse_define_test(&mut self, ctx: &mut Stk) -> ParseResult<DefineTestStatement> {
+		let (if_not_exists, overwrite) = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			(true, false)
+		} else if self.eat(t!("OVERWRITE")) {
+			(false, true)
+		} else {
+			(false, false)
+		};
+		let name = self.next_token_value()?;
+
+		let next = expected!(self, t!("{")).span;
+		let block = self.parse_block(ctx, next).await?;
+
+		let mut res = DefineTestStatement {
+			name,
+			block,
+			if_not_exists,
+			overwrite,
+			..Default::default()
+		};
+
+		while let t!("COMMENT") = self.peek_kind() {
+			self.pop_peek();
+			res.comment = Some(self.next_token_value()?);
+		}
+
+		Ok(res)
+	}
+
 	pub fn parse_define_user(&mut self) -> ParseResult<DefineUserStatement> {
 		let (if_not_exists, overwrite) = if self.eat(t!("IF")) {
 			expected!(self, t!("NOT"));
@@ -726,6 +773,11 @@ impl Parser<'_> {
 		let mut kind: Option<TableType> = None;
 
 		loop {
+			// `CACHE` is a contextual keyword, so it can still be used as an identifier elsewhere.
+			if self.eat_contextual("CACHE") {
+				res.cache = true;
+				continue;
+			}
 			match self.peek_kind() {
 				t!("COMMENT") => {
 					self.pop_peek();
@@ -788,6 +840,23 @@ impl Parser<'_> {
 						_ => unexpected!(self, peek, "`SELECT`"),
 					}
 				}
+				t!("LIKE") => {
+					let token = self.pop_peek();
+					// A table defined from a template takes all of its options from the template
+					if kind.is_some()
+						|| res.drop || res.full
+						|| res.cache || res.view.is_some()
+						|| res.changefeed.is_some()
+						|| res.permissions != Permissions::none()
+					{
+						bail!("Unexpected token `LIKE`, a table defined LIKE another table can only have a COMMENT clause", @token.span);
+					}
+					res.like = Some(self.next_token_value()?);
+					if self.eat(t!("COMMENT")) {
+						res.comment = Some(self.next_token_value()?);
+					}
+					break;
+				}
 				_ => break,
 			}
 		}
@@ -898,9 +967,19 @@ impl Parser<'_> {
 			(false, false)
 		};
 		let name = self.next_token_value()?;
-		expected!(self, t!("ON"));
-		self.eat(t!("TABLE"));
-		let what = self.next_token_value()?;
+		// Database events use FOR, so that ON never shadows a table name
+		let database = self.eat(t!("FOR"));
+		let what = match database {
+			true => {
+				expected!(self, t!("DATABASE"));
+				Ident::default()
+			}
+			false => {
+				expected!(self, t!("ON"));
+				self.eat(t!("TABLE"));
+				self.next_token_value()?
+			}
+		};
 
 		let mut res = DefineEventStatement {
 			name,
@@ -910,12 +989,29 @@ impl Parser<'_> {
 			overwrite,
 			..Default::default()
 		};
+		let mut has_when = false;
 
 		loop {
 			match self.peek_kind() {
-				t!("WHEN") => {
+				t!("WHEN") if database => {
 					self.pop_peek();
-					res.when = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
+					res.database = Some(self.parse_database_event()?);
+				}
+				t!("WHEN") => {
+					let token = self.pop_peek();
+					let before = self.peek_kind() == t!("BEFORE")
+						&& matches!(self.peek1().kind, t!("CREATE") | t!("UPDATE") | t!("DELETE"));
+					// A BEFORE event can not also have a condition
+					if !res.before.is_empty() || (before && has_when) {
+						bail!("Unexpected second `WHEN` clause, an event which runs `WHEN BEFORE` a write can not have a condition", @token.span);
+					}
+					if before {
+						self.pop_peek();
+						res.before = self.parse_table_events()?;
+					} else {
+						res.when = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
+						has_when = true;
+					}
 				}
 				t!("THEN") => {
 					self.pop_peek();
@@ -924,6 +1020,18 @@ impl Parser<'_> {
 						res.then.0.push(ctx.run(|ctx| self.parse_value_field(ctx)).await?)
 					}
 				}
+				t!("ASYNC") if !database => {
+					self.pop_peek();
+					res.asynchronous = true;
+				}
+				t!("RETRY") if res.asynchronous => {
+					self.pop_peek();
+					res.retry = Some(self.next_token_value()?);
+				}
+				t!("BACKOFF") if res.asynchronous => {
+					self.pop_peek();
+					res.backoff = Some(self.next_token_value()?);
+				}
 				t!("COMMENT") => {
 					self.pop_peek();
 					res.comment = Some(self.next_token_value()?);
@@ -931,9 +1039,45 @@ impl Parser<'_> {
 				_ => break,
 			}
 		}
+		if database && res.database.is_none() {
+			bail!("Expected a `WHEN` clause with the database event to trigger on, such as `table_created`", @self.last_span);
+		}
+		if res.asynchronous && !res.before.is_empty() {
+			bail!("An event which runs `WHEN BEFORE` a write can not be `ASYNC`", @self.last_span);
+		}
 		Ok(res)
 	}
 
+	fn parse_table_events(&mut self) -> ParseResult<Vec<TableEvent>> {
+		let mut res = Vec::new();
+		loop {
+			let next = self.next();
+			let event = match next.kind {
+				t!("CREATE") => TableEvent::Create,
+				t!("UPDATE") => TableEvent::Update,
+				t!("DELETE") => TableEvent::Delete,
+				_ => unexpected!(self, next, "CREATE, UPDATE or DELETE"),
+			};
+			if !res.contains(&event) {
+				res.push(event);
+			}
+			if !self.eat(t!(",")) {
+				return Ok(res);
+			}
+		}
+	}
+
+	fn parse_database_event(&mut self) -> ParseResult<DatabaseEvent> {
+		let token = self.peek();
+		let event: Ident = self.next_token_value()?;
+		match event.0.to_ascii_lowercase().as_str() {
+			"table_created" => Ok(DatabaseEvent::TableCreated),
+			_ => {
+				bail!("Expected a database event such as `table_created`, found `{}`", event.0, @token.span)
+			}
+		}
+	}
+
 	pub async fn parse_define_field(&mut self, ctx: &mut Stk) -> ParseResult<DefineFieldStatement> {
 		let (if_not_exists, overwrite) = if self.eat(t!("IF")) {
 			expected!(self, t!("NOT"));
@@ -1052,6 +1196,10 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.index = Index::Uniq;
 				}
+				t!("TRIGRAM") => {
+					self.pop_peek();
+					res.index = Index::Trigram;
+				}
 				t!("SEARCH") => {
 					self.pop_peek();
 					let mut analyzer: Option<Ident> = None;
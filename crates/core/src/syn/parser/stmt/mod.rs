@@ -1,5 +1,6 @@
 use reblessive::Stk;
 
+use crate::cnf::PROTECTED_PARAM_NAMES;
 use crate::sql::block::Entry;
 use crate::sql::statements::rebuild::{RebuildIndexStatement, RebuildStatement};
 use crate::sql::statements::show::{ShowSince, ShowStatement};
@@ -9,9 +10,11 @@ use crate::sql::statements::{
 		AccessStatement, AccessStatementGrant, AccessStatementPurge, AccessStatementRevoke,
 		AccessStatementShow, Subject,
 	},
-	KillStatement, LiveStatement, OptionStatement, SetStatement, ThrowStatement,
+	CheckStatement, EvictLimit, EvictStatement, KillStatement, LiveStatement, OptionStatement,
+	ReferencesStatement, RunStatement, SetStatement, ThrowStatement, WithStatement,
 };
-use crate::sql::{Duration, Fields, Ident, Param};
+use crate::sql::{Duration, Fields, Ident, Param, Version};
+use crate::syn::error::{bail, syntax_error};
 use crate::syn::lexer::compound;
 use crate::syn::parser::enter_query_recursion;
 use crate::syn::token::{t, Glued, TokenKind};
@@ -30,6 +33,7 @@ use crate::{
 use super::{mac::expected, ParseResult, Parser};
 
 mod alter;
+mod case;
 mod create;
 mod define;
 mod delete;
@@ -108,7 +112,7 @@ impl Parser<'_> {
 			}
 			t!("BEGIN") => {
 				self.pop_peek();
-				self.parse_begin().map(Statement::Begin)
+				ctx.run(|ctx| self.parse_begin(ctx)).await.map(Statement::Begin)
 			}
 			t!("BREAK") => {
 				self.pop_peek();
@@ -214,6 +218,49 @@ impl Parser<'_> {
 				self.pop_peek();
 				self.parse_use_stmt().map(Statement::Use)
 			}
+			t!("WITH") => {
+				self.pop_peek();
+				ctx.run(|ctx| self.parse_with_stmt(ctx)).await.map(Statement::With)
+			}
+			// `WHAT` is a contextual keyword, so it can still be used as an identifier elsewhere.
+			TokenKind::Identifier
+				if self.lexer.span_str(token.span).eq_ignore_ascii_case("WHAT")
+					&& self.peek1().kind == t!("REFERENCES") =>
+			{
+				self.pop_peek();
+				self.pop_peek();
+				self.parse_references_stmt().map(Statement::References)
+			}
+			// `CHECK` is a contextual keyword, so it can still be used as an identifier elsewhere.
+			TokenKind::Identifier
+				if self.lexer.span_str(token.span).eq_ignore_ascii_case("CHECK")
+					&& self.peek1().kind == t!("DATABASE") =>
+			{
+				self.pop_peek();
+				self.pop_peek();
+				self.parse_check_stmt().map(Statement::Check)
+			}
+			// `RUN` is a contextual keyword, so it can still be used as an identifier elsewhere.
+			TokenKind::Identifier
+				if self.lexer.span_str(token.span).eq_ignore_ascii_case("RUN") && {
+					let peek = self.peek1();
+					let next = self.lexer.span_str(peek.span);
+					peek.kind == TokenKind::Identifier
+						&& (next.eq_ignore_ascii_case("TEST") || next.eq_ignore_ascii_case("TESTS"))
+				} =>
+			{
+				self.pop_peek();
+				self.parse_run_stmt().map(Statement::Run)
+			}
+			// `EVICT` is a contextual keyword, so it can still be used as an identifier elsewhere.
+			TokenKind::Identifier
+				if self.lexer.span_str(token.span).eq_ignore_ascii_case("EVICT")
+					&& self.peek1().kind == t!("FROM") =>
+			{
+				self.pop_peek();
+				self.pop_peek();
+				self.parse_evict_stmt().map(Statement::Evict)
+			}
 			_ => {
 				// TODO: Provide information about keywords.
 				let value = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
@@ -325,6 +372,7 @@ impl Parser<'_> {
 						name: x.0 .0,
 						what: r,
 						kind: None,
+						local: false,
 					});
 				}
 				Statement::Value(Value::Expression(x))
@@ -346,6 +394,7 @@ impl Parser<'_> {
 						name: x.0 .0,
 						what: r,
 						kind: None,
+						local: false,
 					});
 				}
 				Entry::Value(Value::Expression(x))
@@ -506,9 +555,16 @@ impl Parser<'_> {
 	///
 	/// # Parser State
 	/// Expects `BEGIN` to already be consumed.
-	fn parse_begin(&mut self) -> ParseResult<BeginStatement> {
+	async fn parse_begin(&mut self, ctx: &mut Stk) -> ParseResult<BeginStatement> {
 		self.eat(t!("TRANSACTION"));
-		Ok(BeginStatement)
+		let mut res = BeginStatement::default();
+		if self.eat(t!("READONLY")) {
+			res.readonly = true;
+			if self.eat(t!("AT")) {
+				res.at = Some(Version(self.parse_value_inherit(ctx).await?));
+			}
+		}
+		Ok(res)
 	}
 
 	/// Parsers a cancel statement.
@@ -569,12 +625,26 @@ impl Parser<'_> {
 		expected!(self, t!("IN"));
 		let range = stk.run(|stk| self.parse_value_inherit(stk)).await?;
 
+		let batch = if self.eat_contextual("BATCH") {
+			Some(stk.run(|stk| self.parse_value_inherit(stk)).await?)
+		} else {
+			None
+		};
+
+		let each = self.eat(t!("COMMIT"));
+		if each && !self.eat_contextual("EACH") {
+			let peek = self.peek();
+			unexpected!(self, peek, "`EACH`");
+		}
+
 		let span = expected!(self, t!("{")).span;
 		let block = self.parse_block(stk, span).await?;
 		Ok(ForeachStatement {
 			param,
 			range,
 			block,
+			batch,
+			each,
 		})
 	}
 
@@ -588,7 +658,10 @@ impl Parser<'_> {
 		let mut stmt = match next.kind {
 			t!("ROOT") => InfoStatement::Root(false),
 			t!("NAMESPACE") => InfoStatement::Ns(false),
-			t!("DATABASE") => InfoStatement::Db(false, None),
+			t!("DATABASE") => match self.eat_contextual("GRAPH") {
+				true => InfoStatement::Graph,
+				false => InfoStatement::Db(false, None),
+			},
 			t!("TABLE") => {
 				let ident = self.next_token_value()?;
 				InfoStatement::Tb(ident, false, None)
@@ -710,6 +783,147 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	/// Parsers a WHAT REFERENCES statement.
+	///
+	/// # Parser State
+	/// Expects `WHAT REFERENCES` to already be consumed.
+	pub(super) fn parse_references_stmt(&mut self) -> ParseResult<ReferencesStatement> {
+		self.eat(t!("TABLE"));
+		let what = self.next_token_value()?;
+		Ok(ReferencesStatement {
+			what,
+		})
+	}
+
+	/// Parsers a WITH statement.
+	///
+	/// # Parser State
+	/// Expects `WITH` to already be consumed.
+	pub(super) async fn parse_with_stmt(&mut self, ctx: &mut Stk) -> ParseResult<WithStatement> {
+		// The names can only be referenced until the end of the statement
+		let scope = self.named_subqueries.len();
+		let res = self.parse_with_stmt_inner(ctx).await;
+		self.named_subqueries.truncate(scope);
+		res
+	}
+
+	async fn parse_with_stmt_inner(&mut self, ctx: &mut Stk) -> ParseResult<WithStatement> {
+		let mut named = Vec::new();
+		loop {
+			let name: Ident = self.next_token_value()?;
+			// A named subquery can not shadow a protected param
+			if PROTECTED_PARAM_NAMES.contains(&name.as_str()) {
+				bail!("Cannot name a subquery `{}`, as it is a protected variable", name.0, @self.last_span);
+			}
+			expected!(self, t!("AS"));
+			let start = expected!(self, t!("(")).span;
+			let expr = ctx.run(|ctx| self.parse_inner_subquery(ctx, Some(start))).await?;
+			// Each subquery can reference the subqueries which were named before it
+			self.named_subqueries.push(name.0.clone());
+			named.push((name, expr));
+			if !self.eat(t!(",")) {
+				break;
+			}
+		}
+		let before = self.peek().span;
+		let then = ctx.run(|ctx| self.parse_stmt(ctx)).await?;
+		match then {
+			Statement::Value(_)
+			| Statement::Output(_)
+			| Statement::Ifelse(_)
+			| Statement::Select(_)
+			| Statement::Create(_)
+			| Statement::Update(_)
+			| Statement::Upsert(_)
+			| Statement::Delete(_)
+			| Statement::Relate(_)
+			| Statement::Insert(_) => Ok(WithStatement {
+				named,
+				then: Box::new(then),
+			}),
+			_ => bail!(
+				"Unexpected statement, expected a query which can reference the named subqueries",
+				@before.covers(self.last_span())
+			),
+		}
+	}
+
+	/// Parsers a CHECK DATABASE statement.
+	///
+	/// # Parser State
+	/// Expects `CHECK DATABASE` to already be consumed.
+	pub(super) fn parse_check_stmt(&mut self) -> ParseResult<CheckStatement> {
+		let fix = self.eat_contextual("FIX");
+		let throttle = if self.eat_contextual("THROTTLE") {
+			Some(self.next_token_value()?)
+		} else {
+			None
+		};
+		Ok(CheckStatement {
+			fix,
+			throttle,
+		})
+	}
+
+	/// Parsers a RUN TEST or RUN TESTS statement.
+	///
+	/// # Parser State
+	/// Expects `RUN` to already be consumed.
+	pub(super) fn parse_run_stmt(&mut self) -> ParseResult<RunStatement> {
+		let test = if self.eat_contextual("TESTS") {
+			None
+		} else {
+			self.eat_contextual("TEST");
+			Some(self.next_token_value()?)
+		};
+		Ok(RunStatement {
+			test,
+		})
+	}
+
+	/// Parsers an EVICT statement.
+	///
+	/// # Parser State
+	/// Expects `EVICT FROM` to already be consumed.
+	pub(super) fn parse_evict_stmt(&mut self) -> ParseResult<EvictStatement> {
+		let what = self.next_token_value()?;
+		if !self.eat_contextual("LRU") {
+			let token = self.next();
+			unexpected!(self, token, "`LRU`");
+		}
+		if !self.eat_contextual("KEEP") {
+			let token = self.next();
+			unexpected!(self, token, "`KEEP`");
+		}
+		let token = self.next();
+		let amount: u64 =
+			match token.kind {
+				TokenKind::Digits => self.lexer.span_str(token.span).parse().map_err(
+					|e| syntax_error!("Failed to parse eviction limit: {e}", @token.span),
+				)?,
+				_ => unexpected!(self, token, "an integer"),
+			};
+		// A size unit directly following the number limits the size of the table
+		let peek = self.peek_whitespace();
+		let keep = if peek.kind == TokenKind::Identifier {
+			let unit = self.lexer.span_str(peek.span);
+			let Some(unit) = EvictLimit::unit(unit) else {
+				bail!("Invalid size unit `{unit}`, expected `B`, `KB`, `MB`, `GB`, or `TB`", @peek.span);
+			};
+			self.pop_peek();
+			let Some(bytes) = amount.checked_mul(unit) else {
+				bail!("Failed to parse eviction limit: size is too large", @token.span.covers(peek.span));
+			};
+			EvictLimit::Bytes(bytes)
+		} else {
+			EvictLimit::Records(amount)
+		};
+		Ok(EvictStatement {
+			what,
+			keep,
+		})
+	}
+
 	/// Parsers a RETURN statement.
 	///
 	/// # Parser State
@@ -736,6 +950,7 @@ impl Parser<'_> {
 	/// # Parser State
 	/// Expects `LET` to already be consumed.
 	pub(super) async fn parse_let_stmt(&mut self, ctx: &mut Stk) -> ParseResult<SetStatement> {
+		let local = self.eat_contextual("LOCAL");
 		let name = self.next_token_value::<Param>()?.0 .0;
 		let kind = if self.eat(t!(":")) {
 			Some(self.parse_inner_kind(ctx).await?)
@@ -748,6 +963,7 @@ impl Parser<'_> {
 			name,
 			what,
 			kind,
+			local,
 		})
 	}
 
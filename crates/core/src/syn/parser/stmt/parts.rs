@@ -1,6 +1,7 @@
 //! Contains parsing code for smaller common parts of statements.
 
 use reblessive::Stk;
+use std::borrow::Cow;
 
 use crate::sql::reference::{Reference, ReferenceDeleteStrategy};
 use crate::sql::Fetch;
@@ -22,6 +23,7 @@ use crate::{
 };
 
 pub(crate) enum MissingKind {
+	Distinct,
 	Split,
 	Order,
 	Group,
@@ -169,39 +171,37 @@ impl Parser<'_> {
 	) -> ParseResult<&'a Field> {
 		let mut found = None;
 		for field in fields.iter() {
-			let Field::Single {
-				expr,
-				alias,
-			} = field
-			else {
-				unreachable!()
+			let (alias, name) = match field {
+				Field::Single {
+					expr: Value::Idiom(x),
+					alias,
+				} => (alias, Cow::Borrowed(x)),
+				Field::Single {
+					expr,
+					alias,
+				} => (alias, Cow::Owned(expr.to_idiom())),
+				Field::Window {
+					window,
+					alias,
+				} => (alias, Cow::Owned(window.to_idiom())),
+				Field::All => unreachable!(),
 			};
 
-			if let Some(alias) = alias {
-				if idiom == alias {
-					found = Some(field);
-					break;
-				}
-			}
-
-			match expr {
-				Value::Idiom(x) => {
-					if idiom == x {
-						found = Some(field);
-						break;
-					}
-				}
-				v => {
-					if *idiom == v.to_idiom() {
-						found = Some(field);
-						break;
-					}
-				}
+			if alias.as_ref() == Some(idiom) || *idiom == *name {
+				found = Some(field);
+				break;
 			}
 		}
 
 		let Some(found) = found else {
 			match kind {
+				MissingKind::Distinct => {
+					bail!(
+						"Missing distinct idiom `{idiom}` in statement selection",
+						@idiom_span,
+						@field_span => "Idiom missing here",
+					)
+				}
 				MissingKind::Split => {
 					bail!(
 						"Missing split idiom `{idiom}` in statement selection",
@@ -6,16 +6,16 @@ use crate::{
 			remove::RemoveAnalyzerStatement, RemoveAccessStatement, RemoveDatabaseStatement,
 			RemoveEventStatement, RemoveFieldStatement, RemoveFunctionStatement,
 			RemoveIndexStatement, RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement,
-			RemoveUserStatement,
+			RemoveTestStatement, RemoveUserStatement,
 		},
-		Param,
+		Ident, Param,
 	},
 	syn::{
 		parser::{
 			mac::{expected, unexpected},
 			ParseResult, Parser,
 		},
-		token::t,
+		token::{t, TokenKind},
 	},
 };
 
@@ -134,11 +134,13 @@ impl Parser<'_> {
 				};
 
 				let name = self.next_token_value()?;
+				let safe = self.eat_contextual("SAFE");
 
 				RemoveStatement::Table(crate::sql::statements::RemoveTableStatement {
 					name,
 					if_exists,
 					expunge,
+					safe,
 				})
 			}
 			t!("EVENT") => {
@@ -149,14 +151,24 @@ impl Parser<'_> {
 					false
 				};
 				let name = self.next_token_value()?;
-				expected!(self, t!("ON"));
-				self.eat(t!("TABLE"));
-				let table = self.next_token_value()?;
+				let database = self.eat(t!("FOR"));
+				let table = match database {
+					true => {
+						expected!(self, t!("DATABASE"));
+						Ident::default()
+					}
+					false => {
+						expected!(self, t!("ON"));
+						self.eat(t!("TABLE"));
+						self.next_token_value()?
+					}
+				};
 
 				RemoveStatement::Event(RemoveEventStatement {
 					name,
 					what: table,
 					if_exists,
+					database,
 				})
 			}
 			t!("FIELD") => {
@@ -226,6 +238,23 @@ impl Parser<'_> {
 					if_exists,
 				})
 			}
+			// `TEST` is a contextual keyword, so it can still be used as an identifier elsewhere.
+			TokenKind::Identifier
+				if self.lexer.span_str(next.span).eq_ignore_ascii_case("TEST") =>
+			{
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = self.next_token_value()?;
+
+				RemoveStatement::Test(RemoveTestStatement {
+					name,
+					if_exists,
+				})
+			}
 			// TODO(raphaeldarley): add Config here
 			_ => unexpected!(self, next, "a remove statement keyword"),
 		};
@@ -4,10 +4,11 @@ use crate::{
 	sql::{
 		order::{OrderList, Ordering},
 		statements::SelectStatement,
-		Explain, Field, Fields, Ident, Idioms, Limit, Order, Split, Splits, Start, Values, Version,
-		With,
+		Cond, Distinct, Explain, Field, Fields, Ident, Idioms, Join, JoinKind, Limit, Order,
+		SetOperator, Split, Splits, Start, Values, Version, Window, WindowFunction, With,
 	},
 	syn::{
+		error::bail,
 		parser::{
 			mac::{expected, unexpected},
 			ParseResult, Parser,
@@ -23,9 +24,71 @@ impl Parser<'_> {
 		&mut self,
 		stk: &mut Stk,
 	) -> ParseResult<SelectStatement> {
+		let mut stmt = self.parse_single_select_stmt(stk).await?;
+		// Parse any statements which the results are combined with
+		let mut only = stmt.only;
+		while let Some(op) = self.try_parse_set_operator() {
+			let span = self.last_span();
+			let last = stmt.combine.last().map(|(_, v)| v).unwrap_or(&stmt);
+			if last.order.is_some() || last.start.is_some() || last.limit.is_some() {
+				bail!("Unexpected token `{op}`, ORDER BY, START and LIMIT apply to the combined results, so can only follow the last statement", @span);
+			}
+			expected!(self, t!("SELECT"));
+			let other = stk.run(|stk| self.parse_single_select_stmt(stk)).await?;
+			only |= other.only;
+			if only {
+				bail!("Unexpected token `{op}`, the results of ONLY can not be combined with other results", @span);
+			}
+			stmt.combine.push((op, other));
+		}
+		// The ORDER BY, START and LIMIT clauses of the last statement apply to the combined results
+		if let Some((_, last)) = stmt.combine.last_mut() {
+			stmt.order = last.order.take();
+			stmt.start = last.start.take();
+			stmt.limit = last.limit.take();
+		}
+		Ok(stmt)
+	}
+
+	fn try_parse_set_operator(&mut self) -> Option<SetOperator> {
+		let op = match self.peek_kind() {
+			t!("UNION") => SetOperator::Union,
+			t!("INTERSECT") => SetOperator::Intersect,
+			t!("EXCEPT") => SetOperator::Except,
+			_ => return None,
+		};
+		self.pop_peek();
+		if !self.eat(t!("ALL")) {
+			return Some(op);
+		}
+		Some(match op {
+			SetOperator::Union => SetOperator::UnionAll,
+			SetOperator::Intersect => SetOperator::IntersectAll,
+			_ => SetOperator::ExceptAll,
+		})
+	}
+
+	async fn parse_single_select_stmt(&mut self, stk: &mut Stk) -> ParseResult<SelectStatement> {
+		let before = self.peek().span;
+		let distinct = self.try_parse_distinct(stk).await?;
+		let distinct_span = before.covers(self.last_span());
+
 		let before = self.peek().span;
-		let expr = self.parse_fields(stk).await?;
+		let expr = self.parse_fields_with_windows(stk, true).await?;
 		let fields_span = before.covers(self.last_span());
+		if let Some(Distinct::On(idioms)) = &distinct {
+			if !expr.contains(&Field::All) {
+				for idiom in idioms {
+					Self::check_idiom(
+						MissingKind::Distinct,
+						&expr,
+						fields_span,
+						idiom,
+						distinct_span,
+					)?;
+				}
+			}
+		}
 
 		let omit = if self.eat(t!("OMIT")) {
 			Some(Idioms(self.parse_idiom_list(stk).await?))
@@ -43,10 +106,21 @@ impl Parser<'_> {
 		}
 		let what = Values(what);
 
+		let before = self.peek().span;
+		let join = self.try_parse_joins(stk).await?;
+		if !join.is_empty() && what.len() != 1 {
+			bail!("Unexpected token `JOIN`, only a single table can be joined with other tables",
+				@before.covers(self.last_span()));
+		}
+
 		let with = self.try_parse_with()?;
 		let cond = self.try_parse_condition(stk).await?;
 		let split = self.try_parse_split(stk, &expr, fields_span).await?;
 		let group = self.try_parse_group(stk, &expr, fields_span).await?;
+		if group.is_some() && expr.has_windows() {
+			bail!("Window functions can not be used with GROUP BY", @fields_span);
+		}
+		let having = self.try_parse_having(stk, group.is_some()).await?;
 		let order = self.try_parse_orders(stk, &expr, fields_span).await?;
 		let (limit, start) = if let t!("START") = self.peek_kind() {
 			let start = self.try_parse_start(stk).await?;
@@ -65,14 +139,17 @@ impl Parser<'_> {
 		let explain = self.eat(t!("EXPLAIN")).then(|| Explain(self.eat(t!("FULL"))));
 
 		Ok(SelectStatement {
+			distinct,
 			expr,
 			omit,
 			only,
 			what,
+			join,
 			with,
 			cond,
 			split,
 			group,
+			having,
 			order,
 			limit,
 			start,
@@ -82,17 +159,48 @@ impl Parser<'_> {
 			parallel,
 			tempfiles,
 			explain,
+			combine: Vec::new(),
 		})
 	}
 
+	async fn try_parse_distinct(&mut self, stk: &mut Stk) -> ParseResult<Option<Distinct>> {
+		if self.peek_kind() != t!("DISTINCT") {
+			return Ok(None);
+		}
+		// This is a field named distinct, rather than a DISTINCT clause
+		if let t!("FROM") | t!(",") | t!(".") | t!("AS") | t!("OMIT") = self.peek1().kind {
+			return Ok(None);
+		}
+		self.pop_peek();
+		if !self.eat(t!("ON")) {
+			return Ok(Some(Distinct::Rows));
+		}
+		let start = expected!(self, t!("(")).span;
+		let mut idioms = vec![self.parse_basic_idiom(stk).await?];
+		while self.eat(t!(",")) {
+			idioms.push(self.parse_basic_idiom(stk).await?);
+		}
+		self.expect_closing_delimiter(t!(")"), start)?;
+		Ok(Some(Distinct::On(idioms)))
+	}
+
 	fn try_parse_with(&mut self) -> ParseResult<Option<With>> {
 		if !self.eat(t!("WITH")) {
 			return Ok(None);
 		}
+		if self.eat_contextual("NOJOIN") {
+			return Ok(Some(With::NoJoin));
+		}
+		if self.eat_contextual("BRUTEFORCE") {
+			return Ok(Some(With::BruteForce));
+		}
 		let next = self.next();
 		let with = match next.kind {
 			t!("NOINDEX") => With::NoIndex,
 			t!("NO") => {
+				if self.eat_contextual("JOIN") {
+					return Ok(Some(With::NoJoin));
+				}
 				expected!(self, t!("INDEX"));
 				With::NoIndex
 			}
@@ -103,11 +211,64 @@ impl Parser<'_> {
 				}
 				With::Index(index)
 			}
-			_ => unexpected!(self, next, "`NO`, `NOINDEX` or `INDEX`"),
+			_ => unexpected!(self, next, "`NO`, `NOINDEX`, `NOJOIN`, `BRUTEFORCE` or `INDEX`"),
 		};
 		Ok(Some(with))
 	}
 
+	async fn try_parse_joins(&mut self, stk: &mut Stk) -> ParseResult<Vec<Join>> {
+		let mut joins = Vec::new();
+		loop {
+			let kind = if self.eat_contextual("JOIN") {
+				JoinKind::Inner
+			} else {
+				let kind = if self.eat_contextual("LEFT") {
+					JoinKind::Left
+				} else if self.eat_contextual("INNER") {
+					JoinKind::Inner
+				} else {
+					break;
+				};
+				if !self.eat_contextual("JOIN") {
+					let next = self.next();
+					unexpected!(self, next, "`JOIN`");
+				}
+				kind
+			};
+			let what = self.next_token_value()?;
+			let alias = if self.eat(t!("AS")) {
+				Some(self.next_token_value::<Ident>()?)
+			} else {
+				None
+			};
+			expected!(self, t!("ON"));
+			let cond = stk.run(|stk| self.parse_value_field(stk)).await?;
+			joins.push(Join {
+				kind,
+				what,
+				alias,
+				cond,
+			});
+		}
+		Ok(joins)
+	}
+
+	async fn try_parse_having(
+		&mut self,
+		stk: &mut Stk,
+		grouped: bool,
+	) -> ParseResult<Option<Cond>> {
+		if !self.eat(t!("HAVING")) {
+			return Ok(None);
+		}
+		if !grouped {
+			bail!("Unexpected token `HAVING`, the results can only be filtered with HAVING when they are grouped",
+				@self.last_span() => "add a GROUP BY clause, or use WHERE instead");
+		}
+		let v = stk.run(|stk| self.parse_value_field(stk)).await?;
+		Ok(Some(Cond(v)))
+	}
+
 	pub(crate) async fn try_parse_split(
 		&mut self,
 		ctx: &mut Stk,
@@ -190,6 +351,37 @@ impl Parser<'_> {
 		Ok(Some(Ordering::Order(OrderList(orders))))
 	}
 
+	/// Parses the window of a window function: `(PARTITION BY a ORDER BY b)` in
+	/// `rank() OVER (PARTITION BY a ORDER BY b)`
+	///
+	/// # Parser State
+	/// Expects the parser to have just eaten the `OVER` keyword.
+	pub(crate) async fn parse_window(
+		&mut self,
+		ctx: &mut Stk,
+		func: WindowFunction,
+	) -> ParseResult<Window> {
+		let start = expected!(self, t!("(")).span;
+		let mut window = Window {
+			func,
+			..Default::default()
+		};
+		if self.eat_contextual("PARTITION") {
+			self.eat(t!("BY"));
+			window.partition = self.parse_idiom_list(ctx).await?;
+		}
+		if self.eat(t!("ORDER")) {
+			self.eat(t!("BY"));
+			let mut orders = vec![self.parse_order(ctx).await?];
+			while self.eat(t!(",")) {
+				orders.push(self.parse_order(ctx).await?);
+			}
+			window.order = Some(OrderList(orders));
+		}
+		self.expect_closing_delimiter(t!(")"), start)?;
+		Ok(window)
+	}
+
 	async fn parse_order(&mut self, ctx: &mut Stk) -> ParseResult<Order> {
 		let start = self.parse_basic_idiom(ctx).await?;
 		let collate = self.eat(t!("COLLATE"));
@@ -17,28 +17,31 @@ use crate::{
 				AccessStatementShow,
 			},
 			analyze::AnalyzeStatement,
+			define::{DatabaseEvent, TableEvent},
 			show::{ShowSince, ShowStatement},
 			sleep::SleepStatement,
-			AccessStatement, BeginStatement, BreakStatement, CancelStatement, CommitStatement,
-			ContinueStatement, CreateStatement, DefineAccessStatement, DefineAnalyzerStatement,
-			DefineDatabaseStatement, DefineEventStatement, DefineFieldStatement,
-			DefineFunctionStatement, DefineIndexStatement, DefineNamespaceStatement,
-			DefineParamStatement, DefineStatement, DefineTableStatement, DeleteStatement,
-			ForeachStatement, IfelseStatement, InfoStatement, InsertStatement, KillStatement,
-			OptionStatement, OutputStatement, RelateStatement, RemoveAccessStatement,
+			AccessStatement, BeginStatement, BreakStatement, CancelStatement, CheckStatement,
+			CommitStatement, ContinueStatement, CreateStatement, DefineAccessStatement,
+			DefineAnalyzerStatement, DefineDatabaseStatement, DefineEventStatement,
+			DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
+			DefineNamespaceStatement, DefineParamStatement, DefineStatement, DefineTableStatement,
+			DefineTestStatement, DeleteStatement, EvictLimit, EvictStatement, ForeachStatement,
+			IfelseStatement, InfoStatement, InsertStatement, KillStatement, OptionStatement,
+			OutputStatement, ReferencesStatement, RelateStatement, RemoveAccessStatement,
 			RemoveAnalyzerStatement, RemoveDatabaseStatement, RemoveEventStatement,
 			RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement,
 			RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement, RemoveTableStatement,
-			RemoveUserStatement, SelectStatement, SetStatement, ThrowStatement, UpdateStatement,
-			UpsertStatement, UseStatement,
+			RemoveTestStatement, RemoveUserStatement, RunStatement, SelectStatement, SetStatement,
+			ThrowStatement, UpdateStatement, UpsertStatement, UseStatement,
 		},
 		tokenizer::Tokenizer,
 		user::UserDuration,
-		Algorithm, Array, Base, Block, Cond, Data, Datetime, Dir, Duration, Edges, Explain,
-		Expression, Fetch, Fetchs, Field, Fields, Future, Graph, Group, Groups, Id, Ident, Idiom,
-		Idioms, Index, Kind, Limit, Number, Object, Operator, Order, Output, Param, Part,
-		Permission, Permissions, Scoring, Split, Splits, Start, Statement, Strand, Subquery, Table,
-		TableType, Tables, Thing, Timeout, Uuid, Value, Values, Version, With,
+		Algorithm, Array, Base, Block, Cond, Data, Datetime, Dir, Distinct, Duration, Edges,
+		Explain, Expression, Fetch, Fetchs, Field, Fields, Future, Graph, Group, Groups, Id, Ident,
+		Idiom, Idioms, Index, JoinKind, Kind, Limit, Number, Object, Operator, Order, Output,
+		Param, Part, Permission, Permissions, Scoring, SetOperator, Split, Splits, Start,
+		Statement, Strand, Subquery, Table, TableType, Tables, Thing, Timeout, Uuid, Value, Values,
+		Version, With,
 	},
 	syn::parser::{
 		mac::{test_parse, test_parse_with_settings},
@@ -63,9 +66,20 @@ pub fn parse_analyze() {
 #[test]
 pub fn parse_begin() {
 	let res = test_parse!(parse_stmt, r#"BEGIN"#).unwrap();
-	assert_eq!(res, Statement::Begin(BeginStatement));
+	assert_eq!(res, Statement::Begin(BeginStatement::default()));
 	let res = test_parse!(parse_stmt, r#"BEGIN TRANSACTION"#).unwrap();
-	assert_eq!(res, Statement::Begin(BeginStatement));
+	assert_eq!(res, Statement::Begin(BeginStatement::default()));
+	let res = test_parse!(parse_stmt, r#"BEGIN READONLY AT d"2024-01-01T00:00:00Z""#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Begin(BeginStatement {
+			readonly: true,
+			at: Some(Version(Value::Datetime(Datetime(
+				Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+			)))),
+		})
+	);
+	assert_eq!(res.to_string(), "BEGIN TRANSACTION READONLY AT d'2024-01-01T00:00:00Z'");
 }
 
 #[test]
@@ -223,6 +237,53 @@ fn parse_define_function() {
 			if_not_exists: false,
 			overwrite: false,
 			returns: None,
+			defaults: Vec::new(),
+		}))
+	)
+}
+
+#[test]
+fn parse_define_function_defaults() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE FUNCTION fn::foo($a: int, $b: option<int> = 10, $c: option<string>) { RETURN $a }"#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Function(stmt)) = res else {
+		panic!()
+	};
+	assert_eq!(stmt.args.len(), 3);
+	assert_eq!(stmt.defaults, vec![(Ident("b".to_string()), Value::Number(Number::Int(10)))]);
+	assert_eq!(
+		stmt.to_string(),
+		"DEFINE FUNCTION fn::foo($a: int, $b: option<int> = 10, $c: option<string>) { RETURN $a; } PERMISSIONS FULL"
+	);
+
+	crate::syn::parse("DEFINE FUNCTION fn::foo($a: int = 1, $b: int) { RETURN $a }").unwrap_err();
+}
+
+#[test]
+fn parse_define_test() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE TEST IF NOT EXISTS foo {
+			RETURN a
+		} COMMENT 'test'
+		"#
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Test(DefineTestStatement {
+			name: Ident("foo".to_string()),
+			block: Block(vec![Entry::Output(OutputStatement {
+				what: ident_field("a"),
+				fetch: None,
+			})]),
+			comment: Some(Strand("test".to_string())),
+			if_not_exists: true,
+			overwrite: false,
 		}))
 	)
 }
@@ -1862,8 +1923,46 @@ fn parse_define_table() {
 			cache_events_ts: uuid::Uuid::default(),
 			cache_tables_ts: uuid::Uuid::default(),
 			cache_indexes_ts: uuid::Uuid::default(),
+			like: None,
+			cache: false,
+		}))
+	);
+}
+
+#[test]
+fn parse_define_table_cache() {
+	let res = test_parse!(parse_stmt, r#"DEFINE TABLE session SCHEMALESS CACHE"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Table(DefineTableStatement {
+			name: Ident("session".to_string()),
+			permissions: Permissions::none(),
+			cache: true,
+			..Default::default()
+		}))
+	);
+	assert_eq!(res.to_string(), "DEFINE TABLE session TYPE ANY SCHEMALESS CACHE PERMISSIONS NONE");
+}
+
+#[test]
+fn parse_define_table_like() {
+	let res = test_parse!(parse_stmt, r#"DEFINE TABLE tenant_orders LIKE orders COMMENT "test""#)
+		.unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Table(DefineTableStatement {
+			name: Ident("tenant_orders".to_string()),
+			permissions: Permissions::none(),
+			comment: Some(Strand("test".to_string())),
+			like: Some(Ident("orders".to_string())),
+			..Default::default()
 		}))
 	);
+
+	test_parse!(parse_stmt, r#"DEFINE TABLE tenant_orders SCHEMAFULL LIKE orders"#).unwrap_err();
+	test_parse!(parse_stmt, r#"DEFINE TABLE tenant_orders CACHE LIKE orders"#).unwrap_err();
+	crate::syn::parse("DEFINE TABLE tenant_orders LIKE orders SCHEMAFULL").unwrap_err();
 }
 
 #[test]
@@ -1882,10 +1981,105 @@ fn parse_define_event() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
+			database: None,
+			asynchronous: false,
+			retry: None,
+			backoff: None,
+			before: Vec::new(),
 		}))
 	)
 }
 
+#[test]
+fn parse_define_event_async() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE EVENT notify ON TABLE user WHEN $event = "CREATE" THEN http::post('https://example.com', $after) ASYNC RETRY 3 BACKOFF 5s"#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Event(stmt)) = res else {
+		panic!()
+	};
+	assert!(stmt.asynchronous);
+	assert_eq!(stmt.retry, Some(3));
+	assert_eq!(stmt.backoff, Some(Duration(std::time::Duration::from_secs(5))));
+	assert_eq!(
+		stmt.to_string(),
+		"DEFINE EVENT notify ON user WHEN $event = 'CREATE' THEN http::post('https://example.com', $after) ASYNC RETRY 3 BACKOFF 5s"
+	);
+
+	crate::syn::parse("DEFINE EVENT notify ON TABLE user THEN null RETRY 3").unwrap_err();
+	crate::syn::parse("DEFINE EVENT audit FOR DATABASE WHEN table_created THEN null ASYNC")
+		.unwrap_err();
+}
+
+#[test]
+fn parse_define_event_before() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE EVENT clean ON TABLE user WHEN BEFORE CREATE, UPDATE THEN { $after.{ name: string::trim(name) } }"#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Event(stmt)) = res else {
+		panic!()
+	};
+	assert_eq!(stmt.before, vec![TableEvent::Create, TableEvent::Update]);
+	assert_eq!(
+		stmt.to_string(),
+		"DEFINE EVENT clean ON user WHEN BEFORE CREATE, UPDATE THEN { $after.{ name: string::trim(name) } }"
+	);
+
+	let res = test_parse!(parse_stmt, r#"DEFINE EVENT audit ON TABLE user WHEN before THEN null"#)
+		.unwrap();
+	let Statement::Define(DefineStatement::Event(stmt)) = res else {
+		panic!()
+	};
+	assert!(stmt.before.is_empty());
+
+	crate::syn::parse("DEFINE EVENT clean ON TABLE user WHEN BEFORE SELECT THEN null").unwrap_err();
+	crate::syn::parse("DEFINE EVENT clean ON TABLE user WHEN BEFORE DELETE THEN null ASYNC")
+		.unwrap_err();
+	crate::syn::parse(
+		"DEFINE EVENT clean ON TABLE user WHEN BEFORE CREATE WHEN $after.a THEN null",
+	)
+	.unwrap_err();
+	crate::syn::parse(
+		"DEFINE EVENT clean ON TABLE user WHEN $after.a WHEN BEFORE CREATE THEN null",
+	)
+	.unwrap_err();
+}
+
+#[test]
+fn parse_define_event_on_database() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE EVENT audit FOR DATABASE WHEN table_created THEN (CREATE log SET table = $table)"#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Event(stmt)) = res else {
+		panic!()
+	};
+	assert_eq!(stmt.name, Ident("audit".to_owned()));
+	assert_eq!(stmt.database, Some(DatabaseEvent::TableCreated));
+	assert_eq!(
+		stmt.to_string(),
+		"DEFINE EVENT audit FOR DATABASE WHEN table_created THEN (CREATE log SET table = $table)"
+	);
+
+	test_parse!(parse_stmt, r#"DEFINE EVENT audit FOR DATABASE WHEN table_removed THEN null"#)
+		.unwrap_err();
+	test_parse!(parse_stmt, r#"DEFINE EVENT audit FOR DATABASE THEN null"#).unwrap_err();
+
+	// A table named `database` is still a table
+	let res =
+		test_parse!(parse_stmt, r#"DEFINE EVENT audit ON database WHEN true THEN null"#).unwrap();
+	let Statement::Define(DefineStatement::Event(stmt)) = res else {
+		panic!()
+	};
+	assert_eq!(stmt.what, Ident("database".to_owned()));
+	assert_eq!(stmt.database, None);
+}
+
 #[test]
 fn parse_define_field() {
 	// General
@@ -2030,6 +2224,23 @@ fn parse_define_index() {
 		}))
 	);
 
+	let res =
+		test_parse!(parse_stmt, r#"DEFINE INDEX index ON TABLE table FIELDS a TRIGRAM"#).unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Index(DefineIndexStatement {
+			name: Ident("index".to_owned()),
+			what: Ident("table".to_owned()),
+			cols: Idioms(vec![Idiom(vec![Part::Field(Ident("a".to_owned()))]),]),
+			index: Index::Trigram,
+			comment: None,
+			if_not_exists: false,
+			overwrite: false,
+			concurrently: false
+		}))
+	);
+
 	let res =
 		test_parse!(parse_stmt, r#"DEFINE INDEX index ON TABLE table FIELDS a MTREE DIMENSION 4 DISTANCE MINKOWSKI 5 CAPACITY 6 TYPE I16 DOC_IDS_ORDER 7 DOC_IDS_CACHE 8 MTREE_CACHE 9"#).unwrap();
 
@@ -2197,7 +2408,32 @@ pub fn parse_for() {
 				o: Operator::Mul,
 				r: Value::Number(Number::Int(2))
 			})),
-			block: Block(vec![Entry::Break(BreakStatement)])
+			block: Block(vec![Entry::Break(BreakStatement)]),
+			batch: None,
+			each: false,
+		})
+	);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"FOR $batch IN (SELECT * FROM bar) BATCH 1000 COMMIT EACH {
+			BREAK
+		}"#
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Foreach(ForeachStatement {
+			param: Param(Ident("batch".to_owned())),
+			range: Value::Subquery(Box::new(Subquery::Select(SelectStatement {
+				expr: Fields(vec![Field::All], false),
+				what: Values(vec![Value::Table(Table("bar".to_owned()))]),
+				..Default::default()
+			}))),
+			block: Block(vec![Entry::Break(BreakStatement)]),
+			batch: Some(Value::Number(Number::Int(1000))),
+			each: true,
 		})
 	)
 }
@@ -2254,6 +2490,9 @@ fn parse_info() {
 	let res = test_parse!(parse_stmt, "INFO FOR NS").unwrap();
 	assert_eq!(res, Statement::Info(InfoStatement::Ns(false)));
 
+	let res = test_parse!(parse_stmt, "INFO FOR DB GRAPH").unwrap();
+	assert_eq!(res, Statement::Info(InfoStatement::Graph));
+
 	let res = test_parse!(parse_stmt, "INFO FOR TABLE table").unwrap();
 	assert_eq!(res, Statement::Info(InfoStatement::Tb(Ident("table".to_owned()), false, None)));
 
@@ -2302,6 +2541,7 @@ SELECT bar as foo,[1,2],bar OMIT bar FROM ONLY a,1
 	assert_eq!(
 		res,
 		Statement::Select(SelectStatement {
+			distinct: None,
 			expr: Fields(
 				vec![
 					Field::Single {
@@ -2325,6 +2565,7 @@ SELECT bar as foo,[1,2],bar OMIT bar FROM ONLY a,1
 			omit: Some(Idioms(vec![Idiom(vec![Part::Field(Ident("bar".to_owned()))])])),
 			only: true,
 			what: Values(vec![Value::Table(Table("a".to_owned())), Value::Number(Number::Int(1))]),
+			join: Vec::new(),
 			with: Some(With::Index(vec!["index".to_owned(), "index_2".to_owned()])),
 			cond: Some(Cond(Value::Bool(true))),
 			split: Some(Splits(vec![
@@ -2335,6 +2576,7 @@ SELECT bar as foo,[1,2],bar OMIT bar FROM ONLY a,1
 				Group(Idiom(vec![Part::Field(Ident("foo".to_owned()))])),
 				Group(Idiom(vec![Part::Field(Ident("bar".to_owned()))])),
 			])),
+			having: None,
 			order: Some(Ordering::Order(OrderList(vec![Order {
 				value: Idiom(vec![Part::Field(Ident("foo".to_owned()))]),
 				collate: true,
@@ -2356,10 +2598,125 @@ SELECT bar as foo,[1,2],bar OMIT bar FROM ONLY a,1
 			parallel: false,
 			tempfiles: false,
 			explain: Some(Explain(true)),
+			combine: Vec::new(),
 		}),
 	);
 }
 
+#[test]
+fn parse_select_with_hints() {
+	for (src, with) in [
+		("SELECT * FROM a WITH NOJOIN", With::NoJoin),
+		("SELECT * FROM a WITH NO JOIN", With::NoJoin),
+		("SELECT * FROM a WITH BRUTEFORCE", With::BruteForce),
+		("SELECT * FROM a WITH NO INDEX", With::NoIndex),
+	] {
+		let Statement::Select(res) = test_parse!(parse_stmt, src).unwrap() else {
+			panic!("expected a select statement for `{src}`")
+		};
+		assert_eq!(res.with, Some(with));
+	}
+}
+
+#[test]
+fn parse_select_having() {
+	let Statement::Select(res) = test_parse!(
+		parse_stmt,
+		"SELECT country, count() FROM user GROUP BY country HAVING count > 100"
+	)
+	.unwrap() else {
+		panic!("expected a select statement")
+	};
+	assert_eq!(
+		res.having,
+		Some(Cond(Value::Expression(Box::new(Expression::Binary {
+			l: Value::Idiom(Idiom(vec![Part::Field(Ident("count".to_owned()))])),
+			o: Operator::MoreThan,
+			r: Value::Number(Number::Int(100)),
+		}))))
+	);
+	assert_eq!(
+		res.to_string(),
+		"SELECT country, count() FROM user GROUP BY country HAVING count > 100"
+	);
+	// The results can only be filtered with HAVING when they are grouped
+	test_parse!(parse_stmt, "SELECT * FROM user HAVING count > 100").unwrap_err();
+}
+
+#[test]
+fn parse_select_distinct() {
+	for (src, distinct) in [
+		("SELECT DISTINCT country FROM user", Some(Distinct::Rows)),
+		(
+			"SELECT DISTINCT ON (country) * FROM user",
+			Some(Distinct::On(vec![Idiom(vec![Part::Field(Ident("country".to_owned()))])])),
+		),
+		("SELECT `distinct` FROM user", None),
+	] {
+		let Statement::Select(res) = test_parse!(parse_stmt, src).unwrap() else {
+			panic!("expected a select statement for `{src}`")
+		};
+		assert_eq!(res.distinct, distinct);
+		assert_eq!(res.to_string(), src);
+	}
+	// This is a field named distinct, rather than a DISTINCT clause
+	let Statement::Select(res) = test_parse!(parse_stmt, "SELECT distinct FROM user").unwrap()
+	else {
+		panic!("expected a select statement")
+	};
+	assert_eq!(res.distinct, None);
+	// The DISTINCT ON fields must be selected
+	test_parse!(parse_stmt, "SELECT DISTINCT ON (country) name FROM user").unwrap_err();
+}
+
+#[test]
+fn parse_select_set_operators() {
+	let src =
+		"SELECT name FROM user UNION ALL SELECT name FROM admin EXCEPT SELECT name FROM banned";
+	let Statement::Select(res) = test_parse!(parse_stmt, src).unwrap() else {
+		panic!("expected a select statement")
+	};
+	assert_eq!(
+		res.combine.iter().map(|(op, _)| *op).collect::<Vec<_>>(),
+		vec![SetOperator::UnionAll, SetOperator::Except]
+	);
+	assert_eq!(res.combine[1].1.what, Values(vec![Value::Table(Table("banned".to_owned()))]));
+	assert_eq!(res.to_string(), src);
+	// The LIMIT and START clauses of the last statement apply to the combined results
+	let src = "SELECT name FROM user UNION SELECT name FROM admin LIMIT 2 START 1";
+	let Statement::Select(res) = test_parse!(parse_stmt, src).unwrap() else {
+		panic!("expected a select statement")
+	};
+	assert_eq!(res.limit, Some(Limit(Value::from(2))));
+	assert_eq!(res.start, Some(Start(Value::from(1))));
+	assert_eq!(res.combine[0].1.limit, None);
+	assert_eq!(res.to_string(), src);
+	// They can not be given for any other statement
+	test_parse!(parse_stmt, "SELECT name FROM user LIMIT 1 UNION SELECT name FROM admin")
+		.unwrap_err();
+	// The results of ONLY are not an array, so can not be combined
+	test_parse!(parse_stmt, "SELECT * FROM ONLY user:one INTERSECT SELECT * FROM user")
+		.unwrap_err();
+}
+
+#[test]
+fn parse_select_join() {
+	let src = "SELECT * FROM user JOIN post ON post.author = user.id LEFT JOIN comment AS c ON c.post = post.id WHERE user.active";
+	let Statement::Select(res) = test_parse!(parse_stmt, src).unwrap() else {
+		panic!("expected a select statement")
+	};
+	assert_eq!(
+		res.join.iter().map(|v| (v.kind, v.name().to_owned())).collect::<Vec<_>>(),
+		vec![(JoinKind::Inner, "post".to_owned()), (JoinKind::Left, "c".to_owned())]
+	);
+	assert_eq!(res.to_string(), src);
+	let res = test_parse!(parse_stmt, "SELECT * FROM user INNER JOIN post ON true").unwrap();
+	assert_eq!(res.to_string(), "SELECT * FROM user JOIN post ON true");
+	// Only a single table can be joined with other tables
+	test_parse!(parse_stmt, "SELECT * FROM user, admin JOIN post ON true").unwrap_err();
+	test_parse!(parse_stmt, "SELECT * FROM user LEFT post ON true").unwrap_err();
+}
+
 #[test]
 fn parse_let() {
 	let res = test_parse!(parse_stmt, r#"LET $param = 1"#).unwrap();
@@ -2369,6 +2726,7 @@ fn parse_let() {
 			name: "param".to_owned(),
 			what: Value::Number(Number::Int(1)),
 			kind: None,
+			local: false,
 		})
 	);
 
@@ -2379,6 +2737,18 @@ fn parse_let() {
 			name: "param".to_owned(),
 			what: Value::Number(Number::Int(1)),
 			kind: None,
+			local: false,
+		})
+	);
+
+	let res = test_parse!(parse_stmt, r#"LET LOCAL $param = 1"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Set(SetStatement {
+			name: "param".to_owned(),
+			what: Value::Number(Number::Int(1)),
+			kind: None,
+			local: true,
 		})
 	);
 }
@@ -2423,6 +2793,112 @@ fn parse_show() {
 	)
 }
 
+#[test]
+fn parse_what_references() {
+	let res = test_parse!(parse_stmt, r#"WHAT REFERENCES orders"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::References(ReferencesStatement {
+			what: Ident("orders".to_owned()),
+		})
+	);
+
+	// `WHAT` is only a keyword when followed by `REFERENCES`
+	let res = test_parse!(parse_stmt, r#"what"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Value(Value::Idiom(Idiom(vec![Part::Field(Ident("what".to_owned()))])))
+	);
+}
+
+#[test]
+fn parse_check() {
+	let res = test_parse!(parse_stmt, r#"CHECK DATABASE"#).unwrap();
+	assert_eq!(res, Statement::Check(CheckStatement::default()));
+
+	let res = test_parse!(parse_stmt, r#"CHECK DATABASE FIX THROTTLE 10ms"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Check(CheckStatement {
+			fix: true,
+			throttle: Some(Duration(std::time::Duration::from_millis(10))),
+		})
+	);
+}
+
+#[test]
+fn parse_run() {
+	let res = test_parse!(parse_stmt, r#"RUN TESTS"#).unwrap();
+	assert_eq!(res, Statement::Run(RunStatement::default()));
+
+	let res = test_parse!(parse_stmt, r#"RUN TEST foo"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Run(RunStatement {
+			test: Some(Ident("foo".to_owned())),
+		})
+	);
+
+	// `RUN` is only a keyword when followed by `TEST` or `TESTS`
+	let res = test_parse!(parse_stmt, r#"run"#).unwrap();
+	assert_eq!(res, Statement::Value(ident_field("run")));
+}
+
+#[test]
+fn parse_evict() {
+	let res = test_parse!(parse_stmt, r#"EVICT FROM session LRU KEEP 1000"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Evict(EvictStatement {
+			what: Ident("session".to_owned()),
+			keep: EvictLimit::Records(1000),
+		})
+	);
+
+	let res = test_parse!(parse_stmt, r#"EVICT FROM session LRU KEEP 1GB"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Evict(EvictStatement {
+			what: Ident("session".to_owned()),
+			keep: EvictLimit::Bytes(1024 * 1024 * 1024),
+		})
+	);
+	assert_eq!(res.to_string(), "EVICT FROM session LRU KEEP 1GB");
+
+	test_parse!(parse_stmt, r#"EVICT FROM session LRU KEEP 1PB"#).unwrap_err();
+	test_parse!(parse_stmt, r#"EVICT FROM session KEEP 10"#).unwrap_err();
+}
+
+#[test]
+fn parse_with() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"WITH recent AS (SELECT * FROM post), top AS (SELECT * FROM recent LIMIT 1) SELECT * FROM top, post WHERE id IN recent.id"#
+	)
+	.unwrap();
+	let Statement::With(stmt) = &res else {
+		panic!("not a WITH statement: {res:?}");
+	};
+	assert_eq!(stmt.named.len(), 2);
+	assert_eq!(
+		stmt.named[1].1,
+		Subquery::Select(SelectStatement {
+			expr: Fields::all(),
+			what: Values(vec![Value::Param(Param(Ident("recent".to_owned())))]),
+			limit: Some(Limit(Value::Number(Number::Int(1)))),
+			..Default::default()
+		})
+	);
+	// The named subqueries are referenced as parameters
+	assert_eq!(
+		res.to_string(),
+		"WITH recent AS (SELECT * FROM post), top AS (SELECT * FROM $recent LIMIT 1) SELECT * FROM $top, post WHERE id INSIDE $recent.id"
+	);
+
+	// Statements which can not reference the named subqueries are not allowed
+	test_parse!(parse_stmt, r#"WITH recent AS (SELECT * FROM post) LET $x = recent"#).unwrap_err();
+}
+
 #[test]
 fn parse_sleep() {
 	let res = test_parse!(parse_stmt, r"SLEEP 1s").unwrap();
@@ -2575,6 +3051,7 @@ fn parse_insert_select() {
 			into: Some(Value::Table(Table("bar".to_owned()))),
 			data: Data::SingleExpression(Value::Subquery(Box::new(Subquery::Select(
 				SelectStatement {
+					distinct: None,
 					expr: Fields(
 						vec![Field::Single {
 							expr: Value::Idiom(Idiom(vec![Part::Field(Ident("foo".to_string()))])),
@@ -2585,10 +3062,12 @@ fn parse_insert_select() {
 					omit: None,
 					only: false,
 					what: Values(vec![Value::Table(Table("baz".to_string()))]),
+					join: Vec::new(),
 					with: None,
 					cond: None,
 					split: None,
 					group: None,
+					having: None,
 					order: None,
 					limit: None,
 					start: None,
@@ -2597,7 +3076,8 @@ fn parse_insert_select() {
 					timeout: None,
 					parallel: false,
 					explain: None,
-					tempfiles: false
+					tempfiles: false,
+					combine: Vec::new(),
 				}
 			)))),
 			ignore: true,
@@ -2799,6 +3279,18 @@ fn parse_remove() {
 			name: Ident("foo".to_owned()),
 			if_exists: false,
 			expunge: false,
+			safe: false,
+		}))
+	);
+
+	let res = test_parse!(parse_stmt, r#"REMOVE TABLE foo SAFE"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Table(RemoveTableStatement {
+			name: Ident("foo".to_owned()),
+			if_exists: false,
+			expunge: false,
+			safe: true,
 		}))
 	);
 
@@ -2809,6 +3301,18 @@ fn parse_remove() {
 			name: Ident("foo".to_owned()),
 			what: Ident("bar".to_owned()),
 			if_exists: false,
+			database: false,
+		}))
+	);
+
+	let res = test_parse!(parse_stmt, r#"REMOVE EVENT foo FOR DATABASE"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Event(RemoveEventStatement {
+			name: Ident("foo".to_owned()),
+			what: Ident::default(),
+			if_exists: false,
+			database: true,
 		}))
 	);
 
@@ -2854,6 +3358,15 @@ fn parse_remove() {
 			if_exists: false,
 		}))
 	);
+
+	let res = test_parse!(parse_stmt, r#"REMOVE TEST IF EXISTS foo"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Test(RemoveTestStatement {
+			name: Ident("foo".to_owned()),
+			if_exists: true,
+		}))
+	);
 }
 
 #[test]
@@ -3265,3 +3778,12 @@ fn parse_access_purge() {
 fn parse_like_operator() {
 	test_parse!(parse_stmt, r#"SELECT * FROM "a" ~ "b"; "#).unwrap();
 }
+
+#[test]
+fn parse_with_protected_name() {
+	for name in ["access", "auth", "token", "session"] {
+		let sql = format!("WITH {name} AS (SELECT * FROM user) SELECT * FROM ${name}");
+		let res = test_parse!(parse_stmt, sql);
+		assert!(res.is_err(), "Unexpected successful parsing of protected name {name}: {res:?}");
+	}
+}
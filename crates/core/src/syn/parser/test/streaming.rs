@@ -131,8 +131,8 @@ fn statements() -> Vec<Statement> {
 
 	vec![
 		Statement::Analyze(AnalyzeStatement::Idx(Ident("a".to_string()), Ident("b".to_string()))),
-		Statement::Begin(BeginStatement),
-		Statement::Begin(BeginStatement),
+		Statement::Begin(BeginStatement::default()),
+		Statement::Begin(BeginStatement::default()),
 		Statement::Break(BreakStatement),
 		Statement::Cancel(CancelStatement),
 		Statement::Cancel(CancelStatement),
@@ -213,6 +213,7 @@ fn statements() -> Vec<Statement> {
 			if_not_exists: false,
 			overwrite: false,
 			returns: None,
+			defaults: Vec::new(),
 		})),
 		Statement::Define(DefineStatement::Access(DefineAccessStatement {
 			name: Ident("a".to_string()),
@@ -296,6 +297,8 @@ fn statements() -> Vec<Statement> {
 			cache_events_ts: uuid::Uuid::default(),
 			cache_tables_ts: uuid::Uuid::default(),
 			cache_indexes_ts: uuid::Uuid::default(),
+			like: None,
+			cache: false,
 		})),
 		Statement::Define(DefineStatement::Event(DefineEventStatement {
 			name: Ident("event".to_owned()),
@@ -305,6 +308,11 @@ fn statements() -> Vec<Statement> {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
+			database: None,
+			asynchronous: false,
+			retry: None,
+			backoff: None,
+			before: Vec::new(),
 		})),
 		Statement::Define(DefineStatement::Field(DefineFieldStatement {
 			name: Idiom(vec![
@@ -457,6 +465,8 @@ fn statements() -> Vec<Statement> {
 				r: Value::Number(Number::Int(2)),
 			})),
 			block: Block(vec![Entry::Break(BreakStatement)]),
+			batch: None,
+			each: false,
 		}),
 		Statement::Ifelse(IfelseStatement {
 			exprs: vec![
@@ -482,6 +492,7 @@ fn statements() -> Vec<Statement> {
 		Statement::Info(InfoStatement::Ns(false)),
 		Statement::Info(InfoStatement::User(Ident("user".to_owned()), Some(Base::Ns), false)),
 		Statement::Select(SelectStatement {
+			distinct: None,
 			expr: Fields(
 				vec![
 					Field::Single {
@@ -505,6 +516,7 @@ fn statements() -> Vec<Statement> {
 			omit: Some(Idioms(vec![Idiom(vec![Part::Field(Ident("bar".to_owned()))])])),
 			only: true,
 			what: Values(vec![Value::Table(Table("a".to_owned())), Value::Number(Number::Int(1))]),
+			join: Vec::new(),
 			with: Some(With::Index(vec!["index".to_owned(), "index_2".to_owned()])),
 			cond: Some(Cond(Value::Bool(true))),
 			split: Some(Splits(vec![
@@ -515,6 +527,7 @@ fn statements() -> Vec<Statement> {
 				Group(Idiom(vec![Part::Field(Ident("foo".to_owned()))])),
 				Group(Idiom(vec![Part::Field(Ident("bar".to_owned()))])),
 			])),
+			having: None,
 			order: Some(Ordering::Order(OrderList(vec![Order {
 				value: Idiom(vec![Part::Field(Ident("foo".to_owned()))]),
 				collate: true,
@@ -536,11 +549,13 @@ fn statements() -> Vec<Statement> {
 			parallel: false,
 			tempfiles: false,
 			explain: Some(Explain(true)),
+			combine: Vec::new(),
 		}),
 		Statement::Set(SetStatement {
 			name: "param".to_owned(),
 			what: Value::Number(Number::Int(1)),
 			kind: None,
+			local: false,
 		}),
 		Statement::Show(ShowStatement {
 			table: Some(Table("foo".to_owned())),
@@ -6,7 +6,7 @@ use rust_decimal::Decimal;
 use crate::{
 	sql::{
 		Array, Constant, Expression, Geometry, Id, Ident, Idiom, Number, Object, Operator, Part,
-		Query, Statement, Statements, Strand, Thing, Value,
+		Query, Statement, Statements, Strand, Subquery, Thing, Value,
 	},
 	syn::parser::{mac::test_parse, Parser, ParserSettings},
 };
@@ -28,6 +28,42 @@ fn parse_index_expression() {
 	)
 }
 
+#[test]
+fn parse_case_expression() {
+	let value = test_parse!(
+		parse_value_field,
+		"CASE WHEN a > 1 THEN 'many' WHEN a = 1 THEN 'one' ELSE 'none' END"
+	)
+	.unwrap();
+	let Value::Subquery(x) = value else {
+		panic!("not the right value type");
+	};
+	let Subquery::Case(x) = *x else {
+		panic!("not the right subquery type");
+	};
+	assert_eq!(x.value, None);
+	assert_eq!(x.exprs.len(), 2);
+	assert_eq!(x.close, Some(Value::Strand(Strand("none".to_owned()))));
+	assert_eq!(x.to_string(), "CASE WHEN a > 1 THEN 'many' WHEN a = 1 THEN 'one' ELSE 'none' END");
+
+	let value = test_parse!(parse_value_field, "CASE status WHEN 'open' THEN 1 END").unwrap();
+	assert_eq!(value.to_string(), "CASE status WHEN 'open' THEN 1 END");
+
+	// This is a field named case
+	let value = test_parse!(parse_value_field, "case = 1").unwrap();
+	let Value::Expression(x) = value else {
+		panic!("not the right value type");
+	};
+	let Expression::Binary {
+		l,
+		..
+	} = *x
+	else {
+		panic!("not the right expression type");
+	};
+	assert_eq!(l, Value::Idiom(Idiom(vec![Part::Field(Ident("case".to_string()))])));
+}
+
 #[test]
 fn parse_coordinate() {
 	let coord = test_parse!(parse_value_field, "(1.88, -18.0)").unwrap();
@@ -53,6 +89,12 @@ fn parse_like_operator() {
 	test_parse!(parse_value_field, "a ~ b").unwrap();
 }
 
+#[test]
+fn parse_similar_operator() {
+	let res = test_parse!(parse_value_field, "a ~% 'b'").unwrap();
+	assert_eq!(res.to_string(), "a ~% 'b'");
+}
+
 #[test]
 fn parse_range_operator() {
 	test_parse!(parse_value_field, "1..2").unwrap();
@@ -32,7 +32,7 @@ impl Parser<'_> {
 				| t!("THROW")
 				| t!("UPDATE")
 				| t!("UPSERT")
-				| t!("USE")
+				| t!("USE") | t!("WITH")
 		)
 	}
 
@@ -139,3 +139,10 @@ fn empty_subquery() {
 fn empty_json() {
 	super::json("").unwrap_err();
 }
+
+#[test]
+fn called_functions() {
+	let source = "SELECT count(), string::len(name), fn::greet::hello(name) FROM (SELECT * FROM person) WHERE type::is::string(name) AND person:1;";
+	let functions: Vec<_> = super::functions(source).into_iter().collect();
+	assert_eq!(functions, ["count", "fn::greet::hello", "string::len", "type::is::string"]);
+}
@@ -37,9 +37,11 @@ keyword! {
 	Ascending => "ASCENDING",
 	Ascii => "ASCII",
 	Assert => "ASSERT",
+	Async => "ASYNC",
 	At => "AT",
 	Authenticate => "AUTHENTICATE",
 	Auto => "AUTO",
+	Backoff => "BACKOFF",
 	Bearer => "BEARER",
 	Before => "BEFORE",
 	Begin => "BEGIN",
@@ -51,6 +53,7 @@ keyword! {
 	Camel => "CAMEL",
 	Cancel => "CANCEL",
 	Cascade => "CASCADE",
+	Case => "CASE",
 	ChangeFeed => "CHANGEFEED",
 	Changes => "CHANGES",
 	Capacity => "CAPACITY",
@@ -70,6 +73,7 @@ keyword! {
 	Diff => "DIFF",
 	Dimension => "DIMENSION",
 	Distance => "DISTANCE",
+	Distinct => "DISTINCT",
 	DocIdsCache => "DOC_IDS_CACHE",
 	DocIdsOrder => "DOC_IDS_ORDER",
 	DocLengthsCache => "DOC_LENGTHS_CACHE",
@@ -82,6 +86,7 @@ keyword! {
 	Else => "ELSE",
 	End => "END",
 	Enforced => "ENFORCED",
+	Except => "EXCEPT",
 	Exclude => "EXCLUDE",
 	Exists => "EXISTS",
 	Expired => "EXPIRED",
@@ -102,6 +107,7 @@ keyword! {
 	Grant => "GRANT",
 	Graphql => "GRAPHQL",
 	Group => "GROUP",
+	Having => "HAVING",
 	Headers => "HEADERS",
 	Highlights => "HIGHLIGHTS",
 	Hnsw => "HNSW",
@@ -110,6 +116,7 @@ keyword! {
 	Index => "INDEX",
 	Info => "INFO",
 	Insert => "INSERT",
+	Intersect => "INTERSECT",
 	Into => "INTO",
 	If => "IF",
 	Is => "IS",
@@ -120,6 +127,7 @@ keyword! {
 	KeepPrunedConnections => "KEEP_PRUNED_CONNECTIONS",
 	Kill => "KILL",
 	Let => "LET",
+	Like => "LIKE",
 	Limit => "LIMIT",
 	Live => "LIVE",
 	Lowercase => "LOWERCASE",
@@ -166,6 +174,7 @@ keyword! {
 	Remove => "REMOVE",
 	Replace => "REPLACE",
 	Return => "RETURN",
+	Retry => "RETRY",
 	Revoke => "REVOKE",
 	Revoked => "REVOKED",
 	Roles => "ROLES",
@@ -198,8 +207,10 @@ keyword! {
 	Token => "TOKEN",
 	To => "TO",
 	Transaction => "TRANSACTION",
+	Trigram => "TRIGRAM",
 	True => "true",
 	Type => "TYPE",
+	Union => "UNION",
 	Unique => "UNIQUE",
 	Unset => "UNSET",
 	Update => "UPDATE",
@@ -48,6 +48,8 @@ pub fn core_capabilities_from_test_config(config: &TestConfig) -> Capabilities {
 				.without_functions(extract_targets(&schema_cap.deny_functions, true))
 				.with_network_targets(extract_targets(&schema_cap.allow_net, false))
 				.without_network_targets(extract_targets(&schema_cap.deny_net, true))
+				.with_env_vars(extract_targets(&schema_cap.allow_env, true))
+				.without_env_vars(extract_targets(&schema_cap.deny_env, true))
 				.with_rpc_methods(extract_targets(&schema_cap.allow_rpc, false))
 				.without_rpc_methods(extract_targets(&schema_cap.deny_rpc, true))
 				.with_http_routes(extract_targets(&schema_cap.allow_http, false))
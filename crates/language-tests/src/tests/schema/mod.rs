@@ -7,7 +7,9 @@ use std::{collections::BTreeMap, fmt, str::FromStr};
 use camino::Utf8PathBuf;
 use serde::{Deserialize, Serialize};
 use surrealdb_core::{
-	dbs::capabilities::{ExperimentalTarget, FuncTarget, MethodTarget, NetTarget, RouteTarget},
+	dbs::capabilities::{
+		EnvTarget, ExperimentalTarget, FuncTarget, MethodTarget, NetTarget, RouteTarget,
+	},
 	sql::Value as CoreValue,
 	syn,
 };
@@ -322,6 +324,9 @@ pub struct Capabilities {
 	pub allow_net: Option<BoolOr<Vec<SchemaTarget<NetTarget>>>>,
 	pub deny_net: Option<BoolOr<Vec<SchemaTarget<NetTarget>>>>,
 
+	pub allow_env: Option<BoolOr<Vec<SchemaTarget<EnvTarget>>>>,
+	pub deny_env: Option<BoolOr<Vec<SchemaTarget<EnvTarget>>>>,
+
 	pub allow_rpc: Option<BoolOr<Vec<SchemaTarget<MethodTarget>>>>,
 	pub deny_rpc: Option<BoolOr<Vec<SchemaTarget<MethodTarget>>>>,
 
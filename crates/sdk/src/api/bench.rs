@@ -0,0 +1,523 @@
+//! Synthetic datasets and workloads for reproducing performance issues
+//!
+//! The generated data, including the record ids, is deterministic for a given seed, so the same
+//! dataset can be recreated from a single line in an issue report. The random values come from
+//! the ChaCha8 generator, whose output is stable across versions of the `rand` crates. A [`Load`] then runs queries against the dataset, and
+//! reports the throughput and latency percentiles, to compare storage engines and versions.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use surrealdb::bench::{Dataset, Load, Seed, Workload};
+//! use surrealdb::engine::any::connect;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> surrealdb::Result<()> {
+//! let db = connect("mem://").await?;
+//! db.use_ns("bench").use_db("bench").await?;
+//!
+//! // Generate 100,000 people, with 10 `knows` edges each
+//! Seed::new(Dataset::Graph).records(100_000).edges(10).run(&db).await?;
+//!
+//! // Follow the edges from random people, with 16 queries in flight at once
+//! let report = Load::new(Workload::Graph).records(100_000).concurrency(16).run(&db).await?;
+//! println!("{:.0} queries/s, p99 {:?}", report.throughput(), report.percentile(99.0));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::api::err::Error;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::api::Surreal;
+use chrono::{DateTime, TimeDelta, Utc};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use surrealdb_core::sql::statements::InsertStatement;
+use surrealdb_core::sql::{Array, Data, Datetime, Id, Object, Statement, Table, Thing, Value};
+
+#[cfg(not(target_family = "wasm"))]
+use tokio::time::Instant;
+
+#[cfg(target_family = "wasm")]
+use wasmtimer::std::Instant;
+
+const FIRST_NAMES: &[&str] = &[
+	"Alice", "Bob", "Carol", "Dave", "Eve", "Frank", "Grace", "Heidi", "Ivan", "Judy", "Mallory",
+	"Niaj", "Olivia", "Peggy", "Rupert", "Sybil", "Trent", "Victor", "Walter", "Zoe",
+];
+
+const LAST_NAMES: &[&str] = &[
+	"Smith", "Jones", "Taylor", "Brown", "Williams", "Wilson", "Johnson", "Davies", "Robinson",
+	"Wright", "Thompson", "Evans", "Walker", "White", "Roberts", "Green", "Hall", "Wood",
+];
+
+const CITIES: &[&str] = &[
+	"London",
+	"Paris",
+	"Berlin",
+	"Madrid",
+	"Rome",
+	"Amsterdam",
+	"Dublin",
+	"Vienna",
+	"Lisbon",
+	"Prague",
+	"New York",
+	"Tokyo",
+	"Sydney",
+	"Toronto",
+];
+
+const TAGS: &[&str] = &[
+	"admin",
+	"beta",
+	"customer",
+	"developer",
+	"enterprise",
+	"free",
+	"partner",
+	"premium",
+	"staff",
+	"trial",
+];
+
+/// The kind of data which is generated
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Dataset {
+	/// `person` records with nested objects, arrays and mixed field types
+	Documents,
+	/// `person` records connected by `knows` graph edges
+	Graph,
+	/// `reading` records with `[sensor, time]` array record ids
+	TimeSeries,
+	/// `embedding` records with a fixed dimension float vector
+	Vectors,
+}
+
+impl Dataset {
+	/// The table which records of this dataset are stored in
+	pub fn table(&self) -> &'static str {
+		match self {
+			Self::Documents | Self::Graph => "person",
+			Self::TimeSeries => "reading",
+			Self::Vectors => "embedding",
+		}
+	}
+}
+
+impl fmt::Display for Dataset {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Documents => f.write_str("documents"),
+			Self::Graph => f.write_str("graph"),
+			Self::TimeSeries => f.write_str("timeseries"),
+			Self::Vectors => f.write_str("vectors"),
+		}
+	}
+}
+
+impl FromStr for Dataset {
+	type Err = Error;
+
+	fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"documents" => Ok(Self::Documents),
+			"graph" => Ok(Self::Graph),
+			"timeseries" => Ok(Self::TimeSeries),
+			"vectors" => Ok(Self::Vectors),
+			_ => Err(Error::InvalidParams(format!(
+				"Unknown dataset `{s}`, expected `documents`, `graph`, `timeseries` or `vectors`"
+			))),
+		}
+	}
+}
+
+/// Generates a synthetic dataset into the selected namespace and database
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Seed {
+	dataset: Dataset,
+	records: u64,
+	batch_size: usize,
+	seed: u64,
+	edges: usize,
+	sensors: u64,
+	dimension: usize,
+}
+
+impl Seed {
+	/// Create a generator for a dataset with the default scale
+	pub fn new(dataset: Dataset) -> Self {
+		Self {
+			dataset,
+			records: 10_000,
+			batch_size: 1_000,
+			seed: 0,
+			edges: 5,
+			sensors: 100,
+			dimension: 128,
+		}
+	}
+
+	/// Set the number of records to generate
+	///
+	/// For the graph dataset this is the number of `person` records, not including edges.
+	pub fn records(mut self, records: u64) -> Self {
+		self.records = records;
+		self
+	}
+
+	/// Set the number of records which are inserted with each query
+	pub fn batch_size(mut self, batch_size: usize) -> Self {
+		self.batch_size = batch_size.max(1);
+		self
+	}
+
+	/// Set the seed of the random number generator
+	pub fn seed(mut self, seed: u64) -> Self {
+		self.seed = seed;
+		self
+	}
+
+	/// Set the number of outgoing `knows` edges of each person in the graph dataset
+	pub fn edges(mut self, edges: usize) -> Self {
+		self.edges = edges;
+		self
+	}
+
+	/// Set the number of sensors which readings are spread across in the time-series dataset
+	pub fn sensors(mut self, sensors: u64) -> Self {
+		self.sensors = sensors.max(1);
+		self
+	}
+
+	/// Set the dimension of the vectors in the vectors dataset
+	pub fn dimension(mut self, dimension: usize) -> Self {
+		self.dimension = dimension.max(1);
+		self
+	}
+
+	/// Generate the dataset, returning the number of records which were inserted
+	pub async fn run<C: Connection>(&self, db: &Surreal<C>) -> Result<u64> {
+		let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+		let mut count = 0;
+		let mut start = 0;
+		while start < self.records {
+			let end = self.records.min(start + self.batch_size as u64);
+			let rows = (start..end).map(|i| self.record(&mut rng, i)).collect();
+			count += self.insert(db, self.dataset.table(), rows, false).await?;
+			start = end;
+		}
+		// Edges can only be added once all of the people exist
+		if self.dataset == Dataset::Graph && self.records > 1 {
+			let mut edges = Vec::with_capacity(self.batch_size);
+			for i in 0..self.records {
+				for n in 0..self.edges {
+					edges.push(self.edge(&mut rng, i, n));
+					if edges.len() == self.batch_size {
+						count += self.insert(db, "knows", std::mem::take(&mut edges), true).await?;
+					}
+				}
+			}
+			if !edges.is_empty() {
+				count += self.insert(db, "knows", edges, true).await?;
+			}
+		}
+		Ok(count)
+	}
+
+	async fn insert<C: Connection>(
+		&self,
+		db: &Surreal<C>,
+		table: &str,
+		rows: Vec<Value>,
+		relation: bool,
+	) -> Result<u64> {
+		let count = rows.len() as u64;
+		let stm = InsertStatement {
+			into: Some(Value::Table(Table(table.to_owned()))),
+			data: Data::SingleExpression(Value::Array(Array(rows))),
+			relation,
+			..Default::default()
+		};
+		db.query(Statement::Insert(stm)).await?.check()?;
+		Ok(count)
+	}
+
+	fn record(&self, rng: &mut ChaCha8Rng, i: u64) -> Value {
+		match self.dataset {
+			Dataset::Documents | Dataset::Graph => person(rng, i),
+			Dataset::TimeSeries => self.reading(rng, i),
+			Dataset::Vectors => self.embedding(rng, i),
+		}
+	}
+
+	fn edge(&self, rng: &mut ChaCha8Rng, i: u64, n: usize) -> Value {
+		// Avoid edges which point back to the same person
+		let mut to = rng.gen_range(0..self.records - 1);
+		if to >= i {
+			to += 1;
+		}
+		// Number the edges, so that they get the same ids each time
+		let id = i * self.edges as u64 + n as u64;
+		object([
+			("id", thing("knows", Id::from(id as i64))),
+			("in", thing("person", Id::from(i as i64))),
+			("out", thing("person", Id::from(to as i64))),
+			("since", datetime(rng.gen_range(0..365 * 24 * 60 * 60))),
+			("strength", rng.gen_range(0.0f64..1.0).into()),
+		])
+	}
+
+	fn reading(&self, rng: &mut ChaCha8Rng, i: u64) -> Value {
+		let sensor = (i % self.sensors) as i64;
+		// Each sensor reports once a second
+		let time = datetime((i / self.sensors) as i64);
+		let id = Id::from(vec![Value::from(sensor), time.clone()]);
+		object([
+			("id", thing("reading", id)),
+			("sensor", sensor.into()),
+			("time", time),
+			("temperature", rng.gen_range(-10.0f64..40.0).into()),
+			("humidity", rng.gen_range(0.0f64..100.0).into()),
+			("battery", rng.gen_range(0i64..=100).into()),
+		])
+	}
+
+	fn embedding(&self, rng: &mut ChaCha8Rng, i: u64) -> Value {
+		let vector: Vec<Value> =
+			(0..self.dimension).map(|_| rng.gen_range(-1.0f64..1.0).into()).collect();
+		object([
+			("id", thing("embedding", Id::from(i as i64))),
+			("category", pick(rng, TAGS)),
+			("vector", vector.into()),
+		])
+	}
+}
+
+/// The kind of queries which a [`Load`] runs
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Workload {
+	/// Select random `person` records by their id
+	Read,
+	/// Update the score of random `person` records
+	Write,
+	/// Follow `knows` edges two hops out from random `person` records
+	Graph,
+	/// Nine reads for every write
+	Mixed,
+}
+
+impl fmt::Display for Workload {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Read => f.write_str("read"),
+			Self::Write => f.write_str("write"),
+			Self::Graph => f.write_str("graph"),
+			Self::Mixed => f.write_str("mixed"),
+		}
+	}
+}
+
+impl FromStr for Workload {
+	type Err = Error;
+
+	fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"read" => Ok(Self::Read),
+			"write" => Ok(Self::Write),
+			"graph" => Ok(Self::Graph),
+			"mixed" => Ok(Self::Mixed),
+			_ => Err(Error::InvalidParams(format!(
+				"Unknown workload `{s}`, expected `read`, `write`, `graph` or `mixed`"
+			))),
+		}
+	}
+}
+
+/// Runs a workload against the `person` records of a seeded documents or graph dataset
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Load {
+	workload: Workload,
+	operations: u64,
+	concurrency: usize,
+	records: u64,
+	seed: u64,
+}
+
+impl Load {
+	/// Create a load for a workload with the default scale
+	pub fn new(workload: Workload) -> Self {
+		Self {
+			workload,
+			operations: 10_000,
+			concurrency: 10,
+			records: 10_000,
+			seed: 0,
+		}
+	}
+
+	/// Set the total number of queries to run
+	pub fn operations(mut self, operations: u64) -> Self {
+		self.operations = operations;
+		self
+	}
+
+	/// Set the number of queries which are in flight at once
+	pub fn concurrency(mut self, concurrency: usize) -> Self {
+		self.concurrency = concurrency.max(1);
+		self
+	}
+
+	/// Set the number of `person` records in the dataset, which the queries pick from
+	pub fn records(mut self, records: u64) -> Self {
+		self.records = records.max(1);
+		self
+	}
+
+	/// Set the seed of the random number generator
+	pub fn seed(mut self, seed: u64) -> Self {
+		self.seed = seed;
+		self
+	}
+
+	/// Run the queries, returning the measured throughput and latencies
+	pub async fn run<C: Connection>(&self, db: &Surreal<C>) -> Result<Report> {
+		let next = AtomicU64::new(0);
+		let started = Instant::now();
+		let workers = (0..self.concurrency).map(|i| self.worker(db, &next, i as u64));
+		let results = futures::future::join_all(workers).await;
+		let elapsed = started.elapsed();
+		let mut latencies = Vec::with_capacity(self.operations as usize);
+		let mut errors = 0;
+		for (worker, failed) in results {
+			latencies.extend(worker);
+			errors += failed;
+		}
+		latencies.sort_unstable();
+		Ok(Report {
+			operations: latencies.len() as u64,
+			errors,
+			elapsed,
+			latencies,
+		})
+	}
+
+	/// Runs queries until the operations have been used up by all of the workers
+	async fn worker<C: Connection>(
+		&self,
+		db: &Surreal<C>,
+		next: &AtomicU64,
+		worker: u64,
+	) -> (Vec<Duration>, u64) {
+		let mut rng = ChaCha8Rng::seed_from_u64(self.seed.wrapping_add(worker));
+		let mut latencies = Vec::new();
+		let mut errors = 0;
+		while next.fetch_add(1, Ordering::Relaxed) < self.operations {
+			let write = match self.workload {
+				Workload::Write => true,
+				Workload::Mixed => rng.gen_ratio(1, 10),
+				Workload::Read | Workload::Graph => false,
+			};
+			let sql = match self.workload {
+				_ if write => "UPDATE type::thing('person', $id) SET score = $score",
+				Workload::Graph => {
+					"SELECT ->knows->person->knows->person AS friends FROM type::thing('person', $id)"
+				}
+				_ => "SELECT * FROM type::thing('person', $id)",
+			};
+			let id = rng.gen_range(0..self.records) as i64;
+			let score = rng.gen_range(0.0f64..100.0);
+			let started = Instant::now();
+			let result = db.query(sql).bind(("id", id)).bind(("score", score)).await;
+			match result.and_then(|response| response.check()) {
+				Ok(_) => latencies.push(started.elapsed()),
+				Err(_) => errors += 1,
+			}
+		}
+		(latencies, errors)
+	}
+}
+
+/// The results of running a [`Load`]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Report {
+	/// The number of queries which succeeded
+	pub operations: u64,
+	/// The number of queries which failed
+	pub errors: u64,
+	/// How long the load took to run
+	pub elapsed: Duration,
+	/// The latency of each successful query, in ascending order
+	latencies: Vec<Duration>,
+}
+
+impl Report {
+	/// The number of successful queries per second
+	pub fn throughput(&self) -> f64 {
+		self.operations as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+	}
+
+	/// The latency which the given percentage of queries completed within
+	pub fn percentile(&self, percentile: f64) -> Duration {
+		let Some(last) = self.latencies.len().checked_sub(1) else {
+			return Duration::ZERO;
+		};
+		let rank = (percentile.clamp(0.0, 100.0) / 100.0 * last as f64).round() as usize;
+		self.latencies[rank]
+	}
+}
+
+fn person(rng: &mut ChaCha8Rng, i: u64) -> Value {
+	let first = FIRST_NAMES.choose(rng).copied().unwrap_or_default();
+	let last = LAST_NAMES.choose(rng).copied().unwrap_or_default();
+	let count = rng.gen_range(0..4);
+	let tags: Vec<Value> = TAGS.choose_multiple(rng, count).map(|t| Value::from(*t)).collect();
+	object([
+		("id", thing("person", Id::from(i as i64))),
+		("name", object([("first", first.into()), ("last", last.into())])),
+		("email", format!("{}.{}{i}@example.com", first, last).to_lowercase().into()),
+		("age", rng.gen_range(18i64..90).into()),
+		("active", rng.gen_bool(0.8).into()),
+		("score", rng.gen_range(0.0f64..100.0).into()),
+		("tags", tags.into()),
+		(
+			"address",
+			object([
+				("city", pick(rng, CITIES)),
+				("street", format!("{} {} Street", rng.gen_range(1..200), last).into()),
+			]),
+		),
+		("created", datetime(rng.gen_range(0..365 * 24 * 60 * 60))),
+	])
+}
+
+fn pick(rng: &mut ChaCha8Rng, values: &[&str]) -> Value {
+	values.choose(rng).copied().unwrap_or_default().into()
+}
+
+fn thing(tb: &str, id: Id) -> Value {
+	Value::Thing(Thing::from((tb, id)))
+}
+
+/// A datetime the given number of seconds after the start of 2024
+fn datetime(secs: i64) -> Value {
+	let epoch = DateTime::<Utc>::from_timestamp(1_704_067_200, 0).unwrap_or_default();
+	Value::Datetime(Datetime::from(epoch + TimeDelta::seconds(secs)))
+}
+
+fn object<const N: usize>(fields: [(&str, Value); N]) -> Value {
+	let map: BTreeMap<String, Value> = fields.into_iter().map(|(k, v)| (k.to_owned(), v)).collect();
+	Value::Object(Object(map))
+}
@@ -0,0 +1,248 @@
+use super::{Command, Router};
+use crate::api::method::live;
+use crate::api::opt::RecordCacheConfig;
+use crate::api::ExtraFeatures;
+use crate::api::Result;
+use crate::opt::Resource;
+use crate::Value;
+use quick_cache::sync::Cache;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use surrealdb_core::sql::statements::LiveStatement;
+use surrealdb_core::sql::{Field, Fields, Query, Statement, Table, Thing, Value as CoreValue};
+
+#[cfg(not(target_family = "wasm"))]
+use tokio::time::Instant;
+
+#[cfg(target_family = "wasm")]
+use wasmtimer::std::Instant;
+
+/// A client-side cache of records selected by their record id
+///
+/// Records are evicted when their TTL elapses, when they are written to through
+/// this client, or when a live query on their table reports that they changed.
+pub(crate) struct RecordCache {
+	ttl: Option<Duration>,
+	entries: Arc<Entries>,
+	/// The tables which have a live query evicting changed records
+	watched: Mutex<HashSet<String>>,
+}
+
+/// The cached records, along with the records which are being fetched
+struct Entries {
+	records: Cache<Thing, (CoreValue, Instant)>,
+	/// The fetch which may cache each record once it completes
+	///
+	/// Evicting a record also forgets its fetch, and a fetch only caches its record while it is
+	/// still listed here, with both steps taken under this lock.
+	fetches: Mutex<HashMap<Thing, u64>>,
+	/// The number used for the next fetch
+	next: AtomicU64,
+}
+
+impl Entries {
+	fn remove(&self, record: &Thing) {
+		let mut fetches = self.fetches.lock().unwrap_or_else(|e| e.into_inner());
+		fetches.remove(record);
+		self.records.remove(record);
+	}
+
+	fn clear(&self) {
+		let mut fetches = self.fetches.lock().unwrap_or_else(|e| e.into_inner());
+		fetches.clear();
+		self.records.clear();
+	}
+}
+
+/// A record which is being fetched, and which is cached when the fetch completes
+///
+/// A fetch which fails or is dropped is forgotten, without caching anything.
+pub(crate) struct Fetch {
+	entries: Arc<Entries>,
+	record: Option<Thing>,
+	id: u64,
+}
+
+impl Fetch {
+	/// Cache the fetched record, unless it was evicted or fetched again since the fetch started
+	pub(crate) fn insert(mut self, value: CoreValue) {
+		let Some(record) = self.record.take() else {
+			return;
+		};
+		let mut fetches = self.entries.fetches.lock().unwrap_or_else(|e| e.into_inner());
+		if let Entry::Occupied(entry) = fetches.entry(record) {
+			if *entry.get() == self.id {
+				let (record, _) = entry.remove_entry();
+				self.entries.records.insert(record, (value, Instant::now()));
+			}
+		}
+	}
+}
+
+impl Drop for Fetch {
+	fn drop(&mut self) {
+		let Some(record) = self.record.take() else {
+			return;
+		};
+		let mut fetches = self.entries.fetches.lock().unwrap_or_else(|e| e.into_inner());
+		if let Entry::Occupied(entry) = fetches.entry(record) {
+			if *entry.get() == self.id {
+				entry.remove();
+			}
+		}
+	}
+}
+
+impl fmt::Debug for RecordCache {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("RecordCache")
+			.field("ttl", &self.ttl)
+			.field("len", &self.entries.records.len())
+			.finish()
+	}
+}
+
+impl RecordCache {
+	pub(crate) fn new(config: &RecordCacheConfig) -> Self {
+		Self {
+			ttl: config.ttl,
+			entries: Arc::new(Entries {
+				records: Cache::new(config.capacity.max(1)),
+				fetches: Mutex::new(HashMap::new()),
+				next: AtomicU64::new(0),
+			}),
+			watched: Mutex::new(HashSet::new()),
+		}
+	}
+
+	/// Fetch a record which has not expired yet
+	pub(crate) fn get(&self, record: &Thing) -> Option<CoreValue> {
+		let (value, inserted) = self.entries.records.get(record)?;
+		match self.ttl {
+			Some(ttl) if inserted.elapsed() >= ttl => {
+				self.entries.records.remove(record);
+				None
+			}
+			_ => Some(value),
+		}
+	}
+
+	/// Register a fetch of a record, which must happen before the record is requested
+	pub(crate) fn fetch(&self, record: Thing) -> Fetch {
+		let id = self.entries.next.fetch_add(1, Ordering::Relaxed);
+		let mut fetches = self.entries.fetches.lock().unwrap_or_else(|e| e.into_inner());
+		fetches.insert(record.clone(), id);
+		Fetch {
+			entries: self.entries.clone(),
+			record: Some(record),
+			id,
+		}
+	}
+
+	/// Evict the records which a command is about to change
+	pub(crate) fn invalidate(&self, command: &Command) {
+		match command {
+			// The selected database or the permissions of the session change
+			Command::Use {
+				..
+			}
+			| Command::Signup {
+				..
+			}
+			| Command::Signin {
+				..
+			}
+			| Command::Authenticate {
+				..
+			}
+			| Command::Invalidate => {
+				self.entries.clear();
+				// Live queries only report changes in the database they were started in
+				if let Command::Use {
+					..
+				} = command
+				{
+					self.watched.lock().unwrap_or_else(|e| e.into_inner()).clear();
+				}
+			}
+			Command::Create {
+				what: Resource::RecordId(record),
+				..
+			}
+			| Command::Upsert {
+				what: Resource::RecordId(record),
+				..
+			}
+			| Command::Update {
+				what: Resource::RecordId(record),
+				..
+			}
+			| Command::Patch {
+				what: Resource::RecordId(record),
+				..
+			}
+			| Command::Merge {
+				what: Resource::RecordId(record),
+				..
+			}
+			| Command::Delete {
+				what: Resource::RecordId(record),
+			} => {
+				self.entries.remove(record.into_inner_ref());
+			}
+			// Other writes are only picked up by the live queries
+			_ => (),
+		}
+	}
+
+	/// Start a live query which evicts the records of a table when they change
+	pub(crate) async fn watch(&self, router: &Router, table: &str) {
+		if !router.features.contains(&ExtraFeatures::LiveQueries) {
+			return;
+		}
+		if !self.watched.lock().unwrap_or_else(|e| e.into_inner()).insert(table.to_owned()) {
+			return;
+		}
+		if let Err(error) = self.start_live_query(router, table).await {
+			trace!("Failed to start a live query for the record cache; {error}");
+			self.watched.lock().unwrap_or_else(|e| e.into_inner()).remove(table);
+		}
+	}
+
+	async fn start_live_query(&self, router: &Router, table: &str) -> Result<()> {
+		let mut fields = Fields::default();
+		fields.0 = vec![Field::All];
+		let mut stmt = LiveStatement::new(fields);
+		let mut tb = Table::default();
+		table.clone_into(&mut tb.0);
+		stmt.what = tb.into();
+		let mut response = router
+			.execute_query(Command::Query {
+				query: Query::from(Statement::Live(stmt)),
+				variables: Default::default(),
+			})
+			.await?;
+		let CoreValue::Uuid(id) = response.take::<Value>(0)?.into_inner() else {
+			return Err(crate::api::err::Error::InternalError(
+				"successful live query didn't return a uuid".to_owned(),
+			)
+			.into());
+		};
+		// Buffer without limit, so that no invalidations are dropped
+		let rx = live::register(router, *id, None).await?.rx;
+		// The channel is closed when the connection is dropped, which ends this task
+		let entries = self.entries.clone();
+		router.spawn(async move {
+			while let Ok(notification) = rx.recv().await {
+				if let Some(record) = notification.data.record() {
+					entries.remove(&record);
+				}
+			}
+		});
+		Ok(())
+	}
+}
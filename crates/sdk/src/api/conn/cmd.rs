@@ -1,14 +1,17 @@
 use super::MlExportConfig;
-use crate::{opt::Resource, value::Notification, Result};
-use async_channel::Sender;
+use crate::{method::live::NotificationSender, opt::Resource, Result};
+use async_channel::{Receiver, Sender};
 use bincode::Options;
 use revision::Revisioned;
 use serde::{ser::SerializeMap as _, Serialize};
 use std::borrow::Cow;
 use std::io::Read;
 use std::path::PathBuf;
+use surrealdb_core::dbs::Durability;
 use surrealdb_core::kvs::export::Config as DbExportConfig;
-use surrealdb_core::sql::{Array as CoreArray, Object as CoreObject, Query, Value as CoreValue};
+use surrealdb_core::sql::{
+	Array as CoreArray, Object as CoreObject, Query, Statement, Value as CoreValue, Values,
+};
 use uuid::Uuid;
 
 #[cfg(any(feature = "protocol-ws", feature = "protocol-http"))]
@@ -76,6 +79,10 @@ pub(crate) enum Command {
 		query: Cow<'static, str>,
 		variables: CoreObject,
 	},
+	/// Runs the command, returning the response without decoding it
+	RawResponse {
+		command: Box<Command>,
+	},
 	ExportFile {
 		path: PathBuf,
 		config: Option<DbExportConfig>,
@@ -95,11 +102,16 @@ pub(crate) enum Command {
 	ImportFile {
 		path: PathBuf,
 	},
+	ImportBytes {
+		bytes: Receiver<Result<Vec<u8>>>,
+	},
 	ImportMl {
 		path: PathBuf,
 	},
 	Health,
 	Version,
+	ServerInfo,
+	Compact,
 	Set {
 		key: String,
 		value: CoreValue,
@@ -107,9 +119,10 @@ pub(crate) enum Command {
 	Unset {
 		key: String,
 	},
+	Params,
 	SubscribeLive {
 		uuid: Uuid,
-		notification_sender: Sender<Notification<CoreValue>>,
+		notification_sender: NotificationSender,
 	},
 	Kill {
 		uuid: Uuid,
@@ -122,6 +135,226 @@ pub(crate) enum Command {
 }
 
 impl Command {
+	/// The name of the method this command runs, as reported to observers
+	pub(crate) fn method(&self) -> &'static str {
+		match self {
+			Command::Use {
+				..
+			} => "use",
+			Command::Signup {
+				..
+			} => "signup",
+			Command::Signin {
+				..
+			} => "signin",
+			Command::Authenticate {
+				..
+			} => "authenticate",
+			Command::Invalidate => "invalidate",
+			Command::Create {
+				..
+			} => "create",
+			Command::Upsert {
+				..
+			} => "upsert",
+			Command::Update {
+				..
+			} => "update",
+			Command::Insert {
+				..
+			} => "insert",
+			Command::InsertRelation {
+				..
+			} => "insert_relation",
+			Command::Patch {
+				..
+			} => "patch",
+			Command::Merge {
+				..
+			} => "merge",
+			Command::Select {
+				..
+			} => "select",
+			Command::Delete {
+				..
+			} => "delete",
+			Command::Query {
+				..
+			}
+			| Command::RawQuery {
+				..
+			} => "query",
+			Command::RawResponse {
+				command,
+			} => command.method(),
+			Command::ExportFile {
+				..
+			}
+			| Command::ExportBytes {
+				..
+			} => "export",
+			Command::ExportMl {
+				..
+			}
+			| Command::ExportBytesMl {
+				..
+			} => "export_ml",
+			Command::ImportFile {
+				..
+			}
+			| Command::ImportBytes {
+				..
+			} => "import",
+			Command::ImportMl {
+				..
+			} => "import_ml",
+			Command::Health => "health",
+			Command::Version => "version",
+			Command::ServerInfo => "server_info",
+			Command::Compact => "compact",
+			Command::Set {
+				..
+			} => "let",
+			Command::Unset {
+				..
+			} => "unset",
+			Command::Params => "params",
+			Command::SubscribeLive {
+				..
+			} => "live",
+			Command::Kill {
+				..
+			} => "kill",
+			Command::Run {
+				..
+			} => "run",
+		}
+	}
+
+	/// The statement run by a method which reads or writes records, and whether the method
+	/// returns a single record rather than a list of them
+	pub(crate) fn statement(&self) -> Option<(Statement, bool)> {
+		use crate::api::engine::resource_to_values;
+		use surrealdb_core::sql::{
+			statements::{
+				CreateStatement, DeleteStatement, InsertStatement, SelectStatement,
+				UpdateStatement, UpsertStatement,
+			},
+			Data, Field, Output, Table,
+		};
+
+		let res = match self.clone() {
+			Command::Create {
+				what,
+				data,
+			} => {
+				let mut stmt = CreateStatement::default();
+				stmt.what = resource_to_values(what);
+				stmt.data = data.map(Data::ContentExpression);
+				stmt.output = Some(Output::After);
+				(Statement::Create(stmt), true)
+			}
+			Command::Upsert {
+				what,
+				data,
+			} => {
+				let one = what.is_single_recordid();
+				let mut stmt = UpsertStatement::default();
+				stmt.what = resource_to_values(what);
+				stmt.data = data.map(Data::ContentExpression);
+				stmt.output = Some(Output::After);
+				(Statement::Upsert(stmt), one)
+			}
+			Command::Update {
+				what,
+				data,
+			} => {
+				let one = what.is_single_recordid();
+				let mut stmt = UpdateStatement::default();
+				stmt.what = resource_to_values(what);
+				stmt.data = data.map(Data::ContentExpression);
+				stmt.output = Some(Output::After);
+				(Statement::Update(stmt), one)
+			}
+			Command::Insert {
+				what,
+				data,
+			} => {
+				let one = !data.is_array();
+				let mut stmt = InsertStatement::default();
+				stmt.into = what.map(|w| Table::from(w).into());
+				stmt.data = Data::SingleExpression(data);
+				stmt.output = Some(Output::After);
+				(Statement::Insert(stmt), one)
+			}
+			Command::InsertRelation {
+				what,
+				data,
+			} => {
+				let one = !data.is_array();
+				let mut stmt = InsertStatement::default();
+				stmt.into = what.map(|w| Table::from(w).into());
+				stmt.data = Data::SingleExpression(data);
+				stmt.output = Some(Output::After);
+				stmt.relation = true;
+				(Statement::Insert(stmt), one)
+			}
+			Command::Patch {
+				what,
+				data,
+				upsert,
+			} => {
+				let data = data.map(Data::PatchExpression);
+				(patch_or_merge(resource_to_values(what), data, upsert), false)
+			}
+			Command::Merge {
+				what,
+				data,
+				upsert,
+			} => {
+				let data = data.map(Data::MergeExpression);
+				(patch_or_merge(resource_to_values(what), data, upsert), false)
+			}
+			Command::Select {
+				what,
+			} => {
+				let one = what.is_single_recordid();
+				let mut stmt = SelectStatement::default();
+				stmt.what = resource_to_values(what);
+				stmt.expr.0 = vec![Field::All];
+				(Statement::Select(stmt), one)
+			}
+			Command::Delete {
+				what,
+			} => {
+				let one = what.is_single_recordid();
+				let mut stmt = DeleteStatement::default();
+				stmt.what = resource_to_values(what);
+				stmt.output = Some(Output::Before);
+				(Statement::Delete(stmt), one)
+			}
+			_ => return None,
+		};
+
+		fn patch_or_merge(what: Values, data: Option<Data>, upsert: bool) -> Statement {
+			if upsert {
+				let mut stmt = UpsertStatement::default();
+				stmt.what = what;
+				stmt.data = data;
+				stmt.output = Some(Output::After);
+				Statement::Upsert(stmt)
+			} else {
+				let mut stmt = UpdateStatement::default();
+				stmt.what = what;
+				stmt.data = data;
+				stmt.output = Some(Output::After);
+				Statement::Update(stmt)
+			}
+		}
+
+		Some(res)
+	}
+
 	#[cfg(any(feature = "protocol-ws", feature = "protocol-http"))]
 	pub(crate) fn into_router_request(self, id: Option<i64>) -> Option<RouterRequest> {
 		use crate::api::engine::resource_to_values;
@@ -138,6 +371,7 @@ impl Command {
 				id,
 				method: "use",
 				params: Some(vec![CoreValue::from(namespace), CoreValue::from(database)].into()),
+				durability: None,
 			},
 			Command::Signup {
 				credentials,
@@ -145,6 +379,7 @@ impl Command {
 				id,
 				method: "signup",
 				params: Some(vec![CoreValue::from(credentials)].into()),
+				durability: None,
 			},
 			Command::Signin {
 				credentials,
@@ -152,6 +387,7 @@ impl Command {
 				id,
 				method: "signin",
 				params: Some(vec![CoreValue::from(credentials)].into()),
+				durability: None,
 			},
 			Command::Authenticate {
 				token,
@@ -159,11 +395,13 @@ impl Command {
 				id,
 				method: "authenticate",
 				params: Some(vec![CoreValue::from(token)].into()),
+				durability: None,
 			},
 			Command::Invalidate => RouterRequest {
 				id,
 				method: "invalidate",
 				params: None,
+				durability: None,
 			},
 			Command::Create {
 				what,
@@ -178,6 +416,7 @@ impl Command {
 					id,
 					method: "create",
 					params: Some(params.into()),
+					durability: None,
 				}
 			}
 			Command::Upsert {
@@ -194,6 +433,7 @@ impl Command {
 					id,
 					method: "upsert",
 					params: Some(params.into()),
+					durability: None,
 				}
 			}
 			Command::Update {
@@ -211,6 +451,7 @@ impl Command {
 					id,
 					method: "update",
 					params: Some(params.into()),
+					durability: None,
 				}
 			}
 			Command::Insert {
@@ -232,6 +473,7 @@ impl Command {
 					id,
 					method: "insert",
 					params: Some(params.into()),
+					durability: None,
 				}
 			}
 			Command::InsertRelation {
@@ -252,6 +494,7 @@ impl Command {
 					id,
 					method: "insert_relation",
 					params: Some(params.into()),
+					durability: None,
 				}
 			}
 			Command::Patch {
@@ -281,6 +524,7 @@ impl Command {
 					id,
 					method: "query",
 					params: Some(params.into()),
+					durability: None,
 				}
 			}
 			Command::Merge {
@@ -310,6 +554,7 @@ impl Command {
 					id,
 					method: "query",
 					params: Some(params.into()),
+					durability: None,
 				}
 			}
 			Command::Select {
@@ -319,6 +564,7 @@ impl Command {
 				id,
 				method: "select",
 				params: Some(CoreValue::Array(vec![what.into_core_value()].into())),
+				durability: None,
 			},
 			Command::Delete {
 				what,
@@ -327,6 +573,7 @@ impl Command {
 				id,
 				method: "delete",
 				params: Some(CoreValue::Array(vec![what.into_core_value()].into())),
+				durability: None,
 			},
 			Command::Query {
 				query,
@@ -337,6 +584,7 @@ impl Command {
 					id,
 					method: "query",
 					params: Some(params.into()),
+					durability: None,
 				}
 			}
 			Command::RawQuery {
@@ -348,8 +596,12 @@ impl Command {
 					id,
 					method: "query",
 					params: Some(params.into()),
+					durability: None,
 				}
 			}
+			Command::RawResponse {
+				command,
+			} => return command.into_router_request(id),
 			Command::ExportFile {
 				..
 			}
@@ -359,6 +611,9 @@ impl Command {
 			| Command::ImportFile {
 				..
 			}
+			| Command::ImportBytes {
+				..
+			}
 			| Command::ExportBytesMl {
 				..
 			}
@@ -372,11 +627,25 @@ impl Command {
 				id,
 				method: "ping",
 				params: None,
+				durability: None,
 			},
 			Command::Version => RouterRequest {
 				id,
 				method: "version",
 				params: None,
+				durability: None,
+			},
+			Command::ServerInfo => RouterRequest {
+				id,
+				method: "server_info",
+				params: None,
+				durability: None,
+			},
+			Command::Compact => RouterRequest {
+				id,
+				method: "compact",
+				params: None,
+				durability: None,
 			},
 			Command::Set {
 				key,
@@ -385,6 +654,7 @@ impl Command {
 				id,
 				method: "let",
 				params: Some(CoreValue::from(vec![CoreValue::from(key), value])),
+				durability: None,
 			},
 			Command::Unset {
 				key,
@@ -392,8 +662,10 @@ impl Command {
 				id,
 				method: "unset",
 				params: Some(CoreValue::from(vec![CoreValue::from(key)])),
+				durability: None,
 			},
-			Command::SubscribeLive {
+			Command::Params
+			| Command::SubscribeLive {
 				..
 			} => return None,
 			Command::Kill {
@@ -402,6 +674,7 @@ impl Command {
 				id,
 				method: "kill",
 				params: Some(CoreValue::from(vec![CoreValue::from(uuid)])),
+				durability: None,
 			},
 			Command::Run {
 				name,
@@ -414,6 +687,7 @@ impl Command {
 					vec![CoreValue::from(name), CoreValue::from(version), CoreValue::Array(args)]
 						.into(),
 				),
+				durability: None,
 			},
 		};
 		Some(res)
@@ -461,6 +735,7 @@ pub(crate) struct RouterRequest {
 	id: Option<i64>,
 	method: &'static str,
 	params: Option<CoreValue>,
+	durability: Option<Durability>,
 }
 
 #[cfg(feature = "protocol-ws")]
@@ -475,6 +750,16 @@ fn stringify_queries(value: CoreValue) -> CoreValue {
 }
 
 impl RouterRequest {
+	/// Request a write durability, if this request can write data
+	#[cfg(any(feature = "protocol-ws", feature = "protocol-http"))]
+	pub(crate) fn with_durability(self, durability: Option<Durability>) -> Self {
+		use surrealdb_core::rpc::Method;
+		Self {
+			durability: durability.filter(|_| Method::parse_case_sensitive(self.method).is_write()),
+			..self
+		}
+	}
+
 	#[cfg(feature = "protocol-ws")]
 	pub(crate) fn stringify_queries(self) -> Self {
 		Self {
@@ -537,7 +822,10 @@ impl Serialize for RouterRequest {
 			where
 				S: serde::Serializer,
 			{
-				let size = 1 + self.0.id.is_some() as usize + self.0.params.is_some() as usize;
+				let size = 1
+					+ self.0.id.is_some() as usize
+					+ self.0.params.is_some() as usize
+					+ self.0.durability.is_some() as usize;
 				let mut map = serializer.serialize_map(Some(size))?;
 				if let Some(id) = self.0.id.as_ref() {
 					map.serialize_entry("id", &InnerNumberVariant(*id))?;
@@ -546,6 +834,9 @@ impl Serialize for RouterRequest {
 				if let Some(params) = self.0.params.as_ref() {
 					map.serialize_entry("params", params)?;
 				}
+				if let Some(durability) = self.0.durability {
+					map.serialize_entry("durability", &InnerMethod(durability.as_str()))?;
+				}
 				map.end()
 			}
 		}
@@ -584,7 +875,10 @@ impl Revisioned for RouterRequest {
 		// object wrapper version
 		Revisioned::serialize_revisioned(&1u32, w)?;
 
-		let size = 1 + self.id.is_some() as usize + self.params.is_some() as usize;
+		let size = 1
+			+ self.id.is_some() as usize
+			+ self.params.is_some() as usize
+			+ self.durability.is_some() as usize;
 		size.serialize_revisioned(w)?;
 
 		let serializer = bincode::options()
@@ -638,6 +932,25 @@ impl Revisioned for RouterRequest {
 			x.serialize_revisioned(w)?;
 		}
 
+		if let Some(x) = self.durability {
+			serializer
+				.serialize_into(&mut *w, "durability")
+				.map_err(|err| revision::Error::Serialize(err.to_string()))?;
+
+			// the Value version
+			1u16.serialize_revisioned(w)?;
+
+			// the Value::Strand variant
+			4u16.serialize_revisioned(w)?;
+
+			// the Strand version
+			1u16.serialize_revisioned(w)?;
+
+			serializer
+				.serialize_into(&mut *w, x.as_str())
+				.map_err(|e| revision::Error::Serialize(format!("{:?}", e)))?;
+		}
+
 		Ok(())
 	}
 
@@ -690,6 +1003,7 @@ mod test {
 			id: Some(1234),
 			method: "request",
 			params: Some(vec![Value::from(1234i64), Value::from("request")].into()),
+			durability: None,
 		};
 
 		println!("test convert bincode");
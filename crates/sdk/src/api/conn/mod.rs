@@ -6,16 +6,31 @@ use crate::api::opt::Endpoint;
 use crate::api::ExtraFeatures;
 use crate::api::Result;
 use crate::api::Surreal;
+use crate::opt::Resource;
 use crate::Value;
 use async_channel::Receiver;
 use async_channel::Sender;
 use serde::de::DeserializeOwned;
 use std::collections::HashSet;
+use std::future::Future;
 use std::sync::atomic::AtomicI64;
 use std::sync::atomic::Ordering;
-use surrealdb_core::sql::{from_value as from_core_value, Value as CoreValue};
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::Duration;
+use surrealdb_core::err::Error as DbError;
+use surrealdb_core::sql::{
+	from_value as from_core_value, Object as CoreObject, Query, Value as CoreValue,
+};
 
+#[cfg(not(target_family = "wasm"))]
+use tokio::time::Instant;
+#[cfg(target_family = "wasm")]
+use wasmtimer::std::Instant;
+
+mod cache;
 mod cmd;
+pub(crate) use cache::RecordCache;
 pub(crate) use cmd::Command;
 #[cfg(feature = "protocol-http")]
 pub(crate) use cmd::RouterRequest;
@@ -43,6 +58,8 @@ pub struct Router {
 	pub(crate) config: Config,
 	pub(crate) last_id: AtomicI64,
 	pub(crate) features: HashSet<ExtraFeatures>,
+	pub(crate) cache: OnceLock<RecordCache>,
+	pub(crate) credentials: Mutex<Option<CoreObject>>,
 }
 
 impl Router {
@@ -50,6 +67,31 @@ impl Router {
 		self.last_id.fetch_add(1, Ordering::SeqCst)
 	}
 
+	/// Run a future in the background on the runtime of the connection
+	pub(crate) fn spawn<F>(&self, future: F)
+	where
+		F: Future<Output = ()> + Send + 'static,
+	{
+		#[cfg(not(target_family = "wasm"))]
+		self.config.spawn(future);
+		#[cfg(target_family = "wasm")]
+		wasm_bindgen_futures::spawn_local(future);
+	}
+
+	/// Wait for the duration to elapse on the runtime of the connection
+	pub(crate) async fn sleep(&self, duration: Duration) {
+		#[cfg(not(target_family = "wasm"))]
+		self.config.sleep(duration).await;
+		#[cfg(target_family = "wasm")]
+		wasmtimer::tokio::sleep(duration).await;
+	}
+
+	/// The record cache, if it was enabled in the config
+	pub(crate) fn record_cache(&self) -> Option<&RecordCache> {
+		let config = self.config.record_cache.as_ref()?;
+		Some(self.cache.get_or_init(|| RecordCache::new(config)))
+	}
+
 	pub(crate) fn send(
 		&self,
 		command: Command,
@@ -69,6 +111,164 @@ impl Router {
 		})
 	}
 
+	/// Send a command and wait for its response, re-authenticating and retrying once if the
+	/// session has expired
+	async fn dispatch<T, F>(&self, command: Command, recv: F) -> Result<T>
+	where
+		F: for<'a> Fn(&'a Self, Receiver<Result<DbResponse>>) -> BoxFuture<'a, Result<T>>,
+	{
+		if let Some(cache) = self.record_cache() {
+			cache.invalidate(&command);
+		}
+		// Keep the credentials used to sign in, so they can be used again once the session expires
+		let credentials = match &command {
+			Command::Signup {
+				credentials,
+			}
+			| Command::Signin {
+				credentials,
+			} => Some(Some(credentials.clone())),
+			Command::Authenticate {
+				..
+			}
+			| Command::Invalidate => Some(None),
+			_ => None,
+		};
+		let retry = match credentials {
+			None if self.can_reauthenticate() => Some(command.clone()),
+			_ => None,
+		};
+		let result = self.observe(command, &recv).await;
+		if let (Ok(_), Some(credentials)) = (&result, credentials) {
+			*self.credentials.lock().unwrap_or_else(|e| e.into_inner()) = credentials;
+		}
+		match (result, retry) {
+			(Err(error), Some(command)) if session_expired(&error) => {
+				self.reauthenticate(error).await?;
+				self.observe(command, &recv).await
+			}
+			(result, _) => result,
+		}
+	}
+
+	/// Send a command and wait for its response, notifying the observer if there is one
+	async fn observe<T, F>(&self, command: Command, recv: &F) -> Result<T>
+	where
+		F: for<'a> Fn(&'a Self, Receiver<Result<DbResponse>>) -> BoxFuture<'a, Result<T>>,
+	{
+		let Some(observer) = &self.config.observer else {
+			let rx = self.send(command).await?;
+			return recv(self, rx).await;
+		};
+		let method = command.method();
+		observer.0.on_start(method);
+		let start = Instant::now();
+		let result = match self.send(command).await {
+			Ok(rx) => recv(self, rx).await,
+			Err(error) => Err(error),
+		};
+		if let Err(error) = &result {
+			observer.0.on_error(method, error);
+		}
+		observer.0.on_finish(method, start.elapsed());
+		result
+	}
+
+	/// Whether a new session can be started when the current one expires
+	fn can_reauthenticate(&self) -> bool {
+		self.config.token_refresh.is_some()
+			|| self.credentials.lock().unwrap_or_else(|e| e.into_inner()).is_some()
+	}
+
+	/// Start a new session, using the token refresher or the last used credentials
+	///
+	/// The error which caused the session to be restarted is returned if there is nothing to
+	/// start a new session with.
+	async fn reauthenticate(&self, error: crate::Error) -> Result<()> {
+		let command = match &self.config.token_refresh {
+			Some(refresh) => Command::Authenticate {
+				token: refresh.0.refresh().await?.0,
+			},
+			None => {
+				let credentials =
+					self.credentials.lock().unwrap_or_else(|e| e.into_inner()).clone();
+				match credentials {
+					// Credentials used to sign up are also valid for signing in
+					Some(credentials) => Command::Signin {
+						credentials,
+					},
+					None => return Err(error),
+				}
+			}
+		};
+		self.observe(command, &Self::recv).await?;
+		Ok(())
+	}
+
+	/// Run the query interceptors, if there are any, over the statement which a method runs
+	///
+	/// The method is then sent as a query made up of the intercepted statements, so that methods
+	/// like `select` or `create` can not be used to bypass the interceptors.
+	fn intercept(&self, command: Command) -> Result<(Command, Option<bool>)> {
+		if self.config.interceptors.is_empty() {
+			return Ok((command, None));
+		}
+		let Some((statement, one)) = command.statement() else {
+			return Ok((command, None));
+		};
+		let mut statements = vec![statement];
+		let mut variables = CoreObject::default();
+		self.config.intercept(&mut statements, &mut variables)?;
+		let mut query = Query::default();
+		query.0 .0 = statements;
+		let command = Command::Query {
+			query,
+			variables,
+		};
+		Ok((command, Some(one)))
+	}
+
+	/// Send a command which returns a value, serving records selected by id from the record cache
+	async fn dispatch_value(&self, command: Command) -> Result<CoreValue> {
+		let command = match self.intercept(command)? {
+			(command, Some(one)) => {
+				let mut response = self.dispatch(command, Self::recv_query).await?;
+				let value = match response.results.swap_remove(&0) {
+					Some((_, result)) => result?,
+					None => CoreValue::None,
+				};
+				return Ok(match (one, value) {
+					(true, CoreValue::Array(mut array)) if array.len() == 1 => array.0.remove(0),
+					(true, CoreValue::Array(array)) if array.is_empty() => CoreValue::None,
+					(_, value) => value,
+				});
+			}
+			(command, None) => command,
+		};
+		let (
+			Some(cache),
+			Command::Select {
+				what: Resource::RecordId(record),
+			},
+		) = (self.record_cache(), &command)
+		else {
+			return self.dispatch(command, Self::recv).await;
+		};
+		let record = record.clone().into_inner();
+		if let Some(value) = cache.get(&record) {
+			return Ok(value);
+		}
+		// Register the fetch first, so that an eviction while the record is in flight, from a
+		// notification or a write through this client, stops the fetched value from being cached
+		let table = record.tb.clone();
+		let fetch = cache.fetch(record);
+		// The live query is started before the record is selected, so later changes are reported
+		cache.watch(self, &table).await;
+		let value = self.dispatch(command, Self::recv).await?;
+		fetch.insert(value.clone());
+		Ok(value)
+	}
+
 	/// Receive responses for all methods except `query`
 	pub(crate) fn recv(
 		&self,
@@ -78,7 +278,7 @@ impl Router {
 			let response = receiver.recv().await?;
 			match response? {
 				DbResponse::Other(value) => Ok(value),
-				DbResponse::Query(..) => unreachable!(),
+				DbResponse::Query(..) | DbResponse::Raw(..) => unreachable!(),
 			}
 		})
 	}
@@ -92,7 +292,21 @@ impl Router {
 			let response = receiver.recv().await?;
 			match response? {
 				DbResponse::Query(results) => Ok(results),
-				DbResponse::Other(..) => unreachable!(),
+				DbResponse::Other(..) | DbResponse::Raw(..) => unreachable!(),
+			}
+		})
+	}
+
+	/// Receive the undecoded response of the `query` method
+	pub(crate) fn recv_raw(
+		&self,
+		receiver: Receiver<Result<DbResponse>>,
+	) -> BoxFuture<'_, Result<Vec<u8>>> {
+		Box::pin(async move {
+			let response = receiver.recv().await?;
+			match response? {
+				DbResponse::Raw(bytes) => Ok(bytes),
+				DbResponse::Query(..) | DbResponse::Other(..) => unreachable!(),
 			}
 		})
 	}
@@ -103,8 +317,7 @@ impl Router {
 		R: DeserializeOwned,
 	{
 		Box::pin(async move {
-			let rx = self.send(command).await?;
-			let value = self.recv(rx).await?;
+			let value = self.dispatch_value(command).await?;
 			from_core_value(value).map_err(Into::into)
 		})
 	}
@@ -115,8 +328,7 @@ impl Router {
 		R: DeserializeOwned,
 	{
 		Box::pin(async move {
-			let rx = self.send(command).await?;
-			match self.recv(rx).await? {
+			match self.dispatch_value(command).await? {
 				CoreValue::None | CoreValue::Null => Ok(None),
 				value => from_core_value(value).map_err(Into::into),
 			}
@@ -129,8 +341,7 @@ impl Router {
 		R: DeserializeOwned,
 	{
 		Box::pin(async move {
-			let rx = self.send(command).await?;
-			let value = match self.recv(rx).await? {
+			let value = match self.dispatch_value(command).await? {
 				CoreValue::None | CoreValue::Null => return Ok(Vec::new()),
 				CoreValue::Array(array) => CoreValue::Array(array),
 				value => vec![value].into(),
@@ -142,8 +353,7 @@ impl Router {
 	/// Execute methods that return nothing
 	pub(crate) fn execute_unit(&self, command: Command) -> BoxFuture<'_, Result<()>> {
 		Box::pin(async move {
-			let rx = self.send(command).await?;
-			match self.recv(rx).await? {
+			match self.dispatch(command, Self::recv).await? {
 				CoreValue::None | CoreValue::Null => Ok(()),
 				CoreValue::Array(array) if array.is_empty() => Ok(()),
 				value => Err(Error::FromValue {
@@ -157,19 +367,38 @@ impl Router {
 
 	/// Execute methods that return a raw value
 	pub(crate) fn execute_value(&self, command: Command) -> BoxFuture<'_, Result<Value>> {
-		Box::pin(async move {
-			let rx = self.send(command).await?;
-			Ok(Value::from_inner(self.recv(rx).await?))
-		})
+		Box::pin(async move { Ok(Value::from_inner(self.dispatch_value(command).await?)) })
 	}
 
 	/// Execute the `query` method
 	pub(crate) fn execute_query(&self, command: Command) -> BoxFuture<'_, Result<Response>> {
 		Box::pin(async move {
-			let rx = self.send(command).await?;
-			self.recv_query(rx).await
+			let (command, _) = self.intercept(command)?;
+			self.dispatch(command, Self::recv_query).await
 		})
 	}
+
+	/// Execute the `query` method, returning the undecoded response
+	pub(crate) fn execute_raw(&self, command: Command) -> BoxFuture<'_, Result<Vec<u8>>> {
+		Box::pin(async move { self.dispatch(command, Self::recv_raw).await })
+	}
+}
+
+/// Whether a request failed because the session or token of the connection has expired
+fn session_expired(error: &crate::Error) -> bool {
+	match error {
+		crate::Error::Db(DbError::ExpiredSession | DbError::ExpiredToken) => true,
+		crate::Error::Api(Error::Remote {
+			details,
+			..
+		}) => matches!(details.code.as_str(), "expired_session" | "expired_token"),
+		// Older remote servers only send the error message
+		crate::Error::Api(Error::Query(message)) => {
+			message.ends_with(&DbError::ExpiredSession.to_string())
+				|| message.ends_with(&DbError::ExpiredToken.to_string())
+		}
+		_ => false,
+	}
 }
 
 /// The database response sent from the router to the caller
@@ -179,6 +408,8 @@ pub enum DbResponse {
 	Query(Response),
 	/// The response sent for any method except `query`
 	Other(CoreValue),
+	/// The undecoded response sent for the `query` method
+	Raw(Vec<u8>),
 }
 
 #[derive(Debug, Clone)]
@@ -258,6 +258,9 @@ impl Surreal<Any> {
 /// // Connect using HTTPS
 /// let db = connect("https://cloud.surrealdb.com").await?;
 ///
+/// // Connect to a server listening on a Unix domain socket
+/// let db = connect("unix:///var/run/surrealdb.sock").await?;
+///
 /// // Instantiate an in-memory instance
 /// let db = connect("mem://").await?;
 ///
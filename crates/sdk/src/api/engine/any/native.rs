@@ -22,6 +22,7 @@ use crate::opt::WaitFor;
 use reqwest::ClientBuilder;
 use std::collections::HashSet;
 use std::sync::atomic::AtomicI64;
+use std::sync::OnceLock;
 use tokio::sync::watch;
 #[cfg(feature = "protocol-ws")]
 use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
@@ -169,7 +170,15 @@ impl Connection for Any {
 					.into());
 				}
 
-				EndpointKind::Ws | EndpointKind::Wss => {
+				EndpointKind::Ws | EndpointKind::Wss | EndpointKind::Unix => {
+					#[cfg(all(feature = "protocol-ws", not(unix)))]
+					if let EndpointKind::Unix = EndpointKind::from(address.url.scheme()) {
+						return Err(DbError::Ds(
+							"Unix domain sockets are not supported on this platform".to_owned(),
+						)
+						.into());
+					}
+
 					#[cfg(feature = "protocol-ws")]
 					{
 						features.insert(ExtraFeatures::LiveQueries);
@@ -192,7 +201,7 @@ impl Connection for Any {
 							maybe_connector.clone(),
 						)
 						.await?;
-						tokio::spawn(engine::remote::ws::native::run_router(
+						config.spawn(engine::remote::ws::native::run_router(
 							endpoint,
 							maybe_connector,
 							capacity,
@@ -217,6 +226,8 @@ impl Connection for Any {
 				config,
 				sender: route_tx,
 				last_id: AtomicI64::new(0),
+				cache: OnceLock::new(),
+				credentials: Default::default(),
 			};
 
 			Ok((router, waiter).into())
@@ -13,6 +13,7 @@ use crate::error::Db as DbError;
 use crate::opt::WaitFor;
 use std::collections::HashSet;
 use std::sync::atomic::AtomicI64;
+use std::sync::OnceLock;
 use tokio::sync::watch;
 use wasm_bindgen_futures::spawn_local;
 
@@ -167,6 +168,14 @@ impl Connection for Any {
 					.into());
 				}
 
+				EndpointKind::Unix => {
+					return Err(DbError::Ds(
+						"Cannot connect to a Unix domain socket from a WebAssembly build of SurrealDB"
+							.to_owned(),
+					)
+					.into());
+				}
+
 				EndpointKind::Unsupported(v) => return Err(Error::Scheme(v).into()),
 			}
 
@@ -176,6 +185,8 @@ impl Connection for Any {
 				config,
 				sender: route_tx,
 				last_id: AtomicI64::new(0),
+				cache: OnceLock::new(),
+				credentials: Default::default(),
 			};
 
 			Ok((router, waiter).into())
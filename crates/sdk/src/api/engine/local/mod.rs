@@ -26,11 +26,9 @@ use crate::{
 		conn::{Command, DbResponse, RequestData},
 		Connect, Response as QueryResponse, Result, Surreal,
 	},
-	method::Stats,
+	method::{live::NotificationSender, Stats},
 	opt::{IntoEndpoint, Table},
-	value::Notification,
 };
-use async_channel::Sender;
 #[cfg(not(target_family = "wasm"))]
 use futures::stream::poll_fn;
 use indexmap::IndexMap;
@@ -51,6 +49,7 @@ use surrealdb_core::{
 	dbs::{Response, Session},
 	iam,
 	kvs::Datastore,
+	rpc::{format::Format, Data as RpcData},
 	sql::{
 		statements::{
 			CreateStatement, DeleteStatement, InsertStatement, KillStatement, SelectStatement,
@@ -64,6 +63,8 @@ use tokio::sync::RwLock;
 use tokio_util::bytes::BytesMut;
 use uuid::Uuid;
 
+#[cfg(not(target_family = "wasm"))]
+use futures::StreamExt;
 #[cfg(not(target_family = "wasm"))]
 use std::{future::Future, path::PathBuf};
 #[cfg(not(target_family = "wasm"))]
@@ -80,8 +81,6 @@ use surrealdb_core::sql::Model;
 #[cfg(all(not(target_family = "wasm"), feature = "ml"))]
 use crate::api::conn::MlExportConfig;
 #[cfg(all(not(target_family = "wasm"), feature = "ml"))]
-use futures::StreamExt;
-#[cfg(all(not(target_family = "wasm"), feature = "ml"))]
 use surrealdb_core::{
 	iam::{check::check_ns_db, Action, ResourceKind},
 	kvs::{LockType, TransactionType},
@@ -96,7 +95,7 @@ pub(crate) mod native;
 #[cfg(target_family = "wasm")]
 pub(crate) mod wasm;
 
-type LiveQueryMap = HashMap<Uuid, Sender<Notification<CoreValue>>>;
+type LiveQueryMap = HashMap<Uuid, NotificationSender>;
 
 /// In-memory database
 ///
@@ -805,6 +804,38 @@ async fn router(
 			let response = process(response);
 			Ok(DbResponse::Query(response))
 		}
+		Command::RawResponse {
+			command,
+		} => {
+			let mut vars = vars.read().await.clone();
+			let response = match *command {
+				Command::Query {
+					query,
+					mut variables,
+				} => {
+					vars.append(&mut variables.0);
+					kvs.process(query, &*session.read().await, Some(vars)).await?
+				}
+				Command::RawQuery {
+					query,
+					mut variables,
+				} => {
+					vars.append(&mut variables.0);
+					kvs.execute(query.as_ref(), &*session.read().await, Some(vars)).await?
+				}
+				_ => {
+					return Err(Error::InternalError(
+						"only queries can return raw responses".to_owned(),
+					)
+					.into())
+				}
+			};
+			// There is no server, so encode the responses like the server would
+			let bytes = Format::Revision
+				.res(RpcData::Query(response))
+				.map_err(|error| Error::InternalError(error.to_string()))?;
+			Ok(DbResponse::Raw(bytes))
+		}
 
 		#[cfg(target_family = "wasm")]
 		Command::ExportFile {
@@ -815,6 +846,9 @@ async fn router(
 		}
 		| Command::ImportFile {
 			..
+		}
+		| Command::ImportBytes {
+			..
 		} => Err(crate::api::Error::BackupsNotSupported.into()),
 
 		#[cfg(any(target_family = "wasm", not(feature = "ml")))]
@@ -1031,6 +1065,25 @@ async fn router(
 
 			Ok(DbResponse::Other(CoreValue::None))
 		}
+		#[cfg(not(target_family = "wasm"))]
+		Command::ImportBytes {
+			bytes,
+		} => {
+			let stream = bytes.map(|chunk| match chunk {
+				Ok(chunk) => Ok(chunk.into()),
+				Err(error) => Err(CoreError::QueryStream(error.to_string())),
+			});
+
+			let responses = kvs
+				.execute_import(&*session.read().await, Some(vars.read().await.clone()), stream)
+				.await?;
+
+			for response in responses {
+				response.result?;
+			}
+
+			Ok(DbResponse::Other(CoreValue::None))
+		}
 		#[cfg(all(not(target_family = "wasm"), feature = "ml"))]
 		Command::ImportMl {
 			path,
@@ -1100,6 +1153,21 @@ async fn router(
 		Command::Version => {
 			Ok(DbResponse::Other(CoreValue::from(surrealdb_core::env::VERSION.to_string())))
 		}
+		Command::ServerInfo => {
+			let version = surrealdb_core::env::VERSION.to_string().into();
+			let info = kvs.server_info(&*session.read().await, version);
+			Ok(DbResponse::Other(info))
+		}
+		Command::Compact => {
+			// Check the permissions level
+			kvs.check(&*session.read().await, Action::Edit, ResourceKind::Any.on_root())?;
+			let compaction = kvs.compact().await?;
+			let info = BTreeMap::from([
+				("before".to_string(), compaction.before.into()),
+				("after".to_string(), compaction.after.into()),
+			]);
+			Ok(DbResponse::Other(info.into()))
+		}
 		Command::Set {
 			key,
 			value,
@@ -1122,6 +1190,7 @@ async fn router(
 			vars.write().await.remove(&key);
 			Ok(DbResponse::Other(CoreValue::None))
 		}
+		Command::Params => Ok(DbResponse::Other(CoreValue::from(vars.read().await.clone()))),
 		Command::SubscribeLive {
 			uuid,
 			notification_sender,
@@ -15,7 +15,7 @@ use async_channel::{Receiver, Sender};
 use futures::{stream::poll_fn, StreamExt};
 use std::{
 	collections::{BTreeMap, HashMap, HashSet},
-	sync::{atomic::AtomicI64, Arc},
+	sync::{atomic::AtomicI64, Arc, OnceLock},
 	task::Poll,
 };
 use surrealdb_core::{dbs::Session, iam::Level, kvs::Datastore, options::EngineOptions};
@@ -49,6 +49,8 @@ impl Connection for Db {
 				config,
 				sender: route_tx,
 				last_id: AtomicI64::new(0),
+				cache: OnceLock::new(),
+				credentials: Default::default(),
 			};
 
 			Ok((router, waiter).into())
@@ -109,6 +111,7 @@ pub(crate) async fn run_router(
 		.with_strict_mode(address.config.strict)
 		.with_query_timeout(address.config.query_timeout)
 		.with_transaction_timeout(address.config.transaction_timeout)
+		.with_retry_policy(address.config.retry_policy)
 		.with_capabilities(address.config.capabilities);
 
 	#[cfg(storage)]
@@ -117,7 +120,12 @@ pub(crate) async fn run_router(
 	let kvs = Arc::new(kvs);
 	let vars = Arc::new(RwLock::new(BTreeMap::default()));
 	let live_queries = Arc::new(RwLock::new(HashMap::new()));
-	let session = Arc::new(RwLock::new(Session::default().with_rt(true)));
+	let session = Arc::new(RwLock::new(
+		Session::default()
+			.with_rt(true)
+			.with_transactions()
+			.with_durability(address.config.durability.unwrap_or_default()),
+	));
 
 	let canceller = CancellationToken::new();
 
@@ -134,6 +142,9 @@ pub(crate) async fn run_router(
 	if let Some(interval) = address.config.changefeed_gc_interval {
 		opt.changefeed_gc_interval = interval;
 	}
+	if let Some(interval) = address.config.event_process_interval {
+		opt.event_process_interval = interval;
+	}
 	let tasks = tasks::init(kvs.clone(), canceller.clone(), &opt);
 
 	let mut notifications = kvs.notifications().map(Box::pin);
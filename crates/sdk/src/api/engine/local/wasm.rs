@@ -24,6 +24,7 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::sync::atomic::AtomicI64;
 use std::sync::Arc;
+use std::sync::OnceLock;
 use std::task::Poll;
 use tokio::sync::watch;
 use tokio::sync::RwLock;
@@ -56,6 +57,8 @@ impl Connection for Db {
 				config,
 				sender: route_tx,
 				last_id: AtomicI64::new(0),
+				cache: OnceLock::new(),
+				credentials: Default::default(),
 			};
 
 			Ok((router, waiter).into())
@@ -107,12 +110,18 @@ pub(crate) async fn run_router(
 		.with_strict_mode(address.config.strict)
 		.with_query_timeout(address.config.query_timeout)
 		.with_transaction_timeout(address.config.transaction_timeout)
+		.with_retry_policy(address.config.retry_policy)
 		.with_capabilities(address.config.capabilities);
 
 	let kvs = Arc::new(kvs);
 	let vars = Arc::new(RwLock::new(BTreeMap::new()));
 	let live_queries = Arc::new(RwLock::new(HashMap::new()));
-	let session = Arc::new(RwLock::new(Session::default().with_rt(true)));
+	let session = Arc::new(RwLock::new(
+		Session::default()
+			.with_rt(true)
+			.with_transactions()
+			.with_durability(address.config.durability.unwrap_or_default()),
+	));
 
 	let canceller = CancellationToken::new();
 
@@ -129,6 +138,9 @@ pub(crate) async fn run_router(
 	if let Some(interval) = address.config.changefeed_gc_interval {
 		opt.changefeed_gc_interval = interval;
 	}
+	if let Some(interval) = address.config.event_process_interval {
+		opt.event_process_interval = interval;
+	}
 	let tasks = tasks::init(kvs.clone(), canceller.clone(), &opt);
 
 	let mut notifications = kvs.notifications().map(Box::pin);
@@ -1,13 +1,17 @@
 use revision::revisioned;
 use serde::Deserialize;
+use surrealdb_core::err::ErrorDetails;
 
 use crate::Value;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Failure {
 	pub(crate) code: i64,
 	pub(crate) message: String,
+	#[revision(start = 2)]
+	#[serde(skip)]
+	pub(crate) details: Option<ErrorDetails>,
 }
 
 #[revisioned(revision = 1)]
@@ -30,7 +34,7 @@ pub enum Status {
 	Err,
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Debug, Deserialize)]
 #[doc(hidden)]
 #[non_exhaustive]
@@ -38,4 +42,7 @@ pub struct QueryMethodResponse {
 	pub time: String,
 	pub status: Status,
 	pub result: Value,
+	#[revision(start = 2)]
+	#[serde(skip)]
+	pub error: Option<ErrorDetails>,
 }
@@ -13,6 +13,7 @@ use crate::headers::AUTH_DB;
 use crate::headers::AUTH_NS;
 use crate::headers::DB;
 use crate::headers::NS;
+use crate::opt::Config;
 use crate::opt::IntoEndpoint;
 use crate::Value;
 use futures::TryStreamExt;
@@ -218,8 +219,6 @@ async fn export_bytes(request: RequestBuilder, bytes: BackupSender) -> Result<()
 
 #[cfg(not(target_family = "wasm"))]
 async fn import(request: RequestBuilder, path: PathBuf) -> Result<()> {
-	use crate::engine::proto::{QueryMethodResponse, Status};
-
 	let file = match OpenOptions::new().read(true).open(&path).await {
 		Ok(path) => path,
 		Err(error) => {
@@ -231,7 +230,14 @@ async fn import(request: RequestBuilder, path: PathBuf) -> Result<()> {
 		}
 	};
 
-	let res = request.header(ACCEPT, "application/surrealdb").body(file).send().await?;
+	import_body(request, file.into()).await
+}
+
+#[cfg(not(target_family = "wasm"))]
+async fn import_body(request: RequestBuilder, body: reqwest::Body) -> Result<()> {
+	use crate::engine::proto::{QueryMethodResponse, Status};
+
+	let res = request.header(ACCEPT, "application/surrealdb").body(body).send().await?;
 
 	if res.error_for_status_ref().is_err() {
 		let res = res.text().await?;
@@ -252,7 +258,15 @@ async fn import(request: RequestBuilder, path: PathBuf) -> Result<()> {
 		let response: Vec<QueryMethodResponse> = deserialize(&res.bytes().await?, false)?;
 		for res in response {
 			if let Status::Err = res.status {
-				return Err(Error::Query(res.result.0.as_string()).into());
+				let message = res.result.0.as_string();
+				return Err(match res.error {
+					Some(details) => Error::Remote {
+						message,
+						details,
+					},
+					None => Error::Query(message),
+				}
+				.into());
 			}
 		}
 	}
@@ -265,18 +279,34 @@ pub(crate) async fn health(request: RequestBuilder) -> Result<()> {
 	Ok(())
 }
 
-async fn send_request(
+async fn send_raw_request(
 	req: RouterRequest,
 	base_url: &Url,
 	client: &reqwest::Client,
 	headers: &HeaderMap,
 	auth: &Option<Auth>,
-) -> Result<DbResponse> {
+	config: &Config,
+) -> Result<Vec<u8>> {
 	let url = base_url.join(RPC_PATH).unwrap();
-	let http_req =
-		client.post(url).headers(headers.clone()).auth(auth).body(serialize(&req, false)?);
+	let req = req.with_durability(config.durability);
+	let body = serialize(&req, false)?;
+	config.observe_sent(body.len());
+	let http_req = client.post(url).headers(headers.clone()).auth(auth).body(body);
 	let response = http_req.send().await?.error_for_status()?;
-	let bytes = response.bytes().await?;
+	let bytes = response.bytes().await?.to_vec();
+	config.observe_received(bytes.len());
+	Ok(bytes)
+}
+
+async fn send_request(
+	req: RouterRequest,
+	base_url: &Url,
+	client: &reqwest::Client,
+	headers: &HeaderMap,
+	auth: &Option<Auth>,
+	config: &Config,
+) -> Result<DbResponse> {
+	let bytes = send_raw_request(req, base_url, client, headers, auth, config).await?;
 
 	let response: Response = deserialize(&bytes, false)?;
 	DbResponse::from_server_result(response.result)
@@ -299,6 +329,7 @@ async fn router(
 	headers: &mut HeaderMap,
 	vars: &mut IndexMap<String, CoreValue>,
 	auth: &mut Option<Auth>,
+	config: &Config,
 ) -> Result<DbResponse> {
 	match req.command {
 		Command::Query {
@@ -312,7 +343,27 @@ async fn router(
 			}
 			.into_router_request(None)
 			.expect("query should be valid request");
-			send_request(req, base_url, client, headers, auth).await
+			send_request(req, base_url, client, headers, auth, config).await
+		}
+		Command::RawResponse {
+			command,
+		} => {
+			let command = match *command {
+				Command::Query {
+					query,
+					mut variables,
+				} => {
+					variables.extend(vars.clone());
+					Command::Query {
+						query,
+						variables,
+					}
+				}
+				command => command,
+			};
+			let req = command.into_router_request(None).expect("query should be valid request");
+			let bytes = send_raw_request(req, base_url, client, headers, auth, config).await?;
+			Ok(DbResponse::Raw(bytes))
 		}
 		Command::Use {
 			namespace,
@@ -325,7 +376,7 @@ async fn router(
 			.into_router_request(None)
 			.unwrap();
 			// process request to check permissions
-			let out = send_request(req, base_url, client, headers, auth).await?;
+			let out = send_request(req, base_url, client, headers, auth, config).await?;
 			if let Some(ns) = namespace {
 				let value =
 					HeaderValue::try_from(&ns).map_err(|_| Error::InvalidNsName(ns.to_owned()))?;
@@ -349,7 +400,7 @@ async fn router(
 			.expect("signin should be a valid router request");
 
 			let DbResponse::Other(value) =
-				send_request(req, base_url, client, headers, auth).await?
+				send_request(req, base_url, client, headers, auth, config).await?
 			else {
 				return Err(Error::InternalError(
 					"recieved invalid result from server".to_string(),
@@ -386,7 +437,7 @@ async fn router(
 			}
 			.into_router_request(None)
 			.expect("authenticate should be a valid router request");
-			send_request(req, base_url, client, headers, auth).await?;
+			send_request(req, base_url, client, headers, auth, config).await?;
 
 			*auth = Some(Auth::Bearer {
 				token,
@@ -413,7 +464,7 @@ async fn router(
 			.into_router_request(None)
 			.expect("query is valid request");
 			let DbResponse::Query(mut res) =
-				send_request(req, base_url, client, headers, auth).await?
+				send_request(req, base_url, client, headers, auth, config).await?
 			else {
 				return Err(Error::InternalError(
 					"recieved invalid result from server".to_string(),
@@ -431,6 +482,10 @@ async fn router(
 			vars.shift_remove(&key);
 			Ok(DbResponse::Other(CoreValue::None))
 		}
+		Command::Params => {
+			let params: CoreObject = vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+			Ok(DbResponse::Other(params.into()))
+		}
 		#[cfg(target_family = "wasm")]
 		Command::ExportFile {
 			..
@@ -441,6 +496,9 @@ async fn router(
 		| Command::ImportFile {
 			..
 		}
+		| Command::ImportBytes {
+			..
+		}
 		| Command::ImportMl {
 			..
 		} => {
@@ -526,6 +584,20 @@ async fn router(
 			Ok(DbResponse::Other(CoreValue::None))
 		}
 		#[cfg(not(target_family = "wasm"))]
+		Command::ImportBytes {
+			bytes,
+		} => {
+			let req_path = base_url.join("import")?;
+			let request = client
+				.post(req_path)
+				.headers(headers.clone())
+				.auth(auth)
+				.header(CONTENT_TYPE, "application/octet-stream");
+			let body = bytes.map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()));
+			import_body(request, reqwest::Body::wrap_stream(body)).await?;
+			Ok(DbResponse::Other(CoreValue::None))
+		}
+		#[cfg(not(target_family = "wasm"))]
 		Command::ImportMl {
 			path,
 		} => {
@@ -544,7 +616,7 @@ async fn router(
 		cmd => {
 			let needs_flatten = cmd.needs_flatten();
 			let req = cmd.into_router_request(None).unwrap();
-			let mut res = send_request(req, base_url, client, headers, auth).await?;
+			let mut res = send_request(req, base_url, client, headers, auth, config).await?;
 			if needs_flatten {
 				res = flatten_dbresponse_array(res);
 			}
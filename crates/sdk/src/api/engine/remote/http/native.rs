@@ -3,6 +3,7 @@ use crate::api::conn::Connection;
 use crate::api::conn::Route;
 use crate::api::conn::Router;
 use crate::api::method::BoxFuture;
+use crate::api::opt::Config;
 use crate::api::opt::Endpoint;
 #[cfg(any(feature = "native-tls", feature = "rustls"))]
 use crate::api::opt::Tls;
@@ -16,6 +17,7 @@ use reqwest::header::HeaderMap;
 use reqwest::ClientBuilder;
 use std::collections::HashSet;
 use std::sync::atomic::AtomicI64;
+use std::sync::OnceLock;
 use tokio::sync::watch;
 use url::Url;
 
@@ -30,6 +32,23 @@ impl Connection for Client {
 			#[allow(unused_mut)]
 			let mut builder = ClientBuilder::new().default_headers(headers);
 
+			let http = &address.config.http;
+			if let Some(interval) = http.tcp_keep_alive {
+				builder = builder.tcp_keepalive(interval);
+			}
+			if let Some(interval) = http.http2_keep_alive {
+				builder = builder.http2_keep_alive_interval(interval);
+			}
+			if http.http2_only {
+				builder = builder.http2_prior_knowledge();
+			}
+			if let Some(max) = http.max_idle_connections {
+				builder = builder.pool_max_idle_per_host(max);
+			}
+			if let Some(enabled) = http.tcp_nodelay {
+				builder = builder.tcp_nodelay(enabled);
+			}
+
 			#[cfg(any(feature = "native-tls", feature = "rustls"))]
 			if let Some(tls) = address.config.tls_config {
 				builder = match tls {
@@ -51,7 +70,7 @@ impl Connection for Client {
 				capacity => async_channel::bounded(capacity),
 			};
 
-			tokio::spawn(run_router(base_url, client, route_rx));
+			tokio::spawn(run_router(base_url, client, config.clone(), route_rx));
 
 			let mut features = HashSet::new();
 			features.insert(ExtraFeatures::Backup);
@@ -62,6 +81,8 @@ impl Connection for Client {
 				config,
 				sender: route_tx,
 				last_id: AtomicI64::new(0),
+				cache: OnceLock::new(),
+				credentials: Default::default(),
 			};
 
 			Ok((router, waiter).into())
@@ -69,15 +90,27 @@ impl Connection for Client {
 	}
 }
 
-pub(crate) async fn run_router(base_url: Url, client: reqwest::Client, route_rx: Receiver<Route>) {
+pub(crate) async fn run_router(
+	base_url: Url,
+	client: reqwest::Client,
+	config: Config,
+	route_rx: Receiver<Route>,
+) {
 	let mut headers = HeaderMap::new();
 	let mut vars = IndexMap::new();
 	let mut auth = None;
 
 	while let Ok(route) = route_rx.recv().await {
-		let result =
-			super::router(route.request, &base_url, &client, &mut headers, &mut vars, &mut auth)
-				.await;
+		let result = super::router(
+			route.request,
+			&base_url,
+			&client,
+			&mut headers,
+			&mut vars,
+			&mut auth,
+			&config,
+		)
+		.await;
 		let _ = route.response.send(result).await;
 	}
 }
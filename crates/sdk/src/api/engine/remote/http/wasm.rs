@@ -13,6 +13,7 @@ use reqwest::header::HeaderMap;
 use reqwest::ClientBuilder;
 use std::collections::HashSet;
 use std::sync::atomic::AtomicI64;
+use std::sync::OnceLock;
 use tokio::sync::watch;
 use url::Url;
 use wasm_bindgen_futures::spawn_local;
@@ -40,6 +41,8 @@ impl Connection for Client {
 				config,
 				sender: route_tx,
 				last_id: AtomicI64::new(0),
+				cache: OnceLock::new(),
+				credentials: Default::default(),
 			};
 
 			Ok((router, waiter).into())
@@ -62,6 +65,7 @@ pub(crate) async fn run_router(
 	route_rx: Receiver<Route>,
 ) {
 	let base_url = address.url;
+	let config = address.config;
 
 	let client = match client(&base_url).await {
 		Ok(client) => {
@@ -79,8 +83,16 @@ pub(crate) async fn run_router(
 	let mut auth = None;
 
 	while let Ok(route) = route_rx.recv().await {
-		match super::router(route.request, &base_url, &client, &mut headers, &mut vars, &mut auth)
-			.await
+		match super::router(
+			route.request,
+			&base_url,
+			&client,
+			&mut headers,
+			&mut vars,
+			&mut auth,
+			&config,
+		)
+		.await
 		{
 			Ok(value) => {
 				let _ = route.response.send(Ok(value)).await;
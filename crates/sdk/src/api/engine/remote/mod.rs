@@ -19,6 +19,7 @@ use rust_decimal::Decimal;
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use std::time::Duration;
+use surrealdb_core::err::ErrorDetails;
 use surrealdb_core::sql::Value as CoreValue;
 
 const NANOS_PER_SEC: i64 = 1_000_000_000;
@@ -78,11 +79,14 @@ mod tests {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Failure {
 	pub(crate) code: i64,
 	pub(crate) message: String,
+	#[revision(start = 2)]
+	#[serde(skip)]
+	pub(crate) details: Option<ErrorDetails>,
 }
 
 #[revisioned(revision = 1)]
@@ -97,6 +101,12 @@ type ServerResult = std::result::Result<Data, Failure>;
 
 impl From<Failure> for Error {
 	fn from(failure: Failure) -> Self {
+		if let Some(details) = failure.details {
+			return Self::Remote {
+				message: failure.message,
+				details,
+			};
+		}
 		match failure.code {
 			-32600 => Self::InvalidRequest(failure.message),
 			-32602 => Self::InvalidParams(failure.message),
@@ -131,10 +141,16 @@ impl DbResponse {
 							map.insert(index, (stats, Ok(response.result)));
 						}
 						Status::Err => {
-							map.insert(
-								index,
-								(stats, Err(Error::Query(response.result.as_raw_string()).into())),
-							);
+							let message = response.result.as_raw_string();
+							let error = match response.error {
+								Some(details) => Error::Remote {
+									message,
+									details,
+								},
+								// Older servers only send the error message
+								None => Error::Query(message),
+							};
+							map.insert(index, (stats, Err(error.into())));
 						}
 						_ => unreachable!(),
 					}
@@ -10,8 +10,8 @@ use crate::api::conn::DbResponse;
 use crate::api::Connect;
 use crate::api::Result;
 use crate::api::Surreal;
+use crate::method::live::NotificationSender;
 use crate::opt::IntoEndpoint;
-use crate::value::Notification;
 use async_channel::Sender;
 use indexmap::IndexMap;
 use std::collections::HashMap;
@@ -23,7 +23,10 @@ use uuid::Uuid;
 
 pub(crate) const PATH: &str = "rpc";
 const PING_INTERVAL: Duration = Duration::from_secs(5);
-const REVISION_HEADER: &str = "revision";
+/// The protocols offered to the server, in order of preference. Servers which do
+/// not support `revision-2` select `revision`, and never send any error details.
+const REVISION_PROTOCOLS: [&str; 2] = ["revision-2", "revision"];
+const REVISION_HEADER: &str = "revision-2, revision";
 
 enum RequestEffect {
 	/// Completing this request sets a variable to a give value.
@@ -37,6 +40,8 @@ enum RequestEffect {
 	},
 	/// Insert requests repsonses need to be flattened in an array.
 	Insert,
+	/// The response is forwarded without decoding it.
+	Raw,
 	/// No effect
 	None,
 }
@@ -63,7 +68,7 @@ struct RouterState<Sink, Stream> {
 	/// Messages which aught to be replayed on a reconnect.
 	replay: IndexMap<ReplayMethod, Command>,
 	/// Pending live queries
-	live_queries: HashMap<Uuid, async_channel::Sender<Notification<CoreValue>>>,
+	live_queries: HashMap<Uuid, NotificationSender>,
 	/// Send requests which are still awaiting an awnser.
 	pending_requests: HashMap<i64, PendingRequest>,
 	/// The last time a message was recieved from the server.
@@ -103,6 +108,15 @@ pub struct Ws;
 #[derive(Debug)]
 pub struct Wss;
 
+/// The Unix scheme used to connect to `unix://` endpoints
+///
+/// This connects to a server listening on a Unix domain socket, which avoids the
+/// overhead of TCP and TLS when the client and the server are on the same host.
+#[cfg(unix)]
+#[cfg_attr(docsrs, doc(cfg(unix)))]
+#[derive(Debug)]
+pub struct Unix;
+
 /// A WebSocket client for communicating with the server via WebSockets
 #[derive(Debug, Clone)]
 pub struct Client(());
@@ -10,29 +10,36 @@ use crate::api::engine::remote::{deserialize, serialize};
 use crate::api::err::Error;
 use crate::api::method::BoxFuture;
 use crate::api::opt::Endpoint;
+#[cfg(unix)]
+use crate::api::opt::EndpointKind;
+use crate::api::opt::RuntimeHandle;
 #[cfg(any(feature = "native-tls", feature = "rustls"))]
 use crate::api::opt::Tls;
 use crate::api::ExtraFeatures;
 use crate::api::Result;
 use crate::api::Surreal;
 use crate::engine::remote::Data;
-use crate::engine::IntervalStream;
+use crate::opt::Config;
 use crate::opt::WaitFor;
 use crate::{Action, Notification};
 use async_channel::Receiver;
 use futures::stream::{SplitSink, SplitStream};
 use futures::SinkExt;
 use futures::StreamExt;
+use futures::{Sink, Stream};
 use revision::revisioned;
 use serde::Deserialize;
 use std::collections::hash_map::Entry;
 use std::collections::HashSet;
 use std::sync::atomic::AtomicI64;
+use std::sync::OnceLock;
+use std::time::Duration;
+use surrealdb_core::sql::Object as CoreObject;
 use surrealdb_core::sql::Value as CoreValue;
 use tokio::net::TcpStream;
+#[cfg(unix)]
+use tokio::net::UnixStream;
 use tokio::sync::watch;
-use tokio::time;
-use tokio::time::MissedTickBehavior;
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 use tokio_tungstenite::tungstenite::error::Error as WsError;
 use tokio_tungstenite::tungstenite::http::header::SEC_WEBSOCKET_PROTOCOL;
@@ -42,6 +49,7 @@ use tokio_tungstenite::tungstenite::Message;
 use tokio_tungstenite::Connector;
 use tokio_tungstenite::MaybeTlsStream;
 use tokio_tungstenite::WebSocketStream;
+use tokio_util::compat::FuturesAsyncReadCompatExt;
 use trice::Instant;
 
 pub(crate) const MAX_MESSAGE_SIZE: usize = 64 << 20; // 64 MiB
@@ -50,8 +58,29 @@ pub(crate) const WRITE_BUFFER_SIZE: usize = 128000; // tungstenite default
 pub(crate) const MAX_WRITE_BUFFER_SIZE: usize = WRITE_BUFFER_SIZE + MAX_MESSAGE_SIZE; // Recommended max according to tungstenite docs
 pub(crate) const NAGLE_ALG: bool = false;
 
-type MessageSink = SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>;
-type MessageStream = SplitStream<WebSocketStream<MaybeTlsStream<TcpStream>>>;
+/// A WebSocket connection, over whichever stream it was opened on
+pub(crate) trait Transport:
+	Sink<Message, Error = WsError>
+	+ Stream<Item = std::result::Result<Message, WsError>>
+	+ Send
+	+ Sync
+	+ Unpin
+{
+}
+
+impl<T> Transport for T where
+	T: Sink<Message, Error = WsError>
+		+ Stream<Item = std::result::Result<Message, WsError>>
+		+ Send
+		+ Sync
+		+ Unpin
+{
+}
+
+pub(crate) type Socket = Box<dyn Transport>;
+
+type MessageSink = SplitSink<Socket, Message>;
+type MessageStream = SplitStream<Socket>;
 type RouterState = super::RouterState<MessageSink, MessageStream>;
 
 #[cfg(any(feature = "native-tls", feature = "rustls"))]
@@ -67,6 +96,54 @@ impl From<Tls> for Connector {
 }
 
 pub(crate) async fn connect(
+	endpoint: &Endpoint,
+	config: Option<WebSocketConfig>,
+	maybe_connector: Option<Connector>,
+) -> Result<Socket> {
+	#[cfg(unix)]
+	if let EndpointKind::Unix = EndpointKind::from(endpoint.url.scheme()) {
+		return Ok(Box::new(connect_unix(endpoint, config).await?));
+	}
+	if let Some(runtime) = &endpoint.config.runtime {
+		return connect_runtime(runtime, endpoint, config, maybe_connector).await;
+	}
+	Ok(Box::new(connect_tcp(endpoint, config, maybe_connector).await?))
+}
+
+/// Connects over a TCP stream opened by the configured runtime
+async fn connect_runtime(
+	runtime: &RuntimeHandle,
+	endpoint: &Endpoint,
+	config: Option<WebSocketConfig>,
+	#[allow(unused_variables)] maybe_connector: Option<Connector>,
+) -> Result<Socket> {
+	let host =
+		endpoint.url.host_str().ok_or_else(|| Error::InvalidUrl(endpoint.url.to_string()))?;
+	let port = endpoint
+		.url
+		.port_or_known_default()
+		.ok_or_else(|| Error::InvalidUrl(endpoint.url.to_string()))?;
+	let stream =
+		runtime.0.connect(host, port).await.map_err(|error| Error::Ws(error.to_string()))?.compat();
+
+	let mut request = (&endpoint.url).into_client_request()?;
+
+	request
+		.headers_mut()
+		.insert(SEC_WEBSOCKET_PROTOCOL, HeaderValue::from_static(super::REVISION_HEADER));
+
+	#[cfg(any(feature = "native-tls", feature = "rustls"))]
+	let (socket, _) =
+		tokio_tungstenite::client_async_tls_with_config(request, stream, config, maybe_connector)
+			.await?;
+
+	#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+	let (socket, _) = tokio_tungstenite::client_async_with_config(request, stream, config).await?;
+
+	Ok(Box::new(socket))
+}
+
+async fn connect_tcp(
 	endpoint: &Endpoint,
 	config: Option<WebSocketConfig>,
 	#[allow(unused_variables)] maybe_connector: Option<Connector>,
@@ -92,6 +169,25 @@ pub(crate) async fn connect(
 	Ok(socket)
 }
 
+#[cfg(unix)]
+async fn connect_unix(
+	endpoint: &Endpoint,
+	config: Option<WebSocketConfig>,
+) -> Result<WebSocketStream<UnixStream>> {
+	let path = endpoint.path.strip_prefix("unix://").unwrap_or(&endpoint.path);
+	let stream = UnixStream::connect(path).await.map_err(|error| Error::Ws(error.to_string()))?;
+	// The host is only used for the upgrade request, as the socket is already connected
+	let mut request = format!("ws://localhost/{PATH}").into_client_request()?;
+
+	request
+		.headers_mut()
+		.insert(SEC_WEBSOCKET_PROTOCOL, HeaderValue::from_static(super::REVISION_HEADER));
+
+	let (socket, _) = tokio_tungstenite::client_async_with_config(request, stream, config).await?;
+
+	Ok(socket)
+}
+
 impl crate::api::Connection for Client {}
 
 impl Connection for Client {
@@ -121,7 +217,7 @@ impl Connection for Client {
 			};
 			let config = address.config.clone();
 
-			tokio::spawn(run_router(
+			config.spawn(run_router(
 				address,
 				maybe_connector,
 				capacity,
@@ -139,6 +235,8 @@ impl Connection for Client {
 				config,
 				sender: route_tx,
 				last_id: AtomicI64::new(0),
+				cache: OnceLock::new(),
+				credentials: Default::default(),
 			};
 
 			Ok((router, waiter).into())
@@ -193,6 +291,20 @@ async fn router_handle_route(
 		} => {
 			effect = RequestEffect::Insert;
 		}
+		Command::RawResponse {
+			..
+		} => {
+			effect = RequestEffect::Raw;
+		}
+		Command::Params => {
+			let params: CoreObject =
+				state.vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+			if response.clone().send(Ok(DbResponse::Other(params.into()))).await.is_err() {
+				trace!("Receiver dropped");
+			}
+			// The parameters are tracked locally, so there is nothing to send to the server
+			return HandleResult::Ok;
+		}
 		Command::SubscribeLive {
 			ref uuid,
 			ref notification_sender,
@@ -242,12 +354,14 @@ async fn router_handle_route(
 			let _ = response.send(Err(Error::BackupsNotSupported.into())).await;
 			return HandleResult::Ok;
 		};
+		let request = request.with_durability(endpoint.config.durability);
 		trace!("Request {:?}", request);
 		let payload = if endpoint.config.ast_payload {
 			serialize(&request, true).unwrap()
 		} else {
 			serialize(&request.stringify_queries(), true).unwrap()
 		};
+		endpoint.config.observe_sent(payload.len());
 		Message::Binary(payload)
 	};
 
@@ -270,7 +384,13 @@ async fn router_handle_route(
 	HandleResult::Ok
 }
 
-async fn router_handle_response(response: Message, state: &mut RouterState) -> HandleResult {
+async fn router_handle_response(
+	response: Message,
+	state: &mut RouterState,
+	endpoint: &Endpoint,
+) -> HandleResult {
+	endpoint.config.observe_received(response.len());
+	let message = &response;
 	match Response::try_from(&response) {
 		Ok(option) => {
 			// We are only interested in responses that are not empty
@@ -281,6 +401,15 @@ async fn router_handle_response(response: Message, state: &mut RouterState) -> H
 					Some(id) => {
 						if let Ok(id) = id.coerce_to_i64() {
 							if let Some(pending) = state.pending_requests.remove(&id) {
+								// Raw responses are forwarded without decoding them
+								if let RequestEffect::Raw = pending.effect {
+									let bytes = message.clone().into_data();
+									let _ = pending
+										.response_channel
+										.send(Ok(DbResponse::Raw(bytes)))
+										.await;
+									return HandleResult::Ok;
+								}
 								let resp = match DbResponse::from_server_result(response.result) {
 									Ok(x) => x,
 									Err(e) => {
@@ -290,7 +419,7 @@ async fn router_handle_response(response: Message, state: &mut RouterState) -> H
 								};
 								// We can only route responses with IDs
 								match pending.effect {
-									RequestEffect::None => {}
+									RequestEffect::None | RequestEffect::Raw => {}
 									RequestEffect::Insert => {
 										// For insert, we need to flatten single responses in an array
 										if let DbResponse::Other(CoreValue::Array(array)) = resp {
@@ -423,7 +552,7 @@ async fn router_reconnect(
 
 					if let Err(error) = state.sink.send(Message::Binary(message)).await {
 						trace!("{error}");
-						time::sleep(time::Duration::from_secs(1)).await;
+						endpoint.config.sleep(Duration::from_secs(1)).await;
 						continue;
 					}
 				}
@@ -438,7 +567,7 @@ async fn router_reconnect(
 					let payload = serialize(&request, true).unwrap();
 					if let Err(error) = state.sink.send(Message::Binary(payload)).await {
 						trace!("{error}");
-						time::sleep(time::Duration::from_secs(1)).await;
+						endpoint.config.sleep(Duration::from_secs(1)).await;
 						continue;
 					}
 				}
@@ -447,7 +576,7 @@ async fn router_reconnect(
 			}
 			Err(error) => {
 				trace!("Failed to reconnect; {error}");
-				time::sleep(time::Duration::from_secs(1)).await;
+				endpoint.config.sleep(Duration::from_secs(1)).await;
 			}
 		}
 	}
@@ -458,7 +587,7 @@ pub(crate) async fn run_router(
 	maybe_connector: Option<Connector>,
 	_capacity: usize,
 	config: WebSocketConfig,
-	socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+	socket: Socket,
 	route_rx: Receiver<Route>,
 ) {
 	let ping = {
@@ -471,11 +600,8 @@ pub(crate) async fn run_router(
 	let mut state = RouterState::new(socket_sink, socket_stream);
 
 	'router: loop {
-		let mut interval = time::interval(PING_INTERVAL);
 		// don't bombard the server with pings if we miss some ticks
-		interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
-
-		let mut pinger = IntervalStream::new(interval);
+		let mut pinger = pinger(&endpoint.config);
 		// Turn into a stream instead of calling recv_async
 		// The stream seems to be able to keep some state which would otherwise need to be
 		// recreated with each next.
@@ -533,7 +659,7 @@ pub(crate) async fn run_router(
 					state.last_activity = Instant::now();
 					match result {
 						Ok(message) => {
-							match router_handle_response(message, &mut state).await {
+							match router_handle_response(message, &mut state, &endpoint).await {
 								HandleResult::Ok => continue,
 								HandleResult::Disconnected => {
 									router_reconnect(
@@ -590,6 +716,14 @@ pub(crate) async fn run_router(
 	}
 }
 
+/// Ticks once every ping interval, delaying the following ticks if any are missed
+fn pinger(config: &Config) -> impl Stream<Item = ()> + Unpin + '_ {
+	Box::pin(futures::stream::unfold((), move |_| async move {
+		config.sleep(PING_INTERVAL).await;
+		Some(((), ()))
+	}))
+}
+
 impl Response {
 	fn try_from(message: &Message) -> Result<Option<Self>> {
 		match message {
@@ -32,7 +32,9 @@ use std::collections::hash_map::Entry;
 use std::collections::BTreeMap;
 use std::collections::HashSet;
 use std::sync::atomic::AtomicI64;
+use std::sync::OnceLock;
 use std::time::Duration;
+use surrealdb_core::sql::Object as CoreObject;
 use surrealdb_core::sql::Value as CoreValue;
 use tokio::sync::watch;
 use trice::Instant;
@@ -78,6 +80,8 @@ impl Connection for Client {
 				config,
 				sender: route_tx,
 				last_id: AtomicI64::new(0),
+				cache: OnceLock::new(),
+				credentials: Default::default(),
 			};
 
 			Ok((router, waiter).into())
@@ -132,6 +136,20 @@ async fn router_handle_request(
 		} => {
 			effect = RequestEffect::Insert;
 		}
+		Command::RawResponse {
+			..
+		} => {
+			effect = RequestEffect::Raw;
+		}
+		Command::Params => {
+			let params: CoreObject =
+				state.vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+			if response.send(Ok(DbResponse::Other(params.into()))).await.is_err() {
+				trace!("Receiver dropped");
+			}
+			// The parameters are tracked locally, so there is nothing to send to the server
+			return HandleResult::Ok;
+		}
 		Command::SubscribeLive {
 			ref uuid,
 			ref notification_sender,
@@ -181,12 +199,14 @@ async fn router_handle_request(
 			let _ = response.send(Err(Error::BackupsNotSupported.into())).await;
 			return HandleResult::Ok;
 		};
+		let req = req.with_durability(endpoint.config.durability);
 		trace!("Request {:?}", req);
 		let payload = if endpoint.config.ast_payload {
 			serialize(&req, true).unwrap()
 		} else {
 			serialize(&req.stringify_queries(), true).unwrap()
 		};
+		endpoint.config.observe_sent(payload.len());
 		Message::Binary(payload)
 	};
 
@@ -212,8 +232,14 @@ async fn router_handle_request(
 async fn router_handle_response(
 	response: Message,
 	state: &mut RouterState,
-	_endpoint: &Endpoint,
+	endpoint: &Endpoint,
 ) -> HandleResult {
+	let len = match &response {
+		Message::Text(text) => text.len(),
+		Message::Binary(binary) => binary.len(),
+	};
+	endpoint.config.observe_received(len);
+	let message = &response;
 	match Response::try_from(&response) {
 		Ok(option) => {
 			// We are only interested in responses that are not empty
@@ -225,8 +251,20 @@ async fn router_handle_response(
 						if let Ok(id) = id.coerce_to_i64() {
 							// We can only route responses with IDs
 							if let Some(pending) = state.pending_requests.remove(&id) {
+								// Raw responses are forwarded without decoding them
+								if let RequestEffect::Raw = pending.effect {
+									let bytes = match message {
+										Message::Binary(binary) => binary.clone(),
+										Message::Text(text) => text.clone().into_bytes(),
+									};
+									let _ = pending
+										.response_channel
+										.send(Ok(DbResponse::Raw(bytes)))
+										.await;
+									return HandleResult::Ok;
+								}
 								match pending.effect {
-									RequestEffect::None => {}
+									RequestEffect::None | RequestEffect::Raw => {}
 									RequestEffect::Insert => {
 										// For insert, we need to flatten single responses in an array
 										if let Ok(Data::Other(CoreValue::Array(value))) =
@@ -350,7 +388,7 @@ async fn router_reconnect(
 ) {
 	loop {
 		trace!("Reconnecting...");
-		let connect = WsMeta::connect(&endpoint.url, vec![super::REVISION_HEADER]).await;
+		let connect = WsMeta::connect(&endpoint.url, super::REVISION_PROTOCOLS.to_vec()).await;
 		match connect {
 			Ok((mut meta, stream)) => {
 				let (new_sink, new_stream) = stream.split();
@@ -411,7 +449,7 @@ pub(crate) async fn run_router(
 	conn_tx: Sender<Result<()>>,
 	route_rx: Receiver<Route>,
 ) {
-	let connect = WsMeta::connect(&endpoint.url, vec![super::REVISION_HEADER]).await;
+	let connect = WsMeta::connect(&endpoint.url, super::REVISION_PROTOCOLS.to_vec()).await;
 	let (mut ws, socket) = match connect {
 		Ok(pair) => pair,
 		Err(error) => {
@@ -1,9 +1,12 @@
 use crate::{api::Response, Value};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::path::PathBuf;
 use std::{convert::Infallible, io};
-use surrealdb_core::dbs::capabilities::{ParseFuncTargetError, ParseNetTargetError};
+use surrealdb_core::dbs::capabilities::{
+	ParseEnvTargetError, ParseFuncTargetError, ParseNetTargetError,
+};
+use surrealdb_core::err::ErrorDetails;
 use thiserror::Error;
 
 /// An error originating from a remote SurrealDB database
@@ -14,6 +17,14 @@ pub enum Error {
 	#[error("{0}")]
 	Query(String),
 
+	/// There was an error processing the query on a remote server, which sent the code and
+	/// structured details of the error alongside its message
+	#[error("{message}")]
+	Remote {
+		message: String,
+		details: ErrorDetails,
+	},
+
 	/// There was an error processing a remote HTTP request
 	#[error("There was an error processing a remote HTTP request: {0}")]
 	Http(String),
@@ -34,6 +45,14 @@ pub enum Error {
 	#[error("Already connected")]
 	AlreadyConnected,
 
+	/// Tried to use a retry policy with a remote engine, which runs the queries on the server
+	#[error("A retry policy can only be used with a local engine, not with a remote server")]
+	RetryPolicyRemote,
+
+	/// Tried to use a custom runtime with an engine which needs tokio
+	#[error("A custom runtime can only be used with the WebSocket engine over TCP")]
+	RuntimeUnsupported,
+
 	/// `Query::bind` not called with an object nor a key/value tuple
 	#[error("Invalid bindings: {0}")]
 	InvalidBindings(Value),
@@ -146,6 +165,30 @@ pub enum Error {
 		error: io::Error,
 	},
 
+	/// Failed to write an export to the provided writer
+	#[error("Failed to write the export: {0}")]
+	ExportWrite(io::Error),
+
+	/// Failed to read an import from the provided reader
+	#[error("Failed to read the import: {0}")]
+	ImportRead(io::Error),
+
+	/// An index being waited on failed to build, or its build was aborted
+	#[error("Failed to build index `{0}`: {1}")]
+	IndexBuildFailed(String, String),
+
+	/// Tried to check the version of something other than a single record
+	#[error("Version checked updates are only supported on record IDs")]
+	IfVersionOnNonRecord,
+
+	/// A version checked update found the stored record at a different version
+	#[error("Expected record `{record}` to be at version {expected}, but it is at {}", .found.map_or_else(|| "no version".to_owned(), |v| format!("version {v}")))]
+	VersionConflict {
+		record: String,
+		expected: u64,
+		found: Option<u64>,
+	},
+
 	/// Tried to take only a single result when the query returned multiple records
 	#[error("Tried to take only a single result from a query that contains multiple")]
 	LossyTake(Response),
@@ -190,6 +233,30 @@ pub enum Error {
 	#[error("Live queries on unspecified resource not supported")]
 	LiveOnUnspecified,
 
+	/// Live query notifications arrived faster than the stream was polled, and some were dropped
+	#[error("The live query stream fell behind, and {0} notifications were dropped")]
+	LiveQueryLagged(u64),
+
+	/// Tried to paginate a record ID
+	#[error("Pagination on record IDs not supported")]
+	PaginateOnRecordId,
+
+	/// Tried to paginate an object
+	#[error("Pagination on objects not supported")]
+	PaginateOnObject,
+
+	/// Tried to paginate an array
+	#[error("Pagination on arrays not supported")]
+	PaginateOnArray,
+
+	/// Tried to paginate an edge or edges
+	#[error("Pagination on edges not supported")]
+	PaginateOnEdges,
+
+	/// Tried to paginate an unspecified resource
+	#[error("Pagination on unspecified resource not supported")]
+	PaginateOnUnspecified,
+
 	/// Tried to access a query statement as a live query when it isn't a live query
 	#[error("Query statement {0} is not a live query")]
 	NotLiveQuery(usize),
@@ -228,6 +295,9 @@ pub enum Error {
 	#[error("{0}")]
 	InvalidFuncTarget(#[from] ParseFuncTargetError),
 
+	#[error("{0}")]
+	InvalidEnvTarget(#[from] ParseEnvTargetError),
+
 	#[error("failed to serialize Value: {0}")]
 	SerializeValue(String),
 	#[error("failed to deserialize Value: {0}")]
@@ -251,6 +321,194 @@ pub enum Error {
 	RawQuery(Cow<'static, str>),
 }
 
+impl Error {
+	/// Returns a stable code identifying the kind of this error
+	///
+	/// Unlike the error message, the code does not change between releases, so it can be
+	/// matched on by applications, or sent on to other services.
+	pub fn code(&self) -> &str {
+		match self {
+			Error::Query(_) => "query",
+			Error::Remote {
+				details,
+				..
+			} => &details.code,
+			Error::Http(_) => "http",
+			Error::Ws(_) => "ws",
+			Error::Scheme(_) => "unsupported_scheme",
+			Error::ConnectionUninitialised => "connection_uninitialised",
+			Error::AlreadyConnected => "already_connected",
+			Error::RetryPolicyRemote => "retry_policy_remote",
+			Error::RuntimeUnsupported => "runtime_unsupported",
+			Error::InvalidBindings(_) => "invalid_bindings",
+			Error::RangeOnRecordId
+			| Error::RangeOnObject
+			| Error::RangeOnArray
+			| Error::RangeOnEdges
+			| Error::RangeOnRange
+			| Error::RangeOnUnspecified => "invalid_range_resource",
+			Error::TableColonId {
+				..
+			} => "table_colon_id",
+			Error::DuplicateRequestId(_) => "duplicate_request_id",
+			Error::InvalidRequest(_) => "invalid_request",
+			Error::InvalidParams(_) => "invalid_params",
+			Error::InternalError(_) => "internal_error",
+			Error::ParseError(_) => "parse_error",
+			Error::InvalidSemanticVersion(_) => "invalid_semantic_version",
+			Error::InvalidUrl(_) => "invalid_url",
+			Error::FromValue {
+				..
+			} => "from_value",
+			Error::ResponseFromBinary {
+				..
+			} => "response_from_binary",
+			Error::ToJsonString {
+				..
+			} => "to_json_string",
+			Error::FromJsonString {
+				..
+			} => "from_json_string",
+			Error::InvalidNsName(_) => "invalid_ns_name",
+			Error::InvalidDbName(_) => "invalid_db_name",
+			Error::FileOpen {
+				..
+			} => "file_open",
+			Error::FileRead {
+				..
+			} => "file_read",
+			Error::ExportWrite(_) => "export_write",
+			Error::ImportRead(_) => "import_read",
+			Error::IndexBuildFailed(..) => "index_build_failed",
+			Error::IfVersionOnNonRecord => "if_version_on_non_record",
+			Error::VersionConflict {
+				..
+			} => "version_conflict",
+			Error::LossyTake(_) => "lossy_take",
+			Error::BackupsNotSupported => "backups_not_supported",
+			Error::VersionMismatch {
+				..
+			} => "version_mismatch",
+			Error::BuildMetadataMismatch {
+				..
+			} => "build_metadata_mismatch",
+			Error::LiveQueriesNotSupported => "live_queries_not_supported",
+			Error::LiveOnObject
+			| Error::LiveOnArray
+			| Error::LiveOnEdges
+			| Error::LiveOnUnspecified => "invalid_live_resource",
+			Error::LiveQueryLagged(_) => "live_query_lagged",
+			Error::PaginateOnRecordId
+			| Error::PaginateOnObject
+			| Error::PaginateOnArray
+			| Error::PaginateOnEdges
+			| Error::PaginateOnUnspecified => "invalid_paginate_resource",
+			Error::NotLiveQuery(_) => "not_live_query",
+			Error::QueryIndexOutOfBounds(_) => "query_index_out_of_bounds",
+			Error::ResponseAlreadyTaken => "response_already_taken",
+			Error::InsertOnObject
+			| Error::InsertOnArray
+			| Error::InsertOnEdges
+			| Error::InsertOnRange
+			| Error::InsertOnUnspecified => "invalid_insert_resource",
+			Error::InvalidNetTarget(_) => "invalid_net_target",
+			Error::InvalidFuncTarget(_) => "invalid_func_target",
+			Error::InvalidEnvTarget(_) => "invalid_env_target",
+			Error::SerializeValue(_) | Error::Serializer(_) => "serialize",
+			Error::DeSerializeValue(_) | Error::Deserializer(_) => "deserialize",
+			Error::RecievedInvalidValue => "received_invalid_value",
+			Error::VersionsNotSupported(_) => "versions_not_supported",
+			Error::RawQuery(_) => "query",
+		}
+	}
+
+	/// Returns the code, message, and structured details of this error
+	pub fn info(&self) -> ErrorInfo {
+		let mut info = ErrorInfo::new(self.code(), self.to_string());
+		match self {
+			Error::Remote {
+				details,
+				..
+			} => info.set_details(details),
+			Error::TableColonId {
+				table,
+			} => info.table = Some(table.clone()),
+			Error::IndexBuildFailed(index, _) => info.index = Some(index.clone()),
+			Error::VersionConflict {
+				record,
+				..
+			} => info.record = Some(record.clone()),
+			_ => {}
+		}
+		info
+	}
+}
+
+impl crate::Error {
+	/// Returns a stable code identifying the kind of this error
+	///
+	/// Errors from the embedded storage engines which do not have a dedicated code are
+	/// reported as `database`.
+	pub fn code(&self) -> &str {
+		match self {
+			crate::Error::Api(e) => e.code(),
+			crate::Error::Db(e) => e.code(),
+		}
+	}
+
+	/// Returns the code, message, and structured details of this error
+	pub fn info(&self) -> ErrorInfo {
+		match self {
+			crate::Error::Api(e) => e.info(),
+			crate::Error::Db(e) => {
+				let mut info = ErrorInfo::new(e.code(), e.to_string());
+				info.set_details(&e.details());
+				info
+			}
+		}
+	}
+}
+
+/// The machine readable details of an error
+///
+/// Unlike the error itself, which serializes to its message, this keeps the details when the
+/// error is sent on to another service.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ErrorInfo {
+	/// A stable code identifying the kind of error
+	pub code: String,
+	/// The human readable error message
+	pub message: String,
+	/// The table the error relates to
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub table: Option<String>,
+	/// The record ID the error relates to
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub record: Option<String>,
+	/// The index the error relates to
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub index: Option<String>,
+}
+
+impl ErrorInfo {
+	fn new(code: &str, message: String) -> Self {
+		Self {
+			code: code.to_owned(),
+			message,
+			table: None,
+			record: None,
+			index: None,
+		}
+	}
+
+	fn set_details(&mut self, details: &ErrorDetails) {
+		self.table.clone_from(&details.table);
+		self.record.clone_from(&details.record);
+		self.index.clone_from(&details.index);
+	}
+}
+
 impl serde::ser::Error for Error {
 	fn custom<T>(msg: T) -> Self
 	where
@@ -287,6 +545,12 @@ impl From<ParseFuncTargetError> for crate::Error {
 	}
 }
 
+impl From<ParseEnvTargetError> for crate::Error {
+	fn from(e: ParseEnvTargetError) -> Self {
+		Self::Api(Error::from(e))
+	}
+}
+
 #[cfg(feature = "protocol-http")]
 impl From<reqwest::Error> for crate::Error {
 	fn from(e: reqwest::Error) -> Self {
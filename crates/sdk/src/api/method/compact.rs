@@ -0,0 +1,74 @@
+use crate::api::conn::Command;
+use crate::api::err::Error;
+use crate::api::method::BoxFuture;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::method::OnceLockExt;
+use crate::Surreal;
+use std::borrow::Cow;
+use std::future::IntoFuture;
+use surrealdb_core::sql::Value as CoreValue;
+
+/// A compaction future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Compact<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+}
+
+impl<C> Compact<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> Compact<'static, C> {
+		Compact {
+			client: Cow::Owned(self.client.into_owned()),
+		}
+	}
+}
+
+/// The size of the files of a datastore, before and after it was compacted
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct Compaction {
+	/// The size in bytes of the datastore files before the compaction
+	pub before: u64,
+	/// The size in bytes of the datastore files after the compaction
+	pub after: u64,
+}
+
+impl Compaction {
+	/// The number of bytes which the compaction reclaimed
+	pub fn reclaimed(&self) -> u64 {
+		self.before.saturating_sub(self.after)
+	}
+}
+
+impl<'r, Client> IntoFuture for Compact<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<Compaction>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.inner.router.extract()?;
+			let compaction = router.execute_value(Command::Compact).await?;
+			let CoreValue::Object(mut compaction) = compaction.into_inner() else {
+				return Err(
+					Error::InternalError("received invalid compaction result".to_string()).into()
+				);
+			};
+			let mut field = |name: &str| match compaction.remove(name) {
+				Some(CoreValue::Number(size)) => Ok(size.to_int().max(0) as u64),
+				_ => Err(Error::InternalError(format!("received invalid compaction {name} size"))),
+			};
+			Ok(Compaction {
+				before: field("before")?,
+				after: field("after")?,
+			})
+		})
+	}
+}
@@ -1,9 +1,11 @@
 use crate::api::conn::Command;
+use crate::api::engine::resource_to_values;
 use crate::api::method::BoxFuture;
 use crate::api::opt::Resource;
 use crate::api::Connection;
 use crate::api::Result;
 use crate::method::OnceLockExt;
+use crate::method::Query;
 use crate::opt::KeyRange;
 use crate::Surreal;
 use crate::Value;
@@ -11,6 +13,8 @@ use serde::de::DeserializeOwned;
 use std::borrow::Cow;
 use std::future::IntoFuture;
 use std::marker::PhantomData;
+use surrealdb_core::sql::statements::{DeleteStatement, OutputStatement};
+use surrealdb_core::sql::{Cond, Function, Output, Statement, Subquery, Value as CoreValue};
 
 /// A record delete future
 #[derive(Debug)]
@@ -86,7 +90,7 @@ where
 	into_future! {execute_vec}
 }
 
-impl<C> Delete<'_, C, Value>
+impl<'r, C> Delete<'r, C, Value>
 where
 	C: Connection,
 {
@@ -95,9 +99,14 @@ where
 		self.resource = self.resource.and_then(|x| x.with_range(range.into()));
 		self
 	}
+
+	/// Only deletes the records matching a SurrealQL condition
+	pub fn where_filter(self, cond: impl AsRef<str>) -> DeleteWhere<'r, C, Value> {
+		DeleteWhere::new(self, cond.as_ref())
+	}
 }
 
-impl<C, R> Delete<'_, C, Vec<R>>
+impl<'r, C, R> Delete<'r, C, Vec<R>>
 where
 	C: Connection,
 {
@@ -106,4 +115,179 @@ where
 		self.resource = self.resource.and_then(|x| x.with_range(range.into()));
 		self
 	}
+
+	/// Only deletes the records matching a SurrealQL condition
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[derive(serde::Deserialize)]
+	/// # struct Session;
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// #
+	/// // Select the namespace/database to use
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// // Delete the expired sessions, returning them
+	/// let sessions: Vec<Session> = db.delete("session").where_filter("expires < time::now()").await?;
+	///
+	/// // Delete the expired sessions, only returning how many were deleted
+	/// let count = db.delete("session").where_filter("expires < time::now()").count().await?;
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn where_filter(self, cond: impl AsRef<str>) -> DeleteWhere<'r, C, Vec<R>> {
+		DeleteWhere::new(self, cond.as_ref())
+	}
+}
+
+/// A filtered record delete future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct DeleteWhere<'r, C: Connection, R> {
+	client: Cow<'r, Surreal<C>>,
+	resource: Result<Resource>,
+	cond: Result<Cond>,
+	response_type: PhantomData<R>,
+}
+
+impl<'r, C, R> DeleteWhere<'r, C, R>
+where
+	C: Connection,
+{
+	fn new<T>(delete: Delete<'r, C, T>, cond: &str) -> Self {
+		DeleteWhere {
+			client: delete.client,
+			resource: delete.resource,
+			cond: surrealdb_core::syn::value(cond)
+				.map(|value| {
+					let mut cond = Cond::default();
+					cond.0 = value;
+					cond
+				})
+				.map_err(Into::into),
+			response_type: PhantomData,
+		}
+	}
+
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> DeleteWhere<'static, C, R> {
+		DeleteWhere {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+
+	/// Only returns the number of records which were deleted
+	pub fn count(self) -> DeleteCount<'r, C> {
+		DeleteCount {
+			client: self.client,
+			resource: self.resource,
+			cond: self.cond,
+		}
+	}
+}
+
+fn delete_statement(resource: Result<Resource>, cond: Result<Cond>) -> Result<DeleteStatement> {
+	let mut stmt = DeleteStatement::default();
+	stmt.what = resource_to_values(resource?);
+	stmt.cond = Some(cond?);
+	stmt.output = Some(Output::Before);
+	Ok(stmt)
+}
+
+impl<'r, Client> IntoFuture for DeleteWhere<'r, Client, Value>
+where
+	Client: Connection,
+{
+	type Output = Result<Value>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let stmt = delete_statement(self.resource, self.cond)?;
+			let query = Query::normal(
+				self.client,
+				vec![Statement::Delete(stmt)],
+				Default::default(),
+				false,
+			);
+			query.await?.take(0)
+		})
+	}
+}
+
+impl<'r, Client, R> IntoFuture for DeleteWhere<'r, Client, Vec<R>>
+where
+	Client: Connection,
+	R: DeserializeOwned,
+{
+	type Output = Result<Vec<R>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let stmt = delete_statement(self.resource, self.cond)?;
+			let query = Query::normal(
+				self.client,
+				vec![Statement::Delete(stmt)],
+				Default::default(),
+				false,
+			);
+			query.await?.take(0)
+		})
+	}
+}
+
+/// A future which deletes records and counts them
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct DeleteCount<'r, C: Connection> {
+	client: Cow<'r, Surreal<C>>,
+	resource: Result<Resource>,
+	cond: Result<Cond>,
+}
+
+impl<C> DeleteCount<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> DeleteCount<'static, C> {
+		DeleteCount {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for DeleteCount<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<u64>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let stmt = delete_statement(self.resource, self.cond)?;
+			// Count the deleted records on the server, instead of sending them back
+			let mut output = OutputStatement::default();
+			output.what = CoreValue::Function(Box::new(Function::Normal(
+				"count".to_owned(),
+				vec![CoreValue::Subquery(Box::new(Subquery::Delete(stmt)))],
+			)));
+			let query = Query::normal(
+				self.client,
+				vec![Statement::Output(output)],
+				Default::default(),
+				false,
+			);
+			let count: Option<u64> = query.await?.take(0)?;
+			Ok(count.unwrap_or_default())
+		})
+	}
 }
@@ -22,6 +22,9 @@ use std::task::Context;
 use std::task::Poll;
 use surrealdb_core::kvs::export::{Config as DbExportConfig, TableConfig};
 
+#[cfg(not(target_family = "wasm"))]
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
 /// A database export future
 #[derive(Debug)]
 #[must_use = "futures do nothing unless you `.await` or poll them"]
@@ -111,6 +114,22 @@ where
 		self
 	}
 
+	/// Whether to export events from the database
+	pub fn events(mut self, events: bool) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.events = events;
+		}
+		self
+	}
+
+	/// Whether to export tests from the database
+	pub fn tests(mut self, tests: bool) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.tests = tests;
+		}
+		self
+	}
+
 	/// Whether to export all versions of data from the database
 	pub fn versions(mut self, versions: bool) -> Self {
 		if let Some(cfg) = self.db_config.as_mut() {
@@ -239,6 +258,46 @@ where
 	}
 }
 
+/// A writer which a database export is streamed into
+#[cfg(not(target_family = "wasm"))]
+#[derive(Debug)]
+pub struct ExportWriter<W>(pub(crate) W);
+
+#[cfg(not(target_family = "wasm"))]
+impl<'r, Client, W, T> IntoFuture for Export<'r, Client, ExportWriter<W>, T>
+where
+	Client: Connection,
+	W: AsyncWrite + Unpin + Send + Sync + 'r,
+{
+	type Output = Result<()>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let ExportWriter(mut writer) = self.target;
+			let mut backup = Export {
+				client: self.client,
+				target: (),
+				ml_config: self.ml_config,
+				db_config: self.db_config,
+				response: PhantomData,
+				export_type: self.export_type,
+			}
+			.await?;
+			while let Some(bytes) = backup.next().await {
+				if let Err(error) = writer.write_all(&bytes?).await {
+					return Err(Error::ExportWrite(error).into());
+				}
+			}
+			// The writer is flushed but not shut down, so it can still be finalised by the caller
+			if let Err(error) = writer.flush().await {
+				return Err(Error::ExportWrite(error).into());
+			}
+			Ok(())
+		})
+	}
+}
+
 /// A stream of exported data
 #[derive(Debug, Clone)]
 #[must_use = "streams do nothing unless you poll them"]
@@ -3,9 +3,16 @@ use crate::api::method::BoxFuture;
 use crate::api::Connection;
 use crate::api::Result;
 use crate::method::OnceLockExt;
+use crate::method::WithLatency;
 use crate::Surreal;
 use std::borrow::Cow;
 use std::future::IntoFuture;
+use std::time::Duration;
+
+#[cfg(not(target_family = "wasm"))]
+use tokio::time::Instant;
+#[cfg(target_family = "wasm")]
+use wasmtimer::std::Instant;
 
 /// A health check future
 #[derive(Debug)]
@@ -24,6 +31,11 @@ where
 			client: Cow::Owned(self.client.into_owned()),
 		}
 	}
+
+	/// Return the round-trip latency of the health check once it succeeds
+	pub const fn with_latency(self) -> WithLatency<Self> {
+		WithLatency(self)
+	}
 }
 
 impl<'r, Client> IntoFuture for Health<'r, Client>
@@ -40,3 +52,20 @@ where
 		})
 	}
 }
+
+impl<'r, Client> IntoFuture for WithLatency<Health<'r, Client>>
+where
+	Client: Connection,
+{
+	type Output = Result<Duration>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.0.client.inner.router.extract()?;
+			let start = Instant::now();
+			router.execute_unit(Command::Health).await?;
+			Ok(start.elapsed())
+		})
+	}
+}
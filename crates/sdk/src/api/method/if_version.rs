@@ -0,0 +1,204 @@
+use super::validate_data;
+use crate::api::err::Error;
+use crate::api::method::BoxFuture;
+use crate::api::opt::Resource;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::method::Query;
+use crate::method::Update;
+use crate::Surreal;
+use crate::Value;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::future::IntoFuture;
+use std::marker::PhantomData;
+use surrealdb_core::sql::from_value as from_core_value;
+use surrealdb_core::sql::statements::{SelectStatement, UpdateStatement};
+use surrealdb_core::sql::{
+	to_value as to_core_value, Cond, Data, Expression, Field, Fields, Idiom, Operator, Statement,
+	Value as CoreValue, Values,
+};
+
+/// The field which holds the version of a record
+const VERSION: &str = "version";
+
+/// A version checked update future
+///
+/// The update only succeeds if the `version` field of the stored record
+/// matches the expected version, and increments the version when it does.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct IfVersion<'r, C: Connection, R> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) resource: Result<Resource>,
+	pub(super) version: u64,
+	pub(super) data: Result<Option<Data>>,
+	pub(super) response_type: PhantomData<R>,
+}
+
+impl<C, R> IfVersion<'_, C, R>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> IfVersion<'static, C, R> {
+		IfVersion {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+
+	/// Replaces the current document / record data with the specified data
+	pub fn content<D>(mut self, data: D) -> Self
+	where
+		D: Serialize + 'static,
+	{
+		self.data = self.versioned(data).map(|v| Some(Data::ContentExpression(v)));
+		self
+	}
+
+	/// Merges the current document / record data with the specified data
+	pub fn merge<D>(mut self, data: D) -> Self
+	where
+		D: Serialize + 'static,
+	{
+		self.data = self.versioned(data).map(|v| Some(Data::MergeExpression(v)));
+		self
+	}
+
+	/// Sets the next version on the data which will be written
+	fn versioned<D>(&self, data: D) -> Result<CoreValue>
+	where
+		D: Serialize + 'static,
+	{
+		let mut data = to_core_value(data)?;
+		validate_data(
+			&data,
+			"Tried to update non-object-like data, only structs and objects are supported",
+		)?;
+		if let CoreValue::Object(object) = &mut data {
+			object.insert(VERSION.to_owned(), (self.version + 1).into());
+		}
+		Ok(data)
+	}
+}
+
+impl<'r, Client, R> IntoFuture for IfVersion<'r, Client, R>
+where
+	Client: Connection,
+	R: DeserializeOwned,
+{
+	type Output = Result<Option<R>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let thing = match self.resource? {
+				Resource::RecordId(record) => record.into_inner(),
+				_ => return Err(Error::IfVersionOnNonRecord.into()),
+			};
+			// Only update the record if it is still at the expected version
+			let mut update = UpdateStatement::default();
+			update.only = true;
+			update.what = Values::from(vec![CoreValue::from(thing.clone())]);
+			update.data = Some(self.data?.unwrap_or_else(|| {
+				Data::SetExpression(vec![(
+					Idiom::from(VERSION),
+					Operator::Equal,
+					(self.version + 1).into(),
+				)])
+			}));
+			let mut cond = Cond::default();
+			cond.0 = CoreValue::Expression(Box::new(Expression::Binary {
+				l: CoreValue::Idiom(Idiom::from(VERSION)),
+				o: Operator::Equal,
+				r: self.version.into(),
+			}));
+			update.cond = Some(cond);
+			// Fetch the stored version, so that conflicts can be reported
+			let mut select = SelectStatement::default();
+			let mut fields = Fields::default();
+			fields.0 = vec![Field::Single {
+				expr: CoreValue::Idiom(Idiom::from(VERSION)),
+				alias: None,
+			}];
+			fields.1 = true;
+			select.expr = fields;
+			select.only = true;
+			select.what = Values::from(vec![CoreValue::from(thing.clone())]);
+			let statements = vec![Statement::Update(update), Statement::Select(select)];
+			let query = Query::normal(self.client, statements, Default::default(), false);
+			let mut response = query.await?;
+			match response.take::<Value>(0)?.into_inner() {
+				CoreValue::None | CoreValue::Null => {
+					let found: Option<u64> = response.take(1)?;
+					Err(Error::VersionConflict {
+						record: thing.to_string(),
+						expected: self.version,
+						found,
+					}
+					.into())
+				}
+				value => Ok(Some(from_core_value(value)?)),
+			}
+		})
+	}
+}
+
+impl<'r, C, R> Update<'r, C, Option<R>>
+where
+	C: Connection,
+	R: DeserializeOwned,
+{
+	/// Only updates the record if it is still at the specified version
+	///
+	/// The version of a record is stored in its `version` field, which is
+	/// incremented with every version checked update. If another client has
+	/// updated the record since it was read, the update is not applied and
+	/// [`Error::VersionConflict`](crate::error::Api::VersionConflict) is
+	/// returned instead, so that lost updates can be detected.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use serde::{Deserialize, Serialize};
+	///
+	/// #[derive(Debug, Serialize, Deserialize)]
+	/// struct Person {
+	///     name: String,
+	///     version: u64,
+	/// }
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// #
+	/// // Select the namespace/database to use
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// // Read the record, and write it back if nobody changed it in the meantime
+	/// let person: Option<Person> = db.select(("person", "tobie")).await?;
+	/// if let Some(mut person) = person {
+	///     person.name = "Tobie".to_owned();
+	///     let version = person.version;
+	///     let person: Option<Person> = db
+	///         .update(("person", "tobie"))
+	///         .if_version(version)
+	///         .content(person)
+	///         .await?;
+	/// }
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn if_version(self, version: u64) -> IfVersion<'r, C, R> {
+		IfVersion {
+			client: self.client,
+			resource: self.resource,
+			version,
+			data: Ok(None),
+			response_type: PhantomData,
+		}
+	}
+}
@@ -12,6 +12,9 @@ use std::future::IntoFuture;
 use std::marker::PhantomData;
 use std::path::PathBuf;
 
+#[cfg(not(target_family = "wasm"))]
+use tokio::io::{AsyncRead, AsyncReadExt};
+
 /// An database import future
 #[derive(Debug)]
 #[must_use = "futures do nothing unless you `.await` or poll them"]
@@ -80,3 +83,79 @@ where
 		})
 	}
 }
+
+/// A database import future which reads from an async reader
+#[cfg(not(target_family = "wasm"))]
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ImportFrom<'r, C: Connection, R> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) reader: R,
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl<C, R> ImportFrom<'_, C, R>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> ImportFrom<'static, C, R> {
+		ImportFrom {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl<'r, Client, R> IntoFuture for ImportFrom<'r, Client, R>
+where
+	Client: Connection,
+	R: AsyncRead + Unpin + Send + Sync + 'r,
+{
+	type Output = Result<()>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.inner.router.extract()?;
+			if !router.features.contains(&ExtraFeatures::Backup) {
+				return Err(Error::BackupsNotSupported.into());
+			}
+
+			let (tx, rx) = crate::channel::bounded(1);
+			let mut reader = self.reader;
+
+			// Read from the reader and forward the chunks to the import
+			let read = async move {
+				loop {
+					let mut chunk = Vec::with_capacity(4096);
+					match reader.read_buf(&mut chunk).await {
+						Ok(0) => return Ok(()),
+						Ok(_) => {
+							if tx.send(Ok(chunk)).await.is_err() {
+								// The import has already stopped
+								return Ok(());
+							}
+						}
+						Err(error) => {
+							// Abort the import, so that no more statements are run. Any statements
+							// which were already read have been run, and are not rolled back.
+							let abort = std::io::Error::new(error.kind(), error.to_string());
+							let _ = tx.send(Err(Error::ImportRead(abort).into())).await;
+							return Err(Error::ImportRead(error));
+						}
+					}
+				}
+			};
+
+			let import = router.execute_unit(Command::ImportBytes {
+				bytes: rx,
+			});
+
+			let (read, import) = futures::join!(read, import);
+			read?;
+			import
+		})
+	}
+}
@@ -1,6 +1,7 @@
 use crate::api::conn::Command;
 use crate::api::conn::Router;
 use crate::api::err::Error;
+use crate::api::method::select::Projection;
 use crate::api::method::BoxFuture;
 use crate::api::Connection;
 use crate::api::ExtraFeatures;
@@ -10,16 +11,20 @@ use crate::method::Live;
 use crate::method::OnceLockExt;
 use crate::method::Query;
 use crate::method::Select;
+use crate::opt::LiveOverflow;
+use crate::opt::LiveQueryBuffer;
 use crate::opt::Resource;
 use crate::value::Notification;
 use crate::Surreal;
 use crate::Value;
-use async_channel::Receiver;
+use async_channel::{Receiver, SendError, Sender, TrySendError};
 use futures::StreamExt;
 use serde::de::DeserializeOwned;
 use std::future::IntoFuture;
 use std::marker::PhantomData;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
 use surrealdb_core::sql::{
@@ -28,12 +33,6 @@ use surrealdb_core::sql::{
 };
 use uuid::Uuid;
 
-#[cfg(not(target_family = "wasm"))]
-use tokio::spawn;
-
-#[cfg(target_family = "wasm")]
-use wasm_bindgen_futures::spawn_local as spawn;
-
 const ID: &str = "id";
 
 fn into_future<C, O>(this: Select<C, O, Live>) -> BoxFuture<Result<Stream<O>>>
@@ -43,6 +42,7 @@ where
 	let Select {
 		client,
 		resource,
+		projection,
 		..
 	} = this;
 	Box::pin(async move {
@@ -50,8 +50,20 @@ where
 		if !router.features.contains(&ExtraFeatures::LiveQueries) {
 			return Err(Error::LiveQueriesNotSupported.into());
 		}
-		let mut fields = Fields::default();
-		fields.0 = vec![Field::All];
+		let fields = match projection {
+			Some(Projection::Omit(_)) => {
+				return Err(Error::InvalidParams(
+					"Omitting fields is not supported on live queries".to_owned(),
+				)
+				.into())
+			}
+			Some(projection) => projection.fields()?,
+			None => {
+				let mut fields = Fields::default();
+				fields.0 = vec![Field::All];
+				fields
+			}
+		};
 		let mut stmt = LiveStatement::new(fields);
 		let mut table = Table::default();
 		match resource? {
@@ -95,23 +107,78 @@ where
 			)
 			.into());
 		};
-		let rx = register(router, *id).await?;
-		Ok(Stream::new(client.inner.clone().into(), *id, Some(rx)))
+		let subscription = register(router, *id, router.config.live_query_buffer).await?;
+		Ok(Stream::new(client.inner.clone().into(), *id, Some(subscription)))
 	})
 }
 
+/// The receiving end of a live query subscription
+#[derive(Debug)]
+pub(crate) struct Subscription {
+	pub(crate) rx: Receiver<Notification<CoreValue>>,
+	/// The number of notifications which were dropped and not yet reported
+	pub(crate) lagged: Arc<AtomicU64>,
+}
+
+/// Sends notifications to a live query stream, applying the overflow policy of its buffer
+#[derive(Debug, Clone)]
+pub(crate) struct NotificationSender {
+	tx: Sender<Notification<CoreValue>>,
+	overflow: Option<LiveOverflow>,
+	lagged: Arc<AtomicU64>,
+}
+
+impl NotificationSender {
+	/// Sends a notification, only failing once the stream has been dropped
+	pub(crate) async fn send(
+		&self,
+		notification: Notification<CoreValue>,
+	) -> std::result::Result<(), SendError<Notification<CoreValue>>> {
+		let overflow = match self.overflow {
+			Some(LiveOverflow::DropOldest) => {
+				return self.tx.force_send(notification).map(|_| ());
+			}
+			Some(overflow) => overflow,
+			None => return self.tx.send(notification).await,
+		};
+		match self.tx.try_send(notification) {
+			Err(TrySendError::Full(_)) => {
+				if overflow == LiveOverflow::Lag {
+					self.lagged.fetch_add(1, Ordering::Relaxed);
+				}
+				Ok(())
+			}
+			Err(TrySendError::Closed(notification)) => Err(SendError(notification)),
+			Ok(()) => Ok(()),
+		}
+	}
+}
+
+/// Subscribes to the notifications of a live query, buffering them without limit if no buffer is specified
 pub(crate) async fn register(
 	router: &Router,
 	id: Uuid,
-) -> Result<Receiver<Notification<CoreValue>>> {
-	let (tx, rx) = async_channel::unbounded();
+	buffer: Option<LiveQueryBuffer>,
+) -> Result<Subscription> {
+	let (tx, rx) = match buffer {
+		Some(buffer) => async_channel::bounded(buffer.capacity),
+		None => async_channel::unbounded(),
+	};
+	let lagged = Arc::new(AtomicU64::new(0));
 	router
 		.execute_unit(Command::SubscribeLive {
 			uuid: id,
-			notification_sender: tx,
+			notification_sender: NotificationSender {
+				tx,
+				overflow: buffer.map(|buffer| buffer.overflow),
+				lagged: lagged.clone(),
+			},
 		})
 		.await?;
-	Ok(rx)
+	Ok(Subscription {
+		rx,
+		lagged,
+	})
 }
 
 impl<'r, Client> IntoFuture for Select<'r, Client, Value, Live>
@@ -161,18 +228,20 @@ pub struct Stream<R> {
 	// Leaving them in for backwards compatibility
 	pub(crate) id: Uuid,
 	pub(crate) rx: Option<Pin<Box<Receiver<Notification<CoreValue>>>>>,
+	pub(crate) lagged: Arc<AtomicU64>,
 	pub(crate) response_type: PhantomData<R>,
 }
 
 impl<R> Stream<R> {
-	pub(crate) fn new(
-		client: Surreal<Any>,
-		id: Uuid,
-		rx: Option<Receiver<Notification<CoreValue>>>,
-	) -> Self {
+	pub(crate) fn new(client: Surreal<Any>, id: Uuid, subscription: Option<Subscription>) -> Self {
+		let (rx, lagged) = match subscription {
+			Some(subscription) => (Some(Box::pin(subscription.rx)), subscription.lagged),
+			None => (None, Default::default()),
+		};
 		Self {
 			id,
-			rx: rx.map(Box::pin),
+			rx,
+			lagged,
 			client,
 			response_type: PhantomData,
 		}
@@ -180,8 +249,15 @@ impl<R> Stream<R> {
 }
 
 macro_rules! poll_next {
-	($notification:ident => $body:expr) => {
+	($(lagged $lagged:ident => $lag:expr,)? $notification:ident => $body:expr) => {
 		fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+			$(
+				// Report dropped notifications before any others
+				let $lagged = self.lagged.swap(0, Ordering::Relaxed);
+				if $lagged > 0 {
+					return $lag;
+				}
+			)?
 			let Some(ref mut rx) = self.as_mut().rx else {
 				return Poll::Ready(None);
 			};
@@ -212,6 +288,7 @@ impl futures::Stream for Stream<Value> {
 macro_rules! poll_next_and_convert {
 	() => {
 		poll_next! {
+			lagged missed => Poll::Ready(Some(Err(Error::LiveQueryLagged(missed).into()))),
 			notification => match notification.map_deserialize(){
 				Ok(data) => Poll::Ready(Some(Ok(data))),
 				Err(error) => Poll::Ready(Some(Err(error.into()))),
@@ -251,8 +328,11 @@ pub(crate) fn kill<Client>(client: &Surreal<Client>, uuid: Uuid)
 where
 	Client: Connection,
 {
+	let Ok(router) = client.inner.router.extract() else {
+		return;
+	};
 	let client = client.clone();
-	spawn(async move {
+	router.spawn(async move {
 		if let Ok(router) = client.inner.router.extract() {
 			router
 				.execute_unit(Command::Kill {
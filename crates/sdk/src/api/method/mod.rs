@@ -11,6 +11,8 @@ use crate::api::OnceLockExt;
 use crate::api::Surreal;
 use crate::opt::IntoExportDestination;
 use crate::opt::WaitFor;
+use crate::RecordId;
+use crate::Value;
 use serde::Serialize;
 use std::borrow::Cow;
 use std::marker::PhantomData;
@@ -21,7 +23,6 @@ use std::sync::OnceLock;
 use std::time::Duration;
 use surrealdb_core::sql::to_value as to_core_value;
 use surrealdb_core::sql::Value as CoreValue;
-use surrealdb_core::syn;
 
 pub(crate) mod live;
 pub(crate) mod query;
@@ -30,28 +31,35 @@ mod authenticate;
 mod begin;
 mod cancel;
 mod commit;
+mod compact;
 mod content;
 mod create;
 mod delete;
 mod export;
 mod health;
+mod if_version;
 mod import;
 mod insert;
 mod insert_relation;
 mod invalidate;
 mod merge;
+mod paginate;
+mod params;
 mod patch;
 mod run;
 mod select;
+mod server_info;
 mod set;
 mod signin;
 mod signup;
+mod traverse;
 mod unset;
 mod update;
 mod upsert;
 mod use_db;
 mod use_ns;
 mod version;
+mod wait_for_index;
 
 #[cfg(test)]
 mod tests;
@@ -69,17 +77,27 @@ pub use cancel::Cancel;
 /// Not supported yet
 #[doc(hidden)]
 pub use commit::Commit;
+pub use compact::{Compact, Compaction};
 pub use content::Content;
 pub use create::Create;
 pub use delete::Delete;
+pub use delete::DeleteCount;
+pub use delete::DeleteWhere;
+#[cfg(not(target_family = "wasm"))]
+pub use export::ExportWriter;
 pub use export::{Backup, Export};
 use futures::Future;
 pub use health::Health;
+pub use if_version::IfVersion;
 pub use import::Import;
+#[cfg(not(target_family = "wasm"))]
+pub use import::ImportFrom;
 pub use insert::Insert;
 pub use invalidate::Invalidate;
 pub use live::Stream;
 pub use merge::Merge;
+pub use paginate::Pages;
+pub use params::Params;
 pub use patch::Patch;
 pub use query::Query;
 pub use query::QueryStream;
@@ -87,16 +105,19 @@ pub use run::IntoFn;
 pub use run::Run;
 pub use select::Select;
 use serde_content::Serializer;
+pub use server_info::{ServerInfo, ServerMetadata};
 pub use set::Set;
 pub use signin::Signin;
 pub use signup::Signup;
 use tokio::sync::watch;
+pub use traverse::Traverse;
 pub use unset::Unset;
 pub use update::Update;
 pub use upsert::Upsert;
 pub use use_db::UseDb;
 pub use use_ns::UseNs;
 pub use version::Version;
+pub use wait_for_index::WaitForIndex;
 
 use super::opt::CreateResource;
 use super::opt::IntoResource;
@@ -125,6 +146,14 @@ pub struct Live;
 #[derive(Debug)]
 pub struct WithStats<T>(pub T);
 
+/// Health checks which measure the round-trip latency to the server
+#[derive(Debug)]
+pub struct WithLatency<T>(pub T);
+
+/// Responses returned as the bytes sent by the server, without decoding them
+#[derive(Debug)]
+pub struct RawBytes<T>(pub T);
+
 impl<C> Surreal<C>
 where
 	C: Connection,
@@ -387,6 +416,33 @@ where
 		}
 	}
 
+	/// Lists the parameters currently assigned on this connection
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// #
+	/// // Assign some variables on the connection
+	/// db.set("name", "Tobie").await?;
+	/// db.set("age", 30).await?;
+	///
+	/// // List the variables assigned on the connection
+	/// for (key, value) in db.params().await? {
+	///     println!("${key} = {value}");
+	/// }
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn params(&self) -> Params<C> {
+		Params {
+			client: Cow::Borrowed(self),
+		}
+	}
+
 	/// Signs up a user with a specific record access method
 	///
 	/// # Examples
@@ -651,15 +707,11 @@ where
 	/// # }
 	/// ```
 	pub fn query(&self, query: impl opt::IntoQuery) -> Query<C> {
-		let result = match query.as_str() {
-			Some(surql) => self.inner.router.extract().and_then(|router| {
-				let capabilities = &router.config.capabilities;
-				syn::parse_with_capabilities(surql, capabilities)
-					.map_err(Into::into)
-					.and_then(opt::IntoQuery::into_query)
-			}),
-			None => query.into_query(),
-		};
+		let result = self
+			.inner
+			.router
+			.extract()
+			.and_then(|router| query.into_query_with(&router.config.capabilities));
 		let inner = match result {
 			Ok(query) => Ok(ValidQuery::Normal {
 				query,
@@ -678,6 +730,7 @@ where
 		Query {
 			inner,
 			client: Cow::Borrowed(self),
+			target: Default::default(),
 		}
 	}
 
@@ -726,6 +779,7 @@ where
 			resource: resource.into_resource(),
 			response_type: PhantomData,
 			query_type: PhantomData,
+			projection: None,
 		}
 	}
 
@@ -1294,6 +1348,53 @@ where
 		}
 	}
 
+	/// Returns metadata about the server
+	///
+	/// This includes the version and build of the server, the storage engine it is using, and
+	/// the capabilities it has enabled. It can be used to gate client-side features when
+	/// connecting to servers running different versions. Anonymous sessions only receive
+	/// the version of the server.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let info = db.server_info().await?;
+	/// println!("{} running on {:?}", info.version, info.storage);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn server_info(&self) -> ServerInfo<C> {
+		ServerInfo {
+			client: Cow::Borrowed(self),
+		}
+	}
+
+	/// Compacts the files of the storage engine, reclaiming the space of deleted data
+	///
+	/// Only storage engines which store their files on the machine running the datastore,
+	/// such as RocksDB and SurrealKV, support compaction. Connections to a server need to be
+	/// signed in as a root user with the editor or owner role.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("surrealkv://data").await?;
+	/// let compaction = db.compact().await?;
+	/// println!("Reclaimed {} bytes", compaction.reclaimed());
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn compact(&self) -> Compact<C> {
+		Compact {
+			client: Cow::Borrowed(self),
+		}
+	}
+
 	/// Runs a function
 	///
 	/// # Examples
@@ -1324,6 +1425,48 @@ where
 		}
 	}
 
+	/// Traverses the graph from a record
+	///
+	/// Each step follows an edge table to the records at the other end of the edges, building
+	/// the same traversal as the arrow syntax in SurrealQL. The records at the end of the
+	/// traversal are fetched and deserialized into the type specified with `collect`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use serde::Deserialize;
+	///
+	/// #[derive(Debug, Deserialize)]
+	/// struct Company {
+	///     name: String,
+	/// }
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// #
+	/// // Select the namespace/database to use
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// // SELECT * FROM person:tobie->knows->?->works_at->?
+	/// let companies = db
+	///     .traverse(("person", "tobie"))
+	///     .out("knows")
+	///     .out("works_at")
+	///     .collect::<Vec<Company>>()
+	///     .await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn traverse(&self, from: impl Into<RecordId>) -> Traverse<C, Value> {
+		Traverse {
+			client: Cow::Borrowed(self),
+			from: from.into(),
+			steps: Vec::new(),
+			response_type: PhantomData,
+		}
+	}
+
 	/// Checks whether the server is healthy or not
 	///
 	/// # Examples
@@ -1333,6 +1476,9 @@ where
 	/// # async fn main() -> surrealdb::Result<()> {
 	/// # let db = surrealdb::engine::any::connect("mem://").await?;
 	/// db.health().await?;
+	///
+	/// // Measure the round-trip latency of the health check
+	/// let latency = db.health().with_latency().await?;
 	/// # Ok(())
 	/// # }
 	/// ```
@@ -1342,6 +1488,41 @@ where
 		}
 	}
 
+	/// Waits for an index to finish building
+	///
+	/// Indexes defined with `CONCURRENTLY` are built in the background. This resolves once the
+	/// build has completed, and returns an error if the build failed or was aborted. Indexes which
+	/// were not built concurrently resolve immediately.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// # db.use_ns("namespace").use_db("database").await?;
+	/// db.query("DEFINE INDEX email ON user FIELDS email UNIQUE CONCURRENTLY").await?;
+	/// db.wait_for_index("user", "email").await?;
+	///
+	/// // Check the build status less frequently
+	/// use std::time::Duration;
+	/// db.wait_for_index("user", "email").poll_interval(Duration::from_secs(1)).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn wait_for_index(
+		&self,
+		table: impl Into<String>,
+		index: impl Into<String>,
+	) -> WaitForIndex<C> {
+		WaitForIndex {
+			client: Cow::Borrowed(self),
+			table: table.into(),
+			index: index.into(),
+			interval: Duration::from_millis(100),
+		}
+	}
+
 	/// Wait for the selected event to happen before proceeding
 	pub async fn wait_for(&self, event: WaitFor) {
 		let mut rx = self.inner.waiter.0.subscribe();
@@ -1432,6 +1613,78 @@ where
 			import_type: PhantomData,
 		}
 	}
+
+	/// Streams the database contents into an async writer
+	///
+	/// The writer is flushed once the export completes, but it is not shut down. Writers which
+	/// need finalising, such as compression encoders, should be shut down by the caller.
+	///
+	/// # Support
+	///
+	/// Currently only supported by HTTP and the local engines. *Not* supported on WebAssembly.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// // Select the namespace/database to use
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// let mut backup = Vec::new();
+	/// db.export_to(&mut backup).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	#[cfg(not(target_family = "wasm"))]
+	pub fn export_to<W>(&self, writer: W) -> Export<C, ExportWriter<W>>
+	where
+		W: tokio::io::AsyncWrite + Unpin,
+	{
+		Export {
+			client: Cow::Borrowed(self),
+			target: ExportWriter(writer),
+			ml_config: None,
+			db_config: None,
+			response: PhantomData,
+			export_type: PhantomData,
+		}
+	}
+
+	/// Restores the database from an async reader
+	///
+	/// Statements are run as they are read, so if reading fails part of the way through, the
+	/// statements which were read before the error remain applied.
+	///
+	/// # Support
+	///
+	/// Currently only supported by HTTP and the local engines. *Not* supported on WebAssembly.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// // Select the namespace/database to use
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// let backup = "CREATE person:tobie;".as_bytes();
+	/// db.import_from(backup).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	#[cfg(not(target_family = "wasm"))]
+	pub fn import_from<R>(&self, reader: R) -> ImportFrom<C, R>
+	where
+		R: tokio::io::AsyncRead + Unpin,
+	{
+		ImportFrom {
+			client: Cow::Borrowed(self),
+			reader,
+		}
+	}
 }
 
 fn validate_data(data: &CoreValue, error_message: &str) -> crate::Result<()> {
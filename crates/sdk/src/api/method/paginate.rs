@@ -0,0 +1,244 @@
+use crate::api::err::Error;
+use crate::api::method::select::Projection;
+use crate::api::method::BoxFuture;
+use crate::api::opt::Resource;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::method::Query;
+use crate::method::Select;
+use crate::Surreal;
+use crate::Value;
+use serde::de::DeserializeOwned;
+use std::borrow::Cow;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::ops::Bound;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+use surrealdb_core::sql::from_value as from_core_value;
+use surrealdb_core::sql::{
+	statements::SelectStatement, Field, Fields, Id, IdRange, Limit, Statement, Thing,
+	Value as CoreValue, Values,
+};
+
+const ID: &str = "id";
+
+/// A stream of pages returned by [`Select::paginate`]
+///
+/// Each page is fetched with a separate query which resumes after the last
+/// record ID of the previous page, so records are never returned twice, even
+/// when other clients are writing to the table while the stream is consumed.
+#[must_use = "streams do nothing unless you poll them"]
+pub struct Pages<'r, C: Connection, R> {
+	client: Cow<'r, Surreal<C>>,
+	cursor: Option<Cursor>,
+	error: Option<crate::Error>,
+	size: usize,
+	projection: Option<Projection>,
+	page: Option<BoxFuture<'r, Result<Vec<CoreValue>>>>,
+	response_type: PhantomData<fn() -> R>,
+}
+
+struct Cursor {
+	table: String,
+	beg: Bound<Id>,
+	end: Bound<Id>,
+}
+
+impl Cursor {
+	fn new(resource: Resource) -> Result<Self> {
+		match resource {
+			Resource::Table(table) => Ok(Self {
+				table,
+				beg: Bound::Unbounded,
+				end: Bound::Unbounded,
+			}),
+			Resource::Range(range) => {
+				let range = range.into_inner();
+				match range.id {
+					Id::Range(id) => Ok(Self {
+						table: range.tb,
+						beg: id.beg,
+						end: id.end,
+					}),
+					_ => Err(Error::InternalError(
+						"range resource didn't contain a range id".to_owned(),
+					)
+					.into()),
+				}
+			}
+			Resource::RecordId(_) => Err(Error::PaginateOnRecordId.into()),
+			Resource::Object(_) => Err(Error::PaginateOnObject.into()),
+			Resource::Array(_) => Err(Error::PaginateOnArray.into()),
+			Resource::Edge(_) => Err(Error::PaginateOnEdges.into()),
+			Resource::Unspecified => Err(Error::PaginateOnUnspecified.into()),
+		}
+	}
+
+	fn range(&self) -> Thing {
+		let range = IdRange {
+			beg: self.beg.clone(),
+			end: self.end.clone(),
+		};
+		Thing::from((self.table.clone(), range))
+	}
+}
+
+fn fetch<C>(
+	client: Cow<'_, Surreal<C>>,
+	range: Thing,
+	size: usize,
+	projection: Option<Projection>,
+) -> BoxFuture<'_, Result<Vec<CoreValue>>>
+where
+	C: Connection,
+{
+	Box::pin(async move {
+		let mut stmt = SelectStatement::default();
+		match projection {
+			Some(projection) => projection.apply(&mut stmt)?,
+			None => {
+				let mut fields = Fields::default();
+				fields.0 = vec![Field::All];
+				stmt.expr = fields;
+			}
+		}
+		stmt.what = Values::from(vec![CoreValue::from(range)]);
+		let mut limit = Limit::default();
+		limit.0 = CoreValue::from(size as i64);
+		stmt.limit = Some(limit);
+		let query = Query::normal(client, vec![Statement::Select(stmt)], Default::default(), false);
+		match query.await?.take::<Value>(0)?.into_inner() {
+			CoreValue::None | CoreValue::Null => Ok(Vec::new()),
+			CoreValue::Array(array) => Ok(array.0),
+			value => Ok(vec![value]),
+		}
+	})
+}
+
+impl<C: Connection, R> Unpin for Pages<'_, C, R> {}
+
+impl<C, R> futures::Stream for Pages<'_, C, R>
+where
+	C: Connection,
+	R: DeserializeOwned,
+{
+	type Item = Result<Vec<R>>;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let this = self.get_mut();
+		if let Some(error) = this.error.take() {
+			return Poll::Ready(Some(Err(error)));
+		}
+		let Some(cursor) = this.cursor.as_mut() else {
+			return Poll::Ready(None);
+		};
+		if this.page.is_none() {
+			this.page = Some(fetch(
+				this.client.clone(),
+				cursor.range(),
+				this.size,
+				this.projection.clone(),
+			));
+		}
+		let result = match this.page.as_mut().map(|page| page.as_mut().poll(cx)) {
+			Some(Poll::Ready(result)) => result,
+			_ => return Poll::Pending,
+		};
+		this.page = None;
+		let records = match result {
+			Ok(records) => records,
+			Err(error) => {
+				this.cursor = None;
+				return Poll::Ready(Some(Err(error)));
+			}
+		};
+		// Resume the next page after the last record of this one
+		let last = match records.last() {
+			Some(CoreValue::Object(object)) => match object.get(ID) {
+				Some(CoreValue::Thing(thing)) => Some(thing.id.clone()),
+				_ => None,
+			},
+			_ => None,
+		};
+		match last {
+			Some(id) if records.len() >= this.size => cursor.beg = Bound::Excluded(id),
+			_ => this.cursor = None,
+		}
+		if records.is_empty() {
+			return Poll::Ready(None);
+		}
+		Poll::Ready(Some(from_core_value(records.into()).map_err(Into::into)))
+	}
+}
+
+impl<'r, C, R> Select<'r, C, Vec<R>>
+where
+	C: Connection,
+	R: DeserializeOwned,
+{
+	/// Selects the records in pages of `size` records at a time
+	///
+	/// Pages are ordered by record ID, and each page continues after the last
+	/// record ID of the previous one. Records created or deleted while the
+	/// stream is being consumed will therefore never cause another record to
+	/// be returned twice or skipped.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # use futures::StreamExt;
+	/// # #[derive(Debug, serde::Deserialize)]
+	/// # struct Person;
+	/// #
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// #
+	/// // Select the namespace/database to use
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// // Fetch all people, 100 at a time
+	/// let mut pages = db.select("person").paginate(100);
+	/// while let Some(page) = pages.next().await {
+	///     let people: Vec<Person> = page?;
+	///     println!("{people:?}");
+	/// }
+	///
+	/// // Fetch a range of people, 100 at a time
+	/// let mut pages = db.select("person").range("jane".."john").paginate(100);
+	/// # let _: Option<surrealdb::Result<Vec<Person>>> = pages.next().await;
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn paginate(self, size: usize) -> Pages<'r, C, R> {
+		let (cursor, error) = match self.resource.and_then(Cursor::new) {
+			Ok(cursor) => (Some(cursor), None),
+			Err(error) => (None, Some(error)),
+		};
+		// Pages continue after the last record ID, so it is always selected
+		let projection = self.projection.map(|projection| match projection {
+			Projection::Fields(mut names) => {
+				if !names.iter().any(|name| name == ID) {
+					names.push(ID.to_owned());
+				}
+				Projection::Fields(names)
+			}
+			Projection::Omit(mut names) => {
+				names.retain(|name| name != ID);
+				Projection::Omit(names)
+			}
+		});
+		Pages {
+			client: self.client,
+			cursor,
+			error,
+			size: size.max(1),
+			projection,
+			page: None,
+			response_type: PhantomData,
+		}
+	}
+}
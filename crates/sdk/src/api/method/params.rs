@@ -0,0 +1,56 @@
+use crate::api::conn::Command;
+use crate::api::err::Error;
+use crate::api::method::BoxFuture;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::method::OnceLockExt;
+use crate::Surreal;
+use crate::Value;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::future::IntoFuture;
+use surrealdb_core::sql::Value as CoreValue;
+
+/// A params future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Params<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+}
+
+impl<C> Params<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> Params<'static, C> {
+		Params {
+			client: Cow::Owned(self.client.into_owned()),
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for Params<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<BTreeMap<String, Value>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.inner.router.extract()?;
+			match router.execute_value(Command::Params).await?.into_inner() {
+				CoreValue::None | CoreValue::Null => Ok(BTreeMap::new()),
+				CoreValue::Object(params) => {
+					Ok(params.0.into_iter().map(|(k, v)| (k, Value::from_inner(v))).collect())
+				}
+				value => Err(Error::FromValue {
+					value: Value::from_inner(value),
+					error: "expected the parameters to be an object".to_owned(),
+				}
+				.into()),
+			}
+		})
+	}
+}
@@ -3,10 +3,12 @@ use crate::api::conn::Command;
 use crate::api::err::Error;
 use crate::api::method::BoxFuture;
 use crate::api::opt;
+use crate::api::opt::Config;
 use crate::api::Connection;
 use crate::api::ExtraFeatures;
 use crate::api::Result;
 use crate::method::OnceLockExt;
+use crate::method::RawBytes;
 use crate::method::Stats;
 use crate::method::WithStats;
 use crate::value::Notification;
@@ -20,9 +22,11 @@ use serde::Serialize;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::future::IntoFuture;
+use std::mem;
 use std::pin::Pin;
 use std::task::Context;
 use std::task::Poll;
+use surrealdb_core::sql::statements::UseStatement;
 use surrealdb_core::sql::{
 	self, to_value as to_core_value, Object as CoreObject, Statement, Value as CoreValue,
 };
@@ -33,6 +37,7 @@ use surrealdb_core::sql::{
 pub struct Query<'r, C: Connection> {
 	pub(crate) client: Cow<'r, Surreal<C>>,
 	pub(crate) inner: Result<ValidQuery>,
+	pub(crate) target: UseStatement,
 }
 
 #[derive(Debug)]
@@ -48,6 +53,40 @@ pub(crate) enum ValidQuery {
 	},
 }
 
+impl ValidQuery {
+	/// Runs the interceptors of the connection over the query
+	fn intercept(self, config: &Config) -> Result<Self> {
+		if config.interceptors.is_empty() {
+			return Ok(self);
+		}
+		match self {
+			ValidQuery::Raw {
+				query,
+				mut bindings,
+			} => {
+				let mut query = query.into_owned();
+				config.intercept_raw(&mut query, &mut bindings)?;
+				Ok(ValidQuery::Raw {
+					query: Cow::Owned(query),
+					bindings,
+				})
+			}
+			ValidQuery::Normal {
+				mut query,
+				register_live_queries,
+				mut bindings,
+			} => {
+				config.intercept(&mut query, &mut bindings)?;
+				Ok(ValidQuery::Normal {
+					query,
+					register_live_queries,
+					bindings,
+				})
+			}
+		}
+	}
+}
+
 impl<'r, C> Query<'r, C>
 where
 	C: Connection,
@@ -65,6 +104,7 @@ where
 				bindings,
 				register_live_queries,
 			}),
+			target: Default::default(),
 		}
 	}
 
@@ -76,10 +116,12 @@ where
 			Ok(x) => Query {
 				client: self.client,
 				inner: f(x),
+				target: self.target,
 			},
 			x => Query {
 				client: self.client,
 				inner: x,
+				target: self.target,
 			},
 		}
 	}
@@ -89,8 +131,25 @@ where
 		Query {
 			client: Cow::Owned(self.client.into_owned()),
 			inner: self.inner,
+			target: self.target,
 		}
 	}
+
+	/// The `USE` statement to run before the query, if the namespace or database is overridden
+	fn use_statement(&self) -> Option<UseStatement> {
+		if self.target.ns.is_none() && self.target.db.is_none() {
+			return None;
+		}
+		Some(self.target.clone())
+	}
+}
+
+/// Removes the result of the `USE` statement which was run before the query
+fn skip_use_result(response: &mut Response) {
+	response.results = mem::take(&mut response.results)
+		.into_iter()
+		.filter_map(|(i, result)| Some((i.checked_sub(1)?, result)))
+		.collect();
 }
 
 impl<'r, Client> IntoFuture for Query<'r, Client>
@@ -104,18 +163,28 @@ where
 		Box::pin(async move {
 			// Extract the router from the client
 			let router = self.client.inner.router.extract()?;
+			// Check whether the namespace or database is overridden
+			let target = self.use_statement();
 
-			match self.inner? {
+			match self.inner?.intercept(&router.config)? {
 				ValidQuery::Raw {
 					query,
 					bindings,
 				} => {
-					router
+					let query = match &target {
+						Some(stmt) => Cow::Owned(format!("{stmt};\n{query}")),
+						None => query,
+					};
+					let mut response = router
 						.execute_query(Command::RawQuery {
 							query,
 							variables: bindings,
 						})
-						.await
+						.await?;
+					if target.is_some() {
+						skip_use_result(&mut response);
+					}
+					Ok(response)
 				}
 				ValidQuery::Normal {
 					query,
@@ -153,6 +222,10 @@ where
 
 					let mut query = sql::Query::default();
 					query.0 .0 = query_statements;
+					// Select the namespace and database for this query only
+					if let Some(stmt) = &target {
+						query.0 .0.insert(0, Statement::Use(stmt.clone()));
+					}
 
 					let mut response = router
 						.execute_query(Command::Query {
@@ -161,6 +234,10 @@ where
 						})
 						.await?;
 
+					if target.is_some() {
+						skip_use_result(&mut response);
+					}
+
 					for idx in query_indicies {
 						let Some((_, result)) = response.results.get(&idx) else {
 							continue;
@@ -176,9 +253,15 @@ where
 									)
 									.into());
 								};
-								live::register(router, uuid.0).await.map(|rx| {
-									Stream::new(self.client.inner.clone().into(), uuid.0, Some(rx))
-								})
+								live::register(router, uuid.0, router.config.live_query_buffer)
+									.await
+									.map(|subscription| {
+										Stream::new(
+											self.client.inner.clone().into(),
+											uuid.0,
+											Some(subscription),
+										)
+									})
 							}
 							Err(_) => Err(crate::Error::from(Error::NotLiveQuery(idx))),
 						};
@@ -207,6 +290,57 @@ where
 	}
 }
 
+impl<'r, Client> IntoFuture for RawBytes<Query<'r, Client>>
+where
+	Client: Connection,
+{
+	type Output = Result<Vec<u8>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			// Extract the router from the client
+			let router = self.0.client.inner.router.extract()?;
+
+			if self.0.use_statement().is_some() {
+				return Err(Error::InvalidParams(
+					"Overriding the namespace or database is not supported for raw responses"
+						.to_owned(),
+				)
+				.into());
+			}
+
+			let command = match self.0.inner?.intercept(&router.config)? {
+				ValidQuery::Raw {
+					query,
+					bindings,
+				} => Command::RawQuery {
+					query,
+					variables: bindings,
+				},
+				ValidQuery::Normal {
+					query: query_statements,
+					bindings,
+					..
+				} => {
+					let mut query = sql::Query::default();
+					query.0 .0 = query_statements;
+					Command::Query {
+						query,
+						variables: bindings,
+					}
+				}
+			};
+
+			router
+				.execute_raw(Command::RawResponse {
+					command: Box::new(command),
+				})
+				.await
+		})
+	}
+}
+
 impl<C> Query<'_, C>
 where
 	C: Connection,
@@ -246,11 +380,58 @@ where
 		})
 	}
 
+	/// Runs the query in the specified namespace, without changing the namespace of the client
+	///
+	/// This allows a single client to be shared between tasks which address different
+	/// namespaces concurrently, without racing on the namespace selected by [`Surreal::use_ns`].
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let response = db.query("SELECT * FROM user").use_ns("tenant").use_db("app").await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn use_ns(mut self, ns: impl Into<String>) -> Self {
+		self.target.ns = Some(ns.into());
+		self
+	}
+
+	/// Runs the query in the specified database, without changing the database of the client
+	pub fn use_db(mut self, db: impl Into<String>) -> Self {
+		self.target.db = Some(db.into());
+		self
+	}
+
 	/// Return query statistics along with its results
 	pub const fn with_stats(self) -> WithStats<Self> {
 		WithStats(self)
 	}
 
+	/// Return the response exactly as it was sent by the server, without decoding it
+	///
+	/// This allows proxies and caches to forward responses without decoding and
+	/// re-encoding them. The bytes are encoded in the format used by the connection.
+	/// Embedded engines don't have a server, so they encode the results using the
+	/// revisioned format instead. Live queries are not registered for raw responses.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let bytes: Vec<u8> = db.query("SELECT * FROM user").raw().await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub const fn raw(self) -> RawBytes<Self> {
+		RawBytes(self)
+	}
+
 	/// Binds a parameter or parameters to a query
 	///
 	/// # Examples
@@ -268,7 +449,7 @@ where
 	/// # }
 	/// ```
 	///
-	/// Binding an object
+	/// Binding an object, where every field becomes a parameter of the same name
 	///
 	/// ```no_run
 	/// use serde::Serialize;
@@ -442,6 +623,30 @@ impl Response {
 		index.query_result(self)
 	}
 
+	/// Takes the result of a query as a `serde_json::Value`
+	///
+	/// This is useful for forwarding results as JSON without deserializing them into a
+	/// specific type first. It accepts the same indices as [Response::take].
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// #
+	/// let mut response = db.query("SELECT * FROM user").await?;
+	///
+	/// let users: serde_json::Value = response.take_json(0)?;
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn take_json(&mut self, index: impl opt::QueryResult<Value>) -> Result<serde_json::Value> {
+		let value: Value = self.take(index)?;
+		Ok(value.into_json())
+	}
+
 	/// Takes and streams records returned from a `LIVE SELECT` query
 	///
 	/// This is the counterpart to [Response::take] used to stream the results
@@ -5,6 +5,7 @@ use crate::api::opt::Resource;
 use crate::api::Connection;
 use crate::api::Result;
 use crate::method::Live;
+use crate::method::Query;
 use crate::opt::KeyRange;
 use crate::Surreal;
 use crate::Value;
@@ -12,6 +13,9 @@ use serde::de::DeserializeOwned;
 use std::borrow::Cow;
 use std::future::IntoFuture;
 use std::marker::PhantomData;
+use surrealdb_core::sql::statements::SelectStatement;
+use surrealdb_core::sql::{Field, Fields, Idioms, Statement, Value as CoreValue, Values};
+use surrealdb_core::syn;
 
 /// A select future
 #[derive(Debug)]
@@ -21,6 +25,56 @@ pub struct Select<'r, C: Connection, R, T = ()> {
 	pub(super) resource: Result<Resource>,
 	pub(super) response_type: PhantomData<R>,
 	pub(super) query_type: PhantomData<T>,
+	pub(super) projection: Option<Projection>,
+}
+
+/// The fields returned by a select
+#[derive(Debug, Clone)]
+pub(super) enum Projection {
+	/// Only return these fields
+	Fields(Vec<String>),
+	/// Return every field except these
+	Omit(Vec<String>),
+}
+
+impl Projection {
+	/// The fields returned by the statement
+	pub(super) fn fields(&self) -> Result<Fields> {
+		let mut fields = Fields::default();
+		fields.0 = match self {
+			Projection::Fields(names) => names
+				.iter()
+				.map(|name| {
+					Ok(Field::Single {
+						expr: CoreValue::Idiom(syn::idiom(name)?),
+						alias: None,
+					})
+				})
+				.collect::<Result<_>>()?,
+			Projection::Omit(_) => vec![Field::All],
+		};
+		Ok(fields)
+	}
+
+	/// Sets the fields selected by the statement
+	pub(super) fn apply(&self, stmt: &mut SelectStatement) -> Result<()> {
+		stmt.expr = self.fields()?;
+		if let Projection::Omit(names) = self {
+			let mut omit = Idioms::default();
+			omit.0 = names.iter().map(|name| syn::idiom(name)).collect::<Result<_, _>>()?;
+			stmt.omit = Some(omit);
+		}
+		Ok(())
+	}
+
+	/// Builds a `SELECT` statement which only returns the projected fields of the resource
+	fn statement(&self, resource: Resource) -> Result<Statement> {
+		let mut stmt = SelectStatement::default();
+		self.apply(&mut stmt)?;
+		stmt.only = resource.is_single_recordid();
+		stmt.what = Values::from(vec![resource.into_core_value()]);
+		Ok(Statement::Select(stmt))
+	}
 }
 
 impl<C, R, T> Select<'_, C, R, T>
@@ -42,9 +96,15 @@ macro_rules! into_future {
 			let Select {
 				client,
 				resource,
+				projection,
 				..
 			} = self;
 			Box::pin(async move {
+				if let Some(projection) = projection {
+					let statement = projection.statement(resource?)?;
+					let query = Query::normal(client, vec![statement], Default::default(), false);
+					return query.await?.take(0);
+				}
 				let router = client.inner.router.extract()?;
 				router
 					.$method(Command::Select {
@@ -110,6 +170,66 @@ where
 	}
 }
 
+impl<C, R> Select<'_, C, R>
+where
+	C: Connection,
+{
+	/// Only returns the specified fields of the selected records
+	///
+	/// Fields can be nested, like `address.city`. Only the fields which are listed are returned,
+	/// so the record ID needs to be listed too if it is needed.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[derive(Debug, serde::Deserialize)]
+	/// # struct Contact {
+	/// #     name: String,
+	/// #     email: String,
+	/// # }
+	/// #
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// #
+	/// let contacts: Vec<Contact> = db.select("person").fields(["name", "email"]).await?;
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn fields<I, S>(mut self, fields: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: Into<String>,
+	{
+		self.projection = Some(Projection::Fields(fields.into_iter().map(Into::into).collect()));
+		self
+	}
+
+	/// Returns every field of the selected records except the specified ones
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// #
+	/// let people: Vec<surrealdb::Value> = db.select("person").omit(["password"]).await?;
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn omit<I, S>(mut self, fields: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: Into<String>,
+	{
+		self.projection = Some(Projection::Omit(fields.into_iter().map(Into::into).collect()));
+		self
+	}
+}
+
 impl<'r, C, R> Select<'r, C, R>
 where
 	C: Connection,
@@ -168,6 +288,7 @@ where
 			resource: self.resource,
 			response_type: self.response_type,
 			query_type: PhantomData,
+			projection: self.projection,
 		}
 	}
 }
@@ -0,0 +1,84 @@
+use crate::api::conn::Command;
+use crate::api::err::Error;
+use crate::api::method::BoxFuture;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::method::OnceLockExt;
+use crate::Surreal;
+use crate::Value;
+use std::borrow::Cow;
+use std::future::IntoFuture;
+use surrealdb_core::sql::Value as CoreValue;
+
+/// A server information future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ServerInfo<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+}
+
+impl<C> ServerInfo<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> ServerInfo<'static, C> {
+		ServerInfo {
+			client: Cow::Owned(self.client.into_owned()),
+		}
+	}
+}
+
+/// Metadata describing the server a client is connected to
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ServerMetadata {
+	/// The version of the server, including any build metadata
+	pub version: semver::Version,
+	/// The operating system and architecture the server is running on, unless the session is anonymous
+	pub build: Option<String>,
+	/// The storage engine the server is using, unless the session is anonymous
+	pub storage: Option<String>,
+	/// The capabilities which are enabled on the server, or `NONE` if the session is anonymous
+	pub capabilities: Value,
+}
+
+impl<'r, Client> IntoFuture for ServerInfo<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<ServerMetadata>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.inner.router.extract()?;
+			let info = router.execute_value(Command::ServerInfo).await?;
+			let CoreValue::Object(mut info) = info.into_inner() else {
+				return Err(Error::InternalError(
+					"received invalid server information".to_string(),
+				)
+				.into());
+			};
+			let mut field = |name: &str| info.remove(name).unwrap_or_default();
+			let version = field("version").to_raw_string();
+			let semantic = version.trim_start_matches("surrealdb-");
+			Ok(ServerMetadata {
+				version: semantic
+					.parse()
+					.map_err(|_| Error::InvalidSemanticVersion(format!("\"{version}\"")))?,
+				build: text(field("build")),
+				storage: text(field("storage")),
+				capabilities: Value::from_inner(field("capabilities")),
+			})
+		})
+	}
+}
+
+/// Converts a field which is only returned to authenticated sessions into a string
+fn text(value: CoreValue) -> Option<String> {
+	match value {
+		CoreValue::None => None,
+		value => Some(value.to_raw_string()),
+	}
+}
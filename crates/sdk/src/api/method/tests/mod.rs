@@ -11,15 +11,25 @@ use crate::api::opt::auth::Jwt;
 use crate::api::opt::auth::Namespace;
 use crate::api::opt::auth::Record;
 use crate::api::opt::auth::Root;
+use crate::api::opt::Config;
 use crate::api::opt::PatchOp;
+use crate::api::opt::QueryInterceptor;
+use crate::api::opt::QueryObserver;
+use crate::api::opt::Raw;
 use crate::api::Response as QueryResponse;
 use crate::api::Surreal;
+use crate::Value;
 use protocol::Client;
 use protocol::Test;
 use semver::Version;
+use std::collections::BTreeMap;
 use std::ops::Bound;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::sync::LazyLock;
+use std::time::Duration;
 use surrealdb_core::sql::statements::{BeginStatement, CommitStatement};
+use surrealdb_core::sql::{Object as CoreObject, Statement};
 use types::User;
 use types::USER;
 
@@ -32,6 +42,7 @@ async fn api() {
 
 	// health
 	let _: () = DB.health().await.unwrap();
+	let _: Duration = DB.health().with_latency().await.unwrap();
 
 	// invalidate
 	let _: () = DB.invalidate().await.unwrap();
@@ -90,6 +101,7 @@ async fn api() {
 
 	// query
 	let _: QueryResponse = DB.query("SELECT * FROM user").await.unwrap();
+	let _: Vec<u8> = DB.query("SELECT * FROM user").raw().await.unwrap();
 	let _: QueryResponse =
 		DB.query("CREATE user:john SET name = $name").bind(("name", "John Doe")).await.unwrap();
 	let _: QueryResponse = DB
@@ -165,13 +177,89 @@ async fn api() {
 	// import
 	let _: () = DB.import("backup.sql").await.unwrap();
 
+	// export to a writer
+	let mut backup = Vec::new();
+	let _: () = DB.export_to(&mut backup).await.unwrap();
+
+	// import from a reader
+	let _: () = DB.import_from(backup.as_slice()).await.unwrap();
+
 	// version
 	let _: Version = DB.version().await.unwrap();
 
+	// server info
+	let info = DB.server_info().await.unwrap();
+	assert_eq!(info.version, Version::new(1, 0, 0));
+	assert_eq!(info.storage.as_deref(), Some("memory"));
+
+	// compact
+	let compaction = DB.compact().await.unwrap();
+	assert_eq!(compaction.reclaimed(), 1024);
+
+	// params
+	let _: BTreeMap<String, Value> = DB.params().await.unwrap();
+
 	// run
 	let _: Option<User> = DB.run("foo").await.unwrap();
 }
 
+#[derive(Default)]
+struct Counter {
+	started: AtomicUsize,
+	finished: AtomicUsize,
+}
+
+impl QueryObserver for Arc<Counter> {
+	fn on_start(&self, _method: &'static str) {
+		self.started.fetch_add(1, Ordering::SeqCst);
+	}
+
+	fn on_finish(&self, method: &'static str, _duration: Duration) {
+		assert!(["health", "version"].contains(&method));
+		self.finished.fetch_add(1, Ordering::SeqCst);
+	}
+}
+
+#[tokio::test]
+async fn observer() {
+	let counter = Arc::new(Counter::default());
+	let config = Config::new().observer(counter.clone());
+	let db = Surreal::new::<Test>(((), config)).await.unwrap();
+	db.health().await.unwrap();
+	db.version().await.unwrap();
+	assert_eq!(counter.started.load(Ordering::SeqCst), 2);
+	assert_eq!(counter.finished.load(Ordering::SeqCst), 2);
+}
+
+struct ReadOnly;
+
+impl QueryInterceptor for ReadOnly {
+	fn intercept(
+		&self,
+		statements: &mut Vec<Statement>,
+		vars: &mut CoreObject,
+	) -> crate::Result<()> {
+		if statements.iter().any(|x| matches!(x, Statement::Delete(_))) {
+			return Err(crate::error::Api::InvalidParams("read only".to_owned()).into());
+		}
+		vars.insert("tenant".to_owned(), "acme".into());
+		Ok(())
+	}
+}
+
+#[tokio::test]
+async fn interceptor() {
+	let config = Config::new().interceptor(ReadOnly);
+	let db = Surreal::new::<Test>(((), config)).await.unwrap();
+	db.query("SELECT * FROM user").await.unwrap();
+	db.query("DELETE user").await.unwrap_err();
+	db.query(Raw::from("SELECT * FROM user")).await.unwrap_err();
+	// The other methods are intercepted as well
+	let _: Vec<User> = db.select(USER).await.unwrap();
+	let result: crate::Result<Vec<User>> = db.delete(USER).await;
+	result.unwrap_err();
+}
+
 fn assert_send_sync(_: impl Send + Sync) {}
 
 #[test]
@@ -2,6 +2,7 @@ use super::server;
 use crate::api::conn::Connection;
 use crate::api::conn::Router;
 use crate::api::method::BoxFuture;
+use crate::api::opt::Config;
 use crate::api::opt::Endpoint;
 use crate::api::opt::IntoEndpoint;
 use crate::api::Connect;
@@ -27,6 +28,16 @@ impl IntoEndpoint<Test> for () {
 	}
 }
 
+impl IntoEndpoint<Test> for ((), Config) {
+	type Client = Client;
+
+	fn into_endpoint(self) -> Result<Endpoint> {
+		let mut endpoint = IntoEndpoint::<Test>::into_endpoint(())?;
+		endpoint.config = self.1;
+		Ok(endpoint)
+	}
+}
+
 #[derive(Debug, Clone)]
 pub struct Client(());
 
@@ -57,6 +68,8 @@ impl Connection for Client {
 				sender: route_tx,
 				config: address.config,
 				last_id: AtomicI64::new(0),
+				cache: OnceLock::new(),
+				credentials: Default::default(),
 			};
 			server::mock(route_rx);
 			Ok((OnceLock::with_value(router), watch::channel(None)).into())
@@ -3,6 +3,7 @@ use crate::api::conn::{Command, DbResponse, Route};
 use crate::api::Response as QueryResponse;
 use crate::opt::Resource;
 use async_channel::Receiver;
+use std::collections::BTreeMap;
 use surrealdb_core::sql::{to_value as to_core_value, Value as CoreValue};
 
 pub(super) fn mock(route_rx: Receiver<Route>) {
@@ -29,6 +30,16 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 					..
 				} => Ok(DbResponse::Other("c6c0e36c-e2cf-42cb-b2d5-75415249b261".to_owned().into())),
 				Command::Version => Ok(DbResponse::Other("1.0.0".into())),
+				Command::ServerInfo => Ok(DbResponse::Other(CoreValue::from(BTreeMap::from([
+					("version".to_string(), CoreValue::from("1.0.0")),
+					("build".to_string(), CoreValue::from("linux on x86_64")),
+					("storage".to_string(), CoreValue::from("memory")),
+					("capabilities".to_string(), CoreValue::from(true)),
+				])))),
+				Command::Compact => Ok(DbResponse::Other(CoreValue::from(BTreeMap::from([
+					("before".to_string(), CoreValue::from(2048)),
+					("after".to_string(), CoreValue::from(1024)),
+				])))),
 				Command::Use {
 					..
 				} => Ok(DbResponse::Other(CoreValue::None)),
@@ -41,6 +52,10 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 				Command::Set {
 					..
 				} => Ok(DbResponse::Other(CoreValue::None)),
+				Command::Params => Ok(DbResponse::Other(CoreValue::Object(Default::default()))),
+				Command::RawResponse {
+					..
+				} => Ok(DbResponse::Raw(Vec::new())),
 				Command::Query {
 					..
 				}
@@ -130,6 +145,9 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 				}
 				| Command::ImportFile {
 					..
+				}
+				| Command::ImportBytes {
+					..
 				} => Ok(DbResponse::Other(CoreValue::None)),
 			};
 
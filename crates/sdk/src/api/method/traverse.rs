@@ -0,0 +1,122 @@
+use crate::api::method::BoxFuture;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::method::Query;
+use crate::RecordId;
+use crate::Surreal;
+use crate::Value;
+use serde::de::DeserializeOwned;
+use std::borrow::Cow;
+use std::future::IntoFuture;
+use std::marker::PhantomData;
+use surrealdb_core::sql::statements::SelectStatement;
+use surrealdb_core::sql::{
+	Dir, Field, Fields, Graph, Idiom, Part, Statement, Table, Tables, Value as CoreValue,
+};
+
+/// A typed graph traversal future
+///
+/// Each step follows an edge table from the records reached by the previous
+/// step, and then on to the records at the other end of those edges.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Traverse<'r, C: Connection, R> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) from: RecordId,
+	pub(super) steps: Vec<(Dir, String)>,
+	pub(super) response_type: PhantomData<R>,
+}
+
+impl<'r, C, R> Traverse<'r, C, R>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> Traverse<'static, C, R> {
+		Traverse {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+
+	/// Follows outgoing edges of the specified table, like `->edge->?`
+	pub fn out(mut self, edge: impl Into<String>) -> Self {
+		self.steps.push((Dir::Out, edge.into()));
+		self
+	}
+
+	/// Follows incoming edges of the specified table, like `<-edge<-?`
+	pub fn in_(mut self, edge: impl Into<String>) -> Self {
+		self.steps.push((Dir::In, edge.into()));
+		self
+	}
+
+	/// Follows edges of the specified table in both directions, like `<->edge<->?`
+	pub fn both(mut self, edge: impl Into<String>) -> Self {
+		self.steps.push((Dir::Both, edge.into()));
+		self
+	}
+
+	/// Sets the type which the records at the end of the traversal are deserialized into
+	pub fn collect<T>(self) -> Traverse<'r, C, T> {
+		Traverse {
+			client: self.client,
+			from: self.from,
+			steps: self.steps,
+			response_type: PhantomData,
+		}
+	}
+
+	/// Builds a `SELECT * FROM $from->edge->?...` statement for the traversal
+	fn statement(&self) -> Statement {
+		let mut parts = vec![Part::Start(CoreValue::from(self.from.clone().into_inner()))];
+		for (dir, edge) in &self.steps {
+			let mut via = Graph::default();
+			via.dir = dir.clone();
+			via.what = Tables::from(Table::from(edge.as_str()));
+			let mut to = Graph::default();
+			to.dir = dir.clone();
+			parts.push(Part::Graph(via));
+			parts.push(Part::Graph(to));
+		}
+		let mut stmt = SelectStatement::default();
+		let mut fields = Fields::default();
+		fields.0 = vec![Field::All];
+		stmt.expr = fields;
+		stmt.what.0 = vec![CoreValue::Idiom(Idiom::from(parts))];
+		Statement::Select(stmt)
+	}
+}
+
+impl<'r, Client> IntoFuture for Traverse<'r, Client, Value>
+where
+	Client: Connection,
+{
+	type Output = Result<Value>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let statement = self.statement();
+			let query = Query::normal(self.client, vec![statement], Default::default(), false);
+			query.await?.take(0)
+		})
+	}
+}
+
+impl<'r, Client, R> IntoFuture for Traverse<'r, Client, Vec<R>>
+where
+	Client: Connection,
+	R: DeserializeOwned,
+{
+	type Output = Result<Vec<R>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let statement = self.statement();
+			let query = Query::normal(self.client, vec![statement], Default::default(), false);
+			query.await?.take(0)
+		})
+	}
+}
@@ -0,0 +1,90 @@
+use crate::api::err::Error;
+use crate::api::method::BoxFuture;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::Surreal;
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::future::IntoFuture;
+use std::time::Duration;
+use surrealdb_core::sql::statements::InfoStatement;
+
+/// A future which resolves once an index has finished building
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WaitForIndex<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) table: String,
+	pub(super) index: String,
+	pub(super) interval: Duration,
+}
+
+impl<C> WaitForIndex<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> WaitForIndex<'static, C> {
+		WaitForIndex {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+
+	/// Sets how often the build status of the index is checked
+	pub const fn poll_interval(mut self, interval: Duration) -> Self {
+		self.interval = interval;
+		self
+	}
+}
+
+#[derive(Deserialize)]
+struct IndexInfo {
+	building: Option<Building>,
+}
+
+#[derive(Deserialize)]
+struct Building {
+	status: String,
+	error: Option<String>,
+}
+
+impl<'r, Client> IntoFuture for WaitForIndex<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<()>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			loop {
+				let info = InfoStatement::Index(
+					self.index.as_str().into(),
+					self.table.as_str().into(),
+					false,
+				);
+				let info: Option<IndexInfo> = self.client.query(info).await?.take(0)?;
+				// Indexes which are not built concurrently report no build status
+				let Some(building) = info.and_then(|v| v.building) else {
+					return Ok(());
+				};
+				match building.status.as_str() {
+					"ready" => return Ok(()),
+					"aborted" => {
+						return Err(Error::IndexBuildFailed(
+							self.index,
+							"the build was aborted".to_owned(),
+						)
+						.into());
+					}
+					"error" => {
+						let reason = building.error.unwrap_or_default();
+						return Err(Error::IndexBuildFailed(self.index, reason).into());
+					}
+					_ => self.client.inner.router.extract()?.sleep(self.interval).await,
+				}
+			}
+		})
+	}
+}
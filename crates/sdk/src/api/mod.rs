@@ -118,6 +118,9 @@ macro_rules! impl_serialize_wrapper {
 	};
 }
 
+#[cfg(feature = "bench")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bench")))]
+pub mod bench;
 pub mod engine;
 pub mod err;
 #[cfg(feature = "protocol-http")]
@@ -204,6 +207,17 @@ where
 		Box::pin(async move {
 			let endpoint = self.address?;
 			let endpoint_kind = EndpointKind::from(endpoint.url.scheme());
+			// Transactions are retried by the datastore, which only local engines embed
+			if endpoint_kind.is_remote() && endpoint.config.retry_policy.is_some() {
+				return Err(Error::RetryPolicyRemote.into());
+			}
+			// Only the WebSocket engine can run on a runtime other than tokio
+			#[cfg(not(target_family = "wasm"))]
+			if endpoint.config.runtime.is_some()
+				&& !matches!(endpoint_kind, EndpointKind::Ws | EndpointKind::Wss)
+			{
+				return Err(Error::RuntimeUnsupported.into());
+			}
 			let client = Client::connect(endpoint, self.capacity).await?;
 			if endpoint_kind.is_remote() {
 				match client.version().await {
@@ -238,6 +252,17 @@ where
 			}
 			let endpoint = self.address?;
 			let endpoint_kind = EndpointKind::from(endpoint.url.scheme());
+			// Transactions are retried by the datastore, which only local engines embed
+			if endpoint_kind.is_remote() && endpoint.config.retry_policy.is_some() {
+				return Err(Error::RetryPolicyRemote.into());
+			}
+			// Only the WebSocket engine can run on a runtime other than tokio
+			#[cfg(not(target_family = "wasm"))]
+			if endpoint.config.runtime.is_some()
+				&& !matches!(endpoint_kind, EndpointKind::Ws | EndpointKind::Wss)
+			{
+				return Err(Error::RuntimeUnsupported.into());
+			}
 			let client = Client::connect(endpoint, self.capacity).await?;
 			if endpoint_kind.is_remote() {
 				match client.version().await {
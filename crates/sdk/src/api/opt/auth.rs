@@ -3,6 +3,9 @@
 use serde::Deserialize;
 use serde::Serialize;
 use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
 
 /// A signup action
 #[derive(Debug)]
@@ -140,6 +143,50 @@ impl fmt::Debug for Jwt {
 	}
 }
 
+/// A future returned by a [`TokenRefresh`]
+pub type RefreshFuture = Pin<Box<dyn Future<Output = crate::Result<Jwt>> + Send + Sync + 'static>>;
+
+/// Provides a new token when the session of a connection has expired
+///
+/// When a request fails because its session or token has expired, the client signs in again
+/// with the credentials last passed to `signin` or `signup`, and retries the request once. A
+/// refresher can be set using [`Config::token_refresh`](crate::opt::Config::token_refresh) to
+/// authenticate with a token from elsewhere instead, for example one issued by an external
+/// identity provider.
+///
+/// # Examples
+///
+/// ```no_run
+/// use surrealdb::opt::auth::{Jwt, RefreshFuture, TokenRefresh};
+/// use surrealdb::opt::Config;
+///
+/// struct Vault;
+///
+/// impl TokenRefresh for Vault {
+///     fn refresh(&self) -> RefreshFuture {
+///         Box::pin(async move {
+///             // Fetch a fresh token from wherever it is issued
+///             Ok(Jwt::from("eyJhbGciOiJIUzI1NiJ9..."))
+///         })
+///     }
+/// }
+///
+/// let config = Config::new().token_refresh(Vault);
+/// ```
+pub trait TokenRefresh: Send + Sync + 'static {
+	/// Returns a token to authenticate the connection with
+	fn refresh(&self) -> RefreshFuture;
+}
+
+#[derive(Clone)]
+pub(crate) struct Refresher(pub(crate) Arc<dyn TokenRefresh>);
+
+impl fmt::Debug for Refresher {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("TokenRefresh").finish_non_exhaustive()
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -3,8 +3,8 @@
 use std::{collections::HashSet, mem};
 
 use surrealdb_core::dbs::capabilities::{
-	Capabilities as CoreCapabilities, ExperimentalTarget, FuncTarget, ParseFuncTargetError,
-	ParseNetTargetError, Targets,
+	Capabilities as CoreCapabilities, ExperimentalTarget, FuncTarget, ParseEnvTargetError,
+	ParseFuncTargetError, ParseNetTargetError, Targets,
 };
 
 /// A list of features that are still experimental
@@ -37,12 +37,14 @@ impl From<&ExperimentalFeature> for ExperimentalTarget {
 /// - Guest access: Whether or not unauthenticated users can execute queries
 /// - Functions: Whether or not users can execute certain functions
 /// - Network: Whether or not users can connect to certain network addresses
+/// - Environment: Whether or not users can read certain environment variables
 ///
 /// Capabilities are configured globally. By default, capabilities are configured as:
 /// - Scripting: false
 /// - Guest access: false
 /// - Functions: All functions are allowed
 /// - Network: No network address is allowed, all are impliticly denied
+/// - Environment: No environment variable is allowed, all are implicitly denied
 ///
 /// The capabilities are defined using allow/deny lists for fine-grained control.
 ///
@@ -432,6 +434,88 @@ impl Capabilities {
 		Ok(self)
 	}
 
+	/// Set the allow list to allow all environment variables
+	pub fn allow_all_env_vars(&mut self) -> &mut Self {
+		*self.cap.allowed_env_vars_mut() = Targets::All;
+		self
+	}
+
+	/// Set the allow list to allow all environment variables
+	pub fn with_allow_all_env_vars(mut self) -> Self {
+		self.allow_all_env_vars();
+		self
+	}
+
+	/// Add an environment variable to the allow lists
+	///
+	/// Adding an environment variable to the allow list overwrites previously set allow-all or
+	/// allow-none filters.
+	pub fn allow_env_var<S: AsRef<str>>(
+		&mut self,
+		name: S,
+	) -> Result<&mut Self, ParseEnvTargetError> {
+		let target = name.as_ref().parse()?;
+		match self.cap.allowed_env_vars_mut() {
+			Targets::None | Targets::All => {
+				let mut set = HashSet::new();
+				set.insert(target);
+				*self.cap.allowed_env_vars_mut() = Targets::Some(set);
+			}
+			Targets::Some(ref mut x) => {
+				x.insert(target);
+			}
+			_ => unreachable!(),
+		}
+		Ok(self)
+	}
+
+	/// Add an environment variable to the allow lists
+	///
+	/// Adding an environment variable to the allow list overwrites previously set allow-all or
+	/// allow-none filters.
+	pub fn with_allow_env_var<S: AsRef<str>>(
+		mut self,
+		name: S,
+	) -> Result<Self, ParseEnvTargetError> {
+		self.allow_env_var(name)?;
+		Ok(self)
+	}
+
+	/// Add an environment variable to the deny lists
+	///
+	/// Adding an environment variable to the deny list overwrites previously set deny-all or
+	/// deny-none filters.
+	pub fn deny_env_var<S: AsRef<str>>(
+		&mut self,
+		name: S,
+	) -> Result<&mut Self, ParseEnvTargetError> {
+		let target = name.as_ref().parse()?;
+		match self.cap.denied_env_vars_mut() {
+			Targets::None | Targets::All => {
+				let mut set = HashSet::new();
+				set.insert(target);
+				*self.cap.denied_env_vars_mut() = Targets::Some(set);
+			}
+			Targets::Some(ref mut x) => {
+				x.insert(target);
+			}
+			_ => unreachable!(),
+		}
+		Ok(self)
+	}
+
+	/// Add an environment variable to the deny lists
+	///
+	/// Adding an environment variable to the deny list overwrites previously set deny-all or
+	/// deny-none filters.
+	pub fn with_deny_env_var<S: AsRef<str>>(
+		mut self,
+		name: S,
+	) -> Result<Self, ParseEnvTargetError> {
+		self.deny_env_var(name)?;
+		Ok(self)
+	}
+
 	/// Set the allow list to allow all experimental features
 	pub fn allow_all_experimental_features(&mut self) -> &mut Self {
 		*self.cap.allowed_experimental_features_mut() = Targets::All;
@@ -1,8 +1,24 @@
+use super::auth::Refresher;
+use super::interceptor::Interceptor;
+use super::observer::Observer;
+#[cfg(not(target_family = "wasm"))]
+use super::runtime::RuntimeHandle;
+use crate::api::Result;
+use crate::opt::auth::TokenRefresh;
 use crate::opt::capabilities::Capabilities;
+#[cfg(not(target_family = "wasm"))]
+use crate::opt::Runtime;
+use crate::opt::{QueryInterceptor, QueryObserver};
+#[cfg(not(target_family = "wasm"))]
+use std::future::Future;
 #[cfg(storage)]
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
-use surrealdb_core::{dbs::Capabilities as CoreCapabilities, iam::Level};
+use surrealdb_core::sql::{Object, Statement};
+use surrealdb_core::{
+	dbs::Capabilities as CoreCapabilities, dbs::Durability, iam::Level, kvs::RetryPolicy,
+};
 
 /// Configuration for server connection, including: strictness, notifications, query_timeout, transaction_timeout
 #[derive(Debug, Clone, Default)]
@@ -11,6 +27,8 @@ pub struct Config {
 	pub(crate) ast_payload: bool,
 	pub(crate) query_timeout: Option<Duration>,
 	pub(crate) transaction_timeout: Option<Duration>,
+	pub(crate) retry_policy: Option<RetryPolicy>,
+	pub(crate) durability: Option<Durability>,
 	#[cfg(any(feature = "native-tls", feature = "rustls"))]
 	pub(crate) tls_config: Option<super::Tls>,
 	// Only used by the local engines
@@ -25,6 +43,58 @@ pub struct Config {
 	pub(crate) node_membership_check_interval: Option<Duration>,
 	pub(crate) node_membership_cleanup_interval: Option<Duration>,
 	pub(crate) changefeed_gc_interval: Option<Duration>,
+	pub(crate) event_process_interval: Option<Duration>,
+	pub(crate) observer: Option<Observer>,
+	pub(crate) interceptors: Vec<Interceptor>,
+	pub(crate) record_cache: Option<RecordCacheConfig>,
+	pub(crate) live_query_buffer: Option<LiveQueryBuffer>,
+	pub(crate) token_refresh: Option<Refresher>,
+	#[cfg(not(target_family = "wasm"))]
+	pub(crate) runtime: Option<RuntimeHandle>,
+	#[cfg(feature = "protocol-http")]
+	pub(crate) http: HttpConfig,
+}
+
+/// The size and expiry of the client-side record cache
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RecordCacheConfig {
+	pub(crate) capacity: usize,
+	pub(crate) ttl: Option<Duration>,
+}
+
+/// The number of notifications buffered for each live query stream
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LiveQueryBuffer {
+	pub(crate) capacity: usize,
+	pub(crate) overflow: LiveOverflow,
+}
+
+/// What happens to live query notifications which arrive while a stream's buffer is full
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LiveOverflow {
+	/// Drop the oldest buffered notification to make room for the new one
+	#[default]
+	DropOldest,
+	/// Drop the new notification, keeping the buffered ones
+	DropNewest,
+	/// Drop the new notification, and return [`Error::LiveQueryLagged`](crate::error::Api::LiveQueryLagged)
+	/// with the number of dropped notifications the next time the stream is polled
+	///
+	/// Streams of [`Value`](crate::Value), which don't return errors, skip the dropped notifications.
+	Lag,
+}
+
+/// Connection options which are only used by the HTTP engine
+#[cfg(feature = "protocol-http")]
+#[cfg_attr(target_family = "wasm", allow(dead_code))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct HttpConfig {
+	pub(crate) tcp_keep_alive: Option<Duration>,
+	pub(crate) http2_keep_alive: Option<Duration>,
+	pub(crate) http2_only: bool,
+	pub(crate) max_idle_connections: Option<usize>,
+	pub(crate) tcp_nodelay: Option<bool>,
 }
 
 impl Config {
@@ -69,6 +139,26 @@ impl Config {
 		self
 	}
 
+	/// Set the policy for retrying transactions which fail due to a conflict
+	///
+	/// Statements which are not part of an explicit `BEGIN` / `COMMIT` block are
+	/// re-executed with a jittered exponential backoff. Only local engines can retry
+	/// transactions, so connecting to a remote server with a retry policy fails.
+	pub fn retry_policy(mut self, policy: impl Into<Option<RetryPolicy>>) -> Self {
+		self.retry_policy = policy.into();
+		self
+	}
+
+	/// Set how far writes must have progressed before they are acknowledged
+	///
+	/// Statements which are part of an explicit `BEGIN` / `COMMIT` block are always
+	/// acknowledged once the transaction has been committed. With [`Durability::Memory`]
+	/// a write is acknowledged before it is committed, so it may later be lost.
+	pub fn durability(mut self, durability: impl Into<Option<Durability>>) -> Self {
+		self.durability = durability.into();
+		self
+	}
+
 	/// Set the default user
 	pub fn user(mut self, user: crate::opt::auth::Root<'_>) -> Self {
 		self.auth = Level::Root;
@@ -140,4 +230,219 @@ impl Config {
 		self.changefeed_gc_interval = interval.into().filter(|x| !x.is_zero());
 		self
 	}
+
+	/// Set the interval at which the database should process asynchronous events
+	pub fn event_process_interval(mut self, interval: impl Into<Option<Duration>>) -> Self {
+		self.event_process_interval = interval.into().filter(|x| !x.is_zero());
+		self
+	}
+
+	/// Set the interval at which TCP keep-alive probes are sent on HTTP connections
+	///
+	/// Only used by the HTTP engine, and ignored when targeting WebAssembly. This does not send
+	/// HTTP/2 pings, which are set separately with [`Config::http2_keep_alive`].
+	#[cfg(feature = "protocol-http")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "protocol-http")))]
+	pub fn http_keep_alive(mut self, interval: impl Into<Option<Duration>>) -> Self {
+		self.http.tcp_keep_alive = interval.into().filter(|x| !x.is_zero());
+		self
+	}
+
+	/// Set the interval at which HTTP/2 pings are sent to keep HTTP connections alive
+	///
+	/// Only used by the HTTP engine when the connection uses HTTP/2, and ignored when targeting
+	/// WebAssembly.
+	#[cfg(feature = "protocol-http")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "protocol-http")))]
+	pub fn http2_keep_alive(mut self, interval: impl Into<Option<Duration>>) -> Self {
+		self.http.http2_keep_alive = interval.into().filter(|x| !x.is_zero());
+		self
+	}
+
+	/// Only use HTTP/2 when connecting to the server
+	///
+	/// Only used by the HTTP engine, and ignored when targeting WebAssembly. The server must
+	/// accept HTTP/2 connections without a prior upgrade.
+	#[cfg(feature = "protocol-http")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "protocol-http")))]
+	pub fn http2_only(mut self, enabled: bool) -> Self {
+		self.http.http2_only = enabled;
+		self
+	}
+
+	/// Set the maximum number of idle HTTP connections which are kept open for reuse
+	///
+	/// Only used by the HTTP engine, and ignored when targeting WebAssembly.
+	#[cfg(feature = "protocol-http")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "protocol-http")))]
+	pub fn http_max_idle_connections(mut self, max: usize) -> Self {
+		self.http.max_idle_connections = Some(max);
+		self
+	}
+
+	/// Set whether `TCP_NODELAY` is enabled on HTTP connections
+	///
+	/// Only used by the HTTP engine, and ignored when targeting WebAssembly.
+	#[cfg(feature = "protocol-http")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "protocol-http")))]
+	pub fn http_tcp_nodelay(mut self, enabled: bool) -> Self {
+		self.http.tcp_nodelay = Some(enabled);
+		self
+	}
+
+	/// Cache the records returned when selecting a single record by its id
+	///
+	/// Up to `capacity` records are kept on the client, and each is evicted once its `ttl`
+	/// elapses. Records which are changed through this client are evicted immediately. On
+	/// engines which support live queries, a live query is also started on each cached table
+	/// so that records changed by other clients are evicted as soon as they change.
+	///
+	/// This is intended for read-mostly reference data. Without live queries, as with the HTTP
+	/// engine, a record changed by another client can be served stale until its `ttl` elapses.
+	pub fn record_cache(mut self, capacity: usize, ttl: impl Into<Option<Duration>>) -> Self {
+		self.record_cache = Some(RecordCacheConfig {
+			capacity,
+			ttl: ttl.into(),
+		});
+		self
+	}
+
+	/// Limit the number of notifications buffered for each live query stream
+	///
+	/// By default notifications are buffered without limit, so a stream which is polled more
+	/// slowly than notifications arrive keeps growing. With a limit, at most `capacity`
+	/// notifications are buffered, and `overflow` decides what happens to notifications which
+	/// arrive while the buffer is full.
+	pub fn live_query_buffer(mut self, capacity: usize, overflow: LiveOverflow) -> Self {
+		self.live_query_buffer = Some(LiveQueryBuffer {
+			capacity: capacity.max(1),
+			overflow,
+		});
+		self
+	}
+
+	/// Set the async runtime which the WebSocket engine runs on, instead of tokio
+	///
+	/// The other engines need tokio, so connecting to them with a runtime set returns an error.
+	#[cfg(not(target_family = "wasm"))]
+	pub fn runtime(mut self, runtime: impl Runtime) -> Self {
+		self.runtime = Some(RuntimeHandle(Arc::new(runtime)));
+		self
+	}
+
+	/// Set where a new token comes from when the session of the connection expires
+	///
+	/// By default the client signs in again with the credentials last passed to `signin` or
+	/// `signup`. See [`TokenRefresh`] for details.
+	pub fn token_refresh(mut self, refresh: impl TokenRefresh) -> Self {
+		self.token_refresh = Some(Refresher(Arc::new(refresh)));
+		self
+	}
+
+	/// Set an observer which is notified about every request made by the client
+	pub fn observer(mut self, observer: impl QueryObserver) -> Self {
+		self.observer = Some(Observer(Arc::new(observer)));
+		self
+	}
+
+	/// Add an interceptor which can inspect or rewrite every query before it is sent
+	///
+	/// Interceptors are run in the order in which they were added.
+	pub fn interceptor(mut self, interceptor: impl QueryInterceptor) -> Self {
+		self.interceptors.push(Interceptor(Arc::new(interceptor)));
+		self
+	}
+
+	/// Run each of the interceptors in turn over a query
+	pub(crate) fn intercept(
+		&self,
+		statements: &mut Vec<Statement>,
+		vars: &mut Object,
+	) -> Result<()> {
+		for interceptor in self.interceptors.iter() {
+			interceptor.0.intercept(statements, vars)?;
+		}
+		Ok(())
+	}
+
+	/// Run each of the interceptors in turn over a raw query
+	pub(crate) fn intercept_raw(&self, query: &mut String, vars: &mut Object) -> Result<()> {
+		for interceptor in self.interceptors.iter() {
+			interceptor.0.intercept_raw(query, vars)?;
+		}
+		Ok(())
+	}
+
+	/// Notify the observer, if any, that bytes were sent to the server
+	#[allow(dead_code)] // used by the remote connections
+	pub(crate) fn observe_sent(&self, bytes: usize) {
+		if let Some(observer) = &self.observer {
+			observer.0.on_bytes_sent(bytes);
+		}
+	}
+
+	/// Notify the observer, if any, that bytes were received from the server
+	#[allow(dead_code)] // used by the remote connections
+	pub(crate) fn observe_received(&self, bytes: usize) {
+		if let Some(observer) = &self.observer {
+			observer.0.on_bytes_received(bytes);
+		}
+	}
+
+	/// Run a future in the background on the configured runtime
+	#[cfg(not(target_family = "wasm"))]
+	pub(crate) fn spawn<F>(&self, future: F)
+	where
+		F: Future<Output = ()> + Send + 'static,
+	{
+		match &self.runtime {
+			Some(runtime) => runtime.0.spawn(Box::pin(future)),
+			None => {
+				tokio::spawn(future);
+			}
+		}
+	}
+
+	/// Wait for the duration to elapse on the configured runtime
+	#[cfg(not(target_family = "wasm"))]
+	pub(crate) async fn sleep(&self, duration: Duration) {
+		match &self.runtime {
+			Some(runtime) => runtime.0.sleep(duration).await,
+			None => tokio::time::sleep(duration).await,
+		}
+	}
+}
+
+#[cfg(all(test, feature = "protocol-http"))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn http_options() {
+		let config = Config::new();
+		assert_eq!(config.http.tcp_keep_alive, None);
+		assert_eq!(config.http.http2_keep_alive, None);
+		assert!(!config.http.http2_only);
+		assert_eq!(config.http.max_idle_connections, None);
+		assert_eq!(config.http.tcp_nodelay, None);
+		let config = Config::new()
+			.http_keep_alive(Duration::from_secs(30))
+			.http2_keep_alive(Duration::from_secs(10))
+			.http2_only(true)
+			.http_max_idle_connections(4)
+			.http_tcp_nodelay(false);
+		assert_eq!(config.http.tcp_keep_alive, Some(Duration::from_secs(30)));
+		assert_eq!(config.http.http2_keep_alive, Some(Duration::from_secs(10)));
+		assert!(config.http.http2_only);
+		assert_eq!(config.http.max_idle_connections, Some(4));
+		assert_eq!(config.http.tcp_nodelay, Some(false));
+	}
+
+	#[test]
+	fn http_keep_alive_can_be_disabled() {
+		let config = Config::new().http_keep_alive(Duration::from_secs(30)).http_keep_alive(None);
+		assert_eq!(config.http.tcp_keep_alive, None);
+		let config = Config::new().http2_keep_alive(Duration::ZERO);
+		assert_eq!(config.http.http2_keep_alive, None);
+	}
 }
@@ -121,6 +121,7 @@ pub enum EndpointKind {
 	Https,
 	Ws,
 	Wss,
+	Unix,
 	FoundationDb,
 	#[cfg(target_family = "wasm")]
 	IndxDb,
@@ -141,6 +142,7 @@ impl From<&str> for EndpointKind {
 			"https" => Self::Https,
 			"ws" => Self::Ws,
 			"wss" => Self::Wss,
+			"unix" => Self::Unix,
 			"fdb" => Self::FoundationDb,
 			#[cfg(target_family = "wasm")]
 			"indxdb" => Self::IndxDb,
@@ -161,7 +163,11 @@ impl EndpointKind {
 	pub fn is_remote(&self) -> bool {
 		matches!(
 			self,
-			EndpointKind::Http | EndpointKind::Https | EndpointKind::Ws | EndpointKind::Wss
+			EndpointKind::Http
+				| EndpointKind::Https
+				| EndpointKind::Ws
+				| EndpointKind::Wss
+				| EndpointKind::Unix
 		)
 	}
 
@@ -54,3 +54,47 @@ macro_rules! endpoints {
 }
 
 endpoints!(&str, &String, String, SocketAddr);
+
+#[cfg(unix)]
+mod unix {
+	use crate::api::engine::remote::ws::Client;
+	use crate::api::engine::remote::ws::Unix;
+	use crate::api::opt::IntoEndpoint;
+	use crate::api::Endpoint;
+	use crate::api::Result;
+	use crate::opt::Config;
+	use std::path::Path;
+	use std::path::PathBuf;
+	use url::Url;
+
+	macro_rules! endpoints {
+		($($name:ty),*) => {
+			$(
+				impl IntoEndpoint<Unix> for $name {
+					type Client = Client;
+
+					fn into_endpoint(self) -> Result<Endpoint> {
+						let protocol = "unix://";
+						let url = Url::parse(protocol)
+							.unwrap_or_else(|_| unreachable!("`{protocol}` should be static and valid"));
+						let mut endpoint = Endpoint::new(url);
+						endpoint.path = super::super::path_to_string(protocol, self);
+						Ok(endpoint)
+					}
+				}
+
+				impl IntoEndpoint<Unix> for ($name, Config) {
+					type Client = Client;
+
+					fn into_endpoint(self) -> Result<Endpoint> {
+						let mut endpoint = IntoEndpoint::<Unix>::into_endpoint(self.0)?;
+						endpoint.config = self.1;
+						Ok(endpoint)
+					}
+				}
+			)*
+		}
+	}
+
+	endpoints!(&str, &String, String, &Path, PathBuf);
+}
@@ -0,0 +1,69 @@
+use crate::api::err::Error;
+use crate::api::Result;
+use std::fmt;
+use std::sync::Arc;
+use surrealdb_core::sql::{Object, Statement};
+
+/// Inspects or rewrites queries before they are sent to the database
+///
+/// Interceptors can be registered on a connection using
+/// [`Config::interceptor`](crate::opt::Config::interceptor), and are run in the order in which
+/// they were registered, for every query made with [`Surreal::query`](crate::Surreal::query).
+/// Methods which read or write records, like [`Surreal::select`](crate::Surreal::select) or
+/// [`Surreal::create`](crate::Surreal::create), are intercepted as well. The statement such a
+/// method runs is passed to the interceptors, and is then sent to the database as a query.
+/// Returning an error from an interceptor stops the query from being sent.
+///
+/// # Examples
+///
+/// ```no_run
+/// use surrealdb::opt::{Config, QueryInterceptor};
+/// use surrealdb::sql::{Limit, Object, Statement};
+///
+/// struct Tenant(String);
+///
+/// impl QueryInterceptor for Tenant {
+///     fn intercept(&self, statements: &mut Vec<Statement>, vars: &mut Object) -> surrealdb::Result<()> {
+///         // Bind the tenant, so that it can be used by table permissions
+///         vars.insert("tenant".to_owned(), self.0.clone().into());
+///         // Limit the number of records which can be selected at once
+///         for statement in statements.iter_mut() {
+///             if let Statement::Select(select) = statement {
+///                 select.limit.get_or_insert(Limit(1000.into()));
+///             }
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() -> surrealdb::Result<()> {
+/// let config = Config::new().interceptor(Tenant("acme".to_owned()));
+/// let db = surrealdb::engine::any::connect(("ws://localhost:8000", config)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait QueryInterceptor: Send + Sync + 'static {
+	/// Called with the statements and variables of a query before it is sent
+	fn intercept(&self, statements: &mut Vec<Statement>, vars: &mut Object) -> Result<()>;
+
+	/// Called with the text and variables of a [raw query](crate::opt::Raw) before it is sent
+	///
+	/// Raw queries are not parsed by the client, so they can not be reliably inspected. By
+	/// default they are rejected, so that an interceptor can not be bypassed by sending a raw query.
+	fn intercept_raw(&self, _query: &mut String, _vars: &mut Object) -> Result<()> {
+		Err(Error::InvalidParams(
+			"Raw queries are not supported by the query interceptor".to_owned(),
+		)
+		.into())
+	}
+}
+
+#[derive(Clone)]
+pub(crate) struct Interceptor(pub(crate) Arc<dyn QueryInterceptor>);
+
+impl fmt::Debug for Interceptor {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Interceptor").finish_non_exhaustive()
+	}
+}
@@ -9,17 +9,29 @@ pub mod capabilities;
 mod config;
 mod endpoint;
 mod export;
+mod interceptor;
+mod observer;
 mod query;
 mod resource;
+#[cfg(not(target_family = "wasm"))]
+mod runtime;
 mod tls;
 
 pub use config::*;
 pub use endpoint::*;
 pub use export::*;
+pub use interceptor::QueryInterceptor;
+pub use observer::QueryObserver;
 pub use query::*;
 pub use resource::*;
+#[cfg(not(target_family = "wasm"))]
+pub(crate) use runtime::RuntimeHandle;
+#[cfg(not(target_family = "wasm"))]
+pub use runtime::{Runtime, RuntimeFuture, RuntimeIo};
 use serde_content::Serializer;
 use serde_content::Value as Content;
+pub use surrealdb_core::dbs::Durability;
+pub use surrealdb_core::kvs::RetryPolicy;
 #[cfg(any(feature = "native-tls", feature = "rustls"))]
 pub use tls::*;
 
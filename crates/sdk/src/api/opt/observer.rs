@@ -0,0 +1,69 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Callbacks which are notified about the requests made by a client
+///
+/// An observer can be set on a connection using [`Config::observer`](crate::opt::Config::observer),
+/// which allows metrics and tracing spans to be exported for every request made by the client,
+/// without wrapping each call site. Every callback has a default implementation which does
+/// nothing, so only the relevant callbacks need to be implemented.
+///
+/// Callbacks are run inline with the requests they describe, so they should return quickly.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::atomic::{AtomicU64, Ordering};
+/// use std::time::Duration;
+/// use surrealdb::opt::{Config, QueryObserver};
+///
+/// #[derive(Default)]
+/// struct Metrics {
+///     requests: AtomicU64,
+///     errors: AtomicU64,
+/// }
+///
+/// impl QueryObserver for Metrics {
+///     fn on_finish(&self, method: &'static str, duration: Duration) {
+///         self.requests.fetch_add(1, Ordering::Relaxed);
+///         println!("{method} took {duration:?}");
+///     }
+///
+///     fn on_error(&self, _method: &'static str, _error: &surrealdb::Error) {
+///         self.errors.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() -> surrealdb::Result<()> {
+/// let config = Config::new().observer(Metrics::default());
+/// let db = surrealdb::engine::any::connect(("ws://localhost:8000", config)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait QueryObserver: Send + Sync + 'static {
+	/// Called when a request is about to be sent
+	fn on_start(&self, _method: &'static str) {}
+
+	/// Called when a request has finished, whether it succeeded or not
+	fn on_finish(&self, _method: &'static str, _duration: Duration) {}
+
+	/// Called when a request has failed, before [`QueryObserver::on_finish`]
+	fn on_error(&self, _method: &'static str, _error: &crate::Error) {}
+
+	/// Called when bytes have been sent to a remote server
+	fn on_bytes_sent(&self, _bytes: usize) {}
+
+	/// Called when bytes have been received from a remote server
+	fn on_bytes_received(&self, _bytes: usize) {}
+}
+
+#[derive(Clone)]
+pub(crate) struct Observer(pub(crate) Arc<dyn QueryObserver>);
+
+impl fmt::Debug for Observer {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Observer").finish_non_exhaustive()
+	}
+}
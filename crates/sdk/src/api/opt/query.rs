@@ -9,9 +9,11 @@ use futures::stream::select_all;
 use serde::de::DeserializeOwned;
 use std::marker::PhantomData;
 use std::mem;
+use surrealdb_core::dbs::Capabilities;
 use surrealdb_core::sql::{
 	self, from_value as from_core_value, statements::*, Statement, Statements, Value as CoreValue,
 };
+use surrealdb_core::syn;
 
 use super::Raw;
 
@@ -25,6 +27,18 @@ pub trait IntoQuery {
 	fn as_str(&self) -> Option<&str> {
 		None
 	}
+
+	/// Not public API
+	#[doc(hidden)]
+	fn into_query_with(self, capabilities: &Capabilities) -> Result<Vec<Statement>>
+	where
+		Self: Sized,
+	{
+		match self.as_str() {
+			Some(surql) => syn::parse_with_capabilities(surql, capabilities)?.into_query(),
+			None => self.into_query(),
+		}
+	}
 }
 
 impl IntoQuery for sql::Query {
@@ -39,9 +53,66 @@ impl IntoQuery for Statements {
 	}
 }
 
-impl IntoQuery for Vec<Statement> {
+/// Concatenates the statements of each query fragment
+fn concat<T>(
+	fragments: impl IntoIterator<Item = T>,
+	capabilities: &Capabilities,
+) -> Result<Vec<Statement>>
+where
+	T: IntoQuery,
+{
+	let mut query = Vec::new();
+	for fragment in fragments {
+		match fragment.into_query_with(capabilities) {
+			Ok(statements) => query.extend(statements),
+			Err(crate::Error::Api(Error::RawQuery(_))) => {
+				return Err(Error::InvalidParams(
+					"Combining raw queries with other queries is not supported".to_owned(),
+				)
+				.into())
+			}
+			Err(error) => return Err(error),
+		}
+	}
+	Ok(query)
+}
+
+impl<T> IntoQuery for Vec<T>
+where
+	T: IntoQuery,
+{
+	fn into_query(self) -> Result<Vec<Statement>> {
+		self.into_query_with(&Capabilities::default())
+	}
+
+	fn into_query_with(self, capabilities: &Capabilities) -> Result<Vec<Statement>> {
+		concat(self, capabilities)
+	}
+}
+
+impl<T, const N: usize> IntoQuery for [T; N]
+where
+	T: IntoQuery,
+{
+	fn into_query(self) -> Result<Vec<Statement>> {
+		self.into_query_with(&Capabilities::default())
+	}
+
+	fn into_query_with(self, capabilities: &Capabilities) -> Result<Vec<Statement>> {
+		concat(self, capabilities)
+	}
+}
+
+impl<T> IntoQuery for &[T]
+where
+	T: IntoQuery + Clone,
+{
 	fn into_query(self) -> Result<Vec<Statement>> {
-		Ok(self)
+		self.into_query_with(&Capabilities::default())
+	}
+
+	fn into_query_with(self, capabilities: &Capabilities) -> Result<Vec<Statement>> {
+		concat(self.iter().cloned(), capabilities)
 	}
 }
 
@@ -0,0 +1,81 @@
+use futures::future::BoxFuture;
+use futures::io::{AsyncRead, AsyncWrite};
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A future returned by a [`Runtime`]
+pub type RuntimeFuture<T> = Pin<Box<dyn Future<Output = T> + Send + Sync + 'static>>;
+
+/// A connection opened by a [`Runtime`]
+pub trait RuntimeIo: AsyncRead + AsyncWrite + Send + Sync + Unpin + 'static {}
+
+impl<T> RuntimeIo for T where T: AsyncRead + AsyncWrite + Send + Sync + Unpin + 'static {}
+
+/// The async runtime which the remote engines run on
+///
+/// By default the client spawns its background tasks, sleeps, and opens its connections using
+/// tokio. A runtime can be set on a connection using [`Config::runtime`](crate::opt::Config::runtime),
+/// which allows the WebSocket engine to be used from applications built on other executors, such
+/// as async-std or smol, without running a tokio runtime alongside them.
+///
+/// Only the WebSocket engine over TCP (`ws://` and `wss://`) can run on a custom runtime. The
+/// HTTP engine is built on `reqwest`, the Unix socket engine on tokio's `UnixStream`, and the
+/// embedded engines on the tokio based datastore, so those still need a tokio runtime. Connecting
+/// to any of them with a custom runtime returns an error.
+///
+/// # Examples
+///
+/// ```ignore
+/// use std::time::Duration;
+/// use surrealdb::opt::{Config, Runtime, RuntimeFuture, RuntimeIo};
+///
+/// struct Smol;
+///
+/// impl Runtime for Smol {
+///     fn spawn(&self, future: futures::future::BoxFuture<'static, ()>) {
+///         smol::spawn(future).detach();
+///     }
+///
+///     fn sleep(&self, duration: Duration) -> RuntimeFuture<()> {
+///         Box::pin(async move {
+///             smol::Timer::after(duration).await;
+///         })
+///     }
+///
+///     fn connect(&self, host: &str, port: u16) -> RuntimeFuture<std::io::Result<Box<dyn RuntimeIo>>> {
+///         let host = host.to_owned();
+///         Box::pin(async move {
+///             let stream = smol::net::TcpStream::connect((host.as_str(), port)).await?;
+///             Ok(Box::new(stream) as Box<dyn RuntimeIo>)
+///         })
+///     }
+/// }
+///
+/// let config = Config::new().runtime(Smol);
+/// let db = smol::block_on(surrealdb::engine::any::connect(("ws://localhost:8000", config)))?;
+/// ```
+pub trait Runtime: Send + Sync + 'static {
+	/// Runs a future in the background until it completes
+	fn spawn(&self, future: BoxFuture<'static, ()>);
+
+	/// Returns a future which completes once the duration has elapsed
+	fn sleep(&self, duration: Duration) -> RuntimeFuture<()>;
+
+	/// Opens a TCP connection to the specified host and port
+	///
+	/// TLS and the WebSocket protocol are handled by the client on top of the connection.
+	fn connect(&self, host: &str, port: u16) -> RuntimeFuture<io::Result<Box<dyn RuntimeIo>>>;
+}
+
+#[derive(Clone)]
+pub(crate) struct RuntimeHandle(pub(crate) Arc<dyn Runtime>);
+
+impl fmt::Debug for RuntimeHandle {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Runtime").finish_non_exhaustive()
+	}
+}
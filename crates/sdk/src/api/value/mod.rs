@@ -25,6 +25,33 @@ pub fn from_value<T: DeserializeOwned>(value: Value) -> Result<T, Error> {
 	Ok(surrealdb_core::sql::from_value(value.0)?)
 }
 
+/// Deserializes a value `T` which borrows strings and bytes from `value`
+///
+/// Unlike [`from_value`], this avoids cloning every string in large responses.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() -> surrealdb::Result<()> {
+/// use serde::Deserialize;
+/// use surrealdb::value::from_value_ref;
+/// # let db = surrealdb::Surreal::new::<surrealdb::engine::remote::ws::Ws>("localhost:8000").await?;
+///
+/// #[derive(Deserialize)]
+/// struct Person<'a> {
+///     name: &'a str,
+/// }
+///
+/// let value = db.query("SELECT name FROM person").await?.take::<surrealdb::Value>(0)?;
+/// let people: Vec<Person> = from_value_ref(&value)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn from_value_ref<'a, T: Deserialize<'a>>(value: &'a Value) -> Result<T, Error> {
+	Ok(surrealdb_core::sql::from_value_ref(&value.0)?)
+}
+
 pub fn to_value<T: Serialize + 'static>(value: T) -> Result<Value, Error> {
 	let v = surrealdb_core::sql::to_value(value)?;
 	Ok(Value(v))
@@ -314,6 +341,14 @@ transparent_wrapper!(
 impl_serialize_wrapper!(Value);
 
 impl Value {
+	/// Converts the value into a `serde_json::Value`
+	///
+	/// Types which JSON has no equivalent for are converted into their simpler forms, so record
+	/// IDs and datetimes become strings, and durations become their string representation.
+	pub fn into_json(self) -> serde_json::Value {
+		self.0.into_json()
+	}
+
 	// TODO: Check if all of theses are actually used.
 	#[allow(dead_code)]
 	pub(crate) fn core_to_array(v: Vec<CoreValue>) -> Vec<Value> {
@@ -370,6 +405,12 @@ impl Value {
 	}
 }
 
+impl From<Value> for serde_json::Value {
+	fn from(value: Value) -> Self {
+		value.into_json()
+	}
+}
+
 pub struct ConversionError {
 	from: &'static str,
 	expected: &'static str,
@@ -137,13 +137,19 @@ pub mod channel {
 /// Different error types for embedded and remote databases
 pub mod error {
 	pub use crate::api::err::Error as Api;
+	pub use crate::api::err::ErrorInfo;
 	pub use surrealdb_core::err::Error as Db;
+	pub use surrealdb_core::err::ErrorDetails;
 }
 
 #[cfg(feature = "protocol-http")]
 #[doc(hidden)]
 pub use crate::api::headers;
 
+#[cfg(feature = "bench")]
+#[doc(inline)]
+pub use crate::api::bench;
+
 #[doc(inline)]
 pub use crate::api::{
 	engine, method, opt,
@@ -154,6 +160,9 @@ pub use crate::api::{
 };
 
 /// An error originating from the SurrealDB client library
+///
+/// Both kinds of error have a stable [`code`](Error::code) and structured
+/// [`info`](Error::info), which can be serialized to keep the details.
 #[derive(Debug, thiserror::Error, serde::Serialize)]
 pub enum Error {
 	/// An error with an embedded storage engine
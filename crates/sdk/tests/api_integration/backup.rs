@@ -128,6 +128,102 @@ pub async fn export_with_config(new_db: impl CreateDb) {
 	}
 }
 
+pub async fn export_without_events(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&db_name).await.unwrap();
+	db.query(
+		"DEFINE EVENT audit FOR DATABASE WHEN table_created THEN (CREATE audit_log SET tb = $table)",
+	)
+	.await
+	.unwrap()
+	.check()
+	.unwrap();
+	drop(permit);
+
+	// Export without events, remove the event, and import
+	let file = format!("{db_name}.sql");
+	let res = async {
+		db.export(&file).with_config().events(false).await?;
+		db.query("REMOVE EVENT audit FOR DATABASE").await?.check()?;
+		db.import(&file).await?;
+		Result::<(), Error>::Ok(())
+	}
+	.await;
+	remove_file(&file).await.unwrap();
+	res.unwrap();
+
+	// Verify that the event was not imported
+	let mut response = db.query("INFO FOR DB").await.unwrap();
+	let events: Value = response.take("events").unwrap();
+	assert_eq!(events.to_string(), "{  }");
+}
+
+pub async fn export_with_tests(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&db_name).await.unwrap();
+	db.query("DEFINE TEST truth { assert::equal(true, true) }").await.unwrap().check().unwrap();
+	drop(permit);
+
+	// Export, remove the test, and import
+	let file = format!("{db_name}.sql");
+	let res = async {
+		db.export(&file).await?;
+		db.query("REMOVE TEST truth").await?.check()?;
+		db.import(&file).await?;
+		Result::<(), Error>::Ok(())
+	}
+	.await;
+	remove_file(&file).await.unwrap();
+	res.unwrap();
+
+	// Verify that the test was imported
+	let mut response = db.query("RUN TEST truth").await.unwrap();
+	let passed: Option<bool> = response.take((0, "passed")).unwrap();
+	assert_eq!(passed, Some(true));
+}
+
+pub async fn export_import_streaming(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&db_name).await.unwrap();
+
+	// Insert records
+	for i in 0..10 {
+		let _: Option<ApiRecordId> = db
+			.create("user")
+			.content(Record {
+				name: format!("User {i}"),
+			})
+			.await
+			.unwrap();
+	}
+
+	// Drop the permit to release the database lock
+	drop(permit);
+
+	// Export into a writer, remove table, and import from a reader
+	let mut backup = Vec::new();
+	db.export_to(&mut backup).await.unwrap();
+	assert!(!backup.is_empty());
+	db.query("REMOVE TABLE user").await.unwrap();
+	db.import_from(backup.as_slice()).await.unwrap();
+
+	// Verify that all records exist post-import
+	for i in 0..10 {
+		let mut response =
+			db.query(format!("SELECT name FROM user WHERE name = 'User {i}'")).await.unwrap();
+		let Some(name): Option<String> = response.take("name").unwrap() else {
+			panic!("query returned no record");
+		};
+		assert_eq!(name, format!("User {i}"));
+	}
+
+	// An invalid import is rejected
+	db.import_from("CREATE user:invalid SET".as_bytes()).await.unwrap_err();
+}
+
 #[cfg(feature = "ml")]
 pub async fn ml_export_import(new_db: impl CreateDb) {
 	let (permit, db) = new_db.create_db().await;
@@ -148,6 +244,15 @@ define_include_tests!(backup => {
 	#[tokio::test]
 	export_with_config,
 
+	#[tokio::test]
+	export_without_events,
+
+	#[tokio::test]
+	export_with_tests,
+
+	#[tokio::test]
+	export_import_streaming,
+
 	#[test_log::test(tokio::test)]
 	#[cfg(feature = "ml")]
 	ml_export_import
@@ -1,5 +1,7 @@
 // Tests common to all protocols and storage engines
 
+use futures::StreamExt;
+use futures::TryStreamExt;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_json::json;
@@ -42,7 +44,13 @@ pub async fn yuse(new_db: impl CreateDb) {
 		// Local engines return this error
 		Error::Db(DbError::NsEmpty) => {}
 		// Remote engines return this error
-		Error::Api(ApiError::Query(error)) if error.contains("Specify a namespace to use") => {}
+		Error::Api(
+			ApiError::Query(error)
+			| ApiError::Remote {
+				message: error,
+				..
+			},
+		) if error.contains("Specify a namespace to use") => {}
 		error => panic!("{:?}", error),
 	}
 	db.use_ns(NS).await.unwrap();
@@ -50,7 +58,13 @@ pub async fn yuse(new_db: impl CreateDb) {
 		// Local engines return this error
 		Error::Db(DbError::DbEmpty) => {}
 		// Remote engines return this error
-		Error::Api(ApiError::Query(error)) if error.contains("Specify a database to use") => {}
+		Error::Api(
+			ApiError::Query(error)
+			| ApiError::Remote {
+				message: error,
+				..
+			},
+		) if error.contains("Specify a database to use") => {}
 		error => panic!("{:?}", error),
 	}
 	db.use_db(item.as_str()).await.unwrap();
@@ -138,6 +152,32 @@ pub async fn signin_db(new_db: impl CreateDb) {
 	.unwrap();
 }
 
+pub async fn signin_again_when_session_expires(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	let database = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&database).await.unwrap();
+	let user = Ulid::new().to_string();
+	let pass = "password123";
+	let sql = format!(
+		"DEFINE USER `{user}` ON DATABASE PASSWORD '{pass}' ROLES OWNER DURATION FOR SESSION 1s"
+	);
+	let response = db.query(sql).await.unwrap();
+	drop(permit);
+	response.check().unwrap();
+	db.signin(Database {
+		namespace: NS,
+		database: &database,
+		username: &user,
+		password: pass,
+	})
+	.await
+	.unwrap();
+	tokio::time::sleep(Duration::from_secs(2)).await;
+	// The session has expired, so the client signs in again before running the query
+	let value: Option<i64> = db.query("RETURN 1").await.unwrap().take(0).unwrap();
+	assert_eq!(value, Some(1));
+}
+
 pub async fn signin_record(new_db: impl CreateDb) {
 	let (permit, db) = new_db.create_db().await;
 	let database = Ulid::new().to_string();
@@ -212,7 +252,13 @@ pub async fn record_access_throws_error(new_db: impl CreateDb) {
 		.await
 	{
 		Err(Error::Db(surrealdb::err::Error::Thrown(e))) => assert_eq!(e, "signup_thrown_error"),
-		Err(Error::Api(surrealdb::error::Api::Query(e))) => assert!(e.contains("signup")),
+		Err(Error::Api(
+			surrealdb::error::Api::Query(e)
+			| surrealdb::error::Api::Remote {
+				message: e,
+				..
+			},
+		)) => assert!(e.contains("signup")),
 		Err(Error::Api(surrealdb::error::Api::Http(e))) => assert_eq!(
 			e,
 			"HTTP status client error (400 Bad Request) for url (http://127.0.0.1:8000/signup)"
@@ -233,7 +279,13 @@ pub async fn record_access_throws_error(new_db: impl CreateDb) {
 		.await
 	{
 		Err(Error::Db(surrealdb::err::Error::Thrown(e))) => assert_eq!(e, "signin_thrown_error"),
-		Err(Error::Api(surrealdb::error::Api::Query(e))) => assert!(e.contains("signin")),
+		Err(Error::Api(
+			surrealdb::error::Api::Query(e)
+			| surrealdb::error::Api::Remote {
+				message: e,
+				..
+			},
+		)) => assert!(e.contains("signin")),
 		Err(Error::Api(surrealdb::error::Api::Http(e))) => assert_eq!(
 			e,
 			"HTTP status client error (400 Bad Request) for url (http://127.0.0.1:8000/signin)"
@@ -274,7 +326,13 @@ pub async fn record_access_invalid_query(new_db: impl CreateDb) {
 		.await
 	{
 		Err(Error::Db(surrealdb::err::Error::AccessRecordSignupQueryFailed)) => (),
-		Err(Error::Api(surrealdb::error::Api::Query(e))) => {
+		Err(Error::Api(
+			surrealdb::error::Api::Query(e)
+			| surrealdb::error::Api::Remote {
+				message: e,
+				..
+			},
+		)) => {
 			assert_eq!(
 				e,
 				"There was a problem with the database: The record access signup query failed"
@@ -300,7 +358,13 @@ pub async fn record_access_invalid_query(new_db: impl CreateDb) {
 		.await
 	{
 		Err(Error::Db(surrealdb::err::Error::AccessRecordSigninQueryFailed)) => (),
-		Err(Error::Api(surrealdb::error::Api::Query(e))) => {
+		Err(Error::Api(
+			surrealdb::error::Api::Query(e)
+			| surrealdb::error::Api::Remote {
+				message: e,
+				..
+			},
+		)) => {
 			assert_eq!(
 				e,
 				"There was a problem with the database: The record access signin query failed"
@@ -422,6 +486,49 @@ pub async fn query_binds(new_db: impl CreateDb) {
 	assert_eq!(record.name, "John Doe");
 }
 
+pub async fn query_bind_struct(new_db: impl CreateDb) {
+	#[derive(Debug, Serialize)]
+	struct Params {
+		name: String,
+		age: i64,
+		#[serde(rename = "record")]
+		id: RecordId,
+		nickname: Option<String>,
+	}
+
+	#[derive(Debug, Deserialize)]
+	struct Output {
+		name: String,
+		age: i64,
+		record: RecordId,
+		nickname: Option<String>,
+	}
+
+	let (permit, db) = new_db.create_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	// Every field of the struct becomes a named parameter
+	let mut response = db
+		.query("RETURN { name: $name, age: $age, record: $record, nickname: $nickname }")
+		.bind(Params {
+			name: "John Doe".to_owned(),
+			age: 42,
+			id: RecordId::from(("user", "john")),
+			nickname: None,
+		})
+		.await
+		.unwrap();
+	let Some(output): Option<Output> = response.take(0).unwrap() else {
+		panic!("query returned nothing");
+	};
+	assert_eq!(output.name, "John Doe");
+	assert_eq!(output.age, 42);
+	assert_eq!(output.record, RecordId::from(("user", "john")));
+	assert_eq!(output.nickname, None);
+	// Values which don't serialize to an object can't be bound by name
+	db.query("RETURN $name").bind("John Doe").await.unwrap_err();
+}
+
 pub async fn query_with_stats(new_db: impl CreateDb) {
 	let (permit, db) = new_db.create_db().await;
 	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
@@ -438,6 +545,20 @@ pub async fn query_with_stats(new_db: impl CreateDb) {
 	let _: Vec<ApiRecordId> = result.unwrap();
 }
 
+pub async fn query_take_json(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let sql = "CREATE user:john SET name = 'John', age = 42; SELECT name, age FROM user";
+	let mut response = db.query(sql).await.unwrap();
+	let created = response.take_json(0).unwrap();
+	assert_eq!(created, json!([{ "id": "user:john", "name": "John", "age": 42 }]));
+	let names = response.take_json((1, "name")).unwrap();
+	assert_eq!(names, json!(["John"]));
+	let value: Value = db.query("RETURN user:john").await.unwrap().take(0).unwrap();
+	assert_eq!(serde_json::Value::from(value), json!("user:john"));
+}
+
 pub async fn query_chaining(new_db: impl CreateDb) {
 	let (permit, db) = new_db.create_db().await;
 	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
@@ -566,9 +687,14 @@ pub async fn create_record_with_id_in_content(new_db: impl CreateDb) {
 		surrealdb::Error::Db(DbError::IdMismatch {
 			..
 		}) => {}
-		surrealdb::Error::Api(ApiError::Query {
-			..
-		}) => {}
+		surrealdb::Error::Api(
+			ApiError::Query {
+				..
+			}
+			| ApiError::Remote {
+				..
+			},
+		) => {}
 		error => panic!("unexpected error; {error:?}"),
 	}
 
@@ -712,6 +838,32 @@ pub async fn select_record_id(new_db: impl CreateDb) {
 	);
 }
 
+pub async fn select_fields(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let sql = "
+		CREATE user:john SET name = 'John', email = 'john@example.com', pass = 'secret';
+		CREATE user:jane SET name = 'Jane', email = 'jane@example.com', pass = 'secret';
+	";
+	db.query(sql).await.unwrap().check().unwrap();
+	let users: Value = db.select(Resource::from("user")).fields(["name", "email"]).await.unwrap();
+	assert_eq!(
+		users.into_json(),
+		json!([
+			{ "name": "Jane", "email": "jane@example.com" },
+			{ "name": "John", "email": "john@example.com" },
+		])
+	);
+	let user: Value = db.select(Resource::from(("user", "john"))).omit(["pass"]).await.unwrap();
+	assert_eq!(
+		user.into_json(),
+		json!({ "id": "user:john", "name": "John", "email": "john@example.com" })
+	);
+	let user: Option<RecordName> = db.select(("user", "jane")).fields(["name"]).await.unwrap();
+	assert_eq!(user.unwrap().name, "Jane");
+}
+
 pub async fn select_record_ranges(new_db: impl CreateDb) {
 	let (permit, db) = new_db.create_db().await;
 	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
@@ -754,6 +906,42 @@ pub async fn select_record_ranges(new_db: impl CreateDb) {
 	assert_eq!(convert(users), vec!["john"]);
 }
 
+pub async fn select_paginate(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let table = "user";
+	for name in ["amos", "jane", "john", "mary", "zoey"] {
+		let _: Option<ApiRecordId> = db.create((table, name)).await.unwrap();
+	}
+	let convert = |users: Vec<ApiRecordId>| -> Vec<String> {
+		users
+			.into_iter()
+			.map(|user| {
+				let Id::String(ref x) = user.id.into_inner().id else {
+					panic!()
+				};
+				x.clone()
+			})
+			.collect()
+	};
+	let mut pages = db.select(table).paginate(2);
+	let users: Vec<ApiRecordId> = pages.next().await.unwrap().unwrap();
+	assert_eq!(convert(users), vec!["amos", "jane"]);
+	// Records created before the cursor are not returned again
+	let _: Option<ApiRecordId> = db.create((table, "adam")).await.unwrap();
+	let users: Vec<ApiRecordId> = pages.next().await.unwrap().unwrap();
+	assert_eq!(convert(users), vec!["john", "mary"]);
+	let users: Vec<ApiRecordId> = pages.next().await.unwrap().unwrap();
+	assert_eq!(convert(users), vec!["zoey"]);
+	assert!(pages.next().await.is_none());
+	// Pagination respects ranges
+	let pages: Vec<Vec<ApiRecordId>> =
+		db.select(table).range("jane"..="zoey").paginate(2).try_collect().await.unwrap();
+	let pages: Vec<Vec<String>> = pages.into_iter().map(convert).collect();
+	assert_eq!(pages, vec![vec!["jane", "john"], vec!["mary", "zoey"]]);
+}
+
 pub async fn select_records_order_by_start_limit(new_db: impl CreateDb) {
 	let (permit, db) = new_db.create_db().await;
 	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
@@ -1396,6 +1584,21 @@ pub async fn delete_record_range(new_db: impl CreateDb) {
 	);
 }
 
+pub async fn delete_where_count(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	db.query("FOR $i IN 1..=10 { CREATE user SET age = $i * 10 }").await.unwrap().check().unwrap();
+	let count = db.delete("user").where_filter("age < 40").count().await.unwrap();
+	assert_eq!(count, 3);
+	let users: Vec<ApiRecordId> = db.delete("user").where_filter("age >= 90").await.unwrap();
+	assert_eq!(users.len(), 2);
+	let count = db.delete("user").where_filter("age < 40").count().await.unwrap();
+	assert_eq!(count, 0);
+	let users: Vec<ApiRecordId> = db.select("user").await.unwrap();
+	assert_eq!(users.len(), 5);
+}
+
 pub async fn changefeed(new_db: impl CreateDb) {
 	let (permit, db) = new_db.create_db().await;
 	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
@@ -1627,6 +1830,39 @@ pub async fn version(new_db: impl CreateDb) {
 	db.version().await.unwrap();
 }
 
+pub async fn server_info(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	drop(permit);
+	let info = db.server_info().await.unwrap();
+	let version = db.version().await.unwrap();
+	assert_eq!(info.version, version);
+	assert!(!info.build.unwrap().is_empty());
+	assert!(!info.storage.unwrap().is_empty());
+	let capabilities: serde_json::Value = surrealdb::value::from_value(info.capabilities).unwrap();
+	assert!(capabilities.is_object());
+	db.health().with_latency().await.unwrap();
+}
+
+pub async fn wait_for_index(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let sql = "
+		CREATE user:1 SET email = 'one@example.com';
+		CREATE user:2 SET email = 'two@example.com';
+		DEFINE INDEX email ON user FIELDS email UNIQUE CONCURRENTLY;
+	";
+	db.query(sql).await.unwrap().check().unwrap();
+	db.wait_for_index("user", "email").await.unwrap();
+	let mut response = db.query("INFO FOR INDEX email ON user").await.unwrap();
+	let status: Option<String> = response.take("building.status").unwrap();
+	if let Some(status) = status {
+		assert_eq!(status, "ready");
+	}
+	// Waiting on an index which does not exist fails
+	db.wait_for_index("user", "missing").await.unwrap_err();
+}
+
 pub async fn set_unset(new_db: impl CreateDb) {
 	let (permit, db) = new_db.create_db().await;
 	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
@@ -1649,6 +1885,77 @@ pub async fn set_unset(new_db: impl CreateDb) {
 	assert!(name.is_none());
 }
 
+pub async fn list_params(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	assert!(db.params().await.unwrap().is_empty());
+	db.set("name", "Doe").await.unwrap();
+	db.set("age", 42).await.unwrap();
+	let params = db.params().await.unwrap();
+	assert_eq!(params.keys().collect::<Vec<_>>(), ["age", "name"]);
+	assert_eq!(params["name"].clone().into_inner(), CoreValue::from("Doe"));
+	assert_eq!(params["age"].clone().into_inner(), CoreValue::from(42));
+	db.unset("name").await.unwrap();
+	let params = db.params().await.unwrap();
+	assert_eq!(params.keys().collect::<Vec<_>>(), ["age"]);
+}
+
+pub async fn query_fragments(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	// Strings are concatenated into a single query
+	let fragments = vec!["LET $a = 1".to_owned(), "RETURN $a + 1".to_owned()];
+	let mut response = db.query(fragments).await.unwrap();
+	let Some(value): Option<i64> = response.take(1).unwrap() else {
+		panic!("value not found");
+	};
+	assert_eq!(value, 2);
+	// Statements can be reused across queries
+	let statements = surrealdb_core::syn::parse("RETURN 1; RETURN 2").unwrap().0 .0;
+	let mut response =
+		db.query(statements.as_slice()).query([statements[1].clone()]).await.unwrap();
+	assert_eq!(response.num_statements(), 3);
+	let values: Vec<Option<i64>> = (0..3).map(|i| response.take(i).unwrap()).collect();
+	assert_eq!(values, [Some(1), Some(2), Some(2)]);
+	// Raw queries can't be combined with other fragments
+	db.query(vec![Raw::from("RETURN 1")]).await.unwrap_err();
+}
+
+pub async fn query_raw_response(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let bytes = db.query("RETURN 1").raw().await.unwrap();
+	assert!(!bytes.is_empty());
+	// Failed statements are part of the response
+	let bytes = db.query("THROW 'failed'").raw().await.unwrap();
+	assert!(!bytes.is_empty());
+}
+
+pub async fn query_use_override(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	let (this, other) = (Ulid::new().to_string(), Ulid::new().to_string());
+	db.use_ns(NS).use_db(&this).await.unwrap();
+	drop(permit);
+	// The query runs in the overridden database
+	let mut response =
+		db.query("CREATE user:john; RETURN session::db()").use_ns(NS).use_db(&other).await.unwrap();
+	let Some(database): Option<String> = response.take(1).unwrap() else {
+		panic!("database not found");
+	};
+	assert_eq!(database, other);
+	// The database of the client is unchanged
+	let mut response = db.query("RETURN session::db(); SELECT * FROM user").await.unwrap();
+	let database: Option<String> = response.take(0).unwrap();
+	assert_eq!(database.as_deref(), Some(this.as_str()));
+	let users: Vec<RecordId> = response.take((1, "id")).unwrap();
+	assert!(users.is_empty());
+	// Raw responses can't be overridden
+	db.query("RETURN 1").use_db(&other).raw().await.unwrap_err();
+}
+
 pub async fn return_bool(new_db: impl CreateDb) {
 	let (permit, db) = new_db.create_db().await;
 	let mut response = db.query("RETURN true").await.unwrap();
@@ -1733,6 +2040,8 @@ define_include_tests!(basic => {
 	#[test_log::test(tokio::test)]
 	signin_db,
 	#[test_log::test(tokio::test)]
+	signin_again_when_session_expires,
+	#[test_log::test(tokio::test)]
 	signin_record,
 	#[test_log::test(tokio::test)]
 	record_access_throws_error,
@@ -1749,8 +2058,12 @@ define_include_tests!(basic => {
 	#[test_log::test(tokio::test)]
 	query_binds,
 	#[test_log::test(tokio::test)]
+	query_bind_struct,
+	#[test_log::test(tokio::test)]
 	query_with_stats,
 	#[test_log::test(tokio::test)]
+	query_take_json,
+	#[test_log::test(tokio::test)]
 	query_chaining,
 	#[test_log::test(tokio::test)]
 	mixed_results_query,
@@ -1777,8 +2090,12 @@ define_include_tests!(basic => {
 	#[test_log::test(tokio::test)]
 	select_record_id,
 	#[test_log::test(tokio::test)]
+	select_fields,
+	#[test_log::test(tokio::test)]
 	select_record_ranges,
 	#[test_log::test(tokio::test)]
+	select_paginate,
+	#[test_log::test(tokio::test)]
 	select_records_order_by_start_limit,
 	#[test_log::test(tokio::test)]
 	select_records_order_by,
@@ -1811,12 +2128,26 @@ define_include_tests!(basic => {
 	#[test_log::test(tokio::test)]
 	delete_record_range,
 	#[test_log::test(tokio::test)]
+	delete_where_count,
+	#[test_log::test(tokio::test)]
 	changefeed,
 	#[test_log::test(tokio::test)]
 	version,
 	#[test_log::test(tokio::test)]
+	server_info,
+	#[test_log::test(tokio::test)]
+	wait_for_index,
+	#[test_log::test(tokio::test)]
 	set_unset,
 	#[test_log::test(tokio::test)]
+	list_params,
+	#[test_log::test(tokio::test)]
+	query_fragments,
+	#[test_log::test(tokio::test)]
+	query_raw_response,
+	#[test_log::test(tokio::test)]
+	query_use_override,
+	#[test_log::test(tokio::test)]
 	return_bool,
 	#[test_log::test(tokio::test)]
 	run,
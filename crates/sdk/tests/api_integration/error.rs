@@ -0,0 +1,45 @@
+#![cfg(any(
+	feature = "protocol-ws",
+	feature = "kv-mem",
+	feature = "kv-rocksdb",
+	feature = "kv-tikv",
+	feature = "kv-fdb-7_3",
+	feature = "kv-fdb-7_1",
+	feature = "kv-surrealkv",
+))]
+
+// Tests for the codes and details of errors
+// Supported by the storage engines and the WS protocol
+
+use surrealdb::error::ErrorInfo;
+use ulid::Ulid;
+
+use super::CreateDb;
+use super::NS;
+
+pub async fn error_codes_and_details(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	db.query("CREATE person:tobie").await.unwrap().check().unwrap();
+	let error = db.query("CREATE person:tobie").await.unwrap().check().unwrap_err();
+	drop(permit);
+	assert_eq!(error.code(), "record_exists");
+	let info = error.info();
+	assert_eq!(info.table.as_deref(), Some("person"));
+	assert_eq!(info.record.as_deref(), Some("person:tobie"));
+	assert_eq!(info.index, None);
+	// The details survive serialization
+	let json = serde_json::to_value(&info).unwrap();
+	assert_eq!(json["code"], "record_exists");
+	assert_eq!(json["record"], "person:tobie");
+	let decoded: ErrorInfo = serde_json::from_value(json).unwrap();
+	assert_eq!(decoded, info);
+	// The error itself still serializes to its message
+	let json = serde_json::to_value(&error).unwrap();
+	assert!(json.to_string().contains("person:tobie"));
+}
+
+define_include_tests!(error => {
+	#[test_log::test(tokio::test)]
+	error_codes_and_details,
+});
@@ -40,6 +40,7 @@ macro_rules! include_tests {
 mod backup;
 mod backup_version;
 mod basic;
+mod error;
 mod live;
 mod serialisation;
 mod version;
@@ -141,6 +142,154 @@ mod ws {
 		drop(permit);
 	}
 
+	#[test_log::test]
+	fn custom_runtime_can_connect() {
+		use futures::channel::{mpsc, oneshot};
+		use futures::io::{AsyncRead, AsyncWrite};
+		use futures::StreamExt;
+		use std::io::{self, Read, Write};
+		use std::net::{Shutdown, TcpStream};
+		use std::pin::Pin;
+		use std::sync::atomic::{AtomicUsize, Ordering};
+		use std::sync::{Arc, Mutex};
+		use std::task::{Context, Poll};
+		use std::thread;
+		use std::time::Duration;
+		use surrealdb::opt::{Config, Runtime, RuntimeFuture, RuntimeIo};
+
+		// A runtime which runs every task on its own thread, without using tokio
+		#[derive(Clone, Default)]
+		struct Threads {
+			spawned: Arc<AtomicUsize>,
+			connected: Arc<AtomicUsize>,
+		}
+
+		// A blocking TCP stream, which is read from a separate thread
+		struct Blocking {
+			reader: Mutex<mpsc::UnboundedReceiver<io::Result<Vec<u8>>>>,
+			buffer: Vec<u8>,
+			writer: TcpStream,
+		}
+
+		impl AsyncRead for Blocking {
+			fn poll_read(
+				self: Pin<&mut Self>,
+				cx: &mut Context<'_>,
+				buf: &mut [u8],
+			) -> Poll<io::Result<usize>> {
+				let this = self.get_mut();
+				if this.buffer.is_empty() {
+					let reader = this.reader.get_mut().unwrap();
+					match reader.poll_next_unpin(cx) {
+						Poll::Ready(Some(Ok(bytes))) => this.buffer = bytes,
+						Poll::Ready(Some(Err(error))) => return Poll::Ready(Err(error)),
+						Poll::Ready(None) => return Poll::Ready(Ok(0)),
+						Poll::Pending => return Poll::Pending,
+					}
+				}
+				let len = buf.len().min(this.buffer.len());
+				buf[..len].copy_from_slice(&this.buffer[..len]);
+				this.buffer.drain(..len);
+				Poll::Ready(Ok(len))
+			}
+		}
+
+		impl AsyncWrite for Blocking {
+			fn poll_write(
+				self: Pin<&mut Self>,
+				_: &mut Context<'_>,
+				buf: &[u8],
+			) -> Poll<io::Result<usize>> {
+				Poll::Ready(self.get_mut().writer.write(buf))
+			}
+
+			fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+				Poll::Ready(self.get_mut().writer.flush())
+			}
+
+			fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+				Poll::Ready(self.get_mut().writer.shutdown(Shutdown::Write))
+			}
+		}
+
+		impl Runtime for Threads {
+			fn spawn(&self, future: futures::future::BoxFuture<'static, ()>) {
+				self.spawned.fetch_add(1, Ordering::Relaxed);
+				thread::spawn(move || futures::executor::block_on(future));
+			}
+
+			fn sleep(&self, duration: Duration) -> RuntimeFuture<()> {
+				let (sender, receiver) = oneshot::channel();
+				thread::spawn(move || {
+					thread::sleep(duration);
+					sender.send(()).ok();
+				});
+				Box::pin(async move {
+					receiver.await.ok();
+				})
+			}
+
+			fn connect(
+				&self,
+				host: &str,
+				port: u16,
+			) -> RuntimeFuture<io::Result<Box<dyn RuntimeIo>>> {
+				self.connected.fetch_add(1, Ordering::Relaxed);
+				let stream = TcpStream::connect((host, port));
+				Box::pin(async move {
+					let writer = stream?;
+					let mut stream = writer.try_clone()?;
+					let (sender, reader) = mpsc::unbounded();
+					thread::spawn(move || {
+						let mut buf = [0; 8192];
+						loop {
+							let bytes = match stream.read(&mut buf) {
+								Ok(0) => return,
+								Ok(len) => Ok(buf[..len].to_vec()),
+								Err(error) => Err(error),
+							};
+							if sender.unbounded_send(bytes).is_err() {
+								return;
+							}
+						}
+					});
+					Ok(Box::new(Blocking {
+						reader: Mutex::new(reader),
+						buffer: Vec::new(),
+						writer,
+					}) as Box<dyn RuntimeIo>)
+				})
+			}
+		}
+
+		futures::executor::block_on(async {
+			// The client must not depend on a tokio runtime being available
+			assert!(tokio::runtime::Handle::try_current().is_err());
+			let permit = PERMITS.acquire().await.unwrap();
+			let runtime = Threads::default();
+			let config = Config::new().runtime(runtime.clone());
+			let db = Surreal::new::<Ws>(("127.0.0.1:8000", config)).await.unwrap();
+			db.health().await.unwrap();
+			assert_eq!(runtime.connected.load(Ordering::Relaxed), 1);
+			assert_eq!(runtime.spawned.load(Ordering::Relaxed), 1);
+			drop(permit);
+		});
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn retry_policy_is_rejected() {
+		use surrealdb::error::Api as ApiError;
+		use surrealdb::opt::{Config, RetryPolicy};
+
+		let config = Config::new().retry_policy(RetryPolicy::default());
+		let res = Surreal::new::<Ws>(("127.0.0.1:8000", config)).await;
+		assert!(
+			matches!(res, Err(surrealdb::Error::Api(ApiError::RetryPolicyRemote))),
+			"{:?}",
+			res.err()
+		);
+	}
+
 	#[test_log::test(tokio::test)]
 	async fn wait_for() {
 		use surrealdb::opt::WaitFor::{Connection, Database};
@@ -185,7 +334,7 @@ mod ws {
 		drop(permit);
 	}
 
-	include_tests!(new_db => basic, serialisation, live);
+	include_tests!(new_db => basic, serialisation, live, error);
 }
 
 #[cfg(feature = "protocol-http")]
@@ -222,6 +371,65 @@ mod http {
 		drop(permit);
 	}
 
+	#[test_log::test(tokio::test)]
+	async fn http_options_can_connect() {
+		use std::time::Duration;
+		use surrealdb::opt::Config;
+
+		let permit = PERMITS.acquire().await.unwrap();
+		let config = Config::new()
+			.http_keep_alive(Duration::from_secs(30))
+			.http2_keep_alive(Duration::from_secs(10))
+			.http_max_idle_connections(1)
+			.http_tcp_nodelay(true);
+		let db = Surreal::new::<Http>(("127.0.0.1:8000", config)).await.unwrap();
+		db.signin(Root {
+			username: ROOT_USER,
+			password: ROOT_PASS,
+		})
+		.await
+		.unwrap();
+		db.health().await.unwrap();
+		db.version().await.unwrap();
+		drop(permit);
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn custom_runtime_is_rejected() {
+		use std::time::Duration;
+		use surrealdb::error::Api as ApiError;
+		use surrealdb::opt::{Config, Runtime, RuntimeFuture, RuntimeIo};
+
+		// The HTTP engine is built on reqwest, which needs tokio
+		struct Unused;
+
+		impl Runtime for Unused {
+			fn spawn(&self, _: futures::future::BoxFuture<'static, ()>) {
+				unreachable!()
+			}
+
+			fn sleep(&self, _: Duration) -> RuntimeFuture<()> {
+				unreachable!()
+			}
+
+			fn connect(
+				&self,
+				_: &str,
+				_: u16,
+			) -> RuntimeFuture<std::io::Result<Box<dyn RuntimeIo>>> {
+				unreachable!()
+			}
+		}
+
+		let config = Config::new().runtime(Unused);
+		let res = Surreal::new::<Http>(("127.0.0.1:8000", config)).await;
+		assert!(
+			matches!(res, Err(surrealdb::Error::Api(ApiError::RuntimeUnsupported))),
+			"{:?}",
+			res.err()
+		);
+	}
+
 	include_tests!(new_db => basic, serialisation, backup);
 }
 
@@ -345,7 +553,218 @@ mod mem {
 		db.query(surql).await.unwrap().check().unwrap();
 	}
 
-	include_tests!(new_db => basic, serialisation, live, backup);
+	#[cfg(feature = "bench")]
+	#[test_log::test(tokio::test)]
+	async fn bench_seed_is_deterministic() {
+		use surrealdb::bench::{Dataset, Seed};
+		use surrealdb::Value;
+
+		let seed = Seed::new(Dataset::Graph).records(50).edges(3).batch_size(20).seed(42);
+		let mut datasets = Vec::new();
+		for _ in 0..2 {
+			let db = Surreal::new::<Mem>(()).await.unwrap();
+			db.use_ns("namespace").use_db("database").await.unwrap();
+			assert_eq!(seed.run(&db).await.unwrap(), 200);
+			let mut res = db
+				.query("count(SELECT * FROM knows); SELECT * FROM person ORDER BY id; SELECT * FROM knows ORDER BY id")
+				.await
+				.unwrap();
+			let edges: Option<usize> = res.take(0).unwrap();
+			assert_eq!(edges, Some(150));
+			let people: Value = res.take(1).unwrap();
+			let edges: Value = res.take(2).unwrap();
+			datasets.push((people, edges));
+		}
+		assert_eq!(datasets[0], datasets[1]);
+	}
+
+	#[cfg(feature = "bench")]
+	#[test_log::test(tokio::test)]
+	async fn bench_load_reports_latencies() {
+		use surrealdb::bench::{Dataset, Load, Seed, Workload};
+
+		let db = Surreal::new::<Mem>(()).await.unwrap();
+		db.use_ns("namespace").use_db("database").await.unwrap();
+		Seed::new(Dataset::Graph).records(20).edges(2).run(&db).await.unwrap();
+		for workload in [Workload::Read, Workload::Write, Workload::Graph, Workload::Mixed] {
+			let report = Load::new(workload)
+				.records(20)
+				.operations(50)
+				.concurrency(4)
+				.run(&db)
+				.await
+				.unwrap();
+			assert_eq!(report.operations, 50, "{workload}");
+			assert_eq!(report.errors, 0, "{workload}");
+			assert!(report.percentile(50.0) <= report.percentile(99.0));
+			assert!(report.throughput() > 0.0);
+		}
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn record_cache_is_invalidated() {
+		use std::time::Duration;
+		use surrealdb::RecordId;
+
+		#[derive(Debug, serde::Deserialize)]
+		struct Country {
+			name: String,
+		}
+
+		let config = Config::new().capabilities(Capabilities::all()).record_cache(10, None);
+		let db = Surreal::new::<Mem>(config).await.unwrap();
+		db.use_ns("namespace").use_db("database").await.unwrap();
+		let id = RecordId::from(("country", "uk"));
+		db.query("CREATE country:uk SET name = 'United Kingdom'").await.unwrap().check().unwrap();
+		let country: Option<Country> = db.select(&id).await.unwrap();
+		assert_eq!(country.unwrap().name, "United Kingdom");
+		// Writes to the record through this client evict it immediately
+		let _: Option<Country> =
+			db.update(&id).merge(serde_json::json!({ "name": "Britain" })).await.unwrap();
+		let country: Option<Country> = db.select(&id).await.unwrap();
+		assert_eq!(country.unwrap().name, "Britain");
+		// Other writes are picked up by the live query on the table
+		db.query("UPDATE country SET name = 'UK'").await.unwrap().check().unwrap();
+		let mut name = String::new();
+		for _ in 0..50 {
+			let country: Option<Country> = db.select(&id).await.unwrap();
+			name = country.unwrap().name;
+			if name == "UK" {
+				break;
+			}
+			tokio::time::sleep(Duration::from_millis(10)).await;
+		}
+		assert_eq!(name, "UK", "the cached record was not invalidated by the live query");
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn live_query_buffer_lags() {
+		use crate::api_integration::ApiRecordId;
+		use futures::StreamExt;
+		use std::time::Duration;
+		use surrealdb::error::Api as ApiError;
+		use surrealdb::opt::LiveOverflow;
+		use surrealdb::{Action, Notification};
+
+		let config =
+			Config::new().capabilities(Capabilities::all()).live_query_buffer(2, LiveOverflow::Lag);
+		let db = Surreal::new::<Mem>(config).await.unwrap();
+		db.use_ns("namespace").use_db("database").await.unwrap();
+		let mut stream = db.select("person").live().await.unwrap();
+		for i in 0..5 {
+			db.query(format!("CREATE person:{i}")).await.unwrap().check().unwrap();
+		}
+		// Give the notifications time to be delivered before polling the stream
+		tokio::time::sleep(Duration::from_millis(200)).await;
+		// The dropped notifications are reported first
+		match stream.next().await.unwrap() {
+			Err(surrealdb::Error::Api(ApiError::LiveQueryLagged(missed))) => assert_eq!(missed, 3),
+			other => panic!("unexpected item: {other:?}"),
+		}
+		// Followed by the buffered notifications
+		for _ in 0..2 {
+			let notification: Notification<ApiRecordId> = stream.next().await.unwrap().unwrap();
+			assert_eq!(notification.action, Action::Create);
+		}
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn update_if_version() {
+		use surrealdb::error::Api as ApiError;
+		use surrealdb::RecordId;
+
+		#[derive(Debug, serde::Deserialize)]
+		struct Country {
+			name: String,
+			version: u64,
+		}
+
+		let (permit, db) = new_db().await;
+		db.use_ns("namespace").use_db("database").await.unwrap();
+		let id = RecordId::from(("country", "uk"));
+		db.query("CREATE country:uk SET name = 'United Kingdom', version = 1")
+			.await
+			.unwrap()
+			.check()
+			.unwrap();
+		let country: Option<Country> = db
+			.update(&id)
+			.if_version(1)
+			.merge(serde_json::json!({ "name": "Britain" }))
+			.await
+			.unwrap();
+		let country = country.unwrap();
+		assert_eq!(country.name, "Britain");
+		assert_eq!(country.version, 2);
+		// A stale version is rejected without changing the record
+		let error = db
+			.update(&id)
+			.if_version(1)
+			.merge(serde_json::json!({ "name": "UK" }))
+			.await
+			.map(|_: Option<Country>| ())
+			.unwrap_err();
+		match error {
+			surrealdb::Error::Api(ApiError::VersionConflict {
+				expected,
+				found,
+				..
+			}) => {
+				assert_eq!(expected, 1);
+				assert_eq!(found, Some(2));
+			}
+			error => panic!("unexpected error: {error}"),
+		}
+		let country: Option<Country> = db.select(&id).await.unwrap();
+		assert_eq!(country.unwrap().name, "Britain");
+		drop(permit);
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn traverse_graph() {
+		use surrealdb::RecordId;
+
+		#[derive(Debug, serde::Deserialize)]
+		struct Company {
+			name: String,
+		}
+
+		let (permit, db) = new_db().await;
+		db.use_ns("namespace").use_db("database").await.unwrap();
+		db.query(
+			"
+			CREATE person:tobie, person:jaime;
+			CREATE company:surrealdb SET name = 'SurrealDB';
+			RELATE person:tobie->knows->person:jaime;
+			RELATE person:jaime->works_at->company:surrealdb;
+			",
+		)
+		.await
+		.unwrap()
+		.check()
+		.unwrap();
+		let companies = db
+			.traverse(("person", "tobie"))
+			.out("knows")
+			.out("works_at")
+			.collect::<Vec<Company>>()
+			.await
+			.unwrap();
+		assert_eq!(companies.len(), 1);
+		assert_eq!(companies[0].name, "SurrealDB");
+		let people = db
+			.traverse(("company", "surrealdb"))
+			.in_("works_at")
+			.in_("knows")
+			.collect::<Vec<ApiRecordId>>()
+			.await
+			.unwrap();
+		assert_eq!(people.len(), 1);
+		assert_eq!(people[0].id, RecordId::from(("person", "tobie")));
+		drop(permit);
+	}
+
+	include_tests!(new_db => basic, serialisation, live, backup, error);
 }
 
 #[cfg(feature = "kv-rocksdb")]
@@ -396,7 +815,7 @@ mod file {
 		}
 	}
 
-	include_tests!(new_db => basic, serialisation, live, backup);
+	include_tests!(new_db => basic, serialisation, live, backup, error);
 }
 
 #[cfg(feature = "kv-rocksdb")]
@@ -447,7 +866,7 @@ mod rocksdb {
 		}
 	}
 
-	include_tests!(new_db => basic, serialisation, live, backup);
+	include_tests!(new_db => basic, serialisation, live, backup, error);
 }
 
 #[cfg(feature = "kv-tikv")]
@@ -485,7 +904,7 @@ mod tikv {
 		drop(permit);
 	}
 
-	include_tests!(new_db => basic, serialisation, live, backup);
+	include_tests!(new_db => basic, serialisation, live, backup, error);
 }
 
 #[cfg(any(feature = "kv-fdb-7_1", feature = "kv-fdb-7_3"))]
@@ -518,7 +937,7 @@ mod fdb {
 		(permit, db)
 	}
 
-	include_tests!(new_db => basic, serialisation, live, backup);
+	include_tests!(new_db => basic, serialisation, live, backup, error);
 }
 
 #[cfg(feature = "kv-surrealkv")]
@@ -571,7 +990,7 @@ mod surrealkv {
 		}
 	}
 
-	include_tests!(new_db => basic, serialisation, live, backup);
+	include_tests!(new_db => basic, serialisation, live, backup, error);
 }
 
 #[cfg(feature = "kv-surrealkv")]
@@ -623,7 +1042,7 @@ mod surrealkv_versioned {
 		}
 	}
 
-	include_tests!(new_db => basic, serialisation, version, live, backup, backup_version);
+	include_tests!(new_db => basic, serialisation, version, live, backup, backup_version, error);
 }
 
 #[cfg(feature = "protocol-http")]
@@ -212,6 +212,61 @@ pub async fn info_for_table_with_versioned_fields(new_db: impl CreateDb) {
 	));
 }
 
+pub async fn readonly_transaction_at_version(new_db: impl CreateDb) {
+	let (permit, db) = new_db.create_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+
+	// Create the initial version and record its timestamp.
+	let _ = db.query("CREATE user:john SET name = 'John v1'").await.unwrap().check().unwrap();
+	let create_ts = chrono::Utc::now();
+
+	// Change the data after the timestamp.
+	let _ = db
+		.query("UPDATE user:john SET name = 'John v2'; CREATE user:jane SET name = 'Jane'")
+		.await
+		.unwrap()
+		.check()
+		.unwrap();
+
+	// Every read within the transaction is pinned to the snapshot.
+	let mut response = db
+		.query(format!(
+			"BEGIN READONLY AT d'{}';
+			SELECT VALUE name FROM user;
+			SELECT VALUE name FROM user:john;
+			RETURN $snapshot;
+			COMMIT;
+			SELECT VALUE name FROM user:john;
+			RETURN $snapshot;",
+			create_ts.to_rfc3339()
+		))
+		.await
+		.unwrap()
+		.check()
+		.unwrap();
+	let names: Vec<String> = response.take(0).unwrap();
+	assert_eq!(names, vec!["John v1"]);
+	let names: Vec<String> = response.take(1).unwrap();
+	assert_eq!(names, vec!["John v1"]);
+	let token = response.take::<Value>(2).unwrap();
+	// Reads after the transaction are no longer pinned.
+	let names: Vec<String> = response.take(3).unwrap();
+	assert_eq!(names, vec!["John v2"]);
+	let after = response.take::<Value>(4).unwrap();
+	assert!(after.into_inner().is_none());
+
+	// The snapshot token reads the same data in any later transaction.
+	let mut response = db
+		.query(format!("BEGIN READONLY AT {token}; SELECT VALUE name FROM user; COMMIT;"))
+		.await
+		.unwrap()
+		.check()
+		.unwrap();
+	let names: Vec<String> = response.take(0).unwrap();
+	assert_eq!(names, vec!["John v1"]);
+}
+
 define_include_tests!(version => {
 	#[test_log::test(tokio::test)]
 	select_with_version,
@@ -223,4 +278,6 @@ define_include_tests!(version => {
 	info_for_db_with_versioned_tables,
 	#[test_log::test(tokio::test)]
 	info_for_table_with_versioned_fields,
+	#[test_log::test(tokio::test)]
+	readonly_transaction_at_version,
 });
@@ -10,7 +10,7 @@ use surrealdb::fflags::FFLAGS;
 use surrealdb::kvs::Datastore;
 use surrealdb::kvs::LockType::Optimistic;
 use surrealdb::kvs::TransactionType::Write;
-use surrealdb::sql::Value;
+use surrealdb::sql::{Part, Value};
 use surrealdb_core::vs::VersionStamp;
 
 mod helpers;
@@ -766,3 +766,37 @@ async fn changefeed_with_original() -> Result<(), Error> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn changefeed_with_original_removed_edge() -> Result<(), Error> {
+	if !FFLAGS.change_feed_live_queries.enabled() {
+		return Ok(());
+	}
+	let db = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let sql = "
+		DEFINE TABLE likes TYPE RELATION CHANGEFEED 1h INCLUDE ORIGINAL;
+		RELATE person:tobie->likes:one->person:jaime;
+		DELETE likes:one;
+	";
+	for res in db.execute(sql, &ses, None).await? {
+		res.result?;
+	}
+	let value: Value =
+		db.execute("SHOW CHANGES FOR TABLE likes SINCE 0", &ses, None).await?.remove(0).result?;
+	let Value::Array(array) = value else {
+		unreachable!()
+	};
+	// The deleted edge is recorded with its original value and its relation
+	let last = array.last().unwrap().pick(&[Part::from("changes")]);
+	let Value::Array(changes) = last else {
+		unreachable!()
+	};
+	let removed = changes.last().unwrap();
+	assert_eq!(removed.pick(&[Part::from("delete")]), Value::parse("{ id: likes:one }"));
+	assert_eq!(
+		removed.pick(&[Part::from("relation")]),
+		Value::parse("{ action: 'removed', id: likes:one, in: person:tobie, out: person:jaime }")
+	);
+	Ok(())
+}
@@ -160,6 +160,235 @@ async fn define_statement_event_when_logic() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn define_statement_event_async() -> Result<(), Error> {
+	let sql = "
+		DEFINE EVENT test ON user WHEN $event = 'CREATE' THEN (
+			CREATE activity SET user = $value.id, value = $after.email, action = $event, namespace = $session.ns
+		) ASYNC;
+		DEFINE EVENT fail ON user THEN { THROW 'failed' } ASYNC RETRY 1 BACKOFF 1ns;
+		CREATE user:test SET email = 'info@surrealdb.com' RETURN NONE;
+		SELECT count() FROM activity GROUP ALL;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	for _ in 0..3 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok(), "{:?}", tmp);
+	}
+	// The events have not been processed yet
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	// The first event succeeds, and the second event is retried
+	dbs.event_process().await?;
+	let sql = "SELECT user, value, action, namespace FROM activity;";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	// The session values of the triggering session are kept
+	let val = Value::parse(
+		"[{
+			action: 'CREATE',
+			namespace: 'test',
+			user: user:test,
+			value: 'info@surrealdb.com',
+		}]",
+	);
+	assert_eq!(tmp, val);
+	// The dead-letter table is stored in a reserved namespace
+	let dls = Session::owner().with_ns("__surreal").with_db("events");
+	let sql = "SELECT count() FROM dead_letter GROUP ALL;";
+	let res = &mut dbs.execute(sql, &dls, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	// The second event fails on its retry, and is written to the dead-letter table
+	dbs.event_process().await?;
+	let sql = "SELECT namespace, database, event, table, record, action, attempts, error FROM dead_letter;";
+	let res = &mut dbs.execute(sql, &dls, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[{
+			action: 'CREATE',
+			attempts: 2,
+			database: 'test',
+			error: 'An error occurred: failed',
+			event: 'fail',
+			namespace: 'test',
+			record: user:test,
+			table: 'user',
+		}]",
+	);
+	assert_eq!(tmp, val);
+	// There are no events left to process
+	dbs.event_process().await?;
+	let sql = "SELECT count() FROM activity, dead_letter GROUP ALL;";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ count: 1 }]");
+	assert_eq!(tmp, val);
+	let res = &mut dbs.execute(sql, &dls, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ count: 1 }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_statement_event_async_sleep_and_retry_after() -> Result<(), Error> {
+	let sql = "
+		RETURN sys::sleep(1ms);
+		RETURN job::retry_after(1s);
+		DEFINE EVENT wait ON user THEN {
+			sys::sleep(1ms);
+			CREATE activity SET user = $value.id;
+		} ASYNC;
+		DEFINE EVENT retry ON user THEN { job::retry_after(1ns) } ASYNC RETRY 1 BACKOFF 1h;
+		CREATE user:test RETURN NONE;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	// The functions can only be run within an asynchronous event
+	let tmp = res.remove(0).result.unwrap_err();
+	assert_eq!(tmp.to_string(), "The function 'sys::sleep' can only be run within an ASYNC event");
+	let tmp = res.remove(0).result.unwrap_err();
+	assert_eq!(
+		tmp.to_string(),
+		"The function 'job::retry_after' can only be run within an ASYNC event"
+	);
+	for _ in 0..3 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok(), "{:?}", tmp);
+	}
+	// The first event sleeps, and the second event is retried after its own delay
+	dbs.event_process().await?;
+	dbs.event_process().await?;
+	let sql = "SELECT VALUE user FROM activity;";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[user:test]");
+	assert_eq!(tmp, val);
+	let dls = Session::owner().with_ns("__surreal").with_db("events");
+	let sql = "SELECT event, attempts, error FROM dead_letter;";
+	let res = &mut dbs.execute(sql, &dls, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[{
+			attempts: 2,
+			error: 'The event asked to be retried after 1ns',
+			event: 'retry',
+		}]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_statement_event_before() -> Result<(), Error> {
+	let sql = "
+		DEFINE EVENT clean ON user WHEN BEFORE CREATE, UPDATE THEN {
+			IF $after.email = NONE { THROW 'An email is required' };
+			RETURN { email: string::lowercase($after.email), action: $event };
+		};
+		DEFINE EVENT keep ON post WHEN BEFORE DELETE THEN {
+			IF $before.locked { THROW 'The post is locked' };
+		};
+		CREATE user:test SET email = 'INFO@surrealdb.com';
+		UPDATE user:test SET email = 'Test@SurrealDB.com';
+		CREATE user:fail SET name = 'Tobie';
+		SELECT * FROM user;
+		CREATE post:one SET locked = true;
+		CREATE post:two SET locked = false;
+		DELETE post:one;
+		DELETE post:two;
+		SELECT * FROM post;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(2)?;
+	t.expect_val(
+		"[{
+			action: 'CREATE',
+			email: 'info@surrealdb.com',
+			id: user:test,
+		}]",
+	)?;
+	t.expect_val(
+		"[{
+			action: 'UPDATE',
+			email: 'test@surrealdb.com',
+			id: user:test,
+		}]",
+	)?;
+	t.expect_error("An error occurred: An email is required")?;
+	t.expect_val(
+		"[{
+			action: 'UPDATE',
+			email: 'test@surrealdb.com',
+			id: user:test,
+		}]",
+	)?;
+	t.skip_ok(2)?;
+	t.expect_error("An error occurred: The post is locked")?;
+	t.expect_val("[]")?;
+	t.expect_val(
+		"[{
+			id: post:one,
+			locked: true,
+		}]",
+	)?;
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_statement_event_before_permissions() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE post PERMISSIONS FOR select FULL, FOR create WHERE published = false;
+		DEFINE EVENT audit ON post WHEN BEFORE CREATE THEN {
+			CREATE audit SET post = $after.id;
+			RETURN { published: true };
+		};
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	for _ in 0..2 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok(), "{:?}", tmp);
+	}
+	// The event does not run for a record which is not permitted
+	let usr = Session::for_record("test", "test", "user", Value::parse("user:1"));
+	let sql = "
+		CREATE post:one SET published = true;
+		CREATE post:two SET published = false;
+	";
+	let res = &mut dbs.execute(sql, &usr, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[]"));
+	// The permissions are checked again after the event rewrites the record
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[]"));
+	let sql = "
+		SELECT VALUE post FROM audit;
+		SELECT * FROM post;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[post:two]"));
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[]"));
+	//
+	Ok(())
+}
+
 async fn define_statement_index_concurrently_building_status(
 	def_index: &str,
 	skip_def: usize,
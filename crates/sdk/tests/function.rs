@@ -173,6 +173,28 @@ async fn function_array_combine() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_array_chunk() -> Result<(), Error> {
+	let sql = r#"
+		RETURN array::chunk([0, 1, 2, 3], 2);
+		RETURN array::chunk([0, 1, 2, 3, 4], 2);
+		RETURN array::chunk([], 2);
+		RETURN [0, 1, 2, 3, 4, 5].chunk(3);
+		RETURN array::chunk([0, 1, 2], 0);
+		RETURN array::chunk([0, 1, 2], -1);
+	"#;
+	let error = "Incorrect arguments for function array::chunk(). The second argument must be an integer greater than 0";
+	Test::new(sql)
+		.await?
+		.expect_val("[[0, 1], [2, 3]]")?
+		.expect_val("[[0, 1], [2, 3], [4]]")?
+		.expect_val("[]")?
+		.expect_val("[[0, 1, 2], [3, 4, 5]]")?
+		.expect_error(error)?
+		.expect_error(error)?;
+	Ok(())
+}
+
 #[tokio::test]
 async fn function_array_clump() -> Result<(), Error> {
 	let sql = r#"
@@ -2007,6 +2029,39 @@ async fn function_geo_is_valid() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_geo_transform() -> Result<(), Error> {
+	let sql = r#"
+		RETURN geo::transform((0.0, 0.0), 'EPSG:4326');
+		RETURN geo::distance(geo::transform(geo::transform((-0.118092, 51.509865), 'EPSG:3857'), 'EPSG:4326', 'EPSG:3857'), (-0.118092, 51.509865)) < 0.01;
+		RETURN geo::distance(geo::transform(geo::transform((-0.118092, 51.509865), 'EPSG:32630'), 'EPSG:4326', 'EPSG:32630'), (-0.118092, 51.509865)) < 0.01;
+		RETURN geo::transform((0.0, 0.0), 'EPSG:2154');
+	"#;
+	let mut test = Test::new(sql).await?;
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse("(0.0, 0.0)");
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::from(true);
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::from(true);
+	assert_eq!(tmp, val);
+	//
+	match test.next()?.result {
+		Err(Error::InvalidArguments {
+			name,
+			..
+		}) => assert_eq!(&name, "geo::transform"),
+		_ => panic!("Should have fail!"),
+	}
+	//
+	Ok(())
+}
+
 // --------------------------------------------------
 // math
 // --------------------------------------------------
@@ -5003,6 +5058,26 @@ async fn function_string_words() -> Result<(), Error> {
 // time
 // --------------------------------------------------
 
+#[tokio::test]
+async fn function_time_bucket() -> Result<(), Error> {
+	let sql = r#"
+		RETURN time::bucket(d"2024-03-01T10:07:45Z", 5m);
+		RETURN time::bucket(d"2024-03-01T10:07:45Z", 90s);
+		RETURN time::bucket(d"2024-03-01T10:07:45Z", 15m, d"2024-03-01T10:10:00Z");
+		RETURN time::bucket(d"1969-12-31T23:59:30Z", 1m);
+		RETURN time::bucket(d"2024-03-01T10:07:45Z", 0ns);
+	"#;
+	let error = "Incorrect arguments for function time::bucket(). The second argument must be a duration greater than zero, and must be able to be represented as nanoseconds.";
+	Test::new(sql)
+		.await?
+		.expect_val("d'2024-03-01T10:05:00Z'")?
+		.expect_val("d'2024-03-01T10:07:30Z'")?
+		.expect_val("d'2024-03-01T09:55:00Z'")?
+		.expect_val("d'1969-12-31T23:59:00Z'")?
+		.expect_error(error)?;
+	Ok(())
+}
+
 #[tokio::test]
 async fn function_time_ceil() -> Result<(), Error> {
 	let sql = r#"
@@ -7219,6 +7294,51 @@ async fn function_custom_optional_args() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_custom_default_args() -> Result<(), Error> {
+	let sql = r#"
+		DEFINE FUNCTION fn::add($a: int, $b: option<int> = 10) { $a + $b };
+		DEFINE FUNCTION fn::greet($name: string, $greeting: string = 'Hello', $end: option<string>) {
+			$greeting + ' ' + $name + ($end ?? '')
+		};
+		DEFINE FUNCTION fn::now($time: datetime = time::now()) { $time };
+		DEFINE FUNCTION fn::pair($a: int, $b: int = $a * 2) { [$a, $b] };
+		DEFINE FUNCTION fn::this($v: any = id) { $v };
+		DEFINE FUNCTION fn::bad($a: int = 'one') { $a };
+		RETURN fn::add(1);
+		RETURN fn::add(1, 2);
+		RETURN fn::add();
+		RETURN fn::greet('Tobie');
+		RETURN fn::greet('Tobie', 'Hi', '!');
+		RETURN type::is::datetime(fn::now());
+		RETURN fn::add(1, 2, 3);
+		RETURN fn::pair(2);
+		CREATE thing:one;
+		SELECT VALUE fn::this() FROM thing:one;
+	"#;
+	Test::new(sql)
+		.await?
+		.skip_ok(5)?
+		.expect_error(
+			"The default value of the argument '$a' of the function 'fn::bad' must be of type int, but found 'one'",
+		)?
+		.expect_val("11")?
+		.expect_val("3")?
+		.expect_error(
+			"Incorrect arguments for function fn::add(). The function expects 1 to 2 arguments.",
+		)?
+		.expect_val("'Hello Tobie'")?
+		.expect_val("'Hi Tobie!'")?
+		.expect_val("true")?
+		.expect_error(
+			"Incorrect arguments for function fn::add(). The function expects 1 to 2 arguments.",
+		)?
+		.expect_val("[2, 4]")?
+		.skip_ok(1)?
+		.expect_val("[NONE]")?;
+	Ok(())
+}
+
 #[tokio::test]
 async fn function_outside_database() -> Result<(), Error> {
 	let sql = "RETURN fn::does_not_exist();";
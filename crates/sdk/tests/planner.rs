@@ -3597,3 +3597,106 @@ async fn select_count_group_all_with_or_without_index() -> Result<(), Error> {
 	}
 	Ok(())
 }
+
+/// Returns the operation of each item of an explanation
+fn explained_operations(v: Value) -> Vec<String> {
+	match v {
+		Value::Array(a) => a
+			.0
+			.into_iter()
+			.map(|i| match i {
+				Value::Object(mut o) => o.remove("operation").unwrap_or_default().as_raw_string(),
+				_ => String::new(),
+			})
+			.collect(),
+		_ => vec![],
+	}
+}
+
+#[tokio::test]
+async fn select_with_hints_change_the_plan() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX i_t_id ON TABLE i COLUMNS t;
+		DEFINE INDEX t_name_idx ON TABLE t COLUMNS name;
+		DEFINE INDEX mt_pts ON pts FIELDS point MTREE DIMENSION 2;
+		CREATE t:1 SET name = 'h';
+		CREATE i:A SET t = t:1;
+		CREATE pts:1 SET point = [1,2];
+		CREATE pts:2 SET point = [3,4];
+		SELECT * FROM i WHERE t.name = 'h' EXPLAIN;
+		SELECT * FROM i WITH NOJOIN WHERE t.name = 'h' EXPLAIN;
+		SELECT id FROM pts WHERE point <|1|> [1,2] EXPLAIN;
+		SELECT id FROM pts WITH BRUTEFORCE WHERE point <|1|> [1,2] EXPLAIN;
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(11)?;
+	t.skip_ok(7)?;
+	for expected in ["Iterate Index", "Iterate Table", "Iterate Index", "Iterate Table"] {
+		let operations = explained_operations(t.next_value()?);
+		assert_eq!(operations.first().map(String::as_str), Some(expected), "{operations:?}");
+	}
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_join_with_hints_change_the_strategy() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX author ON post FIELDS author;
+		DEFINE INDEX title ON post FIELDS title;
+		INSERT INTO user [{ id: 1, name: 'a' }, { id: 2, name: 'b' }] RETURN NONE;
+		INSERT INTO post [{ id: 1, author: user:1, title: 'x' }, { id: 2, author: user:2, title: 'y' }] RETURN NONE;
+		SELECT * FROM user JOIN post ON post.author = user.id EXPLAIN;
+		SELECT * FROM user JOIN post ON post.author = user.id WITH INDEX author EXPLAIN;
+		SELECT * FROM user JOIN post ON post.author = user.id WITH INDEX title EXPLAIN;
+		SELECT * FROM user JOIN post ON post.author = user.id WITH NOINDEX EXPLAIN;
+		SELECT * FROM user JOIN post ON post.author = user.id WITH NOJOIN EXPLAIN;
+		SELECT * FROM user JOIN post ON post.author = user.id WITH BRUTEFORCE EXPLAIN;
+		SELECT * FROM user JOIN post ON post.author != user.id EXPLAIN;
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(11)?;
+	t.skip_ok(4)?;
+	let index = "[
+		{
+			detail: {
+				index: 'author',
+				strategy: 'Index',
+				table: 'post'
+			},
+			operation: 'Join'
+		},
+		{
+			detail: {
+				type: 'Memory'
+			},
+			operation: 'Collector'
+		}
+	]";
+	let other = |strategy: &str| {
+		format!(
+			"[
+				{{
+					detail: {{
+						strategy: '{strategy}',
+						table: 'post'
+					}},
+					operation: 'Join'
+				}},
+				{{
+					detail: {{
+						type: 'Memory'
+					}},
+					operation: 'Collector'
+				}}
+			]"
+		)
+	};
+	t.expect_val(index)?;
+	t.expect_val(index)?;
+	t.expect_val(&other("Hash"))?;
+	t.expect_val(&other("Hash"))?;
+	t.expect_val(&other("Hash"))?;
+	t.expect_val(&other("NestedLoop"))?;
+	t.expect_val(&other("NestedLoop"))?;
+	Ok(())
+}
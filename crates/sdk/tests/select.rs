@@ -1405,6 +1405,44 @@ async fn select_field_value_permissions() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn select_with_named_subquery_permissions() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+
+	let sql = r#"
+		DEFINE TABLE secret PERMISSIONS FOR SELECT WHERE $allowed = true;
+		CREATE secret:1;
+
+		DEFINE ACCESS user ON DATABASE TYPE RECORD;
+		DEFINE TABLE user PERMISSIONS FULL;
+		CREATE user:1;
+	"#;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	for _ in 0..5 {
+		let _ = res.remove(0).result?;
+	}
+
+	let sql = r#"
+		WITH allowed AS (RETURN true) SELECT * FROM secret;
+		WITH allowed AS (RETURN true) SELECT * FROM secret WHERE $allowed = true;
+	"#;
+	let ses = Session::for_record("test", "test", "user", Value::parse("user:1"));
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	// The named subquery is not visible to the table permissions
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn select_order_by_rand_large() -> Result<(), Error> {
 	let dbs = new_ds().await?;
@@ -1470,3 +1508,78 @@ async fn select_from_none() -> Result<(), Error> {
 	}
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_window_functions() -> Result<(), Error> {
+	let sql = "
+		CREATE score:1 SET team = 'a', points = 10;
+		CREATE score:2 SET team = 'a', points = 20;
+		CREATE score:3 SET team = 'a', points = 20;
+		CREATE score:4 SET team = 'b', points = 5;
+		CREATE score:5 SET team = 'b', points = 15;
+		SELECT id,
+			row_number() OVER (PARTITION BY team ORDER BY points DESC) AS position,
+			rank() OVER (PARTITION BY team ORDER BY points DESC) AS ranked,
+			dense_rank() OVER (PARTITION BY team ORDER BY points DESC) AS dense
+		FROM score ORDER BY id;
+		SELECT id,
+			lag(points, 1, 0) OVER (ORDER BY id) AS previous,
+			lead(points, 1, 0) OVER (ORDER BY id) AS next,
+			math::sum(points) OVER (PARTITION BY team ORDER BY id) AS running,
+			math::sum(points) OVER (PARTITION BY team) AS total
+		FROM score ORDER BY id;
+		SELECT id, row_number() OVER (ORDER BY points) AS position FROM score ORDER BY position LIMIT 2;
+		SELECT VALUE count() OVER () FROM score;
+		SELECT id,
+			count() OVER (PARTITION BY team ORDER BY points) AS seen,
+			math::min(points) OVER (ORDER BY points DESC) AS lowest
+		FROM score ORDER BY id;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(5)?;
+	t.expect_val(
+		"[
+			{ id: score:1, position: 3, ranked: 3, dense: 2 },
+			{ id: score:2, position: 1, ranked: 1, dense: 1 },
+			{ id: score:3, position: 2, ranked: 1, dense: 1 },
+			{ id: score:4, position: 2, ranked: 2, dense: 2 },
+			{ id: score:5, position: 1, ranked: 1, dense: 1 },
+		]",
+	)?;
+	t.expect_val(
+		"[
+			{ id: score:1, previous: 0, next: 20, running: 10, total: 50 },
+			{ id: score:2, previous: 10, next: 20, running: 30, total: 50 },
+			{ id: score:3, previous: 20, next: 5, running: 50, total: 50 },
+			{ id: score:4, previous: 20, next: 15, running: 5, total: 20 },
+			{ id: score:5, previous: 5, next: 0, running: 20, total: 20 },
+		]",
+	)?;
+	t.expect_val(
+		"[
+			{ id: score:4, position: 1 },
+			{ id: score:1, position: 2 },
+		]",
+	)?;
+	t.expect_val("[5, 5, 5, 5, 5]")?;
+	// Running aggregates include every peer of the row
+	t.expect_val(
+		"[
+			{ id: score:1, seen: 1, lowest: 10 },
+			{ id: score:2, seen: 3, lowest: 20 },
+			{ id: score:3, seen: 3, lowest: 20 },
+			{ id: score:4, seen: 1, lowest: 5 },
+			{ id: score:5, seen: 2, lowest: 15 },
+		]",
+	)?;
+	// Window functions need a window, and can not be grouped
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	assert!(dbs.execute("SELECT rank() FROM score", &ses, None).await.is_err());
+	assert!(dbs
+		.execute("SELECT team, rank() OVER () FROM score GROUP BY team", &ses, None)
+		.await
+		.is_err());
+	assert!(dbs.execute("SELECT string::len(team) OVER () FROM score", &ses, None).await.is_err());
+	Ok(())
+}
@@ -1,7 +1,11 @@
 pub(crate) mod auth;
 
-use auth::CredentialsLevel;
+use crate::err::Error;
+use auth::{CredentialsBuilder, CredentialsLevel};
 use clap::Args;
+use surrealdb::engine::any::{connect, Any, IntoEndpoint};
+use surrealdb::opt::Config;
+use surrealdb::Surreal;
 
 #[derive(Args, Debug)]
 pub(crate) struct AuthArguments {
@@ -13,8 +17,11 @@ pub(crate) struct AuthArguments {
 		visible_alias = "user",
 		requires = "password"
 	)]
+	#[arg(value_parser = super::validator::secret)]
 	pub(crate) username: Option<String>,
-	#[arg(help = "Database authentication password to use when connecting")]
+	#[arg(
+		help = "Database authentication password to use when connecting, or @FILE to read it from a file"
+	)]
 	#[arg(
 		env = "SURREAL_PASS",
 		short = 'p',
@@ -22,14 +29,18 @@ pub(crate) struct AuthArguments {
 		visible_alias = "pass",
 		requires = "username"
 	)]
+	#[arg(value_parser = super::validator::secret)]
 	pub(crate) password: Option<String>,
-	#[arg(help = "Authentication token in JWT format to use when connecting")]
+	#[arg(
+		help = "Authentication token in JWT format to use when connecting, or @FILE to read it from a file"
+	)]
 	#[arg(
 		env = "SURREAL_TOKEN",
 		short = 't',
 		long = "token",
 		conflicts_with_all = ["username", "password", "auth_level"],
 	)]
+	#[arg(value_parser = super::validator::secret)]
 	pub(crate) token: Option<String>,
 	#[arg(help = "Level on which the authenticating user is defined")]
 	#[arg(env = "SURREAL_AUTH_LEVEL", long = "auth-level", default_value = "root", requires_all = ["username", "password"])]
@@ -79,3 +90,87 @@ pub struct OptionalDatabaseConnectionArguments {
 	#[arg(value_parser = super::validator::endpoint_valid)]
 	pub(crate) endpoint: Option<String>,
 }
+
+#[derive(Args, Debug)]
+pub struct VersionCheckArguments {
+	#[arg(help = "Continue even if the server runs a version which is incompatible with this one")]
+	#[arg(long)]
+	pub(crate) force: bool,
+}
+
+/// Connects to the database engine at the endpoint, authenticating with a remote server
+///
+/// If username and password are specified, and we are connecting to a remote SurrealDB server, then we need to authenticate.
+/// If we are connecting directly to a datastore (i.e. surrealkv://local.skv or tikv://...), then we don't need to authenticate because we use an embedded (local) SurrealDB instance with auth disabled.
+/// The namespace and database are only used to sign in, and are not selected.
+pub(crate) async fn connect_to(
+	endpoint: &str,
+	auth: &AuthArguments,
+	namespace: Option<&str>,
+	database: Option<&str>,
+) -> Result<Surreal<Any>, Error> {
+	connect_with_config(endpoint, auth, namespace, database, Config::new()).await
+}
+
+/// Connects to the database engine at the endpoint, like [`connect_to`], configuring local engines
+pub(crate) async fn connect_with_config(
+	endpoint: &str,
+	AuthArguments {
+		username,
+		password,
+		token,
+		auth_level,
+	}: &AuthArguments,
+	namespace: Option<&str>,
+	database: Option<&str>,
+	config: Config,
+) -> Result<Surreal<Any>, Error> {
+	let local = endpoint.to_owned().into_endpoint()?.parse_kind()?.is_local();
+	let client = match (username, password, token) {
+		(Some(username), Some(password), _) if !local => {
+			debug!("Connecting to the database engine with authentication");
+			let creds = CredentialsBuilder::default()
+				.with_username(username.as_str())
+				.with_password(password.as_str())
+				.with_namespace(namespace)
+				.with_database(database);
+
+			let client = connect(endpoint).await?;
+
+			debug!("Signing in to the database engine at '{:?}' level", auth_level);
+			match auth_level {
+				CredentialsLevel::Root => client.signin(creds.root()?).await?,
+				CredentialsLevel::Namespace => client.signin(creds.namespace()?).await?,
+				CredentialsLevel::Database => client.signin(creds.database()?).await?,
+			};
+
+			client
+		}
+		(_, _, Some(token)) if !local => {
+			debug!("Connecting to the database engine with a token");
+			let client = connect(endpoint).await?;
+			client.authenticate(token.clone()).await?;
+
+			client
+		}
+		_ => {
+			debug!("Connecting to the database engine without authentication");
+			connect((endpoint, config)).await?
+		}
+	};
+	Ok(client)
+}
+
+/// Connects to the database engine, and selects the namespace and database
+pub(crate) async fn connect_to_database(
+	endpoint: &str,
+	auth: &AuthArguments,
+	sel: &DatabaseSelectionArguments,
+) -> Result<Surreal<Any>, Error> {
+	let client =
+		connect_to(endpoint, auth, Some(sel.namespace.as_str()), Some(sel.database.as_str()))
+			.await?;
+	// Use the specified namespace / database
+	client.use_ns(&sel.namespace).use_db(&sel.database).await?;
+	Ok(client)
+}
@@ -0,0 +1,577 @@
+use crate::cli::abstraction::{
+	connect_to, AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+};
+use crate::err::Error;
+use chrono::{DateTime, Utc};
+use clap::Args;
+use futures_util::StreamExt;
+#[cfg(feature = "backup-s3")]
+use object_store::aws::AmazonS3Builder;
+use object_store::local::LocalFileSystem;
+use object_store::path::Path;
+#[cfg(feature = "backup-s3")]
+use object_store::prefix::PrefixStore;
+use object_store::{ObjectStore, WriteMultipart};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter, Write};
+use std::sync::Arc;
+use std::time;
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+use surrealdb_core::sql::statements::{DefineStatement, DefineTableStatement};
+use surrealdb_core::sql::{
+	Datetime, Duration as CoreDuration, Object, Statement, Value as CoreValue,
+};
+use surrealdb_core::syn;
+
+/// The file which lists the backups in the target
+const MANIFEST: &str = "manifest.json";
+
+/// The number of changefeed entries read with each query
+const PAGE_SIZE: u32 = 1000;
+
+/// The number of parts of a backup which are uploaded at the same time
+const MAX_UPLOADS: usize = 8;
+
+/// The comment which precedes the statements of each change set in an incremental backup
+pub(super) const VERSIONSTAMP_MARKER: &str = "-- VERSIONSTAMP ";
+
+/// How often the point in time up to which an incremental backup can be restored is recorded
+const MARK_INTERVAL: chrono::Duration = chrono::Duration::minutes(1);
+
+/// The number of marks looked up with each query
+const MARKS_PER_QUERY: usize = 100;
+
+#[derive(Args, Debug)]
+pub struct BackupCommandArguments {
+	#[arg(help = "The directory, or S3 URL (s3://bucket/prefix), to store the backups in")]
+	#[arg(index = 1)]
+	target: String,
+	#[arg(help = "Take a full backup, even if the target already contains a backup chain")]
+	#[arg(long)]
+	full: bool,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+}
+
+/// The chain of backups stored in a target
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct Manifest {
+	pub(super) namespace: String,
+	pub(super) database: String,
+	pub(super) backups: Vec<Backup>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct Backup {
+	pub(super) kind: BackupKind,
+	/// The file containing the backup, relative to the manifest
+	pub(super) file: String,
+	/// When the backup was started, in RFC 3339 format
+	pub(super) started: String,
+	/// The changefeed versionstamp which the next incremental backup starts from, or `None` if it
+	/// starts from the time this backup was started
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub(super) versionstamp: Option<u64>,
+	/// The points in time which an incremental backup can be partially restored up to
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub(super) marks: Vec<Mark>,
+}
+
+/// A point in time during an incremental backup
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct Mark {
+	/// The time, in RFC 3339 format
+	pub(super) time: String,
+	/// The changes with a lower versionstamp were all made before the time
+	pub(super) versionstamp: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum BackupKind {
+	/// A full export of the database
+	Full,
+	/// The changes made since the previous backup in the chain
+	Incremental,
+}
+
+pub async fn init(
+	BackupCommandArguments {
+		target,
+		full,
+		conn: DatabaseConnectionArguments {
+			endpoint,
+		},
+		auth,
+		sel: DatabaseSelectionArguments {
+			namespace,
+			database,
+		},
+	}: BackupCommandArguments,
+) -> Result<(), Error> {
+	let client =
+		connect_to(&endpoint, &auth, Some(namespace.as_str()), Some(database.as_str())).await?;
+
+	// Use the specified namespace / database
+	client.use_ns(&namespace).use_db(&database).await?;
+
+	// Open the backup target, and the chain of backups already stored in it
+	let store = open(&target)?;
+	let mut manifest = match read_manifest(&store).await? {
+		Some(manifest) if manifest.namespace != namespace || manifest.database != database => {
+			return Err(Error::Backup(format!(
+				"The target contains backups of {}/{}, not {namespace}/{database}",
+				manifest.namespace, manifest.database
+			)));
+		}
+		Some(manifest) => manifest,
+		None => Manifest {
+			namespace,
+			database,
+			backups: Vec::new(),
+		},
+	};
+
+	// Check which changes are recorded by the changefeed, which incremental backups are read from
+	let retention = retention(&client, &manifest.database).await?;
+
+	// The manifest is only updated once a backup has been stored, so a failed backup can be retried
+	let started = Utc::now();
+	let name = started.format("%Y%m%dT%H%M%S%.3fZ");
+	let backup = match manifest.backups.last() {
+		Some(last) if !full => {
+			let since = match retention {
+				Some(expiry) => resume(last, started, expiry)?,
+				None => {
+					return Err(Error::Backup(
+						"The database, or one of its tables, has no changefeed, so the changes since the last backup are not recorded. Define a CHANGEFEED on the database, or take a full backup with --full".to_owned(),
+					))
+				}
+			};
+			let file = format!("incremental-{name}.surql");
+			debug!("Backing up the changes since {since}");
+			let Some(versionstamp) = incremental(&client, &store, &file, since).await? else {
+				info!("There are no changes since the last backup");
+				return Ok(());
+			};
+			let marks = marks(&client, time(last)?, started, versionstamp).await?;
+			Backup {
+				kind: BackupKind::Incremental,
+				file,
+				started: started.to_rfc3339(),
+				versionstamp: Some(versionstamp),
+				marks,
+			}
+		}
+		_ => {
+			if retention.is_none() {
+				warn!("The database, or one of its tables, has no changefeed, so incremental backups can not be taken after this backup");
+			}
+			let file = format!("full-{name}.surql");
+			// The next incremental backup reads the changes made since the export was started.
+			// Changes made during the export are included in both backups, which is harmless
+			// as they are applied again in the same order.
+			debug!("Exporting the database");
+			export(&client, &store, &file).await?;
+			Backup {
+				kind: BackupKind::Full,
+				file,
+				started: started.to_rfc3339(),
+				versionstamp: None,
+				marks: Vec::new(),
+			}
+		}
+	};
+	let kind = match backup.kind {
+		BackupKind::Full => "full",
+		BackupKind::Incremental => "incremental",
+	};
+	info!("The {kind} backup was stored as '{}'", backup.file);
+	// Earlier chains are kept, so the database can still be restored as it was before this backup
+	manifest.backups.push(backup);
+	let manifest = serde_json::to_vec_pretty(&manifest)?;
+	store.put(&Path::from(MANIFEST), manifest.into()).await?;
+	// Everything OK
+	Ok(())
+}
+
+/// Opens the directory or S3 bucket which the backups are stored in
+pub(super) fn open(target: &str) -> Result<Arc<dyn ObjectStore>, Error> {
+	match target.strip_prefix("s3://") {
+		#[cfg(feature = "backup-s3")]
+		Some(location) => {
+			let (bucket, prefix) = location.split_once('/').unwrap_or((location, ""));
+			let store = AmazonS3Builder::from_env().with_bucket_name(bucket).build()?;
+			Ok(Arc::new(PrefixStore::new(store, prefix)))
+		}
+		#[cfg(not(feature = "backup-s3"))]
+		Some(_) => Err(Error::Other(
+			"Storing backups in S3 requires the `backup-s3` feature to be enabled".to_owned(),
+		)),
+		None => {
+			std::fs::create_dir_all(target)?;
+			Ok(Arc::new(LocalFileSystem::new_with_prefix(target)?))
+		}
+	}
+}
+
+/// Reads the manifest of the target, if it contains any backups
+pub(super) async fn read_manifest(store: &Arc<dyn ObjectStore>) -> Result<Option<Manifest>, Error> {
+	match store.get(&Path::from(MANIFEST)).await {
+		Ok(result) => Ok(Some(serde_json::from_slice(&result.bytes().await?)?)),
+		Err(object_store::Error::NotFound {
+			..
+		}) => Ok(None),
+		Err(error) => Err(error.into()),
+	}
+}
+
+/// Stores a full export of the database
+async fn export(
+	client: &Surreal<Any>,
+	store: &Arc<dyn ObjectStore>,
+	file: &str,
+) -> Result<(), Error> {
+	let upload = store.put_multipart(&Path::from(file)).await?;
+	let mut writer = WriteMultipart::new(upload);
+	let mut export = client.export(()).await?;
+	while let Some(bytes) = export.next().await {
+		writer.write(&bytes?);
+	}
+	writer.finish().await?;
+	Ok(())
+}
+
+/// Where an incremental backup starts reading the changefeed from
+#[derive(Clone, Copy)]
+enum Since {
+	/// The versionstamp following the changes stored by the previous backup
+	Versionstamp(u64),
+	/// The time the previous backup was started, for backups which do not read the changefeed
+	Timestamp(DateTime<Utc>),
+}
+
+impl Display for Since {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Versionstamp(v) => write!(f, "{v}"),
+			Self::Timestamp(v) => write!(f, "{}", Datetime::from(*v)),
+		}
+	}
+}
+
+/// Finds where the incremental backup following the backup starts from
+///
+/// Fails if the changefeed may already have dropped some of the changes made since the backup.
+fn resume(last: &Backup, started: DateTime<Utc>, expiry: time::Duration) -> Result<Since, Error> {
+	let taken = time(last)?;
+	let elapsed = started.signed_duration_since(taken).to_std().unwrap_or_default();
+	if elapsed >= expiry {
+		return Err(Error::Backup(format!(
+			"The changefeed only keeps changes for {}, but the last backup was taken at {}, so some changes may have been dropped. Take a full backup with --full",
+			CoreDuration(expiry),
+			last.started
+		)));
+	}
+	Ok(match last.versionstamp {
+		Some(versionstamp) => Since::Versionstamp(versionstamp),
+		None => Since::Timestamp(taken),
+	})
+}
+
+/// Returns when the backup was started
+fn time(backup: &Backup) -> Result<DateTime<Utc>, Error> {
+	DateTime::parse_from_rfc3339(&backup.started).map(|v| v.with_timezone(&Utc)).map_err(|e| {
+		Error::Backup(format!("The backup '{}' has an invalid time: {e}", backup.file))
+	})
+}
+
+/// Returns how long the changefeed keeps changes for, or `None` if some changes are not recorded
+///
+/// Changes are recorded for the whole database if it has a changefeed, or otherwise only for
+/// the tables which have one.
+async fn retention(client: &Surreal<Any>, database: &str) -> Result<Option<time::Duration>, Error> {
+	let databases = info(client, "INFO FOR NS", "databases").await?;
+	if let Some(DefineStatement::Database(db)) = definition(databases.get(database))? {
+		if let Some(changefeed) = db.changefeed {
+			return Ok(Some(changefeed.expiry));
+		}
+	}
+	let tables = info(client, "INFO FOR DB", "tables").await?;
+	// Tables defined later would not be recorded either
+	if tables.is_empty() {
+		return Ok(None);
+	}
+	let mut expiry: Option<time::Duration> = None;
+	for table in tables.values() {
+		match definition(Some(table))? {
+			Some(DefineStatement::Table(DefineTableStatement {
+				changefeed: Some(changefeed),
+				..
+			})) => {
+				expiry = Some(expiry.map_or(changefeed.expiry, |v| v.min(changefeed.expiry)));
+			}
+			_ => return Ok(None),
+		}
+	}
+	Ok(expiry)
+}
+
+/// Reads the definitions in a section of the output of an INFO statement
+async fn info(client: &Surreal<Any>, sql: &str, section: &str) -> Result<Object, Error> {
+	let info: surrealdb::Value = client.query(sql).await?.take(0)?;
+	match info.into_inner() {
+		CoreValue::Object(mut info) => match info.remove(section) {
+			Some(CoreValue::Object(section)) => Ok(section),
+			_ => Ok(Object::default()),
+		},
+		_ => Ok(Object::default()),
+	}
+}
+
+/// Parses a definition from the output of an INFO statement
+fn definition(sql: Option<&CoreValue>) -> Result<Option<DefineStatement>, Error> {
+	let Some(CoreValue::Strand(sql)) = sql else {
+		return Ok(None);
+	};
+	let query = syn::parse(sql.as_str())
+		.map_err(|e| Error::Backup(format!("Failed to parse the definition '{sql}': {e}")))?;
+	Ok(query.0 .0.into_iter().find_map(|v| match v {
+		Statement::Define(v) => Some(v),
+		_ => None,
+	}))
+}
+
+/// Stores the schema, and the changes made since the previous backup, as a SurrealQL script
+///
+/// The script is only created once a change is found, and is streamed to the target one page of
+/// changes at a time. Returns the versionstamp which the next backup starts from, or `None` if
+/// nothing has changed.
+async fn incremental(
+	client: &Surreal<Any>,
+	store: &Arc<dyn ObjectStore>,
+	file: &str,
+	since: Since,
+) -> Result<Option<u64>, Error> {
+	let mut writer: Option<WriteMultipart> = None;
+	let mut next = None;
+	loop {
+		let from = match next {
+			Some(versionstamp) => Since::Versionstamp(versionstamp),
+			None => since,
+		};
+		let Some((versionstamp, sets)) = read_changes(client, &from).await? else {
+			break;
+		};
+		next = Some(versionstamp);
+		// Each change set is marked, so the backup can be restored up to any versionstamp
+		let mut script = String::new();
+		for (versionstamp, changes) in sets {
+			let mut set = String::new();
+			for change in changes {
+				write_change(&mut set, &change);
+			}
+			if !set.is_empty() {
+				let _ = writeln!(script, "{VERSIONSTAMP_MARKER}{versionstamp}");
+				script.push_str(&set);
+			}
+		}
+		if script.is_empty() {
+			continue;
+		}
+		if writer.is_none() {
+			let upload = store.put_multipart(&Path::from(file)).await?;
+			let mut created = WriteMultipart::new(upload);
+			created.write(header(client, &since).await?.as_bytes());
+			writer = Some(created);
+		}
+		if let Some(writer) = writer.as_mut() {
+			writer.write(script.as_bytes());
+			writer.wait_for_capacity(MAX_UPLOADS).await?;
+		}
+	}
+	match (writer, next) {
+		(Some(writer), Some(next)) => {
+			writer.finish().await?;
+			Ok(Some(next))
+		}
+		_ => Ok(None),
+	}
+}
+
+/// Writes the start of an incremental backup, which redefines the current schema
+///
+/// Each definition overwrites the existing one, so the schema changes made since the previous
+/// backup are applied when the backup is restored.
+async fn header(client: &Surreal<Any>, since: &Since) -> Result<String, Error> {
+	let mut script = String::from("-- ------------------------------\n");
+	let _ = writeln!(script, "-- INCREMENTAL BACKUP SINCE {since}");
+	script.push_str("-- ------------------------------\n\nOPTION IMPORT;\n\n");
+	let mut schema = Vec::new();
+	let mut export = client.export(()).with_config().records(false).await?;
+	while let Some(bytes) = export.next().await {
+		schema.extend_from_slice(&bytes?);
+	}
+	let schema = String::from_utf8(schema)
+		.map_err(|e| Error::Backup(format!("The exported schema is not valid UTF-8: {e}")))?;
+	let query = syn::parse(&schema)
+		.map_err(|e| Error::Backup(format!("Failed to parse the exported schema: {e}")))?;
+	for statement in query.0 .0 {
+		if let Statement::Define(mut statement) = statement {
+			overwrite(&mut statement);
+			let _ = writeln!(script, "{statement};");
+		}
+	}
+	script.push('\n');
+	Ok(script)
+}
+
+/// Makes a definition replace an existing one with the same name
+fn overwrite(statement: &mut DefineStatement) {
+	match statement {
+		DefineStatement::Namespace(v) => v.overwrite = true,
+		DefineStatement::Database(v) => v.overwrite = true,
+		DefineStatement::Function(v) => v.overwrite = true,
+		DefineStatement::Analyzer(v) => v.overwrite = true,
+		DefineStatement::Param(v) => v.overwrite = true,
+		DefineStatement::Table(v) => v.overwrite = true,
+		DefineStatement::Event(v) => v.overwrite = true,
+		DefineStatement::Field(v) => v.overwrite = true,
+		DefineStatement::Index(v) => v.overwrite = true,
+		DefineStatement::User(v) => v.overwrite = true,
+		DefineStatement::Model(v) => v.overwrite = true,
+		DefineStatement::Access(v) => v.overwrite = true,
+		DefineStatement::Config(v) => v.overwrite = true,
+		DefineStatement::Api(v) => v.overwrite = true,
+		DefineStatement::Test(v) => v.overwrite = true,
+		_ => {}
+	}
+}
+
+/// Reads a page of the changefeed of the database
+///
+/// Returns the versionstamp following the last change set in the page, along with the
+/// versionstamp and changes of each change set, or `None` if there are no more changes.
+async fn read_changes(
+	client: &Surreal<Any>,
+	since: &Since,
+) -> Result<Option<(u64, Vec<(u64, Vec<CoreValue>)>)>, Error> {
+	let sql = format!("SHOW CHANGES FOR DATABASE SINCE {since} LIMIT {PAGE_SIZE}");
+	let result: surrealdb::Value =
+		client.query(sql).await?.take(0).map_err(|e| {
+			Error::Backup(format!("Failed to read the changefeed since {since}: {e}"))
+		})?;
+	let mut next = None;
+	let mut sets = Vec::new();
+	for set in change_sets(result) {
+		let CoreValue::Object(mut set) = set else {
+			continue;
+		};
+		let Some(CoreValue::Number(versionstamp)) = set.get("versionstamp") else {
+			continue;
+		};
+		let versionstamp = versionstamp.to_int() as u64;
+		next = Some(next.map_or(versionstamp + 1, |v: u64| v.max(versionstamp + 1)));
+		if let Some(CoreValue::Array(changes)) = set.remove("changes") {
+			sets.push((versionstamp, changes.0));
+		}
+	}
+	Ok(next.map(|next| (next, sets)))
+}
+
+/// The change sets returned by a SHOW CHANGES statement
+fn change_sets(result: surrealdb::Value) -> Vec<CoreValue> {
+	match result.into_inner() {
+		CoreValue::Array(sets) => sets.0,
+		_ => Vec::new(),
+	}
+}
+
+/// Records the points in time between two backups which the later one can be restored up to
+///
+/// The database records the versionstamp it had reached at regular intervals, so the changes
+/// before each mark are found by reading the changefeed from that time. Marks are never later
+/// than the versionstamp which the backup ends at.
+async fn marks(
+	client: &Surreal<Any>,
+	from: DateTime<Utc>,
+	until: DateTime<Utc>,
+	end: u64,
+) -> Result<Vec<Mark>, Error> {
+	let mut times = Vec::new();
+	let mut time = from + MARK_INTERVAL;
+	while time < until {
+		times.push(time);
+		time += MARK_INTERVAL;
+	}
+	times.push(until);
+	let mut marks = Vec::with_capacity(times.len());
+	for times in times.chunks(MARKS_PER_QUERY) {
+		let mut sql = String::new();
+		for time in times {
+			let since = Since::Timestamp(*time);
+			let _ = writeln!(sql, "SHOW CHANGES FOR DATABASE SINCE {since} LIMIT 1;");
+		}
+		let mut response = client.query(sql).await?;
+		for (index, time) in times.iter().enumerate() {
+			// The database may not have recorded a versionstamp for the time yet
+			let Ok(result) = response.take::<surrealdb::Value>(index) else {
+				continue;
+			};
+			let versionstamp = change_sets(result)
+				.first()
+				.and_then(|set| match set {
+					CoreValue::Object(set) => match set.get("versionstamp") {
+						Some(CoreValue::Number(v)) => Some(v.to_int() as u64),
+						_ => None,
+					},
+					_ => None,
+				})
+				.map_or(end, |v| v.min(end));
+			marks.push(Mark {
+				time: time.to_rfc3339(),
+				versionstamp,
+			});
+		}
+	}
+	Ok(marks)
+}
+
+/// Writes a single change as a SurrealQL statement
+fn write_change(script: &mut String, change: &CoreValue) {
+	let CoreValue::Object(change) = change else {
+		return;
+	};
+	if let Some(id) = change.get("delete").and_then(id) {
+		let _ = writeln!(script, "DELETE {id};");
+		return;
+	}
+	// Changefeeds which include the original record store a diff as the update
+	let record = match (change.get("current"), change.get("update"), change.get("create")) {
+		(Some(record), _, _) => record,
+		(None, Some(record @ CoreValue::Object(_)), _) => record,
+		(None, _, Some(record)) => record,
+		_ => return,
+	};
+	let Some(id) = id(record) else {
+		return;
+	};
+	if change.contains_key("relation") {
+		let _ = writeln!(script, "DELETE {id};\nINSERT RELATION {record};");
+	} else {
+		let _ = writeln!(script, "UPSERT {id} CONTENT {record};");
+	}
+}
+
+/// The ID of a changed record
+fn id(record: &CoreValue) -> Option<&CoreValue> {
+	match record {
+		CoreValue::Object(record) => record.get("id"),
+		_ => None,
+	}
+}
@@ -0,0 +1,145 @@
+use crate::cli::abstraction::{
+	connect_to_database, AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+};
+use crate::err::Error;
+use clap::{Args, Subcommand};
+use surrealdb::bench::{Dataset, Load, Seed, Workload};
+
+#[derive(Debug, Subcommand)]
+pub enum BenchCommand {
+	#[command(about = "Generate a synthetic dataset into an existing database")]
+	Seed(SeedCommandArguments),
+	#[command(
+		about = "Run a workload against a seeded dataset, reporting throughput and latencies"
+	)]
+	Run(RunCommandArguments),
+}
+
+#[derive(Args, Debug)]
+pub struct SeedCommandArguments {
+	#[arg(help = "The dataset to generate (documents, graph, timeseries or vectors)")]
+	#[arg(index = 1)]
+	dataset: Dataset,
+	#[arg(help = "The number of records to generate")]
+	#[arg(long, default_value_t = 10_000)]
+	records: u64,
+	#[arg(help = "The number of records to insert with each query")]
+	#[arg(long, default_value_t = 1_000)]
+	batch_size: usize,
+	#[arg(help = "The seed of the random number generator")]
+	#[arg(long, default_value_t = 0)]
+	seed: u64,
+	#[arg(help = "The number of outgoing edges of each record in the graph dataset")]
+	#[arg(long, default_value_t = 5)]
+	edges: usize,
+	#[arg(help = "The number of sensors in the timeseries dataset")]
+	#[arg(long, default_value_t = 100)]
+	sensors: u64,
+	#[arg(help = "The dimension of the vectors in the vectors dataset")]
+	#[arg(long, default_value_t = 128)]
+	dimension: usize,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+}
+
+#[derive(Args, Debug)]
+pub struct RunCommandArguments {
+	#[arg(help = "The workload to run (read, write, graph or mixed)")]
+	#[arg(index = 1)]
+	workload: Workload,
+	#[arg(help = "The total number of queries to run")]
+	#[arg(long, default_value_t = 10_000)]
+	operations: u64,
+	#[arg(help = "The number of queries which are in flight at once")]
+	#[arg(long, default_value_t = 10)]
+	concurrency: usize,
+	#[arg(help = "The number of records in the seeded documents or graph dataset")]
+	#[arg(long, default_value_t = 10_000)]
+	records: u64,
+	#[arg(help = "The seed of the random number generator")]
+	#[arg(long, default_value_t = 0)]
+	seed: u64,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+}
+
+pub async fn init(command: BenchCommand) -> Result<(), Error> {
+	match command {
+		BenchCommand::Seed(args) => seed(args).await,
+		BenchCommand::Run(args) => run(args).await,
+	}
+}
+
+async fn seed(
+	SeedCommandArguments {
+		dataset,
+		records,
+		batch_size,
+		seed,
+		edges,
+		sensors,
+		dimension,
+		conn,
+		auth,
+		sel,
+	}: SeedCommandArguments,
+) -> Result<(), Error> {
+	let client = connect_to_database(&conn.endpoint, &auth, &sel).await?;
+	// Generate the dataset into the database
+	let count = Seed::new(dataset)
+		.records(records)
+		.batch_size(batch_size)
+		.seed(seed)
+		.edges(edges)
+		.sensors(sensors)
+		.dimension(dimension)
+		.run(&client)
+		.await?;
+	info!("Generated {count} records for the {dataset} dataset with seed {seed}");
+	// All ok
+	Ok(())
+}
+
+async fn run(
+	RunCommandArguments {
+		workload,
+		operations,
+		concurrency,
+		records,
+		seed,
+		conn,
+		auth,
+		sel,
+	}: RunCommandArguments,
+) -> Result<(), Error> {
+	let client = connect_to_database(&conn.endpoint, &auth, &sel).await?;
+	// Run the workload against the database
+	let report = Load::new(workload)
+		.operations(operations)
+		.concurrency(concurrency)
+		.records(records)
+		.seed(seed)
+		.run(&client)
+		.await?;
+	info!(
+		"Ran {} {workload} queries in {:.2?} with {} errors: {:.0} queries/s, p50 {:.2?}, p95 {:.2?}, p99 {:.2?}, max {:.2?}",
+		report.operations,
+		report.elapsed,
+		report.errors,
+		report.throughput(),
+		report.percentile(50.0),
+		report.percentile(95.0),
+		report.percentile(99.0),
+		report.percentile(100.0),
+	);
+	// All ok
+	Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::cli::abstraction::auth::CredentialsLevel;
+use crate::cli::abstraction::{connect_to, AuthArguments, DatabaseConnectionArguments};
+use crate::err::Error;
+use clap::Args;
+
+#[derive(Args, Debug)]
+pub struct CompactCommandArguments {
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+}
+
+pub async fn init(
+	CompactCommandArguments {
+		conn: DatabaseConnectionArguments {
+			endpoint,
+		},
+		auth,
+	}: CompactCommandArguments,
+) -> Result<(), Error> {
+	// Only root users can compact the datastore
+	if auth.username.is_some() && !matches!(auth.auth_level, CredentialsLevel::Root) {
+		return Err(Error::Other(
+			"Compacting the datastore requires signing in as a root user".into(),
+		));
+	}
+	let client = connect_to(&endpoint, &auth, None, None).await?;
+	// Compact the datastore
+	info!("Compacting the datastore, which can take a while for large datastores");
+	let compaction = client.compact().await?;
+	println!(
+		"Compacted the datastore from {} to {}, reclaiming {}",
+		size(compaction.before),
+		size(compaction.after),
+		size(compaction.reclaimed())
+	);
+	// Everything OK
+	Ok(())
+}
+
+/// Formats a number of bytes in the largest unit it has at least one of
+pub(super) fn size(bytes: u64) -> String {
+	const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+	let mut size = bytes as f64;
+	let mut unit = "bytes";
+	for next in UNITS {
+		if size < 1024.0 {
+			break;
+		}
+		size /= 1024.0;
+		unit = next;
+	}
+	match unit {
+		"bytes" => format!("{bytes} bytes"),
+		unit => format!("{size:.1} {unit}"),
+	}
+}
@@ -0,0 +1,76 @@
+use crate::cnf::PKG_VERSION;
+use crate::err::Error;
+use semver::Version;
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+
+/// How well the version of the server matches the version of the command-line tool
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Compatibility {
+	/// The versions share a major and minor version
+	Compatible,
+	/// The versions share a major version, but exports may use features the other doesn't support
+	Minor,
+	/// The versions use different protocols and export formats
+	Incompatible,
+}
+
+fn compatibility(server: &Version, cli: &Version) -> Compatibility {
+	if server.major != cli.major {
+		Compatibility::Incompatible
+	} else if server.minor != cli.minor {
+		Compatibility::Minor
+	} else {
+		Compatibility::Compatible
+	}
+}
+
+/// Checks that the server runs a version which the command-line tool can talk to
+///
+/// Servers with a different major version are refused, unless `force` is set, as their exports
+/// and queries are silently misread. Servers with a different minor version are only warned about.
+pub(crate) async fn check(client: &Surreal<Any>, force: bool) -> Result<(), Error> {
+	let (Ok(server), Ok(cli)) = (client.version().await, Version::parse(&PKG_VERSION)) else {
+		warn!(
+			"Unable to read the version of the server, so it can not be checked for compatibility"
+		);
+		return Ok(());
+	};
+	match compatibility(&server, &cli) {
+		Compatibility::Compatible => {
+			debug!("The server runs version {server}, which is compatible with version {cli}");
+		}
+		Compatibility::Minor => {
+			warn!("The server runs version {server}, but the command-line tool is version {cli}. Exports and imports may not be compatible between them.");
+		}
+		Compatibility::Incompatible if force => {
+			warn!("The server runs version {server}, which is incompatible with version {cli} of the command-line tool. Continuing, as --force was specified.");
+		}
+		Compatibility::Incompatible => {
+			return Err(Error::Other(format!(
+				"The server runs version {server}, which is incompatible with version {cli} of the command-line tool. Run `surreal upgrade --version {server}` to install a compatible version, or use --force to continue anyway"
+			)));
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_compatibility() {
+		let version = |v: &str| Version::parse(v).unwrap();
+		assert_eq!(compatibility(&version("2.1.4"), &version("2.1.0")), Compatibility::Compatible);
+		assert_eq!(compatibility(&version("2.0.0"), &version("2.1.0")), Compatibility::Minor);
+		assert_eq!(
+			compatibility(&version("1.5.6"), &version("2.1.0")),
+			Compatibility::Incompatible
+		);
+		assert_eq!(
+			compatibility(&version("3.0.0-alpha.1"), &version("2.1.0")),
+			Compatibility::Incompatible
+		);
+	}
+}
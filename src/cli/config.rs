@@ -1,5 +1,11 @@
 use crate::net::client_ip::ClientIp;
+use clap::{ArgAction, Command};
+use std::ffi::OsString;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
 use std::sync::OnceLock;
+use std::time::Duration;
 use std::{net::SocketAddr, path::PathBuf};
 use surrealdb::options::EngineOptions;
 
@@ -7,7 +13,7 @@ pub static CF: OnceLock<Config> = OnceLock::new();
 
 #[derive(Clone, Debug)]
 pub struct Config {
-	pub bind: SocketAddr,
+	pub bind: BindAddress,
 	pub path: String,
 	pub client_ip: ClientIp,
 	pub user: Option<String>,
@@ -16,4 +22,233 @@ pub struct Config {
 	pub key: Option<PathBuf>,
 	pub engine: EngineOptions,
 	pub no_identification_headers: bool,
+	pub shutdown_timeout: Option<Duration>,
+}
+
+/// The address which the server listens for connections on
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BindAddress {
+	/// A TCP socket address, such as `127.0.0.1:8000`
+	Tcp(SocketAddr),
+	/// A Unix domain socket path, specified as `unix:///path/to/surreal.sock`
+	#[cfg(unix)]
+	Unix(PathBuf),
+}
+
+impl FromStr for BindAddress {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if let Some(path) = s.strip_prefix("unix://") {
+			#[cfg(unix)]
+			return match path {
+				"" => Err("A Unix domain socket address requires a path".to_owned()),
+				path => Ok(Self::Unix(PathBuf::from(path))),
+			};
+			#[cfg(not(unix))]
+			return Err(format!(
+				"Unix domain sockets are not supported on this platform, unable to listen on '{path}'"
+			));
+		}
+		s.parse().map(Self::Tcp).map_err(|e| format!("Invalid bind address '{s}': {e}"))
+	}
+}
+
+impl fmt::Display for BindAddress {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Tcp(addr) => write!(f, "{addr}"),
+			#[cfg(unix)]
+			Self::Unix(path) => write!(f, "unix://{}", path.display()),
+		}
+	}
+}
+
+/// The settings of a configuration file, and the ids of the arguments which they set
+///
+/// Each setting becomes the default value of its argument, so it is overridden by both the
+/// environment variable and the command-line flag of the argument.
+const SETTINGS: &[(&str, &str)] = &[
+	("path", "path"),
+	("log", "log"),
+	("server.bind", "listen_addresses"),
+	("server.client_ip", "client_ip"),
+	("server.no_banner", "no_banner"),
+	("server.no_identification_headers", "no_identification_headers"),
+	("server.shutdown_timeout", "shutdown_timeout"),
+	("server.pid_file", "pid_file"),
+	("server.web_crt", "web_crt"),
+	("server.web_key", "web_key"),
+	("auth.username", "username"),
+	("auth.password", "password"),
+	("auth.unauthenticated", "unauthenticated"),
+	("engine.strict", "strict_mode"),
+	("engine.query_timeout", "query_timeout"),
+	("engine.transaction_timeout", "transaction_timeout"),
+	("engine.temporary_directory", "temporary_directory"),
+	("engine.table_write_limits", "table_write_limits"),
+	("engine.import_file", "import_file"),
+	("engine.kvs_ca", "kvs_ca"),
+	("engine.kvs_crt", "kvs_crt"),
+	("engine.kvs_key", "kvs_key"),
+	("engine.node_membership_refresh_interval", "node_membership_refresh_interval"),
+	("engine.node_membership_check_interval", "node_membership_check_interval"),
+	("engine.node_membership_cleanup_interval", "node_membership_cleanup_interval"),
+	("engine.changefeed_gc_interval", "changefeed_gc_interval"),
+	("engine.event_process_interval", "event_process_interval"),
+	("capabilities.allow_all", "allow_all"),
+	("capabilities.allow_scripting", "allow_scripting"),
+	("capabilities.allow_guests", "allow_guests"),
+	("capabilities.allow_funcs", "allow_funcs"),
+	("capabilities.allow_experimental", "allow_experimental"),
+	("capabilities.allow_arbitrary_query", "allow_arbitrary_query"),
+	("capabilities.allow_net", "allow_net"),
+	("capabilities.allow_env", "allow_env"),
+	("capabilities.allow_rpc", "allow_rpc"),
+	("capabilities.allow_http", "allow_http"),
+	("capabilities.deny_all", "deny_all"),
+	("capabilities.deny_scripting", "deny_scripting"),
+	("capabilities.deny_guests", "deny_guests"),
+	("capabilities.deny_funcs", "deny_funcs"),
+	("capabilities.deny_experimental", "deny_experimental"),
+	("capabilities.deny_arbitrary_query", "deny_arbitrary_query"),
+	("capabilities.deny_net", "deny_net"),
+	("capabilities.deny_env", "deny_env"),
+	("capabilities.deny_rpc", "deny_rpc"),
+	("capabilities.deny_http", "deny_http"),
+];
+
+/// Finds the configuration file passed to the `start` command, before the arguments are parsed
+pub(super) fn find(args: &[OsString]) -> Option<OsString> {
+	let mut args = args.iter().skip_while(|arg| *arg != "start");
+	args.next()?;
+	while let Some(arg) = args.next() {
+		if arg == "--config" {
+			return args.next().cloned();
+		}
+		if let Some(path) = arg.to_str().and_then(|arg| arg.strip_prefix("--config=")) {
+			return Some(path.into());
+		}
+	}
+	std::env::var_os("SURREAL_CONFIG")
+}
+
+/// Reads a configuration file, setting its settings as the defaults of the command
+pub(super) fn with_file(command: Command, path: &Path) -> Result<Command, String> {
+	let file = std::fs::read_to_string(path)
+		.map_err(|e| format!("Unable to read the configuration file '{}': {e}", path.display()))?;
+	apply(command, &file)
+}
+
+fn apply(mut command: Command, file: &str) -> Result<Command, String> {
+	let table: toml::Table =
+		toml::from_str(file).map_err(|e| format!("Invalid configuration file: {e}"))?;
+	let mut settings = Vec::new();
+	flatten("", table, &mut settings)?;
+	for (key, values) in settings {
+		let Some((_, id)) = SETTINGS.iter().find(|(setting, _)| *setting == key) else {
+			return Err(format!("Unknown setting '{key}' in the configuration file"));
+		};
+		let default = |arg: clap::Arg| match arg.get_action() {
+			ArgAction::Append => arg.default_values(values.clone()),
+			_ => arg.default_value(values.join(",")),
+		};
+		// The log level is a global argument, which is defined on the top level command
+		command = match *id {
+			"log" => command.mut_arg(id, default),
+			_ => command.mut_subcommand("start", |start| start.mut_arg(id, default)),
+		};
+	}
+	Ok(command)
+}
+
+/// Collects the settings in the table, keyed by their dotted path
+fn flatten(
+	prefix: &str,
+	table: toml::Table,
+	settings: &mut Vec<(String, Vec<String>)>,
+) -> Result<(), String> {
+	for (key, value) in table {
+		let key = match prefix {
+			"" => key,
+			prefix => format!("{prefix}.{key}"),
+		};
+		let values = match value {
+			toml::Value::Table(table) => {
+				flatten(&key, table, settings)?;
+				continue;
+			}
+			toml::Value::Array(values) => {
+				values.into_iter().map(|v| scalar(&key, v)).collect::<Result<_, _>>()?
+			}
+			value => vec![scalar(&key, value)?],
+		};
+		settings.push((key, values));
+	}
+	Ok(())
+}
+
+fn scalar(key: &str, value: toml::Value) -> Result<String, String> {
+	match value {
+		toml::Value::String(v) => Ok(v),
+		toml::Value::Table(_) | toml::Value::Array(_) => {
+			Err(format!("The setting '{key}' in the configuration file can not be nested"))
+		}
+		v => Ok(v.to_string()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_bind_address() {
+		let addr: BindAddress = "127.0.0.1:8000".parse().unwrap();
+		assert_eq!(addr, BindAddress::Tcp(SocketAddr::from(([127, 0, 0, 1], 8000))));
+		assert!("localhost".parse::<BindAddress>().is_err());
+		#[cfg(unix)]
+		{
+			let addr: BindAddress = "unix:///var/run/surrealdb.sock".parse().unwrap();
+			assert_eq!(addr, BindAddress::Unix(PathBuf::from("/var/run/surrealdb.sock")));
+			assert_eq!(addr.to_string(), "unix:///var/run/surrealdb.sock");
+			assert!("unix://".parse::<BindAddress>().is_err());
+		}
+	}
+
+	#[test]
+	fn configuration_file_settings() {
+		use clap::CommandFactory;
+		// Every setting refers to an argument of the command
+		let mut command = crate::cli::Cli::command();
+		for (_, id) in SETTINGS {
+			command = match *id {
+				"log" => command.mut_arg(id, |arg| arg),
+				_ => command.mut_subcommand("start", |start| start.mut_arg(id, |arg| arg)),
+			};
+		}
+		command.debug_assert();
+		// The settings are the defaults, which the command-line flags override
+		let file = r#"
+			log = "debug"
+			[server]
+			bind = ["127.0.0.1:9000", "127.0.0.1:9001"]
+			[capabilities]
+			allow_all = true
+			deny_funcs = ["http", "crypto::md5"]
+		"#;
+		let command = apply(crate::cli::Cli::command(), file).unwrap();
+		let matches = command.clone().try_get_matches_from(["surreal", "start"]).unwrap();
+		let start = matches.subcommand_matches("start").unwrap();
+		assert_eq!(start.get_many::<BindAddress>("listen_addresses").unwrap().count(), 2);
+		assert_eq!(start.get_one::<bool>("allow_all"), Some(&true));
+		assert!(start.contains_id("deny_funcs"));
+		let matches =
+			command.try_get_matches_from(["surreal", "start", "--bind", "0.0.0.0:8000"]).unwrap();
+		let start = matches.subcommand_matches("start").unwrap();
+		assert_eq!(start.get_many::<BindAddress>("listen_addresses").unwrap().count(), 1);
+		// Unknown and nested settings are rejected
+		assert!(apply(crate::cli::Cli::command(), "bind = \"0.0.0.0:8000\"").is_err());
+		assert!(apply(crate::cli::Cli::command(), "[server]\nbind = [[\"a\"]]").is_err());
+	}
 }
@@ -0,0 +1,128 @@
+use crate::err::Error;
+use clap::Args;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+use surrealdb::sql::statements::UpsertStatement;
+use surrealdb::sql::{Statement, Value as CoreValue};
+
+/// The parameters which were renamed in SurrealDB 2.0
+const RENAMED_PARAMS: [(&str, &str); 2] = [("$scope", "$access"), ("$session.sc", "$session.ac")];
+
+#[derive(Args, Debug)]
+pub struct ConvertCommandArguments {
+	#[arg(help = "Path to the SurrealQL file exported from SurrealDB 1.x")]
+	#[arg(index = 1)]
+	#[arg(value_parser = super::validator::file_exists)]
+	input: PathBuf,
+	#[arg(help = "Path to the converted file. Use dash - to write into stdout.")]
+	#[arg(default_value = "-")]
+	#[arg(index = 2)]
+	output: String,
+}
+
+pub async fn init(
+	ConvertCommandArguments {
+		input,
+		output,
+	}: ConvertCommandArguments,
+) -> Result<(), Error> {
+	let reader = BufReader::new(File::open(&input)?);
+	let mut writer: BufWriter<Box<dyn Write>> = BufWriter::new(match output.as_str() {
+		"-" => Box::new(std::io::stdout()),
+		file => Box::new(File::create(file)?),
+	});
+	// Exports have a statement on each line, but other files may split statements over lines
+	let mut statement = String::new();
+	let mut start = 0;
+	let mut count = 0;
+	for (number, line) in reader.lines().enumerate() {
+		let line = line?;
+		if statement.is_empty() {
+			start = number + 1;
+			// Keep the comments and blank lines between statements
+			if line.trim().is_empty() || line.trim_start().starts_with("--") {
+				writeln!(writer, "{line}")?;
+				continue;
+			}
+		}
+		statement.push_str(&line);
+		statement.push('\n');
+		if !line.trim_end().ends_with(';') {
+			continue;
+		}
+		match surrealdb::syn::parse_legacy(&statement) {
+			Ok(query) => {
+				for statement in query.0 .0 {
+					writeln!(writer, "{};", convert(statement))?;
+					count += 1;
+				}
+				statement.clear();
+			}
+			// A string or block which contains a semicolon continues on the next line
+			Err(e) if e.to_string().contains("Unexpected end of file") => continue,
+			Err(e) => {
+				return Err(Error::Other(format!(
+					"Unable to convert the statement on line {start} of {}: {e}",
+					input.display()
+				)))
+			}
+		}
+	}
+	if !statement.trim().is_empty() {
+		return Err(Error::Other(format!(
+			"The statement on line {start} of {} is not terminated",
+			input.display()
+		)));
+	}
+	writer.flush()?;
+	info!("Converted {count} statements");
+	// Everything OK
+	Ok(())
+}
+
+/// Rewrites a statement of SurrealDB 1.x in the current dialect
+///
+/// The parser already reads the definitions of scopes and tokens as access methods.
+fn convert(statement: Statement) -> String {
+	match statement {
+		// Updates no longer create records which don't exist, which exports relied on
+		Statement::Update(stmt) if stmt.what.iter().all(|v| matches!(v, CoreValue::Thing(_))) => {
+			let mut upsert = UpsertStatement::default();
+			upsert.only = stmt.only;
+			upsert.what = stmt.what;
+			upsert.data = stmt.data;
+			upsert.cond = stmt.cond;
+			upsert.output = stmt.output;
+			upsert.timeout = stmt.timeout;
+			upsert.parallel = stmt.parallel;
+			Statement::Upsert(upsert).to_string()
+		}
+		// Definitions can refer to parameters which were renamed
+		Statement::Define(stmt) => {
+			let mut sql = stmt.to_string();
+			for (from, to) in RENAMED_PARAMS {
+				sql = rename(&sql, from, to);
+			}
+			sql
+		}
+		statement => statement.to_string(),
+	}
+}
+
+/// Replaces a parameter, leaving longer parameters which start with its name alone
+fn rename(sql: &str, from: &str, to: &str) -> String {
+	let mut renamed = String::with_capacity(sql.len());
+	let mut rest = sql;
+	while let Some(i) = rest.find(from) {
+		let after = &rest[i + from.len()..];
+		renamed.push_str(&rest[..i]);
+		match after.chars().next() {
+			Some(c) if c.is_alphanumeric() || c == '_' => renamed.push_str(from),
+			_ => renamed.push_str(to),
+		}
+		rest = after;
+	}
+	renamed.push_str(rest);
+	renamed
+}
@@ -0,0 +1,215 @@
+use crate::cli::abstraction::{
+	connect_to_database, AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+};
+use crate::err::Error;
+use clap::Args;
+use std::collections::BTreeMap;
+use std::path::Path;
+use surrealdb::engine::any::{connect, Any};
+use surrealdb::opt::{capabilities::Capabilities, Config};
+use surrealdb::sql::{Ident, Value as CoreValue};
+use surrealdb::Surreal;
+
+#[derive(Args, Debug)]
+pub struct DiffCommandArguments {
+	#[arg(
+		help = "The schema to compare the database with, as a remote database server url or a SurrealQL file"
+	)]
+	#[arg(index = 1)]
+	target: String,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+}
+
+/// The kinds of definitions which are compared, in the order they need to be defined in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Kind {
+	Analyzer,
+	Function,
+	Param,
+	Access,
+	Table,
+	Field,
+	Index,
+	Event,
+}
+
+impl Kind {
+	/// The keyword of the definition in DEFINE and REMOVE statements
+	fn keyword(&self) -> &'static str {
+		match self {
+			Self::Analyzer => "ANALYZER",
+			Self::Function => "FUNCTION",
+			Self::Param => "PARAM",
+			Self::Access => "ACCESS",
+			Self::Table => "TABLE",
+			Self::Field => "FIELD",
+			Self::Index => "INDEX",
+			Self::Event => "EVENT",
+		}
+	}
+}
+
+/// A definition, identified by its kind, the table it is defined on, and its name
+type Key = (Kind, String, String);
+
+pub async fn init(
+	DiffCommandArguments {
+		target,
+		conn: DatabaseConnectionArguments {
+			endpoint,
+		},
+		auth,
+		sel,
+	}: DiffCommandArguments,
+) -> Result<(), Error> {
+	let current = schema(&connect_to_database(&endpoint, &auth, &sel).await?).await?;
+	let target = if target.contains("://") {
+		schema(&connect_to_database(&target, &auth, &sel).await?).await?
+	} else {
+		schema(&load(Path::new(&target), &sel).await?).await?
+	};
+	let statements = reconcile(&current, &target);
+	if statements.is_empty() {
+		info!("The schema of the database matches the target");
+	}
+	for statement in statements {
+		println!("{statement}");
+	}
+	// Everything OK
+	Ok(())
+}
+
+/// The statements which change the current schema into the target schema, with any comments
+///
+/// Definitions are removed before new ones are added, with the definitions on tables removed
+/// before the tables themselves, and added after them.
+fn reconcile(current: &BTreeMap<Key, String>, target: &BTreeMap<Key, String>) -> Vec<String> {
+	let mut statements = Vec::new();
+	for key in current.keys().rev() {
+		// Removing a table removes everything defined on it
+		let table = &key.1;
+		let removed_table =
+			!table.is_empty() && !target.contains_key(&(Kind::Table, String::new(), table.clone()));
+		if !target.contains_key(key) && !removed_table {
+			statements.push(format!("{};", remove(key)));
+		}
+	}
+	for (key, definition) in target {
+		let statement = match current.get(key) {
+			Some(existing) if existing == definition => continue,
+			Some(_) => overwrite(key.0, definition),
+			None => definition.clone(),
+		};
+		if statement.contains("[REDACTED]") {
+			statements.push(format!(
+				"-- The key of the access method {} is redacted, and needs to be set before the next statement is run",
+				key.2
+			));
+		}
+		statements.push(format!("{statement};"));
+	}
+	statements
+}
+
+/// The statement which removes a definition
+fn remove((kind, table, name): &Key) -> String {
+	let keyword = kind.keyword();
+	match kind {
+		Kind::Function => return format!("REMOVE {keyword} fn::{name}"),
+		Kind::Param => return format!("REMOVE {keyword} ${name}"),
+		_ => (),
+	}
+	let name = Ident::from(name.as_str());
+	match kind {
+		Kind::Access => format!("REMOVE {keyword} {name} ON DATABASE"),
+		Kind::Field | Kind::Index | Kind::Event => {
+			format!("REMOVE {keyword} {name} ON {}", Ident::from(table.as_str()))
+		}
+		_ => format!("REMOVE {keyword} {name}"),
+	}
+}
+
+/// Turns a DEFINE statement into one which replaces an existing definition
+fn overwrite(kind: Kind, definition: &str) -> String {
+	let prefix = format!("DEFINE {} ", kind.keyword());
+	match definition.strip_prefix(&prefix) {
+		Some(rest) => format!("{prefix}OVERWRITE {rest}"),
+		None => definition.to_owned(),
+	}
+}
+
+/// Reads the definitions of the selected database
+async fn schema(client: &Surreal<Any>) -> Result<BTreeMap<Key, String>, Error> {
+	let mut schema = BTreeMap::new();
+	let info = info(client, "INFO FOR DB".to_owned()).await?;
+	for (kind, field) in [
+		(Kind::Analyzer, "analyzers"),
+		(Kind::Function, "functions"),
+		(Kind::Param, "params"),
+		(Kind::Access, "accesses"),
+		(Kind::Table, "tables"),
+	] {
+		definitions(&mut schema, &info, kind, field, "");
+	}
+	let tables: Vec<String> = schema
+		.keys()
+		.filter(|(kind, _, _)| *kind == Kind::Table)
+		.map(|(_, _, name)| name.clone())
+		.collect();
+	for table in tables {
+		let info = info(client, format!("INFO FOR TABLE {}", Ident::from(table.as_str()))).await?;
+		for (kind, field) in
+			[(Kind::Field, "fields"), (Kind::Index, "indexes"), (Kind::Event, "events")]
+		{
+			definitions(&mut schema, &info, kind, field, &table);
+		}
+	}
+	Ok(schema)
+}
+
+/// Adds the definitions of a kind in the output of an INFO statement to the schema
+fn definitions(
+	schema: &mut BTreeMap<Key, String>,
+	info: &CoreValue,
+	kind: Kind,
+	field: &str,
+	table: &str,
+) {
+	let CoreValue::Object(info) = info else {
+		return;
+	};
+	let Some(CoreValue::Object(definitions)) = info.get(field) else {
+		return;
+	};
+	for (name, definition) in definitions.iter() {
+		let definition = definition.clone().as_raw_string();
+		schema.insert((kind, table.to_owned(), name.clone()), definition);
+	}
+}
+
+async fn info(client: &Surreal<Any>, sql: String) -> Result<CoreValue, Error> {
+	let info: surrealdb::Value = client.query(sql).await?.take(0)?;
+	Ok(info.into_inner())
+}
+
+/// Loads a SurrealQL file into an in-memory database, so its schema can be read
+async fn load(
+	path: &Path,
+	DatabaseSelectionArguments {
+		namespace,
+		database,
+	}: &DatabaseSelectionArguments,
+) -> Result<Surreal<Any>, Error> {
+	let sql = std::fs::read_to_string(path).map_err(|e| {
+		Error::Other(format!("Unable to read the schema file {}: {e}", path.display()))
+	})?;
+	let client = connect(("mem://", Config::new().capabilities(Capabilities::all()))).await?;
+	client.use_ns(namespace).use_db(database).await?;
+	client.query(sql).await?.check()?;
+	Ok(client)
+}
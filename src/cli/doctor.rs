@@ -0,0 +1,301 @@
+use crate::cli::abstraction::{
+	connect_to, AuthArguments, LevelSelectionArguments, OptionalDatabaseConnectionArguments,
+};
+use crate::cli::index;
+use crate::cnf::PKG_VERSION;
+use crate::err::Error;
+use clap::Args;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use surrealdb::engine::any::Any;
+use surrealdb::sql::Ident;
+use surrealdb::Surreal;
+
+/// The free disk space below which a warning is given
+const MIN_DISK_SPACE: u64 = 1024 * 1024 * 1024;
+
+/// The limit of open files below which a warning is given
+#[cfg(unix)]
+const MIN_OPEN_FILES: u64 = 10240;
+
+#[derive(Args, Debug)]
+pub struct DoctorCommandArguments {
+	#[arg(help = "Database path used for storing data, to check its permissions and disk space")]
+	#[arg(long)]
+	path: Option<String>,
+	#[command(flatten)]
+	conn: OptionalDatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: LevelSelectionArguments,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Severity {
+	Ok,
+	Warning,
+	Failure,
+}
+
+impl fmt::Display for Severity {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Ok => f.write_str("[ok]"),
+			Self::Warning => f.write_str("[warning]"),
+			Self::Failure => f.write_str("[failure]"),
+		}
+	}
+}
+
+/// Prints the findings of the checks as they are made
+#[derive(Default)]
+struct Findings {
+	failures: usize,
+}
+
+impl Findings {
+	fn ok(&mut self, message: impl fmt::Display) {
+		self.report(Severity::Ok, message, None);
+	}
+
+	fn warn(&mut self, message: impl fmt::Display, hint: impl fmt::Display) {
+		self.report(Severity::Warning, message, Some(&hint));
+	}
+
+	fn fail(&mut self, message: impl fmt::Display, hint: impl fmt::Display) {
+		self.report(Severity::Failure, message, Some(&hint));
+	}
+
+	fn report(
+		&mut self,
+		severity: Severity,
+		message: impl fmt::Display,
+		hint: Option<&dyn fmt::Display>,
+	) {
+		if severity == Severity::Failure {
+			self.failures += 1;
+		}
+		println!("{severity} {message}");
+		if let Some(hint) = hint {
+			println!("  {hint}");
+		}
+	}
+}
+
+pub async fn init(
+	DoctorCommandArguments {
+		path,
+		conn: OptionalDatabaseConnectionArguments {
+			endpoint,
+		},
+		auth,
+		sel,
+	}: DoctorCommandArguments,
+) -> Result<(), Error> {
+	let mut findings = Findings::default();
+	// Check the local environment
+	if let Some(dir) = path.as_deref().and_then(data_dir) {
+		check_path(&mut findings, &dir);
+		check_disk(&mut findings, &dir);
+	}
+	#[cfg(unix)]
+	check_open_files(&mut findings);
+	// Check the server
+	if let Some(endpoint) = endpoint {
+		if let Some(client) = check_connection(&mut findings, &endpoint, auth, &sel).await {
+			check_version(&mut findings, &client).await;
+			if let (Some(ns), Some(db)) = (sel.namespace, sel.database) {
+				check_indexes(&mut findings, &client, &ns, &db).await?;
+			}
+		}
+	}
+	match findings.failures {
+		0 => Ok(()),
+		1 => Err(Error::Other("A problem was found".into())),
+		n => Err(Error::Other(format!("{n} problems were found"))),
+	}
+}
+
+/// The directory which a datastore path stores its data in, if it is stored on this machine
+fn data_dir(path: &str) -> Option<PathBuf> {
+	if path == "memory" {
+		return None;
+	}
+	let (scheme, dir) = path.split_once("://").unwrap_or(("file", path));
+	match scheme {
+		"file" | "rocksdb" | "surrealkv" | "surrealkv+versioned" => Some(PathBuf::from(dir)),
+		_ => None,
+	}
+}
+
+/// The datastore directory, or the closest parent which it will be created in
+fn existing(dir: &Path) -> Option<&Path> {
+	dir.ancestors().find(|dir| dir.exists() && !dir.as_os_str().is_empty())
+}
+
+fn check_path(findings: &mut Findings, dir: &Path) {
+	let display = dir.display();
+	let Some(existing) = existing(dir) else {
+		findings.fail(
+			format!("The datastore path '{display}' does not exist"),
+			"Create the directory, or use an absolute path",
+		);
+		return;
+	};
+	if !existing.is_dir() {
+		findings.fail(
+			format!("The datastore path '{}' is not a directory", existing.display()),
+			"Choose a directory for the datastore to store its files in",
+		);
+		return;
+	}
+	match tempfile::tempfile_in(existing) {
+		Ok(_) => findings.ok(format!("The datastore path '{display}' is writable")),
+		Err(e) => findings.fail(
+			format!("The datastore path '{}' is not writable: {e}", existing.display()),
+			"Ensure the user running SurrealDB owns the directory, or can write to it",
+		),
+	}
+}
+
+fn check_disk(findings: &mut Findings, dir: &Path) {
+	let Some(dir) = existing(dir).and_then(|dir| dir.canonicalize().ok()) else {
+		return;
+	};
+	let disks = sysinfo::Disks::new_with_refreshed_list();
+	// The disk with the most specific mount point contains the directory
+	let Some(disk) = disks
+		.list()
+		.iter()
+		.filter(|disk| dir.starts_with(disk.mount_point()))
+		.max_by_key(|disk| disk.mount_point().as_os_str().len())
+	else {
+		findings.warn(
+			format!("Unable to find the disk which '{}' is stored on", dir.display()),
+			"Check the free disk space manually",
+		);
+		return;
+	};
+	let available = disk.available_space();
+	let mount = disk.mount_point().display();
+	let size = |bytes: u64| format!("{:.1} GiB", bytes as f64 / (1024.0 * 1024.0 * 1024.0));
+	if available < MIN_DISK_SPACE || available < disk.total_space() / 10 {
+		findings.warn(
+			format!("Only {} of disk space is available on '{mount}'", size(available)),
+			"Free up disk space, or move the datastore to a larger disk, before it runs out",
+		);
+	} else {
+		findings.ok(format!("{} of disk space is available on '{mount}'", size(available)));
+	}
+}
+
+#[cfg(unix)]
+fn check_open_files(findings: &mut Findings) {
+	use nix::sys::resource::{getrlimit, Resource};
+	match getrlimit(Resource::RLIMIT_NOFILE) {
+		Ok((soft, hard)) if soft < MIN_OPEN_FILES => findings.warn(
+			format!(
+				"The limit of open files is {soft}, which datastores with many files can exceed"
+			),
+			format!(
+				"Raise the limit with `ulimit -n {}`, or with LimitNOFILE in a systemd unit",
+				hard.min(65536).max(soft)
+			),
+		),
+		Ok((soft, _)) => findings.ok(format!("The limit of open files is {soft}")),
+		Err(e) => findings.warn(
+			format!("Unable to read the limit of open files: {e}"),
+			"Check the limit with `ulimit -n`",
+		),
+	}
+}
+
+async fn check_connection(
+	findings: &mut Findings,
+	endpoint: &str,
+	auth: AuthArguments,
+	sel: &LevelSelectionArguments,
+) -> Option<Surreal<Any>> {
+	let tls = endpoint.starts_with("https://") || endpoint.starts_with("wss://");
+	match connect_to(endpoint, &auth, sel.namespace.as_deref(), sel.database.as_deref()).await {
+		Ok(client) => {
+			if tls {
+				findings.ok(format!("The TLS certificate of '{endpoint}' is valid"));
+			}
+			findings.ok(format!("Connected to '{endpoint}'"));
+			Some(client)
+		}
+		Err(e) if tls => {
+			findings.fail(
+				format!("Unable to connect to '{endpoint}': {e}"),
+				"Check that the server is running, and that its TLS certificate has not expired and is trusted by this machine",
+			);
+			None
+		}
+		Err(e) => {
+			findings.fail(
+				format!("Unable to connect to '{endpoint}': {e}"),
+				"Check that the server is running, and that the credentials are correct",
+			);
+			None
+		}
+	}
+}
+
+async fn check_version(findings: &mut Findings, client: &Surreal<Any>) {
+	let (Ok(server), Ok(cli)) = (client.version().await, semver::Version::parse(&PKG_VERSION))
+	else {
+		findings.warn(
+			"Unable to read the version of the server",
+			"Check the version with `surreal version --endpoint`",
+		);
+		return;
+	};
+	if (server.major, server.minor) != (cli.major, cli.minor) {
+		findings.warn(
+			format!("The server runs version {server}, but the command-line tool is version {cli}"),
+			format!(
+				"Run `surreal upgrade --version {server}`, so exports and imports are compatible"
+			),
+		);
+	} else {
+		findings.ok(format!(
+			"The server and the command-line tool both run version {}.{}",
+			cli.major, cli.minor
+		));
+	}
+}
+
+async fn check_indexes(
+	findings: &mut Findings,
+	client: &Surreal<Any>,
+	ns: &str,
+	db: &str,
+) -> Result<(), Error> {
+	client.use_ns(ns).use_db(db).await?;
+	let mut count = 0;
+	for table in index::tables(client).await? {
+		for name in index::indexes(client, &table).await? {
+			count += 1;
+			let building = index::building(client, &table, &name).await?;
+			let (name, table) = (Ident::from(name), Ident::from(table.as_str()));
+			match index::status_of(&building).as_str() {
+				"error" | "aborted" => findings.fail(
+					format!(
+						"The index {name} on {table} failed to build: {}",
+						index::describe(&building)
+					),
+					format!("Rebuild the index with `surreal index rebuild --wait {table} {name}`"),
+				),
+				"started" | "indexing" => findings.warn(
+					format!("The index {name} on {table} is still being built"),
+					"Queries using the index may be slow until it is built",
+				),
+				_ => (),
+			}
+		}
+	}
+	findings.ok(format!("Checked {count} indexes in {ns}/{db}"));
+	Ok(())
+}
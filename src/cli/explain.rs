@@ -0,0 +1,107 @@
+use crate::cli::abstraction::{
+	connect_to_database, AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+};
+use crate::err::Error;
+use clap::Args;
+use std::io::IsTerminal;
+use surrealdb::sql::{Explain, Object, Statement, Value as CoreValue};
+
+#[derive(Args, Debug)]
+pub struct ExplainCommandArguments {
+	#[arg(help = "The SELECT query to explain")]
+	#[arg(index = 1, num_args = 1.., required = true)]
+	query: Vec<String>,
+	#[arg(help = "Run the query, to report the number of records fetched and the execution time")]
+	#[arg(long)]
+	analyze: bool,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+}
+
+pub async fn init(
+	ExplainCommandArguments {
+		query,
+		analyze,
+		conn,
+		auth,
+		sel,
+	}: ExplainCommandArguments,
+) -> Result<(), Error> {
+	let mut query = surrealdb::syn::parse(&query.join(" "))
+		.map_err(|e| Error::Other(format!("Invalid query: {e}")))?;
+	// Explain each statement, only running it when it is analyzed
+	let mut statements = Vec::new();
+	for statement in query.0 .0.iter_mut() {
+		let Statement::Select(select) = statement else {
+			return Err(Error::Other("Only SELECT statements can be explained".into()));
+		};
+		statements.push(select.to_string());
+		select.explain = Some(Explain(analyze));
+	}
+	let client = connect_to_database(&conn.endpoint, &auth, &sel).await?;
+	let mut response = client.query(query).with_stats().await?;
+	for (index, statement) in statements.iter().enumerate() {
+		let Some((stats, result)) = response.take(index) else {
+			continue;
+		};
+		let plan: surrealdb::Value = result?;
+		match (analyze, stats.execution_time) {
+			(true, Some(time)) => println!("-- Query {} (execution time: {time:?})", index + 1),
+			_ => println!("-- Query {}", index + 1),
+		}
+		println!("{statement}");
+		let CoreValue::Array(steps) = plan.into_inner() else {
+			continue;
+		};
+		for step in steps {
+			print_step(&step);
+		}
+		println!();
+	}
+	// Everything OK
+	Ok(())
+}
+
+/// Prints a step of a query plan, with its details indented beneath it
+fn print_step(step: &CoreValue) {
+	let CoreValue::Object(step) = step else {
+		return;
+	};
+	let operation =
+		step.get("operation").cloned().map(CoreValue::as_raw_string).unwrap_or_default();
+	// Scanning every record of a table is the most common cause of slow queries
+	if operation.starts_with("Iterate Table") {
+		println!("{operation} {}", highlight("(full table scan)"));
+	} else {
+		println!("{operation}");
+	}
+	if let Some(CoreValue::Object(detail)) = step.get("detail") {
+		print_details(detail, 1);
+	}
+}
+
+fn print_details(detail: &Object, depth: usize) {
+	let indent = "  ".repeat(depth);
+	for (key, value) in detail.iter() {
+		match value {
+			CoreValue::Object(value) => {
+				println!("{indent}{key}:");
+				print_details(value, depth + 1);
+			}
+			CoreValue::Strand(value) => println!("{indent}{key}: {}", value.as_str()),
+			value => println!("{indent}{key}: {value}"),
+		}
+	}
+}
+
+/// Highlights a warning when it is printed to a terminal
+fn highlight(text: &str) -> String {
+	match std::io::stdout().is_terminal() {
+		true => format!("\x1b[1;33m{text}\x1b[0m"),
+		false => text.to_owned(),
+	}
+}
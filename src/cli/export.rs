@@ -1,11 +1,11 @@
-use crate::cli::abstraction::auth::{CredentialsBuilder, CredentialsLevel};
 use crate::cli::abstraction::{
-	AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+	connect_to, AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+	VersionCheckArguments,
 };
+use crate::cli::tabular::{self, ExportFormat};
 use crate::err::Error;
 use clap::Args;
 use futures_util::StreamExt;
-use surrealdb::engine::any::{connect, IntoEndpoint};
 use surrealdb::kvs::export::TableConfig;
 use surrealdb::method::{Export, ExportConfig};
 use surrealdb::Connection;
@@ -31,6 +31,12 @@ struct ExportConfigArguments {
 	/// Whether analyzers should be exported
 	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
 	analyzers: Option<bool>,
+	/// Whether events should be exported
+	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
+	events: Option<bool>,
+	/// Whether tests should be exported
+	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
+	tests: Option<bool>,
 	/// Whether tables should be exported, optionally providing a list of tables
 	#[arg(long, num_args = 0..=1, default_missing_value = "true", value_parser = super::validator::export_tables)]
 	tables: Option<TableConfig>,
@@ -44,7 +50,7 @@ struct ExportConfigArguments {
 
 #[derive(Args, Debug)]
 pub struct ExportCommandArguments {
-	#[arg(help = "Path to the SurrealQL file to export. Use dash - to write into stdout.")]
+	#[arg(help = "Path to the file to export. Use dash - to write into stdout.")]
 	#[arg(default_value = "-")]
 	#[arg(index = 1)]
 	file: String,
@@ -55,7 +61,15 @@ pub struct ExportCommandArguments {
 	#[command(flatten)]
 	sel: DatabaseSelectionArguments,
 	#[command(flatten)]
+	version: VersionCheckArguments,
+	#[command(flatten)]
 	config: ExportConfigArguments,
+	/// The format to export the data in
+	#[arg(long, value_enum, default_value_t = ExportFormat::Surql)]
+	format: ExportFormat,
+	/// The table to export, when exporting as CSV or Parquet
+	#[arg(long, required_if_eq_any = [("format", "csv"), ("format", "parquet")])]
+	table: Option<String>,
 }
 
 pub async fn init(
@@ -64,55 +78,35 @@ pub async fn init(
 		conn: DatabaseConnectionArguments {
 			endpoint,
 		},
-		auth: AuthArguments {
-			username,
-			password,
-			token,
-			auth_level,
-		},
+		auth,
 		sel: DatabaseSelectionArguments {
 			namespace,
 			database,
 		},
+		version: VersionCheckArguments {
+			force,
+		},
 		config,
+		format,
+		table,
 	}: ExportCommandArguments,
 ) -> Result<(), Error> {
-	// If username and password are specified, and we are connecting to a remote SurrealDB server, then we need to authenticate.
-	// If we are connecting directly to a datastore (i.e. surrealkv://local.skv or tikv://...), then we don't need to authenticate because we use an embedded (local) SurrealDB instance with auth disabled.
-	let client = if username.is_some()
-		&& password.is_some()
-		&& !endpoint.clone().into_endpoint()?.parse_kind()?.is_local()
-	{
-		debug!("Connecting to the database engine with authentication");
-		let creds = CredentialsBuilder::default()
-			.with_username(username.as_deref())
-			.with_password(password.as_deref())
-			.with_namespace(namespace.as_str())
-			.with_database(database.as_str());
-
-		let client = connect(endpoint).await?;
-
-		debug!("Signing in to the database engine at '{:?}' level", auth_level);
-		match auth_level {
-			CredentialsLevel::Root => client.signin(creds.root()?).await?,
-			CredentialsLevel::Namespace => client.signin(creds.namespace()?).await?,
-			CredentialsLevel::Database => client.signin(creds.database()?).await?,
-		};
-
-		client
-	} else if token.is_some() && !endpoint.clone().into_endpoint()?.parse_kind()?.is_local() {
-		let client = connect(endpoint).await?;
-		client.authenticate(token.unwrap()).await?;
-
-		client
-	} else {
-		debug!("Connecting to the database engine without authentication");
-		connect(endpoint).await?
-	};
+	let client =
+		connect_to(&endpoint, &auth, Some(namespace.as_str()), Some(database.as_str())).await?;
 
+	// Check that the export format of the server is compatible
+	super::compat::check(&client, force).await?;
 	// Use the specified namespace / database
 	client.use_ns(namespace).use_db(database).await?;
 
+	// Export a single table as rows
+	if let Some(table) = table.filter(|_| format != ExportFormat::Surql) {
+		debug!("Exporting the {table} table from the database");
+		let count = tabular::export(&client, &table, format, &file).await?;
+		info!("The {table} table was exported successfully with {count} rows");
+		return Ok(());
+	}
+
 	// Export the data from the database
 	debug!("Exporting data from the database");
 	if file == "-" {
@@ -145,6 +139,8 @@ fn apply_config<C: Connection, R>(
 			.params(false)
 			.functions(false)
 			.analyzers(false)
+			.events(false)
+			.tests(false)
 			.tables(false)
 			.versions(false)
 			.records(false);
@@ -170,6 +166,14 @@ fn apply_config<C: Connection, R>(
 		export = export.analyzers(value);
 	}
 
+	if let Some(value) = config.events {
+		export = export.events(value);
+	}
+
+	if let Some(value) = config.tests {
+		export = export.tests(value);
+	}
+
 	if let Some(tables) = config.tables {
 		export = export.tables(tables);
 	}
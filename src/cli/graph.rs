@@ -0,0 +1,94 @@
+use crate::cli::abstraction::{
+	connect_to, AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+};
+use crate::err::Error;
+use clap::Args;
+use serde::Deserialize;
+use std::fmt::{self, Display, Formatter};
+use tokio::io::{self, AsyncWriteExt};
+
+#[derive(Args, Debug)]
+pub struct GraphCommandArguments {
+	#[arg(help = "Path to the DOT file to write. Use dash - to write into stdout.")]
+	#[arg(default_value = "-")]
+	#[arg(index = 1)]
+	file: String,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+}
+
+#[derive(Debug, Deserialize)]
+struct Graph {
+	nodes: Vec<Node>,
+	edges: Vec<Edge>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Node {
+	id: String,
+	kind: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Edge {
+	from: String,
+	to: String,
+}
+
+impl Display for Graph {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		writeln!(f, "digraph {{")?;
+		for node in &self.nodes {
+			let shape = match node.kind.as_str() {
+				"view" => "box3d",
+				"function" => "ellipse",
+				"param" => "diamond",
+				"event" => "hexagon",
+				_ => "box",
+			};
+			writeln!(f, "\t{:?} [shape={shape}];", node.id)?;
+		}
+		for edge in &self.edges {
+			writeln!(f, "\t{:?} -> {:?};", edge.from, edge.to)?;
+		}
+		writeln!(f, "}}")
+	}
+}
+
+pub async fn init(
+	GraphCommandArguments {
+		file,
+		conn: DatabaseConnectionArguments {
+			endpoint,
+		},
+		auth,
+		sel: DatabaseSelectionArguments {
+			namespace,
+			database,
+		},
+	}: GraphCommandArguments,
+) -> Result<(), Error> {
+	let client =
+		connect_to(&endpoint, &auth, Some(namespace.as_str()), Some(database.as_str())).await?;
+
+	// Use the specified namespace / database
+	client.use_ns(namespace).use_db(database).await?;
+
+	// Fetch the dependency graph of the database
+	debug!("Fetching the dependency graph of the database");
+	let graph: Option<Graph> = client.query("INFO FOR DB GRAPH").await?.take(0)?;
+	let dot = graph.map(|graph| graph.to_string()).unwrap_or_default();
+	// Write the graph in DOT format
+	if file == "-" {
+		io::stdout().write_all(dot.as_bytes()).await?;
+	} else {
+		tokio::fs::write(file, dot).await?;
+	}
+	info!("The dependency graph was written successfully");
+	// Everything OK
+	Ok(())
+}
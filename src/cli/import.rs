@@ -1,85 +1,253 @@
-use crate::cli::abstraction::auth::{CredentialsBuilder, CredentialsLevel};
 use crate::cli::abstraction::{
-	AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+	connect_to, AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+	VersionCheckArguments,
 };
 use crate::err::Error;
+use bytes::BytesMut;
 use clap::Args;
-use surrealdb::engine::any::{connect, IntoEndpoint};
-use surrealdb::opt::{capabilities::Capabilities, Config};
+use std::io::IsTerminal;
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+use surrealdb_core::sql::Statement;
+use surrealdb_core::syn::parser::{ParserSettings, StatementStream};
+use tokio::io::AsyncReadExt;
 
 #[derive(Args, Debug)]
 pub struct ImportCommandArguments {
 	#[arg(help = "Path to the SurrealQL file to import")]
 	#[arg(index = 1)]
 	file: String,
+	#[arg(help = "The approximate size in bytes of the batches which the file is imported in")]
+	#[arg(long, default_value_t = 4 * 1024 * 1024)]
+	batch_size: usize,
+	#[arg(help = "Continue a failed import from this byte offset in the file")]
+	#[arg(long)]
+	resume_from: Option<u64>,
 	#[command(flatten)]
 	conn: DatabaseConnectionArguments,
 	#[command(flatten)]
 	auth: AuthArguments,
 	#[command(flatten)]
 	sel: DatabaseSelectionArguments,
+	#[command(flatten)]
+	version: VersionCheckArguments,
 }
 
 pub async fn init(
 	ImportCommandArguments {
 		file,
+		batch_size,
+		resume_from,
 		conn: DatabaseConnectionArguments {
 			endpoint,
 		},
-		auth: AuthArguments {
-			username,
-			password,
-			token,
-			auth_level,
-		},
+		auth,
 		sel: DatabaseSelectionArguments {
 			namespace,
 			database,
 		},
+		version: VersionCheckArguments {
+			force,
+		},
 	}: ImportCommandArguments,
 ) -> Result<(), Error> {
-	// Default datastore configuration for local engines
-	let config = Config::new().capabilities(Capabilities::all());
-	// If username and password are specified, and we are connecting to a remote SurrealDB server, then we need to authenticate.
-	// If we are connecting directly to a datastore (i.e. surrealkv://local.skv or tikv://...), then we don't need to authenticate because we use an embedded (local) SurrealDB instance with auth disabled.
-	let client = if username.is_some()
-		&& password.is_some()
-		&& !endpoint.clone().into_endpoint()?.parse_kind()?.is_local()
-	{
-		debug!("Connecting to the database engine with authentication");
-		let creds = CredentialsBuilder::default()
-			.with_username(username.as_deref())
-			.with_password(password.as_deref())
-			.with_namespace(namespace.as_str())
-			.with_database(database.as_str());
-
-		let client = connect(endpoint).await?;
-
-		debug!("Signing in to the database engine at '{:?}' level", auth_level);
-		match auth_level {
-			CredentialsLevel::Root => client.signin(creds.root()?).await?,
-			CredentialsLevel::Namespace => client.signin(creds.namespace()?).await?,
-			CredentialsLevel::Database => client.signin(creds.database()?).await?,
-		};
-
-		client
-	} else if token.is_some() && !endpoint.clone().into_endpoint()?.parse_kind()?.is_local() {
-		let client = connect(endpoint).await?;
-		client.authenticate(token.unwrap()).await?;
-
-		client
-	} else {
-		debug!("Connecting to the database engine without authentication");
-		connect((endpoint, config)).await?
-	};
+	let client =
+		connect_to(&endpoint, &auth, Some(namespace.as_str()), Some(database.as_str())).await?;
 
+	// Check that the server can import files in the format of this version
+	super::compat::check(&client, force).await?;
 	// Use the specified namespace / database
 	client.use_ns(namespace).use_db(database).await?;
 	// Import the data into the database
-	client.import(file).await.inspect_err(|_| {
-		error!("Surreal import failed, import might only be partially completed or have failed entirely.")
-	})?;
+	import(&client, &file, batch_size, resume_from.unwrap_or(0)).await?;
 	info!("The SurrealQL file was imported successfully");
 	// All ok
 	Ok(())
 }
+
+/// Imports the file in batches of statements, starting from the byte offset
+///
+/// The file is read with the SurrealQL parser, so batches are only cut at the end of a statement,
+/// outside of a transaction, and the offset of every completed batch is a point which the import
+/// can be resumed from.
+async fn import(
+	client: &Surreal<Any>,
+	file: &str,
+	batch_size: usize,
+	offset: u64,
+) -> Result<(), Error> {
+	let file = tokio::fs::File::open(file).await?;
+	let total = file.metadata().await?.len();
+	let mut reader = Statements::new(file);
+	// Options such as `OPTION IMPORT` apply to the rest of the file, so they precede every batch
+	let mut options = String::new();
+	while reader.position < offset {
+		match reader.next().await? {
+			Some((Statement::Option(_), text)) => options.push_str(&text),
+			Some(_) => (),
+			None => {
+				return Err(Error::Other(format!(
+					"The offset {offset} is past the end of the file, which is {total} bytes long"
+				)))
+			}
+		}
+	}
+	if reader.position != offset {
+		return Err(Error::Other(format!("The offset {offset} is not at the end of a statement")));
+	}
+	let mut progress = Progress::new(total);
+	let mut batch = String::new();
+	let mut transaction = false;
+	let mut completed = offset;
+	loop {
+		let next = match reader.next().await {
+			Ok(next) => next,
+			Err(e) => {
+				progress.finish();
+				error!("Surreal import failed, the import can be continued with `--resume-from {completed}` once the file is fixed");
+				return Err(e);
+			}
+		};
+		let done = next.is_none();
+		if let Some((statement, text)) = next {
+			match statement {
+				Statement::Option(_) => {
+					options.push_str(&text);
+					continue;
+				}
+				Statement::Begin(_) => transaction = true,
+				Statement::Commit(_) | Statement::Cancel(_) => transaction = false,
+				_ => (),
+			}
+			batch.push_str(&text);
+			if transaction || batch.len() < batch_size {
+				continue;
+			}
+		}
+		if !batch.trim().is_empty() {
+			let sql = format!("{options}{batch}");
+			if let Err(e) = client.import_from(sql.as_bytes()).await {
+				progress.finish();
+				error!("Surreal import failed, the import can be continued with `--resume-from {completed}`");
+				return Err(e.into());
+			}
+			batch.clear();
+			completed = reader.position;
+			progress.update(completed);
+		}
+		if done {
+			break;
+		}
+	}
+	progress.finish();
+	Ok(())
+}
+
+/// Reads the statements of a file one at a time, along with their text
+struct Statements {
+	file: tokio::fs::File,
+	stream: StatementStream,
+	/// The bytes which have been read from the file, but not parsed
+	buffer: BytesMut,
+	/// The same bytes, as the parser drops the bytes of each statement it parses
+	pending: Vec<u8>,
+	/// Whether the whole file has been read
+	complete: bool,
+	/// The byte offset in the file after the last statement
+	position: u64,
+}
+
+impl Statements {
+	/// The number of bytes read from the file at once
+	const CHUNK_SIZE: usize = 64 * 1024;
+
+	fn new(file: tokio::fs::File) -> Self {
+		// The server decides which features are allowed, so every feature is parsed here
+		let settings = ParserSettings {
+			references_enabled: true,
+			bearer_access_enabled: true,
+			define_api_enabled: true,
+			..Default::default()
+		};
+		Self {
+			file,
+			stream: StatementStream::new_with_settings(settings),
+			buffer: BytesMut::new(),
+			pending: Vec::new(),
+			complete: false,
+			position: 0,
+		}
+	}
+
+	/// Returns the next statement and its text, including any comments before it
+	async fn next(&mut self) -> Result<Option<(Statement, String)>, Error> {
+		loop {
+			let before = self.buffer.len();
+			let parsed = match self.complete {
+				true => self.stream.parse_complete(&mut self.buffer),
+				false => self.stream.parse_partial(&mut self.buffer),
+			}
+			.map_err(|e| {
+				Error::Other(format!(
+					"Failed to parse the statement at byte {}: {e}",
+					self.position
+				))
+			})?;
+			// The parser may drop whitespace and comments without returning a statement
+			let used = before - self.buffer.len();
+			let text = String::from_utf8_lossy(&self.pending[..used]).into_owned();
+			self.pending.drain(..used);
+			self.position += used as u64;
+			match parsed {
+				Some(statement) => return Ok(Some((statement, text))),
+				None if self.complete => return Ok(None),
+				None => {
+					let mut chunk = vec![0; Self::CHUNK_SIZE];
+					let read = self.file.read(&mut chunk).await?;
+					self.complete = read == 0;
+					self.buffer.extend_from_slice(&chunk[..read]);
+					self.pending.extend_from_slice(&chunk[..read]);
+				}
+			}
+		}
+	}
+}
+
+/// Displays how much of the file has been imported, when writing to a terminal
+struct Progress {
+	total: u64,
+	terminal: bool,
+}
+
+impl Progress {
+	/// The number of characters in the progress bar
+	const WIDTH: u64 = 40;
+
+	fn new(total: u64) -> Self {
+		Self {
+			total,
+			terminal: std::io::stderr().is_terminal(),
+		}
+	}
+
+	fn update(&mut self, done: u64) {
+		if !self.terminal {
+			debug!("Imported {done} of {} bytes", self.total);
+			return;
+		}
+		let filled = (done * Self::WIDTH).checked_div(self.total).unwrap_or(Self::WIDTH);
+		let percent = (done * 100).checked_div(self.total).unwrap_or(100);
+		eprint!(
+			"\r[{}{}] {percent:>3}% {done}/{} bytes",
+			"#".repeat(filled as usize),
+			" ".repeat((Self::WIDTH - filled) as usize),
+			self.total
+		);
+	}
+
+	fn finish(&mut self) {
+		if std::mem::take(&mut self.terminal) {
+			eprintln!();
+		}
+	}
+}
@@ -0,0 +1,191 @@
+use crate::cli::abstraction::{
+	connect_to_database, AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+};
+use crate::err::Error;
+use clap::{Args, Subcommand};
+use std::time::Duration;
+use surrealdb::engine::any::Any;
+use surrealdb::sql::{Ident, Object, Value as CoreValue};
+use surrealdb::Surreal;
+
+/// How often the status of an index is checked while waiting for it to be built
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Subcommand)]
+pub enum IndexCommand {
+	#[command(about = "List the indexes and their build status")]
+	Status(StatusCommandArguments),
+	#[command(about = "Rebuild an index")]
+	Rebuild(RebuildCommandArguments),
+}
+
+#[derive(Args, Debug)]
+pub struct IndexArguments {
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+}
+
+#[derive(Args, Debug)]
+pub struct StatusCommandArguments {
+	#[arg(help = "Only list the indexes of this table")]
+	#[arg(long)]
+	table: Option<String>,
+	#[command(flatten)]
+	index: IndexArguments,
+}
+
+#[derive(Args, Debug)]
+pub struct RebuildCommandArguments {
+	#[arg(help = "The table which the index is defined on")]
+	#[arg(index = 1)]
+	table: String,
+	#[arg(help = "The name of the index")]
+	#[arg(index = 2)]
+	name: String,
+	#[arg(help = "Wait until the index has been built, for indexes which are built concurrently")]
+	#[arg(long)]
+	wait: bool,
+	#[command(flatten)]
+	index: IndexArguments,
+}
+
+pub async fn init(command: IndexCommand) -> Result<(), Error> {
+	match command {
+		IndexCommand::Status(args) => status(args).await,
+		IndexCommand::Rebuild(args) => rebuild(args).await,
+	}
+}
+
+async fn status(
+	StatusCommandArguments {
+		table,
+		index,
+	}: StatusCommandArguments,
+) -> Result<(), Error> {
+	let client = connect_to_database(&index.conn.endpoint, &index.auth, &index.sel).await?;
+	let tables = match table {
+		Some(table) => vec![table],
+		None => tables(&client).await?,
+	};
+	for table in tables {
+		for index in indexes(&client, &table).await? {
+			let building = building(&client, &table, &index).await?;
+			println!("{table} {index} {}", describe(&building));
+		}
+	}
+	// Everything OK
+	Ok(())
+}
+
+async fn rebuild(
+	RebuildCommandArguments {
+		table,
+		name,
+		wait,
+		index,
+	}: RebuildCommandArguments,
+) -> Result<(), Error> {
+	let client = connect_to_database(&index.conn.endpoint, &index.auth, &index.sel).await?;
+	let sql =
+		format!("REBUILD INDEX {} ON {}", Ident::from(name.as_str()), Ident::from(table.as_str()));
+	client.query(sql).await?.check()?;
+	// Concurrently built indexes are built in the background, after the statement has finished
+	let mut last = String::new();
+	loop {
+		let building = building(&client, &table, &name).await?;
+		let description = describe(&building);
+		match status_of(&building).as_str() {
+			"ready" => break,
+			"error" | "aborted" => {
+				return Err(Error::Other(format!(
+					"The index {name} on {table} failed to build: {description}"
+				)))
+			}
+			_ if !wait => {
+				println!("The index {name} on {table} is being rebuilt in the background");
+				return Ok(());
+			}
+			_ => {
+				if description != last {
+					info!("The index {name} on {table} is {description}");
+					last = description;
+				}
+				tokio::time::sleep(POLL_INTERVAL).await;
+			}
+		}
+	}
+	println!("The index {name} on {table} was rebuilt");
+	// Everything OK
+	Ok(())
+}
+
+/// The names of the tables in the selected database
+pub(super) async fn tables(client: &Surreal<Any>) -> Result<Vec<String>, Error> {
+	keys(client, "INFO FOR DB".to_owned(), "tables").await
+}
+
+/// The names of the indexes defined on a table
+pub(super) async fn indexes(client: &Surreal<Any>, table: &str) -> Result<Vec<String>, Error> {
+	keys(client, format!("INFO FOR TABLE {}", Ident::from(table)), "indexes").await
+}
+
+/// The build status of an index, as reported by `INFO FOR INDEX`
+///
+/// Indexes which are not being built, or which are only built by the statement defining them,
+/// are reported as `ready`.
+pub(super) async fn building(
+	client: &Surreal<Any>,
+	table: &str,
+	index: &str,
+) -> Result<Object, Error> {
+	let sql = format!("INFO FOR INDEX {} ON {}", Ident::from(index), Ident::from(table));
+	let info: surrealdb::Value = client.query(sql).await?.take(0)?;
+	match info.into_inner() {
+		CoreValue::Object(mut info) => match info.remove("building") {
+			Some(CoreValue::Object(building)) => Ok(building),
+			_ => Ok(Object::default()),
+		},
+		_ => Ok(Object::default()),
+	}
+}
+
+/// The status of an index build, such as `indexing`, `ready` or `error`
+pub(super) fn status_of(building: &Object) -> String {
+	match building.get("status") {
+		Some(status) => status.clone().as_raw_string(),
+		None => "ready".to_owned(),
+	}
+}
+
+/// Describes the status of an index build, with its progress or error
+pub(super) fn describe(building: &Object) -> String {
+	let mut description = status_of(building);
+	let details: Vec<String> = building
+		.iter()
+		.filter(|(key, _)| key.as_str() != "status")
+		.map(|(key, value)| match value {
+			CoreValue::Strand(value) => format!("{key} {}", value.as_str()),
+			value => format!("{key} {value}"),
+		})
+		.collect();
+	if !details.is_empty() {
+		description.push_str(&format!(" ({})", details.join(", ")));
+	}
+	description
+}
+
+/// The names of the definitions of a kind in the output of an INFO statement
+async fn keys(client: &Surreal<Any>, sql: String, kind: &str) -> Result<Vec<String>, Error> {
+	let info: surrealdb::Value = client.query(sql).await?.take(0)?;
+	match info.into_inner() {
+		CoreValue::Object(mut info) => match info.remove(kind) {
+			Some(CoreValue::Object(definitions)) => Ok(definitions.keys().cloned().collect()),
+			_ => Ok(Vec::new()),
+		},
+		_ => Ok(Vec::new()),
+	}
+}
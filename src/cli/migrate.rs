@@ -0,0 +1,252 @@
+use crate::cli::abstraction::{
+	connect_to_database, AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+};
+use crate::err::Error;
+use clap::{Args, Subcommand};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+
+/// The table which the applied migrations are recorded in
+const TABLE: &str = "_migrations";
+
+#[derive(Debug, Subcommand)]
+pub enum MigrateCommand {
+	#[command(about = "Apply the pending migrations")]
+	Up(UpCommandArguments),
+	#[command(about = "Revert the most recently applied migrations")]
+	Down(DownCommandArguments),
+	#[command(about = "List the migrations, and whether they have been applied")]
+	Status(StatusCommandArguments),
+}
+
+#[derive(Args, Debug)]
+pub struct MigrationArguments {
+	#[arg(help = "The directory containing the migration files")]
+	#[arg(index = 1)]
+	dir: PathBuf,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+}
+
+#[derive(Args, Debug)]
+pub struct UpCommandArguments {
+	#[arg(help = "Only apply the migrations up to and including this version")]
+	#[arg(long)]
+	target: Option<u64>,
+	#[arg(help = "List the migrations which would be applied, without applying them")]
+	#[arg(long)]
+	dry_run: bool,
+	#[command(flatten)]
+	migrations: MigrationArguments,
+}
+
+#[derive(Args, Debug)]
+pub struct DownCommandArguments {
+	#[arg(help = "Revert every migration after this version, instead of only the latest one")]
+	#[arg(long)]
+	target: Option<u64>,
+	#[arg(help = "List the migrations which would be reverted, without reverting them")]
+	#[arg(long)]
+	dry_run: bool,
+	#[command(flatten)]
+	migrations: MigrationArguments,
+}
+
+#[derive(Args, Debug)]
+pub struct StatusCommandArguments {
+	#[command(flatten)]
+	migrations: MigrationArguments,
+}
+
+/// A migration, made up of the files sharing a version in the migrations directory
+///
+/// Migrations are named `<version>_<name>.up.surql`, or `<version>_<name>.surql`, and can be
+/// reverted by an optional `<version>_<name>.down.surql` file.
+#[derive(Debug, Default)]
+struct Migration {
+	name: String,
+	up: Option<PathBuf>,
+	down: Option<PathBuf>,
+}
+
+pub async fn init(command: MigrateCommand) -> Result<(), Error> {
+	match command {
+		MigrateCommand::Up(args) => up(args).await,
+		MigrateCommand::Down(args) => down(args).await,
+		MigrateCommand::Status(args) => status(args).await,
+	}
+}
+
+async fn up(
+	UpCommandArguments {
+		target,
+		dry_run,
+		migrations,
+	}: UpCommandArguments,
+) -> Result<(), Error> {
+	let dir = migrations.dir.clone();
+	let available = read_dir(&dir)?;
+	let client =
+		connect_to_database(&migrations.conn.endpoint, &migrations.auth, &migrations.sel).await?;
+	let applied = applied(&client).await?;
+	// Apply the pending migrations in order of their version
+	for (version, migration) in available {
+		if applied.contains_key(&version) {
+			continue;
+		}
+		if target.is_some_and(|target| version > target) {
+			break;
+		}
+		let Some(path) = &migration.up else {
+			return Err(Error::Migration(format!(
+				"The migration {version} ({}) only has a down file",
+				migration.name
+			)));
+		};
+		if dry_run {
+			println!("Would apply {version} ({})", migration.name);
+			continue;
+		}
+		let script = std::fs::read_to_string(path)?;
+		let sql = format!(
+			"BEGIN TRANSACTION;\n{script}\n;CREATE type::thing('{TABLE}', $version) CONTENT {{ version: $version, name: $name, applied: time::now() }};\nCOMMIT TRANSACTION;"
+		);
+		client
+			.query(sql)
+			.bind(("version", version))
+			.bind(("name", migration.name.clone()))
+			.await?
+			.check()
+			.map_err(|e| {
+				Error::Migration(format!("Failed to apply {version} ({}): {e}", migration.name))
+			})?;
+		println!("Applied {version} ({})", migration.name);
+	}
+	// Everything OK
+	Ok(())
+}
+
+async fn down(
+	DownCommandArguments {
+		target,
+		dry_run,
+		migrations,
+	}: DownCommandArguments,
+) -> Result<(), Error> {
+	let dir = migrations.dir.clone();
+	let mut available = read_dir(&dir)?;
+	let client =
+		connect_to_database(&migrations.conn.endpoint, &migrations.auth, &migrations.sel).await?;
+	let applied = applied(&client).await?;
+	// Revert the applied migrations in the reverse order of their version
+	let revert: Vec<_> = match target {
+		Some(target) => applied.into_iter().rev().take_while(|(v, _)| *v > target).collect(),
+		None => applied.into_iter().next_back().into_iter().collect(),
+	};
+	for (version, name) in revert {
+		let Some(path) = available.remove(&version).and_then(|m| m.down) else {
+			return Err(Error::Migration(format!(
+				"The migration {version} ({name}) has no down file, so it can not be reverted"
+			)));
+		};
+		if dry_run {
+			println!("Would revert {version} ({name})");
+			continue;
+		}
+		let script = std::fs::read_to_string(path)?;
+		let sql = format!(
+			"BEGIN TRANSACTION;\n{script}\n;DELETE type::thing('{TABLE}', $version);\nCOMMIT TRANSACTION;"
+		);
+		client
+			.query(sql)
+			.bind(("version", version))
+			.await?
+			.check()
+			.map_err(|e| Error::Migration(format!("Failed to revert {version} ({name}): {e}")))?;
+		println!("Reverted {version} ({name})");
+	}
+	// Everything OK
+	Ok(())
+}
+
+async fn status(
+	StatusCommandArguments {
+		migrations,
+	}: StatusCommandArguments,
+) -> Result<(), Error> {
+	let dir = migrations.dir.clone();
+	let available = read_dir(&dir)?;
+	let client =
+		connect_to_database(&migrations.conn.endpoint, &migrations.auth, &migrations.sel).await?;
+	let mut applied = applied(&client).await?;
+	for (version, migration) in available {
+		let state = match applied.remove(&version) {
+			Some(_) => "applied",
+			None => "pending",
+		};
+		println!("{version} {} {state}", migration.name);
+	}
+	// Applied migrations whose files have since been removed
+	for (version, name) in applied {
+		println!("{version} {name} missing");
+	}
+	// Everything OK
+	Ok(())
+}
+
+/// Reads the migrations in the directory, ordered by their version
+fn read_dir(dir: &Path) -> Result<BTreeMap<u64, Migration>, Error> {
+	let mut migrations = BTreeMap::<u64, Migration>::new();
+	for entry in std::fs::read_dir(dir)? {
+		let path = entry?.path();
+		let Some(file) = path.file_name().and_then(|f| f.to_str()) else {
+			continue;
+		};
+		let Some(stem) = file.strip_suffix(".surql") else {
+			continue;
+		};
+		let (stem, down) = match (stem.strip_suffix(".up"), stem.strip_suffix(".down")) {
+			(Some(stem), _) => (stem, false),
+			(_, Some(stem)) => (stem, true),
+			_ => (stem, false),
+		};
+		let (version, name) = stem.split_once('_').unwrap_or((stem, ""));
+		let Ok(version) = version.parse::<u64>() else {
+			return Err(Error::Migration(format!(
+				"The file '{file}' does not start with a numeric version"
+			)));
+		};
+		let migration = migrations.entry(version).or_default();
+		if !migration.name.is_empty() && migration.name != name {
+			return Err(Error::Migration(format!(
+				"The migrations '{}' and '{name}' have the same version {version}",
+				migration.name
+			)));
+		}
+		migration.name = name.to_owned();
+		let slot = if down {
+			&mut migration.down
+		} else {
+			&mut migration.up
+		};
+		if slot.replace(path.clone()).is_some() {
+			return Err(Error::Migration(format!(
+				"There is more than one file for the migration '{file}'"
+			)));
+		}
+	}
+	Ok(migrations)
+}
+
+/// Reads the versions and names of the migrations which have been applied
+async fn applied(client: &Surreal<Any>) -> Result<BTreeMap<u64, String>, Error> {
+	let sql = format!("SELECT VALUE [version, name] FROM {TABLE}");
+	let applied: Vec<(u64, String)> = client.query(sql).await?.take(0)?;
+	Ok(applied.into_iter().collect())
+}
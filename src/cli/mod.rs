@@ -1,17 +1,39 @@
 pub(crate) mod abstraction;
+mod backup;
+mod bench;
+mod compact;
+mod compat;
 mod config;
+mod convert;
+mod diff;
+mod doctor;
+mod explain;
 mod export;
 mod fix;
+mod graph;
 mod import;
+mod index;
 mod isready;
+mod migrate;
 mod ml;
+mod provision;
+mod restore;
+mod seed;
 mod sql;
 mod start;
+#[cfg(unix)]
+mod stop;
+mod tabular;
+mod tail;
 #[cfg(test)]
 mod test;
+mod testing;
+mod top;
 mod upgrade;
+mod user;
 mod validate;
 pub(crate) mod validator;
+mod verify;
 mod version;
 mod version_client;
 
@@ -22,21 +44,41 @@ use crate::cli::version_client::VersionClient;
 use crate::cnf::DEBUG_BUILD_WARNING;
 use crate::cnf::{LOGO, PKG_VERSION};
 use crate::env::RELEASE;
-use clap::{Parser, Subcommand};
-pub use config::CF;
+use backup::BackupCommandArguments;
+use bench::BenchCommand;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use compact::CompactCommandArguments;
+pub use config::{BindAddress, CF};
+use convert::ConvertCommandArguments;
+use diff::DiffCommandArguments;
+use doctor::DoctorCommandArguments;
+use explain::ExplainCommandArguments;
 use export::ExportCommandArguments;
 use fix::FixCommandArguments;
+use graph::GraphCommandArguments;
 use import::ImportCommandArguments;
+use index::IndexCommand;
 use isready::IsReadyCommandArguments;
+use migrate::MigrateCommand;
 use ml::MlCommand;
+use provision::ProvisionCommandArguments;
+use restore::RestoreCommandArguments;
+use seed::SeedCommandArguments;
 use semver::Version;
 use sql::SqlCommandArguments;
 use start::StartCommandArguments;
 use std::ops::Deref;
 use std::process::ExitCode;
 use std::time::Duration;
+#[cfg(unix)]
+use stop::StopCommandArguments;
+use tail::TailCommandArguments;
+use testing::TestCommandArguments;
+use top::TopCommandArguments;
 use upgrade::UpgradeCommandArguments;
+use user::UserCommand;
 use validate::ValidateCommandArguments;
+use verify::VerifyCommandArguments;
 use version::VersionCommandArguments;
 
 const INFO: &str = "
@@ -76,31 +118,72 @@ struct Cli {
 enum Commands {
 	#[command(about = "Start the database server")]
 	Start(StartCommandArguments),
-	/* Not implemented yet
-	#[command(about = "Backup data to or from an existing database")]
+	#[cfg(unix)]
+	#[command(about = "Stop a database server which was started with a PID file")]
+	Stop(StopCommandArguments),
+	#[command(about = "Take a full or incremental backup of an existing database")]
 	Backup(BackupCommandArguments),
-	*/
+	#[command(about = "Restore a database from a chain of backups, up to a point in time")]
+	Restore(RestoreCommandArguments),
 	#[command(about = "Import a SurrealQL script into an existing database")]
 	Import(ImportCommandArguments),
+	#[command(about = "Load a directory of JSON fixture files into an existing database")]
+	Seed(SeedCommandArguments),
+	#[command(
+		about = "Create a namespace, database, user and record access method for a new tenant"
+	)]
+	Provision(ProvisionCommandArguments),
 	#[command(about = "Export an existing database as a SurrealQL script")]
 	Export(ExportCommandArguments),
+	#[command(
+		about = "Print the statements which change the schema of a database to match another"
+	)]
+	Diff(DiffCommandArguments),
+	#[command(about = "Output the dependency graph of an existing database in DOT format")]
+	Graph(GraphCommandArguments),
 	#[command(about = "Output the command-line tool and remote server version information")]
 	Version(VersionCommandArguments),
 	#[command(about = "Upgrade to the latest stable version")]
 	Upgrade(UpgradeCommandArguments),
+	#[command(about = "Print the plan of a query, highlighting full table scans")]
+	Explain(ExplainCommandArguments),
 	#[command(about = "Start an SQL REPL in your terminal with pipe support")]
 	Sql(SqlCommandArguments),
 	#[command(subcommand, about = "Manage SurrealML models within an existing database")]
 	Ml(MlCommand),
+	#[command(subcommand, about = "Apply or revert versioned migrations in an existing database")]
+	Migrate(MigrateCommand),
+	#[command(subcommand, about = "List and rebuild the indexes of an existing database")]
+	Index(IndexCommand),
+	#[command(subcommand, about = "Manage the system users of an existing database")]
+	User(UserCommand),
+	#[command(about = "Follow a live query, printing its notifications as NDJSON")]
+	Tail(TailCommandArguments),
+	#[command(about = "Show the statistics of a running server, refreshing them in real time")]
+	Top(TopCommandArguments),
 	#[command(
 		about = "Check if the SurrealDB server is ready to accept connections",
 		visible_alias = "isready"
 	)]
 	IsReady(IsReadyCommandArguments),
+	#[command(about = "Run the tests defined in an existing database")]
+	Test(TestCommandArguments),
 	#[command(about = "Validate SurrealQL query files")]
 	Validate(ValidateCommandArguments),
+	#[command(about = "Scan the files of a stopped datastore for corrupt and orphaned data")]
+	Verify(VerifyCommandArguments),
+	#[command(about = "Check the local environment and a server for common problems")]
+	Doctor(DoctorCommandArguments),
+	#[command(
+		about = "Compact the files of a RocksDB or SurrealKV datastore, reclaiming disk space"
+	)]
+	Compact(CompactCommandArguments),
 	#[command(about = "Fix database storage issues")]
 	Fix(FixCommandArguments),
+	#[command(about = "Rewrite a SurrealDB 1.x export file in the current SurrealQL dialect")]
+	Convert(ConvertCommandArguments),
+	#[command(subcommand, about = "Generate synthetic datasets for benchmarking")]
+	Bench(BenchCommand),
 }
 
 pub async fn init() -> ExitCode {
@@ -117,11 +200,19 @@ pub async fn init() -> ExitCode {
 		.blocklist(&["libc", "libgcc", "pthread", "vdso"])
 		.build()
 		.unwrap();
-	// Parse the CLI arguments
-	let args = Cli::parse();
+	// Parse the CLI arguments, with the defaults set by any configuration file
+	let argv: Vec<_> = std::env::args_os().collect();
+	let mut command = Cli::command();
+	if let Some(path) = config::find(&argv) {
+		command = match config::with_file(command.clone(), path.as_ref()) {
+			Ok(command) => command,
+			Err(e) => command.error(clap::error::ErrorKind::InvalidValue, e).exit(),
+		};
+	}
+	let args = Cli::from_arg_matches(&command.get_matches_from(argv)).unwrap_or_else(|e| e.exit());
 	// After parsing arguments, we check the version online
 	if args.online_version_check {
-		let client = version_client::new(Some(Duration::from_millis(500))).unwrap();
+		let client = version_client::new(Some(Duration::from_millis(500)), None, None).unwrap();
 		if let Err(opt_version) = check_upgrade(&client, PKG_VERSION.deref()).await {
 			match opt_version {
 				None => {
@@ -144,15 +235,35 @@ pub async fn init() -> ExitCode {
 	// After version warning we can run the respective command
 	let output = match args.command {
 		Commands::Start(args) => start::init(args).await,
+		#[cfg(unix)]
+		Commands::Stop(args) => stop::init(args).await,
 		Commands::Import(args) => import::init(args).await,
+		Commands::Seed(args) => seed::init(args).await,
+		Commands::Provision(args) => provision::init(args).await,
 		Commands::Export(args) => export::init(args).await,
+		Commands::Backup(args) => backup::init(args).await,
+		Commands::Restore(args) => restore::init(args).await,
+		Commands::Diff(args) => diff::init(args).await,
+		Commands::Graph(args) => graph::init(args).await,
 		Commands::Version(args) => version::init(args).await,
 		Commands::Upgrade(args) => upgrade::init(args).await,
+		Commands::Explain(args) => explain::init(args).await,
 		Commands::Sql(args) => sql::init(args).await,
 		Commands::Ml(args) => ml::init(args).await,
+		Commands::Migrate(args) => migrate::init(args).await,
+		Commands::Index(args) => index::init(args).await,
+		Commands::User(args) => user::init(args).await,
+		Commands::Tail(args) => tail::init(args).await,
+		Commands::Top(args) => top::init(args).await,
 		Commands::IsReady(args) => isready::init(args).await,
+		Commands::Test(args) => testing::init(args).await,
 		Commands::Validate(args) => validate::init(args).await,
+		Commands::Verify(args) => verify::init(args).await,
+		Commands::Doctor(args) => doctor::init(args).await,
+		Commands::Compact(args) => compact::init(args).await,
 		Commands::Fix(args) => fix::init(args).await,
+		Commands::Convert(args) => convert::init(args).await,
+		Commands::Bench(args) => bench::init(args).await,
 	};
 	// Save the flamegraph and profile
 	#[cfg(feature = "performance-profiler")]
@@ -0,0 +1,121 @@
+use crate::cli::abstraction::{
+	connect_to, AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+};
+use crate::err::Error;
+use clap::Args;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde_json::json;
+use std::path::PathBuf;
+use surrealdb::sql::{Ident, Strand};
+
+/// The length of the passwords which are generated for database users
+const PASSWORD_LENGTH: usize = 32;
+
+#[derive(Args, Debug)]
+pub struct ProvisionCommandArguments {
+	#[arg(help = "Define a database user with this name")]
+	#[arg(long)]
+	user: Option<String>,
+	#[arg(help = "The roles of the database user")]
+	#[arg(long, value_delimiter = ',', default_value = "editor", requires = "user")]
+	roles: Vec<String>,
+	#[arg(
+		help = "Read the password of the database user from this file, instead of generating one"
+	)]
+	#[arg(long, requires = "user", value_parser = super::validator::file_exists)]
+	password_file: Option<PathBuf>,
+	#[arg(help = "Define a record access method with this name")]
+	#[arg(long)]
+	access: Option<String>,
+	#[arg(
+		help = "The table which the record access method signs records up and in with an email and password, unless --signup and --signin are specified"
+	)]
+	#[arg(long, default_value = "user", requires = "access")]
+	access_table: String,
+	#[arg(help = "The SIGNUP query of the record access method")]
+	#[arg(long, requires = "access")]
+	signup: Option<String>,
+	#[arg(help = "The SIGNIN query of the record access method")]
+	#[arg(long, requires = "access")]
+	signin: Option<String>,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+}
+
+pub async fn init(
+	ProvisionCommandArguments {
+		user,
+		roles,
+		password_file,
+		access,
+		access_table,
+		signup,
+		signin,
+		conn,
+		auth,
+		sel,
+	}: ProvisionCommandArguments,
+) -> Result<(), Error> {
+	let (ns, db) = (sel.namespace.clone(), sel.database.clone());
+	// The namespace and database are selected by the provisioning query, as they may not exist yet
+	let client = connect_to(&conn.endpoint, &auth, Some(ns.as_str()), Some(db.as_str())).await?;
+	// Define everything in a single transaction, so a failure leaves nothing half provisioned
+	let mut sql = format!(
+		"BEGIN TRANSACTION;\nDEFINE NAMESPACE IF NOT EXISTS {ns};\nUSE NS {ns};\nDEFINE DATABASE {db};\nUSE DB {db};\n",
+		ns = Ident::from(ns.as_str()),
+		db = Ident::from(db.as_str()),
+	);
+	let mut credentials = json!({
+		"namespace": ns,
+		"database": db,
+	});
+	if let Some(user) = user {
+		let password = match password_file {
+			Some(file) => std::fs::read_to_string(file)?.trim_end_matches(['\r', '\n']).to_owned(),
+			None => generate_password(),
+		};
+		if password.is_empty() {
+			return Err(Error::Other("The password of the user can not be empty".into()));
+		}
+		let roles: Vec<String> =
+			roles.iter().map(|r| Ident::from(r.as_str()).to_string()).collect();
+		sql.push_str(&format!(
+			"DEFINE USER {} ON DATABASE PASSWORD {} ROLES {};\n",
+			Ident::from(user.as_str()),
+			Strand::from(password.as_str()),
+			roles.join(", ")
+		));
+		credentials["user"] = json!(user);
+		credentials["password"] = json!(password);
+	}
+	if let Some(access) = access {
+		let table = Ident::from(access_table);
+		let signup = signup.unwrap_or_else(|| {
+			format!("CREATE {table} SET email = $email, pass = crypto::argon2::generate($pass)")
+		});
+		let signin = signin.unwrap_or_else(|| {
+			format!("SELECT * FROM {table} WHERE email = $email AND crypto::argon2::compare(pass, $pass)")
+		});
+		sql.push_str(&format!(
+			"DEFINE ACCESS {} ON DATABASE TYPE RECORD SIGNUP ({signup}) SIGNIN ({signin});\n",
+			Ident::from(access.as_str())
+		));
+		credentials["access"] = json!(access);
+	}
+	sql.push_str("COMMIT TRANSACTION;\n");
+	client.query(sql).await?.check()?;
+	info!("The database {ns}/{db} was provisioned successfully");
+	// Print the credentials, so they can be handed over to the tenant
+	println!("{}", serde_json::to_string_pretty(&credentials)?);
+	// Everything OK
+	Ok(())
+}
+
+fn generate_password() -> String {
+	rand::thread_rng().sample_iter(&Alphanumeric).take(PASSWORD_LENGTH).map(char::from).collect()
+}
@@ -0,0 +1,185 @@
+use crate::cli::abstraction::{
+	connect_to, AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+};
+use crate::cli::backup::{open, read_manifest, Backup, BackupKind, Mark, VERSIONSTAMP_MARKER};
+use crate::err::Error;
+use chrono::{DateTime, Utc};
+use clap::Args;
+use futures_util::StreamExt;
+use object_store::path::Path;
+use object_store::ObjectStore;
+use std::io;
+use std::sync::Arc;
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+use surrealdb_core::sql::Value as CoreValue;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+use tokio_util::io::StreamReader;
+
+#[derive(Args, Debug)]
+pub struct RestoreCommandArguments {
+	#[arg(help = "The directory, or S3 URL (s3://bucket/prefix), which the backups are stored in")]
+	#[arg(index = 1)]
+	source: String,
+	#[arg(help = "Restore the database as it was at this time, in RFC 3339 format")]
+	#[arg(long)]
+	until: Option<DateTime<Utc>>,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+}
+
+pub async fn init(
+	RestoreCommandArguments {
+		source,
+		until,
+		conn: DatabaseConnectionArguments {
+			endpoint,
+		},
+		auth,
+		sel: DatabaseSelectionArguments {
+			namespace,
+			database,
+		},
+	}: RestoreCommandArguments,
+) -> Result<(), Error> {
+	// Read the chain of backups, and pick the ones taken up to the requested time
+	let store = open(&source)?;
+	let Some(manifest) = read_manifest(&store).await? else {
+		return Err(Error::Restore(format!("There are no backups in '{source}'")));
+	};
+	let (backups, mark) = chain(manifest.backups, until)?;
+
+	let client =
+		connect_to(&endpoint, &auth, Some(namespace.as_str()), Some(database.as_str())).await?;
+
+	// Use the specified namespace / database
+	client.use_ns(&namespace).use_db(&database).await?;
+
+	// Replaying the changes on top of existing data would not reproduce the backed up database
+	if !is_empty(&client).await? {
+		return Err(Error::Restore(format!(
+			"The database {namespace}/{database} is not empty, so the backups can not be restored into it"
+		)));
+	}
+
+	// Apply the full backup, and then each incremental backup in the order they were taken
+	for (index, backup) in backups.iter().enumerate() {
+		debug!("Restoring the backup '{}'", backup.file);
+		// Only the changes made before the mark are restored from the last backup
+		let limit = match &mark {
+			Some(mark) if index == backups.len() - 1 => Some(mark.versionstamp),
+			_ => None,
+		};
+		restore(&client, &store, backup, limit).await?;
+	}
+	match (&mark, backups.last()) {
+		(Some(mark), _) => info!("The database was restored as it was at {}", mark.time),
+		(None, Some(last)) => info!("The database was restored as it was at {}", last.started),
+		(None, None) => (),
+	}
+	// Everything OK
+	Ok(())
+}
+
+/// Returns the backups which need to be applied to restore the database as it was at the time
+///
+/// The chain starts from the last full backup taken at or before the time. The changes made
+/// between the last backup in the chain and the time are restored from the incremental backup
+/// which follows it, up to the latest mark at or before the time, which is also returned.
+fn chain(
+	backups: Vec<Backup>,
+	until: Option<DateTime<Utc>>,
+) -> Result<(Vec<Backup>, Option<Mark>), Error> {
+	let mut chain = Vec::new();
+	let mut mark = None;
+	for mut backup in backups {
+		let started = time(&backup.file, &backup.started)?;
+		if let Some(until) = until.filter(|until| started > *until) {
+			if backup.kind == BackupKind::Incremental && !chain.is_empty() {
+				for candidate in std::mem::take(&mut backup.marks) {
+					if time(&backup.file, &candidate.time)? > until {
+						break;
+					}
+					mark = Some(candidate);
+				}
+				if mark.is_some() {
+					chain.push(backup);
+				}
+			}
+			break;
+		}
+		// A full backup starts a new chain
+		if backup.kind == BackupKind::Full {
+			chain.clear();
+		}
+		chain.push(backup);
+	}
+	match chain.first() {
+		Some(first) if first.kind == BackupKind::Full => Ok((chain, mark)),
+		Some(first) => Err(Error::Restore(format!(
+			"The backup chain starts with the incremental backup '{}'",
+			first.file
+		))),
+		None => Err(Error::Restore("There are no backups taken at or before that time".into())),
+	}
+}
+
+/// Parses a time recorded in the manifest
+fn time(file: &str, time: &str) -> Result<DateTime<Utc>, Error> {
+	DateTime::parse_from_rfc3339(time)
+		.map(|v| v.with_timezone(&Utc))
+		.map_err(|e| Error::Restore(format!("The backup '{file}' has an invalid time: {e}")))
+}
+
+/// Checks that the selected database does not contain any tables
+async fn is_empty(client: &Surreal<Any>) -> Result<bool, Error> {
+	let info: surrealdb::Value = client.query("INFO FOR DB").await?.take(0)?;
+	match info.into_inner() {
+		CoreValue::Object(mut info) => match info.remove("tables") {
+			Some(CoreValue::Object(tables)) => Ok(tables.is_empty()),
+			_ => Ok(true),
+		},
+		_ => Ok(true),
+	}
+}
+
+/// Downloads a backup from the source and imports it into the database
+///
+/// If a limit is given, only the change sets with a lower versionstamp are imported.
+async fn restore(
+	client: &Surreal<Any>,
+	store: &Arc<dyn ObjectStore>,
+	backup: &Backup,
+	limit: Option<u64>,
+) -> Result<(), Error> {
+	let mut stream = store.get(&Path::from(backup.file.as_str())).await?.into_stream();
+	let download = tempfile::NamedTempFile::new()?;
+	let mut file = tokio::fs::File::create(download.path()).await?;
+	match limit {
+		Some(limit) => {
+			let mut lines = StreamReader::new(stream.map(|v| v.map_err(io::Error::other))).lines();
+			while let Some(line) = lines.next_line().await? {
+				let versionstamp = line
+					.strip_prefix(VERSIONSTAMP_MARKER)
+					.and_then(|v| v.trim().parse::<u64>().ok());
+				if versionstamp.is_some_and(|v| v >= limit) {
+					break;
+				}
+				file.write_all(line.as_bytes()).await?;
+				file.write_all(b"\n").await?;
+			}
+		}
+		None => {
+			while let Some(bytes) = stream.next().await {
+				file.write_all(&bytes?).await?;
+			}
+		}
+	}
+	file.flush().await?;
+	client.import(download.path()).await?;
+	Ok(())
+}
@@ -0,0 +1,123 @@
+use crate::cli::abstraction::{
+	connect_to_database, AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+};
+use crate::err::Error;
+use clap::Args;
+use std::path::{Path, PathBuf};
+use surrealdb::sql::Ident;
+
+#[derive(Args, Debug)]
+pub struct SeedCommandArguments {
+	#[arg(
+		help = "Directory of fixture files, named after the table they are loaded into, such as person.json or person.ndjson"
+	)]
+	#[arg(index = 1)]
+	#[arg(value_parser = super::validator::dir_exists)]
+	dir: PathBuf,
+	#[arg(help = "Delete the records of each table before loading its fixtures")]
+	#[arg(long)]
+	truncate: bool,
+	#[arg(help = "The number of records inserted with each query")]
+	#[arg(long, default_value_t = 1000)]
+	#[arg(value_parser = clap::value_parser!(u64).range(1..))]
+	batch_size: u64,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+}
+
+/// The format of a fixture file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+	/// A JSON array of records, or a single record
+	Json,
+	/// A JSON record on each line
+	Ndjson,
+}
+
+pub async fn init(
+	SeedCommandArguments {
+		dir,
+		truncate,
+		batch_size,
+		conn,
+		auth,
+		sel,
+	}: SeedCommandArguments,
+) -> Result<(), Error> {
+	let fixtures = fixtures(&dir)?;
+	if fixtures.is_empty() {
+		return Err(Error::Other(format!(
+			"The directory {} does not contain any .json or .ndjson files",
+			dir.display()
+		)));
+	}
+	let client = connect_to_database(&conn.endpoint, &auth, &sel).await?;
+	for (path, table, format) in fixtures {
+		let records = read(&path, format)?;
+		let table = Ident::from(table);
+		if truncate {
+			client.query(format!("DELETE {table} RETURN NONE")).await?.check()?;
+		}
+		// Insert the records in batches, with a single statement for each batch
+		for batch in records.chunks(batch_size as usize) {
+			client
+				.query(format!("INSERT INTO {table} $records RETURN NONE"))
+				.bind(("records", batch.to_vec()))
+				.await?
+				.check()?;
+		}
+		println!("Loaded {} records into {table} from {}", records.len(), path.display());
+	}
+	// Everything OK
+	Ok(())
+}
+
+/// The fixture files in a directory, with the table and format of each, sorted by file name
+fn fixtures(dir: &Path) -> Result<Vec<(PathBuf, String, Format)>, Error> {
+	let mut fixtures = Vec::new();
+	for entry in std::fs::read_dir(dir)? {
+		let path = entry?.path();
+		let format = match path.extension().and_then(|e| e.to_str()) {
+			Some("json") => Format::Json,
+			Some("ndjson" | "jsonl") => Format::Ndjson,
+			_ => {
+				debug!("Skipping {}, which is not a fixture file", path.display());
+				continue;
+			}
+		};
+		let Some(table) = path.file_stem().and_then(|s| s.to_str()) else {
+			continue;
+		};
+		fixtures.push((path.clone(), table.to_owned(), format));
+	}
+	fixtures.sort();
+	Ok(fixtures)
+}
+
+/// Reads the records of a fixture file
+fn read(path: &Path, format: Format) -> Result<Vec<serde_json::Value>, Error> {
+	let contents = std::fs::read_to_string(path)?;
+	let invalid = |line: Option<usize>, e: serde_json::Error| {
+		let location = match line {
+			Some(line) => format!("{} line {line}", path.display()),
+			None => path.display().to_string(),
+		};
+		Error::Other(format!("Invalid JSON in {location}: {e}"))
+	};
+	match format {
+		Format::Json => match serde_json::from_str(&contents).map_err(|e| invalid(None, e))? {
+			serde_json::Value::Array(records) => Ok(records),
+			record => Ok(vec![record]),
+		},
+		Format::Ndjson => contents
+			.lines()
+			.enumerate()
+			.filter(|(_, line)| !line.trim().is_empty())
+			.map(|(i, line)| serde_json::from_str(line).map_err(|e| invalid(Some(i + 1), e)))
+			.collect(),
+	}
+}
@@ -1,23 +1,34 @@
-use crate::cli::abstraction::auth::{CredentialsBuilder, CredentialsLevel};
 use crate::cli::abstraction::{
-	AuthArguments, DatabaseConnectionArguments, LevelSelectionArguments,
+	connect_with_config, AuthArguments, DatabaseConnectionArguments, LevelSelectionArguments,
+	VersionCheckArguments,
 };
 use crate::cnf::PKG_VERSION;
 use crate::dbs::DbsCapabilities;
 use crate::err::Error;
-use clap::Args;
+use clap::{Args, ValueEnum};
 use futures::StreamExt;
+use rustyline::completion::Completer;
 use rustyline::error::ReadlineError;
 use rustyline::validate::{ValidationContext, ValidationResult, Validator};
-use rustyline::{Completer, Editor, Helper, Highlighter, Hinter};
+use rustyline::{Context, Editor, Helper, Highlighter, Hinter};
 use serde::Serialize;
 use serde_json::ser::PrettyFormatter;
+use std::collections::BTreeSet;
+use std::io::{IsTerminal, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
 use surrealdb::dbs::Capabilities as CoreCapabilities;
-use surrealdb::engine::any::{connect, IntoEndpoint};
+use surrealdb::engine::any::Any;
 use surrealdb::method::{Stats, WithStats};
 use surrealdb::opt::Config;
-use surrealdb::sql::{Param, Statement, Uuid as CoreUuid, Value as CoreValue};
-use surrealdb::{Notification, Response, Value};
+use surrealdb::sql::{Ident, Limit, Param, Statement, Uuid as CoreUuid, Value as CoreValue};
+use surrealdb::{Notification, Response, Surreal, Value};
+
+/// The pager which long results are shown in, unless the `PAGER` environment variable is set
+#[cfg(not(windows))]
+const DEFAULT_PAGER: &str = "less";
+#[cfg(windows)]
+const DEFAULT_PAGER: &str = "more";
 
 #[derive(Args, Debug)]
 pub struct SqlCommandArguments {
@@ -27,18 +38,32 @@ pub struct SqlCommandArguments {
 	auth: AuthArguments,
 	#[command(flatten)]
 	level: LevelSelectionArguments,
+	#[command(flatten)]
+	version: VersionCheckArguments,
 	/// Whether database responses should be pretty printed
 	#[arg(long)]
 	pretty: bool,
 	/// Whether to emit results in JSON
 	#[arg(long)]
 	json: bool,
+	/// How query results are printed
+	#[arg(long, value_enum, default_value_t = Output::Surql)]
+	output: Output,
 	/// Whether omitting semicolon causes a newline
 	#[arg(long)]
 	multi: bool,
 	/// Whether to show welcome message
 	#[arg(long, env = "SURREAL_HIDE_WELCOME")]
 	hide_welcome: bool,
+	/// The file which the shell history is kept in, defaults to `.surreal_history` in the home directory
+	#[arg(long, env = "SURREAL_HISTORY")]
+	history: Option<PathBuf>,
+	/// A variable which queries can use as a `$key` parameter, with a JSON value or a string
+	#[arg(long = "var", value_name = "KEY=VALUE", value_parser = super::validator::variable)]
+	vars: Vec<(String, String)>,
+	/// Run the queries in this file and exit, instead of starting the shell
+	#[arg(long, value_parser = super::validator::file_exists)]
+	file: Option<PathBuf>,
 	#[command(flatten)]
 	#[command(next_help_heading = "Capabilities")]
 	capabilities: DbsCapabilities,
@@ -46,12 +71,7 @@ pub struct SqlCommandArguments {
 
 pub async fn init(
 	SqlCommandArguments {
-		auth: AuthArguments {
-			username,
-			password,
-			token,
-			auth_level,
-		},
+		auth,
 		conn: DatabaseConnectionArguments {
 			endpoint,
 		},
@@ -59,10 +79,17 @@ pub async fn init(
 			namespace,
 			database,
 		},
+		version: VersionCheckArguments {
+			force,
+		},
 		pretty,
 		json,
+		output,
 		multi,
 		hide_welcome,
+		history,
+		vars: variables,
+		file,
 		capabilities,
 		..
 	}: SqlCommandArguments,
@@ -70,50 +97,33 @@ pub async fn init(
 	// Capabilities configuration for local engines
 	let capabilities = capabilities.into_cli_capabilities();
 	let config = Config::new().capabilities(capabilities.clone().into());
-	// If username and password are specified, and we are connecting to a remote SurrealDB server, then we need to authenticate.
-	// If we are connecting directly to a datastore (i.e. surrealkv://local.skv or tikv://...), then we don't need to authenticate because we use an embedded (local) SurrealDB instance with auth disabled.
-	let client = if username.is_some()
-		&& password.is_some()
-		&& !endpoint.clone().into_endpoint()?.parse_kind()?.is_local()
-	{
-		debug!("Connecting to the database engine with authentication");
-		let creds = CredentialsBuilder::default()
-			.with_username(username.as_deref())
-			.with_password(password.as_deref())
-			.with_namespace(namespace.as_deref())
-			.with_database(database.as_deref());
-
-		let client = connect(endpoint).await?;
-
-		debug!("Signing in to the database engine at '{:?}' level", auth_level);
-		match auth_level {
-			CredentialsLevel::Root => client.signin(creds.root()?).await?,
-			CredentialsLevel::Namespace => client.signin(creds.namespace()?).await?,
-			CredentialsLevel::Database => client.signin(creds.database()?).await?,
-		};
-
-		client
-	} else if token.is_some() && !endpoint.clone().into_endpoint()?.parse_kind()?.is_local() {
-		let client = connect(endpoint).await?;
-		client.authenticate(token.unwrap()).await?;
-
-		client
-	} else {
-		debug!("Connecting to the database engine without authentication");
-		connect((endpoint, config)).await?
-	};
+	let client =
+		connect_with_config(&endpoint, &auth, namespace.as_deref(), database.as_deref(), config)
+			.await?;
 
+	// Check that the server understands the queries of this version
+	super::compat::check(&client, force).await?;
+	// Each request over HTTP has its own session, so transactions can't span queries
+	let http = endpoint.starts_with("http://") || endpoint.starts_with("https://");
 	// Create a new terminal REPL
 	let mut rl = Editor::new().unwrap();
 	// Set custom input validation
 	rl.set_helper(Some(InputValidator {
 		multi,
 		capabilities: &capabilities,
+		names: Vec::new(),
 	}));
 	// Load the command-line history
-	let _ = rl.load_history("history.txt");
+	let history = history.unwrap_or_else(history_path);
+	let _ = rl.load_history(&history);
 	// Configure the prompt
 	let mut prompt = "> ".to_owned();
+	// The `--json` flag predates the other output formats
+	let mut output = if json {
+		Output::Json
+	} else {
+		output
+	};
 
 	// Keep track of current namespace/database.
 	let is_not_empty = |s: &&str| !s.is_empty();
@@ -130,12 +140,41 @@ pub async fn init(
 		}
 		_ => {}
 	}
+	// Set the variables, so every query can use them as parameters
+	for (key, value) in variables {
+		let value = surrealdb::syn::json(&value).unwrap_or_else(|_| CoreValue::from(value));
+		client.set(key, Value::from_inner(value)).await?;
+	}
+	// Run the script non-interactively, failing if any of its statements fail
+	if let Some(file) = file {
+		let script = std::fs::read_to_string(&file)?;
+		let Processed {
+			output,
+			failed,
+			..
+		} = process(pretty, output, client.query(script).with_stats().await)?;
+		print(Ok(output));
+		return match failed {
+			0 => Ok(()),
+			1 => Err(Error::Other(format!("A statement in {} failed", file.display()))),
+			n => Err(Error::Other(format!("{n} statements in {} failed", file.display()))),
+		};
+	}
+	// Complete the names in the selected database
+	if let Some(helper) = rl.helper_mut() {
+		helper.names = completions(&client).await;
+	}
 
 	if !hide_welcome {
 		let hints = [
 			(true, "Different statements within a query should be separated by a (;) semicolon."),
 			(!multi, "To create a multi-line query, end your lines with a (\\) backslash, and press enter."),
-			(true, "To exit, send a SIGTERM or press CTRL+C")
+			(true, "Press TAB to complete table, field and function names."),
+			(true, "To change how results are printed, enter \\format followed by surql, json, ndjson, table or csv."),
+			(true, "To limit the number of rows which SELECT statements fetch, enter \\limit followed by a number, or off."),
+			(!http, "To run several queries in one transaction, enter \\begin, and then \\commit or \\cancel."),
+			(true, "To cancel a running query, press CTRL+C"),
+			(true, "To exit, send a SIGTERM or press CTRL+D")
 		]
 		.iter()
 		.filter(|(show, _)| *show)
@@ -160,22 +199,42 @@ pub async fn init(
 		);
 	}
 
+	// The number of rows which SELECT statements without a LIMIT clause fetch
+	let mut limit: Option<u64> = None;
+	// Long results are paged, and row counts shown, when the results are shown in a terminal
+	let terminal = std::io::stdout().is_terminal();
+	// Whether a transaction was begun, which the following queries run within
+	let mut transaction = false;
 	// Loop over each command-line input
 	loop {
+		// Mark the prompt while a transaction is in progress
+		let current = match transaction {
+			true => prompt.replacen('>', "*>", 1),
+			false => prompt.clone(),
+		};
 		// Prompt the user to input SQL and check the input.
-		let line = match rl.readline(&prompt) {
+		let line = match rl.readline(&current) {
 			// The user typed a query
 			Ok(line) => {
 				// Filter out all new lines
 				let line = filter_line_continuations(&line);
-				// Add the entry to the history
+				// Add the entry to the history, and keep it in case the shell is killed
 				if let Err(e) = rl.add_history_entry(line.as_str()) {
 					eprintln!("{e}");
 				}
+				let _ = rl.append_history(&history);
 				line
 			}
-			// The user typed CTRL-C or CTRL-D
-			Err(ReadlineError::Interrupted | ReadlineError::Eof) => {
+			// The user typed CTRL-C, so discard the input
+			Err(ReadlineError::Interrupted) => {
+				continue;
+			}
+			// The user typed CTRL-D
+			Err(ReadlineError::Eof) => {
+				if transaction {
+					let _ = client.query("CANCEL TRANSACTION;").await;
+					eprintln!("The transaction was cancelled");
+				}
 				break;
 			}
 			// There was en error
@@ -188,13 +247,87 @@ pub async fn init(
 		if line.trim().is_empty() {
 			continue;
 		}
+		// Change the output format
+		if let Some(format) = line.trim().strip_prefix("\\format") {
+			let format = format.trim().trim_end_matches(';');
+			match Output::from_str(format, true) {
+				Ok(v) => output = v,
+				Err(_) => eprintln!(
+					"Unknown format '{format}', expected surql, json, ndjson, table or csv\n"
+				),
+			}
+			continue;
+		}
+		// Change the number of rows which are fetched
+		if let Some(value) = line.trim().strip_prefix("\\limit") {
+			match value.trim().trim_end_matches(';') {
+				"" => match limit {
+					Some(n) => eprintln!("SELECT statements fetch at most {n} rows\n"),
+					None => eprintln!("The number of rows is not limited\n"),
+				},
+				"off" | "none" | "0" => limit = None,
+				value => match value.parse() {
+					Ok(n) => limit = Some(n),
+					Err(_) => eprintln!("Invalid limit '{value}', expected a number or off\n"),
+				},
+			}
+			continue;
+		}
+		// Start, commit or cancel a transaction
+		let command = match line.trim().trim_end_matches(';') {
+			"\\begin" if http => {
+				eprintln!(
+					"Transactions can't span queries over HTTP, connect with ws:// instead\n"
+				);
+				continue;
+			}
+			"\\begin" if transaction => {
+				eprintln!(
+					"A transaction is already in progress, enter \\commit or \\cancel to end it\n"
+				);
+				continue;
+			}
+			"\\commit" | "\\cancel" if !transaction => {
+				eprintln!("There is no transaction in progress\n");
+				continue;
+			}
+			"\\begin" => Some("BEGIN TRANSACTION;"),
+			"\\commit" => Some("COMMIT TRANSACTION;"),
+			"\\cancel" => Some("CANCEL TRANSACTION;"),
+			_ => None,
+		};
+		let line = command.map(String::from).unwrap_or(line);
 		// Complete the request
 		match surrealdb_core::syn::parse_with_capabilities(&line, &capabilities) {
 			Ok(mut query) => {
+				// Cap the rows fetched by SELECT statements which don't have their own limit,
+				// keeping track of which results were limited. Transaction statements have none.
+				let mut limited = Vec::with_capacity(query.len());
+				for statement in query.iter_mut() {
+					if matches!(
+						statement,
+						Statement::Begin(_) | Statement::Commit(_) | Statement::Cancel(_)
+					) {
+						continue;
+					}
+					match (statement, limit) {
+						(Statement::Select(stmt), Some(n)) if stmt.limit.is_none() => {
+							stmt.limit = Some(Limit(CoreValue::from(n)));
+							limited.push(true);
+						}
+						_ => limited.push(false),
+					}
+				}
+				// Whether the query leaves a transaction open, or ends the open transaction
+				let open = query.iter().rev().find_map(|statement| match statement {
+					Statement::Begin(_) => Some(true),
+					Statement::Commit(_) | Statement::Cancel(_) => Some(false),
+					_ => None,
+				});
 				let mut namespace = None;
 				let mut database = None;
 				let mut vars = Vec::new();
-				let init_length = query.len();
+				let init_length = limited.len();
 				// Capture `use` and `set/let` statements from the query
 				for statement in query.iter() {
 					match statement {
@@ -224,8 +357,19 @@ pub async fn init(
 					);
 					continue;
 				}
-				// Run the query provided
-				let mut result = client.query(query).with_stats().await;
+				// Run the query provided, until it completes or the user types CTRL-C
+				let mut result = tokio::select! {
+					result = client.query(query).with_stats() => result,
+					_ = tokio::signal::ctrl_c() => {
+						eprintln!("The query was cancelled\n");
+						continue;
+					}
+				};
+
+				// The session keeps the transaction open between queries, until it is ended
+				if let (Ok(_), Some(open)) = (&result, open) {
+					transaction = open;
+				}
 
 				if let Ok(WithStats(res)) = &mut result {
 					for (i, n) in vars.into_iter().enumerate() {
@@ -235,11 +379,36 @@ pub async fn init(
 					}
 				}
 
-				let result = process(pretty, json, result);
-				let result_is_error = result.is_err();
-				print(result);
-				if result_is_error {
-					continue;
+				match process(pretty, output, result) {
+					Ok(Processed {
+						output,
+						rows,
+						..
+					}) => {
+						match terminal {
+							true => page(output),
+							false => print(Ok(output)),
+						}
+						// Show how many rows each statement returned
+						if terminal {
+							for (i, n) in rows.iter().take(init_length).enumerate() {
+								let Some(n) = n else {
+									continue;
+								};
+								let query_num = i + 1;
+								match limit {
+									Some(l) if limited[i] && *n as u64 == l => eprintln!(
+										"-- Query {query_num}: {n} rows, limited by \\limit {l}"
+									),
+									_ => eprintln!("-- Query {query_num}: {n} rows"),
+								}
+							}
+						}
+					}
+					Err(e) => {
+						print(Err(e));
+						continue;
+					}
 				}
 
 				// Process the last `use` statements, if any
@@ -258,6 +427,10 @@ pub async fn init(
 					else if client.use_ns(namespace).use_db(database).await.is_ok() {
 						prompt = format!("{namespace}/{database}> ");
 					}
+					// Complete the names in the newly selected database
+					if let Some(helper) = rl.helper_mut() {
+						helper.names = completions(&client).await;
+					}
 				}
 			}
 			Err(e) => {
@@ -265,23 +438,49 @@ pub async fn init(
 			}
 		}
 	}
-	// Save the inputs to the history
-	let _ = rl.save_history("history.txt");
 	// All ok
 	Ok(())
 }
 
+/// How query results are printed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Output {
+	/// SurrealQL values
+	Surql,
+	/// JSON values
+	Json,
+	/// A line of JSON for each record
+	Ndjson,
+	/// Aligned columns with a row for each record
+	Table,
+	/// Comma-separated values with a row for each record
+	Csv,
+}
+
+/// The formatted results of a query
+struct Processed {
+	output: String,
+	/// The number of statements which failed
+	failed: usize,
+	/// The number of rows which each statement returned, for the statements which returned rows
+	rows: Vec<Option<usize>>,
+}
+
+/// Formats the results of a query
 fn process(
 	pretty: bool,
-	json: bool,
+	output: Output,
 	res: surrealdb::Result<WithStats<Response>>,
-) -> Result<String, Error> {
+) -> Result<Processed, Error> {
+	// Notifications are printed as JSON when the results are
+	let json = matches!(output, Output::Json | Output::Ndjson);
 	// Check query response for an error
 	let mut response = res?;
 	// Get the number of statements the query contained
 	let num_statements = response.num_statements();
 	// Prepare a single value from the query response
 	let mut vec = Vec::<(Stats, Value)>::with_capacity(num_statements);
+	let mut failed = 0;
 	for index in 0..num_statements {
 		let (stats, result) = response
 			.take(index)
@@ -289,9 +488,19 @@ fn process(
 				format!("Expected some result for a query with index {index}, but found none")
 			})
 			.map_err(Error::Other)?;
-		let output = result.unwrap_or_else(|e| Value::from_inner(CoreValue::from(e.to_string())));
+		let output = result.unwrap_or_else(|e| {
+			failed += 1;
+			Value::from_inner(CoreValue::from(e.to_string()))
+		});
 		vec.push((stats, output));
 	}
+	let rows = vec
+		.iter()
+		.map(|(_, x)| match x.into_inner_ref() {
+			CoreValue::Array(array) => Some(array.len()),
+			_ => None,
+		})
+		.collect();
 
 	tokio::spawn(async move {
 		let mut stream = match response.into_inner().stream::<Value>(()) {
@@ -347,8 +556,36 @@ fn process(
 		}
 	});
 
+	// Print each record separately for the tabular formats
+	let values = || vec.iter().map(|(_, x)| x.clone().into_inner());
+	match output {
+		Output::Ndjson => {
+			let output = values()
+				.flat_map(rows)
+				.map(|x| x.into_json().to_string())
+				.collect::<Vec<_>>()
+				.join("\n");
+			return Ok(Processed {
+				output,
+				failed,
+				rows,
+			});
+		}
+		Output::Table | Output::Csv => {
+			let render = match output {
+				Output::Table => table,
+				_ => csv,
+			};
+			return Ok(Processed {
+				output: values().map(render).collect::<Vec<_>>().join("\n\n"),
+				failed,
+				rows,
+			});
+		}
+		Output::Surql | Output::Json => {}
+	}
 	// Check if we should emit JSON and/or prettify
-	Ok(match (json, pretty) {
+	let output = match (json, pretty) {
 		// Don't prettify the SurrealQL response
 		(false, false) => {
 			CoreValue::from(vec.into_iter().map(|(_, x)| x.into_inner()).collect::<Vec<_>>())
@@ -389,9 +626,127 @@ fn process(
 			})
 			.collect::<Vec<String>>()
 			.join("\n"),
+	};
+	Ok(Processed {
+		output,
+		failed,
+		rows,
 	})
 }
 
+/// Splits the result of a statement into records
+fn rows(value: CoreValue) -> Vec<CoreValue> {
+	match value {
+		CoreValue::Array(array) => array.0,
+		value => vec![value],
+	}
+}
+
+/// Splits the result of a statement into a header and rows of cells
+///
+/// The columns are the fields of the records when every record is an object, or else a single
+/// `value` column.
+fn cells(value: CoreValue) -> (Vec<String>, Vec<Vec<String>>) {
+	let rows = rows(value);
+	if !rows.is_empty() && rows.iter().all(|x| matches!(x, CoreValue::Object(_))) {
+		let mut columns = Vec::<String>::new();
+		for row in &rows {
+			if let CoreValue::Object(row) = row {
+				for key in row.keys() {
+					if !columns.contains(key) {
+						columns.push(key.clone());
+					}
+				}
+			}
+		}
+		let cells = rows
+			.into_iter()
+			.map(|row| match row {
+				CoreValue::Object(mut row) => {
+					columns.iter().map(|c| cell(row.remove(c).unwrap_or_default())).collect()
+				}
+				_ => Vec::new(),
+			})
+			.collect();
+		(columns, cells)
+	} else {
+		(vec!["value".to_owned()], rows.into_iter().map(|x| vec![cell(x)]).collect())
+	}
+}
+
+fn cell(value: CoreValue) -> String {
+	match value {
+		CoreValue::None | CoreValue::Null => String::new(),
+		CoreValue::Strand(v) => v.0,
+		v => v.to_string(),
+	}
+}
+
+/// Renders the result of a statement as aligned columns
+fn table(value: CoreValue) -> String {
+	let (columns, rows) = cells(value);
+	let mut widths: Vec<usize> = columns.iter().map(|c| c.chars().count()).collect();
+	for row in &rows {
+		for (width, cell) in widths.iter_mut().zip(row) {
+			*width = (*width).max(cell.chars().count());
+		}
+	}
+	let line = |row: &[String]| {
+		let cells: Vec<String> =
+			row.iter().zip(&widths).map(|(cell, width)| format!("{cell:<width$}")).collect();
+		cells.join(" | ").trim_end().to_owned()
+	};
+	let mut lines = vec![line(&columns)];
+	lines.push(widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>().join("-+-"));
+	lines.extend(rows.iter().map(|row| line(row)));
+	lines.join("\n")
+}
+
+/// Renders the result of a statement as comma-separated values
+fn csv(value: CoreValue) -> String {
+	let (columns, rows) = cells(value);
+	let line = |row: &[String]| {
+		let cells: Vec<String> = row
+			.iter()
+			.map(|cell| match cell.contains([',', '"', '\n', '\r']) {
+				true => format!("\"{}\"", cell.replace('"', "\"\"")),
+				false => cell.clone(),
+			})
+			.collect();
+		cells.join(",")
+	};
+	let mut lines = vec![line(&columns)];
+	lines.extend(rows.iter().map(|row| line(row)));
+	lines.join("\n")
+}
+
+/// Shows the output in a pager when it is longer than the terminal, falling back to printing it
+fn page(output: String) {
+	let height = std::env::var("LINES").ok().and_then(|v| v.parse().ok()).unwrap_or(24);
+	if output.lines().count() < height {
+		return print(Ok(output));
+	}
+	let pager = std::env::var("PAGER").unwrap_or_else(|_| DEFAULT_PAGER.to_owned());
+	let mut args = pager.split_whitespace();
+	let Some(program) = args.next() else {
+		return print(Ok(output));
+	};
+	let child = Command::new(program)
+		.args(args)
+		// Keep colours, and don't clear the screen when the pager exits
+		.env("LESS", std::env::var("LESS").unwrap_or_else(|_| "FRX".to_owned()))
+		.stdin(Stdio::piped())
+		.spawn();
+	let Ok(mut child) = child else {
+		return print(Ok(output));
+	};
+	// The pager closes its input when it is quit before the end of the output
+	if let Some(mut stdin) = child.stdin.take() {
+		let _ = writeln!(stdin, "{output}");
+	}
+	let _ = child.wait();
+}
+
 fn print(result: Result<String, Error>) {
 	match result {
 		Ok(v) => {
@@ -403,11 +758,38 @@ fn print(result: Result<String, Error>) {
 	}
 }
 
-#[derive(Completer, Helper, Highlighter, Hinter)]
+#[derive(Helper, Highlighter, Hinter)]
 struct InputValidator<'a> {
 	/// If omitting semicolon causes newline.
 	multi: bool,
 	capabilities: &'a CoreCapabilities,
+	/// The table, field and function names which can be completed
+	names: Vec<String>,
+}
+
+impl Completer for InputValidator<'_> {
+	type Candidate = String;
+
+	fn complete(
+		&self,
+		line: &str,
+		pos: usize,
+		_: &Context<'_>,
+	) -> rustyline::Result<(usize, Vec<String>)> {
+		// Find the start of the word being typed
+		let start = line[..pos]
+			.char_indices()
+			.rev()
+			.find(|(_, c)| !(c.is_alphanumeric() || matches!(c, '_' | ':' | '.')))
+			.map(|(i, c)| i + c.len_utf8())
+			.unwrap_or(0);
+		let word = &line[start..pos];
+		if word.is_empty() {
+			return Ok((pos, Vec::new()));
+		}
+		let candidates = self.names.iter().filter(|name| name.starts_with(word)).cloned().collect();
+		Ok((start, candidates))
+	}
 }
 
 #[allow(clippy::if_same_then_else)]
@@ -419,7 +801,9 @@ impl Validator for InputValidator<'_> {
 		// Trim all whitespace from the user input
 		let input = input.trim();
 		// Process the input to check if we can send the query
-		let result = if self.multi && !input.ends_with(';') {
+		let result = if input.starts_with('\\') && !input.contains('\n') {
+			Valid(None) // Shell meta-commands are a single line
+		} else if self.multi && !input.ends_with(';') {
 			Incomplete // The line doesn't end with a ; and we are in multi mode
 		} else if self.multi && input.is_empty() {
 			Incomplete // The line was empty and we are in multi mode
@@ -428,7 +812,12 @@ impl Validator for InputValidator<'_> {
 		} else if input.is_empty() {
 			Valid(None) // Ignore empty lines
 		} else if let Err(e) = surrealdb::syn::parse_with_capabilities(input, self.capabilities) {
-			Invalid(Some(format!(" --< {e}")))
+			// Keep editing on a new line while a block or string is still open
+			if e.to_string().contains("Unexpected end of file") {
+				Incomplete
+			} else {
+				Invalid(Some(format!(" --< {e}")))
+			}
 		} else {
 			Valid(None)
 		};
@@ -445,3 +834,47 @@ fn split_prompt(prompt: &str) -> (&str, &str) {
 	let selection = prompt.split_once('>').unwrap().0;
 	selection.split_once('/').unwrap_or((selection, ""))
 }
+
+/// The default file which the shell history is kept in
+fn history_path() -> PathBuf {
+	let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+	match home {
+		Some(home) => PathBuf::from(home).join(".surreal_history"),
+		None => PathBuf::from(".surreal_history"),
+	}
+}
+
+/// Fetches the table, field and function names in the selected database
+async fn completions(client: &Surreal<Any>) -> Vec<String> {
+	let mut names = BTreeSet::new();
+	let Ok(mut response) = client.query("INFO FOR DB").await else {
+		return Vec::new();
+	};
+	let info: Result<Value, _> = response.take(0);
+	let Ok(CoreValue::Object(info)) = info.map(Value::into_inner) else {
+		return Vec::new();
+	};
+	if let Some(CoreValue::Object(functions)) = info.get("functions") {
+		names.extend(functions.keys().map(|name| format!("fn::{name}")));
+	}
+	let Some(CoreValue::Object(tables)) = info.get("tables") else {
+		return names.into_iter().collect();
+	};
+	names.extend(tables.keys().cloned());
+	// Fetch the fields of every table with a single query
+	let sql: String = tables
+		.keys()
+		.map(|table| format!("INFO FOR TABLE {};", Ident::from(table.as_str())))
+		.collect();
+	if let Ok(mut response) = client.query(sql).await {
+		for index in 0..tables.len() {
+			let info: Result<Value, _> = response.take(index);
+			if let Ok(CoreValue::Object(info)) = info.map(Value::into_inner) {
+				if let Some(CoreValue::Object(fields)) = info.get("fields") {
+					names.extend(fields.keys().cloned());
+				}
+			}
+		}
+	}
+	names.into_iter().collect()
+}
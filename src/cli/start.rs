@@ -1,4 +1,4 @@
-use super::config::{Config, CF};
+use super::config::{BindAddress, Config, CF};
 use crate::cnf::LOGO;
 use crate::dbs;
 use crate::dbs::StartCommandDbsOptions;
@@ -6,8 +6,9 @@ use crate::env;
 use crate::err::Error;
 use crate::net::{self, client_ip::ClientIp};
 use clap::Args;
-use std::net::SocketAddr;
-use std::path::PathBuf;
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use surrealdb::engine::any::IntoEndpoint;
@@ -31,6 +32,18 @@ pub struct StartCommandArguments {
 	#[arg(value_parser = super::validator::key_valid)]
 	#[arg(hide = true)] // Not currently in use
 	key: Option<String>,
+	#[arg(help = "Path to a TOML file with the settings of the server")]
+	#[arg(env = "SURREAL_CONFIG", long = "config")]
+	#[arg(value_parser = super::validator::file_exists)]
+	config: Option<PathBuf>,
+	#[arg(help = "Run the server in the background, detached from the terminal")]
+	#[arg(env = "SURREAL_DAEMONIZE", long, requires = "pid_file")]
+	#[arg(default_value_t = false)]
+	#[cfg(unix)]
+	daemonize: bool,
+	#[arg(help = "Path to a file which the process ID of the server is written to while it runs")]
+	#[arg(env = "SURREAL_PID_FILE", long = "pid-file")]
+	pid_file: Option<PathBuf>,
 	//
 	// Tasks
 	//
@@ -62,6 +75,13 @@ pub struct StartCommandArguments {
 	#[arg(env = "SURREAL_CHANGEFEED_GC_INTERVAL", long = "changefeed-gc-interval", value_parser = super::validator::duration)]
 	#[arg(default_value = "10s")]
 	changefeed_gc_interval: Duration,
+	#[arg(
+		help = "The interval at which to process asynchronous events",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_EVENT_PROCESS_INTERVAL", long = "event-process-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "1s")]
+	event_process_interval: Duration,
 	//
 	// Authentication
 	//
@@ -76,9 +96,10 @@ pub struct StartCommandArguments {
 		visible_alias = "user",
 		requires = "password"
 	)]
+	#[arg(value_parser = super::validator::secret)]
 	username: Option<String>,
 	#[arg(
-		help = "The password for the initial database root user. Only if no other root user exists",
+		help = "The password for the initial database root user, or @FILE to read it from a file. Only if no other root user exists",
 		help_heading = "Authentication"
 	)]
 	#[arg(
@@ -88,6 +109,7 @@ pub struct StartCommandArguments {
 		visible_alias = "pass",
 		requires = "username"
 	)]
+	#[arg(value_parser = super::validator::secret)]
 	password: Option<String>,
 	//
 	// Datastore connection
@@ -105,20 +127,34 @@ pub struct StartCommandArguments {
 	#[arg(env = "SURREAL_CLIENT_IP", long)]
 	#[arg(default_value = "socket", value_enum)]
 	client_ip: ClientIp,
-	#[arg(help = "The hostname or IP address to listen for connections on")]
+	#[arg(
+		help = "The hostname or IP address to listen for connections on, or a Unix domain socket path prefixed with unix://"
+	)]
 	#[arg(env = "SURREAL_BIND", short = 'b', long = "bind")]
 	#[arg(default_value = "127.0.0.1:8000")]
-	listen_addresses: Vec<SocketAddr>,
+	listen_addresses: Vec<BindAddress>,
 	#[arg(help = "Whether to suppress the server name and version headers")]
 	#[arg(env = "SURREAL_NO_IDENTIFICATION_HEADERS", long)]
 	#[arg(default_value_t = false)]
 	no_identification_headers: bool,
+	#[arg(
+		help = "The maximum duration to wait for in-flight requests and connections to finish when shutting down, before they are cancelled"
+	)]
+	#[arg(env = "SURREAL_SHUTDOWN_TIMEOUT", long = "shutdown-timeout")]
+	#[arg(value_parser = super::validator::duration)]
+	shutdown_timeout: Option<Duration>,
 	//
 	// Database options
 	//
 	#[command(flatten)]
 	#[command(next_help_heading = "Database")]
 	dbs: StartCommandDbsOptions,
+	//
+	// Mirroring
+	//
+	#[command(flatten)]
+	#[command(next_help_heading = "Mirroring")]
+	mirror: StartCommandMirrorOptions,
 }
 
 #[derive(Args, Debug)]
@@ -146,6 +182,50 @@ struct StartCommandWebTlsOptions {
 	web_key: Option<PathBuf>,
 }
 
+#[derive(Args, Debug)]
+struct StartCommandMirrorOptions {
+	#[arg(help = "The endpoint of a secondary server which all write queries are mirrored to")]
+	#[arg(env = "SURREAL_MIRROR", long = "mirror")]
+	mirror: Option<String>,
+	#[arg(
+		help = "The username for the root user of the mirror endpoint, whose password is read from SURREAL_MIRROR_PASS or --mirror-password-file"
+	)]
+	#[arg(env = "SURREAL_MIRROR_USER", long = "mirror-username", requires = "mirror")]
+	#[arg(value_parser = super::validator::secret)]
+	mirror_username: Option<String>,
+	#[arg(
+		help = "A file which the password for the root user of the mirror endpoint is read from"
+	)]
+	#[arg(
+		env = "SURREAL_MIRROR_PASS_FILE",
+		long = "mirror-password-file",
+		requires = "mirror_username"
+	)]
+	mirror_password_file: Option<PathBuf>,
+}
+
+impl StartCommandMirrorOptions {
+	/// The credentials for the mirror endpoint
+	///
+	/// The password is never accepted as a command-line argument, so that it
+	/// does not appear in process listings or shell history.
+	fn credentials(&self) -> Result<Option<(String, String)>, Error> {
+		let Some(username) = &self.mirror_username else {
+			return Ok(None);
+		};
+		let password = match &self.mirror_password_file {
+			Some(path) => std::fs::read_to_string(path)?.trim_end_matches(['\r', '\n']).to_owned(),
+			None => std::env::var("SURREAL_MIRROR_PASS").map_err(|_| {
+				Error::Other(
+					"A mirror password must be set with SURREAL_MIRROR_PASS or --mirror-password-file"
+						.to_owned(),
+				)
+			})?,
+		};
+		Ok(Some((username.clone(), password)))
+	}
+}
+
 pub async fn init(
 	StartCommandArguments {
 		path,
@@ -155,15 +235,33 @@ pub async fn init(
 		listen_addresses,
 		dbs,
 		web,
+		mirror,
 		node_membership_refresh_interval,
 		node_membership_check_interval,
 		node_membership_cleanup_interval,
 		changefeed_gc_interval,
+		event_process_interval,
 		no_banner,
 		no_identification_headers,
+		shutdown_timeout,
+		#[cfg(unix)]
+		daemonize,
+		pid_file,
 		..
 	}: StartCommandArguments,
 ) -> Result<(), Error> {
+	// Lock the PID file, so that only one server can run with it at once
+	let mut pid_lock = match &pid_file {
+		Some(pid_file) => Some(PidFile::lock(pid_file)?),
+		None => None,
+	};
+	// Start the server in a background process, and leave it running
+	#[cfg(unix)]
+	if let (true, Some(pid_file)) = (daemonize, &pid_file) {
+		// The server in the background locks the PID file itself
+		drop(pid_lock.take());
+		return self::daemonize(pid_file).await;
+	}
 	// Check if we should output a banner
 	if !no_banner {
 		println!("{LOGO}");
@@ -186,7 +284,8 @@ pub async fn init(
 		.with_node_membership_refresh_interval(node_membership_refresh_interval)
 		.with_node_membership_check_interval(node_membership_check_interval)
 		.with_node_membership_cleanup_interval(node_membership_cleanup_interval)
-		.with_changefeed_gc_interval(changefeed_gc_interval);
+		.with_changefeed_gc_interval(changefeed_gc_interval)
+		.with_event_process_interval(event_process_interval);
 	// Configure the config
 	let config = Config {
 		bind: listen_addresses.first().cloned().unwrap(),
@@ -195,6 +294,7 @@ pub async fn init(
 		user,
 		pass,
 		no_identification_headers,
+		shutdown_timeout,
 		engine,
 		crt,
 		key,
@@ -206,7 +306,23 @@ pub async fn init(
 	// Create a token to cancel tasks
 	let canceller = CancellationToken::new();
 	// Start the datastore
-	let datastore = Arc::new(dbs::init(dbs).await?);
+	let datastore = match mirror.mirror {
+		Some(_) => dbs::init(dbs).await?.with_mirroring(),
+		None => dbs::init(dbs).await?,
+	};
+	let datastore = Arc::new(datastore);
+	// Record the process ID, now that the datastore is open
+	if let Some(pid_lock) = &mut pid_lock {
+		pid_lock.write()?;
+	}
+	// Start mirroring write queries
+	let mirrortask = match mirror.mirror.clone() {
+		Some(endpoint) => {
+			let credentials = mirror.credentials()?;
+			Some(crate::mirror::init(&datastore, endpoint, credentials, canceller.clone()).await?)
+		}
+		None => None,
+	};
 	// Start the node agent
 	let nodetasks = tasks::init(datastore.clone(), canceller.clone(), &CF.get().unwrap().engine);
 	// Start the web server
@@ -215,8 +331,188 @@ pub async fn init(
 	canceller.cancel();
 	// Wait for background tasks to finish
 	nodetasks.resolve().await?;
+	if let Some(task) = mirrortask {
+		let _ = task.await;
+	}
 	// Shutdown the datastore
 	datastore.shutdown().await?;
+	// Remove the PID file, now that the server has stopped
+	drop(pid_lock);
 	// All ok
 	Ok(())
 }
+
+/// Starts the server in a background process, returning once it has opened the datastore
+///
+/// The server is started by running this executable again, with the same arguments apart from
+/// `--daemonize`. Its output is discarded if it would be written to a terminal, so it can be
+/// kept by redirecting the output of this command to a file.
+#[cfg(unix)]
+async fn daemonize(pid_file: &Path) -> Result<(), Error> {
+	use std::io::IsTerminal;
+	use std::os::unix::process::CommandExt;
+	use std::process::{Command, Stdio};
+	let output = |terminal: bool| match terminal {
+		true => Stdio::null(),
+		false => Stdio::inherit(),
+	};
+	// Remove the flag in any of its forms, such as `--daemonize=true`
+	let args = std::env::args_os().skip(1).filter(|arg| {
+		!arg.to_str().is_some_and(|arg| arg == "--daemonize" || arg.starts_with("--daemonize="))
+	});
+	let mut command = Command::new(std::env::current_exe()?);
+	command
+		.args(args)
+		.env_remove("SURREAL_DAEMONIZE")
+		.stdin(Stdio::null())
+		.stdout(output(std::io::stdout().is_terminal()))
+		.stderr(output(std::io::stderr().is_terminal()));
+	// Start a new session, so the server is detached from the controlling terminal, and is not
+	// sent the signals which the terminal sends when it is closed
+	// SAFETY: setsid is async-signal-safe, and nothing else runs between fork and exec
+	unsafe {
+		command.pre_exec(|| match nix::unistd::setsid() {
+			Ok(_) => Ok(()),
+			Err(e) => Err(e.into()),
+		});
+	}
+	let mut child = command.spawn()?;
+	let pid = child.id();
+	// Wait until the server has written its PID file, so a failure to start is reported
+	loop {
+		if let Some(status) = child.try_wait()? {
+			return Err(Error::Other(format!(
+				"The server failed to start in the background, and exited with {status}"
+			)));
+		}
+		if read_pid_file(pid_file)? == Some(pid) {
+			break;
+		}
+		tokio::time::sleep(Duration::from_millis(100)).await;
+	}
+	println!("Started the server in the background with PID {pid}");
+	Ok(())
+}
+
+/// Reads the process ID of a server from a PID file, if the file exists and has been written
+pub(super) fn read_pid_file(path: &Path) -> Result<Option<u32>, Error> {
+	match std::fs::read_to_string(path) {
+		Ok(pid) if pid.trim().is_empty() => Ok(None),
+		Ok(pid) => match pid.trim().parse() {
+			Ok(pid) => Ok(Some(pid)),
+			Err(_) => Err(Error::Other(format!("The PID file {} is not valid", path.display()))),
+		},
+		Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// Whether a running server has locked the PID file
+#[cfg(unix)]
+pub(super) fn is_locked(path: &Path) -> Result<bool, Error> {
+	match File::open(path) {
+		Ok(file) => Ok(!try_lock(&file, false)?),
+		Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// A PID file, which the server keeps locked for as long as it is running
+///
+/// The lock is released by the operating system when the process exits, so a PID file which is
+/// left behind by a server that crashed does not stop a new server from starting. It also shows
+/// whether the recorded process is still the server, and not another process which has since
+/// been given the same ID. The file is removed when this is dropped.
+struct PidFile {
+	file: File,
+	path: PathBuf,
+}
+
+impl PidFile {
+	/// Locks the PID file, failing if a running server has already locked it
+	fn lock(path: &Path) -> Result<Self, Error> {
+		loop {
+			let file = OpenOptions::new()
+				.read(true)
+				.write(true)
+				.create(true)
+				.truncate(false)
+				.open(path)?;
+			if !try_lock(&file, true)? {
+				let pid = match read_pid_file(path)? {
+					Some(pid) => format!(" with PID {pid}"),
+					None => String::new(),
+				};
+				return Err(Error::Other(format!(
+					"A server is already running{pid}, according to {}",
+					path.display()
+				)));
+			}
+			// Retry if the file was removed by a server which stopped before it was locked
+			if is_same_file(&file, path)? {
+				return Ok(Self {
+					file,
+					path: path.to_owned(),
+				});
+			}
+		}
+	}
+
+	/// Records the process ID of this server
+	fn write(&mut self) -> Result<(), Error> {
+		self.file.set_len(0)?;
+		self.file.write_all(format!("{}\n", std::process::id()).as_bytes())?;
+		self.file.sync_all()?;
+		Ok(())
+	}
+}
+
+impl Drop for PidFile {
+	fn drop(&mut self) {
+		// The file is removed before it is unlocked, so no other server can lock it in between
+		if let Err(e) = std::fs::remove_file(&self.path) {
+			warn!("Unable to remove the PID file {}: {e}", self.path.display());
+		}
+	}
+}
+
+/// Tries to lock a file without waiting, returning whether the lock was taken
+///
+/// The lock is held until the file is closed.
+#[cfg(unix)]
+fn try_lock(file: &File, exclusive: bool) -> Result<bool, Error> {
+	use nix::errno::Errno;
+	use nix::fcntl::{flock, FlockArg};
+	use std::os::fd::AsRawFd;
+	let arg = match exclusive {
+		true => FlockArg::LockExclusiveNonblock,
+		false => FlockArg::LockSharedNonblock,
+	};
+	match flock(file.as_raw_fd(), arg) {
+		Ok(()) => Ok(true),
+		Err(Errno::EWOULDBLOCK) => Ok(false),
+		Err(e) => Err(std::io::Error::from(e).into()),
+	}
+}
+
+#[cfg(not(unix))]
+fn try_lock(_: &File, _: bool) -> Result<bool, Error> {
+	Ok(true)
+}
+
+/// Whether an open file is still the file at the path
+#[cfg(unix)]
+fn is_same_file(file: &File, path: &Path) -> Result<bool, Error> {
+	use std::os::unix::fs::MetadataExt;
+	let open = file.metadata()?;
+	match std::fs::metadata(path) {
+		Ok(current) => Ok(open.dev() == current.dev() && open.ino() == current.ino()),
+		Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+		Err(e) => Err(e.into()),
+	}
+}
+
+#[cfg(not(unix))]
+fn is_same_file(_: &File, _: &Path) -> Result<bool, Error> {
+	Ok(true)
+}
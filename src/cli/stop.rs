@@ -0,0 +1,55 @@
+use crate::cli::start::{is_locked, read_pid_file};
+use crate::err::Error;
+use clap::Args;
+use nix::sys::signal::{kill, Signal};
+use nix::unistd::Pid;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// How often the server is checked while waiting for it to stop
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Args, Debug)]
+pub struct StopCommandArguments {
+	#[arg(help = "Path to the PID file which the server was started with")]
+	#[arg(env = "SURREAL_PID_FILE", long = "pid-file")]
+	pid_file: PathBuf,
+	#[arg(help = "The maximum duration to wait for the server to stop")]
+	#[arg(long, default_value = "60s")]
+	#[arg(value_parser = super::validator::duration)]
+	timeout: Duration,
+}
+
+pub async fn init(
+	StopCommandArguments {
+		pid_file,
+		timeout,
+	}: StopCommandArguments,
+) -> Result<(), Error> {
+	let Some(pid) = read_pid_file(&pid_file)? else {
+		return Err(Error::Other(format!(
+			"The PID file {} does not exist, so the server is not running",
+			pid_file.display()
+		)));
+	};
+	// The server keeps its PID file locked while it runs. Once it has stopped, the recorded PID
+	// may be reused by another process, which must not be sent the signal.
+	if !is_locked(&pid_file)? {
+		return Err(Error::Other(format!("The server with PID {pid} is not running")));
+	}
+	// Ask the server to shut down gracefully
+	kill(Pid::from_raw(pid as i32), Signal::SIGTERM)
+		.map_err(|e| Error::Other(format!("Unable to stop the server with PID {pid}: {e}")))?;
+	let start = Instant::now();
+	while is_locked(&pid_file)? {
+		if start.elapsed() > timeout {
+			return Err(Error::Other(format!(
+				"The server with PID {pid} did not stop within {timeout:?}"
+			)));
+		}
+		tokio::time::sleep(POLL_INTERVAL).await;
+	}
+	println!("Stopped the server with PID {pid}");
+	// All ok
+	Ok(())
+}
@@ -0,0 +1,298 @@
+use crate::err::Error;
+#[cfg(feature = "export-parquet")]
+use arrow::array::{ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray};
+#[cfg(feature = "export-parquet")]
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+#[cfg(feature = "export-parquet")]
+use arrow::record_batch::RecordBatch;
+use clap::ValueEnum;
+#[cfg(feature = "export-parquet")]
+use parquet::arrow::ArrowWriter;
+use std::fs::File;
+use std::io::Write;
+#[cfg(feature = "export-parquet")]
+use std::sync::Arc;
+use surrealdb::engine::any::Any;
+use surrealdb::sql::{Ident, Number, Value as CoreValue};
+use surrealdb::Surreal;
+
+/// The number of records read with each query
+const PAGE_SIZE: u64 = 1000;
+
+/// The format which the data is exported in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+	/// A SurrealQL script which recreates the database
+	#[default]
+	Surql,
+	/// Comma-separated values, with a row for each record in a table
+	Csv,
+	/// An Apache Parquet file, with a row for each record in a table
+	///
+	/// This requires the `export-parquet` feature to be enabled.
+	Parquet,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ColumnType {
+	Int,
+	Float,
+	Bool,
+	String,
+}
+
+#[derive(Debug)]
+struct Column {
+	name: String,
+	#[cfg_attr(not(feature = "export-parquet"), allow(dead_code))]
+	kind: ColumnType,
+}
+
+/// Writes a row for each record in a table to the file, returning the number of rows
+///
+/// The columns are the top level fields defined on the table, and their types are derived from
+/// the field types. For schemaless tables, the columns are the fields of the first records.
+pub async fn export(
+	client: &Surreal<Any>,
+	table: &str,
+	format: ExportFormat,
+	file: &str,
+) -> Result<u64, Error> {
+	let mut columns = schema(client, table).await?;
+	let output: Box<dyn Write + Send> = match file {
+		"-" => Box::new(std::io::stdout()),
+		file => Box::new(File::create(file)?),
+	};
+	let mut rows = page(client, table, 0).await?;
+	if columns.len() == 1 {
+		infer(&mut columns, &rows);
+	}
+	let mut writer = Writer::new(format, &columns, output)?;
+	let mut count = 0;
+	loop {
+		writer.write(&columns, &rows)?;
+		count += rows.len() as u64;
+		if (rows.len() as u64) < PAGE_SIZE {
+			break;
+		}
+		rows = page(client, table, count).await?;
+	}
+	writer.finish()?;
+	Ok(count)
+}
+
+enum Writer {
+	Csv(Box<dyn Write + Send>),
+	#[cfg(feature = "export-parquet")]
+	Parquet(ArrowWriter<Box<dyn Write + Send>>, SchemaRef),
+}
+
+impl Writer {
+	/// Creates a writer, writing the header of the file
+	fn new(
+		format: ExportFormat,
+		columns: &[Column],
+		mut output: Box<dyn Write + Send>,
+	) -> Result<Self, Error> {
+		match format {
+			#[cfg(not(feature = "export-parquet"))]
+			ExportFormat::Parquet => Err(Error::Export(
+				"Exporting as Parquet requires the `export-parquet` feature to be enabled"
+					.to_owned(),
+			)),
+			#[cfg(feature = "export-parquet")]
+			ExportFormat::Parquet => {
+				let fields: Vec<Field> = columns
+					.iter()
+					.map(|c| {
+						let kind = match c.kind {
+							ColumnType::Int => DataType::Int64,
+							ColumnType::Float => DataType::Float64,
+							ColumnType::Bool => DataType::Boolean,
+							ColumnType::String => DataType::Utf8,
+						};
+						Field::new(c.name.as_str(), kind, true)
+					})
+					.collect();
+				let schema = Arc::new(Schema::new(fields));
+				Ok(Self::Parquet(ArrowWriter::try_new(output, schema.clone(), None)?, schema))
+			}
+			_ => {
+				let header: Vec<String> = columns.iter().map(|c| csv(c.name.clone())).collect();
+				writeln!(output, "{}", header.join(","))?;
+				Ok(Self::Csv(output))
+			}
+		}
+	}
+
+	/// Writes a page of records
+	fn write(&mut self, columns: &[Column], rows: &[CoreValue]) -> Result<(), Error> {
+		match self {
+			Self::Csv(output) => {
+				for row in rows {
+					let cells: Vec<String> =
+						columns.iter().map(|c| csv(cell(field(row, &c.name)))).collect();
+					writeln!(output, "{}", cells.join(","))?;
+				}
+			}
+			#[cfg(feature = "export-parquet")]
+			Self::Parquet(output, schema) => {
+				if !rows.is_empty() {
+					output.write(&batch(schema.clone(), columns, rows)?)?;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Writes the footer of the file
+	fn finish(self) -> Result<(), Error> {
+		match self {
+			Self::Csv(mut output) => output.flush()?,
+			#[cfg(feature = "export-parquet")]
+			Self::Parquet(output, _) => {
+				output.close()?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Reads the top level fields defined on the table, starting with the record id
+async fn schema(client: &Surreal<Any>, table: &str) -> Result<Vec<Column>, Error> {
+	let mut columns = vec![Column {
+		name: "id".to_owned(),
+		kind: ColumnType::String,
+	}];
+	let sql = format!("INFO FOR TABLE {} STRUCTURE", Ident::from(table));
+	let info: surrealdb::Value = client.query(sql).await?.take(0)?;
+	let CoreValue::Object(mut info) = info.into_inner() else {
+		return Ok(columns);
+	};
+	let Some(CoreValue::Array(fields)) = info.remove("fields") else {
+		return Ok(columns);
+	};
+	for field in fields {
+		let CoreValue::Object(field) = field else {
+			continue;
+		};
+		let Some(CoreValue::Strand(name)) = field.get("name") else {
+			continue;
+		};
+		// Nested fields are exported as part of their parent
+		if name.contains(['.', '[']) || name.as_str() == "id" {
+			continue;
+		}
+		let kind = match field.get("kind") {
+			Some(CoreValue::Strand(kind)) => {
+				let kind = kind.as_str();
+				let kind =
+					kind.strip_prefix("option<").and_then(|k| k.strip_suffix('>')).unwrap_or(kind);
+				match kind {
+					"int" => ColumnType::Int,
+					"float" | "decimal" | "number" => ColumnType::Float,
+					"bool" => ColumnType::Bool,
+					_ => ColumnType::String,
+				}
+			}
+			_ => ColumnType::String,
+		};
+		columns.push(Column {
+			name: name.as_str().trim_matches('`').to_owned(),
+			kind,
+		});
+	}
+	Ok(columns)
+}
+
+/// Adds the fields of the records as columns, for tables without defined fields
+fn infer(columns: &mut Vec<Column>, rows: &[CoreValue]) {
+	for row in rows {
+		let CoreValue::Object(row) = row else {
+			continue;
+		};
+		for (name, value) in row.iter() {
+			if columns.iter().any(|c| &c.name == name) {
+				continue;
+			}
+			let kind = match value {
+				CoreValue::Number(Number::Int(_)) => ColumnType::Int,
+				CoreValue::Number(_) => ColumnType::Float,
+				CoreValue::Bool(_) => ColumnType::Bool,
+				_ => ColumnType::String,
+			};
+			columns.push(Column {
+				name: name.clone(),
+				kind,
+			});
+		}
+	}
+}
+
+async fn page(client: &Surreal<Any>, table: &str, start: u64) -> Result<Vec<CoreValue>, Error> {
+	let rows: surrealdb::Value = client
+		.query("SELECT * FROM type::table($table) START $start LIMIT $limit")
+		.bind(("table", table.to_owned()))
+		.bind(("start", start))
+		.bind(("limit", PAGE_SIZE))
+		.await?
+		.take(0)?;
+	match rows.into_inner() {
+		CoreValue::Array(rows) => Ok(rows.0),
+		_ => Ok(Vec::new()),
+	}
+}
+
+fn field(row: &CoreValue, name: &str) -> CoreValue {
+	match row {
+		CoreValue::Object(row) => row.get(name).cloned().unwrap_or_default(),
+		_ => CoreValue::None,
+	}
+}
+
+fn cell(value: CoreValue) -> String {
+	match value {
+		CoreValue::None | CoreValue::Null => String::new(),
+		CoreValue::Strand(v) => v.0,
+		v => v.to_string(),
+	}
+}
+
+fn csv(cell: String) -> String {
+	if cell.contains([',', '"', '\n', '\r']) {
+		format!("\"{}\"", cell.replace('"', "\"\""))
+	} else {
+		cell
+	}
+}
+
+/// Converts a page of records into columns of the Parquet schema
+#[cfg(feature = "export-parquet")]
+fn batch(schema: SchemaRef, columns: &[Column], rows: &[CoreValue]) -> Result<RecordBatch, Error> {
+	let arrays = columns
+		.iter()
+		.map(|c| {
+			let values = rows.iter().map(|row| field(row, &c.name));
+			let array: ArrayRef = match c.kind {
+				ColumnType::Int => Arc::new(Int64Array::from_iter(values.map(|v| match v {
+					CoreValue::Number(Number::Int(v)) => Some(v),
+					_ => None,
+				}))),
+				ColumnType::Float => Arc::new(Float64Array::from_iter(values.map(|v| match v {
+					CoreValue::Number(v) => Some(v.to_float()),
+					_ => None,
+				}))),
+				ColumnType::Bool => Arc::new(BooleanArray::from_iter(values.map(|v| match v {
+					CoreValue::Bool(v) => Some(v),
+					_ => None,
+				}))),
+				ColumnType::String => Arc::new(StringArray::from_iter(values.map(|v| match v {
+					CoreValue::None | CoreValue::Null => None,
+					v => Some(cell(v)),
+				}))),
+			};
+			array
+		})
+		.collect();
+	Ok(RecordBatch::try_new(schema, arrays)?)
+}
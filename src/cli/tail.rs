@@ -0,0 +1,65 @@
+use crate::cli::abstraction::{
+	connect_to_database, AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+};
+use crate::err::Error;
+use clap::Args;
+use futures::StreamExt;
+use surrealdb::sql::{Statement, Uuid as CoreUuid, Value as CoreValue};
+use surrealdb::{Notification, Value};
+
+#[derive(Args, Debug)]
+pub struct TailCommandArguments {
+	#[arg(help = "The LIVE SELECT query to follow")]
+	#[arg(index = 1, num_args = 1.., required = true)]
+	query: Vec<String>,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+}
+
+pub async fn init(
+	TailCommandArguments {
+		query,
+		conn,
+		auth,
+		sel,
+	}: TailCommandArguments,
+) -> Result<(), Error> {
+	let query = query.join(" ");
+	// Check that the query starts a live query
+	let statements =
+		surrealdb::syn::parse(&query).map_err(|e| Error::Other(format!("Invalid query: {e}")))?;
+	if !statements.iter().any(|s| matches!(s, Statement::Live(_))) {
+		return Err(Error::Other("The query does not contain a LIVE SELECT statement".into()));
+	}
+	let client = connect_to_database(&conn.endpoint, &auth, &sel).await?;
+	let mut response = client.query(query).await?.check()?;
+	let mut stream = response.stream::<Value>(())?;
+	// Print the notifications until the user types CTRL-C
+	loop {
+		let notification = tokio::select! {
+			notification = stream.next() => notification,
+			_ = tokio::signal::ctrl_c() => break,
+		};
+		let Some(Notification {
+			query_id,
+			action,
+			data,
+			..
+		}) = notification
+		else {
+			break;
+		};
+		let value = CoreValue::from(map! {
+			String::from("id") => CoreValue::from(CoreUuid::from(query_id)),
+			String::from("action") => format!("{action:?}").to_ascii_uppercase().into(),
+			String::from("result") => data.into_inner(),
+		});
+		println!("{}", value.into_json());
+	}
+	// Everything OK
+	Ok(())
+}
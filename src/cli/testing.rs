@@ -0,0 +1,71 @@
+use crate::cli::abstraction::{
+	connect_to, AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+};
+use crate::err::Error;
+use clap::Args;
+use serde::Deserialize;
+use surrealdb::sql::Ident;
+
+#[derive(Args, Debug)]
+pub struct TestCommandArguments {
+	#[arg(help = "The name of a single test to run, instead of every test in the database")]
+	#[arg(index = 1)]
+	name: Option<String>,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+}
+
+#[derive(Debug, Deserialize)]
+struct TestResult {
+	name: String,
+	passed: bool,
+	error: Option<String>,
+}
+
+pub async fn init(
+	TestCommandArguments {
+		name,
+		conn: DatabaseConnectionArguments {
+			endpoint,
+		},
+		auth,
+		sel: DatabaseSelectionArguments {
+			namespace,
+			database,
+		},
+	}: TestCommandArguments,
+) -> Result<(), Error> {
+	let client =
+		connect_to(&endpoint, &auth, Some(namespace.as_str()), Some(database.as_str())).await?;
+
+	// Use the specified namespace / database
+	client.use_ns(namespace).use_db(database).await?;
+
+	// Run the tests, each of which is rolled back once finished
+	debug!("Running the tests defined in the database");
+	let results: Vec<TestResult> = match name {
+		Some(name) => client.query(format!("RUN TEST {}", Ident::from(name))),
+		None => client.query("RUN TESTS"),
+	}
+	.await?
+	.take(0)?;
+	// Output the result of each test
+	let failed = results.iter().filter(|v| !v.passed).count();
+	for result in results.iter() {
+		match &result.error {
+			None => println!("test {} ... ok", result.name),
+			Some(e) => println!("test {} ... FAILED\n\t{e}", result.name),
+		}
+	}
+	println!("\n{} passed; {failed} failed", results.len() - failed);
+	// Fail if any of the tests failed
+	if failed > 0 {
+		return Err(Error::Other(format!("{failed} of {} tests failed", results.len())));
+	}
+	// Everything OK
+	Ok(())
+}
@@ -0,0 +1,136 @@
+use crate::cli::abstraction::{AuthArguments, DatabaseConnectionArguments};
+use crate::err::Error;
+use clap::Args;
+use reqwest::{Client, RequestBuilder};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::IsTerminal;
+use std::time::{Duration, Instant};
+
+/// Clears the terminal, and moves the cursor to the top left
+const CLEAR: &str = "\x1b[2J\x1b[H";
+
+#[derive(Args, Debug)]
+pub struct TopCommandArguments {
+	#[arg(help = "How often the statistics are refreshed")]
+	#[arg(long, default_value = "1s", value_parser = super::validator::duration)]
+	interval: Duration,
+	#[arg(help = "Exit after refreshing the statistics this many times")]
+	#[arg(long)]
+	count: Option<u64>,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+}
+
+/// The statistics which the server reports at its `/stats` endpoint
+#[derive(Debug, Deserialize)]
+struct Stats {
+	queries: u64,
+	conflicts: u64,
+	connections: usize,
+	live_queries: usize,
+	namespaces: BTreeMap<String, u64>,
+}
+
+pub async fn init(
+	TopCommandArguments {
+		interval,
+		count,
+		conn: DatabaseConnectionArguments {
+			endpoint,
+		},
+		auth: AuthArguments {
+			username,
+			password,
+			token,
+			..
+		},
+	}: TopCommandArguments,
+) -> Result<(), Error> {
+	let url = stats_url(&endpoint)?;
+	let client = Client::new();
+	// The statistics can only be viewed by root users
+	let request = || match (&username, &password, &token) {
+		(Some(user), Some(pass), _) => client.get(&url).basic_auth(user, Some(pass)),
+		(_, _, Some(token)) => client.get(&url).bearer_auth(token),
+		_ => client.get(&url),
+	};
+	// The dashboard is redrawn in place when it is shown in a terminal
+	let terminal = std::io::stdout().is_terminal();
+	let mut last: Option<(Instant, Stats)> = None;
+	let mut refreshes = 0;
+	loop {
+		let stats = fetch(request()).await?;
+		let now = Instant::now();
+		// The rates are measured between the last two refreshes
+		let rate = |counter: fn(&Stats) -> u64| match &last {
+			Some((then, previous)) => {
+				let elapsed = now.duration_since(*then).as_secs_f64();
+				format!("{:.1}", counter(&stats).saturating_sub(counter(previous)) as f64 / elapsed)
+			}
+			None => "-".to_owned(),
+		};
+		let mut output = String::new();
+		if terminal {
+			output.push_str(CLEAR);
+		}
+		let _ = writeln!(output, "SurrealDB at {endpoint}, refreshed every {interval:?}\n");
+		let _ = writeln!(output, "{:<24}{}", "Queries per second", rate(|s| s.queries));
+		let _ = writeln!(output, "{:<24}{}", "Connections", stats.connections);
+		let _ = writeln!(output, "{:<24}{}", "Live queries", stats.live_queries);
+		let _ = writeln!(
+			output,
+			"{:<24}{} ({} per second)",
+			"Transaction conflicts",
+			stats.conflicts,
+			rate(|s| s.conflicts)
+		);
+		let _ = writeln!(output, "\n{:<24}Storage", "Namespace");
+		for (ns, size) in &stats.namespaces {
+			let _ = writeln!(output, "{ns:<24}{}", super::compact::size(*size));
+		}
+		println!("{output}");
+		last = Some((now, stats));
+		refreshes += 1;
+		if count.is_some_and(|count| refreshes >= count) {
+			break;
+		}
+		// Refresh until the user types CTRL-C
+		tokio::select! {
+			_ = tokio::time::sleep(interval) => (),
+			_ = tokio::signal::ctrl_c() => break,
+		}
+	}
+	// Everything OK
+	Ok(())
+}
+
+/// The URL of the statistics endpoint of a remote server
+fn stats_url(endpoint: &str) -> Result<String, Error> {
+	let url = match endpoint.split_once("://") {
+		Some(("http" | "https", _)) => endpoint.to_owned(),
+		Some(("ws", rest)) => format!("http://{rest}"),
+		Some(("wss", rest)) => format!("https://{rest}"),
+		_ => {
+			return Err(Error::Other(format!(
+				"The statistics of {endpoint} can not be shown, as it is not a remote server"
+			)))
+		}
+	};
+	Ok(format!("{}/stats", url.trim_end_matches('/').trim_end_matches("/rpc")))
+}
+
+async fn fetch(request: RequestBuilder) -> Result<Stats, Error> {
+	let response = request.send().await?;
+	let status = response.status();
+	if !status.is_success() {
+		return Err(Error::Other(format!(
+			"Unable to fetch the statistics of the server, which responded with {status}: {}",
+			response.text().await.unwrap_or_default()
+		)));
+	}
+	Ok(serde_json::from_slice(&response.bytes().await?)?)
+}
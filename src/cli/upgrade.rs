@@ -2,13 +2,18 @@ use crate::cli::version_client;
 use crate::cli::version_client::VersionClient;
 use crate::cnf::PKG_VERSION;
 use crate::err::Error;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
 use clap::Args;
+use reqwest::Client;
+use ring::signature::{UnparsedPublicKey, ED25519};
 use semver::{Comparator, Op, Version};
+use sha2::{Digest, Sha256};
 use std::borrow::Cow;
 use std::fs;
 use std::io::{Error as IoError, ErrorKind};
 use std::ops::Deref;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use surrealdb::env::{arch, os};
 
@@ -18,6 +23,9 @@ const BETA: &str = "beta";
 const LATEST: &str = "latest";
 const NIGHTLY: &str = "nightly";
 
+/// The base64 encoded Ed25519 public key which releases are signed with, pinned when building
+const RELEASE_PUBLIC_KEY: Option<&str> = option_env!("SURREAL_RELEASE_PUBLIC_KEY");
+
 #[derive(Args, Debug)]
 pub struct UpgradeCommandArguments {
 	/// Install the latest nightly version
@@ -32,17 +40,56 @@ pub struct UpgradeCommandArguments {
 	/// Install a specific version
 	#[arg(long, conflicts_with = "nightly", conflicts_with = "alpha", conflicts_with = "beta")]
 	version: Option<String>,
+	/// Restore the executable which was replaced by the last upgrade
+	#[arg(
+		long,
+		conflicts_with = "nightly",
+		conflicts_with = "alpha",
+		conflicts_with = "beta",
+		conflicts_with = "version"
+	)]
+	rollback: bool,
+	/// Install from a release archive or executable on this machine, instead of downloading it
+	#[arg(
+		long,
+		value_name = "PATH",
+		conflicts_with = "nightly",
+		conflicts_with = "alpha",
+		conflicts_with = "beta",
+		conflicts_with = "version",
+		conflicts_with = "rollback"
+	)]
+	from: Option<PathBuf>,
 	/// Don't actually replace the executable
 	#[arg(long)]
 	dry_run: bool,
+	/// Don't verify the checksum and signature of the download
+	#[arg(long)]
+	no_verify: bool,
+	/// Connect through this proxy, like http://proxy:3128, instead of HTTPS_PROXY
+	#[arg(long, value_name = "URL")]
+	proxy: Option<String>,
+	/// The username for the proxy, or @FILE to read it from a file
+	#[arg(long, env = "SURREAL_PROXY_USER", requires_all = ["proxy", "proxy_pass"])]
+	#[arg(value_parser = super::validator::secret)]
+	proxy_user: Option<String>,
+	/// The password for the proxy, or @FILE to read it from a file
+	#[arg(long, env = "SURREAL_PROXY_PASS", requires_all = ["proxy", "proxy_user"])]
+	#[arg(value_parser = super::validator::secret)]
+	proxy_pass: Option<String>,
 }
 
 impl UpgradeCommandArguments {
+	/// Get the username and password for the proxy, if given
+	fn proxy_auth(&self) -> Option<(&str, &str)> {
+		self.proxy_user.as_deref().zip(self.proxy_pass.as_deref())
+	}
+
 	/// Get the version string to download based on the user preference
 	async fn version(&self) -> Result<Cow<'_, str>, Error> {
 		// Convert the version to lowercase, if supplied
 		let version = self.version.as_deref().map(str::to_ascii_lowercase);
-		let client = version_client::new(None)?;
+		let client = version_client::new(None, self.proxy.as_deref(), self.proxy_auth())?;
 
 		if self.nightly || version.as_deref() == Some(NIGHTLY) {
 			Ok(Cow::Borrowed(NIGHTLY))
@@ -109,69 +156,91 @@ pub async fn init(args: UpgradeCommandArguments) -> Result<(), Error> {
 		)));
 	}
 
-	// Compare old and new versions
-	let old_version = PKG_VERSION.deref().clone();
-	let new_version = args.version().await?;
-
-	// Parsed version numbers follow semver format (major.minor.patch)
-	if new_version != NIGHTLY && new_version != ALPHA && new_version != BETA {
-		let old_version_parsed = parse_version(&old_version)?;
-		let new_version_parsed = parse_version(&new_version)?;
-
-		if old_version_parsed == new_version_parsed {
-			println!("{old_version} is already installed");
-			return Ok(());
+	// Swap back to the executable kept by the last upgrade
+	if args.rollback {
+		let previous = previous_exe(&exe);
+		if !previous.exists() {
+			return Err(Error::Io(IoError::new(
+				ErrorKind::NotFound,
+				format!("no previous executable found at {}", previous.display()),
+			)));
+		}
+		println!("restoring {}", previous.display());
+		if args.dry_run {
+			println!("Dry run successfully completed")
+		} else {
+			rollback_exe(&exe)?;
+			println!("SurrealDB successfully rolled back");
 		}
+		return Ok(());
 	}
 
-	let arch = arch();
-	let os = os();
+	// The key which the download is verified with, unless verification is disabled
+	let key = release_key(args.no_verify)?;
 
-	println!("current version is {old_version} for {os} on {arch}",);
+	let (binary, filename) = match args.from.as_deref() {
+		// Install from an archive which was copied to this machine, for air-gapped environments
+		Some(from) => read_local(from, key.as_deref())?,
+		None => {
+			// Compare old and new versions
+			let old_version = PKG_VERSION.deref().clone();
+			let new_version = args.version().await?;
 
-	let download_arch = match arch {
-		"aarch64" => "arm64",
-		"x86_64" => "amd64",
-		_ => {
-			return Err(Error::Io(IoError::new(
-				ErrorKind::Unsupported,
-				format!("unsupported arch {arch}"),
-			)));
-		}
-	};
+			// Parsed version numbers follow semver format (major.minor.patch)
+			if new_version != NIGHTLY && new_version != ALPHA && new_version != BETA {
+				let old_version_parsed = parse_version(&old_version)?;
+				let new_version_parsed = parse_version(&new_version)?;
 
-	let (download_os, download_ext) = match os {
-		"linux" => ("linux", "tgz"),
-		"macos" => ("darwin", "tgz"),
-		"windows" => ("windows", "exe"),
-		_ => {
-			return Err(Error::Io(IoError::new(
-				ErrorKind::Unsupported,
-				format!("unsupported OS {os}"),
-			)));
-		}
-	};
+				if old_version_parsed == new_version_parsed {
+					println!("{old_version} is already installed");
+					return Ok(());
+				}
+			}
 
-	println!("downloading {new_version} for {download_os} on {download_arch}");
+			let arch = arch();
+			let os = os();
 
-	let download_filename =
-		format!("surreal-{new_version}.{download_os}-{download_arch}.{download_ext}");
-	let url = format!("{ROOT}/{new_version}/{download_filename}");
+			println!("current version is {old_version} for {os} on {arch}",);
 
-	let response = reqwest::get(&url).await?;
+			let download_arch = match arch {
+				"aarch64" => "arm64",
+				"x86_64" => "amd64",
+				_ => {
+					return Err(Error::Io(IoError::new(
+						ErrorKind::Unsupported,
+						format!("unsupported arch {arch}"),
+					)));
+				}
+			};
 
-	if !response.status().is_success() {
-		return Err(Error::Io(IoError::new(
-			ErrorKind::Other,
-			format!("received status {} when downloading from {url}", response.status()),
-		)));
-	}
+			let (download_os, download_ext) = match os {
+				"linux" => ("linux", "tgz"),
+				"macos" => ("darwin", "tgz"),
+				"windows" => ("windows", "exe"),
+				_ => {
+					return Err(Error::Io(IoError::new(
+						ErrorKind::Unsupported,
+						format!("unsupported OS {os}"),
+					)));
+				}
+			};
+
+			println!("downloading {new_version} for {download_os} on {download_arch}");
+
+			let download_filename =
+				format!("surreal-{new_version}.{download_os}-{download_arch}.{download_ext}");
 
-	let binary = response.bytes().await?;
+			let client = version_client::client(None, args.proxy.as_deref(), args.proxy_auth())?;
+			let binary =
+				download(&client, ROOT, &new_version, &download_filename, key.as_deref()).await?;
+
+			(binary, download_filename)
+		}
+	};
 
 	// Create a temporary file path
 	let tmp_dir = tempfile::tempdir()?;
-	let mut tmp_path = tmp_dir.path().join(download_filename);
+	let mut tmp_path = tmp_dir.path().join(&filename);
 
 	// Download to a temp file to avoid writing to a running exe file
 	fs::write(&tmp_path, &*binary)?;
@@ -180,7 +249,7 @@ pub async fn init(args: UpgradeCommandArguments) -> Result<(), Error> {
 	fs::set_permissions(&tmp_path, permissions)?;
 
 	// Unarchive
-	if download_ext == "tgz" {
+	if filename.ends_with(".tgz") || filename.ends_with(".tar.gz") {
 		let output = Command::new("tar")
 			.arg("-zxf")
 			.arg(&tmp_path)
@@ -212,18 +281,206 @@ pub async fn init(args: UpgradeCommandArguments) -> Result<(), Error> {
 	Ok(())
 }
 
-/// Replace exe at `to` with contents of `from`
-fn replace_exe(from: &Path, to: &Path) -> Result<(), IoError> {
+/// Read a release archive or executable from this machine, returning its contents and file name
+///
+/// Unless verification is disabled, the archive is checked against the checksum and signature
+/// files published alongside it, which are expected next to the archive with the `.txt` and
+/// `.sig` extensions.
+fn read_local(path: &Path, key: Option<&[u8]>) -> Result<(Vec<u8>, String), Error> {
+	let filename = match path.file_name() {
+		Some(name) => name.to_string_lossy().into_owned(),
+		None => {
+			return Err(Error::Io(IoError::new(
+				ErrorKind::InvalidInput,
+				format!("{} is not a file", path.display()),
+			)));
+		}
+	};
+
+	println!("reading {}", path.display());
+
+	let binary = fs::read(path)?;
+
+	if let Some(key) = key {
+		let name = release_name(&filename);
+		let read = |ext: &str| {
+			let file = path.with_file_name(format!("{name}.{ext}"));
+			fs::read_to_string(&file).map_err(|e| {
+				Error::Io(IoError::new(
+					e.kind(),
+					format!(
+						"unable to read {}: {e}; copy it next to the archive, or use --no-verify",
+						file.display()
+					),
+				))
+			})
+		};
+		verify(&read("txt")?, &binary)?;
+		verify_signature(key, &read("sig")?, &binary)?;
+	}
+
+	Ok((binary, filename))
+}
+
+/// Download a release file, verifying it against its published checksum and signature
+/// with the given public key, unless verification is disabled
+pub(crate) async fn download(
+	client: &Client,
+	root: &str,
+	version: &str,
+	filename: &str,
+	key: Option<&[u8]>,
+) -> Result<Vec<u8>, Error> {
+	let binary = fetch(client, &format!("{root}/{version}/{filename}")).await?.bytes().await?;
+
+	if let Some(key) = key {
+		let name = release_name(filename);
+		let checksum = fetch(client, &format!("{root}/{version}/{name}.txt")).await?;
+		verify(&checksum.text().await?, &binary)?;
+		let signature = fetch(client, &format!("{root}/{version}/{name}.sig")).await?;
+		verify_signature(key, &signature.text().await?, &binary)?;
+	}
+
+	Ok(binary.to_vec())
+}
+
+/// Request a file, failing unless it is found
+async fn fetch(client: &Client, url: &str) -> Result<reqwest::Response, Error> {
+	let response = client.get(url).send().await?;
+
+	if !response.status().is_success() {
+		return Err(Error::Io(IoError::new(
+			ErrorKind::Other,
+			format!("received status {} when downloading from {url}", response.status()),
+		)));
+	}
+
+	Ok(response)
+}
+
+/// The name of a release file, without its extension
+fn release_name(filename: &str) -> &str {
+	[".tar.gz", ".tgz", ".exe"]
+		.iter()
+		.find_map(|ext| filename.strip_suffix(ext))
+		.unwrap_or(filename)
+}
+
+/// The pinned public key which releases are verified with, unless verification is disabled
+fn release_key(no_verify: bool) -> Result<Option<Vec<u8>>, Error> {
+	if no_verify {
+		println!("skipping checksum and signature verification");
+		return Ok(None);
+	}
+	let Some(key) = RELEASE_PUBLIC_KEY else {
+		return Err(Error::Io(IoError::new(
+			ErrorKind::Unsupported,
+			"this build has no pinned release key to verify the download with; use --no-verify to skip verification",
+		)));
+	};
+	let key = STANDARD.decode(key).map_err(|e| {
+		Error::Io(IoError::new(
+			ErrorKind::InvalidData,
+			format!("the pinned release key is invalid: {e}"),
+		))
+	})?;
+	Ok(Some(key))
+}
+
+/// Verify that the SHA-256 checksum of the binary matches the expected one
+fn verify(expected: &str, binary: &[u8]) -> Result<(), Error> {
+	let expected = expected.split_whitespace().next().unwrap_or_default();
+	let actual = format!("{:x}", Sha256::digest(binary));
+
+	if !actual.eq_ignore_ascii_case(expected) {
+		return Err(Error::Io(IoError::new(
+			ErrorKind::InvalidData,
+			format!("checksum mismatch: expected {expected} but the download has {actual}"),
+		)));
+	}
+
+	println!("verified checksum {actual}");
+	Ok(())
+}
+
+/// Verify the detached Ed25519 signature of the binary, which is base64 encoded
+fn verify_signature(key: &[u8], signature: &str, binary: &[u8]) -> Result<(), Error> {
+	let invalid = || {
+		Error::Io(IoError::new(
+			ErrorKind::InvalidData,
+			"the signature of the download does not match the pinned release key",
+		))
+	};
+	let signature = STANDARD.decode(signature.trim()).map_err(|_| invalid())?;
+	UnparsedPublicKey::new(&ED25519, key).verify(binary, &signature).map_err(|_| invalid())?;
+
+	println!("verified signature");
+	Ok(())
+}
+
+/// The path which the executable replaced by an upgrade is kept at
+pub(crate) fn previous_exe(exe: &Path) -> PathBuf {
 	if cfg!(windows) {
-		fs::rename(to, to.with_extension("old.exe"))?;
+		exe.with_extension("old.exe")
+	} else {
+		exe.with_extension("old")
+	}
+}
+
+/// Replace exe at `to` with contents of `from`, keeping the replaced exe for a rollback
+///
+/// The new exe and the backup are copied next to `to` and flushed to disk before they are
+/// renamed into place, so a failure or a crash leaves a complete exe at both paths.
+pub(crate) fn replace_exe(from: &Path, to: &Path) -> Result<(), IoError> {
+	let previous = previous_exe(to);
+	let staged = stage(from, to)?;
+	let res = if cfg!(windows) {
+		// A running exe can't be overwritten on Windows, but it can be renamed
+		fs::rename(to, &previous).and_then(|_| {
+			fs::rename(&staged, to).or_else(|e| {
+				fs::rename(&previous, to)?;
+				Err(e)
+			})
+		})
 	} else {
-		fs::remove_file(to)?;
-	}
-	// Rename works when from and to are on the same file system/device, but
-	// fall back to copy if they're not
-	fs::rename(from, to).or_else(|_| {
-		// Don't worry about deleting the file as the tmp directory will
-		// be deleted automatically
-		fs::copy(from, to).map(|_| ())
-	})
+		// Otherwise the current exe stays in place until it is atomically replaced
+		stage(to, &previous)
+			.and_then(|backup| fs::rename(backup, &previous))
+			.and_then(|_| fs::rename(&staged, to))
+	};
+	if res.is_err() {
+		let _ = fs::remove_file(&staged);
+	}
+	res?;
+	sync_dir(to)
+}
+
+/// Swap the exe at `exe` with the one kept by the last upgrade
+///
+/// The current exe is kept in turn, so the rollback can be undone by rolling back again.
+pub(crate) fn rollback_exe(exe: &Path) -> Result<(), IoError> {
+	replace_exe(&previous_exe(exe), exe)
+}
+
+/// Copy `from` into a file next to `path` and flush it to disk, ready to be renamed to `path`
+fn stage(from: &Path, path: &Path) -> Result<PathBuf, IoError> {
+	let mut staged = path.as_os_str().to_owned();
+	staged.push(".tmp");
+	let staged = PathBuf::from(staged);
+	// Copying keeps the permissions of the source file
+	fs::copy(from, &staged)?;
+	fs::OpenOptions::new().write(true).open(&staged)?.sync_all()?;
+	Ok(staged)
+}
+
+/// Flush the renames in the directory containing `path` to disk
+fn sync_dir(path: &Path) -> Result<(), IoError> {
+	// Directories can't be opened as files on Windows
+	#[cfg(unix)]
+	if let Some(dir) = path.parent().filter(|v| !v.as_os_str().is_empty()) {
+		fs::File::open(dir)?.sync_all()?;
+	}
+	#[cfg(not(unix))]
+	let _ = path;
+	Ok(())
 }
@@ -0,0 +1,266 @@
+use crate::cli::abstraction::auth::CredentialsLevel;
+use crate::cli::abstraction::{
+	self, AuthArguments, DatabaseConnectionArguments, LevelSelectionArguments,
+};
+use crate::cli::validator::parser::creds_level::CredentialsLevelParser;
+use crate::err::Error;
+use clap::{Args, Subcommand};
+use std::io::BufRead;
+use std::path::PathBuf;
+use surrealdb::engine::any::Any;
+use surrealdb::sql::{Ident, Strand, Value as CoreValue};
+use surrealdb::Surreal;
+
+#[derive(Debug, Subcommand)]
+pub enum UserCommand {
+	#[command(about = "Create a system user")]
+	Create(CreateCommandArguments),
+	#[command(about = "Change the password of a system user")]
+	Passwd(PasswdCommandArguments),
+	#[command(about = "List the system users")]
+	List(ListCommandArguments),
+	#[command(about = "Delete a system user")]
+	Delete(DeleteCommandArguments),
+}
+
+#[derive(Args, Debug)]
+pub struct UserArguments {
+	#[arg(help = "Level on which the user is defined")]
+	#[arg(long, default_value = "root")]
+	#[arg(value_parser = CredentialsLevelParser::new())]
+	level: CredentialsLevel,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: LevelSelectionArguments,
+}
+
+#[derive(Args, Debug)]
+pub struct PasswordArguments {
+	#[arg(help = "Read the password of the user from this file, instead of from stdin")]
+	#[arg(long, value_parser = super::validator::file_exists)]
+	password_file: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+pub struct CreateCommandArguments {
+	#[arg(help = "The name of the user")]
+	#[arg(index = 1)]
+	name: String,
+	#[arg(help = "The roles of the user")]
+	#[arg(long, value_delimiter = ',', default_value = "viewer")]
+	roles: Vec<String>,
+	#[command(flatten)]
+	password: PasswordArguments,
+	#[command(flatten)]
+	user: UserArguments,
+}
+
+#[derive(Args, Debug)]
+pub struct PasswdCommandArguments {
+	#[arg(help = "The name of the user")]
+	#[arg(index = 1)]
+	name: String,
+	#[command(flatten)]
+	password: PasswordArguments,
+	#[command(flatten)]
+	user: UserArguments,
+}
+
+#[derive(Args, Debug)]
+pub struct ListCommandArguments {
+	#[command(flatten)]
+	user: UserArguments,
+}
+
+#[derive(Args, Debug)]
+pub struct DeleteCommandArguments {
+	#[arg(help = "The name of the user")]
+	#[arg(index = 1)]
+	name: String,
+	#[command(flatten)]
+	user: UserArguments,
+}
+
+pub async fn init(command: UserCommand) -> Result<(), Error> {
+	match command {
+		UserCommand::Create(args) => create(args).await,
+		UserCommand::Passwd(args) => passwd(args).await,
+		UserCommand::List(args) => list(args).await,
+		UserCommand::Delete(args) => delete(args).await,
+	}
+}
+
+async fn create(
+	CreateCommandArguments {
+		name,
+		roles,
+		password,
+		user,
+	}: CreateCommandArguments,
+) -> Result<(), Error> {
+	let password = read_password(password)?;
+	let (client, base) = connect_to(user).await?;
+	let roles: Vec<String> = roles.iter().map(|r| Ident::from(r.as_str()).to_string()).collect();
+	let sql = format!(
+		"DEFINE USER {} ON {base} PASSWORD {} ROLES {}",
+		Ident::from(name.as_str()),
+		Strand::from(password),
+		roles.join(", ")
+	);
+	client.query(sql).await?.check()?;
+	println!("Created the user '{name}' on {base}");
+	// Everything OK
+	Ok(())
+}
+
+async fn passwd(
+	PasswdCommandArguments {
+		name,
+		password,
+		user,
+	}: PasswdCommandArguments,
+) -> Result<(), Error> {
+	let password = read_password(password)?;
+	let (client, base) = connect_to(user).await?;
+	let ident = Ident::from(name.as_str());
+	// Redefine the user with the new password, keeping the rest of its definition
+	let sql = format!("INFO FOR USER {ident} ON {base} STRUCTURE");
+	let info: surrealdb::Value = client.query(sql).await?.take(0)?;
+	let CoreValue::Object(info) = info.into_inner() else {
+		return Err(Error::Other(format!("The user '{name}' does not exist on {base}")));
+	};
+	let roles = match info.get("roles") {
+		Some(CoreValue::Array(roles)) => {
+			roles.iter().map(|r| Ident::from(r.clone().as_raw_string()).to_string()).collect()
+		}
+		_ => Vec::new(),
+	};
+	let duration = |kind: &str| match info.get("duration") {
+		Some(CoreValue::Object(duration)) => match duration.get(kind) {
+			Some(CoreValue::Duration(duration)) => duration.to_string(),
+			_ => "NONE".to_owned(),
+		},
+		_ => "NONE".to_owned(),
+	};
+	let mut sql = format!(
+		"DEFINE USER OVERWRITE {ident} ON {base} PASSWORD {} ROLES {} DURATION FOR TOKEN {}, FOR SESSION {}",
+		Strand::from(password),
+		roles.join(", "),
+		duration("token"),
+		duration("session"),
+	);
+	if let Some(CoreValue::Strand(comment)) = info.get("comment") {
+		sql.push_str(&format!(" COMMENT {comment}"));
+	}
+	client.query(sql).await?.check()?;
+	println!("Changed the password of the user '{name}' on {base}");
+	// Everything OK
+	Ok(())
+}
+
+async fn list(
+	ListCommandArguments {
+		user,
+	}: ListCommandArguments,
+) -> Result<(), Error> {
+	let (client, base) = connect_to(user).await?;
+	let info: surrealdb::Value =
+		client.query(format!("INFO FOR {base} STRUCTURE")).await?.take(0)?;
+	let CoreValue::Object(mut info) = info.into_inner() else {
+		return Ok(());
+	};
+	let Some(CoreValue::Array(users)) = info.remove("users") else {
+		return Ok(());
+	};
+	for user in users {
+		let CoreValue::Object(user) = user else {
+			continue;
+		};
+		let name = user.get("name").cloned().map(CoreValue::as_raw_string).unwrap_or_default();
+		let roles: Vec<String> = match user.get("roles") {
+			Some(CoreValue::Array(roles)) => {
+				roles.iter().map(|r| r.clone().as_raw_string().to_lowercase()).collect()
+			}
+			_ => Vec::new(),
+		};
+		println!("{name} {}", roles.join(","));
+	}
+	// Everything OK
+	Ok(())
+}
+
+async fn delete(
+	DeleteCommandArguments {
+		name,
+		user,
+	}: DeleteCommandArguments,
+) -> Result<(), Error> {
+	let (client, base) = connect_to(user).await?;
+	client.query(format!("REMOVE USER {} ON {base}", Ident::from(name.as_str()))).await?.check()?;
+	println!("Deleted the user '{name}' on {base}");
+	// Everything OK
+	Ok(())
+}
+
+/// Reads the password from the file, or from the first line of stdin
+fn read_password(
+	PasswordArguments {
+		password_file,
+	}: PasswordArguments,
+) -> Result<String, Error> {
+	let password = match password_file {
+		Some(file) => std::fs::read_to_string(file)?,
+		None => {
+			let mut line = String::new();
+			std::io::stdin().lock().read_line(&mut line)?;
+			line
+		}
+	};
+	let password = password.trim_end_matches(['\r', '\n']);
+	if password.is_empty() {
+		return Err(Error::Other("The password of the user can not be empty".into()));
+	}
+	Ok(password.to_owned())
+}
+
+/// Connects to the database, returning the base which the users are defined on
+async fn connect_to(
+	UserArguments {
+		level,
+		conn: DatabaseConnectionArguments {
+			endpoint,
+		},
+		auth,
+		sel: LevelSelectionArguments {
+			namespace,
+			database,
+		},
+	}: UserArguments,
+) -> Result<(Surreal<Any>, &'static str), Error> {
+	let base = match (level, &namespace, &database) {
+		(CredentialsLevel::Root, _, _) => "ROOT",
+		(CredentialsLevel::Namespace, Some(_), _) => "NAMESPACE",
+		(CredentialsLevel::Database, Some(_), Some(_)) => "DATABASE",
+		(CredentialsLevel::Namespace, None, _) => {
+			return Err(Error::Other("Namespace users require a namespace to be selected".into()))
+		}
+		(CredentialsLevel::Database, _, _) => {
+			return Err(Error::Other(
+				"Database users require a namespace and database to be selected".into(),
+			))
+		}
+	};
+	let client =
+		abstraction::connect_to(&endpoint, &auth, namespace.as_deref(), database.as_deref())
+			.await?;
+	// Use the specified namespace / database
+	match (namespace, database) {
+		(Some(ns), Some(db)) => client.use_ns(ns).use_db(db).await?,
+		(Some(ns), None) => client.use_ns(ns).await?,
+		_ => (),
+	}
+	Ok((client, base))
+}
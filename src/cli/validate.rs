@@ -1,19 +1,26 @@
+use crate::dbs::DbsCapabilities;
 use crate::err::Error;
 use clap::Args;
 use glob::glob;
+use std::collections::BTreeSet;
 use std::io::{Error as IoError, ErrorKind};
-use surrealdb::sql::parse;
+use surrealdb::sql::statements::DefineStatement;
+use surrealdb::sql::Statement;
+use surrealdb::syn::{functions, parse_with_capabilities};
 
 #[derive(Args, Debug)]
 pub struct ValidateCommandArguments {
 	#[arg(help = "Glob pattern for the files to validate")]
 	#[arg(default_value = "**/*.surql")]
 	patterns: Vec<String>,
+	#[command(flatten)]
+	capabilities: DbsCapabilities,
 }
 
 pub async fn init(args: ValidateCommandArguments) -> Result<(), Error> {
 	let ValidateCommandArguments {
 		patterns,
+		capabilities,
 	} = args;
 
 	let mut entries = vec![];
@@ -34,31 +41,69 @@ pub async fn init(args: ValidateCommandArguments) -> Result<(), Error> {
 		entries.extend(pattern_entries.flatten());
 	}
 
-	let mut has_entries = false;
+	if entries.is_empty() {
+		eprintln!("No files found");
+		return Err(Error::Io(IoError::new(ErrorKind::NotFound, "No files found".to_string())));
+	}
+
+	let capabilities = capabilities.into_cli_capabilities();
+	let mut files = Vec::with_capacity(entries.len());
+	let mut defined = BTreeSet::new();
 
+	// Parse every file first, so functions defined in one file can be called from another
 	for entry in entries {
 		let file_content = tokio::fs::read_to_string(entry.clone()).await?;
-		let parse_result = parse(&file_content);
+		let parse_result = parse_with_capabilities(&file_content, &capabilities);
+		if let Ok(query) = &parse_result {
+			for statement in query.iter() {
+				if let Statement::Define(DefineStatement::Function(function)) = statement {
+					defined.insert(format!("fn::{}", function.name.0));
+				}
+			}
+		}
+		files.push((entry, file_content, parse_result));
+	}
 
+	let mut failed = None;
+
+	for (entry, file_content, parse_result) in files {
 		match parse_result {
 			Ok(_) => {
-				println!("{}: OK", entry.display());
+				let functions = functions(&file_content);
+				let denied: Vec<_> =
+					functions.iter().filter(|f| !capabilities.allows_function_name(f)).collect();
+				if denied.is_empty() {
+					println!("{}: OK", entry.display());
+				} else {
+					println!("{}: KO", entry.display());
+					for function in denied {
+						eprintln!("The function '{function}' is not allowed by the capabilities");
+					}
+					failed = Some(Error::Other(format!(
+						"{} calls functions which are not allowed",
+						entry.display()
+					)));
+				}
+				// Custom functions may be defined in the database already, so these are only warnings
+				for function in functions.iter().filter(|f| f.starts_with("fn::")) {
+					if !defined.contains(function) {
+						eprintln!(
+							"{}: the function '{function}' is not defined in any of the validated files",
+							entry.display()
+						);
+					}
+				}
 			}
 			Err(error) => {
 				println!("{}: KO", entry.display());
 				eprintln!("{error}");
-
-				return Err(crate::err::Error::from(error));
+				failed = Some(Error::from(error));
 			}
 		}
-
-		has_entries = true;
 	}
 
-	if !has_entries {
-		eprintln!("No files found");
-		return Err(Error::Io(IoError::new(ErrorKind::NotFound, "No files found".to_string())));
+	match failed {
+		Some(error) => Err(error),
+		None => Ok(()),
 	}
-
-	Ok(())
 }
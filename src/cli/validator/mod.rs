@@ -6,8 +6,8 @@ use std::{
 };
 
 use surrealdb::dbs::capabilities::{
-	ArbitraryQueryTarget, ExperimentalTarget, FuncTarget, MethodTarget, NetTarget, RouteTarget,
-	Targets,
+	ArbitraryQueryTarget, EnvTarget, ExperimentalTarget, FuncTarget, MethodTarget, NetTarget,
+	RouteTarget, Targets,
 };
 use surrealdb::kvs::export::TableConfig;
 
@@ -81,18 +81,114 @@ pub(crate) fn endpoint_valid(v: &str) -> Result<String, String> {
 }
 
 pub(crate) fn key_valid(v: &str) -> Result<String, String> {
+	let v = secret(v)?;
 	match v.len() {
-		16 => Ok(v.to_string()),
-		24 => Ok(v.to_string()),
-		32 => Ok(v.to_string()),
+		16 => Ok(v),
+		24 => Ok(v),
+		32 => Ok(v),
 		_ => Err(String::from("Ensure your database encryption key is 16, 24, or 32 bytes long")),
 	}
 }
 
+/// Resolves a credential, so that it doesn't need to appear in process listings or shell history
+///
+/// A value of `@/path/to/file` is read from the file, without its trailing newline, and
+/// `${NAME}` is replaced with the value of the environment variable. A leading `@@` and `$${`
+/// are escapes for a literal `@` and `${`.
+///
+/// This changes the meaning of existing credentials which start with `@` or contain `${`. Such
+/// credentials either need to be escaped, or `SURREAL_LITERAL_SECRETS=true` can be set to use
+/// every credential verbatim, as before.
+pub(crate) fn secret(v: &str) -> Result<String, String> {
+	if *crate::cnf::LITERAL_SECRETS {
+		return Ok(v.to_owned());
+	}
+	if let Some(v) = v.strip_prefix("@@") {
+		return Ok(format!("@{v}"));
+	}
+	if let Some(path) = v.strip_prefix('@') {
+		let secret = std::fs::read_to_string(path)
+			.map_err(|e| format!("Unable to read the secret from '{path}': {e}"))?;
+		return Ok(secret.trim_end_matches(['\r', '\n']).to_owned());
+	}
+	let mut secret = String::with_capacity(v.len());
+	let mut rest = v;
+	while let Some(i) = rest.find('$') {
+		secret.push_str(&rest[..i]);
+		rest = &rest[i..];
+		if let Some(r) = rest.strip_prefix("$${") {
+			secret.push_str("${");
+			rest = r;
+		} else if let Some(r) = rest.strip_prefix("${") {
+			let end = r.find('}').ok_or("An environment variable is missing its closing }")?;
+			let name = &r[..end];
+			let value = std::env::var(name)
+				.map_err(|_| format!("The environment variable '{name}' is not set"))?;
+			secret.push_str(&value);
+			rest = &r[end + 1..];
+		} else {
+			secret.push('$');
+			rest = &rest[1..];
+		}
+	}
+	secret.push_str(rest);
+	Ok(secret)
+}
+
 pub(crate) fn duration(v: &str) -> Result<Duration, String> {
 	surrealdb::sql::Duration::from_str(v).map(|d| d.0).map_err(|_| String::from("invalid duration"))
 }
 
+/// A table write limit, as the namespace, database, table, rate per second, and burst size
+pub(crate) type TableWriteLimit = (String, String, String, u32, u32);
+
+pub(crate) fn table_write_limit(v: &str) -> Result<TableWriteLimit, String> {
+	let err = || String::from("Provide a limit in the form ns/db/table=writes-per-second[:burst]");
+	let (path, rate) = v.split_once('=').ok_or_else(err)?;
+	let mut parts = path.split('/');
+	match (parts.next(), parts.next(), parts.next(), parts.next()) {
+		(Some(ns), Some(db), Some(tb), None) if ![ns, db, tb].contains(&"") => {
+			// The burst size defaults to one second worth of writes
+			let (rate, burst) = match rate.split_once(':') {
+				Some((rate, burst)) => {
+					let rate = rate.trim().parse().map_err(|_| err())?;
+					(rate, burst.trim().parse().map_err(|_| err())?)
+				}
+				None => {
+					let rate = rate.trim().parse().map_err(|_| err())?;
+					(rate, rate)
+				}
+			};
+			Ok((ns.to_owned(), db.to_owned(), tb.to_owned(), rate, burst))
+		}
+		_ => Err(err()),
+	}
+}
+
+pub(crate) fn variable(v: &str) -> Result<(String, String), String> {
+	let err = || String::from("Provide a variable in the form key=value");
+	let (key, value) = v.split_once('=').ok_or_else(err)?;
+	let key = key.trim().trim_start_matches('$');
+	if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+		return Err(format!("Invalid variable name '{key}', which can only contain letters, numbers and underscores"));
+	}
+	Ok((key.to_owned(), value.to_owned()))
+}
+
+pub(crate) fn env_targets(value: &str) -> Result<Targets<EnvTarget>, String> {
+	if ["*", ""].contains(&value) {
+		return Ok(Targets::All);
+	}
+
+	let mut result = HashSet::new();
+
+	for target in value.split(',').filter(|s| !s.is_empty()) {
+		result.insert(EnvTarget::from_str(target).map_err(|e| e.to_string())?);
+	}
+
+	Ok(Targets::Some(result))
+}
+
 pub(crate) fn net_targets(value: &str) -> Result<Targets<NetTarget>, String> {
 	if ["*", ""].contains(&value) {
 		return Ok(Targets::All);
@@ -236,6 +332,20 @@ mod tests {
 		assert!(net_targets("127.0.0.1,127777.0.0.1").is_err());
 	}
 
+	#[test]
+	fn test_env_targets() {
+		assert_eq!(env_targets("*").unwrap(), Targets::<EnvTarget>::All);
+		assert_eq!(env_targets("").unwrap(), Targets::<EnvTarget>::All);
+		assert_eq!(
+			env_targets("APP_ENV,HOSTNAME").unwrap(),
+			Targets::<EnvTarget>::Some(
+				vec!["APP_ENV".parse().unwrap(), "HOSTNAME".parse().unwrap()].into_iter().collect()
+			)
+		);
+
+		assert!(env_targets("APP-ENV").is_err());
+	}
+
 	#[test]
 	fn test_method_targets() {
 		assert_eq!(method_targets("*").unwrap(), Targets::<MethodTarget>::All);
@@ -254,6 +364,23 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn test_secret() {
+		assert_eq!(secret("plain").unwrap(), "plain");
+		assert_eq!(secret("@@literal").unwrap(), "@literal");
+		assert_eq!(secret("co$t").unwrap(), "co$t");
+		assert_eq!(secret("$${HOME}").unwrap(), "${HOME}");
+		assert_eq!(secret("@@${HOME}").unwrap(), "@${HOME}");
+		std::env::set_var("SURREAL_TEST_SECRET", "value");
+		assert_eq!(secret("a-${SURREAL_TEST_SECRET}-b").unwrap(), "a-value-b");
+		assert!(secret("${SURREAL_TEST_UNSET_SECRET}").is_err());
+		assert!(secret("${SURREAL_TEST_SECRET").is_err());
+		let file = tempfile::NamedTempFile::new().unwrap();
+		std::fs::write(file.path(), "from-file\n").unwrap();
+		assert_eq!(secret(&format!("@{}", file.path().display())).unwrap(), "from-file");
+		assert!(secret("@/nonexistent/secret").is_err());
+	}
+
 	#[test]
 	fn test_route_targets() {
 		assert_eq!(route_targets("*").unwrap(), Targets::<RouteTarget>::All);
@@ -269,4 +396,16 @@ mod tests {
 			)
 		);
 	}
+
+	#[test]
+	fn test_table_write_limit() {
+		let limit = |ns: &str, db: &str, tb: &str, rate, burst| {
+			(ns.to_owned(), db.to_owned(), tb.to_owned(), rate, burst)
+		};
+		assert_eq!(table_write_limit("ns/db/tb=10").unwrap(), limit("ns", "db", "tb", 10, 10));
+		assert_eq!(table_write_limit("ns/db/tb=10:50").unwrap(), limit("ns", "db", "tb", 10, 50));
+		assert!(table_write_limit("ns/db=10").is_err());
+		assert!(table_write_limit("ns/db/tb").is_err());
+		assert!(table_write_limit("ns/db/tb=10:").is_err());
+	}
 }
@@ -0,0 +1,82 @@
+use crate::err::Error;
+use clap::Args;
+use surrealdb::dbs::Session;
+use surrealdb::engine::any::IntoEndpoint;
+use surrealdb::kvs::{Datastore, LockType::*, TransactionType::*};
+use surrealdb::sql::Value as CoreValue;
+
+#[derive(Args, Debug)]
+pub struct VerifyCommandArguments {
+	#[arg(help = "Database path used for storing data")]
+	#[arg(env = "SURREAL_PATH", index = 1)]
+	#[arg(value_parser = super::validator::path_valid)]
+	path: String,
+}
+
+pub async fn init(
+	VerifyCommandArguments {
+		path,
+	}: VerifyCommandArguments,
+) -> Result<(), Error> {
+	// Clean the path
+	let endpoint = path.into_endpoint()?;
+	let path = if endpoint.path.is_empty() {
+		endpoint.url.to_string()
+	} else {
+		endpoint.path
+	};
+	let ds = Datastore::new(&path).await?;
+	// Check that every key can be decoded, and belongs to something which is defined
+	let tx = ds.transaction(Read, Optimistic).await?;
+	let verification = tx.verify().await?;
+	let mut databases = Vec::new();
+	for ns in tx.all_ns().await?.iter() {
+		for db in tx.all_db(&ns.name).await?.iter() {
+			databases.push((ns.name.to_raw(), db.name.to_raw()));
+		}
+	}
+	tx.cancel().await?;
+	println!("Scanned {} keys", verification.keys);
+	let mut problems = verification.problems.len();
+	for problem in verification.problems {
+		println!("{problem}");
+	}
+	// Check the index entries and graph edges of each database against its records
+	for (ns, db) in databases {
+		let ses = Session::owner().with_ns(&ns).with_db(&db);
+		let mut res = ds.execute("CHECK DATABASE", &ses, None).await?;
+		match res.remove(0).result {
+			Ok(CoreValue::Array(issues)) => {
+				for issue in issues {
+					let CoreValue::Object(issue) = issue else {
+						continue;
+					};
+					let field = |key: &str| {
+						issue.get(key).cloned().map(CoreValue::as_raw_string).unwrap_or_default()
+					};
+					println!(
+						"[{}] {ns}/{db} {} ({}): {}",
+						field("check"),
+						field("record"),
+						field("name"),
+						field("detail")
+					);
+					problems += 1;
+				}
+			}
+			Ok(_) => (),
+			Err(e) => {
+				println!("[check] Unable to check the database {ns}/{db}: {e}");
+				problems += 1;
+			}
+		}
+	}
+	match problems {
+		0 => {
+			println!("No problems were found");
+			Ok(())
+		}
+		1 => Err(Error::Other("A problem was found".into())),
+		n => Err(Error::Other(format!("{n} problems were found"))),
+	}
+}
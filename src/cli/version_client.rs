@@ -2,7 +2,7 @@
 // This is used in format! macro
 use crate::cli::upgrade::ROOT;
 use crate::err::Error;
-use reqwest::Client;
+use reqwest::{Client, Proxy, Url};
 use std::borrow::Cow;
 #[cfg(test)]
 use std::collections::BTreeMap;
@@ -18,15 +18,44 @@ pub(crate) struct ReqwestVersionClient {
 	client: Client,
 }
 
-pub(crate) fn new(timeout: Option<Duration>) -> Result<ReqwestVersionClient, Error> {
+pub(crate) fn new(
+	timeout: Option<Duration>,
+	proxy: Option<&str>,
+	proxy_auth: Option<(&str, &str)>,
+) -> Result<ReqwestVersionClient, Error> {
+	Ok(ReqwestVersionClient {
+		client: client(timeout, proxy, proxy_auth)?,
+	})
+}
+
+/// Build an HTTP client for reaching the release server
+///
+/// Without an explicit proxy, the `HTTPS_PROXY`, `ALL_PROXY` and `NO_PROXY` environment
+/// variables are honoured. Credentials for an explicit proxy are passed separately, and are
+/// rejected in its URL, so that they never appear in process listings or shell history.
+pub(crate) fn client(
+	timeout: Option<Duration>,
+	proxy: Option<&str>,
+	proxy_auth: Option<(&str, &str)>,
+) -> Result<Client, Error> {
 	let mut client = Client::builder();
 	if let Some(timeout) = timeout {
 		client = client.timeout(timeout);
 	}
-	let client = client.build()?;
-	Ok(ReqwestVersionClient {
-		client,
-	})
+	if let Some(url) = proxy {
+		if Url::parse(url).is_ok_and(|v| !v.username().is_empty() || v.password().is_some()) {
+			return Err(Error::Other(
+				"Provide the proxy credentials with --proxy-user and --proxy-pass, not in the URL"
+					.to_owned(),
+			));
+		}
+		let mut proxy = Proxy::all(url)?;
+		if let Some((user, pass)) = proxy_auth {
+			proxy = proxy.basic_auth(user, pass);
+		}
+		client = client.proxy(proxy);
+	}
+	Ok(client.build()?)
 }
 
 impl VersionClient for ReqwestVersionClient {
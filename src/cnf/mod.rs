@@ -64,6 +64,10 @@ pub static HTTP_MAX_SIGNIN_BODY_SIZE: LazyLock<usize> =
 pub static HTTP_MAX_IMPORT_BODY_SIZE: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_HTTP_MAX_IMPORT_BODY_SIZE", usize, 4 << 30);
 
+/// How often the TLS certificate and key files are checked for changes, in seconds (defaults to 30)
+pub static TLS_RELOAD_INTERVAL: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_TLS_RELOAD_INTERVAL", u64, 30);
+
 /// Specifies the frequency with which ping messages should be sent to the client
 pub const WEBSOCKET_PING_FREQUENCY: Duration = Duration::from_secs(5);
 
@@ -108,6 +112,10 @@ pub static RUNTIME_STACK_SIZE: LazyLock<usize> =
 pub static RUNTIME_MAX_BLOCKING_THREADS: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_RUNTIME_MAX_BLOCKING_THREADS", usize, 512);
 
+/// If set to "true" then credentials on the command line are used verbatim, without reading
+/// `@FILE` or expanding `${NAME}`, as they were before these were supported
+pub static LITERAL_SECRETS: LazyLock<bool> = lazy_env_parse!("SURREAL_LITERAL_SECRETS", bool);
+
 /// If set to "otlp" then telemetry is sent to the GRPC OTEL collector
 pub static TELEMETRY_PROVIDER: LazyLock<String> =
 	lazy_env_parse!("SURREAL_TELEMETRY_PROVIDER", String);
@@ -1,16 +1,18 @@
+use crate::cli::validator::TableWriteLimit;
 use crate::cli::CF;
 use crate::err::Error;
+use crate::telemetry::metrics::throttle::record_throttled;
 use clap::Args;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use surrealdb::dbs::capabilities::{
-	ArbitraryQueryTarget, Capabilities, ExperimentalTarget, FuncTarget, MethodTarget, NetTarget,
-	RouteTarget, Targets,
+	ArbitraryQueryTarget, Capabilities, EnvTarget, ExperimentalTarget, FuncTarget, MethodTarget,
+	NetTarget, RouteTarget, Targets,
 };
 use surrealdb::dbs::Session;
-use surrealdb::kvs::Datastore;
+use surrealdb::kvs::{Datastore, WriteLimits};
 use surrealdb::opt::capabilities::Capabilities as SdkCapabilities;
 
 #[derive(Args, Debug)]
@@ -38,6 +40,12 @@ pub struct StartCommandDbsOptions {
 	#[arg(env = "SURREAL_TEMPORARY_DIRECTORY", long = "temporary-directory")]
 	#[arg(value_parser = super::cli::validator::dir_exists)]
 	temporary_directory: Option<PathBuf>,
+	#[arg(
+		help = "Limits the number of records which can be written to a table each second, as ns/db/table=rate or ns/db/table=rate:burst"
+	)]
+	#[arg(env = "SURREAL_TABLE_WRITE_LIMIT", long = "table-write-limit", value_delimiter = ',')]
+	#[arg(value_parser = super::cli::validator::table_write_limit)]
+	table_write_limits: Vec<TableWriteLimit>,
 	#[arg(help = "Path to a SurrealQL file that will be imported when starting the server")]
 	#[arg(env = "SURREAL_IMPORT_FILE", long = "import-file")]
 	#[arg(value_parser = super::cli::validator::file_exists)]
@@ -113,6 +121,22 @@ Targets must be in the form of <host>[:<port>], <ipv4|ipv6>[/<mask>]. For exampl
 	#[arg(value_parser = super::cli::validator::net_targets)]
 	allow_net: Option<Targets<NetTarget>>,
 
+	#[arg(
+		help = "Allow reading the provided comma-separated list of environment variables, or all environment variables when no list is provided",
+		long_help = r#"Allow reading the provided comma-separated list of environment variables, or all environment variables when no list is provided.
+Environment variables are never readable unless they are explicitly allowed, even when all other capabilities are allowed.
+Specifically denied environment variables prevail over any other allowed environment variables.
+The 'HOSTNAME' target also controls access to the host name of the server. For example:
+ - 'APP_ENV,APP_REGION' -> Allow reading only these environment variables
+ - 'HOSTNAME' -> Allow reading the host name of the server
+"#
+	)]
+	#[arg(env = "SURREAL_CAPS_ALLOW_ENV", long)]
+	// If the arg is provided without value, then assume it's "", which gets parsed into Targets::All
+	#[arg(default_missing_value_os = "", num_args = 0..)]
+	#[arg(value_parser = super::cli::validator::env_targets)]
+	allow_env: Option<Targets<EnvTarget>>,
+
 	#[arg(
 		help = "Allow all RPC methods to be called except for routes that are specifically denied. Alternatively, you can provide a comma-separated list of RPC methods to allow."
 	)]
@@ -200,6 +224,15 @@ Targets must be in the form of <host>[:<port>], <ipv4|ipv6>[/<mask>]. For exampl
 	#[arg(value_parser = super::cli::validator::net_targets)]
 	deny_net: Option<Targets<NetTarget>>,
 
+	#[arg(
+		help = "Deny reading the provided comma-separated list of environment variables, or all environment variables when no list is provided"
+	)]
+	#[arg(env = "SURREAL_CAPS_DENY_ENV", long)]
+	// If the arg is provided without value, then assume it's "", which gets parsed into Targets::All
+	#[arg(default_missing_value_os = "", num_args = 0..)]
+	#[arg(value_parser = super::cli::validator::env_targets)]
+	deny_env: Option<Targets<EnvTarget>>,
+
 	#[arg(
 		help = "Deny all RPC methods from being called except for methods that are specifically allowed. Alternatively, you can provide a comma-separated list of RPC methods to deny."
 	)]
@@ -378,6 +411,31 @@ impl DbsCapabilities {
 		}
 	}
 
+	fn get_allow_env(&self) -> Targets<EnvTarget> {
+		// Environment variables are not covered by a global allow, so they must be explicitly allowed
+		// If there was a general deny for environment variables, we allow if there are specific allows
+		if let Some(Targets::All) = self.deny_env {
+			match &self.allow_env {
+				Some(Targets::Some(_)) => return self.allow_env.clone().unwrap(), // We already checked for Some
+				Some(_) => return Targets::None,
+				None => return Targets::None,
+			}
+		}
+
+		// If nothing was provided, we do not allow environment variables by default (Targets::None)
+		self.allow_env.clone().unwrap_or(Targets::None)
+	}
+
+	fn get_deny_env(&self) -> Targets<EnvTarget> {
+		// Allowed environment variables already consider a general deny for environment variables
+		// On top of what is explicitly allowed, we deny what is specifically denied
+		match &self.deny_env {
+			Some(Targets::Some(_)) => self.deny_env.clone().unwrap(), // We already checked for Some
+			Some(_) => Targets::None,
+			None => Targets::None,
+		}
+	}
+
 	fn get_deny_all(&self) -> bool {
 		self.deny_all
 	}
@@ -447,6 +505,8 @@ fn merge_capabilities(initial: Capabilities, caps: DbsCapabilities) -> Capabilit
 		.without_functions(caps.get_deny_funcs())
 		.with_network_targets(caps.get_allow_net())
 		.without_network_targets(caps.get_deny_net())
+		.with_env_vars(caps.get_allow_env())
+		.without_env_vars(caps.get_deny_env())
 		.with_rpc_methods(caps.get_allow_rpc())
 		.without_rpc_methods(caps.get_deny_rpc())
 		.with_http_routes(caps.get_allow_http())
@@ -471,6 +531,7 @@ pub async fn init(
 		unauthenticated,
 		capabilities,
 		temporary_directory,
+		table_write_limits,
 		import_file,
 	}: StartCommandDbsOptions,
 ) -> Result<Datastore, Error> {
@@ -498,6 +559,12 @@ pub async fn init(
 	let capabilities = capabilities.into();
 	// Log the specified server capabilities
 	debug!("Server capabilities: {capabilities}");
+	// Convert the table write limits
+	let mut write_limits = WriteLimits::new().with_hook(record_throttled);
+	for (ns, db, tb, rate, burst) in table_write_limits {
+		debug!("Maximum write rate for table {ns}/{db}/{tb} is {rate} records per second, with bursts of {burst}");
+		write_limits = write_limits.with_burst_limit(&ns, &db, &tb, rate, burst);
+	}
 	// Parse and setup the desired kv datastore
 	let dbs = Datastore::new(&opt.path)
 		.await?
@@ -507,6 +574,7 @@ pub async fn init(
 		.with_transaction_timeout(transaction_timeout)
 		.with_auth_enabled(!unauthenticated)
 		.with_temporary_directory(temporary_directory)
+		.with_write_limits(write_limits)
 		.with_capabilities(capabilities);
 	// Ensure the storage version is up-to-date to prevent corruption
 	dbs.check_version().await?;
@@ -835,6 +903,40 @@ mod tests {
 				true,
 				"SUCCESS".to_string(),
 			),
+			//
+			// Some environment variables are not allowed
+			//
+			(
+				Datastore::new("memory").await.unwrap().with_capabilities(Capabilities::all()),
+				Session::owner(),
+				"RETURN sys::env('PATH')".to_string(),
+				false,
+				"Access to environment variable 'PATH' is not allowed".to_string(),
+			),
+			(
+				Datastore::new("memory").await.unwrap().with_capabilities(
+					Capabilities::default()
+						.with_env_vars(Targets::<EnvTarget>::All)
+						.without_env_vars(Targets::<EnvTarget>::Some(
+							[EnvTarget::from_str("HOSTNAME").unwrap()].into(),
+						)),
+				),
+				Session::owner(),
+				"RETURN sys::hostname()".to_string(),
+				false,
+				"Access to environment variable 'HOSTNAME' is not allowed".to_string(),
+			),
+			(
+				Datastore::new("memory").await.unwrap().with_capabilities(
+					Capabilities::default().with_env_vars(Targets::<EnvTarget>::Some(
+						[EnvTarget::from_str("PATH").unwrap()].into(),
+					)),
+				),
+				Session::owner(),
+				"RETURN type::is::string(sys::env('PATH'))".to_string(),
+				true,
+				"true".to_string(),
+			),
 		];
 
 		for (idx, (ds, sess, query, succeeds, contains)) in cases.into_iter().enumerate() {
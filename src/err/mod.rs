@@ -71,6 +71,18 @@ pub enum Error {
 	#[error("There was an error with opentelemetry: {0}")]
 	Otel(#[from] OpentelemetryError),
 
+	#[error("There was an error with the backup: {0}")]
+	Backup(String),
+
+	#[error("There was an error restoring the backup: {0}")]
+	Restore(String),
+
+	#[error("There was an error with the migrations: {0}")]
+	Migration(String),
+
+	#[error("There was an error exporting the table: {0}")]
+	Export(String),
+
 	/// Statement has been deprecated
 	#[error("{0}")]
 	Other(String),
@@ -121,6 +133,26 @@ impl From<serde_pack::decode::Error> for Error {
 	}
 }
 
+impl From<object_store::Error> for Error {
+	fn from(e: object_store::Error) -> Error {
+		Error::Backup(e.to_string())
+	}
+}
+
+#[cfg(feature = "export-parquet")]
+impl From<parquet::errors::ParquetError> for Error {
+	fn from(e: parquet::errors::ParquetError) -> Error {
+		Error::Export(e.to_string())
+	}
+}
+
+#[cfg(feature = "export-parquet")]
+impl From<arrow::error::ArrowError> for Error {
+	fn from(e: arrow::error::ArrowError) -> Error {
+		Error::Export(e.to_string())
+	}
+}
+
 impl From<ciborium::value::Error> for Error {
 	fn from(e: ciborium::value::Error) -> Error {
 		Error::Cbor(format!("{e}"))
@@ -22,6 +22,7 @@ mod dbs;
 mod env;
 mod err;
 mod gql;
+mod mirror;
 mod net;
 mod rpc;
 mod telemetry;
@@ -0,0 +1,108 @@
+use crate::err::Error;
+use crate::telemetry::metrics::mirror::record_mirror;
+use std::time::Duration;
+use surrealdb::engine::any::{connect, Any};
+use surrealdb::kvs::{Datastore, Mirrored};
+use surrealdb::opt::auth::Root;
+use surrealdb::Surreal;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Instant};
+use tokio_util::sync::CancellationToken;
+
+const TARGET: &str = "surreal::mirror";
+
+/// The delay before the first attempt to reconnect to the mirror endpoint
+const RECONNECT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The longest delay between attempts to reconnect to the mirror endpoint
+const RECONNECT_BACKOFF_MAX: Duration = Duration::from_secs(60);
+
+/// Starts replaying write queries against the mirror endpoint
+///
+/// Only the statements which succeeded on this server are replayed, in the order
+/// in which they were run, in the namespace and database of the session which ran
+/// them, and with the parameters of that session. They are run as the root user
+/// of the mirror endpoint. Failures are logged and counted, but never affect the
+/// requests which are being served by this server. If the mirror endpoint can
+/// not be reached, this server starts anyway, and keeps trying to connect, while
+/// queries wait in the bounded mirror channel.
+pub async fn init(
+	ds: &Datastore,
+	endpoint: String,
+	credentials: Option<(String, String)>,
+	canceller: CancellationToken,
+) -> Result<JoinHandle<()>, Error> {
+	// Get the mirrored queries channel
+	let Some(channel) = ds.mirrored() else {
+		return Err(Error::OperationUnsupported);
+	};
+	info!(target: TARGET, "Mirroring write queries to {endpoint}");
+	// Replay the queries in the background
+	Ok(tokio::spawn(async move {
+		// Connect to the mirror endpoint, retrying until it can be reached
+		let mut backoff = RECONNECT_BACKOFF;
+		let db = loop {
+			match connection(&endpoint, credentials.as_ref()).await {
+				Ok(db) => break db,
+				Err(e) => {
+					warn!(target: TARGET, "Unable to connect to the mirror endpoint, retrying in {backoff:?}: {e}");
+				}
+			}
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => return,
+				// Wait before trying again
+				_ = sleep(backoff) => backoff = (backoff * 2).min(RECONNECT_BACKOFF_MAX),
+			}
+		};
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a mirrored query on the channel
+				Ok(mirrored) = channel.recv() => replay(&db, mirrored).await,
+			}
+		}
+	}))
+}
+
+/// Connects and signs in to the mirror endpoint
+async fn connection(
+	endpoint: &str,
+	credentials: Option<&(String, String)>,
+) -> Result<Surreal<Any>, surrealdb::Error> {
+	let db = connect(endpoint).await?;
+	if let Some((username, password)) = credentials {
+		db.signin(Root {
+			username,
+			password,
+		})
+		.await?;
+	}
+	Ok(db)
+}
+
+/// Runs a single mirrored query against the mirror endpoint
+async fn replay(db: &Surreal<Any>, mirrored: Mirrored) {
+	// Include the time spent waiting to be replayed in the lag
+	let waited = mirrored.lag();
+	let start = Instant::now();
+	// Run the query in the same namespace and database
+	let mut query = db.query(mirrored.query).bind(mirrored.vars);
+	if let Some(ns) = mirrored.ns {
+		query = query.use_ns(ns);
+	}
+	if let Some(database) = mirrored.db {
+		query = query.use_db(database);
+	}
+	let res = match query.await {
+		Ok(mut response) => response.take_errors().into_values().next(),
+		Err(e) => Some(e),
+	};
+	if let Some(e) = &res {
+		warn!(target: TARGET, "Failed to mirror a write query: {e}");
+	}
+	record_mirror(waited + start.elapsed(), res.is_some());
+}
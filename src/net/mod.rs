@@ -17,11 +17,17 @@ mod signals;
 mod signin;
 mod signup;
 mod sql;
+mod stats;
 mod sync;
+#[cfg(unix)]
+mod systemd;
+mod tls;
 mod tracer;
+#[cfg(unix)]
+mod unix;
 mod version;
 
-use crate::cli::CF;
+use crate::cli::{BindAddress, CF};
 use crate::cnf;
 use crate::err::Error;
 use crate::net::signals::graceful_shutdown;
@@ -175,6 +181,7 @@ pub async fn init(ds: Arc<Datastore>, ct: CancellationToken) -> Result<(), Error
 		.merge(export::router())
 		.merge(import::router())
 		.merge(rpc::router())
+		.merge(stats::router())
 		.merge(version::router())
 		.merge(sync::router())
 		.merge(sql::router())
@@ -206,21 +213,63 @@ pub async fn init(ds: Arc<Datastore>, ct: CancellationToken) -> Result<(), Error
 
 	let rpc_state = Arc::new(RpcState::new());
 
+	// SIGHUP reloads the TLS certificate, if one is used
+	let tls_reload =
+		matches!(opt.bind, BindAddress::Tcp(_)) && opt.crt.is_some() && opt.key.is_some();
+
 	// Setup the graceful shutdown handler
-	let shutdown_handler = graceful_shutdown(rpc_state.clone(), ct.clone(), handle.clone());
+	let shutdown_handler = graceful_shutdown(
+		rpc_state.clone(),
+		ct.clone(),
+		handle.clone(),
+		opt.shutdown_timeout,
+		tls_reload,
+	);
 
 	let axum_app = axum_app.with_state(rpc_state.clone());
 
 	// Spawn a task to handle notifications
-	tokio::spawn(async move { notifications(ds, rpc_state, ct.clone()).await });
+	tokio::spawn({
+		let ct = ct.clone();
+		async move { notifications(ds, rpc_state, ct).await }
+	});
+	let bind = match &opt.bind {
+		BindAddress::Tcp(bind) => *bind,
+		// Unix domain sockets are only reachable locally, so TLS is not used
+		#[cfg(unix)]
+		BindAddress::Unix(path) => {
+			if opt.crt.is_some() {
+				warn!(target: LOG, "TLS is not used when listening on a Unix domain socket");
+			}
+			unix::serve(axum_app, path, ct).await?;
+			// Wait for the shutdown to finish
+			let _ = shutdown_handler.await;
+			// Log the server shutdown to the CLI
+			info!(target: LOG, "Web server stopped. Bye!");
+			return Ok(());
+		}
+	};
+	// Notify systemd once the server is listening for connections
+	#[cfg(unix)]
+	tokio::spawn({
+		let handle = handle.clone();
+		let ct = ct.clone();
+		async move {
+			if handle.listening().await.is_some() {
+				systemd::ready(ct);
+			}
+		}
+	});
 	// If a certificate and key are specified, then setup TLS
 	let res = if let (Some(cert), Some(key)) = (&opt.crt, &opt.key) {
 		// Configure certificate and private key used by https
 		let tls = RustlsConfig::from_pem_file(cert, key).await?;
+		// Reload the certificate and private key when they are renewed
+		tls::watch(tls.clone(), cert.clone(), key.clone(), ct.clone());
 		// Setup the Axum server with TLS
-		let server = axum_server::bind_rustls(opt.bind, tls);
+		let server = axum_server::bind_rustls(bind, tls);
 		// Log the server startup to the CLI
-		info!(target: LOG, "Started web server on {}", &opt.bind);
+		info!(target: LOG, "Started web server on {}", &bind);
 		// Start the server and listen for connections
 		server
 			.handle(handle)
@@ -228,9 +277,9 @@ pub async fn init(ds: Arc<Datastore>, ct: CancellationToken) -> Result<(), Error
 			.await
 	} else {
 		// Setup the Axum server
-		let server = axum_server::bind(opt.bind);
+		let server = axum_server::bind(bind);
 		// Log the server startup to the CLI
-		info!(target: LOG, "Started web server on {}", &opt.bind);
+		info!(target: LOG, "Started web server on {}", &bind);
 		// Start the server and listen for connections
 		server
 			.handle(handle)
@@ -239,7 +288,7 @@ pub async fn init(ds: Arc<Datastore>, ct: CancellationToken) -> Result<(), Error
 	};
 	// Catch the error and try to provide some guidance
 	if let Err(e) = res {
-		if opt.bind.port() < 1024 {
+		if bind.port() < 1024 {
 			if let io::ErrorKind::PermissionDenied = e.kind() {
 				error!(target: LOG, "Binding to ports below 1024 requires privileged access or special permissions.");
 			}
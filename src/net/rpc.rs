@@ -102,6 +102,8 @@ async fn get_handler(
 	};
 	// This session supports live queries
 	session.rt = true;
+	// This session keeps transactions open between queries
+	session = session.with_transactions();
 	// Store the connection id in session
 	session.id = Some(id.to_string());
 	// Check if a connection with this id already exists
@@ -134,14 +136,17 @@ async fn handle_socket(
 	id: Uuid,
 ) {
 	// Check if there is a WebSocket protocol specified
-	let format = match ws.protocol().and_then(|h| h.to_str().ok()) {
+	let protocol = ws.protocol().and_then(|h| h.to_str().ok());
+	let format = match protocol {
 		// Any selected protocol will always be a valid value
 		Some(protocol) => protocol.into(),
 		// No protocol format was specified
 		_ => Format::Json,
 	};
+	// Clients using the first revisioned protocol can not decode error details
+	let details = protocol != Some(PROTOCOLS[4]);
 	// Serve the socket connection requests
-	Websocket::serve(id, ws, format, session, datastore, state).await;
+	Websocket::serve(id, ws, format, details, session, datastore, state).await;
 }
 
 async fn post_handler(
@@ -172,8 +177,6 @@ async fn post_handler(
 			return Err(Error::InvalidType);
 		}
 	}
-	// Create a new HTTP instance
-	let rpc = Http::new(&state.datastore, session);
 	// Check to see available memory
 	if ALLOC.is_beyond_threshold() {
 		return Err(Error::ServerOverloaded);
@@ -181,6 +184,13 @@ async fn post_handler(
 	// Parse the HTTP request body
 	match fmt.req_http(body) {
 		Ok(req) => {
+			// Apply the requested write durability
+			let session = match req.durability {
+				Some(durability) => session.with_durability(durability),
+				None => session,
+			};
+			// Create a new HTTP instance
+			let rpc = Http::new(&state.datastore, session);
 			// Execute the specified method
 			let res = RpcContext::execute(&rpc, req.version, req.method, req.params).await;
 			// Return the HTTP response
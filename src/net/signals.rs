@@ -3,6 +3,7 @@ use crate::rpc::{self, RpcState};
 use crate::telemetry;
 use axum_server::Handle;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 
@@ -11,21 +12,27 @@ use tokio_util::sync::CancellationToken;
 /// * Stop all WebSocket connections.
 /// * Flush all telemetry data.
 ///
-/// A second signal will force an immediate shutdown.
+/// A second signal, or the timeout elapsing, will force an immediate shutdown. When the TLS
+/// certificate is reloaded on SIGHUP, then SIGHUP does not start a shutdown.
 pub fn graceful_shutdown(
 	state: Arc<RpcState>,
 	canceller: CancellationToken,
 	http_handle: Handle,
+	timeout: Option<Duration>,
+	tls_reload: bool,
 ) -> JoinHandle<()> {
 	// Spawn a new background asynchronous task
 	tokio::spawn(async move {
 		// Listen to the primary OS task signal
-		if let Ok(signal) = listen().await {
+		if let Ok(signal) = listen(tls_reload).await {
 			warn!(target: super::LOG, "{signal} received. Waiting for a graceful shutdown. A second signal will force an immediate shutdown.");
 		} else {
 			error!(target: super::LOG, "Failed to listen to shutdown signal. Terminating immediately.");
 			canceller.cancel();
 		}
+		// Tell systemd that the server is stopping
+		#[cfg(unix)]
+		super::systemd::stopping();
 		// Spawn a task to gracefully shutdown
 		let shutdown = {
 			// Clone the state
@@ -35,13 +42,28 @@ pub fn graceful_shutdown(
 			// Spawn a background task
 			tokio::spawn(async move {
 				// Stop accepting new HTTP connections
-				http_handle.graceful_shutdown(None);
-				// Wait for all connections to close
-				while http_handle.connection_count() > 0 {
-					tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+				http_handle.graceful_shutdown(timeout);
+				// Close the WebSocket connections once their queued messages are processed,
+				// as they would otherwise keep the HTTP connections open
+				let drain = async {
+					rpc::graceful_shutdown(state.clone()).await;
+					// Wait for all in-flight requests to finish
+					while http_handle.connection_count() > 0 {
+						tokio::time::sleep(Duration::from_millis(100)).await;
+					}
+				};
+				match timeout {
+					Some(timeout) => {
+						if tokio::time::timeout(timeout, drain).await.is_err() {
+							warn!(target: super::LOG, "Connections were still open after {timeout:?}. Cancelling them.");
+							// Close all HTTP connections immediately
+							http_handle.shutdown();
+							// Close all WebSocket connections immediately
+							rpc::shutdown(state);
+						}
+					}
+					None => drain.await,
 				}
-				// Stop accepting new WebSocket connections
-				rpc::graceful_shutdown(state).await;
 				// Cancel the cancellation token
 				canceller.cancel();
 				// Flush all telemetry data
@@ -70,7 +92,7 @@ pub fn graceful_shutdown(
 				}
 			}
 			// Listen for a secondary signal
-			res = listen() => {
+			res = listen(tls_reload) => {
 				// If we receive a secondary signal, force a shutdown
 				if let Ok(signal) = res {
 					warn!(target: super::LOG, "{signal} received during graceful shutdown. Terminating immediately.");
@@ -93,7 +115,7 @@ pub fn graceful_shutdown(
 }
 
 #[cfg(unix)]
-pub async fn listen() -> Result<String, Error> {
+pub async fn listen(tls_reload: bool) -> Result<String, Error> {
 	// Log informational message
 	info!(target: super::LOG, "Listening for a system shutdown signal.");
 	// Import the OS signals
@@ -105,8 +127,8 @@ pub async fn listen() -> Result<String, Error> {
 	let mut sigterm = signal(SignalKind::terminate())?;
 	// Listen and wait for the system signals
 	tokio::select! {
-		// Wait for a SIGHUP signal
-		_ = sighup.recv() => {
+		// Wait for a SIGHUP signal, unless it reloads the TLS certificate
+		_ = sighup.recv(), if !tls_reload => {
 			Ok(String::from("SIGHUP"))
 		}
 		// Wait for a SIGINT signal
@@ -125,7 +147,7 @@ pub async fn listen() -> Result<String, Error> {
 }
 
 #[cfg(windows)]
-pub async fn listen() -> Result<String, Error> {
+pub async fn listen(_tls_reload: bool) -> Result<String, Error> {
 	// Log informational message
 	info!(target: super::LOG, "Listening for a system shutdown signal.");
 	// Import the OS signals
@@ -0,0 +1,62 @@
+use super::output;
+use super::AppState;
+use crate::err::Error;
+use crate::rpc::RpcState;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Extension, Router};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use surrealdb::dbs::capabilities::RouteTarget;
+use surrealdb::dbs::Session;
+use surrealdb::iam::Action::View;
+use surrealdb::iam::ResourceKind::Any;
+use tokio::sync::Mutex;
+
+/// How long the storage size of each namespace is reused for, as measuring it reads every key
+const STORAGE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The storage size of each namespace, and when it was measured
+static STORAGE: Mutex<Option<(Instant, BTreeMap<String, u64>)>> = Mutex::const_new(None);
+
+pub(super) fn router() -> Router<Arc<RpcState>> {
+	Router::new().route("/stats", get(handler))
+}
+
+async fn handler(
+	Extension(state): Extension<AppState>,
+	Extension(session): Extension<Session>,
+	State(rpc_state): State<Arc<RpcState>>,
+) -> Result<impl IntoResponse, Error> {
+	// Get the datastore reference
+	let db = &state.datastore;
+	// Check if capabilities allow querying the requested HTTP route
+	if !db.allows_http_route(&RouteTarget::Stats) {
+		warn!("Capabilities denied HTTP route request attempt, target: '{}'", &RouteTarget::Stats);
+		return Err(Error::ForbiddenRoute(RouteTarget::Stats.to_string()));
+	}
+	// The statistics cover every namespace, so can only be viewed at the root level
+	db.check(&session, View, Any.on_root())?;
+	// Measure the storage size of each namespace, unless it was measured recently
+	let mut storage = STORAGE.lock().await;
+	let namespaces = match &*storage {
+		Some((measured, sizes)) if measured.elapsed() < STORAGE_INTERVAL => sizes.clone(),
+		_ => {
+			let sizes = db.namespace_sizes().await?;
+			*storage = Some((Instant::now(), sizes.clone()));
+			sizes
+		}
+	};
+	drop(storage);
+	let statistics = db.statistics();
+	Ok(output::json(&json!({
+		"queries": statistics.queries,
+		"conflicts": statistics.conflicts,
+		"connections": rpc_state.web_sockets.read().await.len(),
+		"live_queries": rpc_state.live_queries.read().await.len(),
+		"namespaces": namespaces,
+	})))
+}
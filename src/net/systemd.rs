@@ -0,0 +1,73 @@
+use super::LOG;
+use std::os::unix::net::UnixDatagram;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// Tells systemd that the server is ready to accept connections
+///
+/// This is used by units with `Type=notify`, which are only reported as started once the
+/// datastore has been opened and the server is listening. If the unit has a watchdog, it is
+/// also pinged until the server is shut down.
+pub(super) fn ready(ct: CancellationToken) {
+	if !notify("READY=1") {
+		return;
+	}
+	debug!(target: LOG, "Notified systemd that the server is ready");
+	if let Some(interval) = watchdog_interval() {
+		tokio::spawn(async move {
+			loop {
+				tokio::select! {
+					_ = ct.cancelled() => break,
+					_ = tokio::time::sleep(interval) => notify("WATCHDOG=1"),
+				};
+			}
+		});
+	}
+}
+
+/// Tells systemd that the server is shutting down
+pub(super) fn stopping() {
+	notify("STOPPING=1");
+}
+
+/// Sends a state to the socket of the service manager, returning whether it was sent
+fn notify(state: &str) -> bool {
+	let Some(path) = std::env::var_os("NOTIFY_SOCKET") else {
+		return false;
+	};
+	let result = UnixDatagram::unbound().and_then(|socket| {
+		let path = path.to_string_lossy();
+		match path.strip_prefix('@') {
+			// Sockets starting with @ are in the abstract namespace
+			#[cfg(target_os = "linux")]
+			Some(name) => {
+				use std::os::linux::net::SocketAddrExt;
+				let addr = std::os::unix::net::SocketAddr::from_abstract_name(name)?;
+				socket.send_to_addr(state.as_bytes(), &addr)
+			}
+			_ => socket.send_to(state.as_bytes(), &*path),
+		}
+	});
+	match result {
+		Ok(_) => true,
+		Err(e) => {
+			warn!(target: LOG, "Unable to notify systemd of the state of the server: {e}");
+			false
+		}
+	}
+}
+
+/// How often the watchdog needs to be pinged, which is half of the watchdog timeout
+fn watchdog_interval() -> Option<Duration> {
+	// The watchdog is meant for a specific process, if a process is specified
+	if let Ok(pid) = std::env::var("WATCHDOG_PID") {
+		if pid.parse() != Ok(std::process::id()) {
+			return None;
+		}
+	}
+	let usec: u64 = std::env::var("WATCHDOG_USEC").ok()?.parse().ok()?;
+	match usec {
+		0 => None,
+		usec => Some(Duration::from_micros(usec) / 2),
+	}
+}
@@ -0,0 +1,83 @@
+use crate::cnf;
+use axum_server::tls_rustls::RustlsConfig;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tokio_util::sync::CancellationToken;
+
+const LOG: &str = "surrealdb::net::tls";
+
+/// Watches the certificate and private key files, reloading them when they change
+///
+/// The files are reloaded straight away when the server receives a SIGHUP signal, which
+/// then no longer shuts down the server. Connections which are already established keep
+/// using the previous certificate, so renewing a certificate does not interrupt clients
+/// or their live queries.
+pub(super) fn watch(tls: RustlsConfig, crt: PathBuf, key: PathBuf, ct: CancellationToken) {
+	// Listen for the signal before returning, so that no SIGHUP is missed
+	let mut hangup = Hangup::new();
+	tokio::spawn(async move {
+		let interval = Duration::from_secs(*cnf::TLS_RELOAD_INTERVAL);
+		let mut last = (modified(&crt), modified(&key));
+		loop {
+			tokio::select! {
+				_ = ct.cancelled() => break,
+				_ = hangup.recv() => {
+					info!(target: LOG, "SIGHUP received. Reloading the TLS certificate.");
+					last = (modified(&crt), modified(&key));
+				}
+				_ = tokio::time::sleep(interval) => {
+					let current = (modified(&crt), modified(&key));
+					if current == last {
+						continue;
+					}
+					// A renewal which replaces the files one at a time changes them again once
+					// it has finished, so the files are reloaded again if they don't match yet
+					last = current;
+				}
+			}
+			match tls.reload_from_pem_file(&crt, &key).await {
+				Ok(_) => info!(target: LOG, "Reloaded the TLS certificate from {}", crt.display()),
+				Err(e) => {
+					warn!(target: LOG, "Unable to reload the TLS certificate, so the previous certificate is still used: {e}")
+				}
+			}
+		}
+	});
+}
+
+/// When a file was last modified, if it can be read
+fn modified(path: &Path) -> Option<SystemTime> {
+	std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Receives the SIGHUP signals which request the certificate is reloaded
+struct Hangup {
+	#[cfg(unix)]
+	signal: Option<tokio::signal::unix::Signal>,
+}
+
+impl Hangup {
+	fn new() -> Self {
+		Self {
+			#[cfg(unix)]
+			signal: match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+				Ok(signal) => Some(signal),
+				Err(e) => {
+					warn!(target: LOG, "Unable to listen for SIGHUP to reload the TLS certificate: {e}");
+					None
+				}
+			},
+		}
+	}
+
+	/// Waits for the next signal, or forever where there are no such signals
+	async fn recv(&mut self) {
+		#[cfg(unix)]
+		if let Some(signal) = &mut self.signal {
+			if signal.recv().await.is_some() {
+				return;
+			}
+		}
+		std::future::pending().await
+	}
+}
@@ -0,0 +1,80 @@
+use super::LOG;
+use axum::Router;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto::Builder;
+use hyper_util::service::TowerToHyperService;
+use std::io;
+use std::os::unix::fs::FileTypeExt;
+use std::path::Path;
+use tokio::net::{UnixListener, UnixStream};
+use tokio_util::sync::CancellationToken;
+
+/// Serve the application on a Unix domain socket until the server is shut down
+///
+/// Connections over a Unix domain socket have no peer IP address, so the
+/// client IP is only available when it is detected from a request header.
+pub async fn serve(app: Router, path: &Path, ct: CancellationToken) -> io::Result<()> {
+	// Remove any socket left behind by a previous server
+	remove_stale_socket(path).await?;
+	// Bind to the socket path
+	let listener = UnixListener::bind(path)?;
+	// Log the server startup to the CLI
+	info!(target: LOG, "Started web server on unix://{}", path.display());
+	// Notify systemd now that the server is listening for connections
+	super::systemd::ready(ct.clone());
+	// Accept connections until the server is shut down
+	loop {
+		let stream = tokio::select! {
+			biased;
+			_ = ct.cancelled() => break,
+			res = listener.accept() => match res {
+				Ok((stream, _)) => stream,
+				Err(e) => {
+					warn!(target: LOG, "Failed to accept a Unix domain socket connection: {e}");
+					continue;
+				}
+			},
+		};
+		let service = TowerToHyperService::new(app.clone());
+		tokio::spawn(async move {
+			let io = TokioIo::new(stream);
+			let res = Builder::new(TokioExecutor::new())
+				.serve_connection_with_upgrades(io, service)
+				.await;
+			if let Err(e) = res {
+				trace!(target: LOG, "Unix domain socket connection closed with an error: {e}");
+			}
+		});
+	}
+	// Clean up the socket path
+	let _ = tokio::fs::remove_file(path).await;
+	Ok(())
+}
+
+/// Removes a socket which was left behind by a server which is no longer running
+///
+/// Anything else at the path, including the socket of a running server, is left
+/// untouched and is reported as an error instead.
+async fn remove_stale_socket(path: &Path) -> io::Result<()> {
+	let metadata = match tokio::fs::symlink_metadata(path).await {
+		Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+		res => res?,
+	};
+	if !metadata.file_type().is_socket() {
+		return Err(io::Error::new(
+			io::ErrorKind::AlreadyExists,
+			format!("The path {} already exists and is not a socket", path.display()),
+		));
+	}
+	// A socket which still accepts connections belongs to a running server
+	match UnixStream::connect(path).await {
+		Ok(_) => Err(io::Error::new(
+			io::ErrorKind::AddrInUse,
+			format!("The socket {} is already in use by a running server", path.display()),
+		)),
+		Err(e) if e.kind() == io::ErrorKind::ConnectionRefused => {
+			tokio::fs::remove_file(path).await
+		}
+		Err(e) => Err(e),
+	}
+}
@@ -3,6 +3,7 @@ use revision::revisioned;
 use revision::Revisioned;
 use serde::Serialize;
 use std::borrow::Cow;
+use surrealdb::err::ErrorDetails;
 use surrealdb::rpc::RpcError;
 use surrealdb::sql::Value;
 
@@ -10,13 +11,18 @@ use surrealdb::sql::Value;
 pub struct Failure {
 	pub(crate) code: i64,
 	pub(crate) message: Cow<'static, str>,
+	/// The code and details of the database error which caused this failure
+	#[serde(skip)]
+	pub(crate) details: Option<ErrorDetails>,
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Serialize)]
 struct Inner {
 	code: i64,
 	message: String,
+	#[revision(start = 2)]
+	details: Option<ErrorDetails>,
 }
 
 impl Revisioned for Failure {
@@ -27,6 +33,7 @@ impl Revisioned for Failure {
 		let inner = Inner {
 			code: self.code,
 			message: self.message.as_ref().to_owned(),
+			details: self.details.clone(),
 		};
 		inner.serialize_revisioned(writer)
 	}
@@ -36,7 +43,7 @@ impl Revisioned for Failure {
 	}
 
 	fn revision() -> u16 {
-		1
+		Inner::revision()
 	}
 }
 
@@ -48,7 +55,14 @@ impl From<&str> for Failure {
 
 impl From<Error> for Failure {
 	fn from(err: Error) -> Self {
-		Failure::custom(err.to_string())
+		let details = match &err {
+			Error::Db(surrealdb::Error::Db(e)) => Some(e.details()),
+			_ => None,
+		};
+		Failure {
+			details,
+			..Failure::custom(err.to_string())
+		}
 	}
 }
 
@@ -59,7 +73,10 @@ impl From<RpcError> for Failure {
 			RpcError::InvalidRequest => Failure::INVALID_REQUEST,
 			RpcError::MethodNotFound => Failure::METHOD_NOT_FOUND,
 			RpcError::InvalidParams => Failure::INVALID_PARAMS,
-			RpcError::InternalError(_) => Failure::custom(err.to_string()),
+			RpcError::InternalError(ref e) => Failure {
+				details: Some(e.details()),
+				..Failure::custom(err.to_string())
+			},
 			RpcError::Thrown(_) => Failure::custom(err.to_string()),
 			_ => Failure::custom(err.to_string()),
 		}
@@ -68,11 +85,16 @@ impl From<RpcError> for Failure {
 
 impl From<Failure> for Value {
 	fn from(err: Failure) -> Self {
-		map! {
+		let mut value = map! {
 			String::from("code") => Value::from(err.code),
 			String::from("message") => Value::from(err.message.to_string()),
+		};
+		if let Some(details) = err.details {
+			if let Ok(details) = surrealdb::sql::to_value(details) {
+				value.insert(String::from("details"), details);
+			}
 		}
-		.into()
+		value.into()
 	}
 }
 
@@ -81,26 +103,31 @@ impl Failure {
 	pub const PARSE_ERROR: Failure = Failure {
 		code: -32700,
 		message: Cow::Borrowed("Parse error"),
+		details: None,
 	};
 
 	pub const INVALID_REQUEST: Failure = Failure {
 		code: -32600,
 		message: Cow::Borrowed("Invalid Request"),
+		details: None,
 	};
 
 	pub const METHOD_NOT_FOUND: Failure = Failure {
 		code: -32601,
 		message: Cow::Borrowed("Method not found"),
+		details: None,
 	};
 
 	pub const INVALID_PARAMS: Failure = Failure {
 		code: -32602,
 		message: Cow::Borrowed("Invalid params"),
+		details: None,
 	};
 
 	pub const INTERNAL_ERROR: Failure = Failure {
 		code: -32603,
 		message: Cow::Borrowed("Internal error"),
+		details: None,
 	};
 
 	pub fn custom<S>(message: S) -> Failure
@@ -110,6 +137,7 @@ impl Failure {
 		Failure {
 			code: -32000,
 			message: message.into(),
+			details: None,
 		}
 	}
 }
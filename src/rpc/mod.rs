@@ -89,7 +89,7 @@ pub(crate) async fn notifications(
 							// Get the WebSocket sending channel
 							let sender = rpc.channel.clone();
 							// Send the notification to the client
-							let future = message.send(cx, format, sender);
+							let future = message.send(cx, format, rpc.details, sender);
 							// Pus the future to the pipeline
 							futures.push(future);
 						}
@@ -4,8 +4,10 @@ use crate::telemetry::metrics::ws::record_rpc;
 use axum::extract::ws::Message;
 use opentelemetry::Context as TelemetryContext;
 use revision::revisioned;
+use revision::Revisioned;
 use serde::Serialize;
 use std::sync::Arc;
+use surrealdb::dbs::{Notification, QueryMethodResponse, Status};
 use surrealdb::rpc::format::Format;
 use surrealdb::rpc::Data;
 use surrealdb::sql::Value;
@@ -36,8 +38,30 @@ impl Response {
 		value.into()
 	}
 
+	/// Process the response for the format, using the first revision
+	/// of the revisioned format for clients which can not decode the
+	/// error details in the newer revision
+	fn res_ws(self, fmt: Format, details: bool) -> Result<(usize, Message), Failure> {
+		match fmt {
+			Format::Revision if !details => {
+				let mut buf = Vec::new();
+				LegacyResponse::from(self)
+					.serialize_revisioned(&mut buf)
+					.map_err(|_| Failure::INTERNAL_ERROR)?;
+				Ok((buf.len(), Message::Binary(buf)))
+			}
+			_ => fmt.res_ws(self),
+		}
+	}
+
 	/// Send the response to the WebSocket channel
-	pub async fn send(self, cx: Arc<TelemetryContext>, fmt: Format, chn: Sender<Message>) {
+	pub async fn send(
+		self,
+		cx: Arc<TelemetryContext>,
+		fmt: Format,
+		details: bool,
+		chn: Sender<Message>,
+	) {
 		// Get the request id
 		let id = self.id.clone();
 		// Create a new tracing span
@@ -53,10 +77,10 @@ impl Response {
 			span.record("rpc.error_message", err.message.as_ref());
 		}
 		// Process the response for the format
-		let (len, msg) = match fmt.res_ws(self) {
+		let (len, msg) = match self.res_ws(fmt, details) {
 			Ok((l, m)) => (l, m),
-			Err(err) => fmt
-				.res_ws(failure(id, err))
+			Err(err) => failure(id, err)
+				.res_ws(fmt, details)
 				.expect("Serialising internal error should always succeed"),
 		};
 		// Send the message to the write channel
@@ -66,6 +90,66 @@ impl Response {
 	}
 }
 
+/// The first revision of the revisioned response format, without any
+/// error details, for clients which have not connected using `revision-2`
+#[revisioned(revision = 1)]
+#[derive(Debug)]
+struct LegacyResponse {
+	id: Option<Value>,
+	result: Result<LegacyData, LegacyFailure>,
+}
+
+#[revisioned(revision = 1)]
+#[derive(Debug)]
+enum LegacyData {
+	Other(Value),
+	Query(Vec<LegacyQueryMethodResponse>),
+	Live(Notification),
+}
+
+#[revisioned(revision = 1)]
+#[derive(Debug)]
+struct LegacyQueryMethodResponse {
+	time: String,
+	status: Status,
+	result: Value,
+}
+
+#[revisioned(revision = 1)]
+#[derive(Debug)]
+struct LegacyFailure {
+	code: i64,
+	message: String,
+}
+
+impl From<Response> for LegacyResponse {
+	fn from(res: Response) -> Self {
+		let result = match res.result {
+			Ok(Data::Other(v)) => Ok(LegacyData::Other(v)),
+			Ok(Data::Query(v)) => Ok(LegacyData::Query(
+				v.iter()
+					.map(QueryMethodResponse::from)
+					.map(|v| LegacyQueryMethodResponse {
+						time: v.time,
+						status: v.status,
+						result: v.result,
+					})
+					.collect(),
+			)),
+			Ok(Data::Live(v)) => Ok(LegacyData::Live(v)),
+			Ok(v) => Ok(LegacyData::Other(v.into())),
+			Err(err) => Err(LegacyFailure {
+				code: err.code,
+				message: err.message.into_owned(),
+			}),
+		};
+		LegacyResponse {
+			id: res.id,
+			result,
+		}
+	}
+}
+
 impl From<Response> for Value {
 	fn from(value: Response) -> Self {
 		value.into_value()
@@ -104,3 +188,25 @@ where
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_legacy_revision() {
+		let err = Failure {
+			details: Some(Default::default()),
+			..Failure::custom("There was a problem")
+		};
+		// Clients using `revision` receive the first revision, without any details
+		let (_, msg) = failure(None, err.clone()).res_ws(Format::Revision, false).unwrap();
+		let res = LegacyResponse::deserialize_revisioned(&mut msg.into_data().as_slice()).unwrap();
+		assert!(
+			matches!(res.result, Err(LegacyFailure { ref message, .. }) if message == "There was a problem")
+		);
+		// Clients using `revision-2` receive the newer revision, with the details
+		let (_, msg) = failure(None, err).res_ws(Format::Revision, true).unwrap();
+		assert!(LegacyResponse::deserialize_revisioned(&mut msg.into_data().as_slice()).is_err());
+	}
+}
@@ -20,7 +20,7 @@ use opentelemetry::trace::FutureExt;
 use opentelemetry::Context as TelemetryContext;
 use std::sync::Arc;
 use std::time::Duration;
-use surrealdb::dbs::Session;
+use surrealdb::dbs::{Durability, Session};
 #[cfg(surrealdb_unstable)]
 use surrealdb::gql::{Pessimistic, SchemaCache};
 use surrealdb::kvs::Datastore;
@@ -52,6 +52,8 @@ pub struct Websocket {
 	pub(crate) id: Uuid,
 	/// The request and response format for messages
 	pub(crate) format: Format,
+	/// Whether the client can decode error details in the revisioned format
+	pub(crate) details: bool,
 	/// The system state for all RPC WebSocket connections
 	pub(crate) state: Arc<RpcState>,
 	/// The datastore accessible to all RPC WebSocket connections
@@ -77,6 +79,7 @@ impl Websocket {
 		id: Uuid,
 		ws: WebSocket,
 		format: Format,
+		details: bool,
 		session: Session,
 		datastore: Arc<Datastore>,
 		state: Arc<RpcState>,
@@ -89,6 +92,7 @@ impl Websocket {
 		let rpc = Arc::new(Websocket {
 			id,
 			format,
+			details,
 			state: state.clone(),
 			lock: Arc::new(Semaphore::new(1)),
 			shutdown: CancellationToken::new(),
@@ -373,7 +377,7 @@ impl Websocket {
 							if shutdown.is_cancelled() {
 								// Process the response
 								failure(req.id, Failure::custom(SERVER_SHUTTING_DOWN))
-									.send(otel_cx.clone(), rpc.format, chn)
+									.send(otel_cx.clone(), rpc.format, rpc.details, chn)
 									.with_context(otel_cx.as_ref().clone())
 									.await;
 							}
@@ -381,16 +385,16 @@ impl Websocket {
 							else if ALLOC.is_beyond_threshold() {
 								// Process the response
 								failure(req.id, Failure::custom(SERVER_OVERLOADED))
-									.send(otel_cx.clone(), rpc.format, chn)
+									.send(otel_cx.clone(), rpc.format, rpc.details, chn)
 									.with_context(otel_cx.as_ref().clone())
 									.await;
 							}
 							// Otherwise process the request message
 							else {
 								// Process the message
-								Self::process_message(rpc.clone(), req.version, req.method, req.params).await
+								Self::process_message(rpc.clone(), req.version, req.durability, req.method, req.params).await
 									.into_response(req.id)
-									.send(otel_cx.clone(), rpc.format, chn)
+									.send(otel_cx.clone(), rpc.format, rpc.details, chn)
 									.with_context(otel_cx.as_ref().clone())
 									.await;
 							}
@@ -400,7 +404,7 @@ impl Websocket {
 				Err(err) => {
 					// Process the response
 					failure(None, err)
-						.send(otel_cx.clone(), rpc.format, chn)
+						.send(otel_cx.clone(), rpc.format, rpc.details, chn)
 						.with_context(otel_cx.as_ref().clone())
 						.await
 				}
@@ -414,6 +418,7 @@ impl Websocket {
 	async fn process_message(
 		rpc: Arc<Websocket>,
 		version: Option<u8>,
+		durability: Option<Durability>,
 		method: Method,
 		params: Array,
 	) -> Result<Data, Failure> {
@@ -422,8 +427,13 @@ impl Websocket {
 		if !method.is_valid() {
 			return Err(Failure::METHOD_NOT_FOUND);
 		}
+		// Apply any requested durability to write methods only
+		let rpc = Durable {
+			rpc: rpc.as_ref(),
+			durability: durability.filter(|_| method.is_write()),
+		};
 		// Execute the specified method
-		RpcContext::execute(rpc.as_ref(), version, method, params).await.map_err(Into::into)
+		RpcContext::execute(&rpc, version, method, params).await.map_err(Into::into)
 	}
 
 	/// Reject a WebSocket message due to server overloading
@@ -520,3 +530,85 @@ impl RpcContext for Websocket {
 		&self.gql_schema
 	}
 }
+
+/// A WebSocket connection executing a single request with a requested write
+/// durability. All connection state, including the session, live queries,
+/// and the modification lock, remains shared with the underlying WebSocket.
+struct Durable<'a> {
+	/// The WebSocket connection executing the request
+	rpc: &'a Websocket,
+	/// The write durability requested for this request
+	durability: Option<Durability>,
+}
+
+impl RpcProtocolV1 for Durable<'_> {}
+impl RpcProtocolV2 for Durable<'_> {}
+
+impl RpcContext for Durable<'_> {
+	/// The datastore for this RPC interface
+	fn kvs(&self) -> &Datastore {
+		self.rpc.kvs()
+	}
+	/// Retrieves the modification lock for this RPC context
+	fn lock(&self) -> Arc<Semaphore> {
+		self.rpc.lock()
+	}
+	/// The current session for this RPC context
+	fn session(&self) -> Arc<Session> {
+		let session = self.rpc.session();
+		match self.durability {
+			Some(durability) => Arc::new(session.as_ref().clone().with_durability(durability)),
+			None => session,
+		}
+	}
+	/// Mutable access to the current session for this RPC context
+	fn set_session(&self, session: Arc<Session>) {
+		match self.durability {
+			// Don't persist the requested durability on the connection
+			Some(_) => {
+				let durability = self.rpc.session().durability;
+				self.rpc.set_session(Arc::new(session.as_ref().clone().with_durability(durability)))
+			}
+			None => self.rpc.set_session(session),
+		}
+	}
+	/// The version information for this RPC context
+	fn version_data(&self) -> Data {
+		self.rpc.version_data()
+	}
+
+	// ------------------------------
+	// Realtime
+	// ------------------------------
+
+	/// Live queries are enabled on WebSockets
+	const LQ_SUPPORT: bool = true;
+
+	/// Handles the execution of a LIVE statement
+	async fn handle_live(&self, lqid: &Uuid) {
+		self.rpc.handle_live(lqid).await
+	}
+
+	/// Handles the execution of a KILL statement
+	async fn handle_kill(&self, lqid: &Uuid) {
+		self.rpc.handle_kill(lqid).await
+	}
+
+	/// Handles the cleanup of live queries
+	async fn cleanup_lqs(&self) {
+		self.rpc.cleanup_lqs().await
+	}
+
+	// ------------------------------
+	// GraphQL
+	// ------------------------------
+
+	/// GraphQL queries are enabled on WebSockets
+	#[cfg(surrealdb_unstable)]
+	const GQL_SUPPORT: bool = true;
+
+	#[cfg(surrealdb_unstable)]
+	fn graphql_schema_cache(&self) -> &SchemaCache {
+		self.rpc.graphql_schema_cache()
+	}
+}
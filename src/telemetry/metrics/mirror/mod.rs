@@ -0,0 +1,42 @@
+use crate::cnf::TELEMETRY_NAMESPACE;
+use opentelemetry::metrics::{Counter, Histogram, Meter};
+use opentelemetry::{global, KeyValue};
+use std::sync::LazyLock;
+use std::time::Duration;
+
+static METER: LazyLock<Meter> = LazyLock::new(|| global::meter("surrealdb.mirror"));
+
+pub static MIRROR_LAG_DURATION: LazyLock<Histogram<u64>> = LazyLock::new(|| {
+	METER
+		.u64_histogram("mirror.lag.duration")
+		.with_description(
+			"Measures the time between a write query running and being mirrored in milliseconds.",
+		)
+		.with_unit("ms")
+		.init()
+});
+
+pub static MIRROR_ERRORS: LazyLock<Counter<u64>> = LazyLock::new(|| {
+	METER
+		.u64_counter("mirror.errors")
+		.with_description("The number of write queries which failed to be mirrored.")
+		.init()
+});
+
+fn otel_common_attrs() -> Vec<KeyValue> {
+	let mut common = vec![KeyValue::new("mirror.service", "surrealdb")];
+	if !TELEMETRY_NAMESPACE.trim().is_empty() {
+		common.push(KeyValue::new("namespace", TELEMETRY_NAMESPACE.clone()));
+	};
+	common
+}
+
+/// Updates the lag and error metrics for a mirrored query.
+pub fn record_mirror(lag: Duration, is_error: bool) {
+	let mut attrs = otel_common_attrs();
+	attrs.push(KeyValue::new("mirror.error", is_error));
+	MIRROR_LAG_DURATION.record(lag.as_millis() as u64, &attrs);
+	if is_error {
+		MIRROR_ERRORS.add(1, &attrs);
+	}
+}
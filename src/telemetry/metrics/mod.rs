@@ -1,4 +1,6 @@
 pub mod http;
+pub mod mirror;
+pub mod throttle;
 pub mod ws;
 
 use crate::cnf::{TELEMETRY_DISABLE_METRICS, TELEMETRY_PROVIDER};
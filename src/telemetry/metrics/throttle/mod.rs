@@ -0,0 +1,30 @@
+use crate::cnf::TELEMETRY_NAMESPACE;
+use opentelemetry::metrics::{Counter, Meter};
+use opentelemetry::{global, KeyValue};
+use std::sync::LazyLock;
+
+static METER: LazyLock<Meter> = LazyLock::new(|| global::meter("surrealdb.throttle"));
+
+pub static THROTTLED_WRITES: LazyLock<Counter<u64>> = LazyLock::new(|| {
+	METER
+		.u64_counter("table.writes.throttled")
+		.with_description("The number of writes which were rejected by a table write rate limit.")
+		.init()
+});
+
+fn otel_common_attrs() -> Vec<KeyValue> {
+	let mut common = vec![KeyValue::new("throttle.service", "surrealdb")];
+	if !TELEMETRY_NAMESPACE.trim().is_empty() {
+		common.push(KeyValue::new("namespace", TELEMETRY_NAMESPACE.clone()));
+	};
+	common
+}
+
+/// Updates the throttled writes metric for a table.
+pub fn record_throttled(ns: &str, db: &str, tb: &str) {
+	let mut attrs = otel_common_attrs();
+	attrs.push(KeyValue::new("throttle.ns", ns.to_owned()));
+	attrs.push(KeyValue::new("throttle.db", db.to_owned()));
+	attrs.push(KeyValue::new("throttle.table", tb.to_owned()));
+	THROTTLED_WRITES.add(1, &attrs);
+}
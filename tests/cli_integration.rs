@@ -264,6 +264,80 @@ mod cli_integration {
 		assert!(output.contains("Started web server"), "couldn't start web server: {output}");
 	}
 
+	#[cfg(unix)]
+	#[test(tokio::test)]
+	async fn start_tls_reload() {
+		use std::collections::HashMap;
+
+		// Waits until the server has logged the message the given number of times
+		async fn logged(server: &common::Child, message: &str, count: usize) -> String {
+			for _ in 0..20 {
+				let output = server.stdout_and_stderr();
+				if output.matches(message).count() >= count {
+					return output;
+				}
+				sleep(Duration::from_millis(500)).await;
+			}
+			panic!(
+				"The server didn't log '{message}' {count} times:\n{}",
+				server.stdout_and_stderr()
+			);
+		}
+
+		let crt = common::tmp_file("crt.crt");
+		let key = common::tmp_file("key.pem");
+		let renew = || {
+			let rcgen::CertifiedKey {
+				cert,
+				key_pair,
+			} = rcgen::generate_simple_self_signed(Vec::new()).unwrap();
+			std::fs::write(&crt, cert.pem()).unwrap();
+			std::fs::write(&key, key_pair.serialize_pem()).unwrap();
+		};
+		renew();
+
+		let (_, mut server) = common::start_server(StartServerArguments {
+			args: format!("--web-crt {crt} --web-key {key}"),
+			wait_is_ready: false,
+			vars: Some(HashMap::from([(
+				"SURREAL_TLS_RELOAD_INTERVAL".to_string(),
+				"1".to_string(),
+			)])),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+		logged(&server, "Started web server", 1).await;
+
+		info!("* Reload a renewed certificate");
+		{
+			renew();
+			logged(&server, "Reloaded the TLS certificate", 1).await;
+		}
+
+		info!("* Reload the certificate on SIGHUP, without shutting down");
+		{
+			let reloaded =
+				server.stdout_and_stderr().matches("Reloaded the TLS certificate").count();
+			server.send_signal(nix::sys::signal::Signal::SIGHUP).unwrap();
+			logged(&server, "SIGHUP received. Reloading the TLS certificate.", 1).await;
+			logged(&server, "Reloaded the TLS certificate", reloaded + 1).await;
+			assert!(server.status().unwrap().is_none(), "The server stopped on SIGHUP");
+		}
+
+		info!("* Keep the previous certificate when the new one is invalid");
+		{
+			let unable =
+				server.stdout_and_stderr().matches("Unable to reload the TLS certificate").count();
+			std::fs::write(&key, "invalid").unwrap();
+			server.send_signal(nix::sys::signal::Signal::SIGHUP).unwrap();
+			logged(&server, "Unable to reload the TLS certificate", unable + 1).await;
+			assert!(server.status().unwrap().is_none(), "The server stopped on SIGHUP");
+		}
+
+		server.finish().unwrap();
+	}
+
 	#[test(tokio::test)]
 	async fn with_root_auth() {
 		// Commands with credentials when auth is enabled, should succeed
@@ -892,186 +966,1300 @@ mod cli_integration {
 	}
 
 	#[test(tokio::test)]
-	async fn with_import_file() {
+	async fn with_backup() {
+		let (addr, _server) = common::start_server(StartServerArguments {
+			auth: false,
+			args: "--allow-all".to_string(),
+			..Default::default()
+		})
+		.await
+		.unwrap();
 		let ns = Ulid::new();
 		let db = Ulid::new();
+		let target = common::tmp_file("backups");
+		let sql = format!("sql --conn http://{addr} --ns {ns} --db {db} --multi --hide-welcome");
+		let backup = format!("backup --conn http://{addr} --ns {ns} --db {db} {target}");
 
-		info!("* Import and reimport root-level file");
+		info!("* Take a full backup");
 		{
-			// Start the server with an import file containing the following query:
-			let query = r#"DEFINE ACCESS OVERWRITE admin ON ROOT TYPE JWT URL "https://www.surrealdb.com/jwks.json";"#;
-			let import_file = common::tmp_file("import.surql");
-			let mut file = File::create(&import_file).unwrap();
-			file.write_all(query.as_bytes()).unwrap();
-			let (addr, mut server) =
-				common::start_server_with_import_file(&import_file).await.unwrap();
-			// Define connection arguments.
-			let args = format!("sql --conn http://{addr} --user {USER} --pass {PASS}");
-			// Verify that the file has been imported correctly.
-			let output = common::run(&args).input("INFO FOR ROOT").output().expect("success");
-			assert!(output.contains(
-				r#"DEFINE ACCESS admin ON ROOT TYPE JWT URL 'https://www.surrealdb.com/jwks.json'"#
-			));
-			// Modify the resource that was imported.
-			common::run(&args)
-				.input("DEFINE ACCESS OVERWRITE admin ON ROOT TYPE JWT URL 'https://www.example.com/jwks.json'")
+			common::run(&sql)
+				.input("DEFINE TABLE thing CHANGEFEED 1h; CREATE thing:one;\n")
 				.output()
-				.expect("success");
-			// Verify that the resource has been modified correctly.
-			let output = common::run(&args).input("INFO FOR ROOT").output().expect("success");
-			assert!(output.contains(
-				r#"DEFINE ACCESS admin ON ROOT TYPE JWT URL 'https://www.example.com/jwks.json'"#
-			));
-			// Restart the server.
-			server.finish().unwrap();
-			let (addr, mut server) =
-				common::start_server_with_import_file(&import_file).await.unwrap();
-			// Verify that the resource has been recreated correctly.
-			let args = format!("sql --conn http://{addr} --user {USER} --pass {PASS}");
-			let output = common::run(&args).input("INFO FOR ROOT").output().expect("success");
-			assert!(output.contains(
-				r#"DEFINE ACCESS admin ON ROOT TYPE JWT URL 'https://www.surrealdb.com/jwks.json'"#
-			));
-			server.finish().unwrap();
+				.unwrap();
+			common::run(&backup).output().unwrap_or_else(|_| panic!("failed to run backup"));
+			let manifest = std::fs::read_to_string(format!("{target}/manifest.json")).unwrap();
+			assert!(manifest.contains(r#""kind": "full""#), "{manifest}");
 		}
 
-		info!("* Multi-level import file");
+		info!("* Take an incremental backup");
 		{
-			// Start the server with an import file containing the following query:
-			let query = format!(
-				r#"
-				USE NS `{ns}`;
-				DEFINE USER test ON NAMESPACE PASSWORD "secret";
-				USE NS `{ns}` DB `{db}`;
-				CREATE user:1;
-			"#
+			common::run(&sql).input("CREATE thing:two; DELETE thing:one;\n").output().unwrap();
+			common::run(&backup).output().unwrap_or_else(|_| panic!("failed to run backup"));
+			let manifest = std::fs::read_to_string(format!("{target}/manifest.json")).unwrap();
+			assert!(manifest.contains(r#""kind": "incremental""#), "{manifest}");
+			let file = std::fs::read_dir(&target)
+				.unwrap()
+				.map(|entry| entry.unwrap().path())
+				.find(|path| path.to_string_lossy().contains("incremental-"))
+				.unwrap();
+			let script = std::fs::read_to_string(file).unwrap();
+			assert!(script.contains("UPSERT thing:two CONTENT { id: thing:two };"), "{script}");
+			assert!(script.contains("DELETE thing:one;"), "{script}");
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_restore() {
+		let (addr, _server) = common::start_server(StartServerArguments {
+			auth: false,
+			args: "--allow-all".to_string(),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+		let ns = Ulid::new();
+		let db = Ulid::new();
+		let target = common::tmp_file("backups");
+		let sql = format!("sql --conn http://{addr} --ns {ns} --db {db} --multi --hide-welcome");
+		let backup = format!("backup --conn http://{addr} --ns {ns} --db {db} {target}");
+
+		info!("* Take a full and an incremental backup");
+		common::run(&sql)
+			.input("DEFINE TABLE thing CHANGEFEED 1h; CREATE thing:one;\n")
+			.output()
+			.unwrap();
+		common::run(&backup).output().unwrap_or_else(|_| panic!("failed to run backup"));
+		let between = Utc::now().to_rfc3339();
+		sleep(Duration::from_millis(10)).await;
+		common::run(&sql).input("CREATE thing:two; DELETE thing:one;\n").output().unwrap();
+		common::run(&backup).output().unwrap_or_else(|_| panic!("failed to run backup"));
+
+		info!("* Restore the latest backup");
+		{
+			let db = Ulid::new();
+			let args = format!("restore --conn http://{addr} --ns {ns} --db {db} {target}");
+			common::run(&args).output().unwrap_or_else(|_| panic!("failed to run restore"));
+			let args = format!("sql --conn http://{addr} --ns {ns} --db {db} --hide-welcome");
+			let output =
+				common::run(&args).input("SELECT VALUE id FROM thing;\n").output().unwrap();
+			assert!(output.contains("[[thing:two]]\n\n"), "failed to send sql: {args}");
+		}
+
+		info!("* Restore up to a point in time");
+		{
+			let db = Ulid::new();
+			let args = format!(
+				"restore --conn http://{addr} --ns {ns} --db {db} --until {between} {target}"
 			);
-			let import_file = common::tmp_file("import.surql");
-			let mut file = File::create(&import_file).unwrap();
-			file.write_all(query.as_bytes()).unwrap();
-			let (addr, mut server) =
-				common::start_server_with_import_file(&import_file).await.unwrap();
-			// Verify that the file has been imported correctly.
-			let args =
-				format!("sql --conn http://{addr} --user {USER} --pass {PASS} --namespace {ns}");
-			let output = common::run(&args).input("INFO FOR NAMESPACE").output().expect("success");
-			assert!(output.contains(r#"DEFINE USER test ON NAMESPACE PASSHASH"#));
-			let args =
-				format!("sql --conn http://{addr} --user {USER} --pass {PASS} --namespace {ns} --database {db}");
-			let output = common::run(&args).input("SELECT * FROM user").output().expect("success");
-			assert!(output.contains(r#"{ id: user:1 }"#));
-			server.finish().unwrap();
+			common::run(&args).output().unwrap_or_else(|_| panic!("failed to run restore"));
+			let args = format!("sql --conn http://{addr} --ns {ns} --db {db} --hide-welcome");
+			let output =
+				common::run(&args).input("SELECT VALUE id FROM thing;\n").output().unwrap();
+			assert!(output.contains("[[thing:one]]\n\n"), "failed to send sql: {args}");
 		}
 
-		info!("* Invalid import file");
+		info!("* Refuse to restore into a database which is not empty");
 		{
-			// Start the server with an import file which contains invalid SurrealQL.
-			let query = r#"
-				DEFINE USER test ON ROOT PASSWORD "secret"; -- Valid
-				DEFINE USER ON ROOT test PASSWORD "secret"; -- Invalid
-			"#;
-			let import_file = common::tmp_file("import.surql");
-			let mut file = File::create(&import_file).unwrap();
-			file.write_all(query.as_bytes()).unwrap();
-			let res = common::start_server_with_import_file(&import_file).await;
-			match res {
-				Ok(_) => panic!("import file should contain parseable SurrealQL"),
-				Err(e) => {
-					assert!(e.to_string().contains("server failed to start"))
-				}
-			}
-			// Verify that no data has been created on the datastore.
-			let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
-			let args =
-				format!("sql --conn http://{addr} --user {USER} --pass {PASS} --namespace {ns}");
-			let output = common::run(&args).input("INFO FOR ROOT").output().expect("success");
-			assert!(!output.contains(r#"DEFINE USER test ON ROOT PASSHASH"#));
-			server.finish().unwrap();
+			let args = format!("restore --conn http://{addr} --ns {ns} --db {db} {target}");
+			let output = common::run(&args).output();
+			assert!(output.unwrap_err().contains("is not empty"));
 		}
+	}
 
-		info!("* Nonexistent import file");
+	#[test(tokio::test)]
+	async fn with_sql_history() {
+		let history = common::tmp_file("history");
+		let args = format!(
+			"sql --conn memory --ns {ns} --db {db} --hide-welcome --history {history}",
+			ns = Ulid::new(),
+			db = Ulid::new()
+		);
+		let input = "CREATE thing:one;\nSELECT * FROM thing;\n";
+		let output = common::run(&args).input(input).output().unwrap();
+		assert!(output.contains("[[{ id: thing:one }]]"), "{output}");
+		let history = std::fs::read_to_string(&history).unwrap();
+		assert!(history.contains("CREATE thing:one;"), "{history}");
+		assert!(history.contains("SELECT * FROM thing;"), "{history}");
+	}
+
+	#[test(tokio::test)]
+	async fn with_sql_output_formats() {
+		let args = "sql --conn memory --ns test --db test --hide-welcome";
+		let input = "CREATE thing:one SET name = 'One, two';\n";
+
+		info!("* Print the results as comma-separated values");
 		{
-			// Start the server with an import file which does not exist.
-			let import_file = common::tmp_file("import.surql");
-			let res = common::start_server_with_import_file(&import_file).await;
-			match res {
-				Ok(_) => panic!("import file should require an existent file"),
-				Err(e) => {
-					assert!(e.to_string().contains("server failed to start"))
-				}
-			}
+			let args = format!("{args} --output csv");
+			let output = common::run(&args).input(input).output().unwrap();
+			assert!(output.contains("id,name\nthing:one,\"One, two\""), "{output}");
+		}
+
+		info!("* Print the results as aligned columns");
+		{
+			let args = format!("{args} --output table");
+			let output = common::run(&args).input(input).output().unwrap();
+			assert!(
+				output.contains("id        | name\n----------+---------\nthing:one | One, two"),
+				"{output}"
+			);
+		}
+
+		info!("* Switch to line-delimited JSON in the shell");
+		{
+			let input = format!("\\format ndjson\n{input}");
+			let output = common::run(args).input(&input).output().unwrap();
+			assert!(output.contains(r#"{"id":"thing:one","name":"One, two"}"#), "{output}");
 		}
 	}
 
 	#[test(tokio::test)]
-	async fn node() {
-		// Commands without credentials when auth is disabled, should succeed
-		let (addr, mut server) = common::start_server(StartServerArguments {
+	async fn with_tabular_export() {
+		let (addr, _server) = common::start_server(StartServerArguments {
 			auth: false,
-			tls: false,
-			wait_is_ready: true,
+			args: "--allow-all".to_string(),
 			..Default::default()
 		})
 		.await
 		.unwrap();
-		let creds = ""; // Anonymous user
+		let ns = Ulid::new();
+		let db = Ulid::new();
+		let sql = format!("sql --conn http://{addr} --ns {ns} --db {db} --multi --hide-welcome");
+		common::run(&sql)
+			.input("DEFINE TABLE person SCHEMAFULL; DEFINE FIELD name ON person TYPE string; DEFINE FIELD age ON person TYPE int; CREATE person:one SET name = 'One, Jr.', age = 30; CREATE person:two SET name = 'Two', age = 40;\n")
+			.output()
+			.unwrap();
+
+		info!("* Export a table as CSV");
+		{
+			let file = common::tmp_file("person.csv");
+			let args = format!("export --conn http://{addr} --ns {ns} --db {db} --format csv --table person {file}");
+			common::run(&args).output().unwrap_or_else(|_| panic!("failed to run export: {args}"));
+			let csv = std::fs::read_to_string(&file).unwrap();
+			assert_eq!(csv, "id,age,name\nperson:one,30,\"One, Jr.\"\nperson:two,40,Two\n");
+		}
+
+		#[cfg(feature = "export-parquet")]
+		{
+			info!("* Export a table as Parquet");
+			let file = common::tmp_file("person.parquet");
+			let args = format!("export --conn http://{addr} --ns {ns} --db {db} --format parquet --table person {file}");
+			common::run(&args).output().unwrap_or_else(|_| panic!("failed to run export: {args}"));
+			let parquet = std::fs::read(&file).unwrap();
+			assert!(parquet.starts_with(b"PAR1") && parquet.ends_with(b"PAR1"));
+		}
+
+		info!("* Require a table for the tabular formats");
+		{
+			let args = format!("export --conn http://{addr} --ns {ns} --db {db} --format csv -");
+			assert!(common::run(&args).output().is_err());
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_config_file() {
+		let file = common::tmp_file("surreal.toml");
+		std::fs::write(&file, "[capabilities]\nallow_all = true\ndeny_funcs = [\"string::len\"]\n")
+			.unwrap();
+		let (addr, _server) = common::start_server(StartServerArguments {
+			auth: false,
+			args: format!("--config {file}"),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+		let sql = format!(
+			"sql --conn http://{addr} --ns {} --db {} --hide-welcome",
+			Ulid::new(),
+			Ulid::new()
+		);
+
+		info!("* The capabilities of the configuration file are applied");
+		{
+			let output = common::run(&sql).input("RETURN string::len('abc');\n").output().unwrap();
+			assert!(output.contains("not allowed"), "{output}");
+			let output =
+				common::run(&sql).input("RETURN string::lowercase('ABC');\n").output().unwrap();
+			assert!(output.contains("'abc'"), "{output}");
+		}
+	}
 
+	#[test(tokio::test)]
+	async fn with_doctor() {
+		let (addr, _server) = common::start_server(StartServerArguments {
+			auth: false,
+			args: "--allow-all".to_string(),
+			..Default::default()
+		})
+		.await
+		.unwrap();
 		let ns = Ulid::new();
 		let db = Ulid::new();
+		let sql = format!("sql --conn http://{addr} --ns {ns} --db {db} --multi --hide-welcome");
+		common::run(&sql)
+			.input("DEFINE TABLE person; DEFINE INDEX name ON person FIELDS name;\n")
+			.output()
+			.unwrap();
 
-		info!("* Define a table");
+		info!("* Check the environment and the server");
 		{
-			let args = format!(
-				"sql --conn http://{addr} {creds} --ns {ns} --db {db} --multi --hide-welcome"
+			let dir = common::tmp_file("doctor");
+			let args =
+				format!("doctor --conn http://{addr} --ns {ns} --db {db} --path surrealkv://{dir}");
+			let output = common::run(&args).output().unwrap_or_else(|e| panic!("{e}"));
+			assert!(
+				output.contains(&format!("[ok] The datastore path '{dir}' is writable")),
+				"{output}"
 			);
-			let output = common::run(&args)
-				.input("DEFINE TABLE thing TYPE ANY CHANGEFEED 1s;\n")
-				.output()
-				.unwrap();
-			let output = remove_debug_info(output);
-			assert_eq!(output, "[NONE]\n\n".to_owned(), "failed to send sql: {args}");
+			assert!(output.contains(&format!("[ok] Connected to 'http://{addr}'")), "{output}");
+			assert!(output.contains(&format!("[ok] Checked 1 indexes in {ns}/{db}")), "{output}");
 		}
 
-		info!("* Create a record");
+		info!("* Report a server which can not be reached");
 		{
-			let args = format!(
-				"sql --conn http://{addr} {creds} --ns {ns} --db {db} --multi --hide-welcome"
-			);
-			let output = common::run(&args)
-				.input("BEGIN TRANSACTION; CREATE thing:one; COMMIT;\n")
-				.output()
-				.unwrap();
-			let output = remove_debug_info(output);
-			assert_eq!(
-				output,
-				"[[{ id: thing:one }]]\n\n".to_owned(),
-				"failed to send sql: {args}"
+			let args = "doctor --conn http://127.0.0.1:1";
+			let output = common::run(args).output().unwrap_err();
+			assert!(
+				output.contains("[failure] Unable to connect to 'http://127.0.0.1:1'"),
+				"{output}"
 			);
 		}
+	}
 
-		info!("* Show changes");
+	#[cfg(unix)]
+	#[test(tokio::test)]
+	async fn with_daemonize() {
+		let pid_file = common::tmp_file("surreal.pid");
+		let port: u16 = rand::random::<u16>() % 11000 + 13000;
+		let addr = format!("127.0.0.1:{port}");
+
+		info!("* Start the server in the background");
 		{
 			let args = format!(
-				"sql --conn http://{addr} {creds} --ns {ns} --db {db} --multi --hide-welcome"
+				"start --daemonize --pid-file {pid_file} --bind {addr} --unauthenticated --no-banner memory"
 			);
-			if FFLAGS.change_feed_live_queries.enabled() {
-				let output = common::run(&args)
-					.input("SHOW CHANGES FOR TABLE thing SINCE 0 LIMIT 10;\n")
-					.output()
-					.unwrap();
-				let output = remove_debug_info(output).replace('\n', "");
-				// TODO: when enabling the feature flag, turn these to `create` not `update`
-				let allowed = [
-					// Delete these
-					"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 1 }, { changes: [{ update: { id: thing:one } }], versionstamp: 2 }]]",
-					"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 1 }, { changes: [{ update: { id: thing:one } }], versionstamp: 3 }]]",
-					"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 2 }, { changes: [{ update: { id: thing:one } }], versionstamp: 3 }]]",
-					"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 2 }, { changes: [{ update: { id: thing:one } }], versionstamp: 4 }]]",
-					// Keep these
-					"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 65536 }, { changes: [{ update: { id: thing:one } }], versionstamp: 131072 }]]",
-					"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 65536 }, { changes: [{ update: { id: thing:one } }], versionstamp: 196608 }]]",
-					"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 131072 }, { changes: [{ update: { id: thing:one } }], versionstamp: 196608 }]]",
-					"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 131072 }, { changes: [{ update: { id: thing:one } }], versionstamp: 262144 }]]",
+			let output = common::run(&args).output().unwrap_or_else(|e| panic!("{e}"));
+			assert!(output.contains("Started the server in the background with PID"), "{output}");
+			assert!(std::path::Path::new(&pid_file).exists());
+			// The server runs in its own session, detached from the terminal
+			let pid: i32 = std::fs::read_to_string(&pid_file).unwrap().trim().parse().unwrap();
+			let pid = nix::unistd::Pid::from_raw(pid);
+			assert_eq!(nix::unistd::getsid(Some(pid)).unwrap(), pid);
+		}
+
+		info!("* Refuse to start a second server with the same PID file");
+		{
+			let args = format!("start --daemonize --pid-file {pid_file} --bind {addr} memory");
+			let output = common::run(&args).output().unwrap_err();
+			assert!(output.contains("A server is already running"), "{output}");
+		}
+
+		info!("* Stop the server");
+		{
+			// The server listens for connections shortly after writing its PID file
+			for _ in 0..10 {
+				if common::run(&format!("isready --conn http://{addr}")).output().is_ok() {
+					break;
+				}
+				sleep(Duration::from_millis(500)).await;
+			}
+			let args = format!("stop --pid-file {pid_file}");
+			let output = common::run(&args).output().unwrap_or_else(|e| panic!("{e}"));
+			assert!(output.contains("Stopped the server with PID"), "{output}");
+			assert!(!std::path::Path::new(&pid_file).exists());
+			common::run(&format!("isready --conn http://{addr}")).output().unwrap_err();
+		}
+
+		info!("* Do not signal a process which has reused the PID of a stopped server");
+		{
+			let mut other = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+			std::fs::write(&pid_file, format!("{}\n", other.id())).unwrap();
+			let args = format!("stop --pid-file {pid_file}");
+			let output = common::run(&args).output().unwrap_err();
+			assert!(output.contains("is not running"), "{output}");
+			assert!(other.try_wait().unwrap().is_none());
+			other.kill().unwrap();
+			other.wait().unwrap();
+		}
+	}
+
+	#[test]
+	fn upgrade_from_local_file() {
+		use sha2::{Digest, Sha256};
+		let dir = common::tmp_file("upgrade");
+		std::fs::create_dir_all(&dir).unwrap();
+		let exe = format!("{dir}/surreal");
+		std::fs::write(&exe, b"surreal").unwrap();
+		let args = format!("upgrade --from {exe} --dry-run");
+
+		info!("* Refuse to install without the checksum file");
+		{
+			let output = common::run(&args).output().unwrap_err();
+			assert!(output.contains("unable to read the checksum file"), "{output}");
+		}
+
+		info!("* Refuse to install a file which does not match its checksum");
+		{
+			std::fs::write(format!("{dir}/surreal.txt"), format!("{:x}", Sha256::digest(b"other")))
+				.unwrap();
+			let output = common::run(&args).output().unwrap_err();
+			assert!(output.contains("checksum mismatch"), "{output}");
+		}
+
+		info!("* Install a file which matches its checksum");
+		{
+			std::fs::write(
+				format!("{dir}/surreal.txt"),
+				format!("{:x}", Sha256::digest(b"surreal")),
+			)
+			.unwrap();
+			let output = common::run(&args).output().unwrap_or_else(|e| panic!("{e}"));
+			assert!(output.contains("Dry run successfully completed"), "{output}");
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_tail() {
+		let (addr, _server) = common::start_server(StartServerArguments {
+			auth: false,
+			args: "--allow-all".to_string(),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+		let ns = Ulid::new();
+		let db = Ulid::new();
+
+		info!("* Refuse a query which is not a live query");
+		{
+			let args = format!("tail --conn ws://{addr} --ns {ns} --db {db} SELECT * FROM orders");
+			let output = common::run(&args).output().unwrap_err();
+			assert!(output.contains("does not contain a LIVE SELECT statement"), "{output}");
+		}
+
+		info!("* Print the notifications of a live query");
+		{
+			let args = format!(
+				"tail --conn ws://{addr} --ns {ns} --db {db} LIVE SELECT * FROM orders WHERE total > 100"
+			);
+			let tail = common::run(&args);
+			sleep(Duration::from_secs(2)).await;
+			let sql =
+				format!("sql --conn http://{addr} --ns {ns} --db {db} --multi --hide-welcome");
+			common::run(&sql)
+				.input("CREATE orders:1 SET total = 50; CREATE orders:2 SET total = 150;\n")
+				.output()
+				.unwrap();
+			sleep(Duration::from_secs(1)).await;
+			let output = tail.kill().stdout();
+			let lines: Vec<&str> = output.lines().filter(|l| l.starts_with('{')).collect();
+			assert_eq!(lines.len(), 1, "{output}");
+			assert!(lines[0].contains(r#""action":"CREATE""#), "{output}");
+			assert!(lines[0].contains(r#""total":150"#), "{output}");
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_diff() {
+		let (addr, _server) = common::start_server(StartServerArguments {
+			auth: false,
+			args: "--allow-all".to_string(),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+		let ns = Ulid::new();
+		let db = Ulid::new();
+		let sql = format!("sql --conn http://{addr} --ns {ns} --db {db} --multi --hide-welcome");
+		common::run(&sql)
+			.input("DEFINE TABLE person SCHEMAFULL; DEFINE FIELD name ON person TYPE string; DEFINE FIELD age ON person TYPE int; DEFINE TABLE old;\n")
+			.output()
+			.unwrap();
+		let file = common::tmp_file("schema.surql");
+		std::fs::write(
+			&file,
+			"DEFINE TABLE person SCHEMAFULL; DEFINE FIELD name ON person TYPE string; DEFINE FIELD age ON person TYPE number; DEFINE INDEX name ON person FIELDS name;",
+		)
+		.unwrap();
+
+		info!("* Print the statements which change the database to match a schema file");
+		{
+			let args = format!("diff --conn http://{addr} --ns {ns} --db {db} {file}");
+			let output = common::run(&args).output().unwrap_or_else(|e| panic!("{e}"));
+			assert!(output.contains("REMOVE TABLE old;"), "{output}");
+			assert!(
+				output.contains("DEFINE FIELD OVERWRITE age ON person TYPE number"),
+				"{output}"
+			);
+			assert!(output.contains("DEFINE INDEX name ON person FIELDS name"), "{output}");
+			assert!(!output.contains("DEFINE FIELD name"), "{output}");
+		}
+
+		info!("* Print nothing when the schemas match");
+		{
+			let args = format!("diff --conn http://{addr} --ns {ns} --db {db} http://{addr}");
+			let output = common::run(&args).output().unwrap_or_else(|e| panic!("{e}"));
+			assert!(!output.contains("DEFINE"), "{output}");
+			assert!(!output.contains("REMOVE"), "{output}");
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_seed() {
+		let (addr, _server) = common::start_server(StartServerArguments {
+			auth: false,
+			args: "--allow-all".to_string(),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+		let ns = Ulid::new();
+		let db = Ulid::new();
+		let dir = common::tmp_file("fixtures");
+		std::fs::create_dir_all(&dir).unwrap();
+		std::fs::write(
+			format!("{dir}/person.json"),
+			r#"[{"id": 1, "name": "Tobie"}, {"id": 2, "name": "Jaime"}, {"id": 3, "name": "Emmanuel"}]"#,
+		)
+		.unwrap();
+		std::fs::write(
+			format!("{dir}/pet.ndjson"),
+			"{\"id\": 1, \"owner\": \"person:1\"}\n\n{\"id\": 2, \"owner\": \"person:2\"}\n",
+		)
+		.unwrap();
+		let args = format!("seed --conn http://{addr} --ns {ns} --db {db} --batch-size 2 {dir}");
+
+		info!("* Load the fixtures into their tables");
+		{
+			let output = common::run(&args).output().unwrap_or_else(|e| panic!("{e}"));
+			assert!(output.contains("Loaded 3 records into person"), "{output}");
+			assert!(output.contains("Loaded 2 records into pet"), "{output}");
+		}
+
+		info!("* Refuse to load records which already exist");
+		{
+			common::run(&args).output().unwrap_err();
+		}
+
+		info!("* Load the fixtures again, deleting the existing records first");
+		{
+			let output = common::run(&format!("{args} --truncate"))
+				.output()
+				.unwrap_or_else(|e| panic!("{e}"));
+			assert!(output.contains("Loaded 3 records into person"), "{output}");
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_sql_script() {
+		let (addr, _server) = common::start_server(StartServerArguments {
+			auth: false,
+			args: "--allow-all".to_string(),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+		let conn = format!("--conn http://{addr} --ns {} --db {}", Ulid::new(), Ulid::new());
+
+		info!("* Run a script with variables");
+		{
+			let file = common::tmp_file("script.surql");
+			std::fs::write(&file, "CREATE person:1 SET name = $name, age = $age;").unwrap();
+			let args = format!("sql {conn} --var name=Tobie --var age=30 --file {file}");
+			let output = common::run(&args).output().unwrap_or_else(|e| panic!("{e}"));
+			assert!(output.contains("[[{ age: 30, id: person:1, name: 'Tobie' }]]"), "{output}");
+		}
+
+		info!("* Fail when a statement of the script fails");
+		{
+			let file = common::tmp_file("script.surql");
+			std::fs::write(&file, "RETURN 1; THROW 'failed';").unwrap();
+			let args = format!("sql {conn} --file {file}");
+			let output = common::run(&args).output().unwrap_err();
+			assert!(output.contains("A statement in"), "{output}");
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_explain() {
+		let (addr, _server) = common::start_server(StartServerArguments {
+			auth: false,
+			args: "--allow-all".to_string(),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+		let ns = Ulid::new();
+		let db = Ulid::new();
+		let sql = format!("sql --conn http://{addr} --ns {ns} --db {db} --multi --hide-welcome");
+		common::run(&sql)
+			.input("CREATE person:1 SET name = 'Tobie', age = 30; DEFINE INDEX name ON person FIELDS name;\n")
+			.output()
+			.unwrap();
+		let conn = format!("--conn http://{addr} --ns {ns} --db {db}");
+
+		info!("* Highlight a full table scan");
+		{
+			let args = format!("explain {conn} SELECT * FROM person WHERE age > 20");
+			let output = common::run(&args).output().unwrap_or_else(|e| panic!("{e}"));
+			assert!(output.contains("Iterate Table (full table scan)"), "{output}");
+		}
+
+		info!("* Show the index which a query uses, with the records fetched");
+		{
+			let args =
+				format!("explain {conn} --analyze SELECT * FROM person WHERE name = 'Tobie'");
+			let output = common::run(&args).output().unwrap_or_else(|e| panic!("{e}"));
+			assert!(output.contains("Iterate Index"), "{output}");
+			assert!(output.contains("index: name"), "{output}");
+			assert!(output.contains("execution time"), "{output}");
+			assert!(output.contains("count: 1"), "{output}");
+			assert!(!output.contains("full table scan"), "{output}");
+		}
+
+		info!("* Refuse to explain other statements");
+		{
+			let args = format!("explain {conn} DELETE person");
+			let output = common::run(&args).output().unwrap_err();
+			assert!(output.contains("Only SELECT statements can be explained"), "{output}");
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_provision() {
+		let (addr, _server) = common::start_server(StartServerArguments {
+			auth: false,
+			args: "--allow-all".to_string(),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+		let conn = format!("--conn http://{addr} --ns acme --db app");
+
+		info!("* Provision a tenant, printing its credentials");
+		let credentials = {
+			let args = format!("provision {conn} --user admin --roles owner --access account");
+			let output = common::run(&args).output().unwrap();
+			let json = &output[output.find('{').expect(&output)..];
+			let credentials: serde_json::Value = serde_json::from_str(json).unwrap();
+			assert_eq!(credentials["namespace"], "acme");
+			assert_eq!(credentials["database"], "app");
+			assert_eq!(credentials["user"], "admin");
+			assert_eq!(credentials["access"], "account");
+			credentials
+		};
+
+		info!("* Sign in with the credentials of the tenant");
+		{
+			let password = credentials["password"].as_str().unwrap();
+			let args = format!(
+				"sql {conn} --user admin --pass {password} --auth-level database --hide-welcome"
+			);
+			let output = common::run(&args).input("INFO FOR DB;\n").output().unwrap();
+			assert!(output.contains("DEFINE ACCESS account ON DATABASE TYPE RECORD"), "{output}");
+			assert!(output.contains("DEFINE USER admin ON DATABASE"), "{output}");
+		}
+
+		info!("* Refuse to provision a database which already exists");
+		{
+			let args = format!("provision {conn} --user other");
+			let output = common::run(&args).output().unwrap_err();
+			assert!(output.contains("already exists"), "{output}");
+			let args = format!("sql {conn} --hide-welcome");
+			let output = common::run(&args).input("INFO FOR DB;\n").output().unwrap();
+			assert!(!output.contains("other"), "{output}");
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_convert() {
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+		let creds = format!("--user {USER} --pass {PASS}");
+		let (ns, db) = (Ulid::new(), Ulid::new());
+
+		info!("* Convert a 1.x export");
+		let converted = common::tmp_file("converted.surql");
+		{
+			let export = common::tmp_file("export.surql");
+			let content = r#"-- ------------------------------
+-- SCOPES
+-- ------------------------------
+
+DEFINE SCOPE account SESSION 1d SIGNIN (SELECT * FROM user WHERE email = $email) SIGNUP (CREATE user SET email = $email);
+DEFINE FIELD owner ON post VALUE $scope;
+
+-- ------------------------------
+-- TABLE DATA: post
+-- ------------------------------
+
+UPDATE post:1 CONTENT { id: post:1, text: 'first; second', created: '2020-01-01T00:00:00Z' };
+"#;
+			std::fs::write(&export, content).unwrap();
+			let args = format!("convert {export} {converted}");
+			common::run(&args).output().unwrap();
+			let output = std::fs::read_to_string(&converted).unwrap();
+			assert!(output.contains("-- TABLE DATA: post"), "{output}");
+			assert!(output.contains("DEFINE ACCESS account ON DATABASE TYPE RECORD"), "{output}");
+			assert!(output.contains("VALUE $access"), "{output}");
+			assert!(output.contains("UPSERT post:1 CONTENT"), "{output}");
+			assert!(!output.contains("UPDATE"), "{output}");
+		}
+
+		info!("* Import the converted file");
+		{
+			let args =
+				format!("import --conn http://{addr} {creds} --ns {ns} --db {db} {converted}");
+			common::run(&args).output().unwrap();
+			let args =
+				format!("sql --conn http://{addr} {creds} --ns {ns} --db {db} --hide-welcome");
+			let output =
+				common::run(&args).input("SELECT VALUE text FROM post;\n").output().unwrap();
+			assert!(output.contains("first; second"), "{output}");
+		}
+
+		info!("* Fail to convert a statement which can not be parsed");
+		{
+			let export = common::tmp_file("invalid.surql");
+			std::fs::write(&export, "SELECT * FROM;\n").unwrap();
+			let args = format!("convert {export}");
+			let output = common::run(&args).output().unwrap_err();
+			assert!(output.contains("Unable to convert the statement on line 1"), "{output}");
+		}
+	}
+
+	#[cfg(unix)]
+	#[test(tokio::test)]
+	async fn with_notify_socket() {
+		use std::collections::HashMap;
+		use std::os::unix::net::UnixDatagram;
+
+		info!("* Notify systemd once the server is listening");
+		{
+			let path = common::tmp_file("notify.sock");
+			let socket = UnixDatagram::bind(&path).unwrap();
+			socket.set_read_timeout(Some(Duration::from_secs(30))).unwrap();
+			let (_, _server) = common::start_server(StartServerArguments {
+				auth: false,
+				args: "--allow-all".to_string(),
+				vars: Some(HashMap::from([("NOTIFY_SOCKET".to_string(), path.clone())])),
+				..Default::default()
+			})
+			.await
+			.unwrap();
+			let mut buf = [0; 64];
+			let len = socket.recv(&mut buf).expect("the server did not notify systemd");
+			assert_eq!(&buf[..len], b"READY=1");
+		}
+	}
+
+	#[cfg(unix)]
+	#[test(tokio::test)]
+	async fn with_unix_socket() {
+		// Socket paths are limited in length, so are kept short
+		let path = std::env::temp_dir().join(format!("{}.sock", Ulid::new()));
+		let path = path.display();
+		let ns = Ulid::new();
+
+		info!("* Refuse to replace a file which is not a socket");
+		{
+			std::fs::write(path.to_string(), "").unwrap();
+			let args = format!("start --bind unix://{path} memory --no-banner --unauthenticated");
+			let output = common::run(&args).output().unwrap_err();
+			assert!(output.contains("already exists and is not a socket"), "{output}");
+			std::fs::remove_file(path.to_string()).unwrap();
+		}
+
+		info!("* Serve queries over a Unix domain socket");
+		let mut server = common::run(&format!(
+			"start --bind unix://{path} memory --no-banner --unauthenticated --log info"
+		));
+		for _ in 0..10 {
+			if server.stdout_and_stderr().contains("Started web server on unix://") {
+				break;
+			}
+			sleep(Duration::from_secs(1)).await;
+		}
+		{
+			let args = format!("sql --conn unix://{path} --ns {ns} --db {ns} --hide-welcome");
+			let output =
+				common::run(&args).input("RETURN 'served over a socket';\n").output().unwrap();
+			assert!(output.contains("'served over a socket'"), "{output}");
+		}
+
+		info!("* Refuse to replace the socket of a running server");
+		{
+			let args = format!("start --bind unix://{path} memory --no-banner --unauthenticated");
+			let output = common::run(&args).output().unwrap_err();
+			assert!(output.contains("is already in use by a running server"), "{output}");
+		}
+
+		info!("* Replace the socket left behind by a server which is no longer running");
+		{
+			// A killed server leaves its socket behind
+			server.finish().unwrap();
+			while server.status().unwrap().is_none() {
+				sleep(Duration::from_millis(100)).await;
+			}
+			let mut server = common::run(&format!(
+				"start --bind unix://{path} memory --no-banner --unauthenticated --log info"
+			));
+			let mut started = false;
+			for _ in 0..10 {
+				sleep(Duration::from_secs(1)).await;
+				started = server.stdout_and_stderr().contains("Started web server on unix://");
+				if started {
+					break;
+				}
+			}
+			assert!(started, "{}", server.kill().stdout_and_stderr());
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_compact() {
+		info!("* Compact a datastore offline, against its path");
+		{
+			let dir = common::tmp_file("compact");
+			let sql = format!(
+				"sql --conn rocksdb://{dir} --ns {} --db {} --multi --hide-welcome",
+				Ulid::new(),
+				Ulid::new()
+			);
+			common::run(&sql)
+				.input("CREATE |person:1000| SET name = rand::string(100); DELETE person;\n")
+				.output()
+				.unwrap();
+			let args = format!("compact --conn rocksdb://{dir}");
+			let output = common::run(&args).output().unwrap_or_else(|e| panic!("{e}"));
+			assert!(output.contains("Compacted the datastore from"), "{output}");
+		}
+
+		info!("* Refuse to compact an in-memory datastore, through a server");
+		{
+			let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+			let args = format!("compact --conn http://{addr} --user {USER} --pass {PASS}");
+			let output = common::run(&args).output().unwrap_err();
+			assert!(output.contains("Compaction is not supported"), "{output}");
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_top() {
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+		let ns = Ulid::new();
+
+		info!("* Show the statistics of the server");
+		{
+			let args = format!(
+				"sql --conn http://{addr} --user {USER} --pass {PASS} --ns {ns} --db {ns} --hide-welcome"
+			);
+			common::run(&args).input("CREATE thing:one;\n").output().unwrap();
+			let args = format!(
+				"top --conn http://{addr} --user {USER} --pass {PASS} --count 2 --interval 100ms"
+			);
+			let output = common::run(&args).output().unwrap();
+			assert_eq!(output.matches("Queries per second").count(), 2, "{output}");
+			assert!(output.contains("Transaction conflicts"), "{output}");
+			assert!(output.contains(&ns.to_string()), "{output}");
+		}
+
+		info!("* Fail to show the statistics without root credentials");
+		{
+			let args = format!("top --conn http://{addr} --count 1");
+			let output = common::run(&args).output().unwrap_err();
+			assert!(output.contains("Unable to fetch the statistics"), "{output}");
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_sql_transaction() {
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+		let args = format!(
+			"sql --conn ws://{addr} --user {USER} --pass {PASS} --ns {} --db {} --hide-welcome",
+			Ulid::new(),
+			Ulid::new()
+		);
+
+		info!("* Run queries one by one in a transaction which is committed or cancelled");
+		{
+			let input = [
+				"\\begin",
+				"CREATE thing:1;",
+				"CREATE thing:2;",
+				"RETURN { after: 'create', count: count(SELECT * FROM thing) };",
+				"\\cancel",
+				"RETURN { after: 'cancel', count: count(SELECT * FROM thing) };",
+				"\\begin",
+				"CREATE thing:3;",
+				"CREATE thing:3;",
+				"CREATE thing:4;",
+				"\\commit",
+				"RETURN { after: 'failure', count: count(SELECT * FROM thing) };",
+				"\\begin",
+				"CREATE thing:4;",
+				"CREATE thing:5;",
+				"\\commit",
+				"RETURN { after: 'commit', count: count(SELECT * FROM thing) };",
+				"",
+			]
+			.join("\n");
+			let output = common::run(&args).input(&input).output().unwrap();
+			assert!(output.contains("after: 'create', count: 2"), "{output}");
+			assert!(output.contains("after: 'cancel', count: 0"), "{output}");
+			assert!(output.contains("already exists"), "{output}");
+			assert!(output.contains("after: 'failure', count: 0"), "{output}");
+			assert!(output.contains("after: 'commit', count: 2"), "{output}");
+		}
+
+		info!("* Transactions can't span queries over HTTP");
+		{
+			let args = args.replace("ws://", "http://");
+			let output = common::run(&args).input("\\begin\n").output().unwrap();
+			assert!(output.contains("connect with ws://"), "{output}");
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_sql_limit() {
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+		let args = format!(
+			"sql --conn http://{addr} --user {USER} --pass {PASS} --ns {} --db {} --hide-welcome",
+			Ulid::new(),
+			Ulid::new()
+		);
+
+		info!("* Cap the rows fetched by SELECT statements without a limit");
+		{
+			let input = "CREATE |thing:10| RETURN NONE;\n\\limit 3\nSELECT * FROM thing;\nSELECT * FROM thing LIMIT 5;\n\\limit off\nSELECT * FROM thing;\n";
+			let output = common::run(&args).input(input).output().unwrap();
+			let counts: Vec<usize> = output
+				.lines()
+				.filter(|line| line.contains("id: thing:"))
+				.map(|line| line.matches("id: thing:").count())
+				.collect();
+			assert_eq!(counts, vec![3, 5, 10], "{output}");
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_secret_file() {
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+		let conn = format!("--conn http://{addr} --ns {} --db {}", Ulid::new(), Ulid::new());
+
+		info!("* Read the password from a file");
+		{
+			let file = common::tmp_file("pass");
+			std::fs::write(&file, format!("{PASS}\n")).unwrap();
+			let args = format!("sql {conn} --user {USER} --pass @{file} --hide-welcome");
+			let output = common::run(&args).input("RETURN 'signed in';\n").output().unwrap();
+			assert!(output.contains("signed in"), "{output}");
+		}
+
+		info!("* Fail to read the password from a file which does not exist");
+		{
+			let file = common::tmp_file("missing");
+			let args = format!("sql {conn} --user {USER} --pass @{file} --hide-welcome");
+			let output = common::run(&args).input("RETURN 'signed in';\n").output().unwrap_err();
+			assert!(output.contains("Unable to read the secret"), "{output}");
+		}
+
+		info!("* Define a user whose password starts with @");
+		{
+			let args = format!("sql {conn} --user {USER} --pass {PASS} --hide-welcome");
+			let output = common::run(&args)
+				.input("DEFINE USER literal ON ROOT PASSWORD '@literal-pass' ROLES OWNER;\n")
+				.output()
+				.unwrap();
+			assert!(!output.contains("ERROR"), "{output}");
+		}
+
+		info!("* Sign in with an escaped password");
+		{
+			let args = format!("sql {conn} --user literal --pass @@literal-pass --hide-welcome");
+			let output = common::run(&args).input("RETURN 'signed in';\n").output().unwrap();
+			assert!(output.contains("signed in"), "{output}");
+		}
+
+		info!("* Sign in with a verbatim password");
+		{
+			use std::collections::HashMap;
+			let args = format!("sql {conn} --user literal --pass @literal-pass --hide-welcome");
+			let vars = HashMap::from([("SURREAL_LITERAL_SECRETS".to_string(), "true".to_string())]);
+			let output = common::run_internal::<String>(&args, None, Some(vars))
+				.input("RETURN 'signed in';\n")
+				.output()
+				.unwrap();
+			assert!(output.contains("signed in"), "{output}");
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_verify() {
+		info!("* Verify a consistent datastore offline, against its path");
+		{
+			let dir = common::tmp_file("verify");
+			let sql = format!(
+				"sql --conn rocksdb://{dir} --ns {} --db {} --multi --hide-welcome",
+				Ulid::new(),
+				Ulid::new()
+			);
+			common::run(&sql)
+				.input("DEFINE INDEX name ON person FIELDS name; CREATE |person:100| SET name = rand::string(10); RELATE person:1->knows->person:2;\n")
+				.output()
+				.unwrap();
+			let args = format!("verify rocksdb://{dir}");
+			let output = common::run(&args).output().unwrap_or_else(|e| panic!("{e}"));
+			assert!(output.contains("Scanned"), "{output}");
+			assert!(output.contains("No problems were found"), "{output}");
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_user() {
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+		let ns = Ulid::new();
+		let creds = format!("--conn http://{addr} --user {USER} --pass {PASS}");
+
+		info!("* Create a namespace user with the password from stdin");
+		{
+			let args = format!("user create {creds} --level ns --ns {ns} --roles editor alice");
+			let output =
+				common::run(&args).input("first\n").output().expect("failed to create the user");
+			assert!(output.contains("Created the user 'alice' on NAMESPACE"), "{output}");
+			let args = format!("user list {creds} --level ns --ns {ns}");
+			let output = common::run(&args).output().expect("failed to list the users");
+			assert!(output.contains("alice editor"), "{output}");
+		}
+
+		info!("* Change the password from a file, keeping the roles");
+		{
+			let file = common::tmp_file("password.txt");
+			std::fs::write(&file, "second").unwrap();
+			let args =
+				format!("user passwd {creds} --level ns --ns {ns} --password-file {file} alice");
+			common::run(&args).output().expect("failed to change the password");
+			let sql = format!("sql --conn http://{addr} --user alice --pass second --auth-level ns --ns {ns} --hide-welcome");
+			let output =
+				common::run(&sql).input("INFO FOR NS;\n").output().expect("failed to sign in");
+			assert!(output.contains("ROLES EDITOR"), "{output}");
+		}
+
+		info!("* Delete the user");
+		{
+			let args = format!("user delete {creds} --level ns --ns {ns} alice");
+			common::run(&args).output().expect("failed to delete the user");
+			let args = format!("user list {creds} --level ns --ns {ns}");
+			let output = common::run(&args).output().expect("failed to list the users");
+			assert!(!output.contains("alice"), "{output}");
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_index() {
+		let (addr, _server) = common::start_server(StartServerArguments {
+			auth: false,
+			args: "--allow-all".to_string(),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+		let ns = Ulid::new();
+		let db = Ulid::new();
+		let sql = format!("sql --conn http://{addr} --ns {ns} --db {db} --multi --hide-welcome");
+		common::run(&sql)
+			.input("CREATE person:one SET name = 'One'; DEFINE INDEX name ON person FIELDS name CONCURRENTLY;\n")
+			.output()
+			.unwrap();
+		let conn = format!("--conn http://{addr} --ns {ns} --db {db}");
+
+		info!("* Rebuild an index, waiting for it to be built");
+		{
+			let args = format!("index rebuild {conn} --wait person name");
+			let output = common::run(&args).output().expect("failed to rebuild the index");
+			assert!(output.contains("The index name on person was rebuilt"), "{output}");
+		}
+
+		info!("* List the indexes and their status");
+		{
+			let args = format!("index status {conn}");
+			let output = common::run(&args).output().expect("failed to list the indexes");
+			assert!(output.contains("person name ready"), "{output}");
+		}
+
+		info!("* Fail to rebuild an index which does not exist");
+		{
+			let args = format!("index rebuild {conn} person missing");
+			assert!(common::run(&args).output().is_err());
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_resumed_import() {
+		let (addr, _server) = common::start_server(StartServerArguments {
+			auth: false,
+			args: "--allow-all".to_string(),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+		let ns = Ulid::new();
+		let db = Ulid::new();
+		let file = common::tmp_file("resumed.surql");
+		let head = "OPTION IMPORT;\nCREATE thing:one;\n";
+		let args = format!("import --conn http://{addr} --ns {ns} --db {db} --batch-size 1 {file}");
+
+		info!("* Import a file which fails part of the way through");
+		{
+			std::fs::write(&file, format!("{head}THROW 'failure';\nCREATE thing:two;\n")).unwrap();
+			let output = common::run(&args).output().unwrap_err();
+			assert!(output.contains(&format!("--resume-from {}", head.len())), "{output}");
+		}
+
+		info!("* Resume the import once the file has been fixed");
+		{
+			std::fs::write(&file, format!("{head}CREATE thing:fix;\nCREATE thing:two;\n")).unwrap();
+			let args = format!("{args} --resume-from {}", head.len());
+			common::run(&args).output().unwrap_or_else(|_| panic!("failed to run import: {args}"));
+			let sql = format!("sql --conn http://{addr} --ns {ns} --db {db} --hide-welcome");
+			let output = common::run(&sql).input("SELECT VALUE id FROM thing;\n").output().unwrap();
+			assert!(output.contains("[[thing:fix, thing:one, thing:two]]\n\n"), "{output}");
+		}
+
+		info!("* Refuse to resume from the middle of a line");
+		{
+			let args = format!("{args} --resume-from 3");
+			assert!(common::run(&args).output().is_err());
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_migrate() {
+		let (addr, _server) = common::start_server(StartServerArguments {
+			auth: false,
+			args: "--allow-all".to_string(),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+		let ns = Ulid::new();
+		let db = Ulid::new();
+		let dir = common::tmp_file("migrations");
+		std::fs::create_dir_all(&dir).unwrap();
+		std::fs::write(format!("{dir}/1_person.up.surql"), "DEFINE TABLE person;").unwrap();
+		std::fs::write(format!("{dir}/1_person.down.surql"), "REMOVE TABLE person;").unwrap();
+		std::fs::write(format!("{dir}/2_email.up.surql"), "DEFINE FIELD email ON person;").unwrap();
+		std::fs::write(format!("{dir}/2_email.down.surql"), "REMOVE FIELD email ON person;")
+			.unwrap();
+		let args = format!("--conn http://{addr} --ns {ns} --db {db} {dir}");
+
+		info!("* Preview the pending migrations");
+		{
+			let output = common::run(&format!("migrate up --dry-run {args}")).output().unwrap();
+			assert!(output.contains("Would apply 1 (person)"), "{output}");
+			assert!(output.contains("Would apply 2 (email)"), "{output}");
+			let output = common::run(&format!("migrate status {args}")).output().unwrap();
+			assert!(output.contains("1 person pending"), "{output}");
+		}
+
+		info!("* Apply the migrations");
+		{
+			let output = common::run(&format!("migrate up {args}")).output().unwrap();
+			assert!(output.contains("Applied 2 (email)"), "{output}");
+			let output = common::run(&format!("migrate status {args}")).output().unwrap();
+			assert!(output.contains("1 person applied"), "{output}");
+			assert!(output.contains("2 email applied"), "{output}");
+		}
+
+		info!("* Revert the latest migration");
+		{
+			let output = common::run(&format!("migrate down {args}")).output().unwrap();
+			assert!(output.contains("Reverted 2 (email)"), "{output}");
+			let output = common::run(&format!("migrate status {args}")).output().unwrap();
+			assert!(output.contains("1 person applied"), "{output}");
+			assert!(output.contains("2 email pending"), "{output}");
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn with_import_file() {
+		let ns = Ulid::new();
+		let db = Ulid::new();
+
+		info!("* Import and reimport root-level file");
+		{
+			// Start the server with an import file containing the following query:
+			let query = r#"DEFINE ACCESS OVERWRITE admin ON ROOT TYPE JWT URL "https://www.surrealdb.com/jwks.json";"#;
+			let import_file = common::tmp_file("import.surql");
+			let mut file = File::create(&import_file).unwrap();
+			file.write_all(query.as_bytes()).unwrap();
+			let (addr, mut server) =
+				common::start_server_with_import_file(&import_file).await.unwrap();
+			// Define connection arguments.
+			let args = format!("sql --conn http://{addr} --user {USER} --pass {PASS}");
+			// Verify that the file has been imported correctly.
+			let output = common::run(&args).input("INFO FOR ROOT").output().expect("success");
+			assert!(output.contains(
+				r#"DEFINE ACCESS admin ON ROOT TYPE JWT URL 'https://www.surrealdb.com/jwks.json'"#
+			));
+			// Modify the resource that was imported.
+			common::run(&args)
+				.input("DEFINE ACCESS OVERWRITE admin ON ROOT TYPE JWT URL 'https://www.example.com/jwks.json'")
+				.output()
+				.expect("success");
+			// Verify that the resource has been modified correctly.
+			let output = common::run(&args).input("INFO FOR ROOT").output().expect("success");
+			assert!(output.contains(
+				r#"DEFINE ACCESS admin ON ROOT TYPE JWT URL 'https://www.example.com/jwks.json'"#
+			));
+			// Restart the server.
+			server.finish().unwrap();
+			let (addr, mut server) =
+				common::start_server_with_import_file(&import_file).await.unwrap();
+			// Verify that the resource has been recreated correctly.
+			let args = format!("sql --conn http://{addr} --user {USER} --pass {PASS}");
+			let output = common::run(&args).input("INFO FOR ROOT").output().expect("success");
+			assert!(output.contains(
+				r#"DEFINE ACCESS admin ON ROOT TYPE JWT URL 'https://www.surrealdb.com/jwks.json'"#
+			));
+			server.finish().unwrap();
+		}
+
+		info!("* Multi-level import file");
+		{
+			// Start the server with an import file containing the following query:
+			let query = format!(
+				r#"
+				USE NS `{ns}`;
+				DEFINE USER test ON NAMESPACE PASSWORD "secret";
+				USE NS `{ns}` DB `{db}`;
+				CREATE user:1;
+			"#
+			);
+			let import_file = common::tmp_file("import.surql");
+			let mut file = File::create(&import_file).unwrap();
+			file.write_all(query.as_bytes()).unwrap();
+			let (addr, mut server) =
+				common::start_server_with_import_file(&import_file).await.unwrap();
+			// Verify that the file has been imported correctly.
+			let args =
+				format!("sql --conn http://{addr} --user {USER} --pass {PASS} --namespace {ns}");
+			let output = common::run(&args).input("INFO FOR NAMESPACE").output().expect("success");
+			assert!(output.contains(r#"DEFINE USER test ON NAMESPACE PASSHASH"#));
+			let args =
+				format!("sql --conn http://{addr} --user {USER} --pass {PASS} --namespace {ns} --database {db}");
+			let output = common::run(&args).input("SELECT * FROM user").output().expect("success");
+			assert!(output.contains(r#"{ id: user:1 }"#));
+			server.finish().unwrap();
+		}
+
+		info!("* Invalid import file");
+		{
+			// Start the server with an import file which contains invalid SurrealQL.
+			let query = r#"
+				DEFINE USER test ON ROOT PASSWORD "secret"; -- Valid
+				DEFINE USER ON ROOT test PASSWORD "secret"; -- Invalid
+			"#;
+			let import_file = common::tmp_file("import.surql");
+			let mut file = File::create(&import_file).unwrap();
+			file.write_all(query.as_bytes()).unwrap();
+			let res = common::start_server_with_import_file(&import_file).await;
+			match res {
+				Ok(_) => panic!("import file should contain parseable SurrealQL"),
+				Err(e) => {
+					assert!(e.to_string().contains("server failed to start"))
+				}
+			}
+			// Verify that no data has been created on the datastore.
+			let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
+			let args =
+				format!("sql --conn http://{addr} --user {USER} --pass {PASS} --namespace {ns}");
+			let output = common::run(&args).input("INFO FOR ROOT").output().expect("success");
+			assert!(!output.contains(r#"DEFINE USER test ON ROOT PASSHASH"#));
+			server.finish().unwrap();
+		}
+
+		info!("* Nonexistent import file");
+		{
+			// Start the server with an import file which does not exist.
+			let import_file = common::tmp_file("import.surql");
+			let res = common::start_server_with_import_file(&import_file).await;
+			match res {
+				Ok(_) => panic!("import file should require an existent file"),
+				Err(e) => {
+					assert!(e.to_string().contains("server failed to start"))
+				}
+			}
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn node() {
+		// Commands without credentials when auth is disabled, should succeed
+		let (addr, mut server) = common::start_server(StartServerArguments {
+			auth: false,
+			tls: false,
+			wait_is_ready: true,
+			..Default::default()
+		})
+		.await
+		.unwrap();
+		let creds = ""; // Anonymous user
+
+		let ns = Ulid::new();
+		let db = Ulid::new();
+
+		info!("* Define a table");
+		{
+			let args = format!(
+				"sql --conn http://{addr} {creds} --ns {ns} --db {db} --multi --hide-welcome"
+			);
+			let output = common::run(&args)
+				.input("DEFINE TABLE thing TYPE ANY CHANGEFEED 1s;\n")
+				.output()
+				.unwrap();
+			let output = remove_debug_info(output);
+			assert_eq!(output, "[NONE]\n\n".to_owned(), "failed to send sql: {args}");
+		}
+
+		info!("* Create a record");
+		{
+			let args = format!(
+				"sql --conn http://{addr} {creds} --ns {ns} --db {db} --multi --hide-welcome"
+			);
+			let output = common::run(&args)
+				.input("BEGIN TRANSACTION; CREATE thing:one; COMMIT;\n")
+				.output()
+				.unwrap();
+			let output = remove_debug_info(output);
+			assert_eq!(
+				output,
+				"[[{ id: thing:one }]]\n\n".to_owned(),
+				"failed to send sql: {args}"
+			);
+		}
+
+		info!("* Show changes");
+		{
+			let args = format!(
+				"sql --conn http://{addr} {creds} --ns {ns} --db {db} --multi --hide-welcome"
+			);
+			if FFLAGS.change_feed_live_queries.enabled() {
+				let output = common::run(&args)
+					.input("SHOW CHANGES FOR TABLE thing SINCE 0 LIMIT 10;\n")
+					.output()
+					.unwrap();
+				let output = remove_debug_info(output).replace('\n', "");
+				// TODO: when enabling the feature flag, turn these to `create` not `update`
+				let allowed = [
+					// Delete these
+					"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 1 }, { changes: [{ update: { id: thing:one } }], versionstamp: 2 }]]",
+					"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 1 }, { changes: [{ update: { id: thing:one } }], versionstamp: 3 }]]",
+					"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 2 }, { changes: [{ update: { id: thing:one } }], versionstamp: 3 }]]",
+					"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 2 }, { changes: [{ update: { id: thing:one } }], versionstamp: 4 }]]",
+					// Keep these
+					"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 65536 }, { changes: [{ update: { id: thing:one } }], versionstamp: 131072 }]]",
+					"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 65536 }, { changes: [{ update: { id: thing:one } }], versionstamp: 196608 }]]",
+					"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 131072 }, { changes: [{ update: { id: thing:one } }], versionstamp: 196608 }]]",
+					"[[{ changes: [{ define_table: { name: 'thing' } }], versionstamp: 131072 }, { changes: [{ update: { id: thing:one } }], versionstamp: 262144 }]]",
 				];
 				allowed
 					.into_iter()
@@ -1172,6 +2360,20 @@ mod cli_integration {
 		assert!(common::run_in_dir("validate", &temp_dir).output().is_err());
 	}
 
+	#[test]
+	fn validate_failed_due_to_denied_functions() {
+		let temp_dir = assert_fs::TempDir::new().unwrap();
+
+		let statement_file = temp_dir.child("statement.surql");
+
+		statement_file.touch().unwrap();
+		statement_file.write_str("RETURN http::get('https://surrealdb.com');").unwrap();
+
+		common::run_in_dir("validate", &temp_dir).output().unwrap();
+		let output = common::run_in_dir("validate --deny-funcs http", &temp_dir).output();
+		assert!(output.unwrap_err().contains("'http::get' is not allowed"));
+	}
+
 	#[cfg(unix)]
 	#[test(tokio::test)]
 	async fn test_server_graceful_shutdown() {
@@ -1266,6 +2468,61 @@ mod cli_integration {
 		server.finish().unwrap();
 	}
 
+	#[cfg(unix)]
+	#[test(tokio::test)]
+	async fn test_server_shutdown_timeout() {
+		let (addr, mut server) = common::start_server(StartServerArguments {
+			auth: false,
+			args: "--shutdown-timeout 2s".to_owned(),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+
+		// Create a long-lived WS connection which is cancelled once the timeout elapses
+		let socket =
+			Socket::connect(&addr, None, Format::Json).await.expect("Failed to connect to server");
+
+		let send_future = socket.send_request("query", json!(["SLEEP 30s;"]));
+
+		let signal_send_fut = async {
+			// Make sure the SLEEP query is being executed
+			tokio::time::timeout(time::Duration::from_secs(10), async {
+				loop {
+					let err = server.stdout_and_stderr();
+					if err.contains("SLEEP 30s") {
+						break;
+					}
+					tokio::time::sleep(time::Duration::from_secs(1)).await;
+				}
+			})
+			.await
+			.expect("Server didn't start executing the SLEEP query");
+
+			info!("* Send SIGTERM signal");
+			server
+				.send_signal(nix::sys::signal::Signal::SIGTERM)
+				.expect("Failed to send SIGTERM to server");
+
+			tokio::time::timeout(time::Duration::from_secs(10), async {
+				loop {
+					if let Ok(Some(exit)) = server.status() {
+						assert!(exit.success(), "Server didn't shutdown successfully");
+						break;
+					}
+					tokio::time::sleep(time::Duration::from_millis(100)).await;
+				}
+			})
+			.await
+			.expect("Server didn't exit once the shutdown timeout elapsed");
+		};
+
+		let _ =
+			futures::future::join(async { send_future.await.unwrap_err() }, signal_send_fut).await;
+
+		server.finish().unwrap();
+	}
+
 	#[test(tokio::test)]
 	async fn test_capabilities() {
 		// Default capabilities only allow functions